@@ -1,9 +1,11 @@
+use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use walkdir::WalkDir;
 use colored::*;
+use fuzzhead_core::report::CampaignReport;
+use fuzzhead_core::severity::{OracleClass, Severity};
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::time::Instant;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,10 +18,28 @@ struct BenchmarkResult {
     fuzz_runs: usize,
     passed: usize,
     failed: usize,
+    /// Runs that hit RPC/infrastructure problems; never counted as detections
+    #[serde(default)]
+    errored: usize,
+    /// Oracles whose findings passed the detection gate
+    #[serde(default)]
+    detected_by: Vec<OracleClass>,
+    /// Findings, invariant violations and bricking calls the gate left out
+    #[serde(default)]
+    below_gate: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct BenchmarkSummary {
+    /// Executor backend the fuzzer ran on
+    #[serde(default)]
+    backend: String,
+    /// Lowest severity that counted as a detection
+    #[serde(default)]
+    min_severity: Severity,
+    /// Oracles that counted as a detection; empty for any
+    #[serde(default)]
+    oracles: Vec<OracleClass>,
     total: usize,
     detected: usize,
     missed: usize,
@@ -27,12 +47,42 @@ struct BenchmarkSummary {
     results: Vec<BenchmarkResult>,
 }
 
+/// Which findings count as a detection: those of a listed oracle (any, if none are listed) with
+/// at least the minimum severity
+struct DetectionGate {
+    min_severity: Severity,
+    oracles: Vec<OracleClass>,
+}
+
+impl DetectionGate {
+    /// From `MIN_SEVERITY` (default medium, or info when `ORACLES` is set) and `ORACLES`
+    /// (comma-separated oracle classes)
+    fn from_env() -> Result<Self, anyhow::Error> {
+        let oracles = match std::env::var("ORACLES") {
+            Ok(list) => list.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| name.parse::<OracleClass>().map_err(anyhow::Error::msg))
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(_) => Vec::new(),
+        };
+        let default_severity = if oracles.is_empty() { Severity::Medium } else { Severity::Info };
+        // The Makefile passes unset variables through as empty ones
+        let min_severity = match std::env::var("MIN_SEVERITY").ok().filter(|severity| !severity.trim().is_empty()) {
+            Some(severity) => severity.trim().parse().map_err(anyhow::Error::msg)?,
+            None => default_severity,
+        };
+        Ok(Self { min_severity, oracles })
+    }
+
+    fn admits(&self, class: OracleClass) -> bool {
+        class.severity() >= self.min_severity && (self.oracles.is_empty() || self.oracles.contains(&class))
+    }
+}
+
 /// Check if a contract file is a test contract (not suitable for fuzzing)
 fn is_test_contract(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
-    let file_name = path.file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("");
     
     // Skip known test/helper files
     if path_str.contains("/lib/") 
@@ -78,7 +128,10 @@ async fn run_fuzzer_on_contract(
     contract_path: &Path,
     fuzzer_binary: &Path,
     fork_url: &str,
+    backend: &str,
     test_cases: usize,
+    results_dir: &Path,
+    gate: &DetectionGate,
 ) -> Result<BenchmarkResult, anyhow::Error> {
     let start = Instant::now();
     
@@ -89,6 +142,9 @@ async fn run_fuzzer_on_contract(
     
     println!("  Testing: {}", contract_name.cyan());
     
+    // Reports, recipes, transaction dumps and PoCs of this contract go to its own subdirectory
+    let contract_dir = results_dir.join(contract_path.file_stem().unwrap_or_default());
+    
     // Run the fuzzer
     let output = Command::new(fuzzer_binary)
         .arg("--input")
@@ -97,49 +153,45 @@ async fn run_fuzzer_on_contract(
         .arg(test_cases.to_string())
         .arg("--fork-url")
         .arg(fork_url)
+        .arg("--backend")
+        .arg(backend)
+        .arg("--out-dir")
+        .arg(&contract_dir)
+        .arg("--output")
+        .arg("json")
+        // Contracts whose constructor needs arguments fail instead of waiting for a prompt
+        .stdin(Stdio::null())
         .output()?;
     
     let execution_time = start.elapsed().as_millis() as u64;
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    // With --output json, stdout carries only the campaign report; progress and errors go to stderr
     let stderr = String::from_utf8_lossy(&output.stderr);
     
     // Check for compilation errors first - these are NOT vulnerabilities
     let is_compilation_error = stderr.contains("Compilation failed") 
         || stderr.contains("Unable to resolve imports")
         || stderr.contains("forge compilation failed")
-        || stdout.contains("Compilation failed")
         || stderr.contains("Contract compilation failed");
     
-    // Parse fuzzer output to determine results
-    let mut detected = false;
-    let mut passed = 0;
-    let mut failed = 0;
-    
-    // Try to extract pass/fail counts from output (only if fuzzer ran)
-    for line in stdout.lines() {
-        if line.contains("✅") && line.contains("runs passed") {
-            if let Some(num) = extract_number(line) {
-                passed = num;
-            }
-        }
-        if line.contains("❌") && line.contains("runs failed") {
-            if let Some(num) = extract_number(line) {
-                failed = num;
-            }
-        }
-    }
+    // No report when the fuzzer gave up before fuzzing (compilation errors, unreachable node, ...)
+    let report = serde_json::from_slice::<CampaignReport>(&output.stdout).ok();
+    let totals = report.as_ref().map(CampaignReport::totals).unwrap_or_default();
+    let passed = totals.passed;
+    let failed = totals.failed;
+    let errored = totals.errors;
+    let detections = report.as_ref().map(CampaignReport::detections).unwrap_or_default();
     
     // Mark as detected if:
     // 1. Fuzzer ran successfully (not a compilation error)
-    // 2. We have actual fuzzing results (passed + failed > 0)
-    // 3. There are failed test cases (indicating potential vulnerabilities)
-    if !is_compilation_error && (passed > 0 || failed > 0) {
-        // Detection is based solely on failed test cases from fuzzing
-        if failed > 0 || stdout.contains("FAILED") {
-            detected = true;
-        }
-    }
+    // 2. An oracle the gate admits reported something; a plain revert only counts when the gate
+    //    goes down to low severity, since most reverts are input validation doing its job
+    //    Infrastructure errors (RPC timeouts, nonce problems) are tracked separately and never count
+    let detected_by: Vec<OracleClass> = OracleClass::ALL.into_iter()
+        .filter(|class| gate.admits(*class) && detections.contains(class))
+        .collect();
+    let below_gate = detections.iter().filter(|class| !gate.admits(**class)).count();
+    let detected = !is_compilation_error && !detected_by.is_empty();
     
     let error = if !output.status.success() {
         Some(format!("Exit code: {}, stderr: {}", 
@@ -158,14 +210,12 @@ async fn run_fuzzer_on_contract(
         fuzz_runs: test_cases,
         passed,
         failed,
+        errored,
+        detected_by,
+        below_gate,
     })
 }
 
-fn extract_number(s: &str) -> Option<usize> {
-    s.split_whitespace()
-        .find_map(|word| word.parse::<usize>().ok())
-}
-
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     println!("{}", "=".repeat(70).bold());
@@ -177,10 +227,14 @@ async fn main() -> Result<(), anyhow::Error> {
     let bench_dir = Path::new("../../benchmarks/defihacklabs/src/test");
     let fuzzer_binary = Path::new("../target/release/base-solidity-fuzzer");
     let fork_url = std::env::var("FORK_URL").unwrap_or_else(|_| "http://localhost:8545".to_string());
+    // Executor backend of the fuzzer: anvil (default) or revm (in-process, no node needed)
+    let backend = std::env::var("BACKEND").unwrap_or_else(|_| "anvil".to_string());
     let test_cases: usize = std::env::var("TEST_CASES")
         .unwrap_or_else(|_| "50".to_string())
         .parse()
         .unwrap_or(50);
+    let results_dir = PathBuf::from(std::env::var("RESULTS_DIR").unwrap_or_else(|_| "results".to_string()));
+    let gate = DetectionGate::from_env()?;
     
     // Check if benchmark directory exists
     if !bench_dir.exists() {
@@ -228,7 +282,14 @@ async fn main() -> Result<(), anyhow::Error> {
     
     println!("  Testing {} contracts with {} test cases each", 
         contracts_to_test.len(), test_cases);
-    println!("  Fork URL: {}\n", fork_url.cyan());
+    println!("  Backend: {}", backend.cyan());
+    println!("  Fork URL: {}", fork_url.cyan());
+    if gate.oracles.is_empty() {
+        println!("  Detection gate: {} severity and above\n", gate.min_severity.to_string().cyan());
+    } else {
+        let oracles = gate.oracles.iter().map(OracleClass::name).collect::<Vec<_>>().join(", ");
+        println!("  Detection gate: {} at {} severity and above\n", oracles.cyan(), gate.min_severity);
+    }
     
     // Run fuzzer on each contract
     let mut results = Vec::new();
@@ -242,7 +303,7 @@ async fn main() -> Result<(), anyhow::Error> {
             contract.file_name().unwrap_or_default().to_string_lossy().bold()
         );
         
-        match run_fuzzer_on_contract(contract, fuzzer_binary, &fork_url, test_cases).await {
+        match run_fuzzer_on_contract(contract, fuzzer_binary, &fork_url, &backend, test_cases, &results_dir, &gate).await {
             Ok(result) => {
                 // Check if this was a compilation error
                 let is_compilation_error = result.error.as_ref()
@@ -256,13 +317,17 @@ async fn main() -> Result<(), anyhow::Error> {
                     println!("  Time: {}ms", result.execution_time_ms);
                 } else if result.detected {
                     detected_count += 1;
-                    println!("  {} Vulnerability detected", "✓".green().bold());
-                    println!("  Time: {}ms, Passed: {}, Failed: {}", 
-                        result.execution_time_ms, result.passed, result.failed);
+                    let oracles = result.detected_by.iter().map(OracleClass::name).collect::<Vec<_>>().join(", ");
+                    println!("  {} Vulnerability detected ({})", "✓".green().bold(), oracles);
+                    println!("  Time: {}ms, Passed: {}, Failed: {}, Errored: {}", 
+                        result.execution_time_ms, result.passed, result.failed, result.errored);
                 } else if result.passed > 0 || result.failed > 0 {
-                    println!("  {} No vulnerability detected", "✗".yellow());
-                    println!("  Time: {}ms, Passed: {}, Failed: {}", 
-                        result.execution_time_ms, result.passed, result.failed);
+                    println!("  {} No vulnerability detected ({} finding(s) below the detection gate)", "✗".yellow(), result.below_gate);
+                    println!("  Time: {}ms, Passed: {}, Failed: {}, Errored: {}", 
+                        result.execution_time_ms, result.passed, result.failed, result.errored);
+                } else if result.errored > 0 {
+                    println!("  {} Only infrastructure errors ({} runs errored)", "⚠".yellow(), result.errored);
+                    println!("  Time: {}ms", result.execution_time_ms);
                 } else {
                     println!("  {} No results (possible error)", "⚠".yellow());
                     println!("  Time: {}ms", result.execution_time_ms);
@@ -283,6 +348,9 @@ async fn main() -> Result<(), anyhow::Error> {
                     fuzz_runs: test_cases,
                     passed: 0,
                     failed: 0,
+                    errored: 0,
+                    detected_by: Vec::new(),
+                    below_gate: 0,
                 });
             }
         }
@@ -293,6 +361,9 @@ async fn main() -> Result<(), anyhow::Error> {
     
     // Generate summary
     let summary = BenchmarkSummary {
+        backend,
+        min_severity: gate.min_severity,
+        oracles: gate.oracles,
         total: results.len(),
         detected: detected_count,
         missed: results.len() - detected_count,
@@ -312,6 +383,7 @@ async fn main() -> Result<(), anyhow::Error> {
         .count();
     
     let compilation_error_count = summary.total - successful_runs_count;
+    let total_errored_runs: usize = summary.results.iter().map(|r| r.errored).sum();
     
     // Print summary
     println!("{}", "=".repeat(70).bold());
@@ -324,7 +396,16 @@ async fn main() -> Result<(), anyhow::Error> {
     if successful_runs_count > 0 {
         println!("  {} Successfully fuzzed: {}", "✓".green(), successful_runs_count);
         println!("  {} Vulnerabilities detected: {}", "✓".green(), summary.detected);
+        for class in OracleClass::ALL {
+            let contracts = summary.results.iter().filter(|r| r.detected_by.contains(&class)).count();
+            if contracts > 0 {
+                println!("      {}: {} contract(s)", class, contracts);
+            }
+        }
         println!("  {} Vulnerabilities missed: {}", "✗".red(), summary.missed);
+        if total_errored_runs > 0 {
+            println!("  {} Infrastructure errors (excluded from detection): {} runs", "⚠".yellow(), total_errored_runs);
+        }
         println!("  Detection rate: {:.1}% (of successfully fuzzed contracts)", 
             (summary.detected as f64 / successful_runs_count as f64) * 100.0
         );
@@ -333,9 +414,10 @@ async fn main() -> Result<(), anyhow::Error> {
     }
     println!("  Total execution time: {:.2}s", summary.total_execution_time_ms as f64 / 1000.0);
     
-    // let results_file = "benchmark-results.json";
-    // fs::write(results_file, serde_json::to_string_pretty(&summary)?)?;
-    // println!("\n  Results saved to: {}", results_file.cyan());
+    fs::create_dir_all(&results_dir)?;
+    let results_file = results_dir.join("benchmark-results.json");
+    fs::write(&results_file, serde_json::to_string_pretty(&summary)?)?;
+    println!("\n  Results saved to: {}", results_file.display().to_string().cyan());
     
     Ok(())
 }
@@ -0,0 +1,419 @@
+use crate::display;
+use crate::types::{ContractMethod, MethodParameter, MethodVisibility, SolidityType, SolidityValue, StateMutability};
+use crate::value_strategy;
+use anyhow::{anyhow, bail, Result};
+use ethers::abi::{Abi, ParamType, Token};
+use ethers::types::{Address, I256, U256};
+
+/// Build method signature string (e.g., "transfer(address,uint256)") from the declared parameter types
+pub fn method_signature(method: &ContractMethod) -> String {
+    let param_types: Vec<String> = method.parameters.iter()
+        .map(|param| param.param_type.abi_type_string())
+        .collect();
+
+    format!("{}({})", method.name, param_types.join(","))
+}
+
+/// ABI-encode arguments (without selector) with `ethers::abi::encode`: static values in place,
+/// dynamic ones (strings, bytes, arrays, tuples containing them) behind offsets
+pub fn encode_args(args: &[SolidityValue]) -> Result<Vec<u8>> {
+    let tokens = args.iter().map(value_to_token).collect::<Result<Vec<_>>>()?;
+    Ok(ethers::abi::encode(&tokens))
+}
+
+/// The `ethers` ABI type for a Solidity type, if it can appear in calldata
+pub fn param_type(sol_type: &SolidityType) -> Option<ParamType> {
+    Some(match sol_type {
+        SolidityType::Uint8 => ParamType::Uint(8),
+        SolidityType::Uint16 => ParamType::Uint(16),
+        SolidityType::Uint32 => ParamType::Uint(32),
+        SolidityType::Uint64 => ParamType::Uint(64),
+        SolidityType::Uint128 => ParamType::Uint(128),
+        SolidityType::Uint256 => ParamType::Uint(256),
+        SolidityType::Int8 => ParamType::Int(8),
+        SolidityType::Int16 => ParamType::Int(16),
+        SolidityType::Int32 => ParamType::Int(32),
+        SolidityType::Int64 => ParamType::Int(64),
+        SolidityType::Int128 => ParamType::Int(128),
+        SolidityType::Int256 => ParamType::Int(256),
+        SolidityType::Address => ParamType::Address,
+        SolidityType::Bool => ParamType::Bool,
+        SolidityType::Bytes1 => ParamType::FixedBytes(1),
+        SolidityType::Bytes2 => ParamType::FixedBytes(2),
+        SolidityType::Bytes4 => ParamType::FixedBytes(4),
+        SolidityType::Bytes8 => ParamType::FixedBytes(8),
+        SolidityType::Bytes16 => ParamType::FixedBytes(16),
+        SolidityType::Bytes32 => ParamType::FixedBytes(32),
+        SolidityType::String => ParamType::String,
+        SolidityType::Bytes => ParamType::Bytes,
+        SolidityType::Array(inner) => ParamType::Array(Box::new(param_type(inner)?)),
+        SolidityType::FixedArray(inner, length) => ParamType::FixedArray(Box::new(param_type(inner)?), *length),
+        SolidityType::Struct(_, fields) => ParamType::Tuple(
+            fields.iter().map(|field| param_type(&field.param_type)).collect::<Option<_>>()?,
+        ),
+        SolidityType::Mapping(..) | SolidityType::Custom(_) => return None,
+    })
+}
+
+/// The Solidity type of an `ethers` ABI type; sizes the generator has no variant for are `Custom`.
+/// `struct_name` is the ABI's `internalType` (`struct Vault.Order`), when known.
+pub fn solidity_type(kind: &ParamType, struct_name: Option<&str>) -> SolidityType {
+    match kind {
+        ParamType::Uint(8) => SolidityType::Uint8,
+        ParamType::Uint(16) => SolidityType::Uint16,
+        ParamType::Uint(32) => SolidityType::Uint32,
+        ParamType::Uint(64) => SolidityType::Uint64,
+        ParamType::Uint(128) => SolidityType::Uint128,
+        ParamType::Uint(256) => SolidityType::Uint256,
+        ParamType::Int(8) => SolidityType::Int8,
+        ParamType::Int(16) => SolidityType::Int16,
+        ParamType::Int(32) => SolidityType::Int32,
+        ParamType::Int(64) => SolidityType::Int64,
+        ParamType::Int(128) => SolidityType::Int128,
+        ParamType::Int(256) => SolidityType::Int256,
+        ParamType::Address => SolidityType::Address,
+        ParamType::Bool => SolidityType::Bool,
+        ParamType::FixedBytes(1) => SolidityType::Bytes1,
+        ParamType::FixedBytes(2) => SolidityType::Bytes2,
+        ParamType::FixedBytes(4) => SolidityType::Bytes4,
+        ParamType::FixedBytes(8) => SolidityType::Bytes8,
+        ParamType::FixedBytes(16) => SolidityType::Bytes16,
+        ParamType::FixedBytes(32) => SolidityType::Bytes32,
+        ParamType::String => SolidityType::String,
+        ParamType::Bytes => SolidityType::Bytes,
+        ParamType::Array(inner) => SolidityType::Array(Box::new(solidity_type(inner, struct_name))),
+        ParamType::FixedArray(inner, length) => SolidityType::FixedArray(Box::new(solidity_type(inner, struct_name)), *length),
+        ParamType::Tuple(fields) => SolidityType::Struct(
+            struct_name.and_then(|name| name.strip_prefix("struct ")).and_then(|name| name.split('[').next()).unwrap_or("tuple").to_string(),
+            fields.iter()
+                .enumerate()
+                .map(|(index, field)| MethodParameter {
+                    name: MethodParameter::placeholder_name(index),
+                    param_type: solidity_type(field, None),
+                    location: None,
+                })
+                .collect(),
+        ),
+        other => SolidityType::Custom(other.to_string()),
+    }
+}
+
+/// The functions, fallback and receive of a compiled ABI as methods: exactly what the deployed
+/// bytecode dispatches on, including functions inherited from imported files
+pub fn methods_from_abi(abi: &Abi) -> Vec<ContractMethod> {
+    let parameters = |params: &[ethers::abi::Param]| -> Vec<MethodParameter> {
+        params.iter()
+            .enumerate()
+            .map(|(index, param)| MethodParameter {
+                name: if param.name.is_empty() { MethodParameter::placeholder_name(index) } else { param.name.clone() },
+                param_type: solidity_type(&param.kind, param.internal_type.as_deref()),
+                location: None,
+            })
+            .collect()
+    };
+    let entry_point = |name: &str, mutability| ContractMethod {
+        name: name.to_string(),
+        parameters: Vec::new(),
+        visibility: MethodVisibility::External,
+        is_constructor: false,
+        is_fallback: name == "fallback",
+        is_receive: name == "receive",
+        mutability,
+        modifiers: Vec::new(),
+        returns: Vec::new(),
+    };
+
+    let mut methods: Vec<ContractMethod> = abi.functions()
+        .map(|function| ContractMethod {
+            name: function.name.clone(),
+            parameters: parameters(&function.inputs),
+            // Public and external functions look the same in an ABI
+            visibility: MethodVisibility::External,
+            is_constructor: false,
+            is_fallback: false,
+            is_receive: false,
+            mutability: match function.state_mutability {
+                ethers::abi::StateMutability::Pure => StateMutability::Pure,
+                ethers::abi::StateMutability::View => StateMutability::View,
+                ethers::abi::StateMutability::NonPayable => StateMutability::NonPayable,
+                ethers::abi::StateMutability::Payable => StateMutability::Payable,
+            },
+            modifiers: Vec::new(),
+            returns: parameters(&function.outputs),
+        })
+        .collect();
+    if abi.fallback {
+        methods.push(entry_point("fallback", StateMutability::NonPayable));
+    }
+    if abi.receive {
+        methods.push(entry_point("receive", StateMutability::Payable));
+    }
+    methods
+}
+
+/// The `ethers` token of a value, as it is encoded and as it is expected to decode
+pub fn value_to_token(value: &SolidityValue) -> Result<Token> {
+    Ok(match value {
+        SolidityValue::Uint8(v) => Token::Uint(U256::from(*v)),
+        SolidityValue::Uint16(v) => Token::Uint(U256::from(*v)),
+        SolidityValue::Uint32(v) => Token::Uint(U256::from(*v)),
+        SolidityValue::Uint64(v) => Token::Uint(U256::from(*v)),
+        SolidityValue::Uint128(v) => Token::Uint(U256::from(*v)),
+        SolidityValue::Uint256(v) => Token::Uint(U256::from_dec_str(v).map_err(|e| anyhow!("invalid uint256 {}: {}", v, e))?),
+        SolidityValue::Int8(v) => Token::Int(I256::from(*v).into_raw()),
+        SolidityValue::Int16(v) => Token::Int(I256::from(*v).into_raw()),
+        SolidityValue::Int32(v) => Token::Int(I256::from(*v).into_raw()),
+        SolidityValue::Int64(v) => Token::Int(I256::from(*v).into_raw()),
+        SolidityValue::Int128(v) => Token::Int(I256::from(*v).into_raw()),
+        SolidityValue::Int256(v) => Token::Int(I256::from_dec_str(v).map_err(|e| anyhow!("invalid int256 {}: {}", v, e))?.into_raw()),
+        SolidityValue::Address(addr) => Token::Address(addr.parse::<Address>().map_err(|e| anyhow!("invalid address {}: {}", addr, e))?),
+        SolidityValue::Bool(b) => Token::Bool(*b),
+        SolidityValue::Bytes1(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::Bytes2(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::Bytes4(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::Bytes8(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::Bytes16(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::Bytes32(bs) => Token::FixedBytes(bs.to_vec()),
+        SolidityValue::String(s) => Token::String(s.clone()),
+        SolidityValue::Bytes(bs) => Token::Bytes(bs.clone()),
+        SolidityValue::Array(values) => Token::Array(values.iter().map(value_to_token).collect::<Result<_>>()?),
+        SolidityValue::FixedArray(values) => Token::FixedArray(values.iter().map(value_to_token).collect::<Result<_>>()?),
+        SolidityValue::Struct(fields) => Token::Tuple(fields.iter().map(|(_, value)| value_to_token(value)).collect::<Result<_>>()?),
+    })
+}
+
+/// Decode ABI-encoded arguments (without selector) into values of the declared types
+pub fn decode_args(types: &[SolidityType], encoded: &[u8]) -> Result<Vec<SolidityValue>> {
+    let param_types = types.iter()
+        .map(|t| param_type(t).ok_or_else(|| anyhow!("type {} cannot be ABI-decoded", t.abi_type_string())))
+        .collect::<Result<Vec<_>>>()?;
+    let tokens = ethers::abi::decode(&param_types, encoded)
+        .map_err(|e| anyhow!("arguments do not decode: {}", e))?;
+    types.iter().zip(&tokens)
+        .map(|(sol_type, token)| token_to_value(sol_type, token)
+            .ok_or_else(|| anyhow!("{:?} is not a valid {}", token, sol_type.abi_type_string())))
+        .collect()
+}
+
+/// The value of a decoded token, if it fits the declared type
+pub fn token_to_value(sol_type: &SolidityType, token: &Token) -> Option<SolidityValue> {
+    let fits = |value: &U256, bits: usize| value.bits() <= bits;
+    let signed = |value: &U256| I256::from_raw(*value);
+    Some(match (sol_type, token) {
+        (SolidityType::Uint8, Token::Uint(v)) if fits(v, 8) => SolidityValue::Uint8(v.as_u32() as u8),
+        (SolidityType::Uint16, Token::Uint(v)) if fits(v, 16) => SolidityValue::Uint16(v.as_u32() as u16),
+        (SolidityType::Uint32, Token::Uint(v)) if fits(v, 32) => SolidityValue::Uint32(v.as_u32()),
+        (SolidityType::Uint64, Token::Uint(v)) if fits(v, 64) => SolidityValue::Uint64(v.as_u64()),
+        (SolidityType::Uint128, Token::Uint(v)) if fits(v, 128) => SolidityValue::Uint128(v.as_u128()),
+        (SolidityType::Uint256, Token::Uint(v)) => SolidityValue::Uint256(v.to_string()),
+        (SolidityType::Int8, Token::Int(v)) => SolidityValue::Int8(i8::try_from(signed(v).as_i128()).ok()?),
+        (SolidityType::Int16, Token::Int(v)) => SolidityValue::Int16(i16::try_from(signed(v).as_i128()).ok()?),
+        (SolidityType::Int32, Token::Int(v)) => SolidityValue::Int32(i32::try_from(signed(v).as_i128()).ok()?),
+        (SolidityType::Int64, Token::Int(v)) => SolidityValue::Int64(i64::try_from(signed(v).as_i128()).ok()?),
+        (SolidityType::Int128, Token::Int(v)) => SolidityValue::Int128(i128::try_from(signed(v)).ok()?),
+        (SolidityType::Int256, Token::Int(v)) => SolidityValue::Int256(signed(v).to_string()),
+        (SolidityType::Address, Token::Address(address)) => SolidityValue::Address(format!("{:?}", address)),
+        (SolidityType::Bool, Token::Bool(v)) => SolidityValue::Bool(*v),
+        (SolidityType::Bytes1, Token::FixedBytes(bytes)) => SolidityValue::Bytes1(bytes.as_slice().try_into().ok()?),
+        (SolidityType::Bytes2, Token::FixedBytes(bytes)) => SolidityValue::Bytes2(bytes.as_slice().try_into().ok()?),
+        (SolidityType::Bytes4, Token::FixedBytes(bytes)) => SolidityValue::Bytes4(bytes.as_slice().try_into().ok()?),
+        (SolidityType::Bytes8, Token::FixedBytes(bytes)) => SolidityValue::Bytes8(bytes.as_slice().try_into().ok()?),
+        (SolidityType::Bytes16, Token::FixedBytes(bytes)) => SolidityValue::Bytes16(bytes.as_slice().try_into().ok()?),
+        (SolidityType::Bytes32, Token::FixedBytes(bytes)) => SolidityValue::Bytes32(bytes.as_slice().try_into().ok()?),
+        (SolidityType::String, Token::String(v)) => SolidityValue::String(v.clone()),
+        (SolidityType::Bytes, Token::Bytes(bytes)) => SolidityValue::Bytes(bytes.clone()),
+        (SolidityType::Array(inner), Token::Array(items)) => {
+            SolidityValue::Array(items.iter().map(|item| token_to_value(inner, item)).collect::<Option<_>>()?)
+        }
+        (SolidityType::FixedArray(inner, length), Token::FixedArray(items)) if items.len() == *length => {
+            SolidityValue::FixedArray(items.iter().map(|item| token_to_value(inner, item)).collect::<Option<_>>()?)
+        }
+        (SolidityType::Struct(_, fields), Token::Tuple(items)) if fields.len() == items.len() => {
+            SolidityValue::Struct(fields.iter().zip(items)
+                .map(|(field, item)| Some((field.name.clone(), token_to_value(&field.param_type, item)?)))
+                .collect::<Option<_>>()?)
+        }
+        _ => return None,
+    })
+}
+
+/// Check every argument against its declared type, then decode `encoded` with
+/// `ethers::abi::decode` and check that every argument comes back unchanged. `ethers` does not
+/// range-check on either side, so the type check is what catches a value too wide for its
+/// parameter. A mismatch is a bug in the fuzzer, not in the contract.
+pub fn verify_round_trip(types: &[SolidityType], args: &[SolidityValue], encoded: &[u8]) -> Result<()> {
+    if types.len() != args.len() {
+        bail!("{} argument(s) for {} parameter(s)", args.len(), types.len());
+    }
+
+    let param_types = types.iter()
+        .map(|t| param_type(t).ok_or_else(|| anyhow!("type {} cannot be ABI-decoded", t.abi_type_string())))
+        .collect::<Result<Vec<_>>>()?;
+    for (index, (sol_type, arg)) in types.iter().zip(args).enumerate() {
+        check_value(sol_type, arg).map_err(|e| anyhow!("argument {}: {}", index, e))?;
+    }
+    let expected = args.iter().map(value_to_token).collect::<Result<Vec<_>>>()?;
+
+    let decoded = ethers::abi::decode(&param_types, encoded)
+        .map_err(|e| anyhow!("encoded arguments do not decode: {}", e))?;
+
+    for (index, (got, want)) in decoded.iter().zip(expected.iter()).enumerate() {
+        if got != want {
+            bail!("argument {} ({}) decodes to {:?}, expected {:?}", index, types[index].abi_type_string(), got, want);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a value is a valid argument of `sol_type`: an integer of the declared signedness
+/// and width, fixed bytes of the declared length, an array of the declared length or a struct
+/// with the declared fields, all the way down
+pub fn check_value(sol_type: &SolidityType, value: &SolidityValue) -> Result<()> {
+    let mismatch = || anyhow!("{} is not a valid {}", display::format_value(value, 66), sol_type.abi_type_string());
+    match (sol_type, value) {
+        (SolidityType::Array(inner), SolidityValue::Array(items)) => {
+            return items.iter().try_for_each(|item| check_value(inner, item));
+        }
+        (SolidityType::FixedArray(inner, length), SolidityValue::FixedArray(items)) if items.len() == *length => {
+            return items.iter().try_for_each(|item| check_value(inner, item));
+        }
+        (SolidityType::Struct(_, fields), SolidityValue::Struct(items)) if fields.len() == items.len() => {
+            return fields.iter().zip(items).try_for_each(|(field, (_, item))| check_value(&field.param_type, item));
+        }
+        (SolidityType::Array(_) | SolidityType::FixedArray(..) | SolidityType::Struct(..), _) => return Err(mismatch()),
+        _ => {}
+    }
+
+    // Scalars: the token must have the declared ABI kind, and integers must fit the declared width
+    let token = value_to_token(value)?;
+    let fits = match (value_strategy::integer_type(sol_type), &token) {
+        (Some((false, bits)), Token::Uint(v)) => is_unsigned(value) && v.bits() <= bits,
+        (Some((true, 256)), Token::Int(_)) => true,
+        (Some((true, bits)), Token::Int(raw)) => {
+            let v = I256::from_raw(*raw);
+            let bound = I256::from_raw(U256::one() << (bits - 1));
+            v >= -bound && v < bound
+        }
+        (Some(_), _) => false,
+        (None, Token::FixedBytes(bytes)) => param_type(sol_type) == Some(ParamType::FixedBytes(bytes.len())),
+        (None, token) => param_type(sol_type).is_some_and(|kind| token.type_check(&kind)),
+    };
+    if fits { Ok(()) } else { Err(mismatch()) }
+}
+
+fn is_unsigned(value: &SolidityValue) -> bool {
+    matches!(value,
+        SolidityValue::Uint8(_) | SolidityValue::Uint16(_) | SolidityValue::Uint32(_)
+        | SolidityValue::Uint64(_) | SolidityValue::Uint128(_) | SolidityValue::Uint256(_))
+}
+
+/// Human-readable rendering of a decoded token: decimal integers, `0x` hex for addresses and
+/// bytes, nested values in brackets
+pub fn format_token(token: &Token) -> String {
+    match token {
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::Address(address) => format!("{:?}", address),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => value.clone(),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => {
+            format!("[{}]", items.iter().map(format_token).collect::<Vec<_>>().join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{generate_value_with_provenance, GeneratorContext};
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn scalar_type() -> impl Strategy<Value = SolidityType> {
+        prop_oneof![
+            Just(SolidityType::Uint8), Just(SolidityType::Uint16), Just(SolidityType::Uint32),
+            Just(SolidityType::Uint64), Just(SolidityType::Uint128), Just(SolidityType::Uint256),
+            Just(SolidityType::Int8), Just(SolidityType::Int16), Just(SolidityType::Int32),
+            Just(SolidityType::Int64), Just(SolidityType::Int128), Just(SolidityType::Int256),
+            Just(SolidityType::Address), Just(SolidityType::Bool),
+            Just(SolidityType::Bytes1), Just(SolidityType::Bytes2), Just(SolidityType::Bytes4),
+            Just(SolidityType::Bytes8), Just(SolidityType::Bytes16), Just(SolidityType::Bytes32),
+            Just(SolidityType::String), Just(SolidityType::Bytes),
+        ]
+    }
+
+    /// Every encodable type, with arrays, fixed arrays and structs nested up to three levels
+    fn solidity_type() -> impl Strategy<Value = SolidityType> {
+        scalar_type().prop_recursive(3, 16, 3, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|t| SolidityType::Array(Box::new(t))),
+                (inner.clone(), 1..4usize).prop_map(|(t, length)| SolidityType::FixedArray(Box::new(t), length)),
+                prop::collection::vec(inner, 1..4).prop_map(|fields| SolidityType::Struct(
+                    "S".to_string(),
+                    fields.into_iter()
+                        .enumerate()
+                        .map(|(index, param_type)| MethodParameter { name: MethodParameter::placeholder_name(index), param_type, location: None })
+                        .collect(),
+                )),
+            ]
+        })
+    }
+
+    fn context() -> GeneratorContext {
+        GeneratorContext {
+            known_addresses: vec!["0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string()],
+            ..Default::default()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn generated_values_round_trip(types in prop::collection::vec(solidity_type(), 1..4), seed: u64) {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let context = context();
+            let args: Vec<SolidityValue> = types.iter()
+                .map(|t| generate_value_with_provenance(&mut rng, t, &context).0)
+                .collect();
+
+            for (sol_type, arg) in types.iter().zip(&args) {
+                prop_assert!(check_value(sol_type, arg).is_ok(), "{:?} rejected for {}", arg, sol_type.abi_type_string());
+            }
+            let encoded = encode_args(&args).unwrap();
+            verify_round_trip(&types, &args, &encoded).unwrap();
+
+            let decoded = decode_args(&types, &encoded).unwrap();
+            let tokens = |values: &[SolidityValue]| values.iter().map(value_to_token).collect::<Result<Vec<_>>>().unwrap();
+            prop_assert_eq!(tokens(&decoded), tokens(&args));
+            prop_assert_eq!(encode_args(&decoded).unwrap(), encoded);
+        }
+    }
+
+    #[test]
+    fn rejects_values_wider_than_the_declared_type() {
+        let over_range = [SolidityValue::Uint256("300".to_string())];
+        let encoded = encode_args(&over_range).unwrap();
+        assert!(verify_round_trip(&[SolidityType::Uint8], &over_range, &encoded).is_err());
+
+        assert!(check_value(&SolidityType::Uint8, &SolidityValue::Uint256("255".to_string())).is_ok());
+        assert!(check_value(&SolidityType::Int8, &SolidityValue::Int256("-129".to_string())).is_err());
+        assert!(check_value(&SolidityType::Int8, &SolidityValue::Int256("-128".to_string())).is_ok());
+        assert!(check_value(&SolidityType::Int8, &SolidityValue::Uint8(1)).is_err());
+        assert!(check_value(&SolidityType::Int256, &SolidityValue::Int256(I256::MIN.to_string())).is_ok());
+    }
+
+    #[test]
+    fn rejects_fixed_bytes_and_arrays_of_the_wrong_length() {
+        assert!(check_value(&SolidityType::Bytes4, &SolidityValue::Bytes8([0; 8])).is_err());
+        assert!(check_value(&SolidityType::Bytes4, &SolidityValue::Bytes(vec![0; 4])).is_err());
+
+        let triple = SolidityType::FixedArray(Box::new(SolidityType::Bool), 3);
+        assert!(check_value(&triple, &SolidityValue::FixedArray(vec![SolidityValue::Bool(true); 2])).is_err());
+        assert!(check_value(&triple, &SolidityValue::FixedArray(vec![SolidityValue::Bool(true); 3])).is_ok());
+
+        let nested = SolidityType::Array(Box::new(SolidityType::Uint16));
+        assert!(check_value(&nested, &SolidityValue::Array(vec![SolidityValue::Uint256("65536".to_string())])).is_err());
+    }
+}
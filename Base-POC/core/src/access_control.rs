@@ -0,0 +1,141 @@
+//! Access control oracle. Privileged-looking methods (guarded by an `only…` modifier, or setting
+//! an owner, admin or role) are expected to turn away senders other than the deployer, the owner
+//! and the accounts held in protected variables, so their reverts for those senders are not failures. A call from such a sender that
+//! succeeds and changes protected state (owner/admin variables, the EIP-1967 admin and
+//! implementation slots, the owner getter) is a finding.
+
+use crate::accounting::StorageVariable;
+use crate::executor::Executor;
+use crate::ownership::{self, ContractOwner};
+use crate::storage_slots;
+use crate::types::ContractMethod;
+use anyhow::Result;
+use ethers::abi::Abi;
+use ethers::types::U256;
+
+/// `only…` modifiers that restrict how a function is called rather than who calls it
+const CALL_CONTEXT_MODIFIERS: [&str; 4] = ["onlyInitializing", "onlyProxy", "onlyDelegateCall", "onlyEOA"];
+
+/// Access modifiers not named `only…`
+const OTHER_ACCESS_MODIFIERS: [&str; 4] = ["auth", "requiresAuth", "ownerOnly", "adminOnly"];
+
+/// Words naming a privileged account or setting, in method and variable names
+const ROLE_WORDS: [&str; 10] = ["owner", "admin", "governance", "governor", "guardian", "operator", "minter", "pauser", "role", "implementation"];
+
+/// Method name prefixes that, followed by a role word, change who holds a privilege
+const SETTER_PREFIXES: [&str; 8] = ["set", "change", "transfer", "update", "grant", "revoke", "accept", "upgrade"];
+
+fn names_role(name: &str) -> bool {
+    let name = name.trim_start_matches('_').to_ascii_lowercase();
+    ROLE_WORDS.iter().any(|word| name.contains(word))
+}
+
+fn is_access_modifier(modifier: &str) -> bool {
+    (modifier.starts_with("only") && !CALL_CONTEXT_MODIFIERS.contains(&modifier))
+        || OTHER_ACCESS_MODIFIERS.contains(&modifier)
+}
+
+/// Whether a method looks restricted to privileged accounts: it has an access modifier, is named
+/// like a setter of an owner, admin or role, or upgrades the implementation
+pub fn is_privileged(method: &ContractMethod) -> bool {
+    if method.modifiers.iter().any(|modifier| is_access_modifier(modifier)) {
+        return true;
+    }
+    let name = method.name.to_ascii_lowercase();
+    name.starts_with("upgrade")
+        || (SETTER_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) && names_role(&name))
+}
+
+/// Owner and admin variables of the storage layout that fit in a slot, plus the EIP-1967 admin
+/// and implementation slots
+pub fn protected_variables(layout: &[StorageVariable]) -> Vec<StorageVariable> {
+    let mut variables: Vec<StorageVariable> = layout.iter()
+        .filter(|variable| names_role(&variable.label) && variable.size <= 32
+            && !variable.type_label.starts_with("mapping") && !variable.type_label.contains('[')
+            && !matches!(variable.type_label.as_str(), "string" | "bytes"))
+        .cloned()
+        .collect();
+    for (label, slot) in [("EIP-1967 admin", "eip1967.proxy.admin"), ("EIP-1967 implementation", "eip1967.proxy.implementation")] {
+        variables.push(StorageVariable {
+            label: label.to_string(),
+            slot: storage_slots::eip1967_slot(slot),
+            offset: 0,
+            size: 32,
+            type_label: "address".to_string(),
+        });
+    }
+    variables
+}
+
+/// Accounts held in the address-typed protected variables of a deployed contract (`admin`,
+/// `governance`, the EIP-1967 admin slot…) as `(label, address)`, skipping zero addresses and the
+/// implementation, which is code rather than an account
+pub async fn read_role_accounts(executor: &mut dyn Executor, contract_name: &str, layout: &[StorageVariable]) -> Vec<(String, String)> {
+    let mut accounts: Vec<(String, String)> = Vec::new();
+    let variables = protected_variables(layout).into_iter()
+        .filter(|variable| variable.type_label.starts_with("address") && !variable.label.to_ascii_lowercase().contains("implementation"));
+    for variable in variables {
+        let Ok(word) = executor.storage_at(contract_name, variable.slot).await else {
+            continue;
+        };
+        let value = variable.decode(word);
+        let address = format_value(&variable, value);
+        if !value.is_zero() && !accounts.iter().any(|(_, known)| known == &address) {
+            accounts.push((variable.label, address));
+        }
+    }
+    accounts
+}
+
+/// Protected values of a contract at one point in time
+#[derive(Debug, Clone)]
+pub struct ProtectedState {
+    /// Owner read from the owner getter, if there is one
+    pub owner: Option<ContractOwner>,
+    values: Vec<(String, String)>,
+}
+
+fn format_value(variable: &StorageVariable, value: U256) -> String {
+    if variable.type_label.starts_with("address") || variable.type_label.starts_with("contract") {
+        format!("0x{:040x}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Read the protected variables and the owner getter of a deployed contract
+pub async fn read(executor: &mut dyn Executor, contract_name: &str, abi: &Abi, variables: &[StorageVariable]) -> Result<ProtectedState> {
+    let mut values = Vec::with_capacity(variables.len() + 1);
+    for variable in variables {
+        let word = executor.storage_at(contract_name, variable.slot).await?;
+        values.push((variable.label.clone(), format_value(variable, variable.decode(word))));
+    }
+    let owner = ownership::read_owner(executor, contract_name, abi).await;
+    if let Some(owner) = &owner {
+        values.push((format!("{}()", owner.getter), owner.address.clone()));
+    }
+    Ok(ProtectedState { owner, values })
+}
+
+/// Whether `sender` may call privileged methods: it deployed the contract, is its owner or is
+/// held in one of its protected variables (an `admin` or `governance` account, say)
+pub fn is_authorized(sender: &str, deployer: Option<&str>, state: &ProtectedState) -> bool {
+    deployer.is_some_and(|deployer| deployer.eq_ignore_ascii_case(sender))
+        || state.owner.as_ref().is_some_and(|owner| owner.address.eq_ignore_ascii_case(sender))
+        || state.values.iter().any(|(_, value)| value.eq_ignore_ascii_case(sender))
+}
+
+/// Protected values that differ between two reads, e.g. `owner 0x… → 0x…`
+pub fn changes(before: &ProtectedState, after: &ProtectedState) -> Vec<String> {
+    let mut changes: Vec<String> = before.values.iter()
+        .filter_map(|(label, value)| {
+            let new_value = after.values.iter().find(|(new_label, _)| new_label == label).map_or("none", |(_, new_value)| new_value.as_str());
+            (new_value != value).then(|| format!("{} {} → {}", label, value, new_value))
+        })
+        .collect();
+    // An owner getter that returned the zero address before the call (none yet, or renounced)
+    changes.extend(after.values.iter()
+        .filter(|(label, _)| !before.values.iter().any(|(old_label, _)| old_label == label))
+        .map(|(label, value)| format!("{} none → {}", label, value)));
+    changes
+}
@@ -0,0 +1,175 @@
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Label fragments of state variables that usually book ETH held by the contract
+const ACCOUNTING_LABELS: [&str; 11] = [
+    "deposit", "balance", "reserve", "locked", "stake", "escrow", "eth", "wei", "fund", "collateral", "pool",
+];
+
+/// A state variable from a compiler storage layout
+#[derive(Debug, Clone)]
+pub struct StorageVariable {
+    pub label: String,
+    pub slot: U256,
+    /// Byte offset inside the slot (packed variables share a slot)
+    pub offset: usize,
+    /// Size in bytes, 32 for a full slot
+    pub size: usize,
+    /// Type label, e.g. `uint256` or `mapping(address => uint256)`
+    pub type_label: String,
+}
+
+impl StorageVariable {
+    /// The variable's value from the raw word of its slot
+    pub fn decode(&self, word: U256) -> U256 {
+        let value = word >> (self.offset * 8);
+        if self.size >= 32 {
+            value
+        } else {
+            value & ((U256::one() << (self.size * 8)) - 1)
+        }
+    }
+}
+
+/// State variables of a `storageLayout` artifact entry (`None` or malformed entries give none)
+pub fn parse_storage_layout(layout: Option<&Value>) -> Vec<StorageVariable> {
+    let Some(layout) = layout else {
+        return Vec::new();
+    };
+    let types = layout.get("types");
+    layout.get("storage")
+        .and_then(|storage| storage.as_array())
+        .map(|storage| storage.iter()
+            .filter_map(|entry| {
+                let type_id = entry.get("type")?.as_str()?;
+                let type_info = types.and_then(|types| types.get(type_id));
+                Some(StorageVariable {
+                    label: entry.get("label")?.as_str()?.to_string(),
+                    slot: U256::from_dec_str(entry.get("slot")?.as_str()?).ok()?,
+                    offset: entry.get("offset")?.as_u64()? as usize,
+                    size: type_info
+                        .and_then(|info| info.get("numberOfBytes"))
+                        .and_then(|size| size.as_str())
+                        .and_then(|size| size.parse().ok())
+                        .unwrap_or(32),
+                    type_label: type_info
+                        .and_then(|info| info.get("label"))
+                        .and_then(|label| label.as_str())
+                        .unwrap_or(type_id)
+                        .to_string(),
+                })
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Unsigned scalar variables whose name suggests they book ETH (`totalDeposits`, `reserve`, `lockedEth`).
+/// Mappings cannot be summed without knowing their keys and are left out.
+pub fn accounting_variables(layout: &[StorageVariable]) -> Vec<StorageVariable> {
+    layout.iter()
+        .filter(|variable| variable.type_label.starts_with("uint"))
+        .filter(|variable| {
+            let label = variable.label.to_ascii_lowercase();
+            ACCOUNTING_LABELS.iter().any(|fragment| label.contains(fragment))
+        })
+        .cloned()
+        .collect()
+}
+
+/// ETH that moved into and out of a contract over a campaign, in wei
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueFlows {
+    pub contract: String,
+    pub inflow: String,
+    pub outflow: String,
+    /// Balance at the end of the campaign
+    pub balance: String,
+    /// Accounting variables compared with the balance
+    pub tracked_variables: Vec<String>,
+}
+
+/// Running ETH balance of one contract next to its accounting variables. Flows are summed from the
+/// balance change of every recorded call, including calls of sequences that were later reverted.
+#[derive(Debug)]
+pub struct ValueLedger {
+    variables: Vec<StorageVariable>,
+    balance: U256,
+    values: Vec<U256>,
+    inflow: U256,
+    outflow: U256,
+    /// Checks that already failed once; each is reported a single time
+    reported: HashSet<String>,
+}
+
+impl ValueLedger {
+    /// Start from the contract's current balance and the raw words of `variables`' slots
+    pub fn new(variables: Vec<StorageVariable>, balance: U256, words: &[U256]) -> Self {
+        let mut ledger = Self {
+            variables,
+            balance,
+            values: Vec::new(),
+            inflow: U256::zero(),
+            outflow: U256::zero(),
+            reported: HashSet::new(),
+        };
+        ledger.values = ledger.decode(words);
+        ledger
+    }
+
+    /// Storage slots to read for `record`, one per variable
+    pub fn slots(&self) -> Vec<U256> {
+        self.variables.iter().map(|variable| variable.slot).collect()
+    }
+
+    /// Adopt the current state without counting a flow (after a snapshot was restored)
+    pub fn resync(&mut self, balance: U256, words: &[U256]) {
+        self.balance = balance;
+        self.values = self.decode(words);
+    }
+
+    /// Record the state after a call and return the discrepancies it revealed, as
+    /// `(check name, reason)`. A variable that books more ETH than the contract holds means the
+    /// contract cannot pay out what it owes; a balance change that no accounting variable follows
+    /// means ETH moved without being booked.
+    pub fn record(&mut self, balance: U256, words: &[U256]) -> Vec<(String, String)> {
+        let values = self.decode(words);
+        let mut discrepancies = Vec::new();
+
+        let balance_moved = balance != self.balance;
+        if balance > self.balance {
+            self.inflow = self.inflow.saturating_add(balance - self.balance);
+        } else {
+            self.outflow = self.outflow.saturating_add(self.balance - balance);
+        }
+
+        for (variable, value) in self.variables.iter().zip(&values) {
+            if *value > balance && self.reported.insert(variable.label.clone()) {
+                discrepancies.push((variable.label.clone(), format!("books {} wei but the contract holds {} wei", value, balance)));
+            }
+        }
+        if balance_moved && !self.variables.is_empty() && values == self.values && self.reported.insert(String::new()) {
+            let labels = self.variables.iter().map(|variable| variable.label.as_str()).collect::<Vec<_>>().join(", ");
+            discrepancies.push((String::new(), format!("balance went from {} to {} wei but none of {} changed", self.balance, balance, labels)));
+        }
+
+        self.balance = balance;
+        self.values = values;
+        discrepancies
+    }
+
+    pub fn flows(&self, contract: &str) -> ValueFlows {
+        ValueFlows {
+            contract: contract.to_string(),
+            inflow: self.inflow.to_string(),
+            outflow: self.outflow.to_string(),
+            balance: self.balance.to_string(),
+            tracked_variables: self.variables.iter().map(|variable| variable.label.clone()).collect(),
+        }
+    }
+
+    fn decode(&self, words: &[U256]) -> Vec<U256> {
+        self.variables.iter().zip(words).map(|(variable, word)| variable.decode(*word)).collect()
+    }
+}
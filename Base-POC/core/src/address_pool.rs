@@ -0,0 +1,196 @@
+//! The pool address arguments are drawn from. Each source has a weight for the campaign
+//! (`--address-weights`, `[address_weights]` in `fuzzhead.toml`): the deployer, the other accounts
+//! the executor sends from, the contracts of the campaign (deployed from the file, or found behind
+//! their address getters while fuzzing), the reentrancy attacker, the zero address, the chain's
+//! precompiles and system contracts, and random addresses. Sources without addresses, such as the
+//! attacker without `--reentrancy`, are left out and the other weights share their part.
+
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::ast_parser::ContractInfo;
+use crate::executor::Executor;
+use crate::generator::GeneratorContext;
+use crate::precompiles;
+use crate::types::{ArgProvenance, SolidityType, StateMutability};
+use ethers::types::U256;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::debug;
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Relative weight of each source of address arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AddressWeights {
+    pub deployer: u32,
+    /// The executor's other accounts, impersonated ones included
+    pub accounts: u32,
+    /// Contracts deployed from the file and contracts discovered behind their getters
+    pub contracts: u32,
+    pub attacker: u32,
+    pub zero: u32,
+    pub precompiles: u32,
+    pub random: u32,
+}
+
+impl Default for AddressWeights {
+    fn default() -> Self {
+        Self { deployer: 5, accounts: 20, contracts: 10, attacker: 10, zero: 10, precompiles: 5, random: 40 }
+    }
+}
+
+impl AddressWeights {
+    fn entries(&self) -> [(&'static str, u32); 7] {
+        [
+            ("deployer", self.deployer),
+            ("accounts", self.accounts),
+            ("contracts", self.contracts),
+            ("attacker", self.attacker),
+            ("zero", self.zero),
+            ("precompiles", self.precompiles),
+            ("random", self.random),
+        ]
+    }
+
+    fn weight_mut(&mut self, source: &str) -> Option<&mut u32> {
+        Some(match source {
+            "deployer" => &mut self.deployer,
+            "accounts" => &mut self.accounts,
+            "contracts" => &mut self.contracts,
+            "attacker" => &mut self.attacker,
+            "zero" => &mut self.zero,
+            "precompiles" => &mut self.precompiles,
+            "random" => &mut self.random,
+            _ => return None,
+        })
+    }
+
+    /// An error when no source could ever be drawn
+    pub fn validate(&self) -> Result<(), String> {
+        // The zero, precompile and random sources always have addresses
+        if self.zero == 0 && self.precompiles == 0 && self.random == 0 {
+            return Err("at least one of zero, precompiles and random needs a weight above 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for AddressWeights {
+    type Err = String;
+
+    /// `source=weight` pairs separated by commas; sources not listed keep their default weight
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut weights = Self::default();
+        for pair in text.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (source, weight) = pair.split_once('=')
+                .ok_or_else(|| format!("invalid address weight '{}' (expected SOURCE=WEIGHT)", pair))?;
+            let slot = weights.weight_mut(source.trim()).ok_or_else(|| format!(
+                "unknown address source '{}' (expected deployer, accounts, contracts, attacker, zero, precompiles or random)",
+                source.trim()
+            ))?;
+            *slot = weight.trim().parse().map_err(|_| format!("invalid weight '{}' for {}", weight.trim(), source.trim()))?;
+        }
+        weights.validate()?;
+        Ok(weights)
+    }
+}
+
+impl fmt::Display for AddressWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs: Vec<String> = self.entries().iter().map(|(source, weight)| format!("{}={}", source, weight)).collect();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+/// An address argument from the pool, and the source it came from
+pub fn generate<R: Rng + ?Sized>(rng: &mut R, context: &GeneratorContext) -> (String, ArgProvenance) {
+    let accounts = context.known_addresses.get(1..).unwrap_or_default();
+    let contract_count = context.contract_addresses.len() + context.discovered_addresses.len();
+    let available = |source: &str| match source {
+        "deployer" => !context.known_addresses.is_empty(),
+        "accounts" => !accounts.is_empty(),
+        "contracts" => contract_count > 0,
+        "attacker" => context.attacker_address.is_some(),
+        _ => true,
+    };
+    let sources: Vec<(&str, u32)> = context.address_weights.entries().into_iter()
+        .filter(|(source, weight)| *weight > 0 && available(source))
+        .collect();
+    let total: u32 = sources.iter().map(|(_, weight)| weight).sum();
+    let mut draw = rng.gen_range(0..total.max(1));
+    let source = sources.iter()
+        .find(|(_, weight)| {
+            let hit = draw < *weight;
+            draw = draw.saturating_sub(*weight);
+            hit
+        })
+        .map_or("random", |(source, _)| source);
+
+    match source {
+        // Often the owner: calls that pass it probe what privileged paths accept
+        "deployer" => (context.known_addresses[0].clone(), ArgProvenance::KnownAccount),
+        // Funded and possibly privileged accounts
+        "accounts" => (accounts[rng.gen_range(0..accounts.len())].clone(), ArgProvenance::KnownAccount),
+        // A contract of the campaign, so contracts get wired to each other (a vault's token, an oracle)
+        "contracts" => {
+            let index = rng.gen_range(0..contract_count);
+            match context.contract_addresses.get(index) {
+                Some(address) => (address.clone(), ArgProvenance::DeployedContract),
+                None => (context.discovered_addresses[index - context.contract_addresses.len()].clone(), ArgProvenance::DiscoveredContract),
+            }
+        }
+        // The reentrancy attacker, which calls back into the target when it is called or paid
+        "attacker" => (context.attacker_address.clone().unwrap_or_default(), ArgProvenance::AttackerContract),
+        // Burn address, null checks, special logic
+        "zero" => (ZERO_ADDRESS.to_string(), ArgProvenance::Boundary),
+        // Precompiles and system contracts, which accept calls they were never meant for
+        "precompiles" if !context.system_addresses.is_empty() => {
+            let system = &context.system_addresses[rng.gen_range(0..context.system_addresses.len())];
+            (system.address.to_string(), ArgProvenance::SystemAddress)
+        }
+        // Without a chain: Address(1), Address(2) etc
+        "precompiles" => {
+            let precompiles = precompiles::ethereum();
+            (precompiles[rng.gen_range(0..precompiles.len())].address.to_string(), ArgProvenance::LowAddress)
+        }
+        // Arbitrary interactions, access control etc.
+        _ => (format!("0x{:040x}", rng.gen::<u128>() & 0xFFFFFFFFFFFFFFFFFFFFu128), ArgProvenance::Uniform),
+    }
+}
+
+/// Contracts a deployed contract points to through its parameterless address getters (`token()`,
+/// `pair()`, `oracle()`), as `(getter, address)`: accounts with code that are not in `known`
+pub async fn discover(executor: &mut dyn Executor, contract: &ContractInfo, known: &[String]) -> Vec<(String, String)> {
+    let getters = contract.methods.iter().filter(|method| {
+        method.parameters.is_empty()
+            && matches!(method.mutability, StateMutability::View | StateMutability::Pure)
+            && matches!(method.returns.as_slice(), [output] if output.param_type == SolidityType::Address)
+    });
+    let mut discovered: Vec<(String, String)> = Vec::new();
+    for getter in getters {
+        let signature = abi_encoding::method_signature(getter);
+        let Ok(output) = executor.static_call(&contract.name, &calculate_selector(&signature)).await else {
+            continue;
+        };
+        let Some(word) = output.get(..32).map(U256::from_big_endian) else {
+            continue;
+        };
+        if word.is_zero() || word.bits() > 160 {
+            continue;
+        }
+        let address = format!("0x{:040x}", word);
+        let seen = known.iter().chain(discovered.iter().map(|(_, address)| address))
+            .any(|known| known.eq_ignore_ascii_case(&address));
+        if seen {
+            continue;
+        }
+        match executor.code_at(&address).await {
+            Ok(code) if !code.is_empty() => discovered.push((signature, address)),
+            Ok(_) => {}
+            Err(e) => debug!("Could not read the code at {} ({} of {}): {:#}", address, signature, contract.name, e),
+        }
+    }
+    discovered
+}
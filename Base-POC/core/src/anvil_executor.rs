@@ -0,0 +1,1552 @@
+use crate::config::AccountOptions;
+use crate::coverage::{self, BranchEdge};
+use crate::events::EventLog;
+use crate::keys;
+use crate::multicall;
+use crate::nonce_pool::NoncePool;
+use crate::oracle::{decode_revert, CustomErrors};
+use crate::reentrancy::{self, CallFrame};
+use crate::types::{BlockContext, Cheat, TxFees};
+use crate::wraparound::{self, WrappedOperation};
+use anyhow::{Context, Result};
+use ethers::signers::LocalWallet;
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use futures::{SinkExt, StreamExt};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
+
+pub struct AnvilForkExecutor {
+    rpc_url: String,
+    client: reqwest::Client,
+    deployed_contracts: HashMap<String, String>,
+    accounts: Vec<String>,
+    current_sender: String,
+    /// Account that deploys every contract, when a key has the deployer role
+    deployer: Option<String>,
+    /// Keys of the accounts whose transactions are signed locally, by lowercase address
+    wallets: HashMap<String, LocalWallet>,
+    /// Chain id signed transactions are bound to (read once, when there are keys)
+    chain_id: Option<u64>,
+    nonces: NoncePool,
+    /// Incremented on every `newHeads` notification when the endpoint supports subscriptions
+    new_heads: Option<watch::Receiver<u64>>,
+    /// Replace stuck pending transactions with higher-priced no-ops instead of only resyncing nonces
+    repair_nonce_gaps: bool,
+    /// How long a transaction may take to be mined before waiting for its receipt fails
+    receipt_timeout: Duration,
+    /// Explicit EIP-1559 fees for every transaction; the node picks fees when `None`
+    fees: Option<TxFees>,
+    /// Hash of the transaction sent by the latest `call_method`, traced for coverage
+    last_tx_hash: Option<String>,
+    /// Logs of the latest `call_method` transaction, from its receipt
+    last_logs: Vec<EventLog>,
+    /// Multicall3 address batched calls are aggregated through, when enabled
+    multicall: Option<String>,
+    /// Gas limit of calls; `DEFAULT_GAS_LIMIT` when `None`
+    gas_limit: Option<u64>,
+    /// Wei sent with calls
+    value: U256,
+    /// Next nonce of every account when each live snapshot was taken, keyed by snapshot number,
+    /// so a revert can rewind the local nonces without asking the node
+    snapshot_nonces: BTreeMap<u64, Vec<(String, u64)>>,
+    /// Custom errors revert data is decoded with
+    custom_errors: CustomErrors,
+    /// Coinbase, base fee and chain id before the first `set_block_context`; these are node
+    /// settings that `evm_revert` leaves alone
+    original_block_context: Option<BlockContext>,
+    /// Chain id set by `set_block_context`, which signed transactions are bound to until reset
+    block_chain_id: Option<u64>,
+}
+
+/// Number of an `evm_snapshot` id (a hex quantity)
+fn snapshot_number(snapshot_id: &str) -> Option<u64> {
+    u64::from_str_radix(snapshot_id.trim_start_matches("0x"), 16).ok()
+}
+
+/// Gas limit of deployments and calls (should be enough for most contracts)
+pub const DEFAULT_GAS_LIMIT: u64 = 0x1000000;
+
+/// How long a transaction may take to be mined unless `set_receipt_timeout` says otherwise
+pub const DEFAULT_RECEIPT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Receipt polling interval without a `newHeads` subscription
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a `newHeads` subscription may stay quiet before the receipt is checked anyway
+const HEADS_QUIET_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Name the Multicall3 aggregator is registered under in `deployed_contracts` (not a valid Solidity identifier)
+const MULTICALL_CONTRACT: &str = "<multicall3>";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    method: String,
+    params: serde_json::Value,
+    id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonRpcResponse {
+    jsonrpc: String,
+    result: Option<serde_json::Value>,
+    error: Option<JsonRpcError>,
+    id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonRpcError {
+    code: i32,
+    message: String,
+    /// Revert data of a reverted `eth_call`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+}
+
+/// Error code of an EVM revert (`execution reverted`) in geth and Anvil
+const EXECUTION_REVERTED: i32 = 3;
+/// Error code of a failed EVM execution (revert, invalid opcode, out of gas) in Parity-style nodes
+const VM_EXECUTION_ERROR: i32 = -32015;
+
+impl JsonRpcError {
+    /// The error's revert data: a hex string on Anvil, `{"data": "0x…"}` on some nodes
+    fn revert_data(&self) -> Option<Vec<u8>> {
+        self.data.as_ref()
+            .and_then(|data| data.as_str().or_else(|| data.get("data")?.as_str()))
+            .and_then(|data| hex::decode(data.trim_start_matches("0x")).ok())
+    }
+
+    /// Whether the node refused the request because the EVM reverted, as opposed to a transport,
+    /// nonce or node problem whose message may still mention a revert
+    fn is_revert(&self) -> bool {
+        self.code == EXECUTION_REVERTED || self.code == VM_EXECUTION_ERROR || self.revert_data().is_some()
+    }
+}
+
+impl std::fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RPC error: {} (code: {})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for JsonRpcError {}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TransactionReceipt {
+    #[serde(rename = "contractAddress")]
+    contract_address: Option<String>,
+    status: Option<String>,
+    #[serde(rename = "gasUsed")]
+    gas_used: Option<String>,
+    #[serde(default)]
+    logs: Vec<EventLog>,
+}
+
+/// Upstream URL and block of a forked node from its `anvil_nodeInfo`; both `None` when it is not a fork
+fn fork_config(info: &serde_json::Value) -> (Option<String>, Option<u64>) {
+    let fork = info.get("forkConfig");
+    let url = fork.and_then(|fork| fork.get("forkUrl")).and_then(|url| url.as_str()).map(str::to_string);
+    let block = fork.and_then(|fork| fork.get("forkBlockNumber")).and_then(|block| {
+        block.as_u64()
+            .or_else(|| block.as_str().and_then(|hex_str| u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok()))
+    });
+    (url, block)
+}
+
+/// Build the HTTP client used for JSON-RPC. One client should be shared by every executor so
+/// connections to the node are pooled and kept alive instead of re-handshaking per fuzzer.
+pub fn build_http_client(request_timeout: Duration) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .pool_max_idle_per_host(32)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(30))
+        .tcp_nodelay(true)
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(request_timeout)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+impl AnvilForkExecutor {
+    pub async fn new(rpc_url: &str) -> Result<Self> {
+        let client = build_http_client(Duration::from_secs(30))?;
+        Self::with_client(rpc_url, client).await
+    }
+    
+    /// Connect using an existing (shared) HTTP client
+    pub async fn with_client(rpc_url: &str, client: reqwest::Client) -> Result<Self> {
+        Self::with_accounts(rpc_url, client, &AccountOptions::default(), None).await
+    }
+
+    /// Connect using an existing HTTP client and a custom sender account set. A forked node is
+    /// first reset to `fork_block_number` of its upstream when it forks another block.
+    pub async fn with_accounts(rpc_url: &str, client: reqwest::Client, account_options: &AccountOptions, fork_block_number: Option<u64>) -> Result<Self> {
+        debug!("Connecting to Anvil at: {}", rpc_url);
+        if let Some(block) = fork_block_number {
+            Self::pin_fork_block(&client, rpc_url, block).await?;
+        }
+        
+        let accounts = Self::resolve_accounts(&client, rpc_url, account_options).await?;
+        
+        if accounts.is_empty() {
+            return Err(anyhow::anyhow!("No accounts found from Anvil"));
+        }
+        
+        debug!("Found {} accounts from Anvil", accounts.len());
+
+        let deployer = account_options.deployer_key().map(|key| key.address());
+        let wallets: HashMap<String, LocalWallet> = account_options.keys.iter()
+            .map(|key| (key.address(), key.wallet.clone()))
+            .collect();
+        let chain_id = if wallets.is_empty() {
+            None
+        } else {
+            let chain_id = Self::rpc_call(&client, rpc_url, "eth_chainId", json!([])).await?;
+            Some(chain_id.as_str()
+                .and_then(|hex_str| u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+                .with_context(|| format!("Invalid eth_chainId response {}", chain_id))?)
+        };
+        
+        // Initialize nonces for each account
+        let mut nonces = NoncePool::default();
+        for account in accounts.iter().chain(&deployer) {
+            let nonce = Self::get_transaction_count(&client, rpc_url, account).await
+                .unwrap_or(0);
+            nonces.add_sender(account, nonce);
+        }
+        
+        let ws_url = websocket_url(rpc_url);
+        let new_heads = match ws_url {
+            Some(ref url) => match subscribe_new_heads(url).await {
+                Ok(rx) => {
+                    debug!("Subscribed to newHeads at {}", url);
+                    Some(rx)
+                }
+                Err(e) => {
+                    debug!("newHeads subscription unavailable ({}), polling for receipts", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        
+        Ok(Self {
+            rpc_url: rpc_url.to_string(),
+            client,
+            deployed_contracts: HashMap::new(),
+            accounts: accounts.clone(),
+            current_sender: accounts[0].clone(),
+            deployer,
+            wallets,
+            chain_id,
+            nonces,
+            new_heads,
+            repair_nonce_gaps: false,
+            receipt_timeout: DEFAULT_RECEIPT_TIMEOUT,
+            fees: None,
+            last_tx_hash: None,
+            last_logs: Vec::new(),
+            multicall: None,
+            gas_limit: None,
+            value: U256::zero(),
+            snapshot_nonces: BTreeMap::new(),
+            custom_errors: CustomErrors::default(),
+            original_block_context: None,
+            block_chain_id: None,
+        })
+    }
+    
+    /// Send a JSON-RPC request and return the raw response, errors included
+    async fn rpc_request(
+        client: &reqwest::Client,
+        url: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<JsonRpcResponse> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+            id: 1,
+        };
+        
+        let response = client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send RPC request")?;
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse RPC response")
+    }
+
+    pub(crate) async fn rpc_call(
+        client: &reqwest::Client,
+        url: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let rpc_response = Self::rpc_request(client, url, method, params).await?;
+        
+        if let Some(error) = rpc_response.error {
+            // Check if this is a method not supported error (common with public RPCs)
+            if (error.code == -32601 || error.message.contains("not supported") || error.message.contains("method not found"))
+                && method == "eth_sendTransaction"
+            {
+                return Err(anyhow::anyhow!(
+                    "RPC error: {} (code: {})\n\n\
+                    ⚠️  This RPC endpoint does not support eth_sendTransaction.\n\
+                    Public RPCs are read-only and cannot send transactions.\n\n\
+                    Solution: drop --no-spawn-anvil so a local fork of it is started for you,\n\
+                    or start Anvil yourself with --fork-url pointing to your RPC:\n\
+                    \t anvil --fork-url {}\n\
+                    Then connect to Anvil at http://localhost:8545",
+                    error.message, error.code, url
+                ));
+            }
+            return Err(error.into());
+        }
+        
+        if rpc_response.result.is_none() {
+            warn!("RPC call to {} returned no result. Full response: {:?}", method, rpc_response);
+            if method == "eth_sendTransaction" {
+                warn!("⚠️  eth_sendTransaction returned no result. This usually means:\n\
+                      - The RPC endpoint doesn't support sending transactions (public RPCs are read-only)\n\
+                      - You need to use Anvil: 'anvil --fork-url <RPC_URL>' then connect to http://localhost:8545");
+            }
+        }
+        
+        rpc_response.result
+            .context("No result in RPC response")
+    }
+    
+    /// Get transaction count (nonce) for an address
+    async fn get_transaction_count(
+        client: &reqwest::Client,
+        url: &str,
+        address: &str,
+    ) -> Result<u64> {
+        Self::get_transaction_count_at(client, url, address, "pending").await
+    }
+
+    /// Get transaction count for an address at a block tag ("latest" counts mined transactions only)
+    async fn get_transaction_count_at(
+        client: &reqwest::Client,
+        url: &str,
+        address: &str,
+        block: &str,
+    ) -> Result<u64> {
+        let params = json!([address, block]);
+        let result = Self::rpc_call(client, url, "eth_getTransactionCount", params).await?;
+        
+        if let Some(hex_str) = result.as_str() {
+            let hex_clean = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+            u64::from_str_radix(hex_clean, 16)
+                .context("Failed to parse nonce")
+        } else {
+            Err(anyhow::anyhow!("Invalid nonce format"))
+        }
+    }
+    
+    /// Get balance for an address
+    async fn get_balance(
+        client: &reqwest::Client,
+        url: &str,
+        address: &str,
+    ) -> Result<()> {
+        let params = json!([address, "latest"]);
+        Self::rpc_call(client, url, "eth_getBalance", params).await?;
+        Ok(())
+    }
+    
+    /// Determine the sender accounts: the sender keys, derived from a mnemonic, or whatever
+    /// `eth_accounts` reports, limited to `count`
+    async fn resolve_accounts(client: &reqwest::Client, url: &str, options: &AccountOptions) -> Result<Vec<String>> {
+        let mut accounts = match &options.mnemonic {
+            _ if !options.keys.is_empty() => options.sender_keys().map(|key| key.address()).collect(),
+            Some(mnemonic) => {
+                let count = options.count.unwrap_or(DEFAULT_MNEMONIC_ACCOUNTS);
+                let derived = derive_mnemonic_accounts(mnemonic, count)?;
+                Self::adopt_accounts(client, url, &derived).await?;
+                derived
+            }
+            None => match Self::get_node_accounts(client, url).await {
+                Ok(node_accounts) if !node_accounts.is_empty() => node_accounts,
+                Ok(_) => {
+                    warn!("eth_accounts returned no accounts, falling back to the default Anvil accounts");
+                    Self::get_anvil_accounts(client, url).await?
+                }
+                Err(e) => {
+                    warn!("eth_accounts failed ({}), falling back to the default Anvil accounts", e);
+                    Self::get_anvil_accounts(client, url).await?
+                }
+            },
+        };
+
+        if let Some(count) = options.count {
+            if count == 0 {
+                return Err(anyhow::anyhow!("--accounts must be at least 1"));
+            }
+            if accounts.len() < count {
+                warn!("Requested {} accounts but only {} are available", count, accounts.len());
+            }
+            accounts.truncate(count);
+        }
+
+        Ok(accounts)
+    }
+
+    /// Unlocked accounts reported by the node
+    async fn get_node_accounts(client: &reqwest::Client, url: &str) -> Result<Vec<String>> {
+        let result = Self::rpc_call(client, url, "eth_accounts", json!([])).await?;
+        serde_json::from_value(result).context("Invalid eth_accounts response")
+    }
+
+    /// Make mnemonic-derived accounts usable with eth_sendTransaction: accounts the node does not
+    /// already manage are impersonated and funded
+    async fn adopt_accounts(client: &reqwest::Client, url: &str, accounts: &[String]) -> Result<()> {
+        let node_accounts = Self::get_node_accounts(client, url).await.unwrap_or_default();
+
+        for account in accounts {
+            if node_accounts.iter().any(|known| known.eq_ignore_ascii_case(account)) {
+                continue;
+            }
+            Self::rpc_call(client, url, "anvil_impersonateAccount", json!([account])).await
+                .with_context(|| format!("Could not impersonate mnemonic account {} (requires Anvil)", account))?;
+            Self::rpc_call(client, url, "anvil_setBalance", json!([account, "0x3635c9adc5dea00000"])).await
+                .with_context(|| format!("Could not fund mnemonic account {}", account))?;
+            debug!("Impersonating mnemonic account {}", account);
+        }
+
+        Ok(())
+    }
+    
+    async fn get_anvil_accounts(client: &reqwest::Client, url: &str) -> Result<Vec<String>> {
+        let anvil_accounts = vec![
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            "0x90F79bf6EB2c4f870365E785982E1f101E93b906",
+            "0x15d34AAf54267DB7D7c367839AAf71A00a2C6A65",
+        ];
+        
+        let mut accounts = Vec::new();
+        for addr_str in &anvil_accounts {
+            match Self::get_balance(client, url, addr_str).await {
+                Ok(_) => accounts.push(addr_str.to_string()),
+                Err(_) => {
+                    warn!("Account {} not found, skipping", addr_str);
+                }
+            }
+        }
+        
+        if accounts.is_empty() {
+            if let Some(first) = anvil_accounts.first() {
+                accounts.push(first.to_string());
+            }
+        }
+        
+        Ok(accounts)
+    }
+    
+    /// Deploy a contract to the Anvil fork
+    pub async fn deploy_contract(
+        &mut self,
+        contract_name: &str,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+    ) -> Result<String> {
+        debug!("Deploying contract: {} to Anvil fork", contract_name);
+        
+        // Combine bytecode with constructor args if provided
+        let mut deployment_bytecode = bytecode.to_vec();
+        if let Some(args) = constructor_args {
+            deployment_bytecode.extend_from_slice(args);
+        }
+        
+        let bytecode_hex = format!("0x{}", hex::encode(&deployment_bytecode));
+        
+        // Reserve the deployer's next nonce
+        let sender = self.deployer().to_string();
+        let nonce = self.nonces.reserve(&sender)
+            .with_context(|| format!("{} already has {} transactions pending", sender, self.nonces.in_flight(&sender)))?;
+        let nonce_hex = format!("0x{:x}", nonce);
+        
+        // Create deployment transaction
+        let mut tx_params = json!({
+            "from": sender,
+            "data": bytecode_hex,
+            "value": "0x0",
+            "nonce": nonce_hex,
+            "gas": format!("0x{:x}", DEFAULT_GAS_LIMIT),
+        });
+        self.apply_fees(&mut tx_params);
+        
+        // Send transaction
+        let tx_hash = match self.send_transaction(tx_params).await {
+            Ok(tx_hash) => tx_hash,
+            Err(e) => {
+                self.nonces.release(&sender, nonce);
+                return Err(e);
+            }
+        };
+        
+        let tx_hash_str = tx_hash.as_str()
+            .context("Invalid transaction hash format")?;
+        
+        // Wait for transaction receipt
+        let receipt = match self.wait_for_transaction(tx_hash_str).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                self.nonces.release(&sender, nonce);
+                self.recover_nonce_gap(&sender).await;
+                return Err(e);
+            }
+        };
+        self.nonces.confirm(&sender, nonce);
+        
+        // Check if transaction succeeded
+        let status = receipt.status.as_deref().unwrap_or("0x0");
+        let success = status == "0x1" || status == "1";
+        
+        if !success {
+            // Try to get revert reason by simulating the deployment
+            let revert_reason = self.get_deployment_revert_reason(&bytecode_hex).await
+                .unwrap_or_else(|_| "Unknown revert reason".to_string());
+            
+            return Err(anyhow::anyhow!(
+                "Contract deployment failed: Transaction reverted (status: {})\nRevert reason: {}",
+                status, revert_reason
+            ));
+        }
+        
+        // Extract contract address from receipt
+        let contract_address = receipt.contract_address
+            .context("No contract address in receipt - deployment may have failed")?;
+        
+        debug!("Contract {} deployed at: {}", contract_name, contract_address);
+        
+        // Store deployed contract info
+        self.deployed_contracts.insert(
+            contract_name.to_string(),
+            contract_address.clone(),
+        );
+        
+        Ok(contract_address)
+    }
+    
+    /// `eth_estimateGas` of a deployment from the deployer
+    pub async fn estimate_deployment_gas(&self, bytecode: &[u8], constructor_args: Option<&[u8]>) -> Result<u64> {
+        let mut deployment_bytecode = bytecode.to_vec();
+        if let Some(args) = constructor_args {
+            deployment_bytecode.extend_from_slice(args);
+        }
+        let params = json!([{
+            "from": self.deployer(),
+            "data": format!("0x{}", hex::encode(&deployment_bytecode)),
+            "value": "0x0",
+        }]);
+        let gas = Self::rpc_call(&self.client, &self.rpc_url, "eth_estimateGas", params).await?;
+        let gas = gas.as_str().context("eth_estimateGas returned no data")?;
+        u64::from_str_radix(gas.trim_start_matches("0x"), 16).context("eth_estimateGas returned invalid hex")
+    }
+
+    /// Fuzz the contract at `address` under `contract_name` without deploying anything
+    pub async fn attach_contract(&mut self, contract_name: &str, address: &str) -> Result<Vec<u8>> {
+        let code = self.code_at(address).await?;
+        if code.is_empty() {
+            return Err(anyhow::anyhow!("No contract at {} on {} (is it a fork of the chain the contract lives on?)", address, self.rpc_url));
+        }
+        self.deployed_contracts.insert(contract_name.to_string(), address.to_string());
+        debug!("Contract {} attached at: {}", contract_name, address);
+        Ok(code)
+    }
+
+    /// Runtime code of any account, empty for accounts without code
+    pub async fn code_at(&self, address: &str) -> Result<Vec<u8>> {
+        let code = Self::rpc_call(&self.client, &self.rpc_url, "eth_getCode", json!([address, "latest"])).await?;
+        Ok(code.as_str()
+            .and_then(|code| hex::decode(code.trim_start_matches("0x")).ok())
+            .unwrap_or_default())
+    }
+
+    /// Fetch a transaction receipt, returning `None` while the transaction is not mined yet
+    async fn fetch_receipt(&self, tx_hash: &str) -> Result<Option<TransactionReceipt>> {
+        let params = json!([tx_hash]);
+        
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "eth_getTransactionReceipt".to_string(),
+            params,
+            id: 1,
+        };
+        
+        let response = self.client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send RPC request")?;
+        
+        let rpc_response: JsonRpcResponse = response
+            .json()
+            .await
+            .context("Failed to parse RPC response")?;
+        
+        if let Some(error) = rpc_response.error {
+            return Err(anyhow::anyhow!("RPC error: {} (code: {})", error.message, error.code));
+        }
+        
+        // null is a valid response (transaction not mined yet)
+        match rpc_response.result {
+            Some(result) if !result.is_null() => {
+                let receipt: TransactionReceipt = serde_json::from_value(result)
+                    .context("Failed to parse transaction receipt")?;
+                Ok(Some(receipt))
+            }
+            _ => Ok(None),
+        }
+    }
+    
+    /// Wait for a transaction to be mined, for at most the receipt timeout. With a `newHeads`
+    /// subscription the receipt is re-checked when a block arrives, or after a quiet second in case a
+    /// notification was missed; without one, or once it closes, the receipt is polled every 100ms.
+    async fn wait_for_transaction(&self, tx_hash: &str) -> Result<TransactionReceipt> {
+        let deadline = tokio::time::Instant::now() + self.receipt_timeout;
+        let mut heads = self.new_heads.clone();
+        loop {
+            // Mark the current head as seen before checking, so a block mined in between still wakes us
+            if let Some(rx) = heads.as_mut() {
+                rx.borrow_and_update();
+            }
+            
+            if let Some(receipt) = self.fetch_receipt(tx_hash).await? {
+                return Ok(receipt);
+            }
+            
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                return Err(anyhow::anyhow!("Transaction {} not mined within {} seconds", tx_hash, self.receipt_timeout.as_secs()));
+            }
+            
+            match heads.as_mut() {
+                Some(rx) => {
+                    if let Ok(Err(_)) = tokio::time::timeout(HEADS_QUIET_INTERVAL.min(remaining), rx.changed()).await {
+                        debug!("newHeads subscription closed, polling for the receipt of {}", tx_hash);
+                        heads = None;
+                    }
+                }
+                None => tokio::time::sleep(RECEIPT_POLL_INTERVAL.min(remaining)).await,
+            }
+        }
+    }
+    
+    /// Send a transaction invoking `method_signature` on a deployed contract.
+    /// Reverts are reported through `MethodExecutionResult`; an `Err` means the call
+    /// itself could not be carried out (RPC, nonce or receipt problems).
+    pub async fn call_method(
+        &mut self,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<MethodExecutionResult> {
+        let sender = self.current_sender.clone();
+        let nonce = self.nonces.reserve(&sender)
+            .with_context(|| format!("{} already has {} transactions pending", sender, self.nonces.in_flight(&sender)))?;
+        match self.submit_call(&sender, nonce, contract_name, method_signature, encoded_args).await? {
+            Submission::Pending(pending) => self.await_call(pending).await,
+            Submission::Rejected(result) => Ok(result),
+        }
+    }
+
+    /// Send a call from `sender` with a nonce reserved from the pool, without waiting for its receipt
+    async fn submit_call(
+        &mut self,
+        sender: &str,
+        nonce: u64,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<Submission> {
+        let Some(contract_address) = self.deployed_contracts.get(contract_name).cloned() else {
+            self.nonces.release(sender, nonce);
+            return Err(anyhow::anyhow!("Contract {} not deployed", contract_name));
+        };
+        
+        debug!(
+            "Calling method {} on contract {} at {} (from {}, nonce {})",
+            method_signature, contract_name, contract_address, sender, nonce
+        );
+        
+        // Calculate method selector (first 4 bytes of keccak256 hash of signature)
+        let selector = calculate_selector(method_signature);
+        
+        // Combine selector with encoded args
+        let mut call_data = selector.to_vec();
+        call_data.extend_from_slice(encoded_args);
+        
+        let call_data_hex = format!("0x{}", hex::encode(&call_data));
+        
+        // Create call transaction
+        let mut tx_params = json!({
+            "from": sender,
+            "to": contract_address,
+            "data": call_data_hex,
+            "value": format!("0x{:x}", self.value),
+            "nonce": format!("0x{:x}", nonce),
+            "gas": format!("0x{:x}", self.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT)),
+        });
+        self.apply_fees(&mut tx_params);
+        
+        self.last_tx_hash = None;
+        self.last_logs.clear();
+        
+        // Execute the call (send transaction for state changes)
+        match self.send_transaction(tx_params).await {
+            Ok(tx_hash_value) => {
+                let Some(tx_hash) = tx_hash_value.as_str() else {
+                    self.recover_nonce_gap(sender).await;
+                    return Err(anyhow::anyhow!("Invalid transaction hash"));
+                };
+                self.last_tx_hash = Some(tx_hash.to_string());
+                Ok(Submission::Pending(PendingCall {
+                    sender: sender.to_string(),
+                    nonce,
+                    tx_hash: tx_hash.to_string(),
+                    contract_address,
+                    call_data_hex,
+                }))
+            }
+            Err(e) if is_revert_error(&e) => {
+                // Some nodes reject reverting transactions at submission time instead of mining them
+                if !self.nonces.release(sender, nonce) {
+                    self.recover_nonce_gap(sender).await;
+                }
+                let reason = e.to_string().replace(['\n', '\r'], " ").trim().to_string();
+                Ok(Submission::Rejected(MethodExecutionResult {
+                    success: false,
+                    gas_used: 0,
+                    return_data: vec![],
+                    error: Some(reason),
+                }))
+            }
+            Err(e) => {
+                if !self.nonces.release(sender, nonce) || is_nonce_error(&e) {
+                    self.recover_nonce_gap(sender).await;
+                }
+                Err(e.context("Transaction submission failed"))
+            }
+        }
+    }
+
+    /// Wait for the receipt of a submitted call and classify its outcome
+    async fn await_call(&mut self, pending: PendingCall) -> Result<MethodExecutionResult> {
+        let receipt = match self.wait_for_transaction(&pending.tx_hash).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                // A missing receipt is an infrastructure problem, not a contract failure.
+                // The transaction may be stuck in the pool and block every later nonce.
+                self.nonces.release(&pending.sender, pending.nonce);
+                self.recover_nonce_gap(&pending.sender).await;
+                return Err(e.context("Failed to get receipt"));
+            }
+        };
+        self.nonces.confirm(&pending.sender, pending.nonce);
+        
+        let status = receipt.status.as_deref().unwrap_or("0x0");
+        let success = status == "0x1" || status == "1";
+        
+        let gas_used = receipt.gas_used
+            .and_then(|g| u64::from_str_radix(g.strip_prefix("0x").unwrap_or(&g), 16).ok())
+            .unwrap_or(0);
+        
+        if success {
+            self.last_logs = receipt.logs;
+            return Ok(MethodExecutionResult {
+                success: true,
+                gas_used,
+                return_data: vec![],
+                error: None,
+            });
+        }
+
+        // Try to get revert reason using eth_call to simulate the transaction
+        let revert_reason = self.get_revert_reason(
+            &pending.contract_address,
+            &pending.call_data_hex,
+        ).await.unwrap_or_else(|_| "Unknown revert reason".to_string());
+        
+        // Extract just the revert reason, removing redundant prefixes and newlines
+        let clean_reason = if revert_reason.contains("execution reverted:") {
+            revert_reason
+                .split("execution reverted:")
+                .nth(1)
+                .map(|s| s.trim().replace(['\n', '\r'], " ").trim().to_string())
+                .unwrap_or_else(|| revert_reason.replace(['\n', '\r'], " ").trim().to_string())
+        } else if revert_reason.contains("RPC error:") {
+            revert_reason
+                .split("RPC error:")
+                .nth(1)
+                .map(|s| s.trim().replace(['\n', '\r'], " ").trim().to_string())
+                .unwrap_or_else(|| revert_reason.replace(['\n', '\r'], " ").trim().to_string())
+        } else {
+            revert_reason.replace(['\n', '\r'], " ").trim().to_string()
+        };
+        
+        Ok(MethodExecutionResult {
+            success: false,
+            gas_used,
+            return_data: vec![],
+            error: Some(clean_reason),
+        })
+    }
+
+    /// Send subsequent transactions as type-2 with these fees (`None` lets the node choose)
+    pub fn set_fees(&mut self, fees: Option<TxFees>) {
+        self.fees = fees;
+    }
+
+    /// Gas limit of subsequent calls (`None` restores the 16M default); deployments always use the default
+    pub fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        self.gas_limit = gas_limit;
+    }
+
+    /// Wei sent with subsequent calls; deployments always send none
+    pub fn set_value(&mut self, value: U256) {
+        self.value = value;
+    }
+
+    /// Send a transaction object: signed locally with `eth_sendRawTransaction` when its sender has a
+    /// key, through the node's `eth_sendTransaction` otherwise. Signed transactions without fees
+    /// pay the node's `eth_gasPrice`.
+    async fn send_transaction(&self, mut tx_params: serde_json::Value) -> Result<serde_json::Value> {
+        let from = tx_params["from"].as_str().unwrap_or_default().to_ascii_lowercase();
+        let (Some(wallet), Some(chain_id)) = (self.wallets.get(&from), self.block_chain_id.or(self.chain_id)) else {
+            return Self::rpc_call(&self.client, &self.rpc_url, "eth_sendTransaction", json!([tx_params])).await;
+        };
+        if tx_params.get("maxFeePerGas").is_none() && tx_params.get("gasPrice").is_none() {
+            tx_params["gasPrice"] = Self::rpc_call(&self.client, &self.rpc_url, "eth_gasPrice", json!([])).await?;
+        }
+        let raw = keys::sign_transaction(wallet, &tx_params, chain_id)?;
+        Self::rpc_call(&self.client, &self.rpc_url, "eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))])).await
+    }
+
+    /// Add the configured EIP-1559 fields to a transaction object
+    fn apply_fees(&self, tx_params: &mut serde_json::Value) {
+        if let Some(fees) = self.fees {
+            tx_params["type"] = json!("0x2");
+            tx_params["maxFeePerGas"] = json!(format!("0x{:x}", fees.max_fee_per_gas));
+            tx_params["maxPriorityFeePerGas"] = json!(format!("0x{:x}", fees.max_priority_fee_per_gas));
+        }
+    }
+
+    /// Apply block environment overrides through Anvil cheats. The base fee applies from the next block on.
+    pub async fn set_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        if self.original_block_context.is_none() {
+            let block = Self::rpc_call(&self.client, &self.rpc_url, "eth_getBlockByNumber", json!(["latest", false])).await?;
+            self.original_block_context = Some(BlockContext {
+                coinbase: block.get("miner").and_then(|miner| miner.as_str()).map(str::to_string),
+                base_fee_per_gas: self.base_fee_per_gas().await?,
+                chain_id: Some(self.chain_id().await?),
+            });
+        }
+        self.apply_block_context(block_context).await
+    }
+
+    /// Restore the coinbase, base fee and chain id the node had before the first `set_block_context`
+    pub async fn reset_block_context(&mut self) -> Result<()> {
+        let Some(original) = self.original_block_context.take() else {
+            return Ok(());
+        };
+        self.apply_block_context(&original).await?;
+        self.block_chain_id = None;
+        Ok(())
+    }
+
+    async fn apply_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        if let Some(coinbase) = &block_context.coinbase {
+            Self::rpc_call(&self.client, &self.rpc_url, "anvil_setCoinbase", json!([coinbase])).await
+                .context("Block-context fuzzing requires Anvil (anvil_setCoinbase failed)")?;
+        }
+        if let Some(base_fee) = block_context.base_fee_per_gas {
+            Self::rpc_call(&self.client, &self.rpc_url, "anvil_setNextBlockBaseFeePerGas", json!([format!("0x{:x}", base_fee)])).await
+                .context("Block-context fuzzing requires Anvil (anvil_setNextBlockBaseFeePerGas failed)")?;
+        }
+        if let Some(chain_id) = block_context.chain_id {
+            Self::rpc_call(&self.client, &self.rpc_url, "anvil_setChainId", json!([chain_id])).await
+                .context("Block-context fuzzing requires Anvil (anvil_setChainId failed)")?;
+            self.block_chain_id = Some(chain_id);
+        }
+        Ok(())
+    }
+
+    /// Decode custom errors declared in `abi` in revert reasons from now on
+    pub fn register_errors(&mut self, abi: &ethers::abi::Abi) {
+        self.custom_errors.add(abi);
+    }
+
+    /// Apply a sequence cheat: warping sets the next block's timestamp and mines it, rolling mines
+    /// empty blocks and dealing sets the account's balance
+    pub async fn apply_cheat(&mut self, cheat: &Cheat) -> Result<()> {
+        match cheat {
+            Cheat::Warp { seconds } => {
+                let block = Self::rpc_call(&self.client, &self.rpc_url, "eth_getBlockByNumber", json!(["latest", false])).await?;
+                let timestamp = block.get("timestamp")
+                    .and_then(|timestamp| timestamp.as_str())
+                    .and_then(|hex_str| u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+                    .ok_or_else(|| anyhow::anyhow!("latest block has no timestamp"))?;
+                Self::rpc_call(&self.client, &self.rpc_url, "evm_setNextBlockTimestamp", json!([format!("0x{:x}", timestamp.saturating_add(*seconds))])).await
+                    .context("Warping requires Anvil (evm_setNextBlockTimestamp failed)")?;
+                Self::rpc_call(&self.client, &self.rpc_url, "evm_mine", json!([])).await
+                    .context("Warping requires Anvil (evm_mine failed)")?;
+            }
+            Cheat::Roll { blocks } => {
+                Self::rpc_call(&self.client, &self.rpc_url, "anvil_mine", json!([format!("0x{:x}", blocks)])).await
+                    .context("Rolling requires Anvil (anvil_mine failed)")?;
+            }
+            Cheat::Deal { account, wei } => {
+                Self::rpc_call(&self.client, &self.rpc_url, "anvil_setBalance", json!([account, format!("0x{:x}", wei)])).await
+                    .context("Dealing requires Anvil (anvil_setBalance failed)")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the runtime code of an account
+    pub async fn set_code(&self, address: &str, code: &[u8]) -> Result<()> {
+        Self::rpc_call(&self.client, &self.rpc_url, "anvil_setCode", json!([address, format!("0x{}", hex::encode(code))])).await
+            .context("Setting code requires Anvil (anvil_setCode failed)")?;
+        Ok(())
+    }
+
+    /// Base fee of the latest block, or `None` before London / on chains without EIP-1559
+    pub async fn base_fee_per_gas(&self) -> Result<Option<u128>> {
+        let block = Self::rpc_call(&self.client, &self.rpc_url, "eth_getBlockByNumber", json!(["latest", false])).await?;
+        Ok(block.get("baseFeePerGas")
+            .and_then(|fee| fee.as_str())
+            .and_then(|hex_str| u128::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok()))
+    }
+
+    /// Upstream block the Anvil fork was started from (`anvil_nodeInfo`), `None` when the node is not a fork
+    pub async fn fork_block_number(&self) -> Result<Option<u64>> {
+        let info = Self::rpc_call(&self.client, &self.rpc_url, "anvil_nodeInfo", json!([])).await?;
+        Ok(fork_config(&info).1)
+    }
+
+    /// Reset a forked node to `block` of its upstream unless it forks that block already, before
+    /// any account is funded or nonce read. Nodes that are not forks are left alone.
+    async fn pin_fork_block(client: &reqwest::Client, rpc_url: &str, block: u64) -> Result<()> {
+        let info = Self::rpc_call(client, rpc_url, "anvil_nodeInfo", json!([])).await
+            .with_context(|| format!("--fork-block-number needs an Anvil node at {}", rpc_url))?;
+        let (fork_url, current) = fork_config(&info);
+        let Some(fork_url) = fork_url else {
+            warn!("{} is not a fork, --fork-block-number {} has no effect on it", rpc_url, block);
+            return Ok(());
+        };
+        if current == Some(block) {
+            return Ok(());
+        }
+        Self::rpc_call(client, rpc_url, "anvil_reset", json!([{ "forking": { "jsonRpcUrl": fork_url, "blockNumber": block } }])).await
+            .with_context(|| format!("Could not reset the fork at {} to block {}", rpc_url, block))?;
+        println!("📌 Fork at {} reset to block {} of {}", rpc_url, block, fork_url);
+        Ok(())
+    }
+
+    /// `eth_chainId` of the node
+    pub async fn chain_id(&self) -> Result<u64> {
+        let chain_id = Self::rpc_call(&self.client, &self.rpc_url, "eth_chainId", json!([])).await?;
+        chain_id.as_str()
+            .and_then(|hex_str| u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+            .with_context(|| format!("Invalid eth_chainId response {}", chain_id))
+    }
+
+    /// Replace stuck pending transactions with higher-priced no-op self-transfers when a gap is found
+    pub fn set_nonce_gap_repair(&mut self, enabled: bool) {
+        self.repair_nonce_gaps = enabled;
+    }
+
+    /// How long to wait for a transaction to be mined before giving up on its receipt
+    pub fn set_receipt_timeout(&mut self, timeout: Duration) {
+        self.receipt_timeout = timeout;
+    }
+
+    /// Pending transactions of `account` that have not been mined, as the nonce range `latest..pending`
+    async fn pending_nonce_gap(&self, account: &str) -> Result<Option<std::ops::Range<u64>>> {
+        let latest = Self::get_transaction_count_at(&self.client, &self.rpc_url, account, "latest").await?;
+        let pending = Self::get_transaction_count_at(&self.client, &self.rpc_url, account, "pending").await?;
+        Ok((pending > latest).then_some(latest..pending))
+    }
+
+    /// Best-effort recovery after a send or receipt failure on `account`: detect stuck pending
+    /// transactions, optionally replace them, and resync the local nonce so the rest of the
+    /// campaign does not fail with nonce errors.
+    async fn recover_nonce_gap(&mut self, account: &str) {
+        let account = account.to_string();
+
+        match self.pending_nonce_gap(&account).await {
+            Ok(Some(gap)) => {
+                warn!("Nonce gap on {}: {} pending transaction(s) not mined (nonces {}..{})",
+                    account, gap.end - gap.start, gap.start, gap.end);
+                if self.repair_nonce_gaps {
+                    if let Err(e) = self.replace_stuck_transactions(&account, gap).await {
+                        warn!("Could not replace stuck transactions of {}: {:#}", account, e);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => debug!("Could not check {} for a nonce gap: {}", account, e),
+        }
+
+        match Self::get_transaction_count(&self.client, &self.rpc_url, &account).await {
+            Ok(nonce) => self.nonces.resync(&account, nonce),
+            Err(e) => warn!("Could not resync nonce of {}: {}", account, e),
+        }
+    }
+
+    /// Send a zero-value self-transfer for every stuck nonce at double the current gas price,
+    /// which replaces the original transaction in the node's pool
+    async fn replace_stuck_transactions(&mut self, account: &str, stuck: std::ops::Range<u64>) -> Result<()> {
+        let gas_price = Self::rpc_call(&self.client, &self.rpc_url, "eth_gasPrice", json!([])).await?;
+        let gas_price = gas_price.as_str()
+            .and_then(|hex_str| u128::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+            .unwrap_or(0)
+            .max(1_000_000_000);
+        let replacement_price = format!("0x{:x}", gas_price.saturating_mul(2));
+
+        let mut last_hash = None;
+        for nonce in stuck {
+            let tx_params = json!({
+                "from": account,
+                "to": account,
+                "value": "0x0",
+                "nonce": format!("0x{:x}", nonce),
+                "gas": "0x5208",
+                "gasPrice": replacement_price,
+            });
+            let tx_hash = self.send_transaction(tx_params).await
+                .with_context(|| format!("Replacement for nonce {} rejected", nonce))?;
+            debug!("Replaced stuck nonce {} of {} with no-op {}", nonce, account, tx_hash);
+            last_hash = tx_hash.as_str().map(|hash| hash.to_string());
+        }
+
+        if let Some(hash) = last_hash {
+            self.wait_for_transaction(&hash).await
+                .context("Replacement transactions were not mined")?;
+        }
+        warn!("Replaced stuck transactions of {} with no-ops", account);
+        Ok(())
+    }
+    
+    /// Switch the node into a high-throughput configuration: auto-impersonation, zero base fee,
+    /// automine and an effectively unlimited block gas limit. Realism is traded for executions/second,
+    /// so findings from turbo campaigns should be confirmed on a normally configured fork.
+    pub async fn enable_turbo_mode(&mut self) -> Result<()> {
+        let cheats = [
+            ("anvil_autoImpersonateAccount", json!([true])),
+            ("anvil_setNextBlockBaseFeePerGas", json!(["0x0"])),
+            ("evm_setAutomine", json!([true])),
+            ("evm_setBlockGasLimit", json!(["0xffffffffffff"])),
+        ];
+        
+        for (method, params) in cheats {
+            Self::rpc_call(&self.client, &self.rpc_url, method, params).await
+                .with_context(|| format!("Turbo mode requires Anvil ({} failed)", method))?;
+        }
+        
+        // Keep every sender funded so zero-fee transactions never fail for lack of balance
+        for account in self.accounts.iter().chain(&self.deployer) {
+            Self::rpc_call(&self.client, &self.rpc_url, "anvil_setBalance", json!([account, "0xffffffffffffffffffffffff"])).await
+                .context("Turbo mode requires Anvil (anvil_setBalance failed)")?;
+        }
+        
+        debug!("Turbo mode enabled on {}", self.rpc_url);
+        Ok(())
+    }
+    
+    /// Aggregate batched calls through the Multicall3 contract at `address`, which must already be
+    /// deployed on the fork (it is on every major chain)
+    pub async fn enable_multicall(&mut self, address: &str) -> Result<()> {
+        let code = Self::rpc_call(&self.client, &self.rpc_url, "eth_getCode", json!([address, "latest"])).await?;
+        if code.as_str().map(|code| code.trim_start_matches("0x").is_empty()).unwrap_or(true) {
+            return Err(anyhow::anyhow!("No contract at Multicall3 address {} on {}", address, self.rpc_url));
+        }
+        self.deployed_contracts.insert(MULTICALL_CONTRACT.to_string(), address.to_string());
+        self.multicall = Some(address.to_string());
+        debug!("Batching calls through Multicall3 at {}", address);
+        Ok(())
+    }
+
+    pub fn multicall_enabled(&self) -> bool {
+        self.multicall.is_some()
+    }
+
+    /// Send calls to a deployed contract as one `aggregate3` transaction. Receipts carry no return
+    /// data, so the batch is simulated with `eth_call` first to learn the outcome of every sub-call.
+    pub async fn call_methods_multicall(
+        &mut self,
+        contract_name: &str,
+        calls: &[(String, Vec<u8>)],
+    ) -> Result<Vec<MethodExecutionResult>> {
+        let target = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow::anyhow!("Contract {} not deployed", contract_name))?
+            .to_string();
+        let multicall_address = self.multicall.clone().context("Multicall is not enabled")?;
+        let calldatas: Vec<Vec<u8>> = calls.iter()
+            .map(|(method_signature, encoded_args)| [calculate_selector(method_signature).as_slice(), encoded_args].concat())
+            .collect();
+        let aggregate_args = multicall::encode_aggregate3(&target, &calldatas)?;
+
+        let mut aggregate_call = calculate_selector(multicall::AGGREGATE3_SIGNATURE).to_vec();
+        aggregate_call.extend_from_slice(&aggregate_args);
+        let params = json!([{
+            "to": multicall_address,
+            "data": format!("0x{}", hex::encode(&aggregate_call)),
+            "from": self.current_sender,
+        }, "latest"]);
+        let output = Self::rpc_call(&self.client, &self.rpc_url, "eth_call", params).await
+            .context("Simulating the multicall batch failed")?;
+        let output = hex::decode(output.as_str().context("eth_call returned no data")?.trim_start_matches("0x"))
+            .context("eth_call returned invalid hex")?;
+        let outcomes = multicall::decode_aggregate3(&output)?;
+        if outcomes.len() != calls.len() {
+            return Err(anyhow::anyhow!("aggregate3 returned {} results for {} calls", outcomes.len(), calls.len()));
+        }
+
+        let batch = self.call_method(MULTICALL_CONTRACT, multicall::AGGREGATE3_SIGNATURE, &aggregate_args).await?;
+        if !batch.success {
+            return Err(anyhow::anyhow!("Multicall batch reverted: {}", batch.error.unwrap_or_default()));
+        }
+
+        let gas_share = batch.gas_used / calls.len().max(1) as u64;
+        Ok(outcomes.into_iter()
+            .map(|(success, return_data)| MethodExecutionResult {
+                success,
+                gas_used: gas_share,
+                error: (!success).then(|| decode_revert(&return_data, &self.custom_errors)),
+                return_data,
+            })
+            .collect())
+    }
+
+    /// Take an EVM snapshot of the current fork state, returning its id
+    pub async fn snapshot(&mut self) -> Result<String> {
+        let result = Self::rpc_call(&self.client, &self.rpc_url, "evm_snapshot", json!([])).await?;
+        let id = result.as_str()
+            .map(|id| id.to_string())
+            .context("Invalid snapshot id format")?;
+        // Local nonces only match the node's while nothing is pending
+        let settled = self.nonce_accounts().all(|account| self.nonces.in_flight(account) == 0);
+        if let (Some(number), true) = (snapshot_number(&id), settled) {
+            let nonces = self.nonce_accounts()
+                .map(|account| (account.clone(), self.nonces.next_nonce(account)))
+                .collect();
+            self.snapshot_nonces.insert(number, nonces);
+        }
+        Ok(id)
+    }
+    
+    /// Restore the fork to a snapshot taken with `snapshot`. Anvil consumes the snapshot (and every
+    /// later one), so callers that want to restore again must take a new one.
+    pub async fn revert_to_snapshot(&mut self, snapshot_id: &str) -> Result<()> {
+        let result = Self::rpc_call(&self.client, &self.rpc_url, "evm_revert", json!([snapshot_id])).await?;
+        if !result.as_bool().unwrap_or(false) {
+            return Err(anyhow::anyhow!("evm_revert rejected snapshot {}", snapshot_id));
+        }
+        // Reverting also rewinds account nonces, so our local view must follow. The nonces recorded
+        // with the snapshot save a round trip per account; accounts added since are read from the node.
+        let recorded = snapshot_number(snapshot_id)
+            .and_then(|number| self.snapshot_nonces.split_off(&number).remove(&number));
+        let Some(recorded) = recorded else {
+            return self.resync_nonces().await;
+        };
+        for account in self.nonce_accounts().cloned().collect::<Vec<_>>() {
+            let nonce = match recorded.iter().find(|(recorded_account, _)| *recorded_account == account) {
+                Some((_, nonce)) => *nonce,
+                None => Self::get_transaction_count(&self.client, &self.rpc_url, &account).await?,
+            };
+            self.nonces.resync(&account, nonce);
+        }
+        Ok(())
+    }
+    
+    /// Accounts whose nonces are tracked: the senders and a separate deployer
+    fn nonce_accounts(&self) -> impl Iterator<Item = &String> {
+        self.accounts.iter().chain(&self.deployer)
+    }
+
+    /// Re-read the pending nonce of every known account from the node
+    pub async fn resync_nonces(&mut self) -> Result<()> {
+        for account in self.accounts.iter().chain(&self.deployer) {
+            let nonce = Self::get_transaction_count(&self.client, &self.rpc_url, account).await?;
+            self.nonces.resync(account, nonce);
+        }
+        Ok(())
+    }
+    
+    /// Set the current transaction sender
+    pub fn set_sender(&mut self, sender_index: usize) {
+        if sender_index < self.accounts.len() {
+            self.current_sender = self.accounts[sender_index].clone();
+        }
+    }
+    
+    /// Set the current transaction sender by address. Returns false if the account is not managed by this executor.
+    pub fn set_sender_address(&mut self, address: &str) -> bool {
+        match self.accounts.iter().find(|account| account.eq_ignore_ascii_case(address)) {
+            Some(account) => {
+                self.current_sender = account.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Send from `address` without holding its key: impersonate it and, if it has less than 1 ETH,
+    /// fund it with 1000 ETH for gas. It is one of `accounts()` afterwards.
+    pub async fn impersonate(&mut self, address: &str) -> Result<()> {
+        if self.accounts.iter().any(|account| account.eq_ignore_ascii_case(address)) {
+            return Ok(());
+        }
+        Self::rpc_call(&self.client, &self.rpc_url, "anvil_impersonateAccount", json!([address])).await
+            .with_context(|| format!("Could not impersonate {} (requires Anvil)", address))?;
+        let balance = Self::rpc_call(&self.client, &self.rpc_url, "eth_getBalance", json!([address, "latest"])).await?;
+        let balance = balance.as_str()
+            .and_then(|hex| U256::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+            .unwrap_or_default();
+        if balance < U256::exp10(18) {
+            Self::rpc_call(&self.client, &self.rpc_url, "anvil_setBalance", json!([address, "0x3635c9adc5dea00000"])).await
+                .with_context(|| format!("Could not fund impersonated account {}", address))?;
+        }
+        let nonce = Self::get_transaction_count(&self.client, &self.rpc_url, address).await.unwrap_or(0);
+        self.nonces.add_sender(address, nonce);
+        self.accounts.push(address.to_string());
+        debug!("Impersonating {}", address);
+        Ok(())
+    }
+
+    /// Get the current sender address
+    pub fn current_sender(&self) -> &str {
+        &self.current_sender
+    }
+
+    pub fn deployer(&self) -> &str {
+        self.deployer.as_deref().unwrap_or(&self.current_sender)
+    }
+    
+    pub fn accounts(&self) -> &[String] {
+        &self.accounts
+    }
+
+    /// `eth_call` raw calldata against a deployed contract from the first account
+    pub async fn static_call(&self, contract_name: &str, calldata: &[u8]) -> Result<Vec<u8>> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow::anyhow!("Contract {} not deployed", contract_name))?;
+        let params = json!([{
+            "to": contract_address,
+            "data": format!("0x{}", hex::encode(calldata)),
+            "from": self.accounts[0],
+        }, "latest"]);
+        let output = Self::rpc_call(&self.client, &self.rpc_url, "eth_call", params).await?;
+        let output = output.as_str().context("eth_call returned no data")?;
+        hex::decode(output.trim_start_matches("0x")).context("eth_call returned invalid hex")
+    }
+
+    /// ETH balance of a deployed contract (`eth_getBalance`), in wei
+    pub async fn contract_balance(&self, contract_name: &str) -> Result<U256> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow::anyhow!("Contract {} not deployed", contract_name))?;
+        self.account_balance(contract_address).await
+    }
+
+    /// ETH balance of an account (`eth_getBalance`)
+    pub async fn account_balance(&self, address: &str) -> Result<U256> {
+        let balance = Self::rpc_call(&self.client, &self.rpc_url, "eth_getBalance", json!([address, "latest"])).await?;
+        let balance = balance.as_str().context("eth_getBalance returned no data")?;
+        U256::from_str_radix(balance.trim_start_matches("0x"), 16).context("eth_getBalance returned invalid hex")
+    }
+
+    /// Raw word in a storage slot of a deployed contract (`eth_getStorageAt`)
+    pub async fn storage_at(&self, contract_name: &str, slot: U256) -> Result<U256> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow::anyhow!("Contract {} not deployed", contract_name))?;
+        let params = json!([contract_address, format!("0x{:x}", slot), "latest"]);
+        let word = Self::rpc_call(&self.client, &self.rpc_url, "eth_getStorageAt", params).await?;
+        let word = word.as_str().context("eth_getStorageAt returned no data")?;
+        U256::from_str_radix(word.trim_start_matches("0x"), 16).context("eth_getStorageAt returned invalid hex")
+    }
+
+    /// Branch edges of the target contract hit by the latest `call_method` transaction,
+    /// from Anvil's `debug_traceTransaction` struct logger (empty if nothing was mined)
+    pub async fn last_call_coverage(&self) -> Result<Vec<BranchEdge>> {
+        let Some(tx_hash) = &self.last_tx_hash else {
+            return Ok(Vec::new());
+        };
+        let options = json!({ "disableStorage": true, "disableStack": true, "enableMemory": false, "enableReturnData": false });
+        let trace = Self::rpc_call(&self.client, &self.rpc_url, "debug_traceTransaction", json!([tx_hash, options])).await
+            .context("Coverage-guided fuzzing requires debug_traceTransaction")?;
+        let struct_logs = trace.get("structLogs").and_then(|logs| logs.as_array())
+            .context("debug_traceTransaction returned no structLogs")?;
+        Ok(coverage::branch_edges(struct_logs))
+    }
+
+    /// Wrapped arithmetic of the target in the latest `call_method` transaction, from the stacks of
+    /// Anvil's struct logger (empty if nothing was mined)
+    pub async fn last_call_wraparounds(&self) -> Result<Vec<WrappedOperation>> {
+        let Some(tx_hash) = &self.last_tx_hash else {
+            return Ok(Vec::new());
+        };
+        let options = json!({ "disableStorage": true, "disableStack": false, "enableMemory": false, "enableReturnData": false });
+        let trace = Self::rpc_call(&self.client, &self.rpc_url, "debug_traceTransaction", json!([tx_hash, options])).await
+            .context("Wraparound detection requires debug_traceTransaction")?;
+        let struct_logs = trace.get("structLogs").and_then(|logs| logs.as_array())
+            .context("debug_traceTransaction returned no structLogs")?;
+        Ok(wraparound::wrapped_operations(struct_logs))
+    }
+
+    /// Logs emitted by the latest `call_method` transaction (empty if it reverted or nothing was mined)
+    pub fn last_call_logs(&self) -> Vec<EventLog> {
+        self.last_logs.clone()
+    }
+
+    /// Message calls of the latest `call_method` transaction, from Anvil's `callTracer`
+    pub async fn last_call_frames(&self) -> Result<Vec<CallFrame>> {
+        let Some(tx_hash) = &self.last_tx_hash else {
+            return Ok(Vec::new());
+        };
+        let options = json!({ "tracer": "callTracer" });
+        let trace = Self::rpc_call(&self.client, &self.rpc_url, "debug_traceTransaction", json!([tx_hash, options])).await
+            .context("Reentrancy detection requires debug_traceTransaction with the callTracer")?;
+        Ok(reentrancy::frames_from_call_trace(&trace))
+    }
+
+    /// Address of a contract deployed by this executor
+    pub fn contract_address(&self, contract_name: &str) -> Option<&str> {
+        self.deployed_contracts.get(contract_name).map(String::as_str)
+    }
+    
+    /// Get the RPC URL
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+    
+    /// Try to get revert reason by calling eth_call
+    async fn get_revert_reason(
+        &self,
+        contract_address: &str,
+        call_data: &str,
+    ) -> Result<String> {
+        // Use eth_call to simulate the transaction and get revert reason
+        let call_params = json!({
+            "to": contract_address,
+            "data": call_data,
+            "from": self.current_sender,
+        });
+        
+        let params = json!([call_params, "latest"]);
+        
+        let response = Self::rpc_request(&self.client, &self.rpc_url, "eth_call", params).await?;
+        match response.error {
+            None => Ok("No revert reason available".to_string()),
+            Some(error) => {
+                if let Some(revert_data) = error.revert_data() {
+                    return Ok(decode_revert(&revert_data, &self.custom_errors));
+                }
+
+                // Extract the revert reason from the error message
+                let clean_msg = error.message.replace(['\n', '\r'], " ").trim().to_string();
+                
+                if clean_msg.contains("execution reverted:") {
+                    if let Some(reason) = clean_msg.split("execution reverted:").nth(1) {
+                        Ok(reason.trim().to_string())
+                    } else {
+                        Ok(clean_msg)
+                    }
+                } else if clean_msg.contains("revert") || clean_msg.contains("Revert") {
+                    Ok(clean_msg)
+                } else {
+                    Ok(format!("Reverted: {}", clean_msg))
+                }
+            }
+        }
+    }
+    
+    /// Try to get deployment revert reason by simulating the deployment
+    async fn get_deployment_revert_reason(
+        &self,
+        bytecode: &str,
+    ) -> Result<String> {
+        // Use eth_call to simulate the deployment and get revert reason
+        let call_params = json!({
+            "data": bytecode,
+            "from": self.current_sender,
+        });
+        
+        let params = json!([call_params, "latest"]);
+        
+        match Self::rpc_call(&self.client, &self.rpc_url, "eth_call", params).await {
+            Ok(_) => Ok("No revert reason available".to_string()),
+            Err(e) => {
+                // The error message might contain the revert reason
+                let error_msg = e.to_string();
+                Ok(error_msg)
+            }
+        }
+    }
+}
+
+/// A call accepted by the node whose receipt has not been awaited yet
+struct PendingCall {
+    sender: String,
+    nonce: u64,
+    tx_hash: String,
+    contract_address: String,
+    call_data_hex: String,
+}
+
+enum Submission {
+    Pending(PendingCall),
+    /// The node refused the transaction because it reverts; its nonce was not consumed
+    Rejected(MethodExecutionResult),
+}
+
+/// Result of a contract method execution
+#[derive(Debug, Clone)]
+pub struct MethodExecutionResult {
+    pub success: bool,
+    pub gas_used: u64,
+    pub return_data: Vec<u8>,
+    pub error: Option<String>,
+}
+
+/// WebSocket endpoint for an HTTP RPC URL (Anvil serves both on the same port)
+fn websocket_url(rpc_url: &str) -> Option<String> {
+    if rpc_url.starts_with("ws://") || rpc_url.starts_with("wss://") {
+        Some(rpc_url.to_string())
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        Some(format!("ws://{}", rest))
+    } else {
+        rpc_url.strip_prefix("https://").map(|rest| format!("wss://{}", rest))
+    }
+}
+
+/// Open an `eth_subscribe("newHeads")` subscription and expose it as a block counter.
+/// The connection is driven by a background task until the last receiver is dropped.
+async fn subscribe_new_heads(ws_url: &str) -> Result<watch::Receiver<u64>> {
+    let (mut socket, _) = tokio::time::timeout(Duration::from_secs(2), tokio_tungstenite::connect_async(ws_url))
+        .await
+        .context("Timed out connecting to WebSocket endpoint")?
+        .context("Failed to connect to WebSocket endpoint")?;
+    
+    let request = json!({"jsonrpc": "2.0", "id": 1, "method": "eth_subscribe", "params": ["newHeads"]});
+    socket.send(Message::Text(request.to_string())).await
+        .context("Failed to send eth_subscribe")?;
+    
+    // The first reply carries the subscription id (or an error if subscriptions are unsupported)
+    let reply = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .context("Timed out waiting for eth_subscribe reply")?
+        .context("WebSocket closed before eth_subscribe reply")??;
+    let reply: serde_json::Value = serde_json::from_str(reply.to_text()?)
+        .context("Failed to parse eth_subscribe reply")?;
+    if reply.get("result").and_then(|r| r.as_str()).is_none() {
+        return Err(anyhow::anyhow!("eth_subscribe rejected: {}", reply));
+    }
+    
+    let (tx, rx) = watch::channel(0u64);
+    tokio::spawn(async move {
+        loop {
+            let message = tokio::select! {
+                // Every executor holding the counter is gone, so is the need for the connection
+                _ = tx.closed() => break,
+                message = socket.next() => message,
+            };
+            let Some(Ok(message)) = message else {
+                break;
+            };
+            let is_notification = message.to_text().ok()
+                .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok())
+                .map(|value| value.get("method").and_then(|m| m.as_str()) == Some("eth_subscription"))
+                .unwrap_or(false);
+            if is_notification {
+                tx.send_modify(|blocks| *blocks += 1);
+            }
+        }
+    });
+    
+    Ok(rx)
+}
+
+/// Accounts derived from `--mnemonic` when `--accounts` is not given
+const DEFAULT_MNEMONIC_ACCOUNTS: usize = 5;
+
+/// Derive the first `count` addresses of a BIP-39 mnemonic on the standard Ethereum path (m/44'/60'/0'/0/i)
+pub(crate) fn derive_mnemonic_accounts(mnemonic: &str, count: usize) -> Result<Vec<String>> {
+    use ethers::signers::{coins_bip39::English, MnemonicBuilder, Signer};
+
+    (0..count as u32)
+        .map(|index| {
+            let wallet = MnemonicBuilder::<English>::default()
+                .phrase(mnemonic)
+                .index(index)?
+                .build()
+                .context("Invalid mnemonic")?;
+            Ok(ethers::utils::to_checksum(&wallet.address(), None))
+        })
+        .collect()
+}
+
+/// Whether an RPC error means the transaction nonce no longer matches the node's view of the account
+fn is_nonce_error(error: &anyhow::Error) -> bool {
+    let msg = error.to_string().to_lowercase();
+    msg.contains("nonce") || msg.contains("replacement transaction underpriced") || msg.contains("already known")
+}
+
+/// Whether an RPC error describes an EVM revert (contract behavior) rather than a transport or node
+/// problem, judged by the JSON-RPC error code and revert data rather than the message text
+fn is_revert_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.downcast_ref::<JsonRpcError>().is_some_and(JsonRpcError::is_revert))
+}
+
+/// Calculate the 4-byte function selector from a method signature
+pub fn calculate_selector(signature: &str) -> [u8; 4] {
+    use sha3::{Digest, Keccak256};
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_error(code: i32, message: &str, data: Option<serde_json::Value>) -> anyhow::Error {
+        JsonRpcError { code, message: message.to_string(), data }.into()
+    }
+
+    #[test]
+    fn reverts_are_recognized_by_code_and_data() {
+        assert!(is_revert_error(&rpc_error(3, "execution reverted", Some(json!("0x")))));
+        assert!(is_revert_error(&rpc_error(-32000, "failed", Some(json!("0x08c379a0")))));
+        assert!(is_revert_error(&rpc_error(-32015, "VM execution error", None)));
+        assert!(is_revert_error(&rpc_error(3, "execution reverted", None).context("Transaction submission failed")));
+    }
+
+    #[test]
+    fn node_errors_mentioning_reverts_are_not_reverts() {
+        assert!(!is_revert_error(&rpc_error(-32000, "nonce too low: reverted replacement", None)));
+        assert!(!is_revert_error(&rpc_error(-32603, "out of gas while estimating, node overloaded", None)));
+        assert!(!is_revert_error(&anyhow::anyhow!("execution reverted")));
+    }
+
+    #[test]
+    fn error_message_is_unchanged() {
+        assert_eq!(rpc_error(3, "execution reverted: no", None).to_string(), "RPC error: execution reverted: no (code: 3)");
+    }
+}
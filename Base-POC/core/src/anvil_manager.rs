@@ -0,0 +1,167 @@
+use crate::anvil_executor::AnvilForkExecutor;
+use crate::contract_compiler::ContractCompiler;
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+
+/// How long a spawned node may take to answer its first request (a fork fetches its block first)
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How a spawned node forks its upstream
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ForkOptions {
+    /// Upstream block to fork; `None` pins the upstream's latest block at startup
+    pub block_number: Option<u64>,
+    /// Do not keep fetched state in Anvil's on-disk cache (`~/.foundry/cache/rpc`)
+    pub no_storage_caching: bool,
+}
+
+/// An `anvil` process started for the campaign. It is killed when dropped, so it lives exactly as
+/// long as the value does, including on early returns and errors.
+pub struct ManagedAnvil {
+    child: Child,
+    url: String,
+    /// Chain the node forks, `None` for an empty local chain
+    upstream: Option<String>,
+    /// Upstream block the node forks
+    fork_block_number: Option<u64>,
+    /// Where the node's stdout and stderr go
+    log_path: PathBuf,
+}
+
+impl ManagedAnvil {
+    /// Start `anvil` on a free local port, forking `upstream` when given, and wait until it answers.
+    /// A fork is always pinned to a block: Anvil only caches fetched state on disk for pinned forks,
+    /// so later campaigns on the same block start without refetching it.
+    pub async fn spawn(client: &reqwest::Client, upstream: Option<&str>, fork: ForkOptions) -> Result<Self> {
+        let anvil = ContractCompiler::find_executable("anvil").context(
+            "anvil not found in PATH (install Foundry: curl -L https://foundry.paradigm.xyz | bash && foundryup), \
+             or start a node yourself and pass --no-spawn-anvil",
+        )?;
+        let port = free_port()?;
+        let log_path = std::env::temp_dir().join(format!("fuzzhead-anvil-{}.log", port));
+        let log = std::fs::File::create(&log_path)
+            .with_context(|| format!("Could not create Anvil log {}", log_path.display()))?;
+
+        let mut command = Command::new(&anvil);
+        command.arg("--host").arg("127.0.0.1").arg("--port").arg(port.to_string());
+        let mut fork_block_number = None;
+        if let Some(upstream) = upstream {
+            let block = match fork.block_number {
+                Some(block) => block,
+                None => latest_block(client, upstream).await?,
+            };
+            command.arg("--fork-url").arg(upstream).arg("--fork-block-number").arg(block.to_string());
+            if fork.no_storage_caching {
+                command.arg("--no-storage-caching");
+            }
+            fork_block_number = Some(block);
+        }
+        // Anvil logs every transaction; a file keeps that out of the console and never blocks it
+        command.stdin(Stdio::null())
+            .stdout(Stdio::from(log.try_clone()?))
+            .stderr(Stdio::from(log))
+            .kill_on_drop(true);
+        let child = command.spawn().with_context(|| format!("Could not start {}", anvil))?;
+
+        let mut node = Self {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+            upstream: upstream.map(str::to_string),
+            fork_block_number,
+            log_path,
+        };
+        node.wait_until_ready(client).await?;
+        Ok(node)
+    }
+
+    /// RPC URL of the node
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Chain the node forks, `None` for an empty local chain
+    pub fn upstream(&self) -> Option<&str> {
+        self.upstream.as_deref()
+    }
+
+    /// Upstream block the node forks, `None` for an empty local chain
+    pub fn fork_block_number(&self) -> Option<u64> {
+        self.fork_block_number
+    }
+
+    pub fn log_path(&self) -> &std::path::Path {
+        &self.log_path
+    }
+
+    async fn wait_until_ready(&mut self, client: &reqwest::Client) -> Result<()> {
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(anyhow::anyhow!("anvil exited during startup ({}): {}", status, self.log_tail()));
+            }
+            if AnvilForkExecutor::rpc_call(client, &self.url, "eth_chainId", json!([])).await.is_ok() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow::anyhow!("anvil did not answer on {} within {}s: {}",
+                    self.url, STARTUP_TIMEOUT.as_secs(), self.log_tail()));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Last lines of the node's log, for startup errors
+    fn log_tail(&self) -> String {
+        let log = std::fs::read_to_string(&self.log_path).unwrap_or_default();
+        let lines: Vec<&str> = log.lines().filter(|line| !line.trim().is_empty()).collect();
+        match lines.len() {
+            0 => format!("no output (log: {})", self.log_path.display()),
+            n => lines[n.saturating_sub(5)..].join(" | "),
+        }
+    }
+}
+
+/// Make sure `url` is served by a node the campaign can send transactions to. An Anvil node that
+/// already answers there is used as is, and so is anything listening on a local URL. A local URL
+/// nobody listens on gets an empty local chain, and a remote RPC that is not Anvil (a public
+/// endpoint, say) gets a local fork of it. Returns the started node, if one was needed.
+pub async fn ensure_node(client: &reqwest::Client, url: &str, fork: ForkOptions) -> Result<Option<ManagedAnvil>> {
+    if AnvilForkExecutor::rpc_call(client, url, "anvil_nodeInfo", json!([])).await.is_ok() {
+        return Ok(None);
+    }
+    let upstream = if is_local(url) {
+        if AnvilForkExecutor::rpc_call(client, url, "eth_chainId", json!([])).await.is_ok() {
+            return Ok(None);
+        }
+        None
+    } else {
+        Some(url)
+    };
+    ManagedAnvil::spawn(client, upstream, fork).await.map(Some)
+}
+
+/// Latest block of `upstream`, the block an unpinned fork starts from
+async fn latest_block(client: &reqwest::Client, upstream: &str) -> Result<u64> {
+    let block = AnvilForkExecutor::rpc_call(client, upstream, "eth_blockNumber", json!([])).await
+        .with_context(|| format!("Could not read the latest block of {}", upstream))?;
+    block.as_str()
+        .and_then(|hex_str| u64::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+        .with_context(|| format!("Unexpected eth_blockNumber result from {}: {}", upstream, block))
+}
+
+fn is_local(url: &str) -> bool {
+    reqwest::Url::parse(url).ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]" | "0.0.0.0"))
+}
+
+/// A port nothing listens on right now (the OS picks it, then it is released for Anvil)
+fn free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0").context("Could not find a free port for Anvil")?;
+    Ok(listener.local_addr()?.port())
+}
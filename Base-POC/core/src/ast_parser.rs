@@ -0,0 +1,825 @@
+use std::collections::{HashMap, HashSet};
+use crate::types::*;
+use solang_parser::pt::{self, ContractPart, Expression, FunctionAttribute, FunctionTy, SourceUnitPart};
+use tracing::warn;
+
+/// What kind of declaration a `ContractInfo` was parsed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContractKind {
+    Contract,
+    Abstract,
+    Interface,
+    Library,
+}
+
+impl ContractKind {
+    /// Only concrete contracts can be deployed on their own and fuzzed
+    pub fn is_deployable(&self) -> bool {
+        *self == ContractKind::Contract
+    }
+}
+
+impl std::fmt::Display for ContractKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ContractKind::Contract => "contract",
+            ContractKind::Abstract => "abstract contract",
+            ContractKind::Interface => "interface",
+            ContractKind::Library => "library",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContractInfo {
+    pub name: String,
+    pub kind: ContractKind,
+    /// Names listed after `is` in the declaration
+    pub bases: Vec<String>,
+    pub methods: Vec<ContractMethod>,
+    pub events: Vec<EventInfo>,
+    pub constructor: Option<ContractMethod>,
+    pub fallback: Option<ContractMethod>,
+    pub receive: Option<ContractMethod>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventInfo {
+    pub name: String,
+    pub parameters: Vec<MethodParameter>,
+    pub anonymous: bool,
+}
+
+/// Keywords that may appear between a parameter's type and its name without being part of either
+const DATA_LOCATION_KEYWORDS: &[&str] = &["memory", "calldata", "storage", "payable"];
+
+/// Structs nested deeper than this (through their fields) are left unresolved
+const MAX_STRUCT_DEPTH: usize = 8;
+
+pub struct SolidityParser {
+    _contracts: HashMap<String, ContractInfo>,
+    /// Add the methods a contract inherits from declarations in the same file to its own
+    inherited_methods: bool,
+}
+
+impl SolidityParser {
+    pub fn new() -> Self {
+        Self::with_inherited_methods(false)
+    }
+
+    /// A parser that lists, with a contract's own methods, those it inherits from the file's other
+    /// declarations (a base's version is dropped when the contract or a more derived base overrides it)
+    pub fn with_inherited_methods(inherited_methods: bool) -> Self {
+        Self {
+            _contracts: HashMap::new(),
+            inherited_methods,
+        }
+    }
+
+    /// Contracts, interfaces and libraries of a source file with their functions and events. Sources
+    /// solang-parser rejects (syntax it does not know yet, or errors) fall back to a line-based scan,
+    /// which only sees single-line function headers.
+    pub fn parse_contract(&mut self, source: &str, filename: &str) -> Result<Vec<ContractInfo>, anyhow::Error> {
+        let mut contracts = match solang_parser::parse(source, 0) {
+            Ok((unit, _comments)) => Self::contracts_from_tree(&unit),
+            Err(diagnostics) => {
+                let reason = diagnostics.first().map(|diagnostic| diagnostic.message.clone()).unwrap_or_default();
+                warn!("solang-parser could not parse {} ({}), falling back to line-based parsing", filename, reason);
+                self.contracts_from_lines(source)
+            }
+        };
+        
+        // Declarations from the same file that a contract inherits from contribute their methods
+        let snapshot = if self.inherited_methods { contracts.clone() } else { Vec::new() };
+        for contract in contracts.iter_mut() {
+            for inherited in Self::inherited_methods(&snapshot, &contract.bases, &mut Vec::new()) {
+                let already_declared = contract.methods.iter().any(|m| {
+                    m.name == inherited.name
+                        && m.parameters.len() == inherited.parameters.len()
+                        && m.parameters.iter().zip(&inherited.parameters).all(|(a, b)| a.param_type == b.param_type)
+                });
+                if !already_declared && !inherited.is_constructor {
+                    contract.methods.push(inherited);
+                }
+            }
+            contract.constructor = contract.methods.iter().find(|m| m.is_constructor).cloned();
+            contract.fallback = contract.methods.iter().find(|m| m.is_fallback).cloned();
+            contract.receive = contract.methods.iter().find(|m| m.is_receive).cloned();
+        }
+        
+        Ok(contracts)
+    }
+
+    fn contracts_from_tree(unit: &pt::SourceUnit) -> Vec<ContractInfo> {
+        let scope = TypeScope::collect(unit);
+        unit.0.iter()
+            .filter_map(|part| match part {
+                SourceUnitPart::ContractDefinition(definition) => Some(definition),
+                _ => None,
+            })
+            .filter_map(|definition| {
+                let name = definition.name.as_ref()?.name.clone();
+                let kind = match definition.ty {
+                    pt::ContractTy::Abstract(_) => ContractKind::Abstract,
+                    pt::ContractTy::Contract(_) => ContractKind::Contract,
+                    pt::ContractTy::Interface(_) => ContractKind::Interface,
+                    pt::ContractTy::Library(_) => ContractKind::Library,
+                };
+                let bases: Vec<String> = definition.base.iter()
+                    .filter_map(|base| base.name.identifiers.last().map(|identifier| identifier.name.clone()))
+                    .collect();
+                let mut methods = Vec::new();
+                let mut events = Vec::new();
+                for part in &definition.parts {
+                    match part {
+                        ContractPart::FunctionDefinition(function) => methods.extend(scope.method(&name, &bases, function)),
+                        ContractPart::EventDefinition(event) => events.extend(scope.event(&name, event)),
+                        _ => {}
+                    }
+                }
+                Some(ContractInfo {
+                    name,
+                    kind,
+                    bases,
+                    methods,
+                    events,
+                    constructor: None,
+                    fallback: None,
+                    receive: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Line-based fallback of `parse_contract`
+    fn contracts_from_lines(&self, source: &str) -> Vec<ContractInfo> {
+        let mut contracts = Vec::new();
+        
+        // One entry per contract/interface/library declaration
+        let mut declarations = self.extract_declarations(source);
+        if declarations.is_empty() {
+            declarations.push((ContractKind::Contract, "UnknownContract".to_string(), Vec::new(), source.to_string()));
+        }
+        
+        for (kind, name, bases, body) in declarations {
+            let methods = self.extract_methods(&body);
+            
+            contracts.push(ContractInfo {
+                name,
+                kind,
+                bases,
+                methods,
+                events: Vec::new(),
+                constructor: None,
+                fallback: None,
+                receive: None,
+            });
+        }
+        contracts
+    }
+
+    /// The version constraint of the first `pragma solidity` directive (e.g. `^0.8.0`)
+    pub fn extract_pragma(source: &str) -> Option<String> {
+        source.lines()
+            .map(|line| line.trim())
+            .find_map(|line| line.strip_prefix("pragma solidity"))
+            .map(|rest| rest.trim().trim_end_matches(';').trim().to_string())
+            .filter(|pragma| !pragma.is_empty())
+    }
+
+    /// 1-based line of a contract's declaration, or of its function `function` when given. A function
+    /// declared in a base contract of the same file is found there when the contract does not declare it.
+    pub fn locate(source: &str, contract: &str, function: Option<&str>) -> Option<usize> {
+        let lines: Vec<&str> = source.lines().collect();
+        let is_header = |line: &str, name: &str| {
+            ["abstract contract ", "contract ", "interface ", "library "].iter()
+                .filter_map(|prefix| line.trim().strip_prefix(prefix))
+                .any(|rest| rest.split(|c: char| c.is_whitespace() || c == '{').next() == Some(name))
+        };
+        let header = lines.iter().position(|line| is_header(line, contract))?;
+        let Some(function) = function else {
+            return Some(header + 1);
+        };
+        let declares = |line: &str| {
+            line.trim().strip_prefix("function ")
+                .is_some_and(|rest| rest.split('(').next().map(str::trim) == Some(function))
+        };
+        let next_header = lines[header + 1..].iter()
+            .position(|line| ["abstract contract ", "contract ", "interface ", "library "].iter().any(|prefix| line.trim().starts_with(prefix)))
+            .map_or(lines.len(), |offset| header + 1 + offset);
+        if let Some(offset) = lines[header..next_header].iter().position(|line| declares(line)) {
+            return Some(header + offset + 1);
+        }
+        // Inherited from a base declared in the same file
+        let bases = lines[header].split('{').next()?.split_once(" is ").map(|(_, bases)| bases.to_string()).unwrap_or_default();
+        bases.split(',')
+            .map(|base| base.split('(').next().unwrap_or("").trim())
+            .filter(|base| !base.is_empty() && *base != contract)
+            .find_map(|base| Self::locate(source, base, Some(function)).filter(|line| declares(lines[*line - 1])))
+    }
+
+    /// Collect methods of the named bases (and their bases) that are declared in the same file, most
+    /// derived first: like Solidity's linearization, the last base listed after `is` overrides the others
+    fn inherited_methods(all: &[ContractInfo], bases: &[String], visited: &mut Vec<String>) -> Vec<ContractMethod> {
+        let mut methods = Vec::new();
+        for base in bases.iter().rev() {
+            if visited.contains(base) {
+                continue;
+            }
+            visited.push(base.clone());
+            if let Some(info) = all.iter().find(|c| &c.name == base) {
+                methods.extend(info.methods.iter().cloned());
+                methods.extend(Self::inherited_methods(all, &info.bases, visited));
+            }
+        }
+        methods
+    }
+
+    /// Split the source into (kind, name, bases, body) for every top-level declaration
+    fn extract_declarations(&self, source: &str) -> Vec<(ContractKind, String, Vec<String>, String)> {
+        let mut declarations: Vec<(ContractKind, String, Vec<String>, String)> = Vec::new();
+        
+        for line in source.lines() {
+            let trimmed = line.trim();
+            let header = [
+                ("abstract contract ", ContractKind::Abstract),
+                ("contract ", ContractKind::Contract),
+                ("interface ", ContractKind::Interface),
+                ("library ", ContractKind::Library),
+            ]
+            .iter()
+            .find_map(|(prefix, kind)| trimmed.strip_prefix(prefix).map(|rest| (*kind, rest)));
+            
+            if let Some((kind, rest)) = header {
+                let rest = rest.split('{').next().unwrap_or("");
+                let mut parts = rest.splitn(2, " is ");
+                let name = parts.next().unwrap_or("").trim().to_string();
+                if !name.is_empty() {
+                    let bases = parts.next()
+                        .map(|list| list.split(',')
+                            .map(|b| b.split('(').next().unwrap_or("").trim().to_string())
+                            .filter(|b| !b.is_empty())
+                            .collect())
+                        .unwrap_or_default();
+                    declarations.push((kind, name, bases, String::new()));
+                    continue;
+                }
+            }
+            
+            if let Some((_, _, _, body)) = declarations.last_mut() {
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+        
+        declarations
+    }
+
+    fn extract_methods(&self, source: &str) -> Vec<ContractMethod> {
+        let mut methods = Vec::new();
+        let lines: Vec<&str> = source.lines().collect();
+        
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.trim();
+            
+            // Look for function definitions
+            if line.starts_with("function ") || line.starts_with("constructor") || line.starts_with("fallback") || line.starts_with("receive") {
+                let method = self.parse_method_from_line(line, i, &lines);
+                methods.push(method);
+            }
+        }
+        
+        methods
+    }
+
+    fn parse_method_from_line(&self, line: &str, _line_num: usize, _all_lines: &[&str]) -> ContractMethod {
+        let is_constructor = line.starts_with("constructor");
+        let is_fallback = line.starts_with("fallback");
+        let is_receive = line.starts_with("receive");
+        
+        let name = if is_constructor {
+            "constructor".to_string()
+        } else if is_fallback {
+            "fallback".to_string()
+        } else if is_receive {
+            "receive".to_string()
+        } else {
+            // Extract function name
+            line.split('(').next().unwrap_or("unknown")
+                .split_whitespace().last().unwrap_or("unknown").to_string()
+        };
+
+        // Determine visibility
+        let visibility = if line.contains("public") {
+            MethodVisibility::Public
+        } else if line.contains("external") {
+            MethodVisibility::External
+        } else if line.contains("internal") {
+            MethodVisibility::Internal
+        } else if line.contains("private") {
+            MethodVisibility::Private
+        } else {
+            MethodVisibility::Public // Default
+        };
+
+
+        let mutability = if line.contains(" pure") {
+            StateMutability::Pure
+        } else if line.contains(" view") {
+            StateMutability::View
+        } else if line.contains(" payable") || is_receive {
+            StateMutability::Payable
+        } else {
+            StateMutability::NonPayable
+        };
+
+        // Extract parameters (simplified)
+        let parameters = self.extract_parameters_from_line(line);
+
+        ContractMethod {
+            name,
+            parameters,
+            visibility,
+            is_constructor,
+            is_fallback,
+            is_receive,
+            mutability,
+            modifiers: Vec::new(),
+            returns: Vec::new(),
+        }
+    }
+
+    fn extract_parameters_from_line(&self, line: &str) -> Vec<MethodParameter> {
+        let mut parameters = Vec::new();
+        
+        // Simple parameter extraction
+        if let Some(params_start) = line.find('(') {
+            if let Some(params_end) = line.find(')') {
+                let params_str = &line[params_start + 1..params_end];
+                if !params_str.trim().is_empty() {
+                    // Split by comma and parse each parameter
+                    for (index, param) in params_str.split(',').enumerate() {
+                        let param = param.trim();
+                        if !param.is_empty() {
+                            // Data locations (`string memory s`, `uint256[] calldata xs`) and the
+                            // `payable` in `address payable to` are not part of the ABI type or the name
+                            let parts: Vec<&str> = param.split_whitespace()
+                                .filter(|token| !DATA_LOCATION_KEYWORDS.contains(token))
+                                .collect();
+                            if parts.is_empty() {
+                                continue;
+                            }
+                            let param_type = elementary_type(parts[0]);
+                            let location = param.split_whitespace().find_map(|token| match token {
+                                "memory" => Some(DataLocation::Memory),
+                                "calldata" => Some(DataLocation::Calldata),
+                                "storage" => Some(DataLocation::Storage),
+                                _ => None,
+                            });
+                            // Unnamed parameters (`function f(uint256)`) still take part in the selector
+                            let name = parts.get(1)
+                                .map(|n| n.to_string())
+                                .unwrap_or_else(|| MethodParameter::placeholder_name(index));
+                            
+                            parameters.push(MethodParameter {
+                                name,
+                                param_type,
+                                location,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        
+        parameters
+    }
+}
+
+impl Default for SolidityParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type of an elementary type name (`uint256`, `bytes32`, `address[]`, `uint8[4][]`); anything else is `Custom`
+fn elementary_type(type_str: &str) -> SolidityType {
+    // The outermost dimension is the last one: `uint8[4][]` is a dynamic array of `uint8[4]`
+    if let Some((inner, length)) = type_str.strip_suffix(']').and_then(|rest| rest.rsplit_once('[')) {
+        return match length {
+            "" => SolidityType::Array(Box::new(elementary_type(inner))),
+            length => match length.parse() {
+                Ok(length) => SolidityType::FixedArray(Box::new(elementary_type(inner)), length),
+                Err(_) => SolidityType::Custom(type_str.to_string()),
+            },
+        };
+    }
+
+    match type_str {
+        "uint8" => SolidityType::Uint8,
+        "uint16" => SolidityType::Uint16,
+        "uint32" => SolidityType::Uint32,
+        "uint64" => SolidityType::Uint64,
+        "uint128" => SolidityType::Uint128,
+        "uint256" | "uint" => SolidityType::Uint256,
+        "int8" => SolidityType::Int8,
+        "int16" => SolidityType::Int16,
+        "int32" => SolidityType::Int32,
+        "int64" => SolidityType::Int64,
+        "int128" => SolidityType::Int128,
+        "int256" | "int" => SolidityType::Int256,
+        "address" => SolidityType::Address,
+        "bool" => SolidityType::Bool,
+        "string" => SolidityType::String,
+        "bytes" => SolidityType::Bytes,
+        "bytes1" => SolidityType::Bytes1,
+        "bytes2" => SolidityType::Bytes2,
+        "bytes4" => SolidityType::Bytes4,
+        "bytes8" => SolidityType::Bytes8,
+        "bytes16" => SolidityType::Bytes16,
+        "bytes32" => SolidityType::Bytes32,
+        _ => SolidityType::Custom(type_str.to_string()),
+    }
+}
+
+/// User-defined type names of a source file, so parameters of struct, enum, contract and
+/// user-defined value types resolve to what they are encoded as. Types declared inside a contract
+/// are known both as `Contract.Name` and, unless a file-level type already takes it, as `Name`.
+#[derive(Default)]
+struct TypeScope<'a> {
+    structs: HashMap<String, &'a pt::StructDefinition>,
+    enums: HashSet<String>,
+    value_types: HashMap<String, &'a Expression>,
+    contracts: HashSet<String>,
+}
+
+impl<'a> TypeScope<'a> {
+    fn collect(unit: &'a pt::SourceUnit) -> Self {
+        let mut scope = Self::default();
+        for part in &unit.0 {
+            match part {
+                SourceUnitPart::StructDefinition(definition) => scope.add_struct(None, definition),
+                SourceUnitPart::EnumDefinition(definition) => scope.add_enum(None, definition),
+                SourceUnitPart::TypeDefinition(definition) => scope.add_value_type(None, definition),
+                _ => {}
+            }
+        }
+        for part in &unit.0 {
+            let SourceUnitPart::ContractDefinition(contract) = part else { continue };
+            let Some(contract_name) = contract.name.as_ref().map(|name| name.name.as_str()) else { continue };
+            scope.contracts.insert(contract_name.to_string());
+            for part in &contract.parts {
+                match part {
+                    ContractPart::StructDefinition(definition) => scope.add_struct(Some(contract_name), definition),
+                    ContractPart::EnumDefinition(definition) => scope.add_enum(Some(contract_name), definition),
+                    ContractPart::TypeDefinition(definition) => scope.add_value_type(Some(contract_name), definition),
+                    _ => {}
+                }
+            }
+        }
+        scope
+    }
+
+    /// Keys a declaration is known by: `Name`, and `Contract.Name` inside a contract
+    fn keys(contract: Option<&str>, name: &str) -> Vec<String> {
+        match contract {
+            Some(contract) => vec![format!("{}.{}", contract, name), name.to_string()],
+            None => vec![name.to_string()],
+        }
+    }
+
+    fn add_struct(&mut self, contract: Option<&str>, definition: &'a pt::StructDefinition) {
+        let Some(name) = &definition.name else { return };
+        for key in Self::keys(contract, &name.name) {
+            self.structs.entry(key).or_insert(definition);
+        }
+    }
+
+    fn add_enum(&mut self, contract: Option<&str>, definition: &pt::EnumDefinition) {
+        let Some(name) = &definition.name else { return };
+        self.enums.extend(Self::keys(contract, &name.name));
+    }
+
+    fn add_value_type(&mut self, contract: Option<&str>, definition: &'a pt::TypeDefinition) {
+        for key in Self::keys(contract, &definition.name.name) {
+            self.value_types.entry(key).or_insert(&definition.ty);
+        }
+    }
+
+    /// The type an expression in a declaration of `contract` names
+    fn resolve(&self, contract: &str, expr: &Expression, depth: usize) -> SolidityType {
+        match expr {
+            Expression::Type(_, ty) => match ty {
+                pt::Type::Address | pt::Type::AddressPayable | pt::Type::Payable => SolidityType::Address,
+                pt::Type::Bool => SolidityType::Bool,
+                pt::Type::String => SolidityType::String,
+                pt::Type::DynamicBytes => SolidityType::Bytes,
+                pt::Type::Int(bits) => elementary_type(&format!("int{}", bits)),
+                pt::Type::Uint(bits) => elementary_type(&format!("uint{}", bits)),
+                pt::Type::Bytes(length) => elementary_type(&format!("bytes{}", length)),
+                pt::Type::Mapping { key, value, .. } => SolidityType::Mapping(
+                    Box::new(self.resolve(contract, key, depth)),
+                    Box::new(self.resolve(contract, value, depth)),
+                ),
+                _ => SolidityType::Custom(expr.to_string()),
+            },
+            Expression::ArraySubscript(_, inner, None) => SolidityType::Array(Box::new(self.resolve(contract, inner, depth))),
+            // Lengths given by a constant's name are not resolved
+            Expression::ArraySubscript(_, inner, Some(length)) => match length.to_string().parse() {
+                Ok(length) => SolidityType::FixedArray(Box::new(self.resolve(contract, inner, depth)), length),
+                Err(_) => SolidityType::Custom(format!("{}[{}]", self.resolve(contract, inner, depth).abi_type_string(), length)),
+            },
+            Expression::Variable(_) | Expression::MemberAccess(..) => self.resolve_name(contract, &expr.to_string(), depth),
+            _ => SolidityType::Custom(expr.to_string()),
+        }
+    }
+
+    /// A user-defined type name, looked up as written, then inside `contract`
+    fn resolve_name(&self, contract: &str, name: &str, depth: usize) -> SolidityType {
+        let local = format!("{}.{}", contract, name);
+        let candidates = [local.as_str(), name];
+        if let Some(definition) = candidates.iter().find_map(|key| self.structs.get(*key)) {
+            if depth >= MAX_STRUCT_DEPTH {
+                return SolidityType::Custom(name.to_string());
+            }
+            let fields = definition.fields.iter()
+                .enumerate()
+                .map(|(index, field)| MethodParameter {
+                    name: field.name.as_ref()
+                        .map(|name| name.name.clone())
+                        .unwrap_or_else(|| MethodParameter::placeholder_name(index)),
+                    param_type: self.resolve(contract, &field.ty, depth + 1),
+                    location: None,
+                })
+                .collect();
+            return SolidityType::Struct(name.to_string(), fields);
+        }
+        if candidates.iter().any(|key| self.enums.contains(*key)) {
+            return SolidityType::Uint8;
+        }
+        if let Some(underlying) = candidates.iter().find_map(|key| self.value_types.get(*key)) {
+            return self.resolve(contract, underlying, depth);
+        }
+        // Contracts and interfaces travel as their address; the last segment covers `Lib.IToken`
+        let last = name.rsplit('.').next().unwrap_or(name);
+        if self.contracts.contains(last) {
+            return SolidityType::Address;
+        }
+        SolidityType::Custom(name.to_string())
+    }
+
+    fn parameters(&self, contract: &str, params: &pt::ParameterList) -> Vec<MethodParameter> {
+        params.iter()
+            .enumerate()
+            .filter_map(|(index, (_, param))| {
+                let param = param.as_ref()?;
+                Some(MethodParameter {
+                    name: param.name.as_ref()
+                        .map(|name| name.name.clone())
+                        .unwrap_or_else(|| MethodParameter::placeholder_name(index)),
+                    param_type: self.resolve(contract, &param.ty, 0),
+                    location: param.storage.as_ref().map(|storage| match storage {
+                        pt::StorageLocation::Memory(_) => DataLocation::Memory,
+                        pt::StorageLocation::Storage(_) => DataLocation::Storage,
+                        pt::StorageLocation::Calldata(_) => DataLocation::Calldata,
+                    }),
+                })
+            })
+            .collect()
+    }
+
+    /// A function, constructor, fallback or receive of `contract`; modifier definitions are skipped
+    fn method(&self, contract: &str, bases: &[String], function: &pt::FunctionDefinition) -> Option<ContractMethod> {
+        let (name, is_constructor, is_fallback, is_receive) = match function.ty {
+            FunctionTy::Constructor => ("constructor".to_string(), true, false, false),
+            FunctionTy::Fallback => ("fallback".to_string(), false, true, false),
+            FunctionTy::Receive => ("receive".to_string(), false, false, true),
+            FunctionTy::Function => (function.name.as_ref()?.name.clone(), false, false, false),
+            FunctionTy::Modifier => return None,
+        };
+
+        // Functions without a visibility are public (pre-0.5 sources); fallback and receive are external
+        let mut visibility = if is_fallback || is_receive { MethodVisibility::External } else { MethodVisibility::Public };
+        let mut mutability = if is_receive { StateMutability::Payable } else { StateMutability::NonPayable };
+        let mut modifiers = Vec::new();
+        for attribute in &function.attributes {
+            match attribute {
+                FunctionAttribute::Visibility(pt::Visibility::External(_)) => visibility = MethodVisibility::External,
+                FunctionAttribute::Visibility(pt::Visibility::Public(_)) => visibility = MethodVisibility::Public,
+                FunctionAttribute::Visibility(pt::Visibility::Internal(_)) => visibility = MethodVisibility::Internal,
+                FunctionAttribute::Visibility(pt::Visibility::Private(_)) => visibility = MethodVisibility::Private,
+                FunctionAttribute::Mutability(pt::Mutability::Pure(_)) => mutability = StateMutability::Pure,
+                FunctionAttribute::Mutability(pt::Mutability::View(_) | pt::Mutability::Constant(_)) => {
+                    mutability = StateMutability::View
+                }
+                FunctionAttribute::Mutability(pt::Mutability::Payable(_)) => mutability = StateMutability::Payable,
+                FunctionAttribute::BaseOrModifier(_, base) => {
+                    let name = base.name.to_string();
+                    // `constructor() Ownable(msg.sender)` calls a base constructor, it is not a modifier
+                    let calls_base = is_constructor && bases.contains(&name);
+                    if !calls_base {
+                        modifiers.push(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(ContractMethod {
+            name,
+            parameters: self.parameters(contract, &function.params),
+            visibility,
+            is_constructor,
+            is_fallback,
+            is_receive,
+            mutability,
+            modifiers,
+            returns: self.parameters(contract, &function.returns),
+        })
+    }
+
+    fn event(&self, contract: &str, event: &pt::EventDefinition) -> Option<EventInfo> {
+        let parameters = event.fields.iter()
+            .enumerate()
+            .map(|(index, field)| MethodParameter {
+                name: field.name.as_ref()
+                    .map(|name| name.name.clone())
+                    .unwrap_or_else(|| MethodParameter::placeholder_name(index)),
+                param_type: self.resolve(contract, &field.ty, 0),
+                location: None,
+            })
+            .collect();
+        Some(EventInfo {
+            name: event.name.as_ref()?.name.clone(),
+            parameters,
+            anonymous: event.anonymous,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str, inherited_methods: bool) -> Vec<ContractInfo> {
+        SolidityParser::with_inherited_methods(inherited_methods).parse_contract(source, "Test.sol").unwrap()
+    }
+
+    fn contract<'a>(contracts: &'a [ContractInfo], name: &str) -> &'a ContractInfo {
+        contracts.iter().find(|contract| contract.name == name).unwrap()
+    }
+
+    fn method<'a>(contract: &'a ContractInfo, name: &str) -> Vec<&'a ContractMethod> {
+        contract.methods.iter().filter(|method| method.name == name).collect()
+    }
+
+    const VAULT: &str = r#"
+        pragma solidity ^0.8.0;
+        interface IVault { function deposit() external payable; }
+        abstract contract Base {
+            constructor(uint256 cap) {}
+            function pause() public virtual {}
+            function withdraw(uint256 amount) public virtual {}
+        }
+        contract Vault is IVault, Base {
+            constructor() Base(10) {}
+            function deposit() external payable {}
+            function withdraw(uint256 amount) public override onlyOwner {}
+        }
+    "#;
+
+    #[test]
+    fn inherited_methods_are_left_out_by_default() {
+        let contracts = parse(VAULT, false);
+        let vault = contract(&contracts, "Vault");
+        assert!(method(vault, "pause").is_empty());
+        assert_eq!(vault.bases, ["IVault", "Base"]);
+    }
+
+    #[test]
+    fn inherited_methods_are_merged_without_overridden_ones_or_constructors() {
+        let contracts = parse(VAULT, true);
+        let vault = contract(&contracts, "Vault");
+        assert_eq!(method(vault, "pause").len(), 1);
+        assert_eq!(method(vault, "deposit").len(), 1);
+        // The override, with its own modifier, replaces the base's version
+        let withdraw = method(vault, "withdraw");
+        assert_eq!(withdraw.len(), 1);
+        assert_eq!(withdraw[0].modifiers, ["onlyOwner"]);
+        assert!(vault.constructor.as_ref().is_some_and(|constructor| constructor.parameters.is_empty()));
+    }
+
+    #[test]
+    fn the_most_derived_base_wins() {
+        let contracts = parse(r#"
+            contract A { function f() public view virtual returns (uint256) { return 1; } }
+            contract B is A { function f() public pure virtual override returns (uint256) { return 2; } }
+            contract C is A, B {}
+        "#, true);
+        let f = method(contract(&contracts, "C"), "f");
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[0].mutability, StateMutability::Pure);
+    }
+
+    #[test]
+    fn grandparents_and_cycles_are_walked_once() {
+        let contracts = parse(r#"
+            contract A is C { function a() public {} }
+            contract B is A { function b() public {} }
+            contract C is B { function c() public {} }
+        "#, true);
+        let c = contract(&contracts, "C");
+        for name in ["a", "b", "c"] {
+            assert_eq!(method(c, name).len(), 1, "{}", name);
+        }
+    }
+
+    #[test]
+    fn declarations_keep_their_kind_and_order() {
+        let contracts = parse(VAULT, false);
+        let kinds: Vec<_> = contracts.iter().map(|contract| (contract.name.as_str(), contract.kind)).collect();
+        assert_eq!(kinds, [("IVault", ContractKind::Interface), ("Base", ContractKind::Abstract), ("Vault", ContractKind::Contract)]);
+    }
+
+    #[test]
+    fn multi_line_signatures_are_parsed_with_their_attributes() {
+        let contracts = parse(r#"
+            contract Token {
+                function transferFrom(
+                    address from,
+                    address to,
+                    uint256 amount
+                )
+                    external
+                    whenNotPaused
+                    returns (bool)
+                {
+                    return true;
+                }
+            }
+        "#, false);
+        let transfer_from = method(contract(&contracts, "Token"), "transferFrom");
+        assert_eq!(transfer_from.len(), 1);
+        let transfer_from = transfer_from[0];
+        let types: Vec<_> = transfer_from.parameters.iter().map(|param| param.param_type.clone()).collect();
+        assert_eq!(types, [SolidityType::Address, SolidityType::Address, SolidityType::Uint256]);
+        assert_eq!(transfer_from.visibility, MethodVisibility::External);
+        assert_eq!(transfer_from.modifiers, ["whenNotPaused"]);
+        assert_eq!(transfer_from.returns.len(), 1);
+        assert_eq!(transfer_from.returns[0].param_type, SolidityType::Bool);
+    }
+
+    #[test]
+    fn overloads_are_separate_methods() {
+        let contracts = parse(r#"
+            contract Vault {
+                function withdraw() public {}
+                function withdraw(uint256 amount) public {}
+                function withdraw(uint256 amount, address to) public {}
+            }
+        "#, false);
+        let arities: Vec<_> = method(contract(&contracts, "Vault"), "withdraw").iter().map(|method| method.parameters.len()).collect();
+        assert_eq!(arities, [0, 1, 2]);
+    }
+
+    #[test]
+    fn struct_parameters_resolve_to_their_fields() {
+        let contracts = parse(r#"
+            struct Order { address maker; uint128 amount; bytes32[] proofs; }
+            contract Exchange {
+                struct Fill { Order order; uint8 share; }
+                function fill(Fill calldata fill, Order memory order) external {}
+            }
+        "#, false);
+        let fill = method(contract(&contracts, "Exchange"), "fill")[0];
+        let SolidityType::Struct(name, fields) = &fill.parameters[0].param_type else {
+            panic!("not a struct: {:?}", fill.parameters[0].param_type);
+        };
+        assert_eq!(name, "Fill");
+        assert!(matches!(&fields[0].param_type, SolidityType::Struct(inner, _) if inner == "Order"));
+        assert_eq!(fields[1].param_type, SolidityType::Uint8);
+        assert_eq!(fill.parameters[0].location, Some(DataLocation::Calldata));
+        assert_eq!(fill.parameters[1].location, Some(DataLocation::Memory));
+        assert_eq!(fill.parameters[1].param_type.abi_type_string(), "(address,uint128,bytes32[])");
+    }
+
+    #[test]
+    fn modifiers_and_base_constructor_calls_are_told_apart() {
+        let contracts = parse(r#"
+            contract Ownable { constructor(address owner) {} }
+            contract Vault is Ownable {
+                modifier onlyOwner() { _; }
+                constructor() Ownable(msg.sender) payable {}
+                function sweep() external onlyOwner nonReentrant {}
+            }
+        "#, false);
+        let vault = contract(&contracts, "Vault");
+        let constructor = vault.constructor.as_ref().unwrap();
+        assert!(constructor.modifiers.is_empty());
+        assert_eq!(constructor.mutability, StateMutability::Payable);
+        assert_eq!(method(vault, "sweep")[0].modifiers, ["onlyOwner", "nonReentrant"]);
+        // Modifier definitions are not methods
+        assert!(method(vault, "onlyOwner").is_empty());
+    }
+}
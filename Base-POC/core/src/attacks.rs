@@ -0,0 +1,154 @@
+use crate::abi_encoding;
+use crate::types::{BlockContext, Cheat, Finding, FuzzSummary};
+use anyhow::{Context, Result};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A named attack scenario: the ordered calls that reproduced a finding, stored so the
+/// scenario can be replayed against later versions of the target to check that a fix holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackRecipe {
+    pub name: String,
+    /// Source file the target was fuzzed from; replays use it unless another source is given
+    pub source_file: String,
+    pub contract: String,
+    /// Seed of the iteration that found the scenario
+    pub seed: u64,
+    pub steps: Vec<AttackStep>,
+    /// Revert reason of the final step when the scenario was recorded; `None` when the
+    /// scenario succeeds by having every step go through
+    pub expected_error: Option<String>,
+}
+
+/// One transaction of an attack scenario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackStep {
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub method: String,
+    pub sender: String,
+    /// ABI-encoded arguments (without selector), hex with `0x` prefix
+    pub calldata: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_context: Option<BlockContext>,
+    /// Wei sent with the step, in decimal, when it sent any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Contract the step calls when it is not the recipe's own (cross-contract scenarios)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    /// Time, block and balance cheats applied before the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cheats: Vec<Cheat>,
+}
+
+/// Result of replaying one recipe against the current version of its target
+#[derive(Debug, Clone)]
+pub enum ReplayOutcome {
+    /// The scenario still behaves as recorded
+    Reproduced(String),
+    /// The scenario no longer reproduces
+    Fixed(String),
+    /// The scenario cannot be applied any more (contract or method removed, earlier step reverted)
+    Obsolete(String),
+    /// The replay itself failed (RPC, encoding); says nothing about the fix
+    Error(String),
+}
+
+impl AttackStep {
+    pub fn decoded_calldata(&self) -> Result<Vec<u8>> {
+        hex::decode(self.calldata.trim_start_matches("0x"))
+            .with_context(|| format!("Invalid calldata in step {}", self.method))
+    }
+
+    /// Wei to send with the step
+    pub fn value(&self) -> Result<U256> {
+        match &self.value {
+            Some(value) => U256::from_dec_str(value)
+                .map_err(|e| anyhow::anyhow!("Invalid value {} in step {}: {}", value, self.method, e)),
+            None => Ok(U256::zero()),
+        }
+    }
+}
+
+impl AttackRecipe {
+    /// Record a confirmed finding as a scenario: the setup calls of its stateful sequence, if any,
+    /// followed by the failing call
+    pub fn from_finding(source_file: &str, finding: &Finding) -> Result<Self> {
+        let calldata = abi_encoding::encode_args(&finding.args)
+            .map_err(|e| anyhow::anyhow!("ABI encoding failed: {}", e))?;
+        let mut steps: Vec<AttackStep> = finding.sequence.iter()
+            .map(|call| AttackStep {
+                method: call.signature.clone(),
+                sender: call.sender.clone(),
+                calldata: format!("0x{}", hex::encode(&call.calldata)),
+                block_context: None,
+                value: (!call.value.is_zero()).then(|| call.value.to_string()),
+                contract: call.contract.clone().filter(|contract| *contract != finding.contract),
+                cheats: call.cheats.clone(),
+            })
+            .collect();
+        steps.push(AttackStep {
+            method: finding.signature.clone(),
+            sender: finding.sender.clone(),
+            calldata: format!("0x{}", hex::encode(calldata)),
+            block_context: finding.block_context.clone(),
+            value: (!finding.value.is_zero()).then(|| finding.value.to_string()),
+            contract: None,
+            cheats: finding.cheats.clone(),
+        });
+        Ok(Self {
+            name: format!("{}-{}-{:016x}", finding.contract, finding.method, finding.seed),
+            source_file: source_file.to_string(),
+            contract: finding.contract.clone(),
+            seed: finding.seed,
+            steps,
+            expected_error: Some(finding.error.clone()),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read attack recipe {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid attack recipe {}", path.display()))
+    }
+
+    /// Write the recipe as `<dir>/<name>.json` and return the path
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create attack recipe directory {}", dir.display()))?;
+        let path = dir.join(format!("{}.json", self.name));
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write attack recipe {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Recipes for every finding of a campaign that held up: flaky findings and findings that
+/// did not reproduce on the confirmation fork are not worth replaying
+pub fn recipes_from_summary(source_file: &str, summary: &FuzzSummary) -> Result<Vec<AttackRecipe>> {
+    summary.findings.iter()
+        .filter(|finding| !finding.is_flaky() && finding.confirmed_on_fork != Some(false))
+        .map(|finding| AttackRecipe::from_finding(source_file, finding))
+        .collect()
+}
+
+/// Load a recipe file, or every `*.json` recipe in a directory (sorted by file name)
+pub fn load_recipes(path: &Path) -> Result<Vec<(PathBuf, AttackRecipe)>> {
+    let mut paths = if path.is_dir() {
+        std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read attack recipe directory {}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>()
+    } else {
+        vec![path.to_path_buf()]
+    };
+    paths.sort();
+
+    paths.into_iter()
+        .map(|path| AttackRecipe::load(&path).map(|recipe| (path, recipe)))
+        .collect()
+}
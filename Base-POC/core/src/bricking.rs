@@ -0,0 +1,42 @@
+use crate::types::TestResult;
+use serde::{Deserialize, Serialize};
+
+/// Fewer probes than this cannot tell a bricked contract from a call that only broke one code path
+pub const MIN_PROBES: usize = 2;
+
+/// Most recent passing calls replayed after each method to check the contract still works
+pub const MAX_PROBES: usize = 4;
+
+/// A method whose calls left the contract unusable (paused, ownership renounced, funds locked):
+/// calls of other methods that passed before it all revert with the same error afterwards.
+/// The fork is restored to the state before the method and it is skipped for the rest of the campaign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrickingCall {
+    pub contract: String,
+    /// Canonical signature, e.g. `setPaused(bool)`
+    pub method: String,
+    /// The revert every probe hit
+    pub error: String,
+    /// How many previously passing calls were replayed
+    pub probes: usize,
+}
+
+impl std::fmt::Display for BrickingCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{} bricks the contract: {} previously passing calls now all revert with \"{}\"",
+            self.contract, self.method, self.probes, self.error)
+    }
+}
+
+/// The shared revert reason when every probe reverted with the same error, `None` otherwise
+pub fn uniform_revert(results: &[TestResult]) -> Option<String> {
+    if results.len() < MIN_PROBES {
+        return None;
+    }
+    let mut errors = results.iter().map(|result| match result {
+        TestResult::Failed(error) => Some(error),
+        _ => None,
+    });
+    let first = errors.next()??;
+    errors.all(|error| error == Some(first)).then(|| first.clone())
+}
@@ -0,0 +1,132 @@
+//! Call trees of failing calls. A failing input is re-executed once on a snapshot and traced
+//! (`debug_traceTransaction` with the `callTracer` on Anvil, the inspector on revm); the tree is
+//! pruned to the frames that reverted and the calls they ran inside, so a finding shows whether
+//! its revert started in the target or in a token or oracle the target called.
+
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::reentrancy::CallFrame;
+use ethers::abi::Abi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Most frames kept per finding
+pub const MAX_FRAMES: usize = 24;
+
+/// Names of the campaign's contracts by address, and of their functions by selector
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    contracts: HashMap<String, String>,
+    functions: HashMap<[u8; 4], String>,
+}
+
+impl Names {
+    pub fn add(&mut self, contract: &str, address: &str, abi: &Abi) {
+        self.contracts.insert(address.to_ascii_lowercase(), contract.to_string());
+        for method in abi_encoding::methods_from_abi(abi).iter().filter(|method| !method.is_fallback && !method.is_receive) {
+            let signature = abi_encoding::method_signature(method);
+            self.functions.insert(calculate_selector(&signature), signature);
+        }
+    }
+}
+
+/// One frame of a pruned call tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceFrame {
+    /// 0 for the transaction itself
+    pub depth: usize,
+    /// Account whose code ran, lowercase
+    pub to: String,
+    /// Campaign contract at that address, if it is one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    /// First four bytes of the input, `0x…`; none for plain ETH transfers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Signature of the selector, when a campaign contract declares it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub reverted: bool,
+    /// The revert started here, as far as the trace tells: the innermost frame reached by
+    /// following the last reverted call of each reverted frame down from the transaction
+    #[serde(default)]
+    pub revert_origin: bool,
+}
+
+impl TraceFrame {
+    /// `Contract.function(types)`, with the address and selector standing in for unknown names
+    pub fn call(&self) -> String {
+        let account = self.contract.as_deref().unwrap_or(&self.to);
+        let function = self.function.as_deref().or(self.selector.as_deref()).unwrap_or("receive()");
+        format!("{}.{}", account, function)
+    }
+}
+
+impl fmt::Display for TraceFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", "  ".repeat(self.depth), self.call())?;
+        if self.revert_origin {
+            write!(f, " ← reverted here")
+        } else if self.reverted {
+            write!(f, " (reverted)")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The frames that reverted and every frame they ran inside, in execution order, with the frame
+/// the revert started in marked; at most `MAX_FRAMES`
+pub fn prune(frames: &[CallFrame], names: &Names) -> Vec<TraceFrame> {
+    let mut keep = vec![false; frames.len()];
+    // Indices of the frames enclosing the current one, outermost first
+    let mut enclosing: Vec<usize> = Vec::new();
+    for (index, frame) in frames.iter().enumerate() {
+        enclosing.truncate(frame.depth);
+        if frame.reverted {
+            for &outer in &enclosing {
+                keep[outer] = true;
+            }
+            keep[index] = true;
+        }
+        enclosing.push(index);
+    }
+    if let Some(root) = keep.first_mut() {
+        *root = true;
+    }
+
+    // A revert bubbles up from the last call that reverted inside a frame; calls that reverted
+    // before it were caught
+    let mut origin = frames.first().filter(|root| root.reverted).map(|_| 0);
+    while let Some(current) = origin {
+        let depth = frames[current].depth;
+        let last_reverted_call = frames.iter().enumerate().skip(current + 1)
+            .take_while(|(_, inner)| inner.depth > depth)
+            .filter(|(_, inner)| inner.depth == depth + 1 && inner.reverted)
+            .last();
+        match last_reverted_call {
+            Some((index, _)) => origin = Some(index),
+            None => break,
+        }
+    }
+
+    frames.iter().enumerate()
+        .filter(|(index, _)| keep[*index])
+        .take(MAX_FRAMES)
+        .map(|(index, frame)| TraceFrame {
+            depth: frame.depth,
+            to: frame.to.clone(),
+            contract: names.contracts.get(&frame.to).cloned(),
+            selector: frame.selector.map(|selector| format!("0x{}", hex::encode(selector))),
+            function: frame.selector.and_then(|selector| names.functions.get(&selector).cloned()),
+            reverted: frame.reverted,
+            revert_origin: origin == Some(index),
+        })
+        .collect()
+}
+
+/// The frame the revert started in, when it is not the transaction itself
+pub fn nested_origin(trace: &[TraceFrame]) -> Option<&TraceFrame> {
+    trace.iter().find(|frame| frame.revert_origin && frame.depth > 0)
+}
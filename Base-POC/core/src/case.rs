@@ -0,0 +1,112 @@
+use crate::hooks;
+use crate::report::CampaignReport;
+use crate::types::{SolidityType, SolidityValue};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// A single failing call to re-execute with the `replay` subcommand. Arguments are given as JSON
+/// (numbers as strings or numbers, bytes as hex, arrays as arrays); without them they and the
+/// sender are regenerated from the iteration seed, exactly as the campaign drew them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailingCase {
+    /// Source file of the target; `replay --source` overrides it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    pub contract: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    /// Iteration seed the case was generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<Value>>,
+    /// Wei sent with the call, in decimal; regenerated from the seed when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl FailingCase {
+    /// Load a case file, or the `finding`-th finding (1-based, counted across files) of a campaign report
+    pub fn load(path: &Path, finding: Option<usize>) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read case {}", path.display()))?;
+        let value: Value = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid case {}", path.display()))?;
+        if value.get("files").is_none() {
+            return serde_json::from_value(value)
+                .with_context(|| format!("Invalid case {}", path.display()));
+        }
+
+        let report = CampaignReport::load(path)?;
+        let index = finding.unwrap_or(1);
+        let (file, finding) = report.files.iter()
+            .flat_map(|file| file.findings.iter().map(move |finding| (file, finding)))
+            .nth(index.saturating_sub(1))
+            .ok_or_else(|| anyhow!("{} has no finding #{}", path.display(), index))?;
+        if finding.signature.is_empty() {
+            bail!("Finding #{} of {} has no signature (report written by an older version)", index, path.display());
+        }
+        Ok(Self {
+            source_file: Some(file.file.clone()),
+            contract: finding.contract.clone(),
+            signature: finding.signature.clone(),
+            seed: Some(finding.seed),
+            sender: Some(finding.sender.clone()),
+            args: (!finding.args.is_empty()).then(|| finding.args.clone()),
+            value: finding.value.clone(),
+        })
+    }
+}
+
+/// JSON form of a value: integers as decimal strings, bytes as hex, arrays as arrays, structs as objects
+pub fn value_to_json(value: &SolidityValue) -> Value {
+    match value {
+        SolidityValue::Uint8(v) => Value::String(v.to_string()),
+        SolidityValue::Uint16(v) => Value::String(v.to_string()),
+        SolidityValue::Uint32(v) => Value::String(v.to_string()),
+        SolidityValue::Uint64(v) => Value::String(v.to_string()),
+        SolidityValue::Uint128(v) => Value::String(v.to_string()),
+        SolidityValue::Int8(v) => Value::String(v.to_string()),
+        SolidityValue::Int16(v) => Value::String(v.to_string()),
+        SolidityValue::Int32(v) => Value::String(v.to_string()),
+        SolidityValue::Int64(v) => Value::String(v.to_string()),
+        SolidityValue::Int128(v) => Value::String(v.to_string()),
+        SolidityValue::Uint256(v) | SolidityValue::Int256(v) | SolidityValue::Address(v) | SolidityValue::String(v) => Value::String(v.clone()),
+        SolidityValue::Bool(v) => Value::Bool(*v),
+        SolidityValue::Bytes1(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes2(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes4(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes8(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes16(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes32(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Bytes(v) => Value::String(format!("0x{}", hex::encode(v))),
+        SolidityValue::Array(items) | SolidityValue::FixedArray(items) => Value::Array(items.iter().map(value_to_json).collect()),
+        SolidityValue::Struct(fields) => Value::Object(fields.iter().map(|(name, value)| (name.clone(), value_to_json(value))).collect()),
+    }
+}
+
+/// Parse the JSON form of a value of `param_type`
+pub fn value_from_json(param_type: &SolidityType, value: &Value) -> Result<SolidityValue> {
+    let text = match (param_type, value) {
+        (SolidityType::Array(inner), Value::Array(items)) => {
+            return Ok(SolidityValue::Array(items.iter().map(|item| value_from_json(inner, item)).collect::<Result<_>>()?));
+        }
+        (SolidityType::FixedArray(inner, length), Value::Array(items)) => {
+            if items.len() != *length {
+                bail!("{} takes {} element(s), got {}", param_type.abi_type_string(), length, items.len());
+            }
+            return Ok(SolidityValue::FixedArray(items.iter().map(|item| value_from_json(inner, item)).collect::<Result<_>>()?));
+        }
+        (SolidityType::String, Value::String(text)) => return Ok(SolidityValue::String(text.clone())),
+        (_, Value::String(text)) => text.clone(),
+        (_, Value::Number(number)) => number.to_string(),
+        (_, Value::Bool(flag)) => flag.to_string(),
+        _ => bail!("cannot read {} as {}", value, param_type.abi_type_string()),
+    };
+    hooks::parse_value(param_type, &text)
+        .ok_or_else(|| anyhow!("invalid {} value {}", param_type.abi_type_string(), value))
+}
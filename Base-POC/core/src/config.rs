@@ -0,0 +1,403 @@
+use crate::address_pool::AddressWeights;
+use crate::explorer::Explorer;
+use crate::invariants::InvariantSchedule;
+use crate::keys::AccountKey;
+use crate::oracle::SolidityVersion;
+use crate::types::{ContractMethod, TxFees};
+use crate::value_strategy::ParameterStrategy;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Where transactions are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// JSON-RPC against an Anvil (fork) node
+    #[default]
+    Anvil,
+    /// In-process revm on an empty in-memory chain
+    Revm,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "anvil" => Ok(Self::Anvil),
+            "revm" => Ok(Self::Revm),
+            other => Err(format!("unknown backend '{}' (expected anvil or revm)", other)),
+        }
+    }
+}
+
+/// Where the list of methods to fuzz and their parameter types come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MethodSource {
+    /// The parsed Solidity source
+    #[default]
+    Source,
+    /// The compiled ABI, falling back to the source when there is none (a dry run without a compiler)
+    Abi,
+}
+
+impl FromStr for MethodSource {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "source" => Ok(Self::Source),
+            "abi" => Ok(Self::Abi),
+            other => Err(format!("unknown method source '{}' (expected source or abi)", other)),
+        }
+    }
+}
+
+/// Which compiler builds the contracts under test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompilerBackend {
+    /// forge when it is in PATH, otherwise the solc command line
+    #[default]
+    Auto,
+    Forge,
+    /// `solc --combined-json`, without import resolution
+    Solc,
+    /// Standard JSON input to `solc --standard-json` (or `solcjs`), with the imports, remappings and
+    /// optimizer settings put together by Fuzzhead
+    StandardJson,
+}
+
+impl FromStr for CompilerBackend {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "forge" => Ok(Self::Forge),
+            "solc" => Ok(Self::Solc),
+            "standard-json" => Ok(Self::StandardJson),
+            other => Err(format!("unknown compiler '{}' (expected auto, forge, solc or standard-json)", other)),
+        }
+    }
+}
+
+/// Which solc compiles the contracts when Fuzzhead runs solc itself (not through forge)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SolcSelection {
+    /// The solc in PATH when it satisfies the file's pragma, otherwise a managed one that does
+    #[default]
+    Auto,
+    /// Always the solc in PATH
+    Path,
+    /// This managed release, e.g. `0.8.20`
+    Version(SolidityVersion),
+}
+
+impl FromStr for SolcSelection {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "path" => Ok(Self::Path),
+            other => other.trim_start_matches('v').parse::<semver::Version>()
+                .map(|version| Self::Version(SolidityVersion {
+                    major: version.major as u32,
+                    minor: version.minor as u32,
+                    patch: version.patch as u32,
+                }))
+                .map_err(|_| format!("unknown solc version '{}' (expected auto, path or a version such as 0.8.20)", other)),
+        }
+    }
+}
+
+/// How contracts are compiled; the optimizer, remapping and EVM settings apply to the standard-JSON backend
+#[derive(Debug, Clone)]
+pub struct CompilerOptions {
+    pub backend: CompilerBackend,
+    pub solc: SolcSelection,
+    pub optimizer: bool,
+    pub optimizer_runs: u32,
+    /// Import remappings, `[context:]prefix=target`, on top of the project's `remappings.txt`
+    pub remappings: Vec<String>,
+    /// EVM version to compile for (the compiler's default when unset)
+    pub evm_version: Option<String>,
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        Self {
+            backend: CompilerBackend::default(),
+            solc: SolcSelection::default(),
+            optimizer: true,
+            optimizer_runs: 200,
+            remappings: Vec::new(),
+            evm_version: None,
+        }
+    }
+}
+
+/// Which accounts the executor sends from
+#[derive(Debug, Clone, Default)]
+pub struct AccountOptions {
+    /// Use at most this many accounts (all accounts the node reports by default)
+    pub count: Option<usize>,
+    /// Derive sender accounts from this mnemonic instead of using the node's unlocked accounts.
+    /// Derived accounts the node does not manage are impersonated and funded through Anvil cheats.
+    pub mnemonic: Option<String>,
+    /// Sign transactions locally with these keys (`eth_sendRawTransaction`) instead of sending from
+    /// the node's accounts. The accounts must already hold ETH for gas.
+    pub keys: Vec<AccountKey>,
+    /// On-chain accounts (token whales, owners, governance) impersonated through the node and sent
+    /// from next to the regular senders; their balances are kept, topped up to 1 ETH for gas
+    pub impersonate: Vec<String>,
+}
+
+impl AccountOptions {
+    /// Keys that send the fuzzed calls, in the order given
+    pub fn sender_keys(&self) -> impl Iterator<Item = &AccountKey> {
+        self.keys.iter().filter(|key| key.sends())
+    }
+
+    /// The key with the deployer role, which deploys every contract instead of the current sender
+    pub fn deployer_key(&self) -> Option<&AccountKey> {
+        self.keys.iter().find(|key| key.deploys())
+    }
+}
+
+/// EIP-1559 fee settings for sent transactions
+#[derive(Debug, Clone, Default)]
+pub struct FeeOptions {
+    /// Fixed `maxFeePerGas` in wei (the node chooses fees when unset)
+    pub max_fee_per_gas: Option<u128>,
+    /// Fixed `maxPriorityFeePerGas` in wei
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Draw fresh fees relative to the current base fee for every fuzz iteration
+    pub fuzz: bool,
+}
+
+impl FeeOptions {
+    /// The fixed fees, if both were given
+    pub fn fixed(&self) -> Option<TxFees> {
+        Some(TxFees {
+            max_fee_per_gas: self.max_fee_per_gas?,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas?,
+        })
+    }
+}
+
+/// Campaign-wide settings shared by the fuzzer and its executor
+#[derive(Debug, Clone)]
+pub struct FuzzConfig {
+    /// Executor backend for exploration (confirmation always runs on Anvil)
+    pub backend: Backend,
+    pub compiler: CompilerOptions,
+    /// RPC URL of the Anvil fork
+    pub fork_url: String,
+    /// How many times a failing input is re-executed on a clean snapshot before it is reported.
+    /// Failures that do not reproduce every time are reported as flaky. 0 disables confirmation.
+    pub confirm_runs: usize,
+    /// Per-request timeout for JSON-RPC calls, in seconds
+    pub rpc_timeout_secs: u64,
+    /// How long a transaction may take to be mined before waiting for its receipt fails, in seconds
+    pub receipt_timeout_secs: u64,
+    /// Enable Anvil throughput cheats (auto-impersonation, zero base fee, instant mining, no gas limit)
+    pub turbo: bool,
+    /// Dual-phase campaign: explore on `fork_url` with turbo cheats, then replay candidate findings
+    /// on this faithful (non-turbo) Anvil fork and only report the ones that reproduce there
+    pub confirm_fork_url: Option<String>,
+    /// Exercise parsing, generation and encoding against a mock executor without touching the network
+    pub dry_run: bool,
+    /// Decode every encoded call with `ethers::abi::decode` and report mismatches as tool errors
+    pub verify_encoding: bool,
+    /// Upstream block Anvil forks are pinned to (`None` keeps whatever block they fork)
+    pub fork_block_number: Option<u64>,
+    /// Fuzz runs per method
+    pub runs_per_method: usize,
+    /// Glob patterns of contract names left out of the campaign
+    pub exclude_contracts: Vec<String>,
+    /// Fuzz every concrete contract of a file, each with the methods it inherits from the file's
+    /// other declarations; otherwise only the first one, with the methods it declares itself
+    pub all_contracts: bool,
+    /// Glob patterns of functions left out: a name, a signature, or either prefixed with `Contract.`
+    pub exclude_functions: Vec<String>,
+    /// Constructor arguments per contract name or glob, as literals; contracts without an entry are prompted for
+    pub constructor_args: BTreeMap<String, Vec<String>>,
+    pub accounts: AccountOptions,
+    /// Replace stuck pending transactions (nonce gaps) with higher-priced no-ops when detected
+    pub repair_nonce_gaps: bool,
+    pub fees: FeeOptions,
+    /// Master seed for input generation; a random one is drawn (and reported) when unset
+    pub seed: Option<u64>,
+    /// Fuzz coinbase, base fee and chain id through Anvil cheats and flag inputs whose outcome depends on them
+    pub fuzz_block_context: bool,
+    /// Probe some passing inputs under a gas limit near their usage and flag state that differs from the full-gas run
+    pub fuzz_gas_limit: bool,
+    /// Compare the target's ETH balance with its accounting variables (from the storage layout) after every call
+    pub value_accounting: bool,
+    /// How often each invariant is evaluated (per call, per sequence or every N calls)
+    pub invariant_schedule: InvariantSchedule,
+    /// Trace every call for branch coverage and mutate the inputs that reach new branches
+    pub coverage_guided: bool,
+    /// Mutate inputs that ended a method in a new way (first success, new revert reason) instead of
+    /// always drawing fresh values; with coverage guidance the corpus is the coverage one
+    pub mutation: bool,
+    /// Keep every method's corpus in the summary, to be written out with `--export-corpus`
+    pub export_corpus: bool,
+    /// Send exploration calls in Multicall3 batches of this many calls (0 sends each call on its own).
+    /// Failures found in a batch are only reported once an individual transaction reproduces them.
+    pub multicall_batch: usize,
+    /// Multicall3 contract the batches are sent through
+    pub multicall_address: String,
+    /// Stateful sequences of calls across a contract's methods to run after the per-method phase (0 disables them)
+    pub sequences: usize,
+    /// Calls per stateful sequence; a sequence ends early at its first failing call
+    pub sequence_length: usize,
+    /// Warp time, mine blocks and deal ETH to senders between the calls of stateful sequences
+    pub cheats: bool,
+    /// Warp time and mine blocks before each stateful sequence, so it runs at a later point in the contract's life
+    pub time_jumps: bool,
+    /// Flag calls that use more gas than this; calls that use all the gas they were sent with are always flagged
+    pub gas_threshold: Option<u64>,
+    /// Print min/mean/max gas per call of every method after each contract
+    pub gas_report: bool,
+    /// Re-executions spent shrinking each deterministic failure to a minimal input (0 disables shrinking)
+    pub shrink_runs: usize,
+    /// Percentage of runs sent from a contract's owner when its owner getter returns an account the
+    /// fuzzer does not control, impersonated through the node (0 disables it)
+    pub owner_percent: u32,
+    /// Impersonate the accounts held in each deployed contract's owner and admin variables and
+    /// send from them like from the regular senders
+    pub discover_senders: bool,
+    /// Let the state each fuzzed call leaves behind carry into the next iteration instead of
+    /// restoring a snapshot after every iteration
+    pub keep_state: bool,
+    /// Longest an argument is printed in console output, in terminal columns (0 prints arguments in full)
+    pub max_arg_display_len: usize,
+    /// Where the methods to fuzz are discovered
+    pub method_source: MethodSource,
+    /// Deploy an attacker contract, pass it as address arguments and flag calls it can re-enter
+    pub reentrancy: bool,
+    /// Expect privileged methods to revert for senders other than the deployer and owner, and flag
+    /// those that succeed and change owner or admin state
+    pub access_control: bool,
+    /// Decode the events of calls and report ones no correct call emits (mints to address(0),
+    /// ownership handed by a stranger to an address it chose)
+    pub events: bool,
+    /// Revert reason fragments counted as expected reverts, on top of `oracle::DEFAULT_EXPECTED_REVERTS`
+    pub expected_reverts: Vec<String>,
+    /// Panic codes reported as findings even when checked math or an expected-revert pattern
+    /// would expect them, on top of assertion failures
+    pub bug_panics: Vec<u64>,
+    /// Block explorer the verified ABIs of proxy implementations are fetched from
+    pub explorer: Option<Explorer>,
+    /// Value strategies of single parameters; the first one matching a parameter is used
+    pub value_strategies: Vec<ParameterStrategy>,
+    /// How often each source of the address pool is drawn for address arguments
+    pub address_weights: AddressWeights,
+}
+
+impl FuzzConfig {
+    pub fn is_dual_phase(&self) -> bool {
+        self.confirm_fork_url.is_some()
+    }
+
+    pub fn excludes_contract(&self, contract_name: &str) -> bool {
+        self.exclude_contracts.iter().any(|pattern| matches_pattern(pattern, contract_name))
+    }
+
+    /// Configured constructor arguments of a contract: its own entry, or the first glob entry matching it
+    pub fn constructor_args_for(&self, contract_name: &str) -> Option<&[String]> {
+        self.constructor_args.get(contract_name)
+            .or_else(|| self.constructor_args.iter()
+                .find(|(pattern, _)| matches_pattern(pattern, contract_name))
+                .map(|(_, values)| values))
+            .map(Vec::as_slice)
+    }
+
+    pub fn excludes_method(&self, contract_name: &str, method: &ContractMethod) -> bool {
+        self.exclude_functions.iter().any(|pattern| matches_method(pattern, contract_name, method))
+    }
+
+    /// The value strategy of the parameter of `method` at `index`, if one is configured
+    pub fn value_strategy(&self, contract_name: &str, method: &ContractMethod, index: usize) -> Option<&ParameterStrategy> {
+        let parameter = method.parameters.get(index)?;
+        self.value_strategies.iter()
+            .find(|strategy| strategy.targets(index, parameter) && matches_method(&strategy.method, contract_name, method))
+    }
+}
+
+/// Whether a function pattern matches the method's name or signature, either prefixed with `Contract.` or not
+fn matches_method(pattern: &str, contract_name: &str, method: &ContractMethod) -> bool {
+    let signature = crate::abi_encoding::method_signature(method);
+    let candidates = [
+        method.name.clone(),
+        format!("{}.{}", contract_name, method.name),
+        format!("{}.{}", contract_name, signature),
+        signature,
+    ];
+    candidates.iter().any(|candidate| matches_pattern(pattern, candidate))
+}
+
+/// Glob match, falling back to equality for patterns that are not valid globs (`f(uint256[])`)
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    match glob::Pattern::new(pattern) {
+        Ok(glob) => glob.matches(text) || pattern == text,
+        Err(_) => pattern == text,
+    }
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            backend: Backend::default(),
+            compiler: CompilerOptions::default(),
+            fork_url: "http://localhost:8545".to_string(),
+            confirm_runs: 3,
+            rpc_timeout_secs: 30,
+            receipt_timeout_secs: 60,
+            turbo: false,
+            confirm_fork_url: None,
+            dry_run: false,
+            verify_encoding: true,
+            fork_block_number: None,
+            runs_per_method: 100,
+            exclude_contracts: Vec::new(),
+            all_contracts: false,
+            exclude_functions: Vec::new(),
+            constructor_args: BTreeMap::new(),
+            accounts: AccountOptions::default(),
+            repair_nonce_gaps: false,
+            fees: FeeOptions::default(),
+            seed: None,
+            fuzz_block_context: false,
+            fuzz_gas_limit: false,
+            value_accounting: false,
+            invariant_schedule: InvariantSchedule::default(),
+            coverage_guided: false,
+            mutation: false,
+            export_corpus: false,
+            multicall_batch: 0,
+            multicall_address: crate::multicall::MULTICALL3_ADDRESS.to_string(),
+            sequences: 0,
+            sequence_length: 10,
+            cheats: false,
+            time_jumps: false,
+            gas_threshold: None,
+            gas_report: false,
+            shrink_runs: 100,
+            owner_percent: 10,
+            discover_senders: false,
+            keep_state: false,
+            max_arg_display_len: crate::display::DEFAULT_MAX_ARG_LEN,
+            method_source: MethodSource::default(),
+            reentrancy: false,
+            access_control: false,
+            events: false,
+            expected_reverts: Vec::new(),
+            bug_panics: Vec::new(),
+            explorer: None,
+            value_strategies: Vec::new(),
+            address_weights: AddressWeights::default(),
+        }
+    }
+}
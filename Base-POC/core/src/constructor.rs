@@ -0,0 +1,160 @@
+use anyhow::Result;
+use crate::types::MethodParameter;
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Input};
+use ethers::abi::token::{LenientTokenizer, Tokenizer};
+use ethers::abi::{Abi, ParamType, Token};
+use ethers::types::{Address, I256, U256};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+pub fn prompt_for_constructor_args(abi: &Abi, contract_name: &str) -> Result<Vec<Token>> {
+    let constructor = match abi.constructor() {
+        Some(c) => c,
+        None => {
+            println!("{} No constructor found for {}. Proceeding with empty args.", "ℹ".blue(), contract_name);
+            return Ok(vec![]);
+        }
+    };
+
+    if constructor.inputs.is_empty() {
+        return Ok(vec![]);
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("{} needs {} constructor argument(s) and stdin is not a terminal to prompt for them; \
+            pass --constructor-args or add them to [constructor_args] in fuzzhead.toml", contract_name, constructor.inputs.len());
+    }
+
+    println!("\n{} Deployment requires arguments for '{}':", "➤".yellow(), contract_name.bold());
+
+    let mut args = Vec::new();
+
+    for (index, input) in constructor.inputs.iter().enumerate() {
+        let arg_name = if input.name.is_empty() {
+            MethodParameter::placeholder_name(index)
+        } else {
+            input.name.clone()
+        };
+        let arg_type = &input.kind;
+
+        // Prompt the user for this specific argument
+        let token = prompt_single_arg(&arg_name, arg_type)?;
+        args.push(token);
+    }
+
+    println!("{} Arguments captured successfully!", "✔".green());
+    Ok(args)
+}
+
+/// Constructor arguments of `--constructor-args`: a JSON array used for every contract
+/// (`["1000", "0xab…"]`), or an object of arrays per contract name or glob (`{"Vault": [1000, true]}`).
+/// Numbers and bools may be bare and nested arrays become array literals; tuples are given as
+/// strings (`"(0xab…,5)"`).
+pub fn parse_constructor_args_json(json: &str) -> std::result::Result<BTreeMap<String, Vec<String>>, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("invalid JSON: {}", e))?;
+    let literals = |values: &[Value]| values.iter().map(json_literal).collect::<std::result::Result<Vec<_>, _>>();
+    match value {
+        Value::Array(values) => Ok(BTreeMap::from([("*".to_string(), literals(&values)?)])),
+        Value::Object(contracts) => contracts.iter()
+            .map(|(contract, values)| match values {
+                Value::Array(values) => Ok((contract.clone(), literals(values)?)),
+                _ => Err(format!("arguments of {} must be an array", contract)),
+            })
+            .collect(),
+        _ => Err("expected an array of arguments or an object of arrays per contract".to_string()),
+    }
+}
+
+/// A JSON argument as the literal `LenientTokenizer` reads
+fn json_literal(value: &Value) -> std::result::Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        Value::Array(values) => {
+            let elements = values.iter().map(json_literal).collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok(format!("[{}]", elements.join(",")))
+        }
+        other => Err(format!("unsupported argument {}", other)),
+    }
+}
+
+/// Constructor arguments given as literals (configuration or command line) instead of at the prompt
+pub fn parse_constructor_args(abi: &Abi, contract_name: &str, values: &[String]) -> Result<Vec<Token>> {
+    let inputs = abi.constructor().map(|constructor| constructor.inputs.as_slice()).unwrap_or_default();
+    if values.len() != inputs.len() {
+        anyhow::bail!("{} constructor takes {} argument(s), {} given", contract_name, inputs.len(), values.len());
+    }
+    inputs.iter().zip(values).enumerate()
+        .map(|(index, (input, value))| {
+            LenientTokenizer::tokenize(&input.kind, value)
+                .map_err(|e| anyhow::anyhow!("argument {} ({}) of {}: {}", index + 1, input.kind, contract_name, e))
+        })
+        .collect()
+}
+
+fn prompt_single_arg(name: &str, kind: &ParamType) -> Result<Token> {
+    let type_str = format!("{}", kind).dimmed();
+    let prompt_text = format!("Enter value for {} ({})", name.bold(), type_str);
+
+    match kind {
+        ParamType::Address => {
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(&prompt_text)
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    Address::from_str(input).map(|_| ()).map_err(|_| "Invalid address format (must start with 0x...)")
+                })
+                .interact_text()?;
+            Ok(Token::Address(Address::from_str(&input)?))
+        }
+
+        ParamType::Uint(_) => {
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(&prompt_text)
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    U256::from_dec_str(input).map(|_| ()).map_err(|_| "Invalid number")
+                })
+                .interact_text()?;
+            Ok(Token::Uint(U256::from_dec_str(&input)?))
+        }
+
+        ParamType::Int(_) => {
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(&prompt_text)
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    I256::from_dec_str(input).map(|_| ()).map_err(|_| "Invalid signed number")
+                })
+                .interact_text()?;
+            Ok(Token::Int(I256::from_dec_str(&input)?.into_raw()))
+        }
+
+        ParamType::String => {
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(&prompt_text)
+                .interact_text()?;
+            Ok(Token::String(input))
+        }
+
+        ParamType::Bool => {
+            let input: bool = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Set {} to true?", name))
+                .interact()?;
+            Ok(Token::Bool(input))
+        }
+
+        // Bytes, arrays and tuples in Solidity literal syntax, e.g. `0xdeadbeef`, `[1,2]`, `(0xab..,5)`
+        _ => {
+            println!("{} Enter {} as a literal: 0x-hex for bytes, [a,b] for arrays, (a,b) for tuples", "ℹ".blue(), kind);
+            let kind_for_validation = kind.clone();
+            let input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(&prompt_text)
+                .validate_with(move |input: &String| -> Result<(), String> {
+                    LenientTokenizer::tokenize(&kind_for_validation, input).map(|_| ()).map_err(|e| e.to_string())
+                })
+                .interact_text()?;
+            Ok(LenientTokenizer::tokenize(kind, &input)?)
+        }
+    }
+}
\ No newline at end of file
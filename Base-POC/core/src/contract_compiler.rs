@@ -0,0 +1,656 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use tracing::{debug, warn};
+use ethers::abi::Abi;
+use crate::types::CompileInfo;
+use crate::accounting;
+use crate::config::{CompilerBackend, CompilerOptions};
+use crate::foundry_project::ProjectArtifacts;
+use crate::standard_json::{self, SourceTree};
+use crate::deployment;
+use crate::linking;
+use crate::oracle::SolidityVersion;
+use std::sync::{Arc, OnceLock};
+
+pub struct ContractCompiler {
+    forge_path: Option<String>,
+    solc_path: Option<String>,
+    solcjs_path: Option<String>,
+    /// Version of the solc in PATH, read on first use
+    path_solc_version: OnceLock<Option<SolidityVersion>>,
+    /// A managed solc matching the current file's pragma, used instead of the one in PATH
+    managed_solc: Option<String>,
+    options: CompilerOptions,
+    /// Artifacts of a project built once up front, looked up before compiling anything
+    project: Option<Arc<ProjectArtifacts>>,
+}
+
+impl ContractCompiler {
+    pub fn new() -> Self {
+        Self::with_options(CompilerOptions::default())
+    }
+
+    /// A compiler using the backend and settings of `options`
+    pub fn with_options(options: CompilerOptions) -> Self {
+        let compiler = Self { options, ..Self::locate() };
+        
+        match compiler.options.backend {
+            CompilerBackend::Auto if compiler.forge_path.is_none() && compiler.solc_path.is_none() => {
+                warn!("Neither 'forge' nor 'solc' found in PATH. Contract compilation will fail.");
+                warn!("Install Foundry: curl -L https://foundry.paradigm.xyz | bash && foundryup");
+                warn!("Or install solc: https://docs.soliditylang.org/en/latest/installing-solidity.html");
+            }
+            CompilerBackend::Forge if compiler.forge_path.is_none() => {
+                warn!("'forge' not found in PATH. Contract compilation will fail.");
+            }
+            CompilerBackend::Solc if compiler.solc_path.is_none() => {
+                warn!("'solc' not found in PATH. Contract compilation will fail.");
+            }
+            CompilerBackend::StandardJson if compiler.standard_json_path().is_none() => {
+                warn!("Neither 'solc' nor 'solcjs' found in PATH. Contract compilation will fail.");
+            }
+            _ => {}
+        }
+        
+        compiler
+    }
+
+    /// Find forge and solc in PATH without warning when neither is there
+    pub fn locate() -> Self {
+        Self {
+            forge_path: Self::find_executable("forge"),
+            solc_path: Self::find_executable("solc"),
+            solcjs_path: Self::find_executable("solcjs"),
+            path_solc_version: OnceLock::new(),
+            managed_solc: None,
+            options: CompilerOptions::default(),
+            project: None,
+        }
+    }
+
+    /// Take contracts from these prebuilt artifacts instead of compiling their source files
+    pub fn use_project_artifacts(&mut self, artifacts: Arc<ProjectArtifacts>) {
+        self.project = Some(artifacts);
+    }
+    
+    /// Path of the `forge` binary, if found in PATH
+    pub fn forge_path(&self) -> Option<&str> {
+        self.forge_path.as_deref()
+    }
+
+    /// Path of the `solc` binary, if found in PATH
+    pub fn solc_path(&self) -> Option<&str> {
+        self.solc_path.as_deref()
+    }
+
+    /// Compiler the standard-JSON backend runs: `solc`, else `solcjs`
+    pub fn standard_json_path(&self) -> Option<&str> {
+        self.solc().or(self.solcjs_path.as_deref())
+    }
+
+    /// The solc compilations run: the managed one when set, else the one in PATH
+    fn solc(&self) -> Option<&str> {
+        self.managed_solc.as_deref().or(self.solc_path.as_deref())
+    }
+
+    /// Whether compilations run solc directly rather than through forge, which picks its own solc
+    pub fn runs_solc(&self) -> bool {
+        match self.options.backend {
+            CompilerBackend::Auto => self.forge_path.is_none(),
+            CompilerBackend::Forge => false,
+            CompilerBackend::Solc | CompilerBackend::StandardJson => true,
+        }
+    }
+
+    /// Version of the solc in PATH, if there is one and it tells
+    pub fn path_solc_version(&self) -> Option<SolidityVersion> {
+        *self.path_solc_version.get_or_init(|| {
+            let output = Command::new(self.solc_path.as_deref()?).arg("--version").output().ok()?;
+            String::from_utf8_lossy(&output.stdout).lines()
+                .find_map(|line| line.strip_prefix("Version:"))
+                .and_then(SolidityVersion::parse)
+        })
+    }
+
+    /// Compile with this solc binary instead of the one in PATH (`None` goes back to PATH's);
+    /// whether that changed the solc in use
+    pub fn use_managed_solc(&mut self, path: Option<String>) -> bool {
+        let changed = self.managed_solc != path;
+        self.managed_solc = path;
+        changed
+    }
+
+    pub fn compile_contract(&self, source_path: &Path, contract_name: &str) -> Result<Vec<u8>> {
+        let (bytecode, _abi) = self.compile_contract_with_abi(source_path, contract_name)?;
+        Ok(bytecode)
+    }
+    
+    pub fn compile_contract_with_abi(&self, source_path: &Path, contract_name: &str) -> Result<(Vec<u8>, Abi)> {
+        let (bytecode, abi, _info) = self.compile_contract_with_info(source_path, contract_name)?;
+        Ok((bytecode, abi))
+    }
+    
+    /// Compile a contract and also report which compiler backend, solc version and EVM version were used
+    pub fn compile_contract_with_info(&self, source_path: &Path, contract_name: &str) -> Result<(Vec<u8>, Abi, CompileInfo)> {
+        if let Some(artifact) = self.project.as_ref().and_then(|project| project.get(source_path, contract_name)) {
+            debug!("Using the project build of {} from {:?}", contract_name, source_path);
+            return Ok((artifact.bytecode.clone(), artifact.abi.clone(), artifact.info.clone()));
+        }
+
+        match self.options.backend {
+            CompilerBackend::Auto => {}
+            CompilerBackend::Forge if self.forge_path.is_none() => {
+                return Err(anyhow::anyhow!("The forge compiler backend was chosen, but forge is not in PATH"));
+            }
+            CompilerBackend::Forge => {}
+            CompilerBackend::Solc => {
+                let solc = self.solc()
+                    .context("The solc compiler backend was chosen, but solc is not in PATH")?;
+                return self.compile_with_solc_full(source_path, contract_name, solc);
+            }
+            CompilerBackend::StandardJson => {
+                let compiler = self.standard_json_path()
+                    .context("The standard-json compiler backend needs solc or solcjs in PATH")?;
+                return self.compile_with_standard_json(source_path, contract_name, compiler);
+            }
+        }
+
+        if let Some(ref forge) = self.forge_path {
+            // Make path absolute if it's relative
+            let abs_source_path = if source_path.is_absolute() {
+                source_path.to_path_buf()
+            } else {
+                std::env::current_dir()?
+                    .join(source_path)
+                    .canonicalize()
+                    .unwrap_or_else(|_| std::env::current_dir().unwrap().join(source_path))
+            };
+            
+            if let Some(project_root) = Self::find_foundry_project_root(&abs_source_path) {
+                debug!("Found Foundry project root at: {:?}", project_root);
+                return self.compile_with_forge_inplace(&abs_source_path, contract_name, &project_root, forge);
+            }
+            // Fall back to temp project approach
+            return self.compile_with_forge_full(&abs_source_path, contract_name, forge);
+        }
+        
+        if let Some(solc) = self.solc() {
+            return self.compile_with_solc_full(source_path, contract_name, solc);
+        }
+        
+        Err(anyhow::anyhow!(
+            "No compiler available. Install Foundry (forge) or solc."
+        ))
+    }
+    
+    fn find_foundry_project_root(source_path: &Path) -> Option<PathBuf> {
+        let mut current = if source_path.is_file() {
+            source_path.parent()?
+        } else {
+            source_path
+        };
+        
+        loop {
+            let foundry_toml = current.join("foundry.toml");
+            let remappings_txt = current.join("remappings.txt");
+            
+            if foundry_toml.exists() || remappings_txt.exists() {
+                return Some(current.to_path_buf());
+            }
+            
+            // Move up one directory
+            match current.parent() {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        
+        None
+    }
+    
+    /// Compile contract in-place from a Foundry project root to preserve remappings, libs, and foundry.toml configuration
+    fn compile_with_forge_inplace(
+        &self,
+        source_path: &Path,
+        contract_name: &str,
+        project_root: &Path,
+        forge_path: &str,
+    ) -> Result<(Vec<u8>, Abi, CompileInfo)> {
+        debug!("Compiling {} with forge in-place from project root: {:?}", contract_name, project_root);
+        
+        // Ensure source_path is relative to project_root or absolute
+        let source_path_abs = if source_path.is_absolute() {
+            source_path.to_path_buf()
+        } else {
+            // If relative, try to resolve it relative to project_root
+            project_root.join(source_path)
+        };
+        
+        // Verify the source file exists
+        if !source_path_abs.exists() {
+            return Err(anyhow::anyhow!(
+                "Source file not found: {:?} (resolved from {:?} in project {:?})",
+                source_path_abs,
+                source_path,
+                project_root
+            ));
+        }
+        
+        let relative_source = source_path_abs
+            .strip_prefix(project_root)
+            .map(source_unit_name)
+            .unwrap_or_else(|_| source_path_abs.to_string_lossy().to_string());
+        
+        let output = Command::new(forge_path)
+            .args(["build", "--force", "--extra-output", "storageLayout", &relative_source])
+            .current_dir(project_root)
+            .output()
+            .context("Failed to execute forge build")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(anyhow::anyhow!(
+                "Forge compilation failed (project root: {:?}):\nSTDOUT: {}\nSTDERR: {}",
+                project_root,
+                stdout,
+                stderr
+            ));
+        }
+        
+        let file_stem = source_path_abs.file_stem()
+            .and_then(|s| s.to_str())
+            .context("Invalid source file name")?;
+        
+        let artifact_path = if let Ok(relative_path) = source_path_abs.strip_prefix(project_root) {
+            // Path prefixes compare by component, so this holds for `src\` on Windows too
+            let path_after_src = relative_path.strip_prefix("src").unwrap_or(relative_path);
+            
+            project_root
+                .join("out")
+                .join(path_after_src.parent().unwrap_or(Path::new("")))
+                .join(format!("{}.sol", file_stem))
+                .join(format!("{}.json", contract_name))
+        } else {
+            // Fallback: try direct path
+            project_root
+                .join("out")
+                .join(format!("{}.sol", file_stem))
+                .join(format!("{}.json", contract_name))
+        };
+        
+        let artifact_path = if !artifact_path.exists() {
+            let out_dir = project_root.join("out");
+            if out_dir.exists() {
+                Self::find_artifact_in_out(&out_dir, file_stem, contract_name)?
+            } else {
+                return Err(anyhow::anyhow!(
+                    "Compiled artifact not found at: {:?} and out/ directory does not exist",
+                    artifact_path
+                ));
+            }
+        } else {
+            artifact_path
+        };
+        
+        let artifact_content = std::fs::read_to_string(&artifact_path)?;
+        let artifact: Value = serde_json::from_str(&artifact_content)
+            .context("Failed to parse forge artifact JSON")?;
+        
+        let bytecode_hex = artifact
+            .get("bytecode")
+            .and_then(|v| v.get("object"))
+            .and_then(|v| v.as_str())
+            .or_else(|| {
+                artifact.get("bytecode").and_then(|v| v.as_str())
+            })
+            .context("Bytecode not found in artifact")?;
+        
+        let abi_value = artifact
+            .get("abi")
+            .context("ABI not found in artifact")?;
+        
+        let abi: Abi = serde_json::from_value(abi_value.clone())
+            .context("Failed to parse ABI")?;
+        
+        let libraries = linking::referenced_libraries(artifact.get("bytecode").and_then(|v| v.get("linkReferences")));
+        let (bytecode, links) = linking::decode(bytecode_hex, &libraries, |unit| project_root.join(unit))?;
+        let mut info = Self::compile_info_from_metadata(contract_name, "forge (project)", artifact.get("metadata"));
+        info.storage_layout = accounting::parse_storage_layout(artifact.get("storageLayout"));
+        info.libraries = links;
+        info.runtime_size = deployment::code_size(artifact.get("deployedBytecode"));
+        
+        Ok((bytecode, abi, info))
+    }
+    
+    /// Read solc and EVM versions from artifact metadata, which may be an object or a JSON string
+    pub(crate) fn compile_info_from_metadata(contract_name: &str, backend: &str, metadata: Option<&Value>) -> CompileInfo {
+        let parsed = match metadata {
+            Some(Value::String(raw)) => serde_json::from_str::<Value>(raw).ok(),
+            Some(value) => Some(value.clone()),
+            None => None,
+        };
+        
+        let compiler_version = parsed.as_ref()
+            .and_then(|m| m.get("compiler"))
+            .and_then(|c| c.get("version"))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        let evm_version = parsed.as_ref()
+            .and_then(|m| m.get("settings"))
+            .and_then(|s| s.get("evmVersion"))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        
+        CompileInfo {
+            contract: contract_name.to_string(),
+            backend: backend.to_string(),
+            compiler_version,
+            evm_version,
+            storage_layout: Vec::new(),
+            libraries: Vec::new(),
+            runtime_size: None,
+        }
+    }
+    
+    fn find_artifact_in_out(out_dir: &Path, file_stem: &str, contract_name: &str) -> Result<PathBuf> {
+        use std::fs;
+        
+        let candidate = out_dir
+            .join(format!("{}.sol", file_stem))
+            .join(format!("{}.json", contract_name));
+        
+        if candidate.exists() {
+            return Ok(candidate);
+        }
+        
+        if out_dir.is_dir() {
+            for entry in fs::read_dir(out_dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                
+                if path.is_dir() {
+                    if let Ok(found) = Self::find_artifact_in_out(&path, file_stem, contract_name) {
+                        return Ok(found);
+                    }
+                } else if path.is_file()
+                    && path.file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| n == format!("{}.json", contract_name))
+                        .unwrap_or(false)
+                    && path.parent()
+                        .and_then(|p| p.file_name())
+                        .and_then(|n| n.to_str())
+                        .map(|n| n == format!("{}.sol", file_stem))
+                        .unwrap_or(false)
+                {
+                    return Ok(path);
+                }
+            }
+        }
+        
+        Err(anyhow::anyhow!(
+            "Contract artifact not found: {}.sol/{}.json in {:?}",
+            file_stem,
+            contract_name,
+            out_dir
+        ))
+    }
+    
+    fn compile_with_forge_full(
+        &self,
+        source_path: &Path,
+        contract_name: &str,
+        forge_path: &str,
+    ) -> Result<(Vec<u8>, Abi, CompileInfo)> {
+        debug!("Compiling {} with forge", contract_name);
+        
+        let temp_dir = std::env::temp_dir().join(format!("fuzzhead_compile_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir)?;
+        std::fs::create_dir_all(temp_dir.join("src"))?;
+        
+        let file_name = source_path.file_name()
+            .with_context(|| format!("Source path has no file name: {}", source_path.display()))?;
+        let temp_source = temp_dir.join("src").join(file_name);
+        std::fs::copy(source_path, &temp_source)?;
+        
+        let _init_output = Command::new(forge_path)
+            .args(["init", "--force", "--no-git", "--no-commit"])
+            .current_dir(&temp_dir)
+            .output();
+        
+        let _ = std::fs::remove_file(temp_dir.join("src").join("Counter.sol"));
+        
+        let output = Command::new(forge_path)
+            .args(["build", "--force", "--extra-output", "storageLayout"])
+            .current_dir(&temp_dir)
+            .output()
+            .context("Failed to execute forge build")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(anyhow::anyhow!("Forge compilation failed:\nSTDOUT: {}\nSTDERR: {}", stdout, stderr));
+        }
+        
+        // Read the compiled artifact
+        // Forge artifact path: out/ContractName.sol/ContractName.json
+        let file_stem = source_path.file_stem().unwrap_or_default().to_string_lossy();
+        let artifact_path = temp_dir
+            .join("out")
+            .join(format!("{}.sol", file_stem))
+            .join(format!("{}.json", contract_name));
+        
+        if !artifact_path.exists() {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(anyhow::anyhow!("Compiled artifact not found at: {:?}", artifact_path));
+        }
+        
+        let artifact_content = std::fs::read_to_string(&artifact_path)?;
+        let artifact: Value = serde_json::from_str(&artifact_content)
+            .context("Failed to parse forge artifact JSON")?;
+        
+        // Extract bytecode (forge uses "bytecode" -> "object")
+        let bytecode_hex = artifact
+            .get("bytecode")
+            .and_then(|v| v.get("object"))
+            .and_then(|v| v.as_str())
+            .or_else(|| {
+                // Try alternative format
+                artifact.get("bytecode").and_then(|v| v.as_str())
+            })
+            .context("Bytecode not found in artifact")?;
+        
+        // Extract ABI
+        let abi_value = artifact
+            .get("abi")
+            .context("ABI not found in artifact")?;
+        
+        let abi: Abi = serde_json::from_value(abi_value.clone())
+            .context("Failed to parse ABI")?;
+        
+        let mut info = Self::compile_info_from_metadata(contract_name, "forge (temp project)", artifact.get("metadata"));
+        info.storage_layout = accounting::parse_storage_layout(artifact.get("storageLayout"));
+        
+        // Clean up temp directory
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        
+        // Decode hex to bytes; the temp project only holds the copied file, so libraries come from its directory
+        let libraries = linking::referenced_libraries(artifact.get("bytecode").and_then(|v| v.get("linkReferences")));
+        let source_dir = source_path.parent().unwrap_or(Path::new(""));
+        let (bytecode, links) = linking::decode(bytecode_hex, &libraries, |unit| {
+            source_dir.join(Path::new(unit).file_name().unwrap_or_default())
+        })?;
+        info.libraries = links;
+        info.runtime_size = deployment::code_size(artifact.get("deployedBytecode"));
+        
+        Ok((bytecode, abi, info))
+    }
+    
+    /// Compile using solc (Solidity compiler) and return both bytecode and ABI
+    fn compile_with_solc_full(
+        &self,
+        source_path: &Path,
+        contract_name: &str,
+        solc_path: &str,
+    ) -> Result<(Vec<u8>, Abi, CompileInfo)> {
+        debug!("Compiling {} with solc", contract_name);
+        
+        let output = Command::new(solc_path)
+            .args([
+                "--optimize",
+                "--combined-json", "bin,bin-runtime,abi,metadata",
+            ])
+            .arg(source_path)
+            .output()
+            .context("Failed to execute solc")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("Solc compilation failed: {}", stderr));
+        }
+        
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: Value = serde_json::from_str(&stdout)
+            .context("Failed to parse solc JSON output")?;
+        
+        // Extract bytecode from combined JSON
+        let contracts = json
+            .get("contracts")
+            .context("No 'contracts' in solc output")?;
+        
+        // Keys are "<source unit>:ContractName", where the source unit is the path as passed, with
+        // `/` separators even on Windows
+        let file_name = source_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let contract = contracts
+            .get(format!("{}:{}", file_name, contract_name))
+            .or_else(|| contracts.as_object()?.iter()
+                .find(|(key, _)| key.rsplit_once(':').is_some_and(|(unit, name)| {
+                    name == contract_name && unit.replace('\\', "/").rsplit('/').next() == Some(file_name)
+                }))
+                .map(|(_, contract)| contract))
+            .context(format!("Contract {} not found in compilation output", contract_name))?;
+        
+        let bytecode_hex = contract
+            .get("bin")
+            .and_then(|v| v.as_str())
+            .context("Bytecode not found in contract")?;
+        
+        let abi_str = contract
+            .get("abi")
+            .and_then(|v| v.as_str())
+            .context("ABI not found in contract")?;
+        
+        let abi: Abi = serde_json::from_str(abi_str)
+            .context("Failed to parse ABI")?;
+        
+        let mut info = Self::compile_info_from_metadata(contract_name, "solc", contract.get("metadata"));
+        
+        // Decode hex to bytes; combined JSON has no link references, so any contract of the output
+        // may be a library it links against
+        let libraries: Vec<(String, String)> = contracts.as_object().into_iter().flatten()
+            .filter_map(|(key, _)| key.rsplit_once(':'))
+            .map(|(unit, name)| (unit.to_string(), name.to_string()))
+            .collect();
+        let (bytecode, links) = linking::decode(bytecode_hex, &libraries, |unit| PathBuf::from(unit))?;
+        info.libraries = links;
+        info.runtime_size = deployment::code_size(contract.get("bin-runtime"));
+        
+        Ok((bytecode, abi, info))
+    }
+    
+    /// Compile through standard JSON input, with imports resolved from the project root (or the
+    /// file's directory) and the remappings and optimizer settings of the options
+    fn compile_with_standard_json(
+        &self,
+        source_path: &Path,
+        contract_name: &str,
+        compiler: &str,
+    ) -> Result<(Vec<u8>, Abi, CompileInfo)> {
+        debug!("Compiling {} with {} --standard-json", contract_name, compiler);
+
+        let source_path = source_path.canonicalize()
+            .with_context(|| format!("Source file not found: {:?}", source_path))?;
+        let base_dir = Self::find_foundry_project_root(&source_path)
+            .or_else(|| source_path.parent().map(Path::to_path_buf))
+            .context("Invalid source file path")?;
+        let tree = SourceTree::collect(&source_path, &base_dir, &self.options)?;
+        debug!("{} source unit(s) from {:?}", tree.sources.len(), base_dir);
+        let output = standard_json::compile(compiler, &tree.input(&self.options))?;
+
+        let contract = output.get("contracts")
+            .and_then(|contracts| contracts.get(&tree.root))
+            .and_then(|unit| unit.get(contract_name))
+            .context(format!("Contract {} not found in compilation output", contract_name))?;
+        let bytecode_hex = contract.get("evm")
+            .and_then(|evm| evm.get("bytecode"))
+            .and_then(|bytecode| bytecode.get("object"))
+            .and_then(|object| object.as_str())
+            .context("Bytecode not found in contract")?;
+        let abi: Abi = serde_json::from_value(contract.get("abi").cloned().context("ABI not found in contract")?)
+            .context("Failed to parse ABI")?;
+
+        let backend = if Some(compiler) == self.solc() { "solc (standard JSON)" } else { "solcjs (standard JSON)" };
+        let mut info = Self::compile_info_from_metadata(contract_name, backend, contract.get("metadata"));
+        info.storage_layout = accounting::parse_storage_layout(contract.get("storageLayout"));
+
+        let libraries = linking::referenced_libraries(contract.get("evm")
+            .and_then(|evm| evm.get("bytecode"))
+            .and_then(|bytecode| bytecode.get("linkReferences")));
+        let (bytecode, links) = linking::decode(bytecode_hex, &libraries, |unit| {
+            tree.locate(unit).unwrap_or_else(|| base_dir.join(unit))
+        })?;
+        info.libraries = links;
+        info.runtime_size = deployment::code_size(contract.get("evm").and_then(|evm| evm.get("deployedBytecode")));
+        Ok((bytecode, abi, info))
+    }
+
+    /// Find an executable in PATH. On Windows the name is tried with every `PATHEXT` extension
+    /// (`forge.exe`, `solc.cmd`), since that is how the shell resolves a bare command there.
+    pub(crate) fn find_executable(name: &str) -> Option<String> {
+        let path = std::env::var_os("PATH")?;
+        let file_names: Vec<String> = if cfg!(windows) {
+            std::env::var("PATHEXT")
+                .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+                .split(';')
+                .filter(|extension| !extension.is_empty())
+                .map(|extension| format!("{}{}", name, extension.to_ascii_lowercase()))
+                .collect()
+        } else {
+            vec![name.to_string()]
+        };
+        std::env::split_paths(&path)
+            .flat_map(|dir| file_names.iter().map(move |file_name| dir.join(file_name)))
+            .find(|candidate| is_executable(candidate))
+            .map(|found| found.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// A relative path with `/` separators, as forge and solc name source units on every platform
+fn source_unit_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl Default for ContractCompiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
@@ -0,0 +1,453 @@
+//! Seed corpora shared with Echidna and Medusa. A corpus is a set of transaction sequences, each
+//! transaction a (sender, target, calldata, value). Imported transactions seed the methods whose
+//! selector they call, the way forge counterexamples do. Exported sequences are written in the
+//! tool's own layout and addressed to the senders and target of its default setup, so the tool
+//! loads them as seeds of its next campaign on the same contract.
+
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::types::{ContractMethod, Finding, SolidityValue};
+use anyhow::{Context, Result};
+use ethers::abi::{HumanReadableParser, ParamType, Token};
+use ethers::types::{Address, I256, U256};
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Layout a corpus is exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CorpusFormat {
+    /// `coverage/` and `reproducers/` files of Echidna `Tx` arrays
+    #[default]
+    Echidna,
+    /// `call_sequences/` and `test_results/` files of Medusa call sequence elements
+    Medusa,
+}
+
+impl FromStr for CorpusFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "echidna" => Ok(Self::Echidna),
+            "medusa" => Ok(Self::Medusa),
+            other => Err(format!("unknown corpus format '{}' (expected echidna or medusa)", other)),
+        }
+    }
+}
+
+impl fmt::Display for CorpusFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Echidna => write!(f, "Echidna"),
+            Self::Medusa => write!(f, "Medusa"),
+        }
+    }
+}
+
+/// Senders of Echidna's and Medusa's default configuration
+const TOOL_SENDERS: [&str; 3] = [
+    "0x0000000000000000000000000000000000010000",
+    "0x0000000000000000000000000000000000020000",
+    "0x0000000000000000000000000000000000030000",
+];
+
+/// Where Echidna deploys the contract under test
+const ECHIDNA_TARGET: &str = "0x00a329c0648769a73afac7f9381e08fb43dbea72";
+
+/// Where Medusa deploys the first target contract (from 0x30000, nonce 0)
+const MEDUSA_TARGET: &str = "0xa647ff3c36cfab592509e13860ab8c4f28781a66";
+
+/// Gas limit of a call in both tools' default configuration
+const TOOL_GAS_LIMIT: u64 = 12_500_000;
+
+/// One transaction of a corpus sequence
+#[derive(Debug, Clone)]
+pub struct CorpusTx {
+    pub sender: String,
+    pub target: String,
+    /// Selector followed by the ABI-encoded arguments
+    pub calldata: Vec<u8>,
+    pub value: U256,
+    /// Canonical signature of the call, when known; needed to export decoded arguments
+    pub signature: Option<String>,
+}
+
+impl CorpusTx {
+    /// The call of `method` with `args`, or `None` when the arguments do not encode
+    pub fn call(method: &ContractMethod, args: &[SolidityValue], sender: &str, target: &str, value: U256) -> Option<Self> {
+        let signature = abi_encoding::method_signature(method);
+        let mut calldata = calculate_selector(&signature).to_vec();
+        calldata.extend(abi_encoding::encode_args(args).ok()?);
+        Some(Self { sender: sender.to_string(), target: target.to_string(), calldata, value, signature: Some(signature) })
+    }
+}
+
+/// Transactions run in order from a fresh deployment of `contract`
+#[derive(Debug, Clone)]
+pub struct CorpusSequence {
+    pub contract: String,
+    pub transactions: Vec<CorpusTx>,
+    /// Ends in a call reported as a finding
+    pub reproducer: bool,
+}
+
+impl CorpusSequence {
+    /// The calls of a finding, setup calls first; `None` when one of them went to another contract
+    pub fn from_finding(finding: &Finding) -> Option<Self> {
+        let target = finding.contract_address.clone().unwrap_or_default();
+        let mut transactions = Vec::with_capacity(finding.sequence.len() + 1);
+        for call in &finding.sequence {
+            if call.contract.as_ref().is_some_and(|contract| *contract != finding.contract) {
+                return None;
+            }
+            let mut calldata = calculate_selector(&call.signature).to_vec();
+            calldata.extend(&call.calldata);
+            transactions.push(CorpusTx {
+                sender: call.sender.clone(),
+                target: target.clone(),
+                calldata,
+                value: call.value,
+                signature: Some(call.signature.clone()),
+            });
+        }
+        let mut calldata = calculate_selector(&finding.signature).to_vec();
+        calldata.extend(abi_encoding::encode_args(&finding.args).ok()?);
+        transactions.push(CorpusTx {
+            sender: finding.sender.clone(),
+            target,
+            calldata,
+            value: finding.value,
+            signature: Some(finding.signature.clone()),
+        });
+        Some(Self { contract: finding.contract.clone(), transactions, reproducer: true })
+    }
+}
+
+/// Every transaction of the Echidna and Medusa corpus files under `dir`. Files that are not JSON
+/// arrays of either tool's transactions, and transactions that cannot be read, are skipped.
+pub fn load(dir: &Path) -> Result<Vec<CorpusTx>> {
+    if !dir.is_dir() {
+        anyhow::bail!("{} is not a directory", dir.display());
+    }
+    let mut transactions = Vec::new();
+    for entry in WalkDir::new(dir).into_iter().filter_map(|entry| entry.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let Ok(Value::Array(items)) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        for item in &items {
+            match echidna_tx(item).or_else(|| medusa_tx(item)) {
+                Some(tx) => transactions.push(tx),
+                None => debug!("Skipping unreadable corpus transaction in {}", entry.path().display()),
+            }
+        }
+    }
+    Ok(transactions)
+}
+
+/// Arguments of `method` in calls to its selector
+pub fn inputs_for(method: &ContractMethod, transactions: &[CorpusTx]) -> Vec<Vec<SolidityValue>> {
+    let selector = calculate_selector(&abi_encoding::method_signature(method));
+    let types: Vec<_> = method.parameters.iter().map(|param| param.param_type.clone()).collect();
+    let mut inputs: Vec<Vec<SolidityValue>> = Vec::new();
+    for tx in transactions.iter().filter(|tx| tx.calldata.get(..4) == Some(&selector[..])) {
+        if let Ok(args) = abi_encoding::decode_args(&types, &tx.calldata[4..]) {
+            if !inputs.contains(&args) {
+                inputs.push(args);
+            }
+        }
+    }
+    inputs
+}
+
+/// Write each sequence as a file of the format's layout under `dir`, returning the paths written
+pub fn export(dir: &Path, format: CorpusFormat, sequences: &[CorpusSequence]) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for (index, sequence) in sequences.iter().enumerate() {
+        // The tool's senders stand in for the campaign's, in order of first appearance
+        let mut senders: Vec<&str> = Vec::new();
+        let mut items = Vec::with_capacity(sequence.transactions.len());
+        for tx in &sequence.transactions {
+            let position = senders.iter().position(|sender| sender.eq_ignore_ascii_case(&tx.sender)).unwrap_or_else(|| {
+                senders.push(&tx.sender);
+                senders.len() - 1
+            });
+            let sender = TOOL_SENDERS[position % TOOL_SENDERS.len()];
+            items.push(match format {
+                CorpusFormat::Echidna => echidna_json(tx, sender),
+                CorpusFormat::Medusa => medusa_json(tx, sender),
+            });
+        }
+        let subdir = match (format, sequence.reproducer) {
+            (CorpusFormat::Echidna, false) => "coverage",
+            (CorpusFormat::Echidna, true) => "reproducers",
+            (CorpusFormat::Medusa, false) => "call_sequences",
+            (CorpusFormat::Medusa, true) => "test_results",
+        };
+        let extension = if format == CorpusFormat::Echidna { "txt" } else { "json" };
+        let path = dir.join(subdir).join(format!("fuzzhead-{}-{}.{}", sequence.contract, index + 1, extension));
+        std::fs::create_dir_all(path.parent().expect("corpus file has a directory"))
+            .with_context(|| format!("could not create {}", dir.display()))?;
+        std::fs::write(&path, serde_json::to_string_pretty(&Value::Array(items))?)
+            .with_context(|| format!("could not write {}", path.display()))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Argument types and tokens of a call, decoded with its signature
+fn decoded_args(tx: &CorpusTx) -> Option<(String, Vec<ParamType>, Vec<Token>)> {
+    let signature = tx.signature.as_ref()?;
+    let function = HumanReadableParser::parse_function(&format!("function {}", signature)).ok()?;
+    let types: Vec<ParamType> = function.inputs.iter().map(|input| input.kind.clone()).collect();
+    let tokens = ethers::abi::decode(&types, tx.calldata.get(4..)?).ok()?;
+    Some((function.name, types, tokens))
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    hex::decode(text.trim_start_matches("0x")).ok()
+}
+
+/// A number written as a JSON number, a decimal string or a `0x` hex string
+fn parse_u256(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(text) => match text.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(text).ok(),
+        },
+        _ => None,
+    }
+}
+
+fn parse_i256(value: &Value) -> Option<I256> {
+    match value {
+        Value::Number(number) => number.as_i64().map(I256::from),
+        Value::String(text) => I256::from_dec_str(text).ok(),
+        _ => None,
+    }
+}
+
+fn parse_address(value: &Value) -> Option<Address> {
+    value.as_str()?.parse().ok()
+}
+
+/// An Echidna `Tx`: `{"call": {"tag": "SolCall", "contents": [name, [AbiValue…]]}, "src", "dst", "value", …}`,
+/// or a `SolCalldata` call with raw calldata
+fn echidna_tx(item: &Value) -> Option<CorpusTx> {
+    let call = item.get("call")?;
+    let (calldata, signature) = match call.get("tag")?.as_str()? {
+        "SolCall" => {
+            let contents = call.get("contents")?.as_array()?;
+            let name = contents.first()?.as_str()?;
+            let (types, tokens): (Vec<ParamType>, Vec<Token>) = contents.get(1)?.as_array()?.iter()
+                .map(echidna_value)
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            let type_list: Vec<String> = types.iter().map(ToString::to_string).collect();
+            let signature = format!("{}({})", name, type_list.join(","));
+            let mut calldata = calculate_selector(&signature).to_vec();
+            calldata.extend(ethers::abi::encode(&tokens));
+            (calldata, Some(signature))
+        }
+        "SolCalldata" => (parse_hex(call.get("contents")?.as_str()?)?, None),
+        _ => return None,
+    };
+    Some(CorpusTx {
+        sender: item.get("src")?.as_str()?.to_string(),
+        target: item.get("dst")?.as_str()?.to_string(),
+        calldata,
+        value: item.get("value").and_then(parse_u256).unwrap_or_default(),
+        signature,
+    })
+}
+
+/// An Echidna `AbiValue` with its type
+fn echidna_value(value: &Value) -> Option<(ParamType, Token)> {
+    let contents = value.get("contents");
+    let pair = || contents.and_then(Value::as_array).filter(|pair| pair.len() == 2);
+    Some(match value.get("tag")?.as_str()? {
+        "AbiUInt" => {
+            let pair = pair()?;
+            (ParamType::Uint(pair[0].as_u64()? as usize), Token::Uint(parse_u256(&pair[1])?))
+        }
+        "AbiInt" => {
+            let pair = pair()?;
+            (ParamType::Int(pair[0].as_u64()? as usize), Token::Int(parse_i256(&pair[1])?.into_raw()))
+        }
+        "AbiAddress" => (ParamType::Address, Token::Address(parse_address(contents?)?)),
+        "AbiBool" => (ParamType::Bool, Token::Bool(contents?.as_bool()?)),
+        "AbiBytes" => {
+            let pair = pair()?;
+            (ParamType::FixedBytes(pair[0].as_u64()? as usize), Token::FixedBytes(parse_hex(pair[1].as_str()?)?))
+        }
+        "AbiBytesDynamic" => (ParamType::Bytes, Token::Bytes(parse_hex(contents?.as_str()?)?)),
+        "AbiString" => {
+            let text = contents?.as_str()?;
+            let text = parse_hex(text).and_then(|bytes| String::from_utf8(bytes).ok()).unwrap_or_else(|| text.to_string());
+            (ParamType::String, Token::String(text))
+        }
+        "AbiArray" => {
+            let contents = contents?.as_array()?;
+            let length = contents.first()?.as_u64()? as usize;
+            let element = echidna_type(contents.get(1)?)?;
+            let tokens = echidna_values(contents.get(2)?)?;
+            (ParamType::FixedArray(Box::new(element), length), Token::FixedArray(tokens))
+        }
+        "AbiArrayDynamic" => {
+            let pair = pair()?;
+            (ParamType::Array(Box::new(echidna_type(&pair[0])?)), Token::Array(echidna_values(&pair[1])?))
+        }
+        "AbiTuple" => {
+            let (types, tokens) = contents?.as_array()?.iter().map(echidna_value).collect::<Option<Vec<_>>>()?.into_iter().unzip();
+            (ParamType::Tuple(types), Token::Tuple(tokens))
+        }
+        _ => return None,
+    })
+}
+
+fn echidna_values(values: &Value) -> Option<Vec<Token>> {
+    values.as_array()?.iter().map(|value| echidna_value(value).map(|(_, token)| token)).collect()
+}
+
+/// An Echidna `AbiType`
+fn echidna_type(kind: &Value) -> Option<ParamType> {
+    let contents = kind.get("contents");
+    let size = || contents.and_then(Value::as_u64).map(|size| size as usize);
+    Some(match kind.get("tag")?.as_str()? {
+        "AbiUIntType" => ParamType::Uint(size()?),
+        "AbiIntType" => ParamType::Int(size()?),
+        "AbiAddressType" => ParamType::Address,
+        "AbiBoolType" => ParamType::Bool,
+        "AbiBytesType" => ParamType::FixedBytes(size()?),
+        "AbiBytesDynamicType" => ParamType::Bytes,
+        "AbiStringType" => ParamType::String,
+        "AbiArrayDynamicType" => ParamType::Array(Box::new(echidna_type(contents?)?)),
+        "AbiArrayType" => {
+            let pair = contents?.as_array()?;
+            ParamType::FixedArray(Box::new(echidna_type(pair.get(1)?)?), pair.first()?.as_u64()? as usize)
+        }
+        "AbiTupleType" => ParamType::Tuple(contents?.as_array()?.iter().map(echidna_type).collect::<Option<_>>()?),
+        _ => return None,
+    })
+}
+
+fn echidna_type_json(kind: &ParamType) -> Value {
+    match kind {
+        ParamType::Uint(size) => json!({"tag": "AbiUIntType", "contents": size}),
+        ParamType::Int(size) => json!({"tag": "AbiIntType", "contents": size}),
+        ParamType::Address => json!({"tag": "AbiAddressType"}),
+        ParamType::Bool => json!({"tag": "AbiBoolType"}),
+        ParamType::FixedBytes(size) => json!({"tag": "AbiBytesType", "contents": size}),
+        ParamType::Bytes => json!({"tag": "AbiBytesDynamicType"}),
+        ParamType::String => json!({"tag": "AbiStringType"}),
+        ParamType::Array(element) => json!({"tag": "AbiArrayDynamicType", "contents": echidna_type_json(element)}),
+        ParamType::FixedArray(element, length) => json!({"tag": "AbiArrayType", "contents": [length, echidna_type_json(element)]}),
+        ParamType::Tuple(types) => json!({"tag": "AbiTupleType", "contents": types.iter().map(echidna_type_json).collect::<Vec<_>>()}),
+    }
+}
+
+fn echidna_value_json(kind: &ParamType, token: &Token) -> Value {
+    match (kind, token) {
+        (ParamType::Uint(size), Token::Uint(value)) => json!({"tag": "AbiUInt", "contents": [size, value.to_string()]}),
+        (ParamType::Int(size), Token::Int(value)) => json!({"tag": "AbiInt", "contents": [size, I256::from_raw(*value).to_string()]}),
+        (_, Token::Address(address)) => json!({"tag": "AbiAddress", "contents": format!("{:?}", address)}),
+        (_, Token::Bool(value)) => json!({"tag": "AbiBool", "contents": value}),
+        (_, Token::FixedBytes(bytes)) => json!({"tag": "AbiBytes", "contents": [bytes.len(), hex_string(bytes)]}),
+        (_, Token::Bytes(bytes)) => json!({"tag": "AbiBytesDynamic", "contents": hex_string(bytes)}),
+        (_, Token::String(text)) => json!({"tag": "AbiString", "contents": hex_string(text.as_bytes())}),
+        (ParamType::FixedArray(element, length), Token::FixedArray(tokens)) => json!({"tag": "AbiArray", "contents": [
+            length, echidna_type_json(element), tokens.iter().map(|token| echidna_value_json(element, token)).collect::<Vec<_>>(),
+        ]}),
+        (ParamType::Array(element), Token::Array(tokens)) => json!({"tag": "AbiArrayDynamic", "contents": [
+            echidna_type_json(element), tokens.iter().map(|token| echidna_value_json(element, token)).collect::<Vec<_>>(),
+        ]}),
+        (ParamType::Tuple(types), Token::Tuple(tokens)) => json!({"tag": "AbiTuple",
+            "contents": types.iter().zip(tokens).map(|(kind, token)| echidna_value_json(kind, token)).collect::<Vec<_>>()}),
+        _ => Value::Null,
+    }
+}
+
+/// An Echidna `Tx`, as a decoded `SolCall` when the signature is known and raw `SolCalldata` otherwise
+fn echidna_json(tx: &CorpusTx, sender: &str) -> Value {
+    let call = match decoded_args(tx) {
+        Some((name, types, tokens)) => json!({"tag": "SolCall", "contents": [
+            name, types.iter().zip(&tokens).map(|(kind, token)| echidna_value_json(kind, token)).collect::<Vec<_>>(),
+        ]}),
+        None => json!({"tag": "SolCalldata", "contents": hex_string(&tx.calldata)}),
+    };
+    json!({
+        "call": call,
+        "src": sender,
+        "dst": ECHIDNA_TARGET,
+        "gas": TOOL_GAS_LIMIT,
+        "gasprice": "0x0",
+        "value": format!("0x{:x}", tx.value),
+        "delay": ["0x0", "0x0"],
+    })
+}
+
+/// A Medusa call sequence element: `{"call": {"from", "to", "value", "data", …}, "blockNumberDelay", …}`
+fn medusa_tx(item: &Value) -> Option<CorpusTx> {
+    let call = item.get("call")?;
+    Some(CorpusTx {
+        sender: call.get("from")?.as_str()?.to_string(),
+        target: call.get("to")?.as_str()?.to_string(),
+        calldata: parse_hex(call.get("data")?.as_str()?)?,
+        value: call.get("value").and_then(parse_u256).unwrap_or_default(),
+        signature: call.get("dataAbiValues")
+            .and_then(|values| values.get("methodSignature"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// A decoded argument as Medusa writes it in `inputValues`; `None` for tuples, which Medusa keys by field name
+fn medusa_value(token: &Token) -> Option<Value> {
+    Some(match token {
+        Token::Uint(value) => json!(value.to_string()),
+        Token::Int(value) => json!(I256::from_raw(*value).to_string()),
+        Token::Address(address) => json!(format!("{:?}", address)),
+        Token::Bool(value) => json!(value),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => json!(hex_string(bytes)),
+        Token::String(text) => json!(text),
+        Token::Array(tokens) | Token::FixedArray(tokens) => Value::Array(tokens.iter().map(medusa_value).collect::<Option<_>>()?),
+        Token::Tuple(_) => return None,
+    })
+}
+
+fn medusa_json(tx: &CorpusTx, sender: &str) -> Value {
+    let mut call = json!({
+        "from": sender,
+        "to": MEDUSA_TARGET,
+        "nonce": 0,
+        "value": format!("0x{:x}", tx.value),
+        "gasLimit": TOOL_GAS_LIMIT,
+        "gasPrice": "0x1",
+        "gasFeeCap": "0x0",
+        "gasTipCap": "0x0",
+        "data": hex_string(&tx.calldata),
+    });
+    let abi_values = decoded_args(tx).and_then(|(_, _, tokens)| {
+        let values = tokens.iter().map(medusa_value).collect::<Option<Vec<_>>>()?;
+        Some(json!({"methodSignature": tx.signature.as_ref()?, "inputValues": values}))
+    });
+    if let Some(abi_values) = abi_values {
+        call["dataAbiValues"] = abi_values;
+    }
+    json!({"call": call, "blockNumberDelay": 0, "blockTimestampDelay": 0})
+}
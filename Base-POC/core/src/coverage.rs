@@ -0,0 +1,87 @@
+use crate::types::{ArgProvenance, SolidityValue};
+use std::collections::HashSet;
+
+/// Keep the corpus of a method bounded; the oldest inputs found the least specific branches
+const MAX_CORPUS_SIZE: usize = 256;
+
+/// A taken or not-taken `JUMPI` of the target contract: `(jumpi pc << 32) | next pc`
+pub type BranchEdge = u64;
+
+/// Branch edges from the `structLogs` of a `debug_traceTransaction` response.
+/// Only steps at depth 1 are the target's own code; deeper frames run other contracts.
+pub fn branch_edges(struct_logs: &[serde_json::Value]) -> Vec<BranchEdge> {
+    let mut edges = Vec::new();
+    let mut pending_jumpi = None;
+    for step in struct_logs {
+        if step.get("depth").and_then(|depth| depth.as_u64()) != Some(1) {
+            continue;
+        }
+        let Some(pc) = step.get("pc").and_then(|pc| pc.as_u64()) else {
+            continue;
+        };
+        if let Some(jumpi_pc) = pending_jumpi.take() {
+            edges.push((jumpi_pc << 32) | (pc & 0xffff_ffff));
+        }
+        if step.get("op").and_then(|op| op.as_str()) == Some("JUMPI") {
+            pending_jumpi = Some(pc);
+        }
+    }
+    edges
+}
+
+/// Branch edges of one contract hit so far in the campaign
+#[derive(Debug, Default)]
+pub struct CoverageMap {
+    edges: HashSet<BranchEdge>,
+}
+
+impl CoverageMap {
+    /// Record the edges of one call, returning how many were not seen before
+    pub fn merge(&mut self, edges: &[BranchEdge]) -> usize {
+        edges.iter().filter(|edge| self.edges.insert(**edge)).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+/// An input that reached branches no earlier input of the contract had reached (or, with
+/// `--mutate`, an outcome no earlier input of the method had)
+#[derive(Debug, Clone)]
+pub struct CorpusEntry {
+    pub args: Vec<SolidityValue>,
+    pub provenance: Vec<ArgProvenance>,
+    pub sender_index: usize,
+}
+
+/// Interesting inputs of one method, mutated to reach the branches behind them
+#[derive(Debug, Default)]
+pub struct Corpus {
+    entries: Vec<CorpusEntry>,
+}
+
+impl Corpus {
+    pub fn add(&mut self, entry: CorpusEntry) {
+        if self.entries.len() >= MAX_CORPUS_SIZE {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[CorpusEntry] {
+        &self.entries
+    }
+}
@@ -0,0 +1,90 @@
+//! Checks before a contract is deployed. A deployment the chain is bound to refuse otherwise only
+//! shows up as a failed transaction with no revert reason; here the code size limits and a gas
+//! estimate of the deployment turn it into an error that says what to change.
+
+use crate::config::CompilerOptions;
+use serde_json::Value;
+
+/// Largest runtime code a deployment may leave behind (EIP-170)
+pub const MAX_CODE_SIZE: usize = 24_576;
+
+/// Largest init code a deployment may run (EIP-3860, Shanghai and later)
+pub const MAX_INITCODE_SIZE: usize = 49_152;
+
+/// Size in bytes of a bytecode hex string, as an artifact's `deployedBytecode` holds it (a string
+/// or an object with the string in `object`); library placeholders count as the 20 bytes they stand for
+pub fn code_size(bytecode: Option<&Value>) -> Option<usize> {
+    let hex = bytecode?;
+    let hex = hex.get("object").unwrap_or(hex).as_str()?;
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    (!hex.is_empty()).then_some(hex.len() / 2)
+}
+
+/// An error when the code of `contract_name` is over a size limit. `backend` is the compile
+/// backend, whose optimizer settings the advice names.
+pub fn check_size(
+    contract_name: &str,
+    init_code_size: usize,
+    runtime_size: Option<usize>,
+    compiler: &CompilerOptions,
+    backend: &str,
+) -> Result<(), String> {
+    if let Some(runtime_size) = runtime_size.filter(|size| *size > MAX_CODE_SIZE) {
+        return Err(format!(
+            "{} has {} bytes of runtime code, over the EIP-170 limit of {} bytes, so no chain accepts its deployment: {}",
+            contract_name, runtime_size, MAX_CODE_SIZE, size_advice(compiler, backend)
+        ));
+    }
+    if init_code_size > MAX_INITCODE_SIZE {
+        return Err(format!(
+            "{} has {} bytes of init code, over the EIP-3860 limit of {} bytes: {}",
+            contract_name, init_code_size, MAX_INITCODE_SIZE, size_advice(compiler, backend)
+        ));
+    }
+    Ok(())
+}
+
+/// What makes a contract smaller, starting with the optimizer settings in effect
+fn size_advice(compiler: &CompilerOptions, backend: &str) -> String {
+    let optimizer = if backend.starts_with("forge") {
+        "enable the optimizer in foundry.toml (optimizer = true, a low optimizer_runs)".to_string()
+    } else if backend == "solc" {
+        // The solc command line always optimizes for 200 runs
+        "compile with --compiler standard-json and a low --optimizer-runs".to_string()
+    } else if !compiler.optimizer {
+        "enable the optimizer (drop --no-optimizer)".to_string()
+    } else if compiler.optimizer_runs > 1 {
+        format!("lower --optimizer-runs (now {}), which trades gas per call for code size", compiler.optimizer_runs)
+    } else {
+        "the optimizer is already tuned for size".to_string()
+    };
+    format!("{}, move code into external libraries, or split the contract", optimizer)
+}
+
+/// The error of a failed deployment gas estimate, when it tells why the deployment would fail:
+/// a constructor revert or a code size limit. `None` for other failures (a node without
+/// `eth_estimateGas`, a timeout), after which the deployment is tried anyway.
+pub fn explain_estimate_failure(
+    contract_name: &str,
+    error: &str,
+    has_constructor_args: bool,
+    compiler: &CompilerOptions,
+    backend: &str,
+) -> Option<String> {
+    let lowered = error.to_ascii_lowercase();
+    if lowered.contains("codesize") || lowered.contains("code size") || lowered.contains("contractsizelimit") {
+        return Some(format!("{} is too large to deploy ({}): {}", contract_name, error, size_advice(compiler, backend)));
+    }
+    if lowered.contains("revert") {
+        let hint = if has_constructor_args {
+            "check the constructor arguments (--constructor-args)"
+        } else {
+            "its constructor fails on this chain; check what it calls and requires"
+        };
+        return Some(format!("The deployment of {} would revert ({}): {}", contract_name, error, hint));
+    }
+    if lowered.contains("out of gas") || lowered.contains("gas required exceeds") {
+        return Some(format!("The deployment of {} needs more gas than a block allows ({}): its constructor does too much work, e.g. unbounded loops", contract_name, error));
+    }
+    None
+}
@@ -0,0 +1,174 @@
+//! Fuzz dictionary of a contract's constants. Comparisons against magic values
+//! (`require(code == 0xDEADBEEF)`, a hard-coded admin address, a password string) are practically
+//! unreachable with random values, so the literals of the source and the PUSH operands of the
+//! bytecode are collected and the generator picks from them now and then.
+
+use ethers::types::U256;
+use solang_parser::lexer::{Lexer, Token};
+
+/// Entries kept per kind; a large contract's constants beyond this are dropped
+const MAX_ENTRIES: usize = 256;
+
+const PUSH1: u8 = 0x60;
+/// Shortest push collected; shorter ones are mostly jump targets and memory offsets
+const PUSH4: u8 = 0x63;
+const PUSH32: u8 = 0x7f;
+
+/// Constants of one contract, deduplicated in the order they were found
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    /// Integer literals and PUSH operands
+    pub words: Vec<U256>,
+    /// Address literals and 20-byte PUSH operands, lowercase
+    pub addresses: Vec<String>,
+    pub strings: Vec<String>,
+    /// `hex"…"` literals
+    pub byte_literals: Vec<Vec<u8>>,
+}
+
+fn push_unique<T: PartialEq>(entries: &mut Vec<T>, entry: T) {
+    if entries.len() < MAX_ENTRIES && !entries.contains(&entry) {
+        entries.push(entry);
+    }
+}
+
+/// Multiplier of a Solidity ether or time unit
+fn unit_multiplier(unit: &str) -> Option<U256> {
+    let multiplier = match unit {
+        "wei" | "seconds" => 1u64,
+        "gwei" => 1_000_000_000,
+        "ether" => 1_000_000_000_000_000_000,
+        "minutes" => 60,
+        "hours" => 3_600,
+        "days" => 86_400,
+        "weeks" => 604_800,
+        _ => return None,
+    };
+    Some(U256::from(multiplier))
+}
+
+/// Value of a decimal literal such as `1_000`, `5e17` or `2`
+fn decimal_value(integer: &str, exponent: &str) -> Option<U256> {
+    let value = U256::from_dec_str(&integer.replace('_', "")).ok()?;
+    if exponent.is_empty() {
+        return Some(value);
+    }
+    let exponent: u32 = exponent.replace('_', "").parse().ok().filter(|exponent| *exponent <= 77)?;
+    value.checked_mul(U256::from(10).checked_pow(U256::from(exponent))?)
+}
+
+impl Dictionary {
+    /// Constants of a source file and of the runtime or init code built from it
+    pub fn harvest(source: &str, bytecode: &[u8]) -> Self {
+        let mut dictionary = Dictionary::default();
+        dictionary.add_source(source);
+        dictionary.add_bytecode(bytecode);
+        dictionary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.addresses.is_empty() && self.strings.is_empty() && self.byte_literals.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len() + self.addresses.len() + self.strings.len() + self.byte_literals.len()
+    }
+
+    /// Number, hex, address, string and `hex"…"` literals of the source; comments are skipped
+    pub fn add_source(&mut self, source: &str) {
+        let mut comments = Vec::new();
+        let mut errors = Vec::new();
+        let tokens: Vec<Token> = Lexer::new(source, 0, &mut comments, &mut errors)
+            .map(|(_, token, _)| token)
+            .collect();
+
+        for (index, token) in tokens.iter().enumerate() {
+            match *token {
+                Token::Number(integer, exponent) => {
+                    let Some(mut value) = decimal_value(integer, exponent) else {
+                        continue;
+                    };
+                    if let Some(Token::Identifier(unit)) = tokens.get(index + 1) {
+                        if let Some(multiplier) = unit_multiplier(unit) {
+                            value = value.saturating_mul(multiplier);
+                        }
+                    }
+                    // 0 and 1 are drawn as boundaries already
+                    if value > U256::one() {
+                        push_unique(&mut self.words, value);
+                    }
+                }
+                Token::HexNumber(number) => {
+                    let digits = number.trim_start_matches("0x").replace('_', "");
+                    if digits.len() == 40 {
+                        push_unique(&mut self.addresses, format!("0x{}", digits.to_ascii_lowercase()));
+                    }
+                    if let Ok(value) = U256::from_str_radix(&digits, 16) {
+                        if value > U256::one() {
+                            push_unique(&mut self.words, value);
+                        }
+                    }
+                }
+                Token::AddressLiteral(literal) => {
+                    let digits = literal.trim_start_matches("address").trim_matches('"').trim_start_matches("0x");
+                    if digits.len() == 40 {
+                        push_unique(&mut self.addresses, format!("0x{}", digits.to_ascii_lowercase()));
+                    }
+                }
+                Token::StringLiteral(_, literal) if !literal.is_empty() => {
+                    push_unique(&mut self.strings, literal.to_string());
+                }
+                Token::HexLiteral(literal) => {
+                    let digits = literal.trim_start_matches("hex").trim_matches(|c| c == '"' || c == '\'').replace('_', "");
+                    if let Ok(bytes) = hex::decode(digits) {
+                        if !bytes.is_empty() {
+                            push_unique(&mut self.byte_literals, bytes);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// PUSH4 to PUSH32 operands of the bytecode, up to the CBOR metadata solc appends
+    pub fn add_bytecode(&mut self, bytecode: &[u8]) {
+        let code = strip_metadata(bytecode);
+        let mut pc = 0;
+        while pc < code.len() {
+            let opcode = code[pc];
+            if !(PUSH1..=PUSH32).contains(&opcode) {
+                pc += 1;
+                continue;
+            }
+            let size = (opcode - PUSH1 + 1) as usize;
+            let Some(operand) = code.get(pc + 1..pc + 1 + size) else {
+                break;
+            };
+            pc += 1 + size;
+            if opcode < PUSH4 {
+                continue;
+            }
+            if size == 20 {
+                push_unique(&mut self.addresses, format!("0x{}", hex::encode(operand)));
+            }
+            let value = U256::from_big_endian(operand);
+            if value > U256::one() {
+                push_unique(&mut self.words, value);
+            }
+        }
+    }
+}
+
+/// Bytecode without its trailing CBOR metadata (length in the last two bytes), when it has one
+fn strip_metadata(bytecode: &[u8]) -> &[u8] {
+    let Some(length_bytes) = bytecode.len().checked_sub(2).and_then(|start| bytecode.get(start..)) else {
+        return bytecode;
+    };
+    let length = u16::from_be_bytes([length_bytes[0], length_bytes[1]]) as usize + 2;
+    match bytecode.len().checked_sub(length) {
+        // A CBOR map of one to three entries (ipfs/bzzr, solc, experimental)
+        Some(start) if matches!(bytecode.get(start), Some(0xa1..=0xa3)) => &bytecode[..start],
+        _ => bytecode,
+    }
+}
@@ -0,0 +1,188 @@
+use crate::types::SolidityValue;
+use unicode_width::UnicodeWidthChar;
+
+/// Default `max_arg_display_len`: the longest an argument is printed, in terminal columns
+pub const DEFAULT_MAX_ARG_LEN: usize = 30;
+
+/// Marker appended to a value that was cut
+const ELLIPSIS: &str = "...";
+
+/// Arguments of a call as printed in the console, each cut to `max_len` columns (0 prints them in full)
+pub fn format_args(args: &[SolidityValue], max_len: usize) -> String {
+    args.iter()
+        .map(|arg| format_value(arg, max_len))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// One value as printed in the console. With a limit, strings, byte strings, arrays and structs are
+/// cut to `max_len` columns, addresses are abbreviated and arrays of more than three items are
+/// summarized; numbers are always printed in full. Never panics, whatever the value holds.
+pub fn format_value(value: &SolidityValue, max_len: usize) -> String {
+    match value {
+        SolidityValue::Uint8(v) => v.to_string(),
+        SolidityValue::Uint16(v) => v.to_string(),
+        SolidityValue::Uint32(v) => v.to_string(),
+        SolidityValue::Uint64(v) => v.to_string(),
+        SolidityValue::Uint128(v) => v.to_string(),
+        SolidityValue::Uint256(v) => v.clone(),
+        SolidityValue::Int8(v) => v.to_string(),
+        SolidityValue::Int16(v) => v.to_string(),
+        SolidityValue::Int32(v) => v.to_string(),
+        SolidityValue::Int64(v) => v.to_string(),
+        SolidityValue::Int128(v) => v.to_string(),
+        SolidityValue::Int256(v) => v.clone(),
+        SolidityValue::Address(address) => {
+            let address = printable(address);
+            let chars: Vec<char> = address.chars().collect();
+            if max_len > 0 && chars.len() > 10 {
+                let head: String = chars[..5].iter().collect();
+                let tail: String = chars[chars.len() - 2..].iter().collect();
+                format!("{}{}{}", head, ELLIPSIS, tail)
+            } else {
+                address
+            }
+        }
+        SolidityValue::Bool(b) => b.to_string(),
+        SolidityValue::String(s) => {
+            let quoted = format!("\"{}\"", printable(s));
+            if max_len == 0 || width(&quoted) <= max_len {
+                quoted
+            } else {
+                // Keep the closing quote so the cut is visible
+                format!("{}{}\"", truncate(&quoted, max_len.saturating_sub(ELLIPSIS.len() + 1)), ELLIPSIS)
+            }
+        }
+        SolidityValue::Bytes(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes1(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes2(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes4(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes8(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes16(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Bytes32(bytes) => format_bytes(bytes, max_len),
+        SolidityValue::Array(values) | SolidityValue::FixedArray(values) => {
+            if max_len > 0 && values.len() > 3 {
+                return format!("[{} items]", values.len());
+            }
+            let items = values.iter()
+                .map(|value| format_value(value, max_len))
+                .collect::<Vec<_>>()
+                .join(", ");
+            cut(format!("[{}]", items), max_len)
+        }
+        SolidityValue::Struct(fields) => {
+            let fields = fields.iter()
+                .map(|(name, value)| format!("{}: {}", name, format_value(value, max_len)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            cut(format!("{{{}}}", fields), max_len)
+        }
+    }
+}
+
+/// Hex of a byte string, cut to the whole bytes that fit in `max_len` columns next to `0x` and the ellipsis
+fn format_bytes(bytes: &[u8], max_len: usize) -> String {
+    if max_len == 0 || bytes.is_empty() || 2 + 2 * bytes.len() <= max_len {
+        return format!("0x{}", hex::encode(bytes));
+    }
+    let shown = (max_len.saturating_sub(2 + ELLIPSIS.len()) / 2).clamp(1, bytes.len());
+    format!("0x{}{}", hex::encode(&bytes[..shown]), ELLIPSIS)
+}
+
+/// A rendering cut to `max_len` columns, ellipsis included
+fn cut(rendered: String, max_len: usize) -> String {
+    if max_len == 0 || width(&rendered) <= max_len {
+        return rendered;
+    }
+    format!("{}{}", truncate(&rendered, max_len.saturating_sub(ELLIPSIS.len())), ELLIPSIS)
+}
+
+/// The longest prefix of `s` that fits in `columns` terminal columns, cut on a character boundary
+fn truncate(s: &str, columns: usize) -> &str {
+    let mut used = 0;
+    for (index, c) in s.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > columns {
+            return &s[..index];
+        }
+    }
+    s
+}
+
+/// Terminal columns `s` takes up: wide (CJK, emoji) characters count twice, combining marks not at all
+fn width(s: &str) -> usize {
+    s.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// `s` with control characters escaped, so a fuzzed string cannot break the line or send escape
+/// sequences to the terminal
+fn printable(s: &str) -> String {
+    if !s.chars().any(char::is_control) {
+        return s.to_string();
+    }
+    s.chars()
+        .map(|c| if c.is_control() { c.escape_default().to_string() } else { c.to_string() })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str) -> SolidityValue {
+        SolidityValue::String(s.to_string())
+    }
+
+    #[test]
+    fn multi_byte_characters_are_cut_on_a_character_boundary() {
+        assert_eq!(format_value(&string("héllo wörld"), 0), "\"héllo wörld\"");
+        assert_eq!(format_value(&string("héllo wörld"), 10), "\"héllo...\"");
+    }
+
+    #[test]
+    fn wide_characters_count_two_columns() {
+        assert_eq!(format_value(&string("漢字漢字"), 10), "\"漢字漢字\"");
+        assert_eq!(format_value(&string("漢字漢字漢字"), 10), "\"漢字...\"");
+        assert!(width(&format_value(&string("漢字漢字漢字"), 9)) <= 9);
+    }
+
+    #[test]
+    fn control_characters_are_escaped() {
+        assert_eq!(format_value(&string("a\nb\u{1b}[2J"), 0), "\"a\\nb\\u{1b}[2J\"");
+        assert_eq!(format_value(&SolidityValue::Address("0x1\r".to_string()), 0), "0x1\\r");
+    }
+
+    #[test]
+    fn empty_bytes_print_as_0x_at_any_limit() {
+        for max_len in 0..5 {
+            assert_eq!(format_value(&SolidityValue::Bytes(Vec::new()), max_len), "0x");
+        }
+    }
+
+    #[test]
+    fn byte_strings_keep_whole_bytes_that_fit() {
+        let bytes = SolidityValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(format_value(&bytes, 0), "0xdeadbeef");
+        assert_eq!(format_value(&bytes, 10), "0xdeadbeef");
+        assert_eq!(format_value(&bytes, 9), "0xdead...");
+    }
+
+    #[test]
+    fn tiny_limits_never_panic() {
+        let values = [
+            string("héllo 漢字\n"),
+            SolidityValue::Bytes(vec![1, 2, 3]),
+            SolidityValue::Bytes1([0xff]),
+            SolidityValue::Address("0x0000000000000000000000000000000000000001".to_string()),
+            SolidityValue::Array(vec![SolidityValue::Bool(true); 2]),
+            SolidityValue::Struct(vec![("x".to_string(), SolidityValue::Uint8(1))]),
+        ];
+        for max_len in 0..=3 {
+            for value in &values {
+                format_value(value, max_len);
+            }
+        }
+        assert_eq!(format_value(&SolidityValue::Bytes(vec![1, 2, 3]), 1), "0x01...");
+        assert_eq!(format_value(&string("abcdef"), 3), "...\"");
+    }
+}
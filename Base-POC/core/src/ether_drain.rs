@@ -0,0 +1,64 @@
+//! Ether-drain oracle for stateful sequences. The ETH balances of the target and of every account
+//! that sends calls (and the reentrancy attacker) are read before and after each sequence. An
+//! account other than the deployer or owner that ends up with more ETH than it started with, while
+//! the target lost ETH, took out more than it paid in: whatever it deposited and the gas it paid
+//! are already subtracted from its balance.
+
+use crate::executor::Executor;
+use anyhow::Result;
+use ethers::types::U256;
+use std::fmt;
+
+/// ETH balances at one point of a sequence
+#[derive(Debug, Clone)]
+pub struct Balances {
+    pub contract: U256,
+    /// Lowercase address and balance of each tracked account
+    pub accounts: Vec<(String, U256)>,
+}
+
+/// Read the balance of the target and of each account
+pub async fn read(executor: &mut dyn Executor, contract_name: &str, accounts: &[String]) -> Result<Balances> {
+    let contract = executor.contract_balance(contract_name).await?;
+    let mut balances = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        balances.push((account.to_ascii_lowercase(), executor.account_balance(account).await?));
+    }
+    Ok(Balances { contract, accounts: balances })
+}
+
+/// An unprivileged account that came out of a sequence with more ETH, taken from the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drain {
+    pub account: String,
+    /// Net gain of the account, after its deposits and gas
+    pub gained: U256,
+    /// What the target lost over the sequence
+    pub contract_lost: U256,
+}
+
+impl fmt::Display for Drain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} gained {} wei more than it paid in (deposits and gas included) while the target lost {} wei",
+            self.account, self.gained, self.contract_lost)
+    }
+}
+
+/// The account with the largest net gain, if the target lost ETH and that account is not privileged
+pub fn find_drain(before: &Balances, after: &Balances, privileged: &[String]) -> Option<Drain> {
+    if after.contract >= before.contract {
+        return None;
+    }
+    before.accounts.iter()
+        .filter(|(account, _)| !privileged.iter().any(|privileged| privileged.eq_ignore_ascii_case(account)))
+        .filter_map(|(account, balance_before)| {
+            let (_, balance_after) = after.accounts.iter().find(|(other, _)| other == account)?;
+            (balance_after > balance_before).then(|| (account, *balance_after - *balance_before))
+        })
+        .max_by_key(|(_, gained)| *gained)
+        .map(|(account, gained)| Drain {
+            account: account.clone(),
+            gained,
+            contract_lost: before.contract - after.contract,
+        })
+}
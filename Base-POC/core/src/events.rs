@@ -0,0 +1,99 @@
+//! Events emitted by fuzzed calls. Logs are read from the call's receipt (or revm's execution
+//! result), decoded with the ABIs of the campaign's contracts, and checked for events no correct
+//! call emits: tokens minted to address(0), or ownership handed by a stranger to an address the
+//! fuzzer chose.
+
+use crate::abi_encoding;
+use crate::reentrancy;
+use crate::types::SolidityValue;
+use ethers::abi::{ethabi, Abi, RawLog, Token};
+use ethers::types::{Bytes, H256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Prefix of the errors of findings reported by the event oracle
+pub const EVENT_ORACLE: &str = "Event oracle:";
+
+/// A log as found in a transaction receipt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLog {
+    /// Contract that emitted it
+    pub address: String,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+/// An event decoded with the ABI of the contract that declares it
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub address: String,
+    pub params: Vec<(String, Token)>,
+}
+
+impl fmt::Display for DecodedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params: Vec<String> = self.params.iter()
+            .map(|(name, value)| format!("{}: {}", name, abi_encoding::format_token(value)))
+            .collect();
+        write!(f, "{}({})", self.name, params.join(", "))
+    }
+}
+
+/// Events declared in the ABIs of the contracts under test, by signature topic. ERC-20 and
+/// ERC-721 `Transfer` share a topic and differ in which parameters are indexed, so a topic can
+/// have several.
+#[derive(Debug, Clone, Default)]
+pub struct EventDecoder {
+    events: HashMap<H256, Vec<ethabi::Event>>,
+}
+
+impl EventDecoder {
+    pub fn add(&mut self, abi: &Abi) {
+        for event in abi.events().filter(|event| !event.anonymous) {
+            let known = self.events.entry(event.signature()).or_default();
+            if !known.contains(event) {
+                known.push(event.clone());
+            }
+        }
+    }
+
+    /// The log as a declared event, `None` for anonymous events and events no ABI declares
+    pub fn decode(&self, log: &EventLog) -> Option<DecodedEvent> {
+        let candidates = self.events.get(log.topics.first()?)?;
+        candidates.iter().find_map(|event| {
+            let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+            let parsed = event.parse_log(raw).ok()?;
+            Some(DecodedEvent {
+                name: event.name.clone(),
+                address: log.address.clone(),
+                params: parsed.params.into_iter().map(|param| (param.name, param.value)).collect(),
+            })
+        })
+    }
+}
+
+/// Why a call from `sender` with `args` should not have emitted `event`, if it should not have
+pub fn violation(event: &DecodedEvent, sender: &str, args: &[SolidityValue]) -> Option<String> {
+    let addresses: Vec<String> = event.params.iter()
+        .filter_map(|(_, value)| match value {
+            Token::Address(address) => Some(format!("{:?}", address)),
+            _ => None,
+        })
+        .collect();
+    let is_zero = |address: &str| address.trim_start_matches("0x").chars().all(|c| c == '0');
+    match (event.name.as_str(), addresses.as_slice()) {
+        ("Transfer", [from, to, ..]) if is_zero(from) && is_zero(to) => {
+            Some(format!("{} minted tokens to address(0)", event))
+        }
+        ("OwnershipTransferred", [previous, new, ..])
+            if !previous.eq_ignore_ascii_case(new)
+                && !previous.eq_ignore_ascii_case(sender)
+                && (new.eq_ignore_ascii_case(sender) || reentrancy::contains_address(args, new)) =>
+        {
+            Some(format!("{} handed ownership to an address the caller {} chose, and the caller was not the owner", event, sender))
+        }
+        _ => None,
+    }
+}
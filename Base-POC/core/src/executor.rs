@@ -0,0 +1,311 @@
+use crate::anvil_executor::{AnvilForkExecutor, MethodExecutionResult};
+use crate::coverage::BranchEdge;
+use crate::events::EventLog;
+use crate::reentrancy::CallFrame;
+use crate::types::{BlockContext, Cheat, TxFees};
+use crate::wraparound::WrappedOperation;
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::abi::Abi;
+use ethers::types::U256;
+
+/// Backend that deploys contracts and executes fuzzed calls.
+/// `AnvilForkExecutor` talks to a live node; `RevmExecutor` runs transactions in-process on revm;
+/// `MockExecutor` answers from a script without any network.
+#[async_trait]
+pub trait Executor: Send {
+    /// Deploy a contract and return its address
+    async fn deploy_contract(
+        &mut self,
+        contract_name: &str,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+    ) -> Result<String>;
+
+    /// Gas a deployment would use, estimated without sending it; `None` where there is no chain
+    /// to estimate on. An `Err` carries the node's reason, e.g. the constructor's revert.
+    async fn estimate_deployment_gas(&mut self, bytecode: &[u8], constructor_args: Option<&[u8]>) -> Result<Option<u64>>;
+
+    /// Fuzz a contract that is already on the chain under `contract_name` instead of deploying it,
+    /// returning its runtime code; an address without code is an error
+    async fn attach_contract(&mut self, contract_name: &str, address: &str) -> Result<Vec<u8>>;
+
+    /// Invoke `method_signature` on a deployed contract. Reverts are reported through
+    /// `MethodExecutionResult`; an `Err` means the call itself could not be carried out.
+    async fn call_method(
+        &mut self,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<MethodExecutionResult>;
+
+    /// Invoke several `(method_signature, encoded_args)` calls on a deployed contract, one result
+    /// per call in order. Backends with per-transaction overhead may send them as one aggregator
+    /// transaction, in which case every sub-call sees the aggregator as `msg.sender` and reports an
+    /// equal share of the batch's gas. By default the calls are sent one by one.
+    async fn call_methods_batched(
+        &mut self,
+        contract_name: &str,
+        calls: &[(String, Vec<u8>)],
+    ) -> Result<Vec<MethodExecutionResult>> {
+        let mut results = Vec::with_capacity(calls.len());
+        for (method_signature, encoded_args) in calls {
+            results.push(self.call_method(contract_name, method_signature, encoded_args).await?);
+        }
+        Ok(results)
+    }
+
+    /// Read-only `eth_call` of raw calldata against a deployed contract, returning its output.
+    /// Always sent from the first account, so sender-dependent getters read consistently.
+    async fn static_call(&mut self, contract_name: &str, calldata: &[u8]) -> Result<Vec<u8>>;
+
+    /// ETH balance of a deployed contract, in wei
+    async fn contract_balance(&mut self, contract_name: &str) -> Result<U256>;
+
+    /// ETH balance of any account, in wei
+    async fn account_balance(&mut self, address: &str) -> Result<U256>;
+
+    /// Runtime code of any account, empty for accounts without code
+    async fn code_at(&mut self, address: &str) -> Result<Vec<u8>>;
+
+    /// Raw 32-byte word in a storage slot of a deployed contract
+    async fn storage_at(&mut self, contract_name: &str, slot: U256) -> Result<U256>;
+
+    /// Branch edges of the target contract hit by the most recent `call_method` transaction
+    async fn last_call_coverage(&mut self) -> Result<Vec<BranchEdge>>;
+
+    /// Message calls of the most recent `call_method` transaction, in execution order
+    async fn last_call_frames(&mut self) -> Result<Vec<CallFrame>>;
+
+    /// Arithmetic of the target contract that wrapped around in the most recent `call_method` transaction
+    async fn last_call_wraparounds(&mut self) -> Result<Vec<WrappedOperation>>;
+
+    /// Logs emitted by the most recent `call_method` transaction (empty when it reverted)
+    async fn last_call_logs(&mut self) -> Result<Vec<EventLog>>;
+
+    /// Take a snapshot of the current chain state
+    async fn snapshot(&mut self) -> Result<String>;
+
+    /// Restore a snapshot taken with `snapshot`
+    async fn revert_to_snapshot(&mut self, snapshot_id: &str) -> Result<()>;
+
+    /// Base fee of the latest block, `None` where EIP-1559 does not apply
+    async fn base_fee_per_gas(&mut self) -> Result<Option<u128>>;
+
+    /// Upstream block a forked node was started from, `None` when it is not a fork
+    async fn fork_block_number(&mut self) -> Result<Option<u64>>;
+
+    /// Chain id the node reports (a fork reports its upstream chain's)
+    async fn chain_id(&mut self) -> Result<u64>;
+
+    /// Send subsequent transactions as EIP-1559 type-2 with these fees (`None` lets the node choose)
+    fn set_fees(&mut self, fees: Option<TxFees>);
+
+    /// Gas limit of subsequent `call_method` transactions (`None` restores the default of 16M)
+    fn set_gas_limit(&mut self, gas_limit: Option<u64>);
+
+    /// ETH (wei) sent with subsequent `call_method` transactions, i.e. their `msg.value`
+    fn set_value(&mut self, value: U256);
+
+    /// Apply block environment overrides (coinbase, next base fee, chain id) for subsequent transactions
+    async fn set_block_context(&mut self, block_context: &BlockContext) -> Result<()>;
+
+    /// Restore the coinbase, base fee and chain id in effect before the first `set_block_context`.
+    /// Snapshot reverts do not undo block context overrides, so callers reset them explicitly.
+    async fn reset_block_context(&mut self) -> Result<()>;
+
+    /// Warp time, mine blocks or set a balance on the chain, for the transactions that follow
+    async fn apply_cheat(&mut self, cheat: &Cheat) -> Result<()>;
+
+    /// Replace the runtime code of an account
+    async fn set_code(&mut self, address: &str, code: &[u8]) -> Result<()>;
+
+    /// Decode reverts with the custom errors declared in `abi` from now on
+    fn register_errors(&mut self, abi: &Abi);
+
+    fn set_sender(&mut self, sender_index: usize);
+
+    /// Returns false if the account is not managed by this executor
+    fn set_sender_address(&mut self, address: &str) -> bool;
+
+    /// Send from an address this executor holds no key for (funded for gas if needed); it is
+    /// appended to `accounts()`, so it can then be selected by index or address
+    async fn impersonate(&mut self, address: &str) -> Result<()>;
+
+    fn current_sender(&self) -> &str;
+
+    /// Account the next contract is deployed from
+    fn deployer(&self) -> &str;
+
+    fn accounts(&self) -> &[String];
+
+    /// Address of a contract deployed through this executor
+    fn contract_address(&self, contract_name: &str) -> Option<&str>;
+
+    /// Human-readable endpoint, used in progress output
+    fn rpc_url(&self) -> &str;
+}
+
+#[async_trait]
+impl Executor for AnvilForkExecutor {
+    async fn deploy_contract(
+        &mut self,
+        contract_name: &str,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+    ) -> Result<String> {
+        AnvilForkExecutor::deploy_contract(self, contract_name, bytecode, constructor_args).await
+    }
+
+    async fn estimate_deployment_gas(&mut self, bytecode: &[u8], constructor_args: Option<&[u8]>) -> Result<Option<u64>> {
+        AnvilForkExecutor::estimate_deployment_gas(self, bytecode, constructor_args).await.map(Some)
+    }
+
+    async fn call_method(
+        &mut self,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<MethodExecutionResult> {
+        AnvilForkExecutor::call_method(self, contract_name, method_signature, encoded_args).await
+    }
+
+    async fn call_methods_batched(
+        &mut self,
+        contract_name: &str,
+        calls: &[(String, Vec<u8>)],
+    ) -> Result<Vec<MethodExecutionResult>> {
+        if !self.multicall_enabled() {
+            let mut results = Vec::with_capacity(calls.len());
+            for (method_signature, encoded_args) in calls {
+                results.push(AnvilForkExecutor::call_method(self, contract_name, method_signature, encoded_args).await?);
+            }
+            return Ok(results);
+        }
+        AnvilForkExecutor::call_methods_multicall(self, contract_name, calls).await
+    }
+
+    async fn static_call(&mut self, contract_name: &str, calldata: &[u8]) -> Result<Vec<u8>> {
+        AnvilForkExecutor::static_call(self, contract_name, calldata).await
+    }
+
+    async fn contract_balance(&mut self, contract_name: &str) -> Result<U256> {
+        AnvilForkExecutor::contract_balance(self, contract_name).await
+    }
+
+    async fn account_balance(&mut self, address: &str) -> Result<U256> {
+        AnvilForkExecutor::account_balance(self, address).await
+    }
+
+    async fn code_at(&mut self, address: &str) -> Result<Vec<u8>> {
+        AnvilForkExecutor::code_at(self, address).await
+    }
+
+    async fn storage_at(&mut self, contract_name: &str, slot: U256) -> Result<U256> {
+        AnvilForkExecutor::storage_at(self, contract_name, slot).await
+    }
+
+    async fn last_call_coverage(&mut self) -> Result<Vec<BranchEdge>> {
+        AnvilForkExecutor::last_call_coverage(self).await
+    }
+
+    async fn last_call_frames(&mut self) -> Result<Vec<CallFrame>> {
+        AnvilForkExecutor::last_call_frames(self).await
+    }
+
+    async fn last_call_wraparounds(&mut self) -> Result<Vec<WrappedOperation>> {
+        AnvilForkExecutor::last_call_wraparounds(self).await
+    }
+
+    async fn last_call_logs(&mut self) -> Result<Vec<EventLog>> {
+        Ok(AnvilForkExecutor::last_call_logs(self))
+    }
+
+    async fn snapshot(&mut self) -> Result<String> {
+        AnvilForkExecutor::snapshot(self).await
+    }
+
+    async fn revert_to_snapshot(&mut self, snapshot_id: &str) -> Result<()> {
+        AnvilForkExecutor::revert_to_snapshot(self, snapshot_id).await
+    }
+
+    async fn base_fee_per_gas(&mut self) -> Result<Option<u128>> {
+        AnvilForkExecutor::base_fee_per_gas(self).await
+    }
+
+    async fn fork_block_number(&mut self) -> Result<Option<u64>> {
+        AnvilForkExecutor::fork_block_number(self).await
+    }
+
+    async fn chain_id(&mut self) -> Result<u64> {
+        AnvilForkExecutor::chain_id(self).await
+    }
+
+    fn set_fees(&mut self, fees: Option<TxFees>) {
+        AnvilForkExecutor::set_fees(self, fees)
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        AnvilForkExecutor::set_gas_limit(self, gas_limit)
+    }
+
+    fn set_value(&mut self, value: U256) {
+        AnvilForkExecutor::set_value(self, value)
+    }
+
+    async fn set_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        AnvilForkExecutor::set_block_context(self, block_context).await
+    }
+
+    async fn reset_block_context(&mut self) -> Result<()> {
+        AnvilForkExecutor::reset_block_context(self).await
+    }
+
+    async fn apply_cheat(&mut self, cheat: &Cheat) -> Result<()> {
+        AnvilForkExecutor::apply_cheat(self, cheat).await
+    }
+
+    async fn set_code(&mut self, address: &str, code: &[u8]) -> Result<()> {
+        AnvilForkExecutor::set_code(self, address, code).await
+    }
+
+    fn register_errors(&mut self, abi: &Abi) {
+        AnvilForkExecutor::register_errors(self, abi)
+    }
+
+    fn set_sender(&mut self, sender_index: usize) {
+        AnvilForkExecutor::set_sender(self, sender_index)
+    }
+
+    fn set_sender_address(&mut self, address: &str) -> bool {
+        AnvilForkExecutor::set_sender_address(self, address)
+    }
+
+    async fn attach_contract(&mut self, contract_name: &str, address: &str) -> Result<Vec<u8>> {
+        AnvilForkExecutor::attach_contract(self, contract_name, address).await
+    }
+
+    async fn impersonate(&mut self, address: &str) -> Result<()> {
+        AnvilForkExecutor::impersonate(self, address).await
+    }
+
+    fn current_sender(&self) -> &str {
+        AnvilForkExecutor::current_sender(self)
+    }
+
+    fn deployer(&self) -> &str {
+        AnvilForkExecutor::deployer(self)
+    }
+
+    fn accounts(&self) -> &[String] {
+        AnvilForkExecutor::accounts(self)
+    }
+
+    fn contract_address(&self, contract_name: &str) -> Option<&str> {
+        AnvilForkExecutor::contract_address(self, contract_name)
+    }
+
+    fn rpc_url(&self) -> &str {
+        AnvilForkExecutor::rpc_url(self)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::live_target;
+use crate::types::{Finding, SolidityValue};
+use ethers::abi::Abi;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Block explorers known by name. Etherscan-style and Blockscout explorers share the
+/// `/address/` and `/block/` paths, so any other one can be given by its base URL.
+const PRESETS: [(&str, &str); 7] = [
+    ("base", "https://base.blockscout.com"),
+    ("base-sepolia", "https://base-sepolia.blockscout.com"),
+    ("ethereum", "https://eth.blockscout.com"),
+    ("sepolia", "https://eth-sepolia.blockscout.com"),
+    ("optimism", "https://optimism.blockscout.com"),
+    ("horizen-eon", "https://eon-explorer.horizenlabs.io"),
+    ("horizen-gobi", "https://gobi-explorer.horizenlabs.io"),
+];
+
+/// Block explorer of the chain a campaign forks, used to link findings to on-chain pages
+#[derive(Debug, Clone)]
+pub struct Explorer {
+    pub base_url: String,
+}
+
+impl FromStr for Explorer {
+    type Err = String;
+
+    /// A preset name (`base`, `horizen-eon`, ...) or the explorer's base URL
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some((_, url)) = PRESETS.iter().find(|(name, _)| name.eq_ignore_ascii_case(value)) {
+            return Ok(Self { base_url: url.to_string() });
+        }
+        if value.starts_with("http://") || value.starts_with("https://") {
+            return Ok(Self { base_url: value.trim_end_matches('/').to_string() });
+        }
+        let names = PRESETS.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ");
+        Err(format!("unknown explorer '{}' (expected one of {} or an http(s) URL)", value, names))
+    }
+}
+
+/// An explorer page related to a finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerLink {
+    /// What the page shows, e.g. `sender` or `argument 1`
+    pub label: String,
+    pub url: String,
+}
+
+impl Explorer {
+    pub fn address_url(&self, address: &str) -> String {
+        format!("{}/address/{}", self.base_url, address)
+    }
+
+    pub fn block_url(&self, block_number: u64) -> String {
+        format!("{}/block/{}", self.base_url, block_number)
+    }
+
+    /// The verified ABI of a contract, from the Etherscan-compatible API Blockscout explorers
+    /// serve under `/api` without a key
+    pub async fn fetch_abi(&self, client: &reqwest::Client, address: &str) -> anyhow::Result<Abi> {
+        let url = format!("{}/api?module=contract&action=getabi&address={}", self.base_url, address);
+        let response: serde_json::Value = client.get(&url).send().await?.error_for_status()?.json().await?;
+        live_target::parse_abi(response, &url)
+    }
+
+    /// Pages of the upstream block the fork was taken at and of the on-chain accounts a finding
+    /// involves: its sender and every non-zero address among its arguments. The failing transaction
+    /// and the fuzzed target only exist on the local fork, so they have no page.
+    pub fn links(&self, finding: &Finding, fork_block_number: Option<u64>) -> Vec<ExplorerLink> {
+        let mut links = Vec::new();
+        if let Some(block_number) = fork_block_number {
+            links.push(ExplorerLink { label: "fork block".to_string(), url: self.block_url(block_number) });
+        }
+
+        let mut addresses = vec![("sender".to_string(), finding.sender.clone())];
+        for (index, arg) in finding.args.iter().enumerate() {
+            collect_addresses(arg, &format!("argument {}", index + 1), &mut addresses);
+        }
+        let mut seen = Vec::new();
+        for (label, address) in addresses {
+            let address = address.to_ascii_lowercase();
+            if is_zero_address(&address) || seen.contains(&address) {
+                continue;
+            }
+            links.push(ExplorerLink { label, url: self.address_url(&address) });
+            seen.push(address);
+        }
+        links
+    }
+}
+
+fn collect_addresses(value: &SolidityValue, label: &str, addresses: &mut Vec<(String, String)>) {
+    match value {
+        SolidityValue::Address(address) => addresses.push((label.to_string(), address.clone())),
+        SolidityValue::Array(items) | SolidityValue::FixedArray(items) => {
+            for item in items {
+                collect_addresses(item, label, addresses);
+            }
+        }
+        SolidityValue::Struct(fields) => {
+            for (name, field) in fields {
+                collect_addresses(field, &format!("{}.{}", label, name), addresses);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_zero_address(address: &str) -> bool {
+    address.trim_start_matches("0x").chars().all(|c| c == '0')
+}
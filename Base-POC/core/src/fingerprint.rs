@@ -0,0 +1,65 @@
+use crate::abi_encoding;
+use crate::executor::Executor;
+use ethers::abi::{Abi, StateMutability};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Values of every parameterless view/pure function of a contract at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct Fingerprint {
+    /// Keyed by getter signature, e.g. `totalSupply()`; a failed read is kept as `reverted: <reason>`
+    pub values: BTreeMap<String, String>,
+}
+
+/// A getter whose value differs between the baseline and the end of the campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateChange {
+    pub contract: String,
+    pub getter: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl std::fmt::Display for StateChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}: {} → {}", self.contract, self.getter, self.before, self.after)
+    }
+}
+
+/// Call every parameterless view/pure function of the ABI (public state variables included)
+pub async fn sample(executor: &mut dyn Executor, contract_name: &str, abi: &Abi) -> Fingerprint {
+    let mut fingerprint = Fingerprint::default();
+    for function in abi.functions() {
+        if !function.inputs.is_empty()
+            || !matches!(function.state_mutability, StateMutability::View | StateMutability::Pure)
+        {
+            continue;
+        }
+        let getter = format!("{}()", function.name);
+        let value = match executor.static_call(contract_name, &function.short_signature()).await {
+            Ok(output) => match function.decode_output(&output) {
+                Ok(tokens) if tokens.len() == 1 => abi_encoding::format_token(&tokens[0]),
+                Ok(tokens) => format!("({})", tokens.iter().map(abi_encoding::format_token).collect::<Vec<_>>().join(", ")),
+                Err(_) => format!("0x{}", hex::encode(&output)),
+            },
+            Err(e) => format!("reverted: {}", e),
+        };
+        fingerprint.values.insert(getter, value);
+    }
+    fingerprint
+}
+
+/// Getters whose value changed, in signature order
+pub fn diff(contract: &str, before: &Fingerprint, after: &Fingerprint) -> Vec<StateChange> {
+    before.values.iter()
+        .filter_map(|(getter, before)| {
+            let after = after.values.get(getter)?;
+            (before != after).then(|| StateChange {
+                contract: contract.to_string(),
+                getter: getter.clone(),
+                before: before.clone(),
+                after: after.clone(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::types::{ContractMethod, SolidityValue};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// forge's defaults for `failure_persist_dir` of fuzz and invariant tests
+const DEFAULT_FUZZ_DIR: &str = "cache/fuzz";
+const DEFAULT_INVARIANT_DIR: &str = "cache/invariant";
+
+/// A call forge recorded while a fuzz or invariant test failed
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    /// Signature of the called function, when forge recorded it (e.g. `deposit(uint256)`)
+    pub signature: Option<String>,
+    /// Selector followed by the ABI-encoded arguments
+    pub calldata: Vec<u8>,
+}
+
+/// Failure persistence directories of a Foundry project: `failure_persist_dir` of the `fuzz` and
+/// `invariant` sections of `foundry.toml` (default profile or top level), else forge's defaults
+pub fn persistence_dirs(project_root: &Path) -> Result<Vec<PathBuf>> {
+    let config_path = project_root.join("foundry.toml");
+    let config: toml::Table = if config_path.exists() {
+        let text = std::fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        text.parse().with_context(|| format!("Invalid {}", config_path.display()))?
+    } else {
+        toml::Table::new()
+    };
+
+    let configured = |section: &str| -> Option<String> {
+        let from_profile = config.get("profile")
+            .and_then(|profile| profile.get("default"))
+            .and_then(|profile| profile.get(section));
+        from_profile.or_else(|| config.get(section))
+            .and_then(|section| section.get("failure_persist_dir"))
+            .and_then(|dir| dir.as_str())
+            .map(str::to_string)
+    };
+    Ok([
+        configured("fuzz").unwrap_or_else(|| DEFAULT_FUZZ_DIR.to_string()),
+        configured("invariant").unwrap_or_else(|| DEFAULT_INVARIANT_DIR.to_string()),
+    ]
+    .into_iter()
+    .map(|dir| project_root.join(dir))
+    .collect())
+}
+
+/// Every counterexample recorded under the project's failure persistence directories. Invariant
+/// failures are JSON call sequences; other files are scanned for hex calldata, which covers the
+/// regression files of fuzz tests. Unreadable files are skipped.
+pub fn load(project_root: &Path) -> Result<Vec<Counterexample>> {
+    let mut counterexamples = Vec::new();
+    for dir in persistence_dirs(project_root)? {
+        for entry in WalkDir::new(&dir).into_iter().filter_map(|entry| entry.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            match serde_json::from_str::<Value>(&text) {
+                Ok(json) => collect_json(&json, &mut counterexamples),
+                Err(_) => collect_text(&text, &mut counterexamples),
+            }
+        }
+    }
+    Ok(counterexamples)
+}
+
+/// Objects with a `calldata` field, anywhere in the document (a bare call sequence, or one under `call_sequence`)
+fn collect_json(json: &Value, counterexamples: &mut Vec<Counterexample>) {
+    match json {
+        Value::Array(items) => items.iter().for_each(|item| collect_json(item, counterexamples)),
+        Value::Object(fields) => {
+            if let Some(calldata) = fields.get("calldata").and_then(Value::as_str).and_then(decode_hex) {
+                counterexamples.push(Counterexample {
+                    signature: fields.get("signature").and_then(Value::as_str).map(str::to_string),
+                    calldata,
+                });
+            }
+            fields.values().for_each(|value| collect_json(value, counterexamples));
+        }
+        _ => {}
+    }
+}
+
+/// `0x` hex words long enough to hold a selector
+fn collect_text(text: &str, counterexamples: &mut Vec<Counterexample>) {
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()) {
+        if let Some(calldata) = decode_hex(word).filter(|bytes| bytes.len() >= 4) {
+            counterexamples.push(Counterexample { signature: None, calldata });
+        }
+    }
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    hex::decode(text.strip_prefix("0x")?).ok()
+}
+
+/// Arguments of `method` found in counterexamples: calls to the method itself (same selector), and
+/// calls to a forge test whose name contains the method's name and whose parameters have the same
+/// types, e.g. `testFuzz_Deposit(uint256)` for `deposit(uint256)`
+pub fn inputs_for(method: &ContractMethod, counterexamples: &[Counterexample]) -> Vec<Vec<SolidityValue>> {
+    let signature = abi_encoding::method_signature(method);
+    let selector = calculate_selector(&signature);
+    let param_list = &signature[method.name.len()..];
+    let types: Vec<_> = method.parameters.iter().map(|param| param.param_type.clone()).collect();
+
+    let mut inputs: Vec<Vec<SolidityValue>> = Vec::new();
+    for counterexample in counterexamples.iter().filter(|counterexample| counterexample.calldata.len() >= 4) {
+        let same_method = counterexample.calldata[..4] == selector;
+        let harness_of_method = counterexample.signature.as_ref().is_some_and(|test_signature| {
+            test_signature.split_once('(').is_some_and(|(name, params)| {
+                format!("({}", params) == param_list && name.to_ascii_lowercase().contains(&method.name.to_ascii_lowercase())
+            })
+        });
+        if !same_method && !harness_of_method {
+            continue;
+        }
+        if let Ok(args) = abi_encoding::decode_args(&types, &counterexample.calldata[4..]) {
+            if !inputs.contains(&args) {
+                inputs.push(args);
+            }
+        }
+    }
+    inputs
+}
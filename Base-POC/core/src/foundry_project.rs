@@ -0,0 +1,174 @@
+//! Whole Foundry projects. Instead of compiling each file with its own `forge build --force`, the
+//! project is built once with the layout of its `foundry.toml`, and every deployable contract is
+//! read from the artifacts in `out/`: contracts of the `src` directory with creation code, so
+//! interfaces, abstract contracts, tests, scripts and dependencies are left out.
+
+use crate::accounting;
+use crate::contract_compiler::ContractCompiler;
+use crate::deployment;
+use crate::linking;
+use crate::types::CompileInfo;
+use anyhow::{Context, Result};
+use ethers::abi::Abi;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Backend shown for contracts read from a project build
+const BACKEND: &str = "forge (project build)";
+
+/// Directories of a Foundry project, from `foundry.toml` (default profile or top level), else
+/// forge's defaults
+#[derive(Debug, Clone)]
+pub struct FoundryProject {
+    pub root: PathBuf,
+    pub src: PathBuf,
+    pub out: PathBuf,
+}
+
+/// A deployable contract of the project build
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub bytecode: Vec<u8>,
+    pub abi: Abi,
+    pub info: CompileInfo,
+}
+
+/// Artifacts of a project build by source file and contract name, shared by every file's campaign
+#[derive(Debug, Clone, Default)]
+pub struct ProjectArtifacts {
+    contracts: BTreeMap<(PathBuf, String), Artifact>,
+}
+
+impl FoundryProject {
+    pub fn load(path: &Path) -> Result<Self> {
+        let root = path.canonicalize()
+            .with_context(|| format!("Project directory not found: {}", path.display()))?;
+        let config_path = root.join("foundry.toml");
+        if !config_path.exists() {
+            return Err(anyhow::anyhow!("No foundry.toml in {}", root.display()));
+        }
+        let text = std::fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let config: toml::Table = text.parse().with_context(|| format!("Invalid {}", config_path.display()))?;
+
+        let configured = |key: &str, default: &str| -> PathBuf {
+            let from_profile = config.get("profile")
+                .and_then(|profile| profile.get("default"))
+                .and_then(|profile| profile.get(key));
+            let dir = from_profile.or_else(|| config.get(key))
+                .and_then(|dir| dir.as_str())
+                .unwrap_or(default);
+            root.join(dir)
+        };
+        Ok(Self {
+            src: configured("src", "src"),
+            out: configured("out", "out"),
+            root,
+        })
+    }
+
+    /// `forge build` of the whole project, with the storage layouts the accounting checks read
+    pub fn build(&self, forge_path: &str) -> Result<()> {
+        debug!("Building Foundry project at {:?}", self.root);
+        let output = Command::new(forge_path)
+            .args(["build", "--extra-output", "storageLayout"])
+            .current_dir(&self.root)
+            .output()
+            .context("Failed to execute forge build")?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Forge build failed (project root: {:?}):\nSTDOUT: {}\nSTDERR: {}",
+                self.root,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deployable contracts of the `src` directory found in `out/`
+    pub fn artifacts(&self) -> Result<ProjectArtifacts> {
+        let mut artifacts = ProjectArtifacts::default();
+        if !self.out.is_dir() {
+            return Ok(artifacts);
+        }
+        let build_info = self.out.join("build-info");
+        for entry in WalkDir::new(&self.out).into_iter().filter_entry(|entry| entry.path() != build_info) {
+            let entry = entry?;
+            if entry.path().extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let text = std::fs::read_to_string(entry.path())?;
+            let Ok(artifact) = serde_json::from_str::<Value>(&text) else {
+                debug!("Skipping unreadable artifact {:?}", entry.path());
+                continue;
+            };
+            let Some((source, contract)) = compilation_target(&artifact) else {
+                continue;
+            };
+            let source = self.root.join(source);
+            if !source.starts_with(&self.src) {
+                continue;
+            }
+            let bytecode_hex = artifact.get("bytecode")
+                .and_then(|bytecode| bytecode.get("object").or(Some(bytecode)))
+                .and_then(|object| object.as_str())
+                .unwrap_or_default();
+            let libraries = linking::referenced_libraries(artifact.get("bytecode").and_then(|bytecode| bytecode.get("linkReferences")));
+            let (bytecode, links) = linking::decode(bytecode_hex, &libraries, |unit| self.root.join(unit))
+                .with_context(|| format!("Invalid bytecode in {}", entry.path().display()))?;
+            if bytecode.is_empty() {
+                // Interfaces and abstract contracts have no creation code
+                continue;
+            }
+            let abi: Abi = serde_json::from_value(artifact.get("abi").cloned().unwrap_or(Value::Array(Vec::new())))
+                .with_context(|| format!("Invalid ABI in {}", entry.path().display()))?;
+            let mut info = ContractCompiler::compile_info_from_metadata(&contract, BACKEND, artifact.get("metadata"));
+            info.storage_layout = accounting::parse_storage_layout(artifact.get("storageLayout"));
+            info.libraries = links;
+            info.runtime_size = deployment::code_size(artifact.get("deployedBytecode"));
+            let source = source.canonicalize().unwrap_or(source);
+            // Builds with several compiler versions write one artifact per version; the first is kept
+            artifacts.contracts.entry((source, contract)).or_insert(Artifact { bytecode, abi, info });
+        }
+        Ok(artifacts)
+    }
+}
+
+impl ProjectArtifacts {
+    /// The contract `contract_name` of `source_path`, if the project build has it
+    pub fn get(&self, source_path: &Path, contract_name: &str) -> Option<&Artifact> {
+        let source = source_path.canonicalize().ok()?;
+        self.contracts.get(&(source, contract_name.to_string()))
+    }
+
+    /// Source files with at least one deployable contract, in path order
+    pub fn sources(&self) -> Vec<PathBuf> {
+        let mut sources: Vec<PathBuf> = self.contracts.keys().map(|(source, _)| source.clone()).collect();
+        sources.dedup();
+        sources
+    }
+
+    pub fn len(&self) -> usize {
+        self.contracts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+}
+
+/// Source unit and contract name an artifact was compiled for, from its metadata, which may be an
+/// object or a JSON string
+fn compilation_target(artifact: &Value) -> Option<(String, String)> {
+    let metadata = match artifact.get("metadata")? {
+        Value::String(raw) => serde_json::from_str::<Value>(raw).ok()?,
+        value => value.clone(),
+    };
+    let (source, contract) = metadata.get("settings")?.get("compilationTarget")?.as_object()?.iter().next()?;
+    Some((source.clone(), contract.as_str()?.to_string()))
+}
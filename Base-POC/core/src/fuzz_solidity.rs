@@ -0,0 +1,3950 @@
+use crate::types::*;
+use crate::address_pool;
+use crate::ast_parser::{ContractInfo, SolidityParser};
+use crate::anvil_executor::{build_http_client, calculate_selector, AnvilForkExecutor, MethodExecutionResult, DEFAULT_GAS_LIMIT};
+use crate::executor::Executor;
+use crate::mock_executor::MockExecutor;
+use crate::revm_executor::RevmExecutor;
+use crate::contract_compiler::ContractCompiler;
+use crate::config::{Backend, FuzzConfig, MethodSource, SolcSelection};
+use crate::attacks::{AttackRecipe, ReplayOutcome};
+use crate::oracle::{self, OracleProfile, RevertClass, SolidityVersion};
+use crate::trace::{TracePhase, TraceRecord, TransactionTracer};
+use crate::coverage::{CorpusEntry, CoverageMap, Corpus};
+use crate::hooks::{HookCall, Hooks};
+use crate::invariants::{self, InvariantViolation};
+use crate::standards::{self, Standard, StandardChecks};
+use crate::accounting::{self, StorageVariable, ValueLedger};
+use crate::case::{self, FailingCase};
+use crate::dictionary::Dictionary;
+use crate::events::{self, EventDecoder};
+use crate::live_target::LiveTarget;
+use crate::gas_profile::GasAlert;
+use crate::mutation::{CorpusMutation, RandomGeneration, Strategy};
+use crate::corpus::{self, CorpusSequence, CorpusTx};
+use crate::foundry_project::ProjectArtifacts;
+use crate::deployment;
+use crate::linking::{self, LibraryLink};
+use crate::proxy::{self, ProxyInfo, ProxyKind};
+use anyhow::Context;
+use crate::{abi_encoding, access_control, call_tree, bricking, display, fingerprint, forge_failures, gas_profile, generator, harness, multicall, ownership, ether_drain, precompiles, recommendations, reentrancy, scribble, selectors, shrink, solc_manager, wraparound};
+use crate::generator::{CallValue, GeneratorContext, PlanOptions};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use std::path::Path;
+use std::sync::Arc;
+use ethers::types::U256;
+use tracing::{debug, warn};
+
+/// Lowest gas limit an OOG probe sends (the intrinsic cost of a call)
+const MIN_GAS_LIMIT: u64 = 21_000;
+
+/// Gas a sender sending its whole balance keeps paying for (the executors' default gas limit)
+const VALUE_RESERVE_GAS: u64 = 0x1000000;
+
+/// Fee per gas assumed for that reserve when the node picks the fees
+const VALUE_RESERVE_FEE_PER_GAS: u128 = 100_000_000_000;
+
+/// Properties of one contract checked after fuzzed calls
+struct PropertyChecks {
+    /// `invariant_*`/`echidna_*` functions that have not been violated yet
+    invariants: Vec<String>,
+    /// ETH balance next to the contract's accounting variables, with value accounting on
+    ledger: Option<ValueLedger>,
+    /// Properties of the standards the contract declares through ERC-165 or implements (ERC-20)
+    standards: Option<StandardChecks>,
+    /// Accounts allowed to take ETH out of the contract (the deployer and the owner), checked
+    /// against at the end of each sequence
+    drain_privileged: Vec<String>,
+}
+
+impl PropertyChecks {
+    fn is_empty(&self) -> bool {
+        self.invariants.is_empty() && self.ledger.is_none() && self.standards.is_none()
+    }
+}
+
+/// What the confirmation phase made of a contract's candidate findings, counted in occurrences
+#[derive(Debug, Default)]
+struct ConfirmationTotals {
+    confirmed: usize,
+    unconfirmed: usize,
+    /// Replays that errored (RPC, encoding, snapshots): neither confirmed nor refuted
+    errors: usize,
+}
+
+/// Run counters of the single-call phase of one method
+#[derive(Debug, Default)]
+struct MethodTotals {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    errors: usize,
+    flaky: usize,
+    checked_math_panics: usize,
+    expected_reverts: usize,
+    block_context_dependent: usize,
+    gas_dependent: usize,
+    system_address_successes: usize,
+    /// A method is reported as re-entrant once, at its first re-entry
+    reentered: bool,
+    wrapped: bool,
+    /// Reverts of a privileged method for a sender that is neither the deployer nor the owner
+    access_denied: usize,
+    unauthorized: bool,
+    /// Failures inside a multicall batch that an individual transaction did not reproduce
+    unconfirmed: usize,
+    calls: usize,
+    gas_used: u64,
+    gas_min: u64,
+    gas_max: u64,
+    /// Costliest individually sent call over the gas threshold or out of gas
+    gas_alert: Option<GasAlert>,
+    /// Parameters already reported for succeeding with a precompile or system contract
+    system_address_params: HashSet<String>,
+}
+
+impl MethodTotals {
+    fn record_gas(&mut self, gas_used: u64) {
+        self.gas_min = if self.calls == 0 { gas_used } else { self.gas_min.min(gas_used) };
+        self.gas_max = self.gas_max.max(gas_used);
+        self.gas_used += gas_used;
+        self.calls += 1;
+    }
+
+    fn stats(&self, contract: &str, method: String, branch_edges: usize) -> MethodStats {
+        MethodStats {
+            contract: contract.to_string(),
+            method,
+            calls: self.calls,
+            passed: self.passed,
+            failed: self.failed,
+            expected_reverts: self.expected_reverts,
+            errors: self.errors,
+            skipped: self.skipped,
+            gas_used: self.gas_used,
+            gas_min: self.gas_min,
+            gas_max: self.gas_max,
+            branch_edges,
+        }
+    }
+}
+
+/// The method being fuzzed and what its iterations are checked against
+struct MethodTarget<'a> {
+    contract: &'a ContractInfo,
+    abi: &'a ethers::abi::Abi,
+    method: &'a ContractMethod,
+    signature: &'a str,
+    oracle: &'a OracleProfile,
+    /// Storage variables a privileged method must not let an unauthorized sender change
+    protected_variables: &'a [StorageVariable],
+}
+
+/// A single iteration's call as it was sent
+struct IterationCall {
+    /// 1-based, as reported
+    iteration: usize,
+    plan: generator::IterationPlan,
+    fees: Option<TxFees>,
+    value: U256,
+    /// Sent as its own transaction rather than inside a multicall batch
+    sent_alone: bool,
+}
+
+/// An executed iteration, before its outcome is classified
+struct ExecutedIteration {
+    call: IterationCall,
+    result: TestResult,
+    /// Outcome of the differential run under the alternative block context
+    alt_result: Option<TestResult>,
+    /// The attacker contract was handed to the target and calls back into it
+    attacker_armed: bool,
+    /// Protected state before the call, when a privileged method was called by someone not entitled to it
+    unauthorized: Option<access_control::ProtectedState>,
+}
+
+/// Run counters of the stateful phase of one contract
+#[derive(Debug, Default)]
+struct SequenceTotals {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    errors: usize,
+    flaky: usize,
+    checked_math_panics: usize,
+    expected_reverts: usize,
+    /// Sequences after which an unprivileged account had taken ETH out of the contract
+    ether_drains: usize,
+    invariant_violations: Vec<InvariantViolation>,
+}
+
+/// Progress of a campaign run in time slices (`fuzz_slice`), carried from one slice to the next
+#[derive(Default)]
+struct SliceState {
+    /// End of the current slice
+    deadline: Option<Instant>,
+    /// The current slice ran out before the file was finished
+    interrupted: bool,
+    /// Next iteration of each `Contract.signature` method that has started
+    next_iteration: HashMap<String, usize>,
+    /// Contracts whose slice ended part way, with their deployment and property state
+    paused: HashMap<String, PausedContract>,
+    /// Contracts that are done (or were skipped)
+    completed: HashSet<String>,
+    /// Dispatch tables of every contract deployed so far, checked for proxy shadowing once the file is done
+    dispatch_tables: Vec<(String, Vec<String>, bool)>,
+    /// Contracts deployed with the rest of the file whose campaign has not started yet
+    deployed: HashMap<String, DeployedTarget>,
+    /// Contracts done so far that take part in the cross-contract sequences
+    cross_targets: Vec<CrossTarget>,
+}
+
+/// A fuzzed contract whose methods take part in the cross-contract sequences at the end of the file
+struct CrossTarget {
+    contract: ContractInfo,
+    methods: Vec<ContractMethod>,
+    oracle: OracleProfile,
+    plan_options: PlanOptions,
+}
+
+/// A deployed contract whose campaign continues in a later slice
+struct PausedContract {
+    target: DeployedTarget,
+    baseline: fingerprint::Fingerprint,
+    properties: PropertyChecks,
+}
+
+/// A passing input that also succeeded under a reduced gas limit but left different state
+struct GasProbe {
+    gas_limit: u64,
+    /// Gas the input used with the default limit
+    gas_used: u64,
+    changes: Vec<fingerprint::StateChange>,
+}
+
+pub struct SolidityFuzzer {
+    parser: SolidityParser,
+    master_seed: u64,
+    executor: Box<dyn Executor>,
+    /// Faithful (non-turbo) fork used to replay candidate findings in dual-phase campaigns
+    confirm_executor: Option<Box<dyn Executor>>,
+    compiler: ContractCompiler,
+    config: FuzzConfig,
+    generator_context: GeneratorContext,
+    /// Writes every sent transaction to the `--trace-all` file
+    tracer: Option<TransactionTracer>,
+    /// `Contract.signature` of methods found to brick their contract; never called again
+    skip_list: HashSet<String>,
+    /// User WASM module from `--hooks`
+    hooks: Option<Hooks>,
+    /// Calls forge recorded in failing fuzz and invariant tests, run before generated inputs
+    forge_counterexamples: Vec<forge_failures::Counterexample>,
+    /// Transactions of Echidna and Medusa corpora, whose arguments run after the forge counterexamples
+    imported_corpus: Vec<CorpusTx>,
+    /// Set while the campaign runs in time slices
+    slice: Option<SliceState>,
+    /// Account index of each contract's impersonated owner, for contracts owned by an account the fuzzer does not control
+    owners: HashMap<String, usize>,
+    /// Account each contract was deployed from, allowed to call its privileged methods
+    deployers: HashMap<String, String>,
+    /// Events of every deployed contract's ABI, to decode the logs of fuzzed calls with
+    event_decoder: EventDecoder,
+    /// Deployed contracts and their functions, to name the frames of call trees
+    call_names: call_tree::Names,
+    /// The executor failed to trace a call; findings are reported without call trees from then on
+    call_traces_unavailable: bool,
+    /// Verified ABIs of proxy implementations by address, `None` where the explorer had none
+    implementation_abis: HashMap<String, Option<ethers::abi::Abi>>,
+}
+
+impl SolidityFuzzer {
+    pub async fn new(config: FuzzConfig) -> Result<Self, anyhow::Error> {
+        let client = build_http_client(Duration::from_secs(config.rpc_timeout_secs))?;
+        Self::with_client(config, client).await
+    }
+
+    /// Create a fuzzer that reuses an existing HTTP client (and its connection pool)
+    pub async fn with_client(config: FuzzConfig, client: reqwest::Client) -> Result<Self, anyhow::Error> {
+        if config.dry_run {
+            println!("🧪 Dry run: parsing, generation and encoding only, calls go to a mock executor (no network)");
+            let mut executor: Box<dyn Executor> = Box::new(MockExecutor::new());
+            Self::impersonate_senders(executor.as_mut(), &config.accounts.impersonate).await?;
+            return Ok(Self::with_executor(config, executor));
+        }
+
+        if config.multicall_batch > multicall::MAX_BATCH_SIZE {
+            return Err(anyhow::anyhow!("--multicall-batch must be at most {}", multicall::MAX_BATCH_SIZE));
+        }
+
+        let mut executor: Box<dyn Executor> = match config.backend {
+            Backend::Revm => {
+                println!("🦀 revm backend: transactions run in-process on an empty chain (no fork state)");
+                if config.multicall_batch > 0 {
+                    println!("📦 revm has no per-transaction overhead, batched calls are executed one by one");
+                }
+                Box::new(RevmExecutor::new(&config.accounts)?)
+            }
+            Backend::Anvil => {
+                let mut anvil_executor = AnvilForkExecutor::with_accounts(&config.fork_url, client.clone(), &config.accounts, config.fork_block_number).await?;
+                anvil_executor.set_nonce_gap_repair(config.repair_nonce_gaps);
+                anvil_executor.set_receipt_timeout(Duration::from_secs(config.receipt_timeout_secs));
+                // Exploration always runs in turbo mode in a dual-phase campaign; realism comes from the confirmation fork
+                if config.turbo || config.is_dual_phase() {
+                    anvil_executor.enable_turbo_mode().await?;
+                    println!("⚡ Turbo mode: auto-impersonation, zero base fee, instant mining, unlimited block gas");
+                }
+                if config.multicall_batch > 0 {
+                    anvil_executor.enable_multicall(&config.multicall_address).await
+                        .map_err(|e| anyhow::anyhow!("--multicall-batch needs Multicall3 on the fork (see --multicall-address): {}", e))?;
+                    println!("📦 Multicall batches of {} calls through {}; failures are confirmed with individual transactions",
+                        config.multicall_batch, config.multicall_address);
+                }
+                Box::new(anvil_executor)
+            }
+        };
+        Self::impersonate_senders(executor.as_mut(), &config.accounts.impersonate).await?;
+
+        let confirm_executor = match &config.confirm_fork_url {
+            Some(confirm_url) => {
+                if config.backend == Backend::Anvil && confirm_url == &config.fork_url {
+                    return Err(anyhow::anyhow!(
+                        "--confirm-fork-url must point to a different Anvil instance than --fork-url (turbo cheats would leak into confirmation)"
+                    ));
+                }
+                println!("🧪 Dual-phase campaign: exploring on {}, confirming on {}", config.fork_url, confirm_url);
+                let mut confirm_executor = AnvilForkExecutor::with_accounts(confirm_url, client, &config.accounts, config.fork_block_number).await?;
+                confirm_executor.set_nonce_gap_repair(config.repair_nonce_gaps);
+                confirm_executor.set_receipt_timeout(Duration::from_secs(config.receipt_timeout_secs));
+                Some(Box::new(confirm_executor) as Box<dyn Executor>)
+            }
+            None => None,
+        };
+
+        let mut fuzzer = Self::with_executor(config, executor);
+        fuzzer.confirm_executor = confirm_executor;
+        if let Some(confirm_executor) = fuzzer.confirm_executor.as_mut() {
+            confirm_executor.set_fees(fuzzer.config.fees.fixed());
+        }
+        Ok(fuzzer)
+    }
+
+    /// Add the `--impersonate` accounts to the executor's senders, before the generator takes its
+    /// snapshot of them
+    async fn impersonate_senders(executor: &mut dyn Executor, accounts: &[String]) -> Result<(), anyhow::Error> {
+        if accounts.is_empty() {
+            return Ok(());
+        }
+        for account in accounts {
+            executor.impersonate(account).await
+                .map_err(|e| anyhow::anyhow!("--impersonate {} needs a node that supports anvil_impersonateAccount: {:#}", account, e))?;
+        }
+        println!("🎭 Sending from {} impersonated on-chain account(s) as well: {}", accounts.len(), accounts.join(", "));
+        Ok(())
+    }
+
+    /// Create a fuzzer on top of any executor backend
+    pub fn with_executor(config: FuzzConfig, mut executor: Box<dyn Executor>) -> Self {
+        executor.set_fees(config.fees.fixed());
+
+        // Keep the generator's "known account" pool in sync with the accounts the executor sends from
+        let generator_context = GeneratorContext {
+            known_addresses: executor.accounts().to_vec(),
+            system_addresses: Vec::new(),
+            attacker_address: None,
+            contract_addresses: Vec::new(),
+            discovered_addresses: Vec::new(),
+            address_weights: config.address_weights,
+            dictionary: Dictionary::default(),
+        };
+        Self {
+            parser: SolidityParser::with_inherited_methods(config.all_contracts),
+            master_seed: config.seed.unwrap_or_else(rand::random),
+            executor,
+            confirm_executor: None,
+            compiler: ContractCompiler::with_options(config.compiler.clone()),
+            config,
+            generator_context,
+            tracer: None,
+            skip_list: HashSet::new(),
+            hooks: None,
+            forge_counterexamples: Vec::new(),
+            imported_corpus: Vec::new(),
+            slice: None,
+            owners: HashMap::new(),
+            deployers: HashMap::new(),
+            event_decoder: EventDecoder::default(),
+            call_names: call_tree::Names::default(),
+            call_traces_unavailable: false,
+            implementation_abis: HashMap::new(),
+        }
+    }
+
+    /// Let a user WASM module generate values, judge outcomes and order methods
+    pub fn set_hooks(&mut self, hooks: Hooks) {
+        self.hooks = Some(hooks);
+    }
+
+    /// Run the arguments of these forge counterexamples first on the methods they fit
+    pub fn set_forge_counterexamples(&mut self, counterexamples: Vec<forge_failures::Counterexample>) {
+        self.forge_counterexamples = counterexamples;
+    }
+
+    /// Run the arguments of these corpus transactions first on the methods whose selector they call
+    pub fn set_imported_corpus(&mut self, transactions: Vec<CorpusTx>) {
+        self.imported_corpus = transactions;
+    }
+
+    /// Deploy contracts from a project built once up front instead of compiling each file
+    pub fn set_project_artifacts(&mut self, artifacts: Arc<ProjectArtifacts>) {
+        self.compiler.use_project_artifacts(artifacts);
+    }
+
+    /// Log every transaction sent from now on (exploration, confirmation and replays)
+    pub fn set_tracer(&mut self, tracer: TransactionTracer) {
+        self.tracer = Some(tracer);
+    }
+
+    /// Fuzz a file for at most `slice`, picking up where the previous slice of the same file
+    /// stopped: deployed contracts, iteration counts and invariant state are kept, coverage corpora
+    /// start over. The summary covers this slice only; call again until `campaign_complete`.
+    pub async fn fuzz_slice(&mut self, source: &str, filename: &str, slice: Duration) -> Result<FuzzSummary, anyhow::Error> {
+        let state = self.slice.get_or_insert_with(SliceState::default);
+        state.deadline = Some(Instant::now() + slice);
+        state.interrupted = false;
+        self.fuzz_contract(source, filename).await
+    }
+
+    /// Whether the last `fuzz_slice` finished the file (always true outside of time slices)
+    pub fn campaign_complete(&self) -> bool {
+        self.slice.as_ref().is_none_or(|state| !state.interrupted)
+    }
+
+    /// Whether the current time slice has run out; once it has, it stays interrupted
+    fn slice_expired(&mut self) -> bool {
+        let Some(state) = self.slice.as_mut() else {
+            return false;
+        };
+        if !state.interrupted && state.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            state.interrupted = true;
+        }
+        state.interrupted
+    }
+
+    fn slice_interrupted(&self) -> bool {
+        self.slice.as_ref().is_some_and(|state| state.interrupted)
+    }
+
+    /// Record a contract as done so later slices skip it
+    fn complete_contract(&mut self, contract_name: &str) {
+        if let Some(state) = self.slice.as_mut() {
+            state.completed.insert(contract_name.to_string());
+        }
+    }
+
+    pub async fn fuzz_contract(&mut self, source: &str, filename: &str) -> Result<FuzzSummary, anyhow::Error> {
+        let instrumented = Self::instrument_scribble(source, filename)?;
+        if let Some((instrumentation, _)) = &instrumented {
+            println!("📜 {} Scribble annotation(s) compiled into checks:", instrumentation.properties.len());
+            for property in &instrumentation.properties {
+                println!("   ↳ {}", property);
+            }
+        }
+        let source = instrumented.as_ref().map_or(source, |(instrumentation, _)| instrumentation.source.as_str());
+        let contracts = self.parser.parse_contract(source, filename)?;
+        let source_path = instrumented.as_ref().map_or(Path::new(filename), |(_, file)| file.path());
+        self.fuzz_contracts(source, source_path, contracts, HashMap::new()).await
+    }
+
+    /// Fuzz a contract that is already deployed, through its ABI: the compile and deploy stages
+    /// are skipped and the contract is attached at its address on the fork
+    pub async fn fuzz_live(&mut self, target: &LiveTarget) -> Result<FuzzSummary, anyhow::Error> {
+        let code = self.executor.attach_contract(&target.name, &target.address).await?;
+        println!("🎯 Fuzzing {} at {} on {} ({} bytes of code), nothing compiled or deployed",
+            target.name, target.address, self.executor.rpc_url(), code.len());
+        self.register_abi(&target.name, &target.abi);
+        let attached = DeployedTarget {
+            bytecode: code,
+            abi: target.abi.clone(),
+            compile_info: None,
+            constructor_args: None,
+            attached: true,
+            libraries: Vec::new(),
+        };
+        let contracts = vec![target.contract_info()];
+        self.fuzz_contracts("", Path::new(&target.name), contracts, HashMap::from([(target.name.clone(), attached)])).await
+    }
+
+    /// The campaign over the contracts of a file: every contract not in `attached` is compiled
+    /// from `source_path` and deployed, then each is fuzzed in turn
+    async fn fuzz_contracts(
+        &mut self,
+        source: &str,
+        source_path: &Path,
+        contracts: Vec<ContractInfo>,
+        attached: HashMap<String, DeployedTarget>,
+    ) -> Result<FuzzSummary, anyhow::Error> {
+        let mut total_passed = 0;
+        let mut total_failed = 0;
+        let mut total_skipped = 0;
+        let mut total_errors = 0;
+        let mut total_flaky = 0;
+        let mut total_unconfirmed = 0;
+        let mut total_confirmation_errors = 0;
+        let mut total_checked_math_panics = 0;
+        let mut total_expected_reverts = 0;
+        let mut total_block_context_dependent = 0;
+        let mut total_gas_dependent = 0;
+        let mut total_system_address_successes = 0;
+        let mut total_reentrancies = 0;
+        let mut total_wraparounds = 0;
+        let mut total_access_denied = 0;
+        let mut total_unauthorized_changes = 0;
+        let mut total_ether_drains = 0;
+        let mut findings = Vec::new();
+        let mut skipped_contracts = Vec::new();
+        let mut compile_info = Vec::new();
+        let mut method_seeds = Vec::new();
+        let mut selector_issues = Vec::new();
+        let mut bricking_calls = Vec::new();
+        let mut gas_alerts = Vec::new();
+        let mut invariant_violations = Vec::new();
+        let mut value_flows = Vec::new();
+        let mut state_changes = Vec::new();
+        let mut method_stats = Vec::new();
+        let mut recommendations = Vec::new();
+        let mut exported_corpus = Vec::new();
+        let campaign_start = Instant::now();
+        let mut deployments = Vec::new();
+        let fork_block_number = match self.executor.fork_block_number().await {
+            Ok(block) => block,
+            Err(e) => {
+                warn!("Could not read fork block number: {}", e);
+                None
+            }
+        };
+        if self.generator_context.system_addresses.is_empty() {
+            let chain_id = match self.executor.chain_id().await {
+                Ok(chain_id) => Some(chain_id),
+                Err(e) => {
+                    warn!("Could not read chain id, generating Ethereum precompiles only: {}", e);
+                    None
+                }
+            };
+            self.generator_context.system_addresses = precompiles::for_chain(chain_id);
+            println!("🔌 {} precompile and system contract address(es) of chain {} in the address pool",
+                self.generator_context.system_addresses.len(), chain_id.map_or_else(|| "?".to_string(), |id| id.to_string()));
+        }
+        // (contract, external signatures, has fallback) of every deployed contract, for proxy shadowing checks
+        let mut dispatch_tables = Vec::new();
+
+        // Every contract of the file is deployed before the first one is fuzzed, so each can be
+        // handed the addresses of the others
+        let (mut deployed, mut cross_targets) = match self.slice.as_mut() {
+            Some(state) => (std::mem::take(&mut state.deployed), std::mem::take(&mut state.cross_targets)),
+            None => (HashMap::new(), Vec::new()),
+        };
+        // Contracts deployed or attached by this call, whose proxies are announced and reported
+        let mut fresh: HashSet<String> = attached.keys().cloned().collect();
+        for (name, target) in attached {
+            deployed.entry(name).or_insert(target);
+        }
+        // Without --all-contracts the file's first contract is the only one deployed and fuzzed
+        let secondary: HashSet<String> = if self.config.all_contracts {
+            HashSet::new()
+        } else {
+            contracts.iter()
+                .filter(|contract| contract.kind.is_deployable() && !self.config.excludes_contract(&contract.name))
+                .skip(1)
+                .map(|contract| contract.name.clone())
+                .collect()
+        };
+        let pending: Vec<&ContractInfo> = contracts.iter()
+            .filter(|contract| contract.kind.is_deployable() && !self.config.excludes_contract(&contract.name))
+            .filter(|contract| !secondary.contains(&contract.name))
+            .filter(|contract| !deployed.contains_key(&contract.name))
+            .filter(|contract| self.slice.as_ref()
+                .is_none_or(|state| !state.completed.contains(&contract.name) && !state.paused.contains_key(&contract.name)))
+            .collect();
+        let newly_deployed = pending.len();
+        if newly_deployed > 1 {
+            println!("🏗️  Deploying {} contracts", newly_deployed);
+        }
+        for contract in pending {
+            println!("Deploying contract: {}", contract.name);
+            let target = self.compile_and_deploy(source_path, contract).await?;
+            deployed.insert(contract.name.clone(), target);
+            fresh.insert(contract.name.clone());
+        }
+        self.generator_context.contract_addresses = contracts.iter()
+            .filter_map(|contract| self.executor.contract_address(&contract.name).map(str::to_string))
+            .collect();
+        if newly_deployed > 0 && self.generator_context.contract_addresses.len() > 1 {
+            println!("🔗 {} deployed contract address(es) in the address pool", self.generator_context.contract_addresses.len());
+        }
+        if newly_deployed > 0 {
+            self.generator_context.discovered_addresses.clear();
+            for contract in contracts.iter().filter(|contract| deployed.contains_key(&contract.name)) {
+                self.discover_contracts(contract).await;
+            }
+        }
+        let (implementations, proxies) = self.resolve_proxies(&contracts, &deployed, &fresh).await;
+        if newly_deployed > 0 || !proxies.is_empty() {
+            println!();
+        }
+
+        for contract in contracts {
+            if self.slice.as_ref().is_some_and(|state| state.completed.contains(&contract.name)) {
+                continue;
+            }
+            if self.slice_expired() {
+                break;
+            }
+            if self.config.excludes_contract(&contract.name) {
+                println!("⏭️  Skipping {} (excluded by configuration)", contract.name);
+                skipped_contracts.push(SkippedContract {
+                    name: contract.name.clone(),
+                    reason: "excluded by configuration".to_string(),
+                });
+                self.complete_contract(&contract.name);
+                continue;
+            }
+            if secondary.contains(&contract.name) {
+                println!("⏭️  Skipping {} (only the file's first contract is fuzzed without --all-contracts)", contract.name);
+                skipped_contracts.push(SkippedContract {
+                    name: contract.name.clone(),
+                    reason: "not the file's first contract (see --all-contracts)".to_string(),
+                });
+                self.complete_contract(&contract.name);
+                continue;
+            }
+            if !contract.kind.is_deployable() {
+                // Interfaces and abstract contracts have no deployable bytecode; forge would only
+                // produce a confusing artifact-not-found error for them
+                println!("⏭️  Skipping {} {} (not deployable)", contract.kind, contract.name);
+                skipped_contracts.push(SkippedContract {
+                    name: contract.name.clone(),
+                    reason: format!("{} (not deployable)", contract.kind),
+                });
+                self.complete_contract(&contract.name);
+                continue;
+            }
+
+            let paused = self.slice.as_mut().and_then(|state| state.paused.remove(&contract.name));
+            if paused.is_some() {
+                println!("Resuming contract: {}", contract.name);
+            } else {
+                println!("Fuzzing contract: {}", contract.name);
+            }
+            println!("{}", "-".repeat(50));
+
+            let (target, resumed) = match paused {
+                Some(PausedContract { target, baseline, properties }) => (target, Some((baseline, properties))),
+                None => match deployed.remove(&contract.name) {
+                    Some(target) => (target, None),
+                    None => (self.compile_and_deploy(source_path, &contract).await?, None),
+                },
+            };
+            let DeployedTarget { bytecode: contract_bytecode, abi: contract_abi, compile_info: info, constructor_args, attached, libraries } = target;
+            let compiler_version = info.as_ref().and_then(|info| info.compiler_version.clone());
+            let storage_layout = info.as_ref().map(|info| info.storage_layout.clone()).unwrap_or_default();
+            if resumed.is_none() {
+                if let Some(address) = self.executor.contract_address(&contract.name) {
+                    deployments.push(Deployment {
+                        contract: contract.name.clone(),
+                        address: address.to_string(),
+                        constructor_args: constructor_args.clone(),
+                        attached,
+                    });
+                }
+                compile_info.extend(info.clone());
+            }
+
+            // Interpret reverts in the semantics of the compiler that built the target
+            let solidity_version = compiler_version.as_deref()
+                .and_then(SolidityVersion::parse)
+                .or_else(|| SolidityParser::extract_pragma(source).as_deref().and_then(SolidityVersion::parse));
+            let oracle = OracleProfile::for_version(solidity_version)
+                .with_revert_rules(&self.config.expected_reverts, &self.config.bug_panics);
+            println!("- {}", oracle.describe());
+            self.generator_context.dictionary = match implementations.get(&contract.name) {
+                Some(implementation) => Dictionary::harvest(source, &[contract_bytecode.as_slice(), &implementation.code].concat()),
+                None => Dictionary::harvest(source, &contract_bytecode),
+            };
+            if !self.generator_context.dictionary.is_empty() {
+                println!("- Dictionary: {} constant(s) from the source and bytecode", self.generator_context.dictionary.len());
+            }
+            // Turned off for the rest of the contract when the executor cannot trace calls
+            let mut wraparound_detection = oracle.wraparound_detection;
+            let protected_variables = if self.config.access_control {
+                let variables = access_control::protected_variables(&storage_layout);
+                let labels = variables.iter().map(|variable| variable.label.as_str()).collect::<Vec<_>>().join(", ");
+                println!("- Access control: privileged methods checked against {} and the owner getter", labels);
+                variables
+            } else {
+                Vec::new()
+            };
+
+            let contract = self.discover_methods(contract, &contract_abi, resumed.is_none());
+            // A Foundry harness is driven through its fuzz tests only, as `forge test` would
+            let is_harness = harness::is_harness(&contract);
+
+            if resumed.is_none() {
+                let signatures = external_signatures(&contract, &contract_abi);
+                for issue in selectors::check_contract(&contract.name, &signatures) {
+                    println!("  🧷 {}", issue);
+                    selector_issues.push(issue);
+                }
+                let has_fallback = contract_abi.fallback || contract.methods.iter().any(|m| m.is_fallback);
+                dispatch_tables.push((contract.name.clone(), signatures, has_fallback));
+            }
+            // A proxy is fuzzed with its implementation's methods as well, called through the proxy
+            let contract = match implementations.get(&contract.name) {
+                Some(implementation) => proxy::with_implementation(contract, &implementation.contract),
+                None => contract,
+            };
+
+
+            let num_fuzz_runs = self.config.runs_per_method;
+
+            // Find all public/external methods
+            let methods_to_test: Vec<_> = contract.methods.iter()
+                .filter(|method| {
+                    (method.visibility == MethodVisibility::Public || method.visibility == MethodVisibility::External) 
+                    && !method.is_constructor 
+                    && !method.is_fallback 
+                    && !method.is_receive
+                    && (!method.parameters.is_empty() || !invariants::is_invariant(&method.name))
+                    && (!is_harness || harness::is_fuzz_test(&method.name))
+                    && !self.config.excludes_method(&contract.name, method)
+                })
+                .collect();
+
+            if methods_to_test.is_empty() {
+                println!("   - No public methods found to fuzz");
+                self.complete_contract(&contract.name);
+                continue;
+            }
+
+            let (baseline, mut properties) = match resumed {
+                Some(resumed) => resumed,
+                None => {
+                    if is_harness {
+                        println!("- Foundry harness: {} fuzz test(s), vm.assume rejections counted as skipped runs", methods_to_test.len());
+                        Self::prepare_harness(self.executor.as_mut(), &contract).await;
+                    }
+                    if self.config.reentrancy {
+                        self.deploy_attacker().await;
+                    }
+                    self.bootstrap_owner(&contract.name, &contract_abi).await;
+                    if self.config.discover_senders {
+                        self.discover_senders(&contract.name, &storage_layout).await;
+                    }
+                    let properties = self.setup_properties(&contract, &contract_abi, &storage_layout, &mut invariant_violations).await;
+                    let baseline = fingerprint::sample(self.executor.as_mut(), &contract.name, &contract_abi).await;
+                    if !baseline.values.is_empty() {
+                        println!("- Baseline fingerprint: {} view function(s) sampled", baseline.values.len());
+                    }
+                    (baseline, properties)
+                }
+            };
+
+            let contract_findings_start = findings.len();
+            println!("- Starting fuzzing of {} method(s)...", methods_to_test.len());
+            println!();
+
+            // Fee fuzzing draws fees relative to the base fee at the start of the contract's campaign
+            let fuzz_base_fee = if self.config.fees.fuzz {
+                match self.executor.base_fee_per_gas().await {
+                    Ok(Some(base_fee)) => Some(base_fee),
+                    Ok(None) => {
+                        warn!("Chain reports no base fee, fee fuzzing disabled");
+                        None
+                    }
+                    Err(e) => {
+                        warn!("Could not read base fee, fee fuzzing disabled: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let plan_options = PlanOptions {
+                fee_base: fuzz_base_fee,
+                block_context: self.config.fuzz_block_context,
+                gas_limit: self.config.fuzz_gas_limit,
+                owner_sender: self.owners.get(&contract.name).map(|index| (*index, self.config.owner_percent)),
+                payable: false,
+            };
+
+            // Branch edges reached by all methods; `None` when coverage guidance is off or unavailable
+            let mut coverage_map = self.config.coverage_guided.then(CoverageMap::default);
+
+            // Most recent passing call of each method (sender, method, args, value), replayed to detect bricking
+            let mut probe_calls: Vec<(String, &ContractMethod, Vec<SolidityValue>, U256)> = Vec::new();
+
+            let method_count = methods_to_test.len();
+            let sequence_methods = methods_to_test.clone();
+            let mut remaining_methods = methods_to_test;
+            while !remaining_methods.is_empty() && !self.slice_expired() {
+                let method = remaining_methods.remove(self.next_method_index(&contract.name, &remaining_methods));
+                let method_key = format!("{}.{}", contract.name, abi_encoding::method_signature(method));
+                let first_iteration = self.slice.as_ref()
+                    .and_then(|state| state.next_iteration.get(&method_key).copied())
+                    .unwrap_or(0);
+                if first_iteration >= num_fuzz_runs {
+                    continue;
+                }
+                // A payable method without parameters still has its `msg.value` to fuzz
+                let payable = method.mutability == StateMutability::Payable;
+                if method.parameters.is_empty() && !payable {
+                    println!("- Skipping method: {} (no input parameters)", method.name);
+                    continue;
+                }
+
+                let signature = abi_encoding::method_signature(method);
+                if self.skip_list.contains(&format!("{}.{}", contract.name, signature)) {
+                    println!("- Skipping method: {} (bricks the contract)", method.name);
+                    continue;
+                }
+
+                if first_iteration > 0 {
+                    println!("- Resuming method: {} at iteration {}", method.display_signature(), first_iteration + 1);
+                } else {
+                    println!("- Fuzzing method: {}", method.display_signature());
+                }
+                self.print_value_strategies(&contract.name, method);
+
+                // Restored if the method turns out to brick the contract; isolated iterations cannot brick it
+                let method_snapshot = if self.config.keep_state {
+                    match self.executor.snapshot().await {
+                        Ok(id) => Some(id),
+                        Err(e) => {
+                            warn!("Could not snapshot fork before {}, bricking detection disabled for it: {}", method.name, e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let mut last_passing_call = None;
+                let method_seed = generator::method_seed(self.master_seed, &contract.name, &signature);
+                if first_iteration == 0 {
+                    method_seeds.push(MethodSeed {
+                        contract: contract.name.clone(),
+                        method: signature.clone(),
+                        seed: method_seed,
+                    });
+                }
+
+                let method_findings_start = findings.len();
+                let mut method_totals = MethodTotals::default();
+                let target = MethodTarget {
+                    contract: &contract,
+                    abi: &contract_abi,
+                    method,
+                    signature: &signature,
+                    oracle: &oracle,
+                    protected_variables: &protected_variables,
+                };
+                // Guides this method only, so its corpus does not depend on which methods ran before it
+                let mut method_coverage = CoverageMap::default();
+                let mut corpus = Corpus::default();
+                // Distinct outcomes (success, revert reasons) that earned an input its corpus place with `--mutate`
+                let mut method_outcomes = HashSet::new();
+                let mut method_mutated = 0;
+                // Iterations already executed in the current multicall batch, with their outcomes
+                let mut batched_iterations = VecDeque::new();
+                // Replace the arguments of the first iterations; with coverage guidance they seed the corpus
+                let mut imported: Vec<_> = forge_failures::inputs_for(method, &self.forge_counterexamples).into_iter()
+                    .map(|args| (args, ArgProvenance::ForgeCounterexample))
+                    .collect();
+                if !imported.is_empty() {
+                    println!("  🧩 {} forge counterexample(s) run first", imported.len().min(num_fuzz_runs));
+                }
+                let forge_inputs = imported.len();
+                for args in corpus::inputs_for(method, &self.imported_corpus) {
+                    if !imported.iter().any(|(existing, _)| *existing == args) {
+                        imported.push((args, ArgProvenance::ImportedCorpus));
+                    }
+                }
+                if imported.len() > forge_inputs && forge_inputs < num_fuzz_runs {
+                    println!("  📥 {} imported corpus input(s) run first", imported.len().min(num_fuzz_runs) - forge_inputs);
+                }
+
+                let mut next_iteration = num_fuzz_runs;
+                // Snapshot the current iteration started from, restored before the next one
+                let mut iteration_snapshot = None;
+                let mut isolate_iterations = !self.config.keep_state;
+                for i in first_iteration..num_fuzz_runs {
+                    // A multicall batch is finished before the slice ends, its outcomes are already in
+                    if batched_iterations.is_empty() && self.slice_expired() {
+                        next_iteration = i;
+                        break;
+                    }
+                    // Every iteration (or multicall batch) starts from the state the method started from
+                    if isolate_iterations && batched_iterations.is_empty() {
+                        self.restore_iteration(&mut iteration_snapshot, &contract.name, &mut properties).await;
+                        match self.executor.snapshot().await {
+                            Ok(id) => iteration_snapshot = Some(id),
+                            Err(e) => {
+                                warn!("Could not snapshot fork, state now carries over between iterations of {}: {}", method.name, e);
+                                isolate_iterations = false;
+                            }
+                        }
+                    }
+                    let iteration_seed = generator::iteration_seed(method_seed, i + 1);
+                    // Payable calls go out one by one, the aggregator forwards no value
+                    let (plan, batched_outcome) = if self.config.multicall_batch > 0 && !payable {
+                        if batched_iterations.is_empty() {
+                            let plans: Vec<_> = (i..num_fuzz_runs.min(i + self.config.multicall_batch))
+                                .map(|j| {
+                                    let plan = self.plan_iteration(&contract.name, method, generator::iteration_seed(method_seed, j + 1), &plan_options, None);
+                                    Self::import_args(plan, imported.get(j))
+                                })
+                                .collect();
+                            batched_iterations = self.execute_batch_metered(method, plans, &contract).await.into();
+                        }
+                        batched_iterations.pop_front().expect("batch covers the current iteration")
+                    } else {
+                        let corpus = (coverage_map.is_some() || self.config.mutation).then_some(&corpus);
+                        let plan = self.plan_iteration(&contract.name, method, iteration_seed, &plan_options, corpus);
+                        (Self::import_args(plan, imported.get(i)), None)
+                    };
+                    let Some(executed) = self.execute_iteration(&target, plan, batched_outcome, i + 1, &mut method_totals, &mut findings).await else {
+                        continue;
+                    };
+                    let call = &executed.call;
+                    let mock_args = &call.plan.args;
+                    let result = &executed.result;
+
+                    if executed.attacker_armed && !method_totals.reentered && !matches!(result, TestResult::Error(_)) {
+                        if let Some(chain) = self.detect_reentry(&contract).await {
+                            println!("  🪃 {}.{}({}) can be re-entered on iteration {}: {}",
+                                contract.name, method.name, self.format_args_for_display(mock_args), call.iteration, chain);
+                            println!("     ↳ {}", Self::format_provenance(method, &call.plan.provenance));
+                            method_totals.reentered = true;
+                            findings.push(self.iteration_finding(&target, call, format!("Reentrancy: {}", chain)));
+                        }
+                    }
+
+                    // A multicall batch has already run as a whole, so its invariants are checked once, at its end
+                    let batch_end = self.config.multicall_batch == 0 || batched_iterations.is_empty();
+                    if !properties.is_empty() && batch_end && !matches!(result, TestResult::Error(_)) {
+                        let sequence_end = i + 1 == num_fuzz_runs || self.config.multicall_batch > 0;
+                        let after_call = format!("{}({}) from {} (iteration {})",
+                            method.name, self.format_args_for_display(mock_args), self.executor.current_sender(), call.iteration);
+                        self.check_properties(&contract, &mut properties, call.iteration, sequence_end, Some((after_call, call.plan.seed)), &mut invariant_violations).await;
+                    }
+
+                    if let (Some(before), TestResult::Passed, false) = (&executed.unauthorized, result, method_totals.unauthorized) {
+                        match access_control::read(self.executor.as_mut(), &contract.name, &contract_abi, &protected_variables).await {
+                            Ok(after) => {
+                                let changes = access_control::changes(before, &after);
+                                if !changes.is_empty() {
+                                    let changes = changes.join(", ");
+                                    println!("  🔓 {}.{}({}) from {} changed protected state on iteration {}: {}",
+                                        contract.name, method.name, self.format_args_for_display(mock_args), self.executor.current_sender(), call.iteration, changes);
+                                    println!("     ↳ {}", Self::format_provenance(method, &call.plan.provenance));
+                                    method_totals.unauthorized = true;
+                                    findings.push(Finding {
+                                        impersonated_owner: false,
+                                        ..self.iteration_finding(&target, call, format!("Unauthorized state change (sender is neither the deployer nor the owner): {}", changes))
+                                    });
+                                }
+                            }
+                            Err(e) => warn!("Could not read protected state of {} after {}: {:#}", contract.name, method.name, e),
+                        }
+                    }
+
+                    // Wrapping in a call that reverts afterwards is SafeMath (or a later check) doing its job
+                    if wraparound_detection && call.sent_alone && !method_totals.wrapped && matches!(result, TestResult::Passed) {
+                        match self.executor.last_call_wraparounds().await {
+                            Ok(operations) => {
+                                if let Some((argument, operation)) = wraparound::attribute(&operations, &method.parameters, mock_args) {
+                                    println!("  🌀 {}.{}({}) wraps around on iteration {}: {} with {} ({})",
+                                        contract.name, method.name, self.format_args_for_display(mock_args), call.iteration, operation.kind(), argument, operation);
+                                    println!("     ↳ {}", Self::format_provenance(method, &call.plan.provenance));
+                                    method_totals.wrapped = true;
+                                    findings.push(self.iteration_finding(&target, call, format!("Integer {} with {}: {}", operation.kind(), argument, operation)));
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Execution traces unavailable, wraparound detection disabled: {:#}", e);
+                                wraparound_detection = false;
+                            }
+                        }
+                    }
+
+                    if let (Some(map), false) = (coverage_map.as_mut(), matches!(result, TestResult::Error(_))) {
+                        match self.executor.last_call_coverage().await {
+                            Ok(edges) => {
+                                map.merge(&edges);
+                                if method_coverage.merge(&edges) > 0 {
+                                    corpus.add(CorpusEntry {
+                                        args: mock_args.clone(),
+                                        provenance: call.plan.provenance.clone(),
+                                        sender_index: call.plan.sender_index,
+                                    });
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Coverage unavailable, continuing with random inputs: {:#}", e);
+                                coverage_map = None;
+                            }
+                        }
+                    } else if self.config.mutation {
+                        // Without coverage, an input is interesting when the method had not ended this way before
+                        let outcome = match result {
+                            TestResult::Passed => Some("passed"),
+                            TestResult::Failed(error) => Some(error.as_str()),
+                            TestResult::Error(_) => None,
+                        };
+                        if outcome.is_some_and(|outcome| method_outcomes.insert(outcome.to_string())) {
+                            corpus.add(CorpusEntry {
+                                args: mock_args.clone(),
+                                provenance: call.plan.provenance.clone(),
+                                sender_index: call.plan.sender_index,
+                            });
+                        }
+                    }
+                    if call.plan.provenance.contains(&ArgProvenance::Mutation) {
+                        method_mutated += 1;
+                    }
+
+                    if matches!(result, TestResult::Passed) {
+                        last_passing_call = Some((self.executor.current_sender().to_string(), mock_args.clone(), call.value));
+                    }
+                    self.classify_outcome(&target, executed, &mut findings, method_findings_start, &mut method_totals).await;
+                }
+
+                self.restore_iteration(&mut iteration_snapshot, &contract.name, &mut properties).await;
+                self.executor.set_value(U256::zero());
+                if plan_options.block_context {
+                    if let Err(e) = self.executor.reset_block_context().await {
+                        warn!("Could not restore block context after {}: {:#}", method.name, e);
+                    }
+                }
+
+                Self::print_failure_groups(&findings[method_findings_start..]);
+                if let Some(alert) = method_totals.gas_alert.take() {
+                    println!("  🔥 {}", alert);
+                    gas_alerts.push(alert);
+                }
+
+                if let Some(state) = self.slice.as_mut() {
+                    state.next_iteration.insert(method_key, next_iteration);
+                }
+
+                if let (Some(snapshot_id), true) = (&method_snapshot, method_totals.passed > 0) {
+                    if let Some(error) = self.probe_bricking(&probe_calls, &contract).await {
+                        let bricking_call = bricking::BrickingCall {
+                            contract: contract.name.clone(),
+                            method: signature.clone(),
+                            error,
+                            probes: probe_calls.len(),
+                        };
+                        println!("  🧱 {}", bricking_call);
+                        match self.executor.revert_to_snapshot(snapshot_id).await {
+                            Ok(()) => println!("     ↳ state restored to before {}, skipped for the rest of the campaign", method.name),
+                            Err(e) => warn!("Could not restore fork after bricking call {}: {}", method.name, e),
+                        }
+                        self.resync_properties(&contract.name, &mut properties).await;
+                        self.skip_list.insert(format!("{}.{}", contract.name, signature));
+                        bricking_calls.push(bricking_call);
+                        last_passing_call = None;
+                    }
+                }
+                // State carries over with `--keep-state`, so the method may have created contracts
+                if self.config.keep_state && method_totals.passed > 0 {
+                    self.discover_contracts(&contract).await;
+                }
+                if let Some((sender, args, value)) = last_passing_call {
+                    probe_calls.retain(|(_, probe_method, _, _)| abi_encoding::method_signature(probe_method) != signature);
+                    probe_calls.push((sender, method, args, value));
+                    if probe_calls.len() > bricking::MAX_PROBES {
+                        probe_calls.remove(0);
+                    }
+                }
+
+                if coverage_map.is_some() {
+                    println!("  🧭 {} branch edge(s) reached, {} corpus input(s)", method_coverage.len(), corpus.len());
+                } else if self.config.mutation {
+                    println!("  🧬 {} corpus input(s) with distinct outcomes", corpus.len());
+                }
+                if method_mutated > 0 {
+                    println!("  🧬 {} iteration(s) mutated from the corpus", method_mutated);
+                }
+                if self.config.export_corpus {
+                    let target = self.executor.contract_address(&contract.name).unwrap_or_default().to_string();
+                    for entry in corpus.entries() {
+                        let sender = self.executor.accounts().get(entry.sender_index).cloned().unwrap_or_default();
+                        if let Some(tx) = CorpusTx::call(method, &entry.args, &sender, &target, U256::zero()) {
+                            exported_corpus.push(CorpusSequence { contract: contract.name.clone(), transactions: vec![tx], reproducer: false });
+                        }
+                    }
+                }
+
+                total_passed += method_totals.passed;
+                total_failed += method_totals.failed;
+                total_skipped += method_totals.skipped;
+                total_errors += method_totals.errors;
+                total_flaky += method_totals.flaky;
+                total_checked_math_panics += method_totals.checked_math_panics;
+                total_expected_reverts += method_totals.expected_reverts;
+                total_block_context_dependent += method_totals.block_context_dependent;
+                total_gas_dependent += method_totals.gas_dependent;
+                total_system_address_successes += method_totals.system_address_successes;
+                total_reentrancies += usize::from(method_totals.reentered);
+                total_wraparounds += usize::from(method_totals.wrapped);
+                total_access_denied += method_totals.access_denied;
+                total_unauthorized_changes += usize::from(method_totals.unauthorized);
+                total_unconfirmed += method_totals.unconfirmed;
+                method_stats.push(method_totals.stats(&contract.name, signature, method_coverage.len()));
+            }
+
+            // The stateful phase and the end-of-campaign checks run once every method is done
+            let interrupted = self.slice_interrupted();
+            if self.config.sequences > 0 && !interrupted {
+                // Methods that brick the contract would end every sequence they appear in
+                let sequence_methods: Vec<_> = sequence_methods.into_iter()
+                    .filter(|method| !self.skip_list.contains(&format!("{}.{}", contract.name, abi_encoding::method_signature(method))))
+                    .collect();
+                // Every sequence starts from the same snapshot, so the owner is read once
+                properties.drain_privileged = self.deployers.get(&contract.name).cloned().into_iter()
+                    .chain(ownership::read_owner(self.executor.as_mut(), &contract.name, &contract_abi).await.map(|owner| owner.address))
+                    .collect();
+                let sequence_totals = self.run_sequence_phase(
+                    &contract,
+                    &sequence_methods,
+                    &plan_options,
+                    &oracle,
+                    &mut properties,
+                    &mut findings,
+                ).await;
+                cross_targets.push(CrossTarget {
+                    contract: contract.clone(),
+                    methods: sequence_methods.iter().map(|method| (*method).clone()).collect(),
+                    oracle: oracle.clone(),
+                    plan_options,
+                });
+                total_passed += sequence_totals.passed;
+                total_failed += sequence_totals.failed;
+                total_skipped += sequence_totals.skipped;
+                total_errors += sequence_totals.errors;
+                total_flaky += sequence_totals.flaky;
+                total_checked_math_panics += sequence_totals.checked_math_panics;
+                total_expected_reverts += sequence_totals.expected_reverts;
+                total_ether_drains += sequence_totals.ether_drains;
+                invariant_violations.extend(sequence_totals.invariant_violations);
+            }
+
+            if !baseline.values.is_empty() && !interrupted {
+                let after = fingerprint::sample(self.executor.as_mut(), &contract.name, &contract_abi).await;
+                let changes = fingerprint::diff(&contract.name, &baseline, &after);
+                if changes.is_empty() {
+                    println!("🔬 No observable state change in {} view function(s) of {}", baseline.values.len(), contract.name);
+                } else {
+                    println!("🔬 Observable state changes of {}:", contract.name);
+                    for change in &changes {
+                        println!("   {}: {} → {}", change.getter, change.before, change.after);
+                    }
+                }
+                state_changes.extend(changes);
+            }
+
+            if let (Some(ledger), false) = (&properties.ledger, interrupted) {
+                let flows = ledger.flows(&contract.name);
+                println!("💰 ETH flows of {}: {} wei in, {} wei out, balance now {} wei", contract.name, flows.inflow, flows.outflow, flows.balance);
+                value_flows.push(flows);
+            }
+
+            if self.confirm_executor.is_some() {
+                let confirmation = self.run_confirmation_phase(
+                    &contract,
+                    &contract_bytecode,
+                    constructor_args.as_deref(),
+                    &libraries,
+                    &mut findings[contract_findings_start..],
+                ).await?;
+                total_failed += confirmation.confirmed;
+                total_unconfirmed += confirmation.unconfirmed;
+                total_confirmation_errors += confirmation.errors;
+            }
+
+            println!();
+            if interrupted {
+                println!("⏸️  Time slice used up, {} continues in the next round", contract.name);
+            }
+            println!("🏁 Fuzzing complete:");
+            if self.slice.is_some() {
+                println!("   ⏱️  Counts cover this time slice only");
+            }
+            println!("   ✅ {} runs passed", total_passed);
+            println!("   ❌ {} runs failed", total_failed);
+            if total_skipped > 0 {
+                println!("   ⏭️  {} runs skipped (unsupported parameter types or inputs rejected by vm.assume)", total_skipped);
+            }
+            if total_flaky > 0 {
+                println!("   🔁 {} runs flaky (did not reproduce on every re-execution, not counted as failures)", total_flaky);
+            }
+            if total_block_context_dependent > 0 {
+                println!("   🎲 {} inputs depend on block context (coinbase/basefee/chainid, miner-manipulable)", total_block_context_dependent);
+            }
+            if total_gas_dependent > 0 {
+                println!("   ⛽ {} inputs leave different state under a reduced gas limit (partial execution under OOG)", total_gas_dependent);
+            }
+            if total_system_address_successes > 0 {
+                println!("   🔌 {} calls succeeded with a precompile or system contract argument (the first per parameter is listed)", total_system_address_successes);
+            }
+            if total_reentrancies > 0 {
+                println!("   🪃 {} methods can be re-entered through the attacker contract", total_reentrancies);
+            }
+            if total_wraparounds > 0 {
+                println!("   🌀 {} methods wrap arithmetic around with their arguments (unchecked arithmetic)", total_wraparounds);
+            }
+            if total_unauthorized_changes > 0 {
+                println!("   🔓 {} privileged methods changed protected state for a sender that is neither the deployer nor the owner", total_unauthorized_changes);
+            }
+            if total_ether_drains > 0 {
+                println!("   💸 {} sequences let an account other than the deployer or owner take ETH out of the contract", total_ether_drains);
+            }
+            if !invariant_violations.is_empty() {
+                println!("   🚨 {} invariant violation(s)", invariant_violations.len());
+            }
+            if !gas_alerts.is_empty() {
+                println!("   🔥 {} method(s) with a call over the gas threshold or out of gas", gas_alerts.len());
+            }
+            if total_checked_math_panics > 0 {
+                println!("   🧮 {} runs hit checked-math panics (expected on Solidity >=0.8, not counted as failures)", total_checked_math_panics);
+            }
+            if total_expected_reverts > 0 {
+                println!("   🚧 {} runs reverted with an expected reason (access control, pausing, reentrancy guards…; not counted as failures)", total_expected_reverts);
+            }
+            if total_access_denied > 0 {
+                println!("   🛂 {} runs of privileged methods reverted for senders other than the deployer and owner (expected, not counted as failures)", total_access_denied);
+            }
+            if total_unconfirmed > 0 {
+                println!("   🧪 {} runs unconfirmed (failed only under turbo exploration or inside a multicall batch, not counted as failures)", total_unconfirmed);
+                if self.confirm_executor.is_some() {
+                    println!("      Replays run on a fresh deployment without the exploration's state, so a failure that needed it is not confirmed even if real");
+                }
+            }
+            if total_confirmation_errors > 0 {
+                println!("   ⚠️  {} candidate runs could not be replayed on the confirmation fork (RPC/infrastructure, neither confirmed nor refuted)", total_confirmation_errors);
+            }
+            if total_errors > 0 {
+                println!("   ⚠️  {} runs errored (RPC/infrastructure, not counted as failures)", total_errors);
+            }
+            println!("   📊 Total: {} runs across {} method(s)", total_passed + total_failed + total_skipped + total_errors + total_flaky + total_unconfirmed + total_confirmation_errors + total_checked_math_panics + total_expected_reverts + total_access_denied, method_count);
+            println!("   🔄 {} iterations per method", num_fuzz_runs);
+            println!("   🎲 Seed {} (pass --seed {} to reproduce)", self.master_seed, self.master_seed);
+            if let Some(map) = &coverage_map {
+                println!("   🧭 {} branch edges covered", map.len());
+            }
+            if self.config.dry_run {
+                println!("   🧪 SIMULATED: results come from the mock executor, nothing ran on a chain");
+            }
+            if self.config.gas_report {
+                let contract_stats: Vec<MethodStats> = method_stats.iter()
+                    .filter(|stats| stats.contract == contract.name)
+                    .cloned()
+                    .collect();
+                println!("⛽ Gas per call of {} (min / mean / max):", contract.name);
+                for row in gas_profile::table(&contract_stats) {
+                    println!("   {}", row);
+                }
+            }
+
+            if !interrupted {
+                let owner = ownership::read_owner(self.executor.as_mut(), &contract.name, &contract_abi).await;
+                let contract_stats: Vec<MethodStats> = method_stats.iter()
+                    .filter(|stats| stats.contract == contract.name)
+                    .cloned()
+                    .collect();
+                let campaign = recommendations::ContractCampaign {
+                    contract: &contract,
+                    abi: &contract_abi,
+                    constructor_args: constructor_args.as_deref(),
+                    method_stats: &contract_stats,
+                    findings: &findings[contract_findings_start..],
+                    owner: owner.as_ref(),
+                    owner_impersonated: self.owners.contains_key(&contract.name),
+                    accounts: self.executor.accounts(),
+                };
+                let suggestions = recommendations::for_contract(&campaign, &self.config);
+                if !suggestions.is_empty() {
+                    println!("💡 Suggestions for {}:", contract.name);
+                    for suggestion in &suggestions {
+                        println!("   - {}", suggestion);
+                    }
+                }
+                recommendations.extend(suggestions);
+            }
+
+            if interrupted {
+                let paused = PausedContract {
+                    target: DeployedTarget { bytecode: contract_bytecode, abi: contract_abi, compile_info: info, constructor_args, attached, libraries },
+                    baseline,
+                    properties,
+                };
+                if let Some(state) = self.slice.as_mut() {
+                    state.paused.insert(contract.name.clone(), paused);
+                }
+                break;
+            }
+            self.complete_contract(&contract.name);
+        }
+
+        if self.slice_interrupted() {
+            if let Some(state) = self.slice.as_mut() {
+                state.deployed = deployed;
+                state.cross_targets = cross_targets;
+            }
+        } else if cross_targets.len() > 1 {
+            if self.confirm_executor.is_some() {
+                println!("🔗 Cross-contract sequences skipped: dual-phase campaigns confirm each contract on its own fork deployment");
+            } else {
+                self.generator_context.dictionary = Dictionary::harvest(source, &[]);
+                let cross_totals = self.run_cross_contract_phase(&cross_targets, &mut findings).await;
+                total_passed += cross_totals.passed;
+                total_failed += cross_totals.failed;
+                total_skipped += cross_totals.skipped;
+                total_errors += cross_totals.errors;
+                total_flaky += cross_totals.flaky;
+                total_checked_math_panics += cross_totals.checked_math_panics;
+                total_expected_reverts += cross_totals.expected_reverts;
+                println!();
+            }
+        }
+
+        if let Some(tracer) = self.tracer.as_mut() {
+            if let Err(e) = tracer.flush() {
+                warn!("{:#}", e);
+            }
+            if tracer.dropped() > 0 {
+                println!("🧵 Traced {} transactions, {} more dropped by the trace rate limit", tracer.written(), tracer.dropped());
+            } else {
+                println!("🧵 Traced {} transactions", tracer.written());
+            }
+        }
+
+        // In time slices, the contracts of earlier slices are checked too, once the whole file is done
+        if let Some(state) = self.slice.as_mut() {
+            state.dispatch_tables.append(&mut dispatch_tables);
+            if !state.interrupted {
+                dispatch_tables = state.dispatch_tables.clone();
+            }
+        }
+        for (proxy, proxy_signatures, has_fallback) in &dispatch_tables {
+            if !selectors::looks_like_proxy(proxy_signatures, *has_fallback) {
+                continue;
+            }
+            for (implementation, implementation_signatures, _) in dispatch_tables.iter().filter(|(name, _, _)| name != proxy) {
+                for issue in selectors::check_proxy_shadowing(proxy, proxy_signatures, implementation, implementation_signatures) {
+                    println!("🧷 {}", issue);
+                    selector_issues.push(issue);
+                }
+            }
+        }
+
+        Ok(FuzzSummary {
+            total_passed,
+            total_failed,
+            total_skipped,
+            total_errors,
+            total_flaky,
+            total_unconfirmed,
+            total_confirmation_errors,
+            total_checked_math_panics,
+            total_expected_reverts,
+            total_block_context_dependent,
+            total_gas_dependent,
+            total_system_address_successes,
+            total_reentrancies,
+            total_wraparounds,
+            total_access_denied,
+            total_unauthorized_changes,
+            total_ether_drains,
+            findings,
+            skipped_contracts,
+            pragma: SolidityParser::extract_pragma(source),
+            compile_info,
+            master_seed: self.master_seed,
+            method_seeds,
+            selector_issues,
+            bricking_calls,
+            gas_alerts,
+            invariant_violations,
+            value_flows,
+            state_changes,
+            method_stats,
+            recommendations,
+            deployments,
+            proxies,
+            corpus: exported_corpus,
+            fork_block_number,
+            elapsed_secs: campaign_start.elapsed().as_secs_f64(),
+        })
+    }
+
+    /// The proxies among the deployed contracts, by name, with the implementations they forward to:
+    /// another contract of the campaign, or the contract at the implementation address, described
+    /// by its verified ABI on `--explorer`. Proxies deployed or attached by this call (`fresh`) are
+    /// announced and returned for the report; the rest were in earlier time slices.
+    async fn resolve_proxies(
+        &mut self,
+        contracts: &[ContractInfo],
+        deployed: &HashMap<String, DeployedTarget>,
+        fresh: &HashSet<String>,
+    ) -> (HashMap<String, ProxyImplementation>, Vec<ProxyInfo>) {
+        let mut implementations = HashMap::new();
+        let mut proxies = Vec::new();
+        for contract in contracts.iter().filter(|contract| contract.kind.is_deployable()) {
+            let Some(address) = self.executor.contract_address(&contract.name).map(str::to_string) else {
+                continue;
+            };
+            let Some(slots) = proxy::detect(self.executor.as_mut(), &contract.name).await else {
+                continue;
+            };
+            let announce = fresh.contains(&contract.name);
+            let campaign_contract = contracts.iter().find(|other| other.name != contract.name
+                && self.executor.contract_address(&other.name).is_some_and(|other_address| other_address.eq_ignore_ascii_case(&slots.implementation)));
+            let (implementation, abi_source) = match campaign_contract {
+                Some(other) => {
+                    let target = deployed.get(&other.name);
+                    if let Some(target) = target {
+                        self.register_abi(&contract.name, &target.abi);
+                    }
+                    let code = target.map(|target| target.bytecode.clone()).unwrap_or_default();
+                    (Some(ProxyImplementation { contract: other.clone(), code }), "source")
+                }
+                None => match self.attach_implementation(&contract.name, &slots.implementation, announce).await {
+                    Some(implementation) if !implementation.contract.methods.is_empty() => (Some(implementation), "explorer"),
+                    Some(implementation) => (Some(implementation), "abi"),
+                    None => (None, "abi"),
+                },
+            };
+
+            let methods: Vec<&ContractMethod> = implementation.iter()
+                .flat_map(|implementation| proxy::forwarded_methods(&implementation.contract))
+                .collect();
+            let uups = methods.iter().any(|method| abi_encoding::method_signature(method) == "proxiableUUID()")
+                || implementation.as_ref().is_some_and(|implementation| proxy::dispatches(&implementation.code, "proxiableUUID()"));
+            let info = ProxyInfo {
+                contract: contract.name.clone(),
+                address,
+                kind: if slots.kind == ProxyKind::Eip1967 && uups { ProxyKind::Uups } else { slots.kind },
+                implementation: slots.implementation.clone(),
+                implementation_contract: campaign_contract.map(|other| other.name.clone()),
+                beacon: slots.beacon,
+                admin: slots.admin,
+                abi_source: abi_source.to_string(),
+                methods: methods.len(),
+            };
+            if announce {
+                let named = info.implementation_contract.as_ref().map(|name| format!(" ({})", name)).unwrap_or_default();
+                println!("🪞 {} is a proxy ({}) forwarding to {}{}", info.contract, info.kind, info.implementation, named);
+                match abi_source {
+                    "abi" if proxy::forwarded_methods(contract).all(|method| selectors::PROXY_ADMIN_SIGNATURES.contains(&abi_encoding::method_signature(method).as_str())) =>
+                        println!("   only the proxy's own functions are known: pass the implementation's ABI with --abi or set --explorer to fuzz its methods"),
+                    "abi" => println!("   fuzzing through the proxy with the methods of its own ABI"),
+                    _ => println!("   fuzzing {} method(s) of the implementation ({} ABI) through the proxy", info.methods, abi_source),
+                }
+                proxies.push(info);
+            }
+            if let Some(implementation) = implementation.filter(|implementation| !implementation.contract.methods.is_empty()) {
+                implementations.insert(contract.name.clone(), implementation);
+            }
+        }
+        (implementations, proxies)
+    }
+
+    /// Attach the implementation of the proxy `proxy_name` as `<proxy>Implementation`, described by
+    /// its verified ABI on `--explorer` (fetched once per address) or, without one, by no methods
+    async fn attach_implementation(&mut self, proxy_name: &str, address: &str, announce: bool) -> Option<ProxyImplementation> {
+        let name = format!("{}Implementation", proxy_name);
+        let code = match self.executor.attach_contract(&name, address).await {
+            Ok(code) => code,
+            Err(e) => {
+                warn!("Could not read the implementation of proxy {} at {}: {:#}", proxy_name, address, e);
+                return None;
+            }
+        };
+        let abi = match (&self.config.explorer, self.implementation_abis.get(address)) {
+            (_, Some(abi)) => abi.clone(),
+            // Nothing leaves the machine in a dry run
+            (Some(_), None) if self.config.dry_run => None,
+            (Some(explorer), None) => {
+                let fetched = match build_http_client(Duration::from_secs(self.config.rpc_timeout_secs)) {
+                    Ok(client) => explorer.fetch_abi(&client, address).await,
+                    Err(e) => Err(e),
+                };
+                let abi = match fetched {
+                    Ok(abi) => Some(abi),
+                    Err(e) => {
+                        if announce {
+                            println!("⚠️  No verified ABI for the implementation {} on {}: {:#}", address, explorer.base_url, e);
+                        }
+                        None
+                    }
+                };
+                self.implementation_abis.insert(address.to_string(), abi.clone());
+                abi
+            }
+            (None, None) => None,
+        };
+        let contract = match abi {
+            Some(abi) => {
+                self.register_abi(&name, &abi);
+                self.register_abi(proxy_name, &abi);
+                LiveTarget { name: name.clone(), address: address.to_string(), abi }.contract_info()
+            }
+            None => ContractInfo {
+                name,
+                kind: crate::ast_parser::ContractKind::Contract,
+                bases: Vec::new(),
+                methods: Vec::new(),
+                events: Vec::new(),
+                fallback: None,
+                receive: None,
+                constructor: None,
+            },
+        };
+        Some(ProxyImplementation { contract, code })
+    }
+
+    /// Put the cheatcode stand-in in place and run `setUp()`, as `forge test` does before a harness's fuzz tests
+    async fn prepare_harness(executor: &mut dyn Executor, contract: &ContractInfo) {
+        if let Err(e) = executor.set_code(harness::CHEATCODE_ADDRESS, &harness::cheatcode_stub()).await {
+            warn!("Could not place the cheatcode stand-in, every vm.* call of the harness will revert: {:#}", e);
+        }
+        if !contract.methods.iter().any(|method| method.name == "setUp" && method.parameters.is_empty()) {
+            return;
+        }
+        match executor.call_method(&contract.name, harness::SET_UP_SIGNATURE, &[]).await {
+            Ok(result) if result.success => println!("- {}.setUp() called", contract.name),
+            Ok(result) => warn!("{}.setUp() reverted, fuzz tests run without it: {}", contract.name, result.error.unwrap_or_default()),
+            Err(e) => warn!("Could not call {}.setUp(): {:#}", contract.name, e),
+        }
+    }
+
+    /// Deploy the reentrancy attacker and let the generator pass it as an address argument
+    async fn deploy_attacker(&mut self) {
+        match self.executor.deploy_contract(reentrancy::ATTACKER_NAME, &reentrancy::attacker_init_code(), None).await {
+            Ok(address) => {
+                println!("- Reentrancy attacker deployed at {}", address);
+                self.generator_context.attacker_address = Some(address);
+            }
+            Err(e) => {
+                warn!("Could not deploy the reentrancy attacker, reentrancy detection disabled: {:#}", e);
+                self.generator_context.attacker_address = None;
+            }
+        }
+    }
+
+    /// Tell the attacker to re-enter the target with this very call when the arguments pass it,
+    /// returning whether it was armed. The attacker is configured without value; `value` is restored
+    /// for the call that follows.
+    async fn arm_attacker(&mut self, contract: &ContractInfo, method: &ContractMethod, args: &[SolidityValue], value: U256) -> bool {
+        let Some(attacker) = &self.generator_context.attacker_address else {
+            return false;
+        };
+        let Some(target) = self.executor.contract_address(&contract.name).map(str::to_string) else {
+            return false;
+        };
+        if !reentrancy::contains_address(args, attacker) {
+            return false;
+        }
+        let Ok((signature, encoded_args)) = self.encode_call(method, args) else {
+            return false;
+        };
+        let mut calldata = calculate_selector(&signature).to_vec();
+        calldata.extend(encoded_args);
+        self.executor.set_value(U256::zero());
+        let configured = match reentrancy::configure_args(&target, &calldata) {
+            Ok(configure_args) => self.executor.call_method(reentrancy::ATTACKER_NAME, reentrancy::CONFIGURE_SIGNATURE, &configure_args).await,
+            Err(e) => Err(e),
+        };
+        self.executor.set_value(value);
+        match configured {
+            Ok(result) if result.success => true,
+            Ok(result) => {
+                warn!("Could not arm the reentrancy attacker: {}", result.error.unwrap_or_default());
+                false
+            }
+            Err(e) => {
+                warn!("Could not arm the reentrancy attacker: {:#}", e);
+                false
+            }
+        }
+    }
+
+    /// Whether the last call ran the target again inside itself through the attacker, described as
+    /// the chain of calls. Detection is turned off when the executor cannot trace calls.
+    async fn detect_reentry(&mut self, contract: &ContractInfo) -> Option<String> {
+        let attacker = self.generator_context.attacker_address.clone()?;
+        let target = self.executor.contract_address(&contract.name)?.to_string();
+        let frames = match self.executor.last_call_frames().await {
+            Ok(frames) => frames,
+            Err(e) => {
+                warn!("Call traces unavailable, reentrancy detection disabled: {:#}", e);
+                self.generator_context.attacker_address = None;
+                return None;
+            }
+        };
+        let reentry = reentrancy::find_reentry(&frames, &target, &attacker)?;
+        let describe = |selector: Option<[u8; 4]>| {
+            let method = selector.and_then(|selector| contract.methods.iter()
+                .map(abi_encoding::method_signature)
+                .find(|signature| calculate_selector(signature) == selector));
+            match (method, selector) {
+                (Some(method), _) => method,
+                (None, Some(selector)) => format!("0x{}", hex::encode(selector)),
+                (None, None) => "fallback".to_string(),
+            }
+        };
+        Some(format!("{} → attacker {} → {}", describe(reentry.outer), attacker, describe(reentry.inner)))
+    }
+
+    /// Impersonate the owner of a freshly deployed contract when its owner getter returns an account
+    /// the fuzzer does not control, so a share of the runs also explores its admin paths
+    async fn bootstrap_owner(&mut self, contract_name: &str, contract_abi: &ethers::abi::Abi) {
+        if self.config.owner_percent == 0 {
+            return;
+        }
+        let Some(owner) = ownership::read_owner(self.executor.as_mut(), contract_name, contract_abi).await else {
+            return;
+        };
+        if !ownership::is_external(&owner, self.executor.accounts()) {
+            return;
+        }
+        if let Err(e) = self.executor.impersonate(&owner.address).await {
+            warn!("Could not impersonate the owner {} of {}, its admin paths stay unexplored: {:#}", owner.address, contract_name, e);
+            return;
+        }
+        if let Some(index) = self.executor.accounts().iter().position(|account| account.eq_ignore_ascii_case(&owner.address)) {
+            println!("- 👑 {}() is {}, an account the fuzzer does not control: impersonating it for {}% of runs",
+                owner.getter, owner.address, self.config.owner_percent);
+            self.owners.insert(contract_name.to_string(), index);
+        }
+    }
+
+    /// Add the contracts a deployed contract points to through its address getters to the address pool
+    async fn discover_contracts(&mut self, contract: &ContractInfo) {
+        let context = &self.generator_context;
+        let known: Vec<String> = context.contract_addresses.iter()
+            .chain(&context.discovered_addresses)
+            .chain(context.attacker_address.iter())
+            .chain(&context.known_addresses)
+            .cloned()
+            .collect();
+        for (getter, address) in address_pool::discover(self.executor.as_mut(), contract, &known).await {
+            println!("🔎 {}.{} is a contract at {}, added to the address pool", contract.name, getter, address);
+            self.generator_context.discovered_addresses.push(address);
+        }
+    }
+
+    /// Impersonate the accounts a freshly deployed contract keeps in its owner and admin variables
+    /// (a governance multisig on a fork, an admin passed to the constructor) and add them to the
+    /// senders every method is fuzzed from
+    async fn discover_senders(&mut self, contract_name: &str, storage_layout: &[StorageVariable]) {
+        let role_accounts = access_control::read_role_accounts(self.executor.as_mut(), contract_name, storage_layout).await;
+        let mut discovered = 0;
+        for (label, address) in role_accounts {
+            let campaign_contract = self.generator_context.contract_addresses.iter()
+                .chain(self.generator_context.attacker_address.iter())
+                .any(|known| known.eq_ignore_ascii_case(&address));
+            if campaign_contract || self.executor.accounts().iter().any(|account| account.eq_ignore_ascii_case(&address)) {
+                continue;
+            }
+            match self.executor.impersonate(&address).await {
+                Ok(()) => {
+                    println!("- 🎭 {} is {}: impersonating it as a sender", label, address);
+                    discovered += 1;
+                }
+                Err(e) => warn!("Could not impersonate {} ({} of {}): {:#}", address, label, contract_name, e),
+            }
+        }
+        if discovered > 0 {
+            // Sender indexes are positions in the executor's account list
+            self.generator_context.known_addresses = self.executor.accounts().to_vec();
+        }
+    }
+
+    /// Protected state of a contract before a call, when the current sender is neither its deployer
+    /// nor its owner; `None` for those two, or when the state cannot be read
+    async fn protected_state_if_unauthorized(&mut self, contract_name: &str, abi: &ethers::abi::Abi, variables: &[StorageVariable]) -> Option<access_control::ProtectedState> {
+        let state = match access_control::read(self.executor.as_mut(), contract_name, abi, variables).await {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Could not read protected state of {}, call not checked for access control: {:#}", contract_name, e);
+                return None;
+            }
+        };
+        let sender = self.executor.current_sender();
+        let deployer = self.deployers.get(contract_name).map(String::as_str);
+        (!access_control::is_authorized(sender, deployer, &state)).then_some(state)
+    }
+
+    /// Send from `address`, impersonating it when the executor does not control it (the owner of a
+    /// contract the campaign impersonated, for example). Returns false if neither works.
+    async fn switch_sender(executor: &mut dyn Executor, address: &str) -> bool {
+        if executor.set_sender_address(address) {
+            return true;
+        }
+        match executor.impersonate(address).await {
+            Ok(()) => executor.set_sender_address(address),
+            Err(e) => {
+                warn!("Could not impersonate {}: {:#}", address, e);
+                false
+            }
+        }
+    }
+
+    /// Whether the current sender is the impersonated owner of the contract
+    fn sending_as_owner(&self, contract_name: &str) -> bool {
+        self.owners.get(contract_name)
+            .and_then(|index| self.executor.accounts().get(*index))
+            .is_some_and(|owner| owner == self.executor.current_sender())
+    }
+
+    /// A finding for a call just sent to `contract_name`, from the current sender to where the
+    /// contract is deployed; the caller sets the fields that describe how the call was made
+    fn finding(&self, contract_name: &str, signature: &str, args: Vec<SolidityValue>, error: String) -> Finding {
+        Finding {
+            contract_address: self.executor.contract_address(contract_name).map(str::to_string),
+            sender: self.executor.current_sender().to_string(),
+            impersonated_owner: self.sending_as_owner(contract_name),
+            ..Finding::new(contract_name, signature, args, error)
+        }
+    }
+
+    /// The contract with the methods to fuzz taken from where `method_source` says. With the source,
+    /// parsed functions missing from the compiled ABI are reported (`announce`), since their selector
+    /// matches nothing and every call would end up in the fallback.
+    fn discover_methods(&self, mut contract: ContractInfo, abi: &ethers::abi::Abi, announce: bool) -> ContractInfo {
+        let compiled = abi_encoding::methods_from_abi(abi);
+        if compiled.is_empty() {
+            return contract;
+        }
+        let compiled_signatures: HashSet<String> = compiled.iter().map(abi_encoding::method_signature).collect();
+        if self.config.method_source == MethodSource::Source {
+            if announce {
+                for method in &contract.methods {
+                    let external = method.visibility == MethodVisibility::Public || method.visibility == MethodVisibility::External;
+                    let signature = abi_encoding::method_signature(method);
+                    if external && !method.is_constructor && !method.is_fallback && !method.is_receive && !compiled_signatures.contains(&signature) {
+                        warn!("{}.{} is not in the compiled ABI, its calls would hit the fallback (try --methods-from abi)", contract.name, signature);
+                    }
+                }
+            }
+            return contract;
+        }
+
+        // Modifiers and data locations only exist in the source
+        let parsed: HashMap<String, &ContractMethod> = contract.methods.iter()
+            .map(|method| (abi_encoding::method_signature(method), method))
+            .collect();
+        let methods: Vec<ContractMethod> = compiled.into_iter()
+            .map(|mut method| {
+                if let Some(source) = parsed.get(&abi_encoding::method_signature(&method)) {
+                    method.modifiers = source.modifiers.clone();
+                    method.visibility = source.visibility.clone();
+                    for (param, source_param) in method.parameters.iter_mut().zip(&source.parameters) {
+                        param.location = source_param.location;
+                    }
+                }
+                method
+            })
+            .collect();
+        if announce {
+            println!("- {} method(s) from the compiled ABI", methods.len());
+        }
+        contract.methods = methods.into_iter().chain(contract.constructor.clone()).collect();
+        contract.fallback = contract.methods.iter().find(|method| method.is_fallback).cloned();
+        contract.receive = contract.methods.iter().find(|method| method.is_receive).cloned();
+        contract
+    }
+
+    /// Invariant properties of a freshly deployed contract, checked once up front, and its value
+    /// ledger when value accounting is on
+    async fn setup_properties(
+        &mut self,
+        contract: &ContractInfo,
+        contract_abi: &ethers::abi::Abi,
+        storage_layout: &[StorageVariable],
+        invariant_violations: &mut Vec<InvariantViolation>,
+    ) -> PropertyChecks {
+        // Properties checked after fuzzed calls; inherited ones only show up in the ABI
+        let mut invariant_names: BTreeSet<String> = contract.methods.iter()
+            .filter(|method| method.parameters.is_empty() && invariants::is_invariant(&method.name))
+            .map(|method| method.name.clone())
+            .collect();
+        invariant_names.extend(contract_abi.functions()
+            .filter(|function| function.inputs.is_empty() && invariants::is_invariant(&function.name))
+            .map(|function| function.name.clone()));
+        let mut properties = PropertyChecks {
+            invariants: invariant_names.into_iter().collect(),
+            ledger: None,
+            standards: None,
+            drain_privileged: Vec::new(),
+        };
+        if !properties.invariants.is_empty() {
+            println!("- Checking {} invariant(s): {}", properties.invariants.len(), properties.invariants.join(", "));
+        }
+        let mut supported = standards::detect(self.executor.as_mut(), &contract.name).await;
+        if standards::is_erc20(contract_abi) {
+            supported.insert(0, Standard::Erc20);
+        }
+        if !supported.is_empty() {
+            let checks = StandardChecks::new(self.executor.as_mut(), &contract.name, supported).await;
+            println!("- Standards: supports {}, checking {}",
+                checks.standards.iter().map(|standard| standard.to_string()).collect::<Vec<_>>().join(", "),
+                checks.active().join(", "));
+            properties.standards = Some(checks);
+        }
+        if !properties.is_empty() {
+            self.check_properties(contract, &mut properties, 0, true, None, invariant_violations).await;
+        }
+        if self.config.value_accounting {
+            let variables = accounting::accounting_variables(storage_layout);
+            if variables.is_empty() {
+                println!("- Value accounting: no accounting variables in the storage layout, tracking ETH flows only");
+            } else {
+                let labels = variables.iter().map(|variable| variable.label.as_str()).collect::<Vec<_>>().join(", ");
+                println!("- Value accounting: comparing the balance with {}", labels);
+            }
+            let slots: Vec<U256> = variables.iter().map(|variable| variable.slot).collect();
+            if let Some((balance, words)) = self.read_ledger_state(&contract.name, &slots).await {
+                properties.ledger = Some(ValueLedger::new(variables, balance, &words));
+            }
+        }
+        properties
+    }
+
+    /// Replay a recorded attack scenario against the current version of its target: deploy the
+    /// contract from `source` and send every step with its recorded sender and calldata.
+    pub async fn replay_attack(&mut self, source: &str, filename: &str, recipe: &AttackRecipe) -> Result<ReplayOutcome, anyhow::Error> {
+        let outcome = self.replay_attack_steps(source, filename, recipe).await;
+        if recipe.steps.iter().any(|step| step.block_context.is_some()) {
+            if let Err(e) = self.executor.reset_block_context().await {
+                warn!("Could not restore block context after replaying {}: {:#}", recipe.contract, e);
+            }
+        }
+        outcome
+    }
+
+    async fn replay_attack_steps(&mut self, source: &str, filename: &str, recipe: &AttackRecipe) -> Result<ReplayOutcome, anyhow::Error> {
+        let contracts = self.parser.parse_contract(source, filename)?;
+        let Some(target) = contracts.iter().find(|c| c.name == recipe.contract) else {
+            return Ok(ReplayOutcome::Obsolete(format!("contract {} no longer exists in {}", recipe.contract, filename)));
+        };
+        self.compile_and_deploy(Path::new(filename), target).await?;
+        let mut deployed = HashSet::from([target.name.clone()]);
+
+        let step_count = recipe.steps.len();
+        for (index, step) in recipe.steps.iter().enumerate() {
+            // Steps of cross-contract scenarios call other contracts of the file, deployed when first needed
+            let contract = match &step.contract {
+                Some(name) => {
+                    let Some(other) = contracts.iter().find(|c| c.name == *name) else {
+                        return Ok(ReplayOutcome::Obsolete(format!("contract {} no longer exists in {}", name, filename)));
+                    };
+                    if deployed.insert(name.clone()) {
+                        self.compile_and_deploy(Path::new(filename), other).await?;
+                    }
+                    other
+                }
+                None => target,
+            };
+            if !contract.methods.iter().any(|m| abi_encoding::method_signature(m) == step.method) {
+                return Ok(ReplayOutcome::Obsolete(format!("{}.{} no longer exists", contract.name, step.method)));
+            }
+            if !Self::switch_sender(self.executor.as_mut(), &step.sender).await {
+                warn!("Sender {} is not available on this fork, replaying from {}", step.sender, self.executor.current_sender());
+            }
+            if let Some(block_context) = &step.block_context {
+                if let Err(e) = self.executor.set_block_context(block_context).await {
+                    return Ok(ReplayOutcome::Error(format!("could not apply block context [{}]: {:#}", block_context, e)));
+                }
+            }
+            for cheat in &step.cheats {
+                if let Err(e) = self.executor.apply_cheat(cheat).await {
+                    return Ok(ReplayOutcome::Error(format!("could not apply cheat [{}]: {:#}", cheat, e)));
+                }
+            }
+
+            let call = (step.method.clone(), step.decoded_calldata()?);
+            self.executor.set_value(step.value()?);
+            let is_final = index + 1 == step_count;
+            let (result, gas_used) = Self::execute_call_metered(self.executor.as_mut(), &contract.name, &call).await;
+            if let Some(method) = contract.methods.iter().find(|m| abi_encoding::method_signature(m) == step.method) {
+                let sender = self.executor.current_sender().to_string();
+                self.trace(TraceRecord::new(TracePhase::Replay, &contract.name, &sender, method, &call, &result, gas_used));
+            }
+            match (result, is_final) {
+                (TestResult::Error(error), _) => return Ok(ReplayOutcome::Error(error)),
+                (TestResult::Passed, false) => {}
+                (TestResult::Failed(error), false) => {
+                    return Ok(ReplayOutcome::Obsolete(format!("step {} ({}) now reverts: {}", index + 1, step.method, error)));
+                }
+                (TestResult::Failed(error), true) => {
+                    return Ok(match &recipe.expected_error {
+                        Some(_) => ReplayOutcome::Reproduced(format!("{} still reverts: {}", step.method, error)),
+                        None => ReplayOutcome::Fixed(format!("{} now reverts: {}", step.method, error)),
+                    });
+                }
+                (TestResult::Passed, true) => {
+                    return Ok(match &recipe.expected_error {
+                        Some(expected) => ReplayOutcome::Fixed(format!("{} no longer reverts (was: {})", step.method, expected)),
+                        None => ReplayOutcome::Reproduced("every step of the scenario still succeeds".to_string()),
+                    });
+                }
+            }
+        }
+
+        Ok(ReplayOutcome::Obsolete("recipe has no steps".to_string()))
+    }
+
+    /// Re-execute a single saved case on a fresh deployment of its target, for debugging.
+    /// Arguments and sender missing from the case are regenerated from its seed.
+    pub async fn replay_case(&mut self, source: &str, filename: &str, case: &FailingCase) -> Result<TestResult, anyhow::Error> {
+        // Property checks are part of what the case failed on
+        let instrumented = Self::instrument_scribble(source, filename)?;
+        let source = instrumented.as_ref().map_or(source, |(instrumentation, _)| instrumentation.source.as_str());
+        let contracts = self.parser.parse_contract(source, filename)?;
+        let contract = contracts.into_iter().find(|c| c.name == case.contract)
+            .ok_or_else(|| anyhow::anyhow!("contract {} not found in {}", case.contract, filename))?;
+        let method = contract.methods.iter().find(|m| abi_encoding::method_signature(m) == case.signature)
+            .ok_or_else(|| anyhow::anyhow!("{}.{} not found in {}", case.contract, case.signature, filename))?
+            .clone();
+        let source_path = instrumented.as_ref().map_or(Path::new(filename), |(_, file)| file.path());
+        let target = self.compile_and_deploy(source_path, &contract).await?;
+        // Seeds regenerate the same arguments only from the same dictionary
+        self.generator_context.dictionary = Dictionary::harvest(source, &target.bytecode);
+
+        let plan = case.seed.map(|seed| self.plan_iteration(&contract.name, &method, seed, &PlanOptions::default(), None));
+        let args = match (&case.args, &plan) {
+            (Some(args), _) => {
+                if args.len() != method.parameters.len() {
+                    anyhow::bail!("{} takes {} argument(s), the case has {}", case.signature, method.parameters.len(), args.len());
+                }
+                method.parameters.iter().zip(args)
+                    .map(|(param, value)| case::value_from_json(&param.param_type, value)
+                        .map_err(|e| anyhow::anyhow!("argument {}: {:#}", param.name, e)))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            (None, Some(plan)) => plan.args.clone(),
+            (None, None) => anyhow::bail!("the case has neither arguments nor a seed"),
+        };
+        match (&case.sender, &plan) {
+            (Some(sender), _) => {
+                if !Self::switch_sender(self.executor.as_mut(), sender).await {
+                    warn!("Sender {} is not available on this fork, replaying from {}", sender, self.executor.current_sender());
+                }
+            }
+            (None, Some(plan)) => self.executor.set_sender(plan.sender_index),
+            (None, None) => {}
+        }
+        let value = match (&case.value, &plan) {
+            (Some(value), _) => U256::from_dec_str(value).map_err(|e| anyhow::anyhow!("invalid value {}: {}", value, e))?,
+            (None, Some(plan)) => self.resolve_call_value(plan.value, None).await,
+            (None, None) => U256::zero(),
+        };
+        self.executor.set_value(value);
+
+        let call = self.encode_call(&method, &args)?;
+        let value_display = if value.is_zero() { String::new() } else { format!(" with {} wei", value) };
+        println!("🔁 Replaying {}.{}({}) from {}{}", contract.name, method.name, self.format_args_for_display(&args), self.executor.current_sender(), value_display);
+        println!("     ↳ calldata 0x{}{}", hex::encode(calculate_selector(&call.0)), hex::encode(&call.1));
+        let (result, gas_used) = Self::execute_call_metered(self.executor.as_mut(), &contract.name, &call).await;
+        let sender = self.executor.current_sender().to_string();
+        self.trace(TraceRecord::new(TracePhase::Replay, &contract.name, &sender, &method, &call, &result, gas_used));
+        Ok(result)
+    }
+
+    /// Compile a contract, collect its constructor arguments and deploy it on the exploration executor
+    /// Compile the Scribble annotations of a source into checks and write the instrumented copy that
+    /// gets built in place of the original; `None` when the source has no annotations
+    fn instrument_scribble(source: &str, filename: &str) -> Result<Option<(scribble::Instrumentation, scribble::InstrumentedFile)>, anyhow::Error> {
+        let Some(instrumentation) = scribble::instrument(source) else {
+            return Ok(None);
+        };
+        for skipped in &instrumentation.skipped {
+            warn!("Scribble annotation skipped: {}", skipped);
+        }
+        let file = scribble::InstrumentedFile::write(Path::new(filename), &instrumentation.source)?;
+        Ok(Some((instrumentation, file)))
+    }
+
+    /// Point the compiler at a managed solc when the one in PATH does not satisfy the file's
+    /// pragma, or at the release given with `--solc-version`. A dry run only uses installed
+    /// releases; when none fits, the solc in PATH is tried anyway.
+    async fn select_solc(&mut self, source_path: &Path) {
+        if !self.compiler.runs_solc() {
+            return;
+        }
+        let (requirement, reason) = match &self.config.compiler.solc {
+            SolcSelection::Path => return,
+            SolcSelection::Version(version) => (format!("={}", version), "--solc-version".to_string()),
+            SolcSelection::Auto => {
+                let Some(pragma) = std::fs::read_to_string(source_path).ok().and_then(|source| SolidityParser::extract_pragma(&source)) else {
+                    self.compiler.use_managed_solc(None);
+                    return;
+                };
+                if self.compiler.path_solc_version().is_some_and(|version| version.satisfies(&pragma) == Some(true)) {
+                    self.compiler.use_managed_solc(None);
+                    return;
+                }
+                let reason = format!("pragma {}", pragma);
+                (pragma, reason)
+            }
+        };
+        match solc_manager::ensure(&requirement, self.config.dry_run).await {
+            Ok((version, path)) => {
+                if self.compiler.use_managed_solc(Some(path.to_string_lossy().to_string())) {
+                    println!("- Compiling with solc {} from {} ({})", version, path.display(), reason);
+                }
+            }
+            Err(e) => {
+                warn!("No managed solc for {}: {:#}; compiling with the solc in PATH", requirement, e);
+                self.compiler.use_managed_solc(None);
+            }
+        }
+    }
+
+    async fn compile_and_deploy(&mut self, source_path: &Path, contract: &ContractInfo) -> Result<DeployedTarget, anyhow::Error> {
+        self.select_solc(source_path).await;
+        let (mut contract_bytecode, contract_abi, compile_info) = match self.compiler.compile_contract_with_info(source_path, &contract.name) {
+            Ok((bytecode, abi, info)) => {
+                println!("- Contract compiled successfully ({} bytes)", bytecode.len());
+                (bytecode, abi, Some(info))
+            }
+            Err(e) if self.config.dry_run => {
+                // A dry run only needs the parsed methods; the mock executor accepts any bytecode
+                println!("- Compilation unavailable in dry run, continuing without bytecode: {}", e);
+                (Vec::new(), ethers::abi::Abi::default(), None)
+            }
+            Err(e) => {
+                eprintln!("❌ Compilation failed for contract {}: {}", contract.name, e);
+                eprintln!("   Cannot proceed without compiled bytecode. Please fix compilation errors.");
+                return Err(anyhow::anyhow!("Contract compilation failed: {}", e));
+            }
+        };
+
+        // Bytecode with library placeholders has to be linked before it can be deployed
+        let links = compile_info.as_ref().map(|info| info.libraries.clone()).unwrap_or_default();
+        let libraries = if links.is_empty() {
+            Vec::new()
+        } else {
+            println!("- Links against {} librar{}: {}", links.len(), if links.len() == 1 { "y" } else { "ies" },
+                links.iter().map(|link| link.name.as_str()).collect::<Vec<_>>().join(", "));
+            match self.deploy_libraries(&links, &mut contract_bytecode).await {
+                Ok(libraries) => libraries,
+                Err(e) => {
+                    eprintln!("❌ Library linking failed for contract {}: {:#}", contract.name, e);
+                    return Err(anyhow::anyhow!("Library linking failed: {:#}", e));
+                }
+            }
+        };
+
+        // Check if contract has constructor parameters
+        let constructor_args = if contract_abi.constructor().is_some() && !contract_abi.constructor().unwrap().inputs.is_empty() {
+            println!("- Constructor requires {} parameter(s)", contract_abi.constructor().unwrap().inputs.len());
+
+            // Arguments from --constructor-args or fuzzhead.toml, otherwise prompt for them
+            let tokens = match self.config.constructor_args_for(&contract.name) {
+                Some(values) => {
+                    println!("- Constructor arguments given: {}", values.join(", "));
+                    crate::constructor::parse_constructor_args(&contract_abi, &contract.name, values)
+                }
+                None => crate::constructor::prompt_for_constructor_args(&contract_abi, &contract.name),
+            };
+            match tokens {
+                Ok(tokens) => {
+                    match contract_abi.constructor().unwrap().encode_input(contract_bytecode.clone(), &tokens) {
+                        Ok(encoded_deployment) => {
+                            let constructor_args_bytes = &encoded_deployment[contract_bytecode.len()..];
+                            println!("- Constructor arguments encoded ({} bytes)", constructor_args_bytes.len());
+                            Some(constructor_args_bytes.to_vec())
+                        }
+                        Err(e) => {
+                            eprintln!("❌ Failed to encode constructor arguments: {}", e);
+                            return Err(anyhow::anyhow!("Constructor argument encoding failed: {}", e));
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to get constructor arguments: {}", e);
+                    return Err(anyhow::anyhow!("Constructor argument input failed: {}", e));
+                }
+            }
+        } else {
+            None
+        };
+
+        // A deployment the chain is bound to refuse fails here, with the reason and what to change
+        if let Err(e) = self.check_deployment(contract, &contract_bytecode, constructor_args.as_deref(), compile_info.as_ref()).await {
+            eprintln!("❌ {}", e);
+            return Err(anyhow::anyhow!("Deployment check failed: {}", e));
+        }
+
+        // Deploy contract to Anvil fork
+        let deployer = self.executor.deployer().to_string();
+        match self.executor.deploy_contract(&contract.name, &contract_bytecode, constructor_args.as_deref()).await {
+            Ok(addr) => {
+                println!("- Contract deployed at: {}", addr);
+                self.deployers.insert(contract.name.clone(), deployer);
+                self.register_abi(&contract.name, &contract_abi);
+            }
+            Err(e) => {
+                eprintln!("❌ Deployment failed: {}", e);
+                return Err(anyhow::anyhow!("Contract deployment failed: {}", e));
+            }
+        }
+
+        Ok(DeployedTarget {
+            bytecode: contract_bytecode,
+            abi: contract_abi,
+            compile_info,
+            constructor_args,
+            attached: false,
+            libraries,
+        })
+    }
+
+    /// Check the EIP-170 and EIP-3860 size limits and estimate the deployment's gas before it is
+    /// sent. A dry run only warns about the size, since the mock executor deploys anything.
+    async fn check_deployment(
+        &mut self,
+        contract: &ContractInfo,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+        compile_info: Option<&CompileInfo>,
+    ) -> Result<(), String> {
+        let backend = compile_info.map_or("", |info| info.backend.as_str());
+        let runtime_size = compile_info.and_then(|info| info.runtime_size);
+        let init_code_size = bytecode.len() + constructor_args.map_or(0, <[u8]>::len);
+        match deployment::check_size(&contract.name, init_code_size, runtime_size, &self.config.compiler, backend) {
+            Err(e) if self.config.dry_run => warn!("{}", e),
+            Err(e) => return Err(e),
+            Ok(()) => {}
+        }
+        if bytecode.is_empty() {
+            return Ok(());
+        }
+
+        match self.executor.estimate_deployment_gas(bytecode, constructor_args).await {
+            Ok(Some(gas)) if gas > DEFAULT_GAS_LIMIT => Err(format!(
+                "The deployment of {} needs ~{} gas, more than the {} a deployment transaction is sent with: its constructor does too much work",
+                contract.name, gas, DEFAULT_GAS_LIMIT
+            )),
+            Ok(Some(gas)) => {
+                let size = runtime_size
+                    .map(|size| format!("{} of {} bytes of runtime code, ", size, deployment::MAX_CODE_SIZE))
+                    .unwrap_or_default();
+                println!("- Deployment check: {}~{} gas", size, gas);
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(e) => {
+                let error = format!("{:#}", e);
+                match deployment::explain_estimate_failure(&contract.name, &error, constructor_args.is_some(), &self.config.compiler, backend) {
+                    Some(explained) => Err(explained),
+                    None => {
+                        warn!("Could not estimate the deployment gas of {}, deploying anyway: {}", contract.name, error);
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Deploy the libraries of `links`, after the libraries they link against in turn, and write
+    /// their addresses into `bytecode`
+    async fn deploy_libraries(&mut self, links: &[LibraryLink], bytecode: &mut [u8]) -> Result<Vec<DeployedLibrary>, anyhow::Error> {
+        let mut compiled = Vec::new();
+        self.compile_libraries(links, &mut Vec::new(), &mut compiled)?;
+
+        let mut deployed: Vec<DeployedLibrary> = Vec::new();
+        for (link, mut code, abi, dependencies) in compiled {
+            for dependency in &dependencies {
+                Self::link_deployed(&mut code, dependency, &deployed)?;
+            }
+            let address = self.executor.deploy_contract(&link.name, &code, None).await
+                .with_context(|| format!("Deployment of library {} failed", link.name))?;
+            println!("- Library {} deployed at: {}", link.name, address);
+            // The runtime code, to place the library at the same address on the confirmation fork
+            let code = self.executor.attach_contract(&link.name, &address).await?;
+            self.register_abi(&link.name, &abi);
+            deployed.push(DeployedLibrary { name: link.name, source_path: link.source_path, address, code });
+        }
+        for link in links {
+            Self::link_deployed(bytecode, link, &deployed)?;
+        }
+        Ok(deployed)
+    }
+
+    /// Compile the libraries of `links` and those they link against, dependencies first, each once
+    fn compile_libraries(
+        &self,
+        links: &[LibraryLink],
+        linking: &mut Vec<LibraryLink>,
+        compiled: &mut Vec<(LibraryLink, Vec<u8>, ethers::abi::Abi, Vec<LibraryLink>)>,
+    ) -> Result<(), anyhow::Error> {
+        let same = |a: &LibraryLink, b: &LibraryLink| a.name == b.name && a.source_path == b.source_path;
+        for link in links {
+            if compiled.iter().any(|(known, ..)| same(known, link)) {
+                continue;
+            }
+            if linking.iter().any(|known| same(known, link)) {
+                return Err(anyhow::anyhow!("Libraries link against each other in a cycle through {}", link.name));
+            }
+            let (code, abi, info) = self.compiler.compile_contract_with_info(&link.source_path, &link.name)
+                .with_context(|| format!("Failed to compile library {} from {}", link.name, link.source_path.display()))?;
+            linking.push(link.clone());
+            self.compile_libraries(&info.libraries, linking, compiled)?;
+            linking.pop();
+            compiled.push((link.clone(), code, abi, info.libraries));
+        }
+        Ok(())
+    }
+
+    fn link_deployed(bytecode: &mut [u8], link: &LibraryLink, deployed: &[DeployedLibrary]) -> Result<(), anyhow::Error> {
+        let library = deployed.iter()
+            .find(|library| library.name == link.name && library.source_path == link.source_path)
+            .with_context(|| format!("Library {} was not deployed", link.name))?;
+        linking::link(bytecode, &link.offsets, &library.address)
+    }
+
+    /// The finding a failure of `contract.signature` belongs to: the first one of `findings` with the
+    /// same revert reason (custom errors by name) that was as flaky as this one
+    fn same_failure<'a>(findings: &'a mut [Finding], contract: &str, signature: &str, error: &str, flaky: bool) -> Option<&'a mut Finding> {
+        findings.iter_mut().find(|finding| {
+            finding.contract == contract
+                && finding.signature == signature
+                && finding.is_flaky() == flaky
+                && oracle::revert_group(&finding.error) == oracle::revert_group(error)
+        })
+    }
+
+    /// One line for every finding that stands for more than one failing call
+    fn print_failure_groups(findings: &[Finding]) {
+        for finding in findings.iter().filter(|finding| finding.occurrences > 1) {
+            println!("  🗂️  {}.{} failed {} times with \"{}\" (first input above)",
+                finding.contract, finding.method, finding.occurrences, oracle::revert_group(&finding.error));
+        }
+    }
+
+    /// Decode the custom errors declared in `abi` in revert reasons, on the confirmation fork too,
+    /// its events in call logs and its functions in call trees. Every deployed contract stays
+    /// registered, so a revert bubbling up from another contract of the file, or an event it
+    /// emits, is decoded as well.
+    fn register_abi(&mut self, contract_name: &str, abi: &ethers::abi::Abi) {
+        self.event_decoder.add(abi);
+        if let Some(address) = self.executor.contract_address(contract_name) {
+            self.call_names.add(contract_name, address, abi);
+        }
+        self.executor.register_errors(abi);
+        if let Some(confirm_executor) = self.confirm_executor.as_mut() {
+            confirm_executor.register_errors(abi);
+        }
+    }
+
+    /// Re-execute a failing input on snapshots of the current state to check that it fails deterministically.
+    /// A failure inside a multicall batch (`batched`) always gets at least one individual transaction.
+    /// Returns (reproductions, attempted re-runs).
+    async fn confirm_failure(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo, batched: bool) -> (usize, usize) {
+        let mut reproductions = 0;
+        let mut attempted = 0;
+        let runs = if batched { self.config.confirm_runs.max(1) } else { self.config.confirm_runs };
+
+        for _ in 0..runs {
+            let snapshot_id = match self.executor.snapshot().await {
+                Ok(id) => id,
+                Err(e) => {
+                    warn!("Could not snapshot fork for failure confirmation: {}", e);
+                    break;
+                }
+            };
+
+            attempted += 1;
+            if let TestResult::Failed(_) = self.execute_test_case_evm(method, args, contract).await {
+                reproductions += 1;
+            }
+
+            if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+                warn!("Could not restore fork after failure confirmation: {}", e);
+                break;
+            }
+        }
+
+        (reproductions, attempted)
+    }
+
+    /// Call tree of a failing input, re-executed once on a snapshot of the current state and traced.
+    /// Empty on executors that cannot trace calls, which are only asked once.
+    async fn trace_failure(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo) -> Vec<call_tree::TraceFrame> {
+        if self.call_traces_unavailable {
+            return Vec::new();
+        }
+        let snapshot_id = match self.executor.snapshot().await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("Could not snapshot fork to trace a failing call: {}", e);
+                return Vec::new();
+            }
+        };
+        self.execute_test_case_evm(method, args, contract).await;
+        let frames = self.executor.last_call_frames().await;
+        if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+            warn!("Could not restore fork after tracing a failing call: {}", e);
+        }
+        match frames {
+            Ok(frames) => call_tree::prune(&frames, &self.call_names),
+            Err(e) => {
+                warn!("Could not trace a failing call, findings are reported without call trees: {:#}", e);
+                self.call_traces_unavailable = true;
+                Vec::new()
+            }
+        }
+    }
+
+    /// The call tree of a finding whose revert started in a nested call, one frame per line
+    fn print_call_tree(trace: &[call_tree::TraceFrame]) {
+        if let Some(origin) = call_tree::nested_origin(trace) {
+            println!("     🌳 reverted in a nested call to {}:", origin.call());
+            for frame in trace {
+                println!("          {}", frame);
+            }
+        }
+    }
+
+    /// Shrink the arguments of a deterministic failure: try simpler variants of one argument at a time
+    /// on snapshots of the current state and keep each variant that still fails with the same error,
+    /// until no variant does or the run budget is spent. Updates the finding when anything shrank
+    /// and returns the number of runs spent.
+    async fn shrink_finding(&mut self, method: &ContractMethod, contract: &ContractInfo, finding: &mut Finding) -> usize {
+        if finding.is_flaky() || self.config.shrink_runs == 0 {
+            return 0;
+        }
+
+        let mut args = finding.args.clone();
+        let mut runs = 0;
+        'shrinking: loop {
+            let mut shrank = false;
+            for index in 0..args.len() {
+                // A strategy used for every value of the parameter bounds what it may shrink to
+                let strategy = self.config.value_strategy(&contract.name, method, index)
+                    .filter(|strategy| strategy.percent == 100)
+                    .map(|strategy| strategy.strategy.clone());
+                let param_type = &method.parameters[index].param_type;
+                for candidate in shrink::candidates(&args[index]) {
+                    if strategy.as_ref().is_some_and(|strategy| !strategy.allows(param_type, &candidate)) {
+                        continue;
+                    }
+                    if runs == self.config.shrink_runs {
+                        break 'shrinking;
+                    }
+                    let mut attempt = args.clone();
+                    attempt[index] = candidate;
+
+                    let snapshot_id = match self.executor.snapshot().await {
+                        Ok(id) => id,
+                        Err(e) => {
+                            warn!("Could not snapshot fork for shrinking: {}", e);
+                            break 'shrinking;
+                        }
+                    };
+                    runs += 1;
+                    let result = self.execute_test_case_evm(method, &attempt, contract).await;
+                    if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+                        warn!("Could not restore fork after shrinking: {}", e);
+                        break 'shrinking;
+                    }
+
+                    if matches!(result, TestResult::Failed(ref error) if *error == finding.error) {
+                        args = attempt;
+                        shrank = true;
+                        break;
+                    }
+                }
+            }
+            if !shrank {
+                break;
+            }
+        }
+
+        if args != finding.args {
+            for (index, (shrunk, original)) in args.iter().zip(&finding.args).enumerate() {
+                if let (true, Some(provenance)) = (shrunk != original, finding.provenance.get_mut(index)) {
+                    *provenance = ArgProvenance::Shrunk;
+                }
+            }
+            finding.shrunk_from = Some(std::mem::replace(&mut finding.args, args));
+        }
+        runs
+    }
+
+    /// A finding of an iteration's call, with the arguments, sender, fees and block context it was sent with
+    fn iteration_finding(&self, target: &MethodTarget<'_>, call: &IterationCall, error: String) -> Finding {
+        Finding {
+            provenance: call.plan.provenance.clone(),
+            iteration: call.iteration,
+            seed: call.plan.seed,
+            fees: call.fees,
+            block_context: call.plan.block_context.clone(),
+            value: call.value,
+            ..self.finding(&target.contract.name, target.signature, call.plan.args.clone(), error)
+        }
+    }
+
+    /// Send one iteration's call, or take its outcome from the multicall batch it ran in, after the
+    /// block-context and gas-limit probes its plan asks for. `None` when the iteration was skipped or
+    /// could not be set up, which `totals` already counts.
+    async fn execute_iteration(
+        &mut self,
+        target: &MethodTarget<'_>,
+        plan: generator::IterationPlan,
+        batched_outcome: Option<(TestResult, u64)>,
+        iteration: usize,
+        totals: &mut MethodTotals,
+        findings: &mut Vec<Finding>,
+    ) -> Option<ExecutedIteration> {
+        let MethodTarget { contract, abi, method, signature, .. } = *target;
+
+        // Check if we can generate all required parameters
+        if plan.args.iter().any(|arg| matches!(arg, SolidityValue::String(ref s) if s == "default")) {
+            totals.skipped += 1;
+            return None;
+        }
+
+        self.executor.set_sender(plan.sender_index);
+
+        let fees = match plan.fees {
+            Some(fees) => {
+                self.executor.set_fees(Some(fees));
+                Some(fees)
+            }
+            None => self.config.fees.fixed(),
+        };
+        let value = self.resolve_call_value(plan.value, fees).await;
+        self.executor.set_value(value);
+
+        // Differential block-context run on a snapshot first, then the real run under the primary context
+        let alt_result = match (&plan.block_context, &plan.alt_block_context) {
+            (Some(block_context), Some(alt_block_context)) => {
+                let alt_result = self.probe_block_context(method, &plan.args, contract, alt_block_context).await;
+                if let Err(e) = self.executor.set_block_context(block_context).await {
+                    println!("  ⚠️  {}.{} ERROR on iteration {} (not a contract failure): {:#}",
+                        contract.name, method.name, iteration, e);
+                    totals.errors += 1;
+                    return None;
+                }
+                alt_result
+            }
+            _ => None,
+        };
+        let call = IterationCall { iteration, plan, fees, value, sent_alone: batched_outcome.is_none() };
+        let args = &call.plan.args;
+
+        // OOG probe on snapshots: the input with its full gas, then with a limit near its usage
+        if let (Some(permille), true) = (call.plan.gas_limit_permille, call.sent_alone) {
+            if let Some(probe) = self.probe_gas_limit(method, args, contract, abi, permille).await {
+                let changes = probe.changes.iter()
+                    .map(|change| format!("{} {} instead of {}", change.getter, change.after, change.before))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("  ⛽ {}.{}({}) succeeds with gas limit {} (used {}) but leaves different state on iteration {}: {}",
+                    contract.name, method.name, self.format_args_for_display(args), probe.gas_limit, probe.gas_used, iteration, changes);
+                totals.gas_dependent += 1;
+                findings.push(Finding {
+                    gas_limit: Some(probe.gas_limit),
+                    ..self.iteration_finding(target, &call, format!("Gas-dependent state (gas limit {}, {} used with the default limit): {}", probe.gas_limit, probe.gas_used, changes))
+                });
+            }
+        }
+
+        // The attacker only calls back when the arguments hand it to the target
+        let attacker_armed = call.sent_alone && self.arm_attacker(contract, method, args, value).await;
+        // Protected state before the call, when a privileged method is called by someone not entitled to it
+        let unauthorized = if self.config.access_control && call.sent_alone && access_control::is_privileged(method) {
+            self.protected_state_if_unauthorized(&contract.name, abi, target.protected_variables).await
+        } else {
+            None
+        };
+
+        // Execute on Anvil fork - fail loudly if execution fails
+        let (result, gas_used) = match batched_outcome {
+            Some(outcome) => outcome,
+            None => self.execute_test_case_metered(method, args, contract).await,
+        };
+        totals.record_gas(gas_used);
+        // A batched call's gas is only its share of the batch
+        if call.sent_alone && !matches!(result, TestResult::Error(_)) && totals.gas_alert.as_ref().is_none_or(|alert| gas_used > alert.gas_used) {
+            if let Some((limit, exhausted)) = gas_profile::check(gas_used, DEFAULT_GAS_LIMIT, self.config.gas_threshold) {
+                totals.gas_alert = Some(GasAlert {
+                    contract: contract.name.clone(),
+                    method: signature.to_string(),
+                    iteration,
+                    seed: call.plan.seed,
+                    gas_used,
+                    limit,
+                    exhausted,
+                });
+            }
+        }
+
+        Some(ExecutedIteration { call, result, alt_result, attacker_armed, unauthorized })
+    }
+
+    /// Count an executed iteration in `totals` and report what its outcome reveals: a dependency on the
+    /// block context, a success with a system address as argument, or a failure. A failure is confirmed,
+    /// shrunk and traced, unless it is like one already reported for the method (`findings` from
+    /// `findings_start` on), which only counts it.
+    async fn classify_outcome(
+        &mut self,
+        target: &MethodTarget<'_>,
+        executed: ExecutedIteration,
+        findings: &mut Vec<Finding>,
+        findings_start: usize,
+        totals: &mut MethodTotals,
+    ) {
+        let MethodTarget { contract, method, signature, oracle, .. } = *target;
+        let ExecutedIteration { call, result, alt_result, unauthorized, .. } = executed;
+        let args = &call.plan.args;
+
+        if let (Some(alt_result), Some(block_context), Some(alt_block_context)) = (&alt_result, &call.plan.block_context, &call.plan.alt_block_context) {
+            let divergence = match (&result, alt_result) {
+                (TestResult::Passed, TestResult::Failed(error)) => Some((block_context, alt_block_context, error)),
+                (TestResult::Failed(error), TestResult::Passed) => Some((alt_block_context, block_context, error)),
+                _ => None,
+            };
+            if let Some((passing, failing, error)) = divergence {
+                println!("  🎲 {}.{}({}) depends on block context on iteration {}: passes with [{}], fails with [{}]: {}",
+                    contract.name, method.name, self.format_args_for_display(args), call.iteration, passing, failing, error);
+                println!("     ↳ {}", Self::format_provenance(method, &call.plan.provenance));
+                totals.block_context_dependent += 1;
+                findings.push(Finding {
+                    block_context: Some(failing.clone()),
+                    ..self.iteration_finding(target, &call, format!("Outcome depends on block context (passes with [{}]): {}", passing, error))
+                });
+            }
+        }
+
+        match result {
+            TestResult::Passed => {
+                totals.passed += 1;
+                // Precompiles and system contracts answer calls no contract meant to make; a call that
+                // treats one as a token, recipient or callee and still succeeds is worth a look
+                let system_args: Vec<(String, precompiles::SystemAddress)> =
+                    precompiles::in_args(&method.parameters, args, &self.generator_context.system_addresses)
+                        .into_iter()
+                        .map(|(param, system)| (param.to_string(), *system))
+                        .collect();
+                if !system_args.is_empty() {
+                    totals.system_address_successes += 1;
+                }
+                for (param, system) in system_args {
+                    if !totals.system_address_params.insert(param.clone()) {
+                        continue;
+                    }
+                    println!("  🔌 {}.{}({}) succeeded with {} = {} on iteration {}",
+                        contract.name, method.name, self.format_args_for_display(args), param, system, call.iteration);
+                    findings.push(self.iteration_finding(target, &call, format!("Succeeded with {} = {}", param, system)));
+                }
+            }
+            TestResult::Failed(_) if unauthorized.is_some() => {
+                totals.access_denied += 1;
+            }
+            TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::CheckedMath => {
+                totals.checked_math_panics += 1;
+            }
+            TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::Expected => {
+                totals.expected_reverts += 1;
+            }
+            TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::Rejected => {
+                totals.skipped += 1;
+            }
+            TestResult::Failed(error) => {
+                let args_display = self.format_args_for_display(args);
+                let (reproductions, confirmation_runs) = self.confirm_failure(method, args, contract, !call.sent_alone).await;
+                if !call.sent_alone && reproductions == 0 {
+                    // Inside the batch the aggregator was msg.sender and earlier sub-calls had run
+                    println!("  📦 {}.{}({}) failed only inside a multicall batch on iteration {} (not reproduced by an individual transaction): {}",
+                        contract.name, method.name, args_display, call.iteration, error);
+                    totals.unconfirmed += 1;
+                    return;
+                }
+                // Failures like one already reported are counted, not reported again
+                let flaky = reproductions < confirmation_runs;
+                if let Some(representative) = Self::same_failure(&mut findings[findings_start..], &contract.name, signature, &error, flaky) {
+                    representative.occurrences += 1;
+                    if flaky {
+                        totals.flaky += 1;
+                    } else if self.confirm_executor.is_none() {
+                        totals.failed += 1;
+                    }
+                    return;
+                }
+                let mut finding = Finding {
+                    reproductions,
+                    confirmation_runs,
+                    ..self.iteration_finding(target, &call, error)
+                };
+                let shrink_runs = self.shrink_finding(method, contract, &mut finding).await;
+                finding.call_trace = self.trace_failure(method, &finding.args, contract).await;
+                let args_display = self.format_args_for_display(&finding.args);
+                if finding.is_flaky() {
+                    println!("  🔁 {}.{}({}) FLAKY on iteration {}: {} (reproduced {}/{})",
+                        contract.name, method.name, args_display, call.iteration, finding.error, reproductions, confirmation_runs);
+                    totals.flaky += 1;
+                } else if self.confirm_executor.is_some() {
+                    // Counted once the confirmation phase has replayed it
+                    println!("  🔎 {}.{}({}) CANDIDATE on iteration {}: {}",
+                        contract.name, method.name, args_display, call.iteration, finding.error);
+                } else {
+                    println!("  ❌ {}.{}({}) FAILED on iteration {}: {}",
+                        contract.name, method.name, args_display, call.iteration, finding.error);
+                    totals.failed += 1;
+                }
+                println!("     ↳ {}", Self::format_provenance(method, &finding.provenance));
+                if !finding.value.is_zero() {
+                    println!("     ↳ sent with {} wei", finding.value);
+                }
+                if let Some(original) = &finding.shrunk_from {
+                    println!("     ↳ shrunk from ({}) in {} run(s)", self.format_args_for_display(original), shrink_runs);
+                }
+                if finding.impersonated_owner {
+                    println!("     👑 sent as the impersonated owner {}", finding.sender);
+                }
+                Self::print_call_tree(&finding.call_trace);
+                if let Some(property) = scribble::violated_property(&finding.error) {
+                    println!("     📜 Scribble property violated: {}", property);
+                }
+                findings.push(finding);
+            }
+            TestResult::Error(error) => {
+                println!("  ⚠️  {}.{} ERROR on iteration {} (not a contract failure): {}",
+                    contract.name, method.name, call.iteration, error);
+                totals.errors += 1;
+            }
+        }
+    }
+
+    /// Confirmation phase of a dual-phase campaign: redeploy the contract on the faithful fork and replay
+    /// every confirmed candidate finding with its original sender and arguments. The replays start from
+    /// the fresh deployment, not from the state exploration had built up, so a failure that depended on
+    /// that state comes out unconfirmed.
+    async fn run_confirmation_phase(
+        &mut self,
+        contract: &ContractInfo,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+        libraries: &[DeployedLibrary],
+        findings: &mut [Finding],
+    ) -> Result<ConfirmationTotals, anyhow::Error> {
+        let mut totals = ConfirmationTotals::default();
+        let candidates = findings.iter().filter(|finding| !finding.is_flaky()).count();
+        if candidates == 0 {
+            return Ok(totals);
+        }
+
+        let Some(confirm_executor) = self.confirm_executor.as_mut() else {
+            return Ok(totals);
+        };
+
+        println!();
+        println!("- Confirmation phase: replaying {} candidate(s) on {}", candidates, confirm_executor.rpc_url());
+        // The bytecode is linked against the library addresses of the first fork, so the libraries go to the same ones
+        for library in libraries {
+            confirm_executor.set_code(&library.address, &library.code).await
+                .map_err(|e| anyhow::anyhow!("Library {} could not be placed on the confirmation fork: {}", library.name, e))?;
+        }
+        let addr = confirm_executor.deploy_contract(&contract.name, bytecode, constructor_args).await
+            .map_err(|e| anyhow::anyhow!("Contract deployment on confirmation fork failed: {}", e))?;
+        println!("- Contract deployed at: {}", addr);
+        if harness::is_harness(contract) {
+            Self::prepare_harness(confirm_executor.as_mut(), contract).await;
+        }
+
+        for finding in findings.iter_mut().filter(|finding| !finding.is_flaky()) {
+            let Some(method) = contract.methods.iter().find(|m| m.name == finding.method && m.parameters.len() == finding.args.len()) else {
+                continue;
+            };
+            let args_display = self.format_args_for_display(&finding.args);
+            let call = match self.encode_call(method, &finding.args) {
+                Ok(call) => call,
+                Err(e) => {
+                    println!("  ⚠️  {}.{} ERROR during confirmation (not a contract failure): {}", contract.name, method.name, e);
+                    totals.errors += finding.occurrences;
+                    continue;
+                }
+            };
+
+            // Sequence findings replay their setup calls and cheats first, on a snapshot so later findings start clean
+            let confirm_executor = self.confirm_executor.as_mut().expect("checked above");
+            let sequence_snapshot = if finding.sequence.is_empty() && finding.cheats.is_empty() {
+                None
+            } else {
+                match confirm_executor.snapshot().await {
+                    Ok(id) => Some(id),
+                    Err(e) => {
+                        warn!("Could not snapshot confirmation fork, skipping sequence finding: {}", e);
+                        totals.errors += finding.occurrences;
+                        continue;
+                    }
+                }
+            };
+            for setup in &finding.sequence {
+                let Some(setup_method) = contract.methods.iter().find(|m| abi_encoding::method_signature(m) == setup.signature) else {
+                    continue;
+                };
+                let confirm_executor = self.confirm_executor.as_mut().expect("checked above");
+                Self::apply_cheats(confirm_executor.as_mut(), &setup.cheats).await;
+                Self::switch_sender(confirm_executor.as_mut(), &setup.sender).await;
+                confirm_executor.set_value(setup.value);
+                let call = (setup.signature.clone(), setup.calldata.clone());
+                let (result, gas_used) = Self::execute_call_metered(confirm_executor.as_mut(), &contract.name, &call).await;
+                let sender = confirm_executor.current_sender().to_string();
+                self.trace(TraceRecord::new(TracePhase::Confirm, &contract.name, &sender, setup_method, &call, &result, gas_used));
+            }
+
+            let confirm_executor = self.confirm_executor.as_mut().expect("checked above");
+            if !Self::switch_sender(confirm_executor.as_mut(), &finding.sender).await {
+                warn!("Sender {} is not available on the confirmation fork, replaying from {}", finding.sender, confirm_executor.current_sender());
+            }
+
+            let confirm_executor = self.confirm_executor.as_mut().expect("checked above");
+            Self::apply_cheats(confirm_executor.as_mut(), &finding.cheats).await;
+            confirm_executor.set_value(finding.value);
+            let (mut result, gas_used) = Self::execute_call_metered(confirm_executor.as_mut(), &contract.name, &call).await;
+            confirm_executor.set_value(U256::zero());
+            // The event oracle judged a call that passed; it has to emit the suspicious event again
+            if matches!(result, TestResult::Passed) && finding.error.starts_with(events::EVENT_ORACLE) {
+                if let Some(violation) = Self::event_violation(confirm_executor.as_mut(), &self.event_decoder, &contract.name, &finding.args).await {
+                    result = TestResult::Failed(format!("{} {}", events::EVENT_ORACLE, violation));
+                }
+            }
+            let sender = confirm_executor.current_sender().to_string();
+            self.trace(TraceRecord::new(TracePhase::Confirm, &contract.name, &sender, method, &call, &result, gas_used));
+            if let Some(snapshot_id) = &sequence_snapshot {
+                let confirm_executor = self.confirm_executor.as_mut().expect("checked above");
+                if let Err(e) = confirm_executor.revert_to_snapshot(snapshot_id).await {
+                    warn!("Could not restore confirmation fork after sequence finding: {}", e);
+                }
+            }
+            match result {
+                TestResult::Failed(error) => {
+                    println!("  ❌ {}.{}({}) CONFIRMED on realistic fork: {}", contract.name, method.name, args_display, error);
+                    finding.confirmed_on_fork = Some(true);
+                    totals.confirmed += finding.occurrences;
+                }
+                TestResult::Passed => {
+                    println!("  🧪 {}.{}({}) not reproduced on realistic fork (turbo-only)", contract.name, method.name, args_display);
+                    finding.confirmed_on_fork = Some(false);
+                    totals.unconfirmed += finding.occurrences;
+                }
+                TestResult::Error(error) => {
+                    println!("  ⚠️  {}.{} ERROR during confirmation (not a contract failure): {}", contract.name, method.name, error);
+                    totals.errors += finding.occurrences;
+                }
+            }
+        }
+
+        Ok(totals)
+    }
+
+    /// Stateful phase: sequences of calls across the contract's methods with random senders and
+    /// arguments, so failures that need earlier calls to set up state (deposit → withdraw,
+    /// approve → transferFrom) can be reached. Each sequence starts from a snapshot of the state the
+    /// per-method phase left, ends at its first failing call and is reverted afterwards.
+    async fn run_sequence_phase(
+        &mut self,
+        contract: &ContractInfo,
+        methods: &[&ContractMethod],
+        plan_options: &PlanOptions,
+        oracle: &OracleProfile,
+        properties: &mut PropertyChecks,
+        findings: &mut Vec<Finding>,
+    ) -> SequenceTotals {
+        let mut totals = SequenceTotals::default();
+        if methods.is_empty() {
+            return totals;
+        }
+        println!("- Stateful phase: {} sequence(s) of up to {} call(s) across {} method(s){}",
+            self.config.sequences, self.config.sequence_length, methods.len(), self.cheats_note());
+
+        // Fees carry over; block-context and gas-limit probes stay with the per-method phase
+        let plan_options = PlanOptions { block_context: false, gas_limit: false, ..*plan_options };
+        let phase_seed = generator::method_seed(self.master_seed, &contract.name, "<sequences>");
+        let mut sequences_run = 0;
+        let mut failing_sequences = 0;
+        let phase_findings_start = findings.len();
+        // Every sender and the reentrancy attacker are watched for ETH they take out of the contract
+        let mut watched_accounts = self.executor.accounts().to_vec();
+        watched_accounts.extend(self.generator_context.attacker_address.clone());
+        let mut drain_detection = true;
+
+        for sequence in 0..self.config.sequences {
+            let sequence_seed = generator::iteration_seed(phase_seed, sequence + 1);
+            let snapshot_id = match self.executor.snapshot().await {
+                Ok(id) => id,
+                Err(e) => {
+                    warn!("Could not snapshot fork, stateful phase stopped: {}", e);
+                    break;
+                }
+            };
+            sequences_run += 1;
+
+            let balances_before = if drain_detection {
+                match ether_drain::read(self.executor.as_mut(), &contract.name, &watched_accounts).await {
+                    Ok(balances) => Some(balances),
+                    Err(e) => {
+                        warn!("Could not read ETH balances, ether-drain detection disabled: {:#}", e);
+                        drain_detection = false;
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            let mut prefix: Vec<SequenceCall> = Vec::new();
+            // Cheats stay pending until a call that keeps its effects (a passing one) or a finding records them
+            let mut pending_cheats = self.apply_time_jump(sequence_seed).await;
+            let mut dealt: Vec<String> = Vec::new();
+            for step in 0..self.config.sequence_length {
+                let call_seed = generator::iteration_seed(sequence_seed, step + 1);
+                let method = methods[generator::sequence_method_index(call_seed, methods.len())];
+                let plan = self.plan_iteration(&contract.name, method, call_seed, &plan_options, None);
+                if plan.args.iter().any(|arg| matches!(arg, SolidityValue::String(ref s) if s == "default")) {
+                    totals.skipped += 1;
+                    continue;
+                }
+                self.apply_sequence_cheats(call_seed, &mut pending_cheats, &mut dealt).await;
+
+                self.executor.set_sender(plan.sender_index);
+                let tx_fees = match plan.fees {
+                    Some(fees) => {
+                        self.executor.set_fees(Some(fees));
+                        Some(fees)
+                    }
+                    None => self.config.fees.fixed(),
+                };
+                let call_value = self.resolve_call_value(plan.value, tx_fees).await;
+                self.executor.set_value(call_value);
+
+                let call = match self.encode_call(method, &plan.args) {
+                    Ok(call) => call,
+                    Err(e) => {
+                        println!("  ⚠️  {}.{} ERROR in sequence {} (not a contract failure): {}", contract.name, method.name, sequence + 1, e);
+                        totals.errors += 1;
+                        continue;
+                    }
+                };
+                let (result, gas_used) = Self::execute_call_metered(self.executor.as_mut(), &contract.name, &call).await;
+                let result = self.observe_call(method, contract, &call, result, gas_used);
+                let result = self.observe_events(contract, &plan.args, result).await;
+
+                if !properties.is_empty() && !matches!(result, TestResult::Error(_)) {
+                    // A failing call ends the sequence, so per-sequence invariants are due after it
+                    let sequence_end = step + 1 == self.config.sequence_length
+                        || matches!(&result, TestResult::Failed(error) if !oracle.is_expected_revert(error));
+                    let after_call = format!("{}({}) from {} (call {} of sequence {})",
+                        method.name, self.format_args_for_display(&plan.args), self.executor.current_sender(), step + 1, sequence + 1);
+                    self.check_properties(contract, properties, step + 1, sequence_end, Some((after_call, call_seed)), &mut totals.invariant_violations).await;
+                }
+
+                match result {
+                    TestResult::Passed => {
+                        totals.passed += 1;
+                        prefix.push(SequenceCall {
+                            signature: call.0,
+                            sender: self.executor.current_sender().to_string(),
+                            args: plan.args,
+                            calldata: call.1,
+                            value: call_value,
+                            contract: None,
+                            contract_address: None,
+                            cheats: std::mem::take(&mut pending_cheats),
+                        });
+                    }
+                    TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::CheckedMath => {
+                        totals.checked_math_panics += 1;
+                    }
+                    TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::Expected => {
+                        totals.expected_reverts += 1;
+                    }
+                    TestResult::Failed(error) if oracle.classify_revert(&error) == RevertClass::Rejected => {
+                        totals.skipped += 1;
+                    }
+                    TestResult::Failed(error) => {
+                        let (reproductions, confirmation_runs) = self.confirm_failure(method, &plan.args, contract, false).await;
+                        let flaky = reproductions < confirmation_runs;
+                        if let Some(representative) = Self::same_failure(&mut findings[phase_findings_start..], &contract.name, &call.0, &error, flaky) {
+                            representative.occurrences += 1;
+                            if flaky {
+                                totals.flaky += 1;
+                            } else if self.confirm_executor.is_none() {
+                                totals.failed += 1;
+                            }
+                            failing_sequences += 1;
+                            break;
+                        }
+                        let mut finding = Finding {
+                            provenance: plan.provenance,
+                            iteration: sequence + 1,
+                            seed: call_seed,
+                            reproductions,
+                            confirmation_runs,
+                            fees: tx_fees,
+                            value: call_value,
+                            // Kept for the ether-drain check at the end of the sequence
+                            sequence: prefix.clone(),
+                            cheats: std::mem::take(&mut pending_cheats),
+                            ..self.finding(&contract.name, &abi_encoding::method_signature(method), plan.args, error)
+                        };
+                        let shrink_runs = self.shrink_finding(method, contract, &mut finding).await;
+                        finding.call_trace = self.trace_failure(method, &finding.args, contract).await;
+                        let args_display = self.format_args_for_display(&finding.args);
+                        let position = format!("call {} of sequence {}", finding.sequence.len() + 1, sequence + 1);
+                        if finding.is_flaky() {
+                            println!("  🔁 {}.{}({}) FLAKY at {}: {} (reproduced {}/{})",
+                                contract.name, method.name, args_display, position, finding.error, reproductions, confirmation_runs);
+                            totals.flaky += 1;
+                        } else if self.confirm_executor.is_some() {
+                            println!("  🔎 {}.{}({}) CANDIDATE at {}: {}",
+                                contract.name, method.name, args_display, position, finding.error);
+                        } else {
+                            println!("  ❌ {}.{}({}) FAILED at {}: {}",
+                                contract.name, method.name, args_display, position, finding.error);
+                            totals.failed += 1;
+                        }
+                        println!("     ↳ {}", Self::format_provenance(method, &finding.provenance));
+                        if !finding.value.is_zero() {
+                            println!("     ↳ sent with {} wei", finding.value);
+                        }
+                        if let Some(original) = &finding.shrunk_from {
+                            println!("     ↳ shrunk from ({}) in {} run(s)", self.format_args_for_display(original), shrink_runs);
+                        }
+                        if finding.impersonated_owner {
+                            println!("     👑 sent as the impersonated owner {}", finding.sender);
+                        }
+                        Self::print_call_tree(&finding.call_trace);
+                        if !finding.sequence.is_empty() {
+                            println!("     ↳ after {}", self.format_sequence(&finding.sequence));
+                        }
+                        if !finding.cheats.is_empty() {
+                            println!("     ⏰ right after {}", Self::format_cheats(&finding.cheats));
+                        }
+                        if let Some(property) = scribble::violated_property(&finding.error) {
+                            println!("     📜 Scribble property violated: {}", property);
+                        }
+                        findings.push(finding);
+                        failing_sequences += 1;
+                        break;
+                    }
+                    TestResult::Error(error) => {
+                        println!("  ⚠️  {}.{} ERROR in sequence {} (not a contract failure): {}", contract.name, method.name, sequence + 1, error);
+                        totals.errors += 1;
+                    }
+                }
+            }
+
+            if let Some(before) = &balances_before {
+                match ether_drain::read(self.executor.as_mut(), &contract.name, &watched_accounts).await {
+                    Ok(after) => {
+                        // Dealt balances are not the contract's ETH
+                        let privileged: Vec<String> = properties.drain_privileged.iter().chain(&dealt).cloned().collect();
+                        if let Some(drain) = ether_drain::find_drain(before, &after, &privileged) {
+                            if let Some(finding) = self.drain_finding(contract, &drain, &prefix, sequence, sequence_seed) {
+                                println!("  💸 {} drained in sequence {}: {}", contract.name, sequence + 1, drain);
+                                println!("     ↳ {}", self.format_sequence(&prefix));
+                                findings.push(finding);
+                                totals.ether_drains += 1;
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Could not read ETH balances after sequence {}: {:#}", sequence + 1, e),
+                }
+            }
+
+            if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+                warn!("Could not restore fork after sequence {}, stateful phase stopped: {}", sequence + 1, e);
+                break;
+            }
+            self.resync_properties(&contract.name, properties).await;
+        }
+
+        self.executor.set_value(U256::zero());
+        println!("  🔗 {} sequence(s) run, {} ended in a failure", sequences_run, failing_sequences);
+        Self::print_failure_groups(&findings[phase_findings_start..]);
+        totals
+    }
+
+    /// Stateful sequences that mix the methods of every fuzzed contract of the file, for bugs that
+    /// take calls to several contracts (a token, the vault holding it, an oracle). A failing call is
+    /// reported on the contract it went to, after the calls to the others that set it up.
+    async fn run_cross_contract_phase(&mut self, targets: &[CrossTarget], findings: &mut Vec<Finding>) -> SequenceTotals {
+        let mut totals = SequenceTotals::default();
+        let callable: Vec<(&CrossTarget, &ContractMethod)> = targets.iter()
+            .flat_map(|target| target.methods.iter().map(move |method| (target, method)))
+            .collect();
+        if callable.is_empty() {
+            return totals;
+        }
+        println!("🔗 Cross-contract phase: {} sequence(s) of up to {} call(s) across {} method(s) of {} contracts{}",
+            self.config.sequences, self.config.sequence_length, callable.len(), targets.len(), self.cheats_note());
+
+        let phase_seed = generator::method_seed(self.master_seed, "<file>", "<cross-contract sequences>");
+        let mut sequences_run = 0;
+        let mut failing_sequences = 0;
+        let phase_findings_start = findings.len();
+        for sequence in 0..self.config.sequences {
+            let sequence_seed = generator::iteration_seed(phase_seed, sequence + 1);
+            let snapshot_id = match self.executor.snapshot().await {
+                Ok(id) => id,
+                Err(e) => {
+                    warn!("Could not snapshot fork, cross-contract phase stopped: {}", e);
+                    break;
+                }
+            };
+            sequences_run += 1;
+
+            let mut prefix: Vec<SequenceCall> = Vec::new();
+            let mut pending_cheats = self.apply_time_jump(sequence_seed).await;
+            let mut dealt: Vec<String> = Vec::new();
+            for step in 0..self.config.sequence_length {
+                let call_seed = generator::iteration_seed(sequence_seed, step + 1);
+                let (target, method) = callable[generator::sequence_method_index(call_seed, callable.len())];
+                let contract = &target.contract;
+                let plan_options = PlanOptions { block_context: false, gas_limit: false, ..target.plan_options };
+                let plan = self.plan_iteration(&contract.name, method, call_seed, &plan_options, None);
+                if plan.args.iter().any(|arg| matches!(arg, SolidityValue::String(ref s) if s == "default")) {
+                    totals.skipped += 1;
+                    continue;
+                }
+                self.apply_sequence_cheats(call_seed, &mut pending_cheats, &mut dealt).await;
+
+                self.executor.set_sender(plan.sender_index);
+                let tx_fees = match plan.fees {
+                    Some(fees) => {
+                        self.executor.set_fees(Some(fees));
+                        Some(fees)
+                    }
+                    None => self.config.fees.fixed(),
+                };
+                let call_value = self.resolve_call_value(plan.value, tx_fees).await;
+                self.executor.set_value(call_value);
+
+                let call = match self.encode_call(method, &plan.args) {
+                    Ok(call) => call,
+                    Err(e) => {
+                        println!("  ⚠️  {}.{} ERROR in cross-contract sequence {} (not a contract failure): {}", contract.name, method.name, sequence + 1, e);
+                        totals.errors += 1;
+                        continue;
+                    }
+                };
+                let (result, gas_used) = Self::execute_call_metered(self.executor.as_mut(), &contract.name, &call).await;
+                let result = self.observe_call(method, contract, &call, result, gas_used);
+                let result = self.observe_events(contract, &plan.args, result).await;
+
+                match result {
+                    TestResult::Passed => {
+                        totals.passed += 1;
+                        prefix.push(SequenceCall {
+                            signature: call.0,
+                            sender: self.executor.current_sender().to_string(),
+                            args: plan.args,
+                            calldata: call.1,
+                            value: call_value,
+                            contract: Some(contract.name.clone()),
+                            contract_address: self.executor.contract_address(&contract.name).map(str::to_string),
+                            cheats: std::mem::take(&mut pending_cheats),
+                        });
+                    }
+                    TestResult::Failed(error) if target.oracle.classify_revert(&error) == RevertClass::CheckedMath => {
+                        totals.checked_math_panics += 1;
+                    }
+                    TestResult::Failed(error) if target.oracle.classify_revert(&error) == RevertClass::Expected => {
+                        totals.expected_reverts += 1;
+                    }
+                    TestResult::Failed(error) if target.oracle.classify_revert(&error) == RevertClass::Rejected => {
+                        totals.skipped += 1;
+                    }
+                    TestResult::Failed(error) => {
+                        let (reproductions, confirmation_runs) = self.confirm_failure(method, &plan.args, contract, false).await;
+                        let flaky = reproductions < confirmation_runs;
+                        if let Some(representative) = Self::same_failure(&mut findings[phase_findings_start..], &contract.name, &call.0, &error, flaky) {
+                            representative.occurrences += 1;
+                            if flaky {
+                                totals.flaky += 1;
+                            } else if self.confirm_executor.is_none() {
+                                totals.failed += 1;
+                            }
+                            failing_sequences += 1;
+                            break;
+                        }
+                        let mut finding = Finding {
+                            provenance: plan.provenance,
+                            iteration: sequence + 1,
+                            seed: call_seed,
+                            reproductions,
+                            confirmation_runs,
+                            fees: tx_fees,
+                            value: call_value,
+                            sequence: prefix.clone(),
+                            cheats: std::mem::take(&mut pending_cheats),
+                            ..self.finding(&contract.name, &abi_encoding::method_signature(method), plan.args, error)
+                        };
+                        let shrink_runs = self.shrink_finding(method, contract, &mut finding).await;
+                        finding.call_trace = self.trace_failure(method, &finding.args, contract).await;
+                        let args_display = self.format_args_for_display(&finding.args);
+                        let position = format!("call {} of cross-contract sequence {}", finding.sequence.len() + 1, sequence + 1);
+                        if finding.is_flaky() {
+                            println!("  🔁 {}.{}({}) FLAKY at {}: {} (reproduced {}/{})",
+                                contract.name, method.name, args_display, position, finding.error, reproductions, confirmation_runs);
+                            totals.flaky += 1;
+                        } else {
+                            println!("  ❌ {}.{}({}) FAILED at {}: {}",
+                                contract.name, method.name, args_display, position, finding.error);
+                            totals.failed += 1;
+                        }
+                        println!("     ↳ {}", Self::format_provenance(method, &finding.provenance));
+                        if !finding.value.is_zero() {
+                            println!("     ↳ sent with {} wei", finding.value);
+                        }
+                        if let Some(original) = &finding.shrunk_from {
+                            println!("     ↳ shrunk from ({}) in {} run(s)", self.format_args_for_display(original), shrink_runs);
+                        }
+                        if finding.impersonated_owner {
+                            println!("     👑 sent as the impersonated owner {}", finding.sender);
+                        }
+                        Self::print_call_tree(&finding.call_trace);
+                        if !finding.sequence.is_empty() {
+                            println!("     ↳ after {}", self.format_sequence(&finding.sequence));
+                        }
+                        if !finding.cheats.is_empty() {
+                            println!("     ⏰ right after {}", Self::format_cheats(&finding.cheats));
+                        }
+                        findings.push(finding);
+                        failing_sequences += 1;
+                        break;
+                    }
+                    TestResult::Error(error) => {
+                        println!("  ⚠️  {}.{} ERROR in cross-contract sequence {} (not a contract failure): {}", contract.name, method.name, sequence + 1, error);
+                        totals.errors += 1;
+                    }
+                }
+            }
+
+            if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+                warn!("Could not restore fork after cross-contract sequence {}, phase stopped: {}", sequence + 1, e);
+                break;
+            }
+        }
+
+        self.executor.set_value(U256::zero());
+        println!("  🔗 {} cross-contract sequence(s) run, {} ended in a failure", sequences_run, failing_sequences);
+        Self::print_failure_groups(&findings[phase_findings_start..]);
+        totals
+    }
+
+    /// Finding for an ether drain: the drainer's last call of the sequence (the last call of all, when
+    /// another call sent it the ETH) replayed after the calls that came before it
+    fn drain_finding(&self, contract: &ContractInfo, drain: &ether_drain::Drain, calls: &[SequenceCall], sequence: usize, sequence_seed: u64) -> Option<Finding> {
+        let last = calls.iter().rposition(|call| call.sender.eq_ignore_ascii_case(&drain.account))
+            .or(calls.len().checked_sub(1))?;
+        let call = &calls[last];
+        let owner = self.owners.get(&contract.name).and_then(|index| self.executor.accounts().get(*index));
+        Some(Finding {
+            sender: call.sender.clone(),
+            iteration: sequence + 1,
+            seed: sequence_seed,
+            fees: self.config.fees.fixed(),
+            value: call.value,
+            sequence: calls[..last].to_vec(),
+            impersonated_owner: owner.is_some_and(|owner| owner.eq_ignore_ascii_case(&call.sender)),
+            cheats: call.cheats.clone(),
+            ..self.finding(&contract.name, &call.signature, call.args.clone(), format!("Ether drain: {}", drain))
+        })
+    }
+
+    /// Evaluate the invariants due after the `call`-th call of a sequence (call 0 is the deployment)
+    /// and retire the ones that are violated, then compare the ETH balance with the accounting
+    /// variables. `after_call` describes that call and carries its seed.
+    async fn check_properties(
+        &mut self,
+        contract: &ContractInfo,
+        properties: &mut PropertyChecks,
+        call: usize,
+        sequence_end: bool,
+        after_call: Option<(String, u64)>,
+        violations: &mut Vec<InvariantViolation>,
+    ) {
+        let active = &mut properties.invariants;
+        let mut index = 0;
+        while index < active.len() {
+            if !self.config.invariant_schedule.is_due(&active[index], call, sequence_end) {
+                index += 1;
+                continue;
+            }
+            let signature = format!("{}()", active[index]);
+            let reason = match self.executor.static_call(&contract.name, &calculate_selector(&signature)).await {
+                Ok(output) => invariants::check_output(&output),
+                Err(e) if e.to_string().to_lowercase().contains("revert") => Some(format!("reverted: {}", e)),
+                Err(e) => {
+                    warn!("Could not check invariant {}.{}: {:#}", contract.name, signature, e);
+                    None
+                }
+            };
+            let Some(reason) = reason else {
+                index += 1;
+                continue;
+            };
+            active.remove(index);
+            let violation = InvariantViolation {
+                contract: contract.name.clone(),
+                invariant: signature,
+                reason,
+                after_call: after_call.as_ref().map(|(description, _)| description.clone()),
+                seed: after_call.as_ref().map(|(_, seed)| *seed),
+            };
+            println!("  🚨 {}", violation);
+            violations.push(violation);
+        }
+
+        if let Some(standards) = properties.standards.as_mut() {
+            let due: Vec<&'static str> = standards.active().iter().copied()
+                .filter(|check| self.config.invariant_schedule.is_due(check, call, sequence_end))
+                .collect();
+            for check in due {
+                let Some(reason) = standards.check(self.executor.as_mut(), &contract.name, check).await else {
+                    continue;
+                };
+                let violation = InvariantViolation {
+                    contract: contract.name.clone(),
+                    invariant: check.to_string(),
+                    reason,
+                    after_call: after_call.as_ref().map(|(description, _)| description.clone()),
+                    seed: after_call.as_ref().map(|(_, seed)| *seed),
+                };
+                println!("  🚨 {}", violation);
+                violations.push(violation);
+            }
+        }
+
+        let Some(ledger) = properties.ledger.as_mut() else {
+            return;
+        };
+        if call == 0 {
+            return;
+        }
+        let Some((balance, words)) = self.read_ledger_state(&contract.name, &ledger.slots()).await else {
+            return;
+        };
+        for (check, reason) in ledger.record(balance, &words) {
+            let invariant = if check.is_empty() { "eth-accounting".to_string() } else { format!("eth-accounting({})", check) };
+            let violation = InvariantViolation {
+                contract: contract.name.clone(),
+                invariant,
+                reason,
+                after_call: after_call.as_ref().map(|(description, _)| description.clone()),
+                seed: after_call.as_ref().map(|(_, seed)| *seed),
+            };
+            println!("  🚨 {}", violation);
+            violations.push(violation);
+        }
+    }
+
+    /// ETH balance of a contract and the words in `slots`, `None` when they could not be read
+    async fn read_ledger_state(&mut self, contract_name: &str, slots: &[U256]) -> Option<(U256, Vec<U256>)> {
+        let balance = match self.executor.contract_balance(contract_name).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!("Could not read balance of {} for value accounting: {:#}", contract_name, e);
+                return None;
+            }
+        };
+        let mut words = Vec::with_capacity(slots.len());
+        for slot in slots {
+            match self.executor.storage_at(contract_name, *slot).await {
+                Ok(word) => words.push(word),
+                Err(e) => {
+                    warn!("Could not read storage of {} for value accounting: {:#}", contract_name, e);
+                    return None;
+                }
+            }
+        }
+        Some((balance, words))
+    }
+
+    /// Re-read the ledger's state and role assignments after a snapshot was restored, so the restore
+    /// is not counted as a flow or a privilege change
+    async fn resync_properties(&mut self, contract_name: &str, properties: &mut PropertyChecks) {
+        if let Some(standards) = properties.standards.as_mut() {
+            standards.resync(self.executor.as_mut(), contract_name).await;
+        }
+        let Some(slots) = properties.ledger.as_ref().map(ValueLedger::slots) else {
+            return;
+        };
+        if let Some((balance, words)) = self.read_ledger_state(contract_name, &slots).await {
+            if let Some(ledger) = properties.ledger.as_mut() {
+                ledger.resync(balance, &words);
+            }
+        }
+    }
+
+    /// Restore the snapshot an isolated iteration started from, if one is pending
+    async fn restore_iteration(&mut self, snapshot: &mut Option<String>, contract_name: &str, properties: &mut PropertyChecks) {
+        let Some(snapshot_id) = snapshot.take() else {
+            return;
+        };
+        match self.executor.revert_to_snapshot(&snapshot_id).await {
+            Ok(()) => self.resync_properties(contract_name, properties).await,
+            Err(e) => warn!("Could not restore fork after an iteration, its state carries over: {}", e),
+        }
+    }
+
+    /// Replay earlier passing calls on a snapshot, restoring the chain afterwards. Returns the shared
+    /// revert reason when they all revert the same way, i.e. the contract has been bricked.
+    async fn probe_bricking(&mut self, probe_calls: &[(String, &ContractMethod, Vec<SolidityValue>, U256)], contract: &ContractInfo) -> Option<String> {
+        if probe_calls.len() < bricking::MIN_PROBES {
+            return None;
+        }
+        let snapshot_id = match self.executor.snapshot().await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("Could not snapshot fork for bricking probe: {}", e);
+                return None;
+            }
+        };
+
+        let mut results = Vec::new();
+        for (sender, method, args, value) in probe_calls {
+            self.executor.set_sender_address(sender);
+            self.executor.set_value(*value);
+            results.push(self.execute_test_case_evm(method, args, contract).await);
+        }
+        self.executor.set_value(U256::zero());
+
+        if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+            warn!("Could not restore fork after bricking probe: {}", e);
+            return None;
+        }
+
+        bricking::uniform_revert(&results)
+    }
+
+    /// Run an input on a snapshot under a different block context, restoring the chain and the
+    /// block context afterwards.
+    /// Returns `None` when the probe could not be carried out.
+    async fn probe_block_context(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo, block_context: &BlockContext) -> Option<TestResult> {
+        let snapshot_id = match self.executor.snapshot().await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("Could not snapshot fork for block-context probe: {}", e);
+                return None;
+            }
+        };
+
+        let result = match self.executor.set_block_context(block_context).await {
+            Ok(()) => Some(self.execute_test_case_evm(method, args, contract).await),
+            Err(e) => {
+                warn!("Could not apply block context [{}]: {:#}", block_context, e);
+                None
+            }
+        };
+
+        if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+            warn!("Could not restore fork after block-context probe: {}", e);
+            return None;
+        }
+        if let Err(e) = self.executor.reset_block_context().await {
+            warn!("Could not restore block context after block-context probe: {:#}", e);
+            return None;
+        }
+
+        result
+    }
+
+    /// Run an input twice on snapshots, with the default gas limit and with `permille` of the gas it
+    /// used, restoring the chain afterwards. Returns the getters whose values differ when both runs
+    /// succeed: a nested call ran out of gas but the transaction completed without it.
+    async fn probe_gas_limit(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo, abi: &ethers::abi::Abi, permille: u64) -> Option<GasProbe> {
+        let snapshot_id = match self.executor.snapshot().await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("Could not snapshot fork for gas-limit probe: {}", e);
+                return None;
+            }
+        };
+        let (result, gas_used) = self.execute_test_case_metered(method, args, contract).await;
+        let full_gas_state = match result {
+            TestResult::Passed => Some(fingerprint::sample(self.executor.as_mut(), &contract.name, abi).await),
+            _ => None,
+        };
+        if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+            warn!("Could not restore fork after gas-limit probe: {}", e);
+            return None;
+        }
+        let full_gas_state = full_gas_state.filter(|state| !state.values.is_empty())?;
+
+        let snapshot_id = match self.executor.snapshot().await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("Could not snapshot fork for gas-limit probe: {}", e);
+                return None;
+            }
+        };
+        // Below the intrinsic cost the node rejects the transaction outright
+        let gas_limit = (gas_used * permille / 1000).max(MIN_GAS_LIMIT);
+        self.executor.set_gas_limit(Some(gas_limit));
+        let (result, _) = self.execute_test_case_metered(method, args, contract).await;
+        self.executor.set_gas_limit(None);
+        let reduced_gas_state = match result {
+            TestResult::Passed => Some(fingerprint::sample(self.executor.as_mut(), &contract.name, abi).await),
+            _ => None,
+        };
+        if let Err(e) = self.executor.revert_to_snapshot(&snapshot_id).await {
+            warn!("Could not restore fork after gas-limit probe: {}", e);
+            return None;
+        }
+
+        let changes = fingerprint::diff(&contract.name, &full_gas_state, &reduced_gas_state?);
+        (!changes.is_empty()).then_some(GasProbe { gas_limit, gas_used, changes })
+    }
+
+    /// Execute test case using Anvil fork
+    async fn execute_test_case_evm(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo) -> TestResult {
+        self.execute_test_case_metered(method, args, contract).await.0
+    }
+
+    /// Execute a test case and also return the gas it used (0 when nothing was executed)
+    async fn execute_test_case_metered(&mut self, method: &ContractMethod, args: &[SolidityValue], contract: &ContractInfo) -> (TestResult, u64) {
+        let call = match self.encode_call(method, args) {
+            Ok(call) => call,
+            Err(e) => {
+                return (TestResult::Error(e.to_string()), 0);
+            }
+        };
+
+        let (result, gas_used) = Self::execute_call_metered(self.executor.as_mut(), &contract.name, &call).await;
+        let result = self.observe_call(method, contract, &call, result, gas_used);
+        (self.observe_events(contract, args, result).await, gas_used)
+    }
+
+    /// Execute planned iterations of a method as one batch (a single Multicall3 transaction on Anvil),
+    /// returning each plan with its outcome. Plans with unsupported parameters get no outcome.
+    async fn execute_batch_metered(&mut self, method: &ContractMethod, plans: Vec<generator::IterationPlan>, contract: &ContractInfo) -> Vec<(generator::IterationPlan, Option<(TestResult, u64)>)> {
+        let mut outcomes = vec![None; plans.len()];
+        let mut calls = Vec::new();
+        let mut call_indices = Vec::new();
+        for (index, plan) in plans.iter().enumerate() {
+            if plan.args.iter().any(|arg| matches!(arg, SolidityValue::String(ref s) if s == "default")) {
+                continue;
+            }
+            match self.encode_call(method, &plan.args) {
+                Ok(call) => {
+                    calls.push(call);
+                    call_indices.push(index);
+                }
+                Err(e) => outcomes[index] = Some((TestResult::Error(e.to_string()), 0)),
+            }
+        }
+
+        if let Some(&first) = call_indices.first() {
+            self.executor.set_sender(plans[first].sender_index);
+            self.executor.set_value(U256::zero());
+            match self.executor.call_methods_batched(&contract.name, &calls).await {
+                Ok(results) => {
+                    for ((&index, call), execution_result) in call_indices.iter().zip(&calls).zip(results) {
+                        let gas_used = execution_result.gas_used;
+                        let result = self.observe_call(method, contract, call, Self::classify(execution_result), gas_used);
+                        outcomes[index] = Some((result, gas_used));
+                    }
+                }
+                Err(e) => {
+                    for &index in &call_indices {
+                        outcomes[index] = Some((TestResult::Error(format!("EVM execution failed: {:#}", e)), 0));
+                    }
+                }
+            }
+        }
+
+        plans.into_iter().zip(outcomes).collect()
+    }
+
+    /// Trace an executed exploration call and let the outcome hook turn it into a failure
+    fn observe_call(&mut self, method: &ContractMethod, contract: &ContractInfo, call: &(String, Vec<u8>), mut result: TestResult, gas_used: u64) -> TestResult {
+        let sender = self.executor.current_sender().to_string();
+        let record = TraceRecord::new(TracePhase::Explore, &contract.name, &sender, method, call, &result, gas_used);
+        if let Some(violation) = self.check_outcome_hook(&record) {
+            result = TestResult::Failed(format!("Hook oracle: {}", violation));
+        }
+        self.trace(record);
+        result
+    }
+
+    /// Let the event oracle (`--events`) turn a passing call sent on its own into a failure
+    async fn observe_events(&mut self, contract: &ContractInfo, args: &[SolidityValue], result: TestResult) -> TestResult {
+        if !matches!(result, TestResult::Passed) || !(self.config.events || tracing::enabled!(tracing::Level::DEBUG)) {
+            return result;
+        }
+        match Self::event_violation(self.executor.as_mut(), &self.event_decoder, &contract.name, args).await {
+            Some(violation) if self.config.events => TestResult::Failed(format!("{} {}", events::EVENT_ORACLE, violation)),
+            _ => result,
+        }
+    }
+
+    /// The first suspicious event emitted by the call `executor` just sent. Every event it decodes
+    /// is logged at debug level, so `--verbose` shows what each call emitted before a failure.
+    async fn event_violation(executor: &mut dyn Executor, decoder: &EventDecoder, contract_name: &str, args: &[SolidityValue]) -> Option<String> {
+        let logs = match executor.last_call_logs().await {
+            Ok(logs) => logs,
+            Err(e) => {
+                warn!("Could not read the logs of the last call: {:#}", e);
+                return None;
+            }
+        };
+        let decoded: Vec<events::DecodedEvent> = logs.iter().filter_map(|log| decoder.decode(log)).collect();
+        if !decoded.is_empty() {
+            debug!("📣 {} call from {} emitted {}", contract_name, executor.current_sender(),
+                decoded.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "));
+        }
+        let sender = executor.current_sender();
+        decoded.iter().find_map(|event| events::violation(event, sender, args))
+    }
+
+    /// Plan of one iteration: a mutation of a corpus input when coverage guidance or `--mutate`
+    /// passes the method's corpus, else generated values overridden by the value hook and the
+    /// configured value strategies
+    fn plan_iteration(&mut self, contract_name: &str, method: &ContractMethod, iteration_seed: u64, plan_options: &PlanOptions, corpus: Option<&Corpus>) -> generator::IterationPlan {
+        let plan_options = PlanOptions { payable: method.mutability == StateMutability::Payable, ..*plan_options };
+        let mutation = corpus.map(|corpus| CorpusMutation { corpus });
+        let strategies = mutation.iter().map(|mutation| mutation as &dyn Strategy).chain([&RandomGeneration as &dyn Strategy]);
+        let (strategy, mut plan) = strategies
+            .filter_map(|strategy| Some((strategy.name(), strategy.plan(&method.parameters, iteration_seed, &self.generator_context, &plan_options)?)))
+            .next()
+            .expect("random generation plans every iteration");
+        if strategy == RandomGeneration.name() {
+            self.apply_value_hook(method, iteration_seed, &mut plan);
+            self.apply_value_strategies(contract_name, method, iteration_seed, &mut plan);
+        }
+        plan
+    }
+
+    /// Use imported arguments instead of the generated ones; sender, fees and block context stay as planned
+    fn import_args(mut plan: generator::IterationPlan, imported: Option<&(Vec<SolidityValue>, ArgProvenance)>) -> generator::IterationPlan {
+        if let Some((args, provenance)) = imported {
+            plan.provenance = vec![*provenance; args.len()];
+            plan.args = args.clone();
+        }
+        plan
+    }
+
+    /// Index of the method to fuzz next: the `fuzzhead_next_method` hook's choice, else declaration order
+    fn next_method_index(&mut self, contract_name: &str, remaining: &[&ContractMethod]) -> usize {
+        let Some(hooks) = self.hooks.as_mut() else {
+            return 0;
+        };
+        let signatures: Vec<String> = remaining.iter().map(|method| abi_encoding::method_signature(method)).collect();
+        match hooks.next_method(contract_name, &signatures) {
+            Ok(choice) => choice.unwrap_or(0),
+            Err(e) => {
+                warn!("fuzzhead_next_method hook failed, keeping declaration order: {:#}", e);
+                0
+            }
+        }
+    }
+
+    /// Replace generated arguments with values from the `fuzzhead_generate_value` hook.
+    /// Each parameter gets its own seed derived from the iteration seed, so runs stay reproducible.
+    fn apply_value_hook(&mut self, method: &ContractMethod, iteration_seed: u64, plan: &mut generator::IterationPlan) {
+        let Some(hooks) = self.hooks.as_mut() else {
+            return;
+        };
+        for (index, param) in method.parameters.iter().enumerate() {
+            let seed = generator::derive_seed(iteration_seed, &param.name);
+            match hooks.generate_value(&param.param_type, seed) {
+                Ok(Some(value)) => {
+                    plan.args[index] = value;
+                    plan.provenance[index] = ArgProvenance::Hook;
+                }
+                Ok(None) => {}
+                Err(e) => warn!("fuzzhead_generate_value hook failed for {} {}: {:#}", method.name, param.name, e),
+            }
+        }
+    }
+
+    /// The configured strategies of a method's parameters, warning about those that cannot
+    /// generate the parameter's type
+    fn print_value_strategies(&self, contract_name: &str, method: &ContractMethod) {
+        for (index, param) in method.parameters.iter().enumerate() {
+            let Some(strategy) = self.config.value_strategy(contract_name, method, index) else {
+                continue;
+            };
+            if strategy.strategy.supports(&param.param_type) {
+                println!("  🎯 {} from {}", param.name, strategy);
+            } else {
+                warn!("Strategy {} does not fit {} {} of {}, its values are generated as usual",
+                    strategy, param.param_type.abi_type_string(), param.name, method.name);
+            }
+        }
+    }
+
+    /// Replace generated arguments with values of the parameters' configured strategies, each
+    /// drawn from its own seed derived from the iteration seed
+    fn apply_value_strategies(&self, contract_name: &str, method: &ContractMethod, iteration_seed: u64, plan: &mut generator::IterationPlan) {
+        for (index, param) in method.parameters.iter().enumerate() {
+            let Some(strategy) = self.config.value_strategy(contract_name, method, index) else {
+                continue;
+            };
+            let seed = generator::derive_seed(iteration_seed, &format!("strategy.{}", index));
+            if let Some(value) = strategy.draw(seed, &param.param_type) {
+                plan.args[index] = value;
+                plan.provenance[index] = ArgProvenance::Configured;
+            }
+        }
+    }
+
+    /// Violation reported by the `fuzzhead_check_outcome` hook for an executed call.
+    /// Infrastructure errors are not passed to the hook.
+    fn check_outcome_hook(&mut self, record: &TraceRecord) -> Option<String> {
+        let hooks = self.hooks.as_mut()?;
+        if record.outcome == "error" {
+            return None;
+        }
+        let call = HookCall {
+            contract: &record.contract,
+            method: &record.method,
+            sender: &record.sender,
+            args: record.args.clone(),
+            outcome: record.outcome,
+            error: record.error.as_deref(),
+            gas_used: record.gas_used,
+        };
+        match hooks.check_outcome(&call) {
+            Ok(violation) => violation,
+            Err(e) => {
+                warn!("fuzzhead_check_outcome hook failed for {}: {:#}", record.method, e);
+                None
+            }
+        }
+    }
+
+    fn trace(&mut self, record: TraceRecord) {
+        let Some(tracer) = self.tracer.as_mut() else {
+            return;
+        };
+        if let Err(e) = tracer.record(&record) {
+            // A full disk should not abort the campaign; stop tracing instead
+            warn!("Transaction tracing disabled: {:#}", e);
+            self.tracer = None;
+        }
+    }
+
+    /// Build the method signature and ABI-encoded arguments for a call.
+    /// With `verify_encoding` the arguments are decoded again and compared, so encoder bugs
+    /// surface as tool errors instead of reaching the contract as garbage calldata.
+    fn encode_call(&self, method: &ContractMethod, args: &[SolidityValue]) -> Result<(String, Vec<u8>), anyhow::Error> {
+        let method_signature = abi_encoding::method_signature(method);
+        let encoded_args = abi_encoding::encode_args(args)
+            .map_err(|e| anyhow::anyhow!("ABI encoding failed: {}", e))?;
+        if self.config.verify_encoding {
+            let types: Vec<SolidityType> = method.parameters.iter().map(|p| p.param_type.clone()).collect();
+            abi_encoding::verify_round_trip(&types, args, &encoded_args)
+                .map_err(|e| anyhow::anyhow!("ABI round-trip check failed for {}: {}", method_signature, e))?;
+        }
+        Ok((method_signature, encoded_args))
+    }
+
+    /// Send an encoded call through the given executor, classify the outcome and return the gas used by the transaction
+    async fn execute_call_metered(executor: &mut dyn Executor, contract_name: &str, call: &(String, Vec<u8>)) -> (TestResult, u64) {
+        let start_time = Instant::now();
+        let (method_signature, encoded_args) = call;
+
+        // Execute on Anvil fork - fail loudly if execution fails
+        match executor.call_method(contract_name, method_signature, encoded_args).await {
+            Ok(execution_result) => {
+                let _execution_time = start_time.elapsed();
+                let gas_used = execution_result.gas_used;
+                (Self::classify(execution_result), gas_used)
+            }
+            Err(e) => {
+                // Fail loudly - no fallback to simulation - but keep infrastructure problems out of the failure count
+                (TestResult::Error(format!("EVM execution failed: {:#}", e)), 0)
+            }
+        }
+    }
+    
+    /// A call that was carried out either passed or failed with its revert reason
+    fn classify(execution_result: MethodExecutionResult) -> TestResult {
+        if execution_result.success {
+            TestResult::Passed
+        } else {
+            let error_msg = execution_result.error
+                .unwrap_or_else(|| "Execution failed".to_string());
+            TestResult::Failed(error_msg)
+        }
+    }
+
+    /// Which strategy generated each argument, e.g. `_to: known account, _value: boundary`
+    fn format_provenance(method: &ContractMethod, provenance: &[ArgProvenance]) -> String {
+        method.parameters.iter()
+            .zip(provenance)
+            .map(|(param, provenance)| format!("{}: {}", param.name, provenance))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Format arguments for human-readable display in error messages
+    /// Calls of a stateful sequence in order, e.g. `deposit(100) from 0x…, withdraw(5) from 0x…`
+    /// Wei to send for a planned `msg.value`. A sender sending its whole balance keeps enough to
+    /// pay for the gas of the call at its fees.
+    async fn resolve_call_value(&mut self, value: Option<CallValue>, fees: Option<TxFees>) -> U256 {
+        match value {
+            None => U256::zero(),
+            Some(CallValue::Wei(wei)) => wei,
+            Some(CallValue::SenderBalance) => {
+                let sender = self.executor.current_sender().to_string();
+                let fee_per_gas = fees.map_or(VALUE_RESERVE_FEE_PER_GAS, |fees| fees.max_fee_per_gas);
+                let reserve = U256::from(VALUE_RESERVE_GAS).saturating_mul(U256::from(fee_per_gas));
+                match self.executor.account_balance(&sender).await {
+                    Ok(balance) => balance.saturating_sub(reserve),
+                    Err(e) => {
+                        warn!("Could not read the balance of {}, sending no value: {:#}", sender, e);
+                        U256::zero()
+                    }
+                }
+            }
+        }
+    }
+
+    fn format_sequence(&self, calls: &[SequenceCall]) -> String {
+        calls.iter()
+            .map(|call| {
+                let name = call.signature.split('(').next().unwrap_or(&call.signature);
+                let name = match &call.contract {
+                    Some(contract) => format!("{}.{}", contract, name),
+                    None => name.to_string(),
+                };
+                let value = if call.value.is_zero() { String::new() } else { format!(" with {} wei", call.value) };
+                let cheats = if call.cheats.is_empty() { String::new() } else { format!("[{}] ", Self::format_cheats(&call.cheats)) };
+                format!("{}{}({}) from {}{}", cheats, name, self.format_args_for_display(&call.args), call.sender, value)
+            })
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    /// Apply recorded cheats before replaying the call they preceded
+    async fn apply_cheats(executor: &mut dyn Executor, cheats: &[Cheat]) {
+        for cheat in cheats {
+            if let Err(e) = executor.apply_cheat(cheat).await {
+                warn!("Could not apply cheat [{}]: {:#}", cheat, e);
+            }
+        }
+    }
+
+    fn format_cheats(cheats: &[Cheat]) -> String {
+        cheats.iter().map(Cheat::to_string).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Suffix of the stateful phase headers when cheats or time jumps are on
+    fn cheats_note(&self) -> &'static str {
+        match (self.config.cheats, self.config.time_jumps) {
+            (true, true) => ", jumping ahead in time before each sequence and warping time, mining blocks and dealing ETH between calls",
+            (true, false) => ", warping time, mining blocks and dealing ETH between calls",
+            (false, true) => ", jumping ahead in time before each sequence",
+            (false, false) => "",
+        }
+    }
+
+    /// With `--time-jumps`, draw the warp and roll a sequence starts with and apply them. The
+    /// applied ones are returned as the first pending cheats, so the sequence's first recorded
+    /// call or finding carries them into its reproductions.
+    async fn apply_time_jump(&mut self, sequence_seed: u64) -> Vec<Cheat> {
+        if !self.config.time_jumps {
+            return Vec::new();
+        }
+        let mut applied = Vec::new();
+        for cheat in generator::generate_time_jump(sequence_seed) {
+            match self.executor.apply_cheat(&cheat).await {
+                Ok(()) => applied.push(cheat),
+                Err(e) => warn!("Could not apply time jump [{}]: {:#}", cheat, e),
+            }
+        }
+        applied
+    }
+
+    /// With `--cheats`, draw the cheats of one sequence call and apply them, adding them to
+    /// `pending` and the accounts they dealt ETH to to `dealt`
+    async fn apply_sequence_cheats(&mut self, call_seed: u64, pending: &mut Vec<Cheat>, dealt: &mut Vec<String>) {
+        if !self.config.cheats {
+            return;
+        }
+        for cheat in generator::generate_cheats(call_seed, self.executor.accounts()) {
+            if let Err(e) = self.executor.apply_cheat(&cheat).await {
+                warn!("Could not apply cheat [{}]: {:#}", cheat, e);
+                continue;
+            }
+            if let Cheat::Deal { account, .. } = &cheat {
+                dealt.push(account.clone());
+            }
+            pending.push(cheat);
+        }
+    }
+
+    fn format_args_for_display(&self, args: &[SolidityValue]) -> String {
+        display::format_args(args, self.config.max_arg_display_len)
+    }
+
+}
+/// Canonical signatures of the functions callable on a contract, from the compiled ABI or,
+/// when there is none (dry run without a compiler), from the parsed source
+fn external_signatures(contract: &ContractInfo, abi: &ethers::abi::Abi) -> Vec<String> {
+    if abi.functions().next().is_some() {
+        return abi.functions()
+            .map(|function| {
+                let inputs: Vec<String> = function.inputs.iter().map(|input| input.kind.to_string()).collect();
+                format!("{}({})", function.name, inputs.join(","))
+            })
+            .collect();
+    }
+
+    contract.methods.iter()
+        .filter(|method| {
+            (method.visibility == MethodVisibility::Public || method.visibility == MethodVisibility::External)
+            && !method.is_constructor
+            && !method.is_fallback
+            && !method.is_receive
+        })
+        .map(abi_encoding::method_signature)
+        .collect()
+}
+
+/// A compiled and deployed target contract
+struct DeployedTarget {
+    bytecode: Vec<u8>,
+    /// Empty when a dry run continued without compiling
+    abi: ethers::abi::Abi,
+    /// `None` when a dry run continued without compiling
+    compile_info: Option<CompileInfo>,
+    constructor_args: Option<Vec<u8>>,
+    /// Already on the chain (`--target-address`), not deployed by the campaign
+    attached: bool,
+    /// Libraries deployed for the bytecode, which is linked against their addresses
+    libraries: Vec<DeployedLibrary>,
+}
+
+/// The implementation a proxy of the campaign forwards its calls to
+struct ProxyImplementation {
+    contract: ContractInfo,
+    /// Its bytecode, for the dictionary
+    code: Vec<u8>,
+}
+
+/// A library deployed for a target, with the runtime code it got
+struct DeployedLibrary {
+    name: String,
+    source_path: std::path::PathBuf,
+    address: String,
+    code: Vec<u8>,
+}
+
+/// Compile-time check that a fuzzer and its campaign future can be moved onto other tokio tasks
+#[allow(dead_code)]
+fn assert_fuzzer_is_send(fuzzer: SolidityFuzzer) {
+    fn is_send<T: Send>(_: T) {}
+    is_send(async move {
+        let mut fuzzer = fuzzer;
+        let _ = fuzzer.fuzz_contract("", "").await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_executor::MockResponse;
+
+    fn withdraw_target() -> LiveTarget {
+        let abi: ethers::abi::Abi = serde_json::from_str(r#"[{
+            "type": "function", "name": "withdraw", "stateMutability": "nonpayable",
+            "inputs": [{"name": "amount", "type": "uint256"}], "outputs": []
+        }]"#).unwrap();
+        LiveTarget { name: "Vault".to_string(), address: "0x00000000000000000000000000000000000000aa".to_string(), abi }
+    }
+
+    /// A fuzzer whose every call reverts with `reason`, one run per method and no replays
+    fn reverting_fuzzer(reason: &str) -> SolidityFuzzer {
+        let mut executor = MockExecutor::new();
+        for _ in 0..16 {
+            executor.push_response(MockResponse::Revert(reason.to_string()));
+        }
+        let config = FuzzConfig {
+            seed: Some(7),
+            runs_per_method: 1,
+            confirm_runs: 0,
+            shrink_runs: 0,
+            ..FuzzConfig::default()
+        };
+        SolidityFuzzer::with_executor(config, Box::new(executor))
+    }
+
+    #[tokio::test]
+    async fn scripted_revert_becomes_a_finding() {
+        let mut fuzzer = reverting_fuzzer("insufficient balance");
+        let summary = fuzzer.fuzz_live(&withdraw_target()).await.unwrap();
+
+        assert_eq!(summary.total_failed, 1);
+        assert_eq!(summary.findings.len(), 1);
+        let finding = &summary.findings[0];
+        assert_eq!(finding.contract, "Vault");
+        assert_eq!(finding.signature, "withdraw(uint256)");
+        assert!(finding.error.contains("insufficient balance"), "{}", finding.error);
+        assert_eq!(finding.args.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn expected_revert_is_not_reported() {
+        let mut fuzzer = reverting_fuzzer("Ownable: caller is not the owner");
+        let summary = fuzzer.fuzz_live(&withdraw_target()).await.unwrap();
+
+        assert_eq!(summary.total_expected_reverts, 1);
+        assert_eq!(summary.total_failed, 0);
+        assert!(summary.findings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rpc_error_is_counted_as_an_error_not_a_finding() {
+        let mut executor = MockExecutor::new();
+        executor.push_response(MockResponse::RpcError("connection reset".to_string()));
+        let config = FuzzConfig { seed: Some(7), runs_per_method: 1, confirm_runs: 0, ..FuzzConfig::default() };
+        let mut fuzzer = SolidityFuzzer::with_executor(config, Box::new(executor));
+        let summary = fuzzer.fuzz_live(&withdraw_target()).await.unwrap();
+
+        assert_eq!(summary.total_errors, 1);
+        assert!(summary.findings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn payable_failure_is_reported_when_multicall_is_on() {
+        let abi: ethers::abi::Abi = serde_json::from_str(r#"[{
+            "type": "function", "name": "deposit", "stateMutability": "payable",
+            "inputs": [{"name": "amount", "type": "uint256"}], "outputs": []
+        }]"#).unwrap();
+        let target = LiveTarget { name: "Vault".to_string(), address: "0x00000000000000000000000000000000000000aa".to_string(), abi };
+        // Only the fuzzed call reverts: a confirmation replay would succeed and drop the failure as batch-only
+        let mut executor = MockExecutor::new();
+        executor.push_response(MockResponse::Revert("paused".to_string()));
+        let config = FuzzConfig { seed: Some(7), runs_per_method: 1, confirm_runs: 0, shrink_runs: 0, multicall_batch: 4, ..FuzzConfig::default() };
+        let mut fuzzer = SolidityFuzzer::with_executor(config, Box::new(executor));
+        let summary = fuzzer.fuzz_live(&target).await.unwrap();
+
+        assert_eq!(summary.total_failed, 1);
+        assert_eq!(summary.findings.len(), 1);
+        assert_eq!(summary.findings[0].confirmation_runs, 0);
+    }
+}
@@ -0,0 +1,54 @@
+//! Gas profiling. Every fuzzed call's gas goes into its method's min/mean/max in `MethodStats`;
+//! a call that used more than `--gas-threshold`, or all of the gas it was sent with, is flagged.
+//! Gas that grows with the arguments or with earlier calls (a loop over an array anyone can push
+//! to) is how a method ends up exceeding the block gas limit and becoming uncallable.
+
+use crate::types::MethodStats;
+use serde::{Deserialize, Serialize};
+
+/// The costliest call of a method that went over the threshold or ran out of gas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasAlert {
+    pub contract: String,
+    /// Canonical signature, e.g. `distribute(uint256)`
+    pub method: String,
+    pub iteration: usize,
+    /// Seed of the iteration, so `replay` can send the call again
+    pub seed: u64,
+    pub gas_used: u64,
+    /// The threshold the call went over, or the gas limit it used up
+    pub limit: u64,
+    /// Used all the gas it was sent with rather than only going over the threshold
+    pub exhausted: bool,
+}
+
+impl std::fmt::Display for GasAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exhausted {
+            write!(f, "{}.{} used all {} gas it was sent with on iteration {} (unbounded loop or gas griefing?)",
+                self.contract, self.method, self.limit, self.iteration)
+        } else {
+            write!(f, "{}.{} used {} gas on iteration {}, over the {} gas threshold",
+                self.contract, self.method, self.gas_used, self.iteration, self.limit)
+        }
+    }
+}
+
+/// Whether a call that used `gas_used` out of `gas_limit` is flagged: `Some((limit, exhausted))`
+/// with the limit it hit
+pub fn check(gas_used: u64, gas_limit: u64, threshold: Option<u64>) -> Option<(u64, bool)> {
+    if gas_used >= gas_limit {
+        return Some((gas_limit, true));
+    }
+    threshold.filter(|threshold| gas_used > *threshold).map(|threshold| (threshold, false))
+}
+
+/// Gas per call of each method of a contract that sent any, as printed with `--gas-report`
+pub fn table(stats: &[MethodStats]) -> Vec<String> {
+    let rows: Vec<&MethodStats> = stats.iter().filter(|stats| stats.calls > 0).collect();
+    let width = rows.iter().map(|stats| stats.method.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|stats| format!("{:<width$}  {:>10} {:>10} {:>10}  ({} calls)",
+            stats.method, stats.gas_min, stats.gas_mean(), stats.gas_max, stats.calls, width = width))
+        .collect()
+}
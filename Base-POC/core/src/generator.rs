@@ -0,0 +1,575 @@
+use crate::address_pool::{self, AddressWeights};
+use crate::dictionary::Dictionary;
+use crate::precompiles::SystemAddress;
+use crate::types::{ArgProvenance, BlockContext, Cheat, MethodParameter, SolidityType, SolidityValue, TxFees};
+use ethers::types::U256;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha3::{Digest, Keccak256};
+
+/// Campaign facts the generator draws interesting values from
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorContext {
+    /// Addresses the executor sends from (funded and possibly privileged)
+    pub known_addresses: Vec<String>,
+    /// Precompiles and system contracts of the chain the campaign runs on
+    pub system_addresses: Vec<SystemAddress>,
+    /// Reentrancy attacker deployed next to the target (`--reentrancy`)
+    pub attacker_address: Option<String>,
+    /// Every contract deployed from the file under test, the target included
+    pub contract_addresses: Vec<String>,
+    /// Contracts found behind the address getters of the deployed ones
+    pub discovered_addresses: Vec<String>,
+    /// How often each source of the address pool is drawn
+    pub address_weights: AddressWeights,
+    /// Constants of the contract under test
+    pub dictionary: Dictionary,
+}
+
+/// Everything random about one fuzz iteration. It depends only on the iteration seed, so it is
+/// `Send`, can be computed on any task, and can be regenerated later from a report.
+#[derive(Debug, Clone)]
+pub struct IterationPlan {
+    pub seed: u64,
+    pub args: Vec<SolidityValue>,
+    /// Strategy behind each argument, in parameter order
+    pub provenance: Vec<ArgProvenance>,
+    /// Index into the executor's account list (`context.known_addresses`)
+    pub sender_index: usize,
+    /// Fuzzed EIP-1559 fees, when fee fuzzing is on
+    pub fees: Option<TxFees>,
+    /// Block environment to run under, when block-context fuzzing is on
+    pub block_context: Option<BlockContext>,
+    /// A different block environment for the differential re-run that detects miner-manipulable branches
+    pub alt_block_context: Option<BlockContext>,
+    /// Re-run the input with a gas limit of this many per mille of the gas it used, when gas-limit fuzzing picked it
+    pub gas_limit_permille: Option<u64>,
+    /// ETH sent with the call, for payable methods
+    pub value: Option<CallValue>,
+}
+
+/// `msg.value` of a call to a payable method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallValue {
+    Wei(U256),
+    /// Everything the sender holds, less a reserve for gas; only known once the sender is
+    SenderBalance,
+}
+
+/// Which optional dimensions an iteration plan fuzzes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlanOptions {
+    /// Base fee to draw EIP-1559 fees around (fee fuzzing is off when `None`)
+    pub fee_base: Option<u128>,
+    pub block_context: bool,
+    /// Occasionally probe the input under a gas limit near its observed usage
+    pub gas_limit: bool,
+    /// Account index of the contract's impersonated owner and the percentage of iterations sent from it
+    pub owner_sender: Option<(usize, u32)>,
+    /// Draw a `msg.value` (the method is payable)
+    pub payable: bool,
+}
+
+/// One in this many iterations is probed under a reduced gas limit
+const GAS_PROBE_ONE_IN: u32 = 8;
+
+/// Draw the arguments, sender, fees and block context of one iteration from its seed
+pub fn plan_iteration(
+    parameters: &[MethodParameter],
+    seed: u64,
+    context: &GeneratorContext,
+    options: &PlanOptions,
+) -> IterationPlan {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let (args, provenance) = parameters.iter()
+        .map(|param| generate_value_with_provenance(&mut rng, &param.param_type, context))
+        .unzip();
+
+    // Rotate sender to test access control
+    // Bias towards non-owner accounts (70% chance) to catch access control issues
+    let num_accounts = context.known_addresses.len();
+    let mut sender_index = if num_accounts > 1 && rng.gen_range(0..100) < 70 {
+        rng.gen_range(1..num_accounts)
+    } else {
+        0
+    };
+
+    let fees = options.fee_base.map(|base_fee| generate_fees(&mut rng, base_fee));
+
+    let (block_context, alt_block_context) = if options.block_context {
+        let primary = generate_block_context(&mut rng, context);
+        let mut alternative = generate_block_context(&mut rng, context);
+        while alternative == primary {
+            alternative = generate_block_context(&mut rng, context);
+        }
+        (Some(primary), Some(alternative))
+    } else {
+        (None, None)
+    };
+
+    // Drawn last so enabling it leaves every other draw of the iteration unchanged
+    let gas_limit_permille = (options.gas_limit && rng.gen_range(0..GAS_PROBE_ONE_IN) == 0)
+        .then(|| generate_gas_limit_permille(&mut rng));
+
+    // Likewise drawn after everything else, and only for contracts with an impersonated owner
+    if let Some((owner_index, percent)) = options.owner_sender {
+        if rng.gen_range(0..100) < percent {
+            sender_index = owner_index;
+        }
+    }
+
+    // Drawn last of all, so the other draws do not depend on whether the method is payable
+    let value = options.payable.then(|| generate_call_value(&mut rng));
+
+    IterationPlan {
+        seed,
+        args,
+        provenance,
+        sender_index,
+        fees,
+        block_context,
+        alt_block_context,
+        gas_limit_permille,
+        value,
+    }
+}
+
+/// `msg.value` for a payable call: nothing, dust, exactly 1 ether, or the sender's whole balance
+pub fn generate_call_value<R: Rng + ?Sized>(rng: &mut R) -> CallValue {
+    const ETHER: u64 = 1_000_000_000_000_000_000;
+
+    match rng.gen_range(0..100) {
+        // 25% - No value, the path a non-payable caller takes
+        0..=24 => CallValue::Wei(U256::zero()),
+        // 25% - Dust, where rounding and minimum-deposit checks live
+        25..=49 => CallValue::Wei(U256::from(rng.gen_range(1..=1000u64))),
+        // 35% - One ether
+        50..=84 => CallValue::Wei(U256::from(ETHER)),
+        // 15% - Everything the sender has
+        _ => CallValue::SenderBalance,
+    }
+}
+
+/// Gas limit for an OOG probe, in per mille of the gas the input used. Most limits sit just
+/// around the usage, where a nested call can run out of gas (it only gets 63/64 of what is left)
+/// while the transaction itself still completes.
+pub fn generate_gas_limit_permille<R: Rng + ?Sized>(rng: &mut R) -> u64 {
+    match rng.gen_range(0..4) {
+        0 => rng.gen_range(850..950),
+        1 | 2 => rng.gen_range(950..1050),
+        _ => rng.gen_range(1050..=1200),
+    }
+}
+
+/// Random block environment: coinbase, next base fee and chain id
+pub fn generate_block_context<R: Rng + ?Sized>(rng: &mut R, context: &GeneratorContext) -> BlockContext {
+    const GWEI: u128 = 1_000_000_000;
+
+    let coinbase = match rng.gen_range(0..100) {
+        // 30% - Zero coinbase (default on many dev chains)
+        0..=29 => "0x0000000000000000000000000000000000000000".to_string(),
+        // 30% - One of our own accounts (the "miner" is a participant)
+        30..=59 if !context.known_addresses.is_empty() => {
+            context.known_addresses[rng.gen_range(0..context.known_addresses.len())].clone()
+        }
+        _ => format!("0x{:040x}", rng.gen::<u128>()),
+    };
+
+    let base_fee_per_gas = match rng.gen_range(0..4) {
+        0 => 0,
+        1 => 1,
+        2 => GWEI,
+        _ => rng.gen_range(1..=100) * GWEI,
+    };
+
+    // Mainnet, Base, Base Sepolia, Anvil's default and an arbitrary id (replay scenarios)
+    let chain_id = match rng.gen_range(0..5) {
+        0 => 1,
+        1 => 8453,
+        2 => 84532,
+        3 => 31337,
+        _ => rng.gen_range(1..=u32::MAX as u64),
+    };
+
+    BlockContext {
+        coinbase: Some(coinbase),
+        base_fee_per_gas: Some(base_fee_per_gas),
+        chain_id: Some(chain_id),
+    }
+}
+
+/// The jump in time and block number a stateful sequence starts with, drawn from the sequence's
+/// seed: a clock warp from a minute to four years, spread evenly across orders of magnitude so
+/// deadlines, auction ends and vesting cliffs are all hit as often as each other, followed by the
+/// blocks a 12-second chain would have mined meanwhile (at most a week of them). One sequence in
+/// ten starts without a jump, at the time the contract was left in.
+pub fn generate_time_jump(sequence_seed: u64) -> Vec<Cheat> {
+    const MAX_SECONDS: u64 = 4 * 365 * 24 * 60 * 60;
+    const MAX_BLOCKS: u64 = 7 * 7200;
+
+    let mut rng = StdRng::seed_from_u64(derive_seed(sequence_seed, "time"));
+    if rng.gen_range(0..100) < 10 {
+        return Vec::new();
+    }
+    let exponent = rng.gen_range((60f64).ln()..(MAX_SECONDS as f64).ln());
+    let seconds = (exponent.exp() as u64).clamp(60, MAX_SECONDS);
+    vec![
+        Cheat::Warp { seconds },
+        Cheat::Roll { blocks: (seconds / 12).clamp(1, MAX_BLOCKS) },
+    ]
+}
+
+/// Cheats to apply before one call of a stateful sequence, drawn from the call's seed on a stream
+/// of their own so the call itself is the same with or without them. Time jumps range from
+/// seconds to years, past the delays of timelocks, vesting schedules and interest accrual.
+/// Balances are only dealt to `senders`: the contract's own balance stays what its calls made it.
+pub fn generate_cheats(call_seed: u64, senders: &[String]) -> Vec<Cheat> {
+    const ETHER: u64 = 1_000_000_000_000_000_000;
+    const DAY: u64 = 24 * 60 * 60;
+
+    let mut rng = StdRng::seed_from_u64(derive_seed(call_seed, "cheats"));
+    let mut cheats = Vec::new();
+    // 20% - Warp
+    if rng.gen_range(0..100) < 20 {
+        let seconds = match rng.gen_range(0..6) {
+            0 => rng.gen_range(1..60),
+            1 => 60 * 60,
+            2 => DAY,
+            3 => 7 * DAY,
+            4 => 30 * DAY,
+            _ => rng.gen_range(1..=4) * 365 * DAY,
+        };
+        cheats.push(Cheat::Warp { seconds });
+    }
+    // 10% - Roll up to a day of 12-second blocks
+    if rng.gen_range(0..100) < 10 {
+        cheats.push(Cheat::Roll { blocks: rng.gen_range(1..=7200) });
+    }
+    // 10% - Deal: just enough for gas and a small deposit, or a whale's balance
+    if rng.gen_range(0..100) < 10 && !senders.is_empty() {
+        let account = senders[rng.gen_range(0..senders.len())].clone();
+        let wei = match rng.gen_range(0..2) {
+            0 => U256::from(ETHER),
+            _ => U256::from(ETHER) * U256::from(1_000_000u64),
+        };
+        cheats.push(Cheat::Deal { account, wei });
+    }
+    cheats
+}
+
+/// Generate a random value of the given type.
+/// Pure apart from the RNG it is handed, so the same seed always yields the same value.
+pub fn generate_value<R: Rng + ?Sized>(rng: &mut R, sol_type: &SolidityType, context: &GeneratorContext) -> SolidityValue {
+    generate_value_with_provenance(rng, sol_type, context).0
+}
+
+/// `generate_value`, also reporting which strategy produced the value
+pub fn generate_value_with_provenance<R: Rng + ?Sized>(rng: &mut R, sol_type: &SolidityType, context: &GeneratorContext) -> (SolidityValue, ArgProvenance) {
+    if let Some(value) = dictionary_value(rng, sol_type, &context.dictionary) {
+        return (value, ArgProvenance::Dictionary);
+    }
+    match sol_type {
+        SolidityType::Uint8 => (SolidityValue::Uint8(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Uint16 => (SolidityValue::Uint16(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Uint32 => (SolidityValue::Uint32(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Uint64 => (SolidityValue::Uint64(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Uint128 => (SolidityValue::Uint128(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Uint256 => {
+            let strategy = rng.gen_range(0..100);
+            let (val, provenance) = match strategy {
+                // 20% - Very small values (0-100) - good for: counters, indices, percentages, small IDs
+                0..=19 => (rng.gen_range(0..101), ArgProvenance::SmallRange),
+                // 20% - Small-medium values (100-100,000) - good for: amounts, IDs, array sizes
+                20..=39 => (rng.gen_range(100..100_001), ArgProvenance::MediumRange),
+                // 15% - Medium-large values (100k-10M) - good for: larger amounts, timestamps (recent years)
+                40..=54 => (rng.gen_range(100_000..10_000_001), ArgProvenance::MediumRange),
+                // 10% - Edge cases: boundaries that often cause bugs
+                55..=64 => {
+                    let val = match rng.gen_range(0..6) {
+                        0 => 0,                    // Minimum value
+                        1 => 1,                    // Smallest non-zero
+                        2 => 2,                    // Common threshold
+                        3 => u32::MAX as u128,     // 32-bit boundary
+                        4 => u64::MAX as u128,     // 64-bit boundary
+                        _ => u128::MAX,            // Maximum uint256 (2^256-1)
+                    };
+                    (val, ArgProvenance::Boundary)
+                },
+                // 15% - Powers of 2 (useful for: bit flags, sizes, testing overflow at boundaries)
+                65..=79 => {
+                    let power = rng.gen_range(0..256); // 2^0 to 2^255
+                    let val = if power < 128 {
+                        1u128 << power
+                    } else {
+                        // For powers > 127, use a large value close to max
+                        u128::MAX >> rng.gen_range(0..10)
+                    };
+                    (val, ArgProvenance::PowerOfTwo)
+                },
+                // 10% - Powers of 10 (useful for: decimal math, price calculations)
+                80..=89 => {
+                    let power = rng.gen_range(0..39); // 10^0 to 10^38 (uint256 max is ~10^77)
+                    let val = if power <= 18 {
+                        10u128.pow(power)
+                    } else {
+                        // For larger powers, use multiplier
+                        let base = rng.gen_range(1..1000);
+                        (base as u128) * 10u128.pow(18)
+                    };
+                    (val, ArgProvenance::PowerOfTen)
+                },
+                // 10% - Large random values (stress testing, overflow detection)
+                _ => (rng.gen::<u128>(), ArgProvenance::Uniform),
+            };
+            (SolidityValue::Uint256(val.to_string()), provenance)
+        },
+        SolidityType::Int8 => (SolidityValue::Int8(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Int16 => (SolidityValue::Int16(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Int32 => (SolidityValue::Int32(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Int64 => (SolidityValue::Int64(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Int128 => (SolidityValue::Int128(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::Int256 => {
+            // General-purpose signed integer generation
+            let strategy = rng.gen_range(0..100);
+            let (val, provenance) = match strategy {
+                // 25% - Small values around zero
+                0..=24 => (rng.gen_range(-100..101), ArgProvenance::SmallRange),
+                // 25% - Medium positive and negative values
+                25..=49 => (rng.gen_range(-100_000..100_001), ArgProvenance::MediumRange),
+                // 15% - Edge cases for signed integers
+                50..=64 => {
+                    let val = match rng.gen_range(0..6) {
+                        0 => 0,                       // Zero
+                        1 => 1,                       // Positive one
+                        2 => -1,                      // Negative one
+                        3 => i32::MAX as i128,        // 32-bit max
+                        4 => i32::MIN as i128,        // 32-bit min
+                        _ => i64::MAX as i128,        // 64-bit max
+                    };
+                    (val, ArgProvenance::Boundary)
+                },
+                // 15% - Negative boundary testing
+                65..=79 => {
+                    let positive = rng.gen_range(1..1_000_000);
+                    (-(positive as i128), ArgProvenance::MediumRange)
+                },
+                // 20% - Large random values (both positive and negative)
+                _ => (rng.gen::<i64>() as i128, ArgProvenance::Uniform),
+            };
+            (SolidityValue::Int256(val.to_string()), provenance)
+        },
+        SolidityType::Address => {
+            // Drawn from the weighted sources of the campaign's address pool
+            let (addr, provenance) = address_pool::generate(rng, context);
+            (SolidityValue::Address(addr), provenance)
+        },
+        SolidityType::Bool => (SolidityValue::Bool(rng.gen()), ArgProvenance::Uniform),
+        SolidityType::String => {
+            // Generate more realistic ASCII strings instead of random unicode
+            let length = rng.gen_range(0..50);
+            let chars: String = (0..length)
+                .map(|_| {
+                    // Printable ASCII characters (space to ~)
+                    (rng.gen_range(32..127)) as u8 as char
+                })
+                .collect();
+            (SolidityValue::String(chars), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes => {
+            // Smaller, more realistic byte arrays
+            let length = rng.gen_range(0..256);
+            let bytes: Vec<u8> = (0..length).map(|_| rng.gen()).collect();
+            (SolidityValue::Bytes(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Array(inner_type) => {
+            let length = rng.gen_range(0..10);
+            let values: Vec<SolidityValue> = (0..length)
+                .map(|_| generate_value(rng, inner_type, context))
+                .collect();
+            (SolidityValue::Array(values), ArgProvenance::Uniform)
+        },
+        SolidityType::FixedArray(inner_type, length) => {
+            let values: Vec<SolidityValue> = (0..*length)
+                .map(|_| generate_value(rng, inner_type, context))
+                .collect();
+            (SolidityValue::FixedArray(values), ArgProvenance::Uniform)
+        },
+        SolidityType::Struct(_, fields) => {
+            let mut provenance = ArgProvenance::Uniform;
+            let fields = fields.iter()
+                .map(|field| {
+                    let (value, field_provenance) = generate_value_with_provenance(rng, &field.param_type, context);
+                    // A struct is only as encodable as its least supported field
+                    if field_provenance == ArgProvenance::Unsupported {
+                        provenance = ArgProvenance::Unsupported;
+                    }
+                    (field.name.clone(), value)
+                })
+                .collect();
+            (SolidityValue::Struct(fields), provenance)
+        },
+        SolidityType::Bytes1 => {
+            let bytes: [u8; 1] = [rng.gen()];
+            (SolidityValue::Bytes1(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes2 => {
+            let bytes: [u8; 2] = [rng.gen(), rng.gen()];
+            (SolidityValue::Bytes2(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes4 => {
+            let bytes: [u8; 4] = [rng.gen(), rng.gen(), rng.gen(), rng.gen()];
+            (SolidityValue::Bytes4(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes8 => {
+            let bytes: [u8; 8] = [rng.gen(); 8];
+            (SolidityValue::Bytes8(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes16 => {
+            let bytes: [u8; 16] = [rng.gen(); 16];
+            (SolidityValue::Bytes16(bytes), ArgProvenance::Uniform)
+        },
+        SolidityType::Bytes32 => {
+            let bytes: [u8; 32] = [rng.gen(); 32];
+            (SolidityValue::Bytes32(bytes), ArgProvenance::Uniform)
+        },
+        _ => (SolidityValue::String("default".to_string()), ArgProvenance::Unsupported),
+    }
+}
+
+/// Share of values taken from the contract's constants, for types the dictionary has entries for
+const DICTIONARY_PERCENT: u32 = 15;
+
+/// A random entry, `DICTIONARY_PERCENT` of the time; no randomness is drawn when there are none
+fn choose<R: Rng + ?Sized, T: Clone>(rng: &mut R, entries: &[T]) -> Option<T> {
+    if entries.is_empty() || rng.gen_range(0..100) >= DICTIONARY_PERCENT {
+        return None;
+    }
+    Some(entries[rng.gen_range(0..entries.len())].clone())
+}
+
+/// Width in bits of the integer types and of the fixed-size byte arrays
+fn value_bits(sol_type: &SolidityType) -> Option<usize> {
+    let bits = match sol_type {
+        SolidityType::Uint8 | SolidityType::Bytes1 => 8,
+        SolidityType::Uint16 | SolidityType::Bytes2 => 16,
+        SolidityType::Uint32 | SolidityType::Bytes4 => 32,
+        SolidityType::Uint64 | SolidityType::Bytes8 => 64,
+        SolidityType::Uint128 | SolidityType::Bytes16 => 128,
+        SolidityType::Uint256 | SolidityType::Bytes32 => 256,
+        // Constants are non-negative, so signed types take them up to their positive maximum
+        SolidityType::Int8 => 7,
+        SolidityType::Int16 => 15,
+        SolidityType::Int32 => 31,
+        SolidityType::Int64 => 63,
+        SolidityType::Int128 => 127,
+        SolidityType::Int256 => 255,
+        _ => return None,
+    };
+    Some(bits)
+}
+
+/// The last `N` bytes of a word, i.e. a `bytesN` compared against a hex constant
+fn low_bytes<const N: usize>(word: U256) -> [u8; N] {
+    let mut buffer = [0u8; 32];
+    word.to_big_endian(&mut buffer);
+    buffer[32 - N..].try_into().expect("N is at most 32")
+}
+
+/// A constant of the contract that fits the type, or a neighbour of one (off-by-one comparisons)
+fn dictionary_value<R: Rng + ?Sized>(rng: &mut R, sol_type: &SolidityType, dictionary: &Dictionary) -> Option<SolidityValue> {
+    match sol_type {
+        SolidityType::Address => choose(rng, &dictionary.addresses).map(SolidityValue::Address),
+        SolidityType::String => choose(rng, &dictionary.strings).map(SolidityValue::String),
+        SolidityType::Bytes => {
+            let entries: Vec<Vec<u8>> = dictionary.byte_literals.iter().cloned()
+                .chain(dictionary.strings.iter().map(|string| string.as_bytes().to_vec()))
+                .collect();
+            choose(rng, &entries).map(SolidityValue::Bytes)
+        }
+        _ => {
+            let bits = value_bits(sol_type)?;
+            let words: Vec<U256> = dictionary.words.iter().filter(|word| word.bits() <= bits).copied().collect();
+            let mut word = choose(rng, &words)?;
+            match rng.gen_range(0..10) {
+                0 => word = word.saturating_sub(U256::one()),
+                1 if word.saturating_add(U256::one()).bits() <= bits => word += U256::one(),
+                _ => {}
+            }
+            let value = match sol_type {
+                SolidityType::Uint8 => SolidityValue::Uint8(word.low_u32() as u8),
+                SolidityType::Uint16 => SolidityValue::Uint16(word.low_u32() as u16),
+                SolidityType::Uint32 => SolidityValue::Uint32(word.low_u32()),
+                SolidityType::Uint64 => SolidityValue::Uint64(word.low_u64()),
+                SolidityType::Uint128 => SolidityValue::Uint128(word.low_u128()),
+                SolidityType::Int8 => SolidityValue::Int8(word.low_u32() as i8),
+                SolidityType::Int16 => SolidityValue::Int16(word.low_u32() as i16),
+                SolidityType::Int32 => SolidityValue::Int32(word.low_u32() as i32),
+                SolidityType::Int64 => SolidityValue::Int64(word.low_u64() as i64),
+                SolidityType::Int128 => SolidityValue::Int128(word.low_u128() as i128),
+                SolidityType::Uint256 => SolidityValue::Uint256(word.to_string()),
+                SolidityType::Int256 => SolidityValue::Int256(word.to_string()),
+                SolidityType::Bytes1 => SolidityValue::Bytes1(low_bytes(word)),
+                SolidityType::Bytes2 => SolidityValue::Bytes2(low_bytes(word)),
+                SolidityType::Bytes4 => SolidityValue::Bytes4(low_bytes(word)),
+                SolidityType::Bytes8 => SolidityValue::Bytes8(low_bytes(word)),
+                SolidityType::Bytes16 => SolidityValue::Bytes16(low_bytes(word)),
+                _ => SolidityValue::Bytes32(low_bytes(word)),
+            };
+            Some(value)
+        }
+    }
+}
+
+/// Random but valid EIP-1559 fees for a block with the given base fee. The priority fee covers
+/// edge cases (zero, one wei, typical and very large tips); the max fee always stays above the base fee.
+pub fn generate_fees<R: Rng + ?Sized>(rng: &mut R, base_fee: u128) -> TxFees {
+    const GWEI: u128 = 1_000_000_000;
+
+    let max_priority_fee_per_gas = match rng.gen_range(0..100) {
+        // 20% - No tip at all
+        0..=19 => 0,
+        // 10% - Smallest non-zero tip
+        20..=29 => 1,
+        // 40% - Typical tips
+        30..=69 => rng.gen_range(1..=3) * GWEI,
+        // 20% - High tips
+        70..=89 => rng.gen_range(4..=100) * GWEI,
+        // 10% - Extreme tips (large tx.gasprice, refund math)
+        _ => rng.gen_range(100..=10_000) * GWEI,
+    };
+    let headroom = [2u128, 3, 10][rng.gen_range(0..3)];
+
+    TxFees {
+        max_fee_per_gas: base_fee.saturating_mul(headroom).saturating_add(max_priority_fee_per_gas),
+        max_priority_fee_per_gas,
+    }
+}
+
+/// Derive an independent child seed from a parent seed and a label
+/// (e.g. a per-method seed from the campaign's master seed)
+pub fn derive_seed(parent: u64, label: &str) -> u64 {
+    let mut hasher = Keccak256::new();
+    hasher.update(parent.to_be_bytes());
+    hasher.update(label.as_bytes());
+    let hash = hasher.finalize();
+    u64::from_be_bytes(hash[..8].try_into().expect("keccak256 output is 32 bytes"))
+}
+
+/// Seed of one method of a contract. Methods are the campaign's units of work: everything random
+/// about a method derives from this seed alone, never from RNG state shared with other methods,
+/// so findings do not depend on the order (or concurrency) in which methods are fuzzed.
+pub fn method_seed(master_seed: u64, contract: &str, signature: &str) -> u64 {
+    derive_seed(master_seed, &format!("{}.{}", contract, signature))
+}
+
+/// Index of the method one call of a stateful sequence goes to, drawn from the call's seed
+pub fn sequence_method_index(call_seed: u64, method_count: usize) -> usize {
+    StdRng::seed_from_u64(derive_seed(call_seed, "method")).gen_range(0..method_count)
+}
+
+/// Seed for one (1-based) iteration of a method, so any single iteration can be regenerated
+/// without replaying the ones before it
+pub fn iteration_seed(method_seed: u64, iteration: usize) -> u64 {
+    derive_seed(method_seed, &format!("#{}", iteration))
+}
@@ -0,0 +1,233 @@
+//! Foundry fuzz test harnesses. A contract with `testFuzz*` functions is driven the way `forge test`
+//! drives it: `setUp()` runs once after deployment, then only the fuzz tests are called. Anvil and
+//! revm do not implement Foundry's cheatcodes, so a stand-in is placed at the cheatcode address:
+//! `vm.assume(false)` reverts with Foundry's rejection marker, counted as a skipped run, and the
+//! `vm.assert*` checks of value types revert with `assertion failed`, reported like any other
+//! failure. Every other cheatcode does nothing and returns a zero word.
+
+use crate::anvil_executor::calculate_selector;
+use crate::ast_parser::ContractInfo;
+use std::collections::HashMap;
+
+/// Name prefix of the functions run as fuzz tests
+pub const FUZZ_TEST_PREFIX: &str = "testFuzz";
+
+/// Where Foundry's cheatcodes live, `address(uint160(uint256(keccak256("hevm cheat code"))))`
+pub const CHEATCODE_ADDRESS: &str = "0x7109709ecfa91a80626ff3989d68f67f5b1dd12d";
+
+/// Revert reason of `vm.assume(false)`, as in Foundry
+pub const ASSUME_REJECTED: &str = "FOUNDRY::ASSUME";
+
+/// Revert reason of a failed `vm.assert*`
+const ASSERTION_FAILED: &str = "assertion failed";
+
+/// Signature of the function Foundry runs once before the tests
+pub const SET_UP_SIGNATURE: &str = "setUp()";
+
+pub fn is_fuzz_test(name: &str) -> bool {
+    name.starts_with(FUZZ_TEST_PREFIX)
+}
+
+/// Whether the contract is a Foundry fuzz harness: it has a `testFuzz*` function taking arguments
+pub fn is_harness(contract: &ContractInfo) -> bool {
+    contract.methods.iter().any(|method| is_fuzz_test(&method.name) && !method.parameters.is_empty())
+}
+
+/// Whether a revert is `vm.assume` turning the input away, by its reason or its raw data
+pub fn is_assume_rejection(error: &str) -> bool {
+    error.contains(ASSUME_REJECTED) || error.to_ascii_lowercase().contains(&hex::encode(ASSUME_REJECTED))
+}
+
+/// A cheatcode the stand-in checks: the arguments it reads and when they fail the check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Check {
+    Assume,
+    True,
+    False,
+    Eq,
+    NotEq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    SignedGt,
+    SignedGe,
+    SignedLt,
+    SignedLe,
+}
+
+const ISZERO: u8 = 0x15;
+const EQ: u8 = 0x14;
+const LT: u8 = 0x10;
+const GT: u8 = 0x11;
+const SLT: u8 = 0x12;
+const SGT: u8 = 0x13;
+
+impl Check {
+    fn operands(self) -> usize {
+        match self {
+            Check::Assume | Check::True | Check::False => 1,
+            _ => 2,
+        }
+    }
+
+    /// Opcodes turning the operands (first argument on top) into a nonzero word when the check fails
+    fn violated(self) -> &'static [u8] {
+        match self {
+            Check::Assume | Check::True => &[ISZERO],
+            Check::False => &[],
+            Check::Eq => &[EQ, ISZERO],
+            Check::NotEq => &[EQ],
+            Check::Gt => &[GT, ISZERO],
+            Check::Ge => &[LT],
+            Check::Lt => &[LT, ISZERO],
+            Check::Le => &[GT],
+            Check::SignedGt => &[SGT, ISZERO],
+            Check::SignedGe => &[SLT],
+            Check::SignedLt => &[SLT, ISZERO],
+            Check::SignedLe => &[SGT],
+        }
+    }
+}
+
+/// Cheatcodes the stand-in checks, each also with a trailing `string` message
+fn checks() -> Vec<(String, Check)> {
+    let mut checks = vec![
+        ("assume(bool)".to_string(), Check::Assume),
+        ("assertTrue(bool)".to_string(), Check::True),
+        ("assertFalse(bool)".to_string(), Check::False),
+    ];
+    for kind in ["uint256", "int256", "address", "bool", "bytes32"] {
+        checks.push((format!("assertEq({0},{0})", kind), Check::Eq));
+        checks.push((format!("assertNotEq({0},{0})", kind), Check::NotEq));
+    }
+    for (kind, [gt, ge, lt, le]) in [
+        ("uint256", [Check::Gt, Check::Ge, Check::Lt, Check::Le]),
+        ("int256", [Check::SignedGt, Check::SignedGe, Check::SignedLt, Check::SignedLe]),
+    ] {
+        for (name, check) in [("assertGt", gt), ("assertGe", ge), ("assertLt", lt), ("assertLe", le)] {
+            checks.push((format!("{}({1},{1})", name, kind), check));
+        }
+    }
+    let with_message: Vec<(String, Check)> = checks.iter()
+        .filter(|(_, check)| *check != Check::Assume)
+        .map(|(signature, check)| (signature.replacen(')', ",string)", 1), *check))
+        .collect();
+    checks.extend(with_message);
+    checks
+}
+
+/// Jump destinations of the stand-in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Label {
+    Check(Check),
+    Rejected,
+    AssertionFailed,
+}
+
+/// Bytecode with forward jumps, patched once every label is placed
+#[derive(Default)]
+struct Assembler {
+    code: Vec<u8>,
+    labels: HashMap<Label, usize>,
+    jumps: Vec<(usize, Label)>,
+}
+
+impl Assembler {
+    fn op(&mut self, opcodes: &[u8]) {
+        self.code.extend_from_slice(opcodes);
+    }
+
+    fn push1(&mut self, value: u8) {
+        self.op(&[0x60, value]);
+    }
+
+    /// PUSH32 of up to 32 bytes, left-aligned as in a string or selector word
+    fn push_word(&mut self, bytes: &[u8]) {
+        let mut word = [0u8; 32];
+        word[..bytes.len()].copy_from_slice(bytes);
+        self.op(&[0x7f]);
+        self.op(&word);
+    }
+
+    /// PUSH2 of the label's offset, then JUMPI
+    fn jump_if(&mut self, label: Label) {
+        self.op(&[0x61]);
+        self.jumps.push((self.code.len(), label));
+        self.op(&[0x00, 0x00, 0x57]);
+    }
+
+    fn place(&mut self, label: Label) {
+        self.labels.insert(label, self.code.len());
+        self.op(&[0x5b]);
+    }
+
+    /// `revert Error(reason)` for a reason of at most 32 bytes
+    fn revert_with(&mut self, reason: &str) {
+        self.push_word(&[0x08, 0xc3, 0x79, 0xa0]);
+        self.push1(0x00);
+        self.op(&[0x52]);
+        self.push1(0x20);
+        self.push1(0x04);
+        self.op(&[0x52]);
+        self.push1(reason.len() as u8);
+        self.push1(0x24);
+        self.op(&[0x52]);
+        self.push_word(reason.as_bytes());
+        self.push1(0x44);
+        self.op(&[0x52]);
+        self.push1(0x64);
+        self.push1(0x00);
+        self.op(&[0xfd]);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for (offset, label) in &self.jumps {
+            let target = self.labels[label] as u16;
+            self.code[*offset..*offset + 2].copy_from_slice(&target.to_be_bytes());
+        }
+        self.code
+    }
+}
+
+/// Runtime code of the stand-in placed at `CHEATCODE_ADDRESS`
+pub fn cheatcode_stub() -> Vec<u8> {
+    let checks = checks();
+    let mut asm = Assembler::default();
+    // selector = calldataload(0) >> 224
+    asm.push1(0x00);
+    asm.op(&[0x35]);
+    asm.push1(0xe0);
+    asm.op(&[0x1c]);
+    for (signature, check) in &checks {
+        asm.op(&[0x80, 0x63]);
+        asm.op(&calculate_selector(signature));
+        asm.op(&[EQ]);
+        asm.jump_if(Label::Check(*check));
+    }
+    // Any other cheatcode: return one zero word
+    asm.push1(0x20);
+    asm.push1(0x00);
+    asm.op(&[0xf3]);
+
+    let mut placed = Vec::new();
+    for (_, check) in checks {
+        if placed.contains(&check) {
+            continue;
+        }
+        placed.push(check);
+        asm.place(Label::Check(check));
+        for operand in (0..check.operands()).rev() {
+            asm.push1(0x04 + 0x20 * operand as u8);
+            asm.op(&[0x35]);
+        }
+        asm.op(check.violated());
+        asm.jump_if(if check == Check::Assume { Label::Rejected } else { Label::AssertionFailed });
+        asm.op(&[0x00]);
+    }
+    asm.place(Label::Rejected);
+    asm.revert_with(ASSUME_REJECTED);
+    asm.place(Label::AssertionFailed);
+    asm.revert_with(ASSERTION_FAILED);
+    asm.finish()
+}
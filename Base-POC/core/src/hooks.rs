@@ -0,0 +1,425 @@
+use crate::types::{SolidityType, SolidityValue};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+/// Version of the hook ABI spoken by this build. A module exports `fuzzhead_abi_version() -> i32`
+/// returning it; the exports below only change together with this number.
+///
+/// Every export other than `memory` and `fuzzhead_abi_version` is optional:
+///
+/// ```text
+/// fuzzhead_alloc(len: i32) -> i32                                  buffer for inputs (required by the hooks below)
+/// fuzzhead_generate_value(type_ptr: i32, type_len: i32, seed: i64) -> i64
+/// fuzzhead_check_outcome(json_ptr: i32, json_len: i32) -> i64
+/// fuzzhead_next_method(json_ptr: i32, json_len: i32) -> i32
+/// ```
+///
+/// Strings are UTF-8 in the module's memory. An `i64` result packs an output string as
+/// `(ptr << 32) | len`; 0 means "no opinion" and falls back to the built-in behavior.
+pub const HOOK_ABI_VERSION: i32 = 1;
+
+/// Fuel (roughly: wasm instructions) one hook invocation may burn before it is aborted
+pub const FUEL_PER_CALL: u64 = 10_000_000;
+
+/// One executed call, as handed to `fuzzhead_check_outcome` (JSON)
+#[derive(Debug, Serialize)]
+pub struct HookCall<'a> {
+    pub contract: &'a str,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub method: &'a str,
+    pub sender: &'a str,
+    pub args: Vec<String>,
+    /// `passed`, `failed` or `error`
+    pub outcome: &'a str,
+    pub error: Option<&'a str>,
+    pub gas_used: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct MethodChoice<'a> {
+    contract: &'a str,
+    /// Signatures of the methods not fuzzed yet, in declaration order
+    remaining: &'a [String],
+}
+
+/// A loaded user module. Hooks that are not exported, and hooks that trap or run out of fuel,
+/// leave the built-in behavior in place.
+pub struct Hooks {
+    runtime: runtime::Runtime,
+}
+
+impl Hooks {
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self { runtime: runtime::Runtime::load(path)? })
+    }
+
+    /// Names of the optional hooks the module exports
+    pub fn exported(&self) -> Vec<&'static str> {
+        self.runtime.exported()
+    }
+
+    /// `fuzzhead_generate_value`: a value for a parameter of ABI type `abi_type`, as text
+    /// (decimal or `0x` hex integers, `0x` addresses and bytes, `true`/`false`, raw strings)
+    pub fn generate_value(&mut self, param_type: &SolidityType, seed: u64) -> Result<Option<SolidityValue>> {
+        let abi_type = param_type.abi_type_string();
+        let Some(text) = self.runtime.generate_value(&abi_type, seed)? else {
+            return Ok(None);
+        };
+        parse_value(param_type, &text)
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("fuzzhead_generate_value returned '{}', not a valid {}", text, abi_type))
+    }
+
+    /// `fuzzhead_check_outcome`: a violation message when the module's oracle rejects the call
+    pub fn check_outcome(&mut self, call: &HookCall) -> Result<Option<String>> {
+        self.runtime.check_outcome(&serde_json::to_vec(call)?)
+    }
+
+    /// `fuzzhead_next_method`: index into `remaining` of the method to fuzz next
+    pub fn next_method(&mut self, contract: &str, remaining: &[String]) -> Result<Option<usize>> {
+        let choice = self.runtime.next_method(&serde_json::to_vec(&MethodChoice { contract, remaining })?)?;
+        Ok(choice.filter(|index| *index < remaining.len()))
+    }
+}
+
+/// Parse a hook's textual value for a scalar parameter type
+pub(crate) fn parse_value(param_type: &SolidityType, text: &str) -> Option<SolidityValue> {
+    let text = text.trim();
+    let unsigned = |text: &str| -> Option<ethers::types::U256> {
+        match text.strip_prefix("0x") {
+            Some(hex) => ethers::types::U256::from_str_radix(hex, 16).ok(),
+            None => ethers::types::U256::from_dec_str(text).ok(),
+        }
+    };
+    let fixed_bytes = |len: usize| -> Option<Vec<u8>> {
+        let bytes = hex::decode(text.strip_prefix("0x")?).ok()?;
+        (bytes.len() == len).then_some(bytes)
+    };
+
+    Some(match param_type {
+        SolidityType::Uint8 => SolidityValue::Uint8(text.parse().ok()?),
+        SolidityType::Uint16 => SolidityValue::Uint16(text.parse().ok()?),
+        SolidityType::Uint32 => SolidityValue::Uint32(text.parse().ok()?),
+        SolidityType::Uint64 => SolidityValue::Uint64(text.parse().ok()?),
+        SolidityType::Uint128 => SolidityValue::Uint128(text.parse().ok()?),
+        SolidityType::Uint256 => SolidityValue::Uint256(unsigned(text)?.to_string()),
+        SolidityType::Int8 => SolidityValue::Int8(text.parse().ok()?),
+        SolidityType::Int16 => SolidityValue::Int16(text.parse().ok()?),
+        SolidityType::Int32 => SolidityValue::Int32(text.parse().ok()?),
+        SolidityType::Int64 => SolidityValue::Int64(text.parse().ok()?),
+        SolidityType::Int128 => SolidityValue::Int128(text.parse().ok()?),
+        SolidityType::Int256 => SolidityValue::Int256(ethers::types::I256::from_dec_str(text).ok()?.to_string()),
+        SolidityType::Address => {
+            let bytes = hex::decode(text.strip_prefix("0x")?).ok()?;
+            (bytes.len() == 20).then(|| SolidityValue::Address(format!("0x{}", hex::encode(bytes))))?
+        }
+        SolidityType::Bool => SolidityValue::Bool(text.parse().ok()?),
+        SolidityType::Bytes1 => SolidityValue::Bytes1(fixed_bytes(1)?.try_into().ok()?),
+        SolidityType::Bytes2 => SolidityValue::Bytes2(fixed_bytes(2)?.try_into().ok()?),
+        SolidityType::Bytes4 => SolidityValue::Bytes4(fixed_bytes(4)?.try_into().ok()?),
+        SolidityType::Bytes8 => SolidityValue::Bytes8(fixed_bytes(8)?.try_into().ok()?),
+        SolidityType::Bytes16 => SolidityValue::Bytes16(fixed_bytes(16)?.try_into().ok()?),
+        SolidityType::Bytes32 => SolidityValue::Bytes32(fixed_bytes(32)?.try_into().ok()?),
+        SolidityType::Bytes => SolidityValue::Bytes(hex::decode(text.strip_prefix("0x")?).ok()?),
+        SolidityType::String => SolidityValue::String(text.to_string()),
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "wasm-hooks")]
+mod runtime {
+    use super::{FUEL_PER_CALL, HOOK_ABI_VERSION};
+    use anyhow::{Context, Result};
+    use std::path::Path;
+    use wasmtime::{Config, Engine, Instance, Memory, Module, Store, TypedFunc};
+
+    pub struct Runtime {
+        store: Store<()>,
+        memory: Memory,
+        alloc: Option<TypedFunc<i32, i32>>,
+        generate_value: Option<TypedFunc<(i32, i32, i64), i64>>,
+        check_outcome: Option<TypedFunc<(i32, i32), i64>>,
+        next_method: Option<TypedFunc<(i32, i32), i32>>,
+    }
+
+    impl Runtime {
+        pub fn load(path: &Path) -> Result<Self> {
+            let mut config = Config::new();
+            config.consume_fuel(true);
+            let engine = Engine::new(&config)?;
+            let module = Module::from_file(&engine, path)
+                .with_context(|| format!("Failed to load hook module {}", path.display()))?;
+            let mut store = Store::new(&engine, ());
+            store.set_fuel(FUEL_PER_CALL)?;
+            // No imports: hooks are pure functions of their inputs
+            let instance = Instance::new(&mut store, &module, &[])
+                .with_context(|| format!("Failed to instantiate hook module {} (hooks may not import anything)", path.display()))?;
+
+            let version = instance.get_typed_func::<(), i32>(&mut store, "fuzzhead_abi_version")
+                .context("Hook module does not export fuzzhead_abi_version")?
+                .call(&mut store, ())?;
+            if version != HOOK_ABI_VERSION {
+                anyhow::bail!("Hook module speaks ABI version {}, this build supports version {}", version, HOOK_ABI_VERSION);
+            }
+            let memory = instance.get_memory(&mut store, "memory")
+                .context("Hook module does not export its memory")?;
+
+            let alloc = instance.get_typed_func(&mut store, "fuzzhead_alloc").ok();
+            let generate_value = instance.get_typed_func(&mut store, "fuzzhead_generate_value").ok();
+            let check_outcome = instance.get_typed_func(&mut store, "fuzzhead_check_outcome").ok();
+            let next_method = instance.get_typed_func(&mut store, "fuzzhead_next_method").ok();
+            if alloc.is_none() && (generate_value.is_some() || check_outcome.is_some() || next_method.is_some()) {
+                anyhow::bail!("Hook module exports hooks but no fuzzhead_alloc to pass them inputs");
+            }
+
+            Ok(Self { store, memory, alloc, generate_value, check_outcome, next_method })
+        }
+
+        pub fn exported(&self) -> Vec<&'static str> {
+            [
+                ("fuzzhead_generate_value", self.generate_value.is_some()),
+                ("fuzzhead_check_outcome", self.check_outcome.is_some()),
+                ("fuzzhead_next_method", self.next_method.is_some()),
+            ]
+            .into_iter()
+            .filter_map(|(name, exported)| exported.then_some(name))
+            .collect()
+        }
+
+        pub fn generate_value(&mut self, abi_type: &str, seed: u64) -> Result<Option<String>> {
+            let Some(hook) = self.generate_value.clone() else {
+                return Ok(None);
+            };
+            let (ptr, len) = self.write_input(abi_type.as_bytes())?;
+            self.store.set_fuel(FUEL_PER_CALL)?;
+            let packed = hook.call(&mut self.store, (ptr, len, seed as i64))?;
+            self.read_output(packed)
+        }
+
+        pub fn check_outcome(&mut self, json: &[u8]) -> Result<Option<String>> {
+            let Some(hook) = self.check_outcome.clone() else {
+                return Ok(None);
+            };
+            let (ptr, len) = self.write_input(json)?;
+            self.store.set_fuel(FUEL_PER_CALL)?;
+            let packed = hook.call(&mut self.store, (ptr, len))?;
+            self.read_output(packed)
+        }
+
+        pub fn next_method(&mut self, json: &[u8]) -> Result<Option<usize>> {
+            let Some(hook) = self.next_method.clone() else {
+                return Ok(None);
+            };
+            let (ptr, len) = self.write_input(json)?;
+            self.store.set_fuel(FUEL_PER_CALL)?;
+            let index = hook.call(&mut self.store, (ptr, len))?;
+            Ok(usize::try_from(index).ok())
+        }
+
+        fn write_input(&mut self, bytes: &[u8]) -> Result<(i32, i32)> {
+            let alloc = self.alloc.clone().context("Hook module has no fuzzhead_alloc")?;
+            let len = i32::try_from(bytes.len()).context("Hook input too large")?;
+            self.store.set_fuel(FUEL_PER_CALL)?;
+            let ptr = alloc.call(&mut self.store, len)?;
+            self.memory.write(&mut self.store, ptr as u32 as usize, bytes)
+                .context("fuzzhead_alloc returned a buffer outside the module's memory")?;
+            Ok((ptr, len))
+        }
+
+        fn read_output(&mut self, packed: i64) -> Result<Option<String>> {
+            if packed == 0 {
+                return Ok(None);
+            }
+            let ptr = (packed as u64 >> 32) as usize;
+            let len = (packed as u64 & 0xffff_ffff) as usize;
+            let mut bytes = vec![0u8; len];
+            self.memory.read(&self.store, ptr, &mut bytes)
+                .context("Hook returned a string outside the module's memory")?;
+            Ok(Some(String::from_utf8(bytes).context("Hook returned invalid UTF-8")?))
+        }
+    }
+}
+
+#[cfg(not(feature = "wasm-hooks"))]
+mod runtime {
+    use anyhow::Result;
+    use std::path::Path;
+
+    pub enum Runtime {}
+
+    impl Runtime {
+        pub fn load(_path: &Path) -> Result<Self> {
+            anyhow::bail!("Fuzzhead was built without WASM hook support; rebuild with `cargo build --release --features wasm-hooks`")
+        }
+
+        pub fn exported(&self) -> Vec<&'static str> {
+            match *self {}
+        }
+
+        pub fn generate_value(&mut self, _abi_type: &str, _seed: u64) -> Result<Option<String>> {
+            match *self {}
+        }
+
+        pub fn check_outcome(&mut self, _json: &[u8]) -> Result<Option<String>> {
+            match *self {}
+        }
+
+        pub fn next_method(&mut self, _json: &[u8]) -> Result<Option<usize>> {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_values_parse_by_parameter_type() {
+        assert_eq!(parse_value(&SolidityType::Uint8, " 255 "), Some(SolidityValue::Uint8(255)));
+        assert_eq!(parse_value(&SolidityType::Uint8, "256"), None);
+        assert_eq!(parse_value(&SolidityType::Uint256, "0xff"), Some(SolidityValue::Uint256("255".to_string())));
+        assert_eq!(parse_value(&SolidityType::Int256, "-1"), Some(SolidityValue::Int256("-1".to_string())));
+        assert_eq!(parse_value(&SolidityType::Address, "0x00000000000000000000000000000000000000AA"),
+            Some(SolidityValue::Address("0x00000000000000000000000000000000000000aa".to_string())));
+        assert_eq!(parse_value(&SolidityType::Address, "0xaa"), None);
+        assert_eq!(parse_value(&SolidityType::Bytes2, "0xbeef"), Some(SolidityValue::Bytes2([0xbe, 0xef])));
+        assert_eq!(parse_value(&SolidityType::Bytes2, "0xbe"), None);
+        assert_eq!(parse_value(&SolidityType::Array(Box::new(SolidityType::Bool)), "[true]"), None);
+    }
+
+    #[cfg(not(feature = "wasm-hooks"))]
+    #[test]
+    fn loading_without_the_feature_says_how_to_get_it() {
+        let error = Hooks::load(Path::new("hooks.wasm")).err().unwrap();
+        assert!(error.to_string().contains("--features wasm-hooks"), "{}", error);
+    }
+
+    /// A compiled module of `wat` in a temporary file, removed on drop
+    #[cfg(feature = "wasm-hooks")]
+    struct ModuleFile(std::path::PathBuf);
+
+    #[cfg(feature = "wasm-hooks")]
+    impl ModuleFile {
+        fn new(wat: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("fuzzhead_hooks_{}.wasm", uuid::Uuid::new_v4()));
+            std::fs::write(&path, wat::parse_str(wat).unwrap()).unwrap();
+            Self(path)
+        }
+
+        fn load(&self) -> Result<Hooks> {
+            Hooks::load(&self.0)
+        }
+    }
+
+    #[cfg(feature = "wasm-hooks")]
+    impl Drop for ModuleFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    /// Answers `42` for `uint8` (the only 5-byte type name), always rejects the outcome with
+    /// "too much gas" and picks the second remaining method
+    #[cfg(feature = "wasm-hooks")]
+    const EVERY_HOOK: &str = r#"(module
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 1024))
+        (data (i32.const 16) "42")
+        (data (i32.const 32) "too much gas")
+        (func (export "fuzzhead_abi_version") (result i32) (i32.const 1))
+        (func (export "fuzzhead_alloc") (param $len i32) (result i32)
+            (global.get $next)
+            (global.set $next (i32.add (global.get $next) (local.get $len))))
+        (func (export "fuzzhead_generate_value") (param $ptr i32) (param $len i32) (param $seed i64) (result i64)
+            (if (result i64) (i32.eq (local.get $len) (i32.const 5))
+                (then (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 2)))
+                (else (i64.const 0))))
+        (func (export "fuzzhead_check_outcome") (param i32 i32) (result i64)
+            (i64.or (i64.shl (i64.const 32) (i64.const 32)) (i64.const 12)))
+        (func (export "fuzzhead_next_method") (param i32 i32) (result i32) (i32.const 1))
+    )"#;
+
+    #[cfg(feature = "wasm-hooks")]
+    #[test]
+    fn every_hook_is_called_through_the_abi() {
+        let module = ModuleFile::new(EVERY_HOOK);
+        let mut hooks = module.load().unwrap();
+        assert_eq!(hooks.exported(), ["fuzzhead_generate_value", "fuzzhead_check_outcome", "fuzzhead_next_method"]);
+
+        assert_eq!(hooks.generate_value(&SolidityType::Uint8, 7).unwrap(), Some(SolidityValue::Uint8(42)));
+        assert_eq!(hooks.generate_value(&SolidityType::Uint256, 7).unwrap(), None);
+        // `bytes` has a 5-byte name too, and "42" is not 0x-prefixed hex
+        assert!(hooks.generate_value(&SolidityType::Bytes, 7).is_err());
+
+        let call = HookCall {
+            contract: "Vault",
+            method: "withdraw(uint256)",
+            sender: "0x00000000000000000000000000000000000000aa",
+            args: vec!["1".to_string()],
+            outcome: "passed",
+            error: None,
+            gas_used: 21_000,
+        };
+        assert_eq!(hooks.check_outcome(&call).unwrap().as_deref(), Some("too much gas"));
+
+        assert_eq!(hooks.next_method("Vault", &["a()".to_string(), "b()".to_string()]).unwrap(), Some(1));
+        // An index past the remaining methods is no choice
+        assert_eq!(hooks.next_method("Vault", &["a()".to_string()]).unwrap(), None);
+    }
+
+    #[cfg(feature = "wasm-hooks")]
+    #[test]
+    fn a_hook_out_of_fuel_fails_alone() {
+        let module = ModuleFile::new(r#"(module
+            (memory (export "memory") 1)
+            (func (export "fuzzhead_abi_version") (result i32) (i32.const 1))
+            (func (export "fuzzhead_alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "fuzzhead_generate_value") (param i32 i32 i64) (result i64)
+                (loop $spin (br $spin))
+                (i64.const 0))
+            (func (export "fuzzhead_next_method") (param i32 i32) (result i32) (i32.const 0))
+        )"#);
+        let mut hooks = module.load().unwrap();
+        let error = hooks.generate_value(&SolidityType::Uint8, 1).unwrap_err();
+        assert_eq!(error.downcast_ref::<wasmtime::Trap>(), Some(&wasmtime::Trap::OutOfFuel), "{:?}", error);
+        // Every invocation gets fresh fuel
+        assert_eq!(hooks.next_method("Vault", &["a()".to_string()]).unwrap(), Some(0));
+    }
+
+    #[cfg(feature = "wasm-hooks")]
+    #[test]
+    fn modules_of_another_abi_version_are_rejected() {
+        let module = ModuleFile::new(r#"(module
+            (memory (export "memory") 1)
+            (func (export "fuzzhead_abi_version") (result i32) (i32.const 2))
+        )"#);
+        let error = module.load().err().unwrap();
+        assert!(error.to_string().contains("ABI version 2, this build supports version 1"), "{}", error);
+
+        let module = ModuleFile::new(r#"(module (memory (export "memory") 1))"#);
+        let error = module.load().err().unwrap();
+        assert!(error.to_string().contains("does not export fuzzhead_abi_version"), "{}", error);
+    }
+
+    #[cfg(feature = "wasm-hooks")]
+    #[test]
+    fn modules_with_imports_or_without_alloc_are_rejected() {
+        let module = ModuleFile::new(r#"(module
+            (import "env" "now" (func (result i64)))
+            (memory (export "memory") 1)
+            (func (export "fuzzhead_abi_version") (result i32) (i32.const 1))
+        )"#);
+        let error = module.load().err().unwrap();
+        assert!(error.to_string().contains("hooks may not import anything"), "{}", error);
+
+        let module = ModuleFile::new(r#"(module
+            (memory (export "memory") 1)
+            (func (export "fuzzhead_abi_version") (result i32) (i32.const 1))
+            (func (export "fuzzhead_next_method") (param i32 i32) (result i32) (i32.const 0))
+        )"#);
+        let error = module.load().err().unwrap();
+        assert!(error.to_string().contains("no fuzzhead_alloc"), "{}", error);
+    }
+}
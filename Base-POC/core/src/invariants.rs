@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Name prefixes of parameterless functions treated as properties (Foundry and Echidna conventions)
+pub const INVARIANT_PREFIXES: [&str; 2] = ["invariant_", "echidna_"];
+
+/// Whether a function with this name and no parameters is a property to check, not a method to fuzz
+pub fn is_invariant(name: &str) -> bool {
+    INVARIANT_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Why an invariant's return data breaks the property: it returned false or something that is not a bool.
+/// `None` when it returned true.
+pub fn check_output(output: &[u8]) -> Option<String> {
+    if output.len() < 32 {
+        return Some(format!("returned 0x{} instead of a bool", hex::encode(output)));
+    }
+    output[..32].iter().all(|byte| *byte == 0).then(|| "returned false".to_string())
+}
+
+/// An invariant that returned false or reverted. Each invariant is reported at most once per
+/// contract; it is not checked again once violated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvariantViolation {
+    pub contract: String,
+    /// Signature of the invariant function, e.g. `invariant_solvency()`
+    pub invariant: String,
+    pub reason: String,
+    /// The last call before the check, e.g. `withdraw(5) from 0x… (iteration 3)`;
+    /// `None` when the invariant already failed right after deployment
+    pub after_call: Option<String>,
+    /// Seed of that call
+    pub seed: Option<u64>,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.after_call {
+            Some(call) => write!(f, "{}.{} violated after {}: {}", self.contract, self.invariant, call, self.reason),
+            None => write!(f, "{}.{} violated right after deployment: {}", self.contract, self.invariant, self.reason),
+        }
+    }
+}
+
+/// How often an invariant is evaluated. Checking less often trades precision (which call broke
+/// the property) for speed when an invariant reads a lot of storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckFrequency {
+    /// After every fuzzed call
+    #[default]
+    PerCall,
+    /// Once, after the last call of a sequence
+    PerSequence,
+    /// After every n-th call of a sequence, and after its last call
+    EveryNCalls(usize),
+}
+
+impl FromStr for CheckFrequency {
+    type Err = String;
+
+    /// Accepts `per-call`, `per-sequence` and `every-N` (or just `N`)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_ascii_lowercase();
+        match text.as_str() {
+            "per-call" | "call" => Ok(Self::PerCall),
+            "per-sequence" | "sequence" => Ok(Self::PerSequence),
+            _ => {
+                let count = text.strip_prefix("every-").unwrap_or(&text);
+                match count.parse::<usize>() {
+                    Ok(0) => Err("check interval must be at least 1 call".to_string()),
+                    Ok(1) => Ok(Self::PerCall),
+                    Ok(n) => Ok(Self::EveryNCalls(n)),
+                    Err(_) => Err(format!("invalid check frequency '{}' (expected per-call, per-sequence or every-N)", text)),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for CheckFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PerCall => write!(f, "per-call"),
+            Self::PerSequence => write!(f, "per-sequence"),
+            Self::EveryNCalls(n) => write!(f, "every-{}", n),
+        }
+    }
+}
+
+/// Check frequency of every invariant: a campaign-wide default plus per-invariant overrides
+#[derive(Debug, Clone, Default)]
+pub struct InvariantSchedule {
+    pub default: CheckFrequency,
+    /// Keyed by invariant function name, e.g. `invariant_solvency`
+    pub overrides: HashMap<String, CheckFrequency>,
+}
+
+impl InvariantSchedule {
+    pub fn frequency(&self, invariant: &str) -> CheckFrequency {
+        self.overrides.get(invariant).copied().unwrap_or(self.default)
+    }
+
+    /// Whether `invariant` is due after the `call`-th call (1-based) of a sequence.
+    /// `sequence_end` marks the last call, where every invariant is checked.
+    pub fn is_due(&self, invariant: &str, call: usize, sequence_end: bool) -> bool {
+        match self.frequency(invariant) {
+            CheckFrequency::PerCall => true,
+            CheckFrequency::PerSequence => sequence_end,
+            CheckFrequency::EveryNCalls(n) => sequence_end || call.is_multiple_of(n),
+        }
+    }
+}
+
+/// Parse a per-invariant override such as `invariant_solvency=every-10`
+pub fn parse_override(spec: &str) -> Result<(String, CheckFrequency), String> {
+    let (name, frequency) = spec.split_once('=')
+        .ok_or_else(|| format!("expected NAME=FREQUENCY, got '{}'", spec))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing invariant name in '{}'", spec));
+    }
+    Ok((name.to_string(), frequency.parse()?))
+}
@@ -0,0 +1,176 @@
+//! Private keys for signed-transaction mode. Nodes that neither unlock nor fund accounts (private
+//! devnets, permissioned testnets) only accept transactions signed by the sender, so senders can
+//! come from environment variables or encrypted keystores, each assigned a role.
+
+use anyhow::{anyhow, Context, Result};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Eip1559TransactionRequest, TransactionRequest, U256};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// What a key is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRole {
+    /// Deploys the contracts under test and sends no fuzzed calls
+    Deployer,
+    /// Sends fuzzed calls only
+    Sender,
+    /// A regular sender account (no role given); the first one deploys when there is no deployer
+    Any,
+}
+
+impl FromStr for KeyRole {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "deployer" => Ok(KeyRole::Deployer),
+            "sender" => Ok(KeyRole::Sender),
+            other => Err(format!("unknown key role '{}' (expected deployer or sender)", other)),
+        }
+    }
+}
+
+/// Where a key is loaded from, as given on the command line: `LOCATION` or `LOCATION:ROLE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySource {
+    pub location: String,
+    pub role: KeyRole,
+}
+
+impl FromStr for KeySource {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // Only a known role counts as a suffix, so Windows paths such as C:\keys\a.json stay intact
+        let (location, role) = match text.rsplit_once(':') {
+            Some((location, role)) if role.parse::<KeyRole>().is_ok() => (location, role.parse()?),
+            _ => (text, KeyRole::Any),
+        };
+        if location.is_empty() {
+            return Err("missing key location".to_string());
+        }
+        Ok(KeySource { location: location.to_string(), role })
+    }
+}
+
+/// A loaded private key and its role
+#[derive(Debug, Clone)]
+pub struct AccountKey {
+    pub wallet: LocalWallet,
+    pub role: KeyRole,
+    /// Where it came from, for messages (never the key itself)
+    pub origin: String,
+}
+
+impl AccountKey {
+    /// Lowercase `0x`-prefixed address
+    pub fn address(&self) -> String {
+        format!("{:?}", self.wallet.address())
+    }
+
+    pub fn deploys(&self) -> bool {
+        self.role == KeyRole::Deployer
+    }
+
+    pub fn sends(&self) -> bool {
+        self.role != KeyRole::Deployer
+    }
+}
+
+impl fmt::Display for AccountKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (from {})", self.address(), self.origin)
+    }
+}
+
+/// Hex private key held in an environment variable
+pub fn from_env(source: &KeySource) -> Result<AccountKey> {
+    let value = std::env::var(&source.location)
+        .with_context(|| format!("Environment variable {} is not set", source.location))?;
+    let hex_key = value.trim();
+    let hex_key = hex_key.strip_prefix("0x").unwrap_or(hex_key);
+    let wallet = hex_key.parse::<LocalWallet>()
+        .map_err(|e| anyhow!("{} does not hold a valid private key: {}", source.location, e))?;
+    Ok(AccountKey { wallet, role: source.role, origin: format!("${}", source.location) })
+}
+
+/// Encrypted JSON keystore, unlocked with the password in `password_env` or one typed at a prompt
+pub fn from_keystore(source: &KeySource, password_env: Option<&str>) -> Result<AccountKey> {
+    let password = match password_env {
+        Some(variable) => std::env::var(variable)
+            .with_context(|| format!("Environment variable {} is not set", variable))?,
+        None => dialoguer::Password::new()
+            .with_prompt(format!("Password for keystore {}", source.location))
+            .interact()
+            .context("Could not read the keystore password")?,
+    };
+    let wallet = LocalWallet::decrypt_keystore(&source.location, password)
+        .map_err(|e| anyhow!("Could not decrypt keystore {}: {}", source.location, e))?;
+    Ok(AccountKey { wallet, role: source.role, origin: source.location.clone() })
+}
+
+/// Load every key, checking that at most one is the deployer and that some key sends calls
+pub fn load(env_keys: &[KeySource], keystores: &[KeySource], password_env: Option<&str>) -> Result<Vec<AccountKey>> {
+    let mut keys = Vec::new();
+    for source in env_keys {
+        keys.push(from_env(source)?);
+    }
+    for source in keystores {
+        keys.push(from_keystore(source, password_env)?);
+    }
+    if keys.iter().filter(|key| key.deploys()).count() > 1 {
+        return Err(anyhow!("Only one key can have the deployer role"));
+    }
+    if !keys.is_empty() && !keys.iter().any(AccountKey::sends) {
+        return Err(anyhow!("Every key has the deployer role; at least one key must send the fuzzed calls"));
+    }
+    Ok(keys)
+}
+
+fn quantity(tx_params: &Value, field: &str) -> Result<Option<U256>> {
+    tx_params.get(field).and_then(Value::as_str)
+        .map(|value| U256::from_str_radix(value.trim_start_matches("0x"), 16))
+        .transpose()
+        .with_context(|| format!("Invalid {} in transaction", field))
+}
+
+/// Sign a transaction object in `eth_sendTransaction` form, returning the raw transaction for
+/// `eth_sendRawTransaction`. It is a type-2 transaction when it carries EIP-1559 fees, a legacy
+/// EIP-155 one with its `gasPrice` otherwise.
+pub fn sign_transaction(wallet: &LocalWallet, tx_params: &Value, chain_id: u64) -> Result<Vec<u8>> {
+    let to = tx_params.get("to").and_then(Value::as_str)
+        .map(|to| to.parse::<Address>().with_context(|| format!("Invalid recipient {}", to)))
+        .transpose()?;
+    let data = tx_params.get("data").and_then(Value::as_str)
+        .map(|data| hex::decode(data.trim_start_matches("0x")).context("Invalid transaction data"))
+        .transpose()?
+        .unwrap_or_default();
+    let value = quantity(tx_params, "value")?.unwrap_or_default();
+    let nonce = quantity(tx_params, "nonce")?.context("Signed transactions need a nonce")?;
+    let gas = quantity(tx_params, "gas")?.context("Signed transactions need a gas limit")?;
+
+    let mut tx: TypedTransaction = match (quantity(tx_params, "maxFeePerGas")?, quantity(tx_params, "maxPriorityFeePerGas")?) {
+        (Some(max_fee), Some(priority_fee)) => Eip1559TransactionRequest::new()
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee)
+            .into(),
+        _ => TransactionRequest::new()
+            .gas_price(quantity(tx_params, "gasPrice")?.context("Signed transactions need a gas price or EIP-1559 fees")?)
+            .into(),
+    };
+    tx.set_from(wallet.address());
+    if let Some(to) = to {
+        tx.set_to(to);
+    }
+    tx.set_data(data.into());
+    tx.set_value(value);
+    tx.set_nonce(nonce);
+    tx.set_gas(gas);
+    tx.set_chain_id(chain_id);
+
+    let signature = wallet.sign_transaction_sync(&tx).context("Could not sign transaction")?;
+    Ok(tx.rlp_signed(&signature).to_vec())
+}
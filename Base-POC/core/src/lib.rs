@@ -0,0 +1,70 @@
+//! Fuzzing engine shared by the Fuzzhead front-ends: Solidity parsing, input generation,
+//! ABI encoding, compilation, and the executor backends (Anvil fork, in-process revm, mock).
+
+pub mod types;
+pub mod ast_parser;
+pub mod fuzz_solidity;
+pub mod generator;
+pub mod abi_encoding;
+pub mod anvil_executor;
+pub mod anvil_manager;
+pub mod executor;
+pub mod mock_executor;
+pub mod contract_compiler;
+pub mod constructor;
+pub mod report;
+pub mod report_diff;
+pub mod oracle;
+pub mod config;
+pub mod attacks;
+pub mod tx_dump;
+pub mod repro;
+pub mod selectors;
+pub mod invariants;
+pub mod output;
+pub mod display;
+pub mod trace;
+pub mod bricking;
+pub mod preflight;
+pub mod fingerprint;
+pub mod hooks;
+pub mod coverage;
+pub mod nonce_pool;
+pub mod revm_executor;
+pub mod multicall;
+pub mod accounting;
+pub mod storage_slots;
+pub mod shrink;
+pub mod explorer;
+pub mod case;
+pub mod triage;
+pub mod forge_failures;
+pub mod scribble;
+pub mod sarif;
+pub mod ownership;
+pub mod precompiles;
+pub mod standards;
+pub mod reentrancy;
+pub mod keys;
+pub mod wraparound;
+pub mod access_control;
+pub mod severity;
+pub mod recommendations;
+pub mod ether_drain;
+pub mod dictionary;
+pub mod mutation;
+pub mod project_config;
+pub mod live_target;
+pub mod gas_profile;
+pub mod events;
+pub mod call_tree;
+pub mod harness;
+pub mod corpus;
+pub mod foundry_project;
+pub mod standard_json;
+pub mod solc_manager;
+pub mod linking;
+pub mod proxy;
+pub mod deployment;
+pub mod value_strategy;
+pub mod address_pool;
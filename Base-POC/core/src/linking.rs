@@ -0,0 +1,105 @@
+//! Library linking. Bytecode calling an external library leaves a 20-byte hole for the library's
+//! address, written in the hex as a 40-character placeholder: `__$<34 hex>$__` (the first 17 bytes
+//! of the Keccak-256 of `unit:Library`) since Solidity 0.5, `__unit:Library__` padded or cut to 40
+//! characters before that. Such bytecode cannot be deployed as is; the libraries are deployed
+//! first and their addresses written into the holes.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const PLACEHOLDER_LEN: usize = 40;
+
+/// A library a contract's bytecode has to be linked against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryLink {
+    pub name: String,
+    /// Source unit the library is declared in, as the compiler names it
+    pub source_unit: String,
+    /// File the library is compiled from
+    pub source_path: PathBuf,
+    /// Byte offsets of its address in the bytecode
+    pub offsets: Vec<usize>,
+}
+
+/// Libraries of a `linkReferences` object (`{"unit": {"Library": [{"start", "length"}]}}`) as
+/// `(unit, name)` pairs
+pub fn referenced_libraries(link_references: Option<&Value>) -> Vec<(String, String)> {
+    link_references.and_then(Value::as_object).into_iter().flatten()
+        .flat_map(|(unit, libraries)| libraries.as_object().into_iter().flatten()
+            .map(move |(name, _)| (unit.clone(), name.clone())))
+        .collect()
+}
+
+/// Decode bytecode hex that may hold library placeholders: the bytes with zeroes in the holes,
+/// and the libraries to link, each placeholder matched against the `(unit, name)` candidates.
+/// `locate` turns a source unit into the file to compile the library from.
+pub fn decode(
+    bytecode_hex: &str,
+    candidates: &[(String, String)],
+    locate: impl Fn(&str) -> PathBuf,
+) -> Result<(Vec<u8>, Vec<LibraryLink>)> {
+    let bytecode_hex = bytecode_hex.strip_prefix("0x").unwrap_or(bytecode_hex);
+    if !bytecode_hex.contains("__") {
+        return Ok((hex::decode(bytecode_hex)?, Vec::new()));
+    }
+
+    let mut hex_without_placeholders = String::with_capacity(bytecode_hex.len());
+    let mut links: BTreeMap<(String, String), Vec<usize>> = BTreeMap::new();
+    let mut position = 0;
+    // Placeholders start at byte boundaries, and plain hex never holds `_`
+    while position < bytecode_hex.len() {
+        let rest = &bytecode_hex[position..];
+        match rest.get(..PLACEHOLDER_LEN).filter(|placeholder| placeholder.starts_with("__")) {
+            Some(placeholder) => {
+                let (unit, name) = candidates.iter()
+                    .find(|(unit, name)| matches(placeholder, unit, name))
+                    .with_context(|| format!("Bytecode links against an unknown library (placeholder {})", placeholder))?;
+                links.entry((unit.clone(), name.clone())).or_default().push(position / 2);
+                hex_without_placeholders.push_str(&"0".repeat(PLACEHOLDER_LEN));
+                position += PLACEHOLDER_LEN;
+            }
+            None => {
+                let byte = rest.get(..2).context("Invalid bytecode hex")?;
+                hex_without_placeholders.push_str(byte);
+                position += 2;
+            }
+        }
+    }
+    let bytecode = hex::decode(&hex_without_placeholders).context("Invalid bytecode hex")?;
+    let links = links.into_iter()
+        .map(|((source_unit, name), offsets)| LibraryLink {
+            source_path: locate(&source_unit),
+            name,
+            source_unit,
+            offsets,
+        })
+        .collect();
+    Ok((bytecode, links))
+}
+
+/// Whether a placeholder stands for `unit:name`, in either placeholder format
+fn matches(placeholder: &str, unit: &str, name: &str) -> bool {
+    let qualified = format!("{}:{}", unit, name);
+    if let Some(hash) = placeholder.strip_prefix("__$").and_then(|rest| rest.strip_suffix("$__")) {
+        return hash.eq_ignore_ascii_case(&hex::encode(&Keccak256::digest(qualified.as_bytes())[..17]));
+    }
+    let legacy: String = qualified.chars().chain(std::iter::repeat('_')).take(PLACEHOLDER_LEN - 4).collect();
+    placeholder[2..PLACEHOLDER_LEN - 2] == legacy
+}
+
+/// Write a deployed library's address into its holes
+pub fn link(bytecode: &mut [u8], offsets: &[usize], address: &str) -> Result<()> {
+    let address = hex::decode(address.trim_start_matches("0x")).context("Invalid library address")?;
+    if address.len() != 20 {
+        return Err(anyhow::anyhow!("Invalid library address length: {} bytes", address.len()));
+    }
+    for &offset in offsets {
+        bytecode.get_mut(offset..offset + 20)
+            .context("Library placeholder outside the bytecode")?
+            .copy_from_slice(&address);
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Contracts that are already deployed (`--target-address` with `--abi`). Compilation and
+//! deployment are skipped: the contract on the fork is described by its ABI alone, so modifiers,
+//! the storage layout and source constants are unknown and the oracles that need them stay idle.
+
+use crate::abi_encoding;
+use crate::ast_parser::{ContractInfo, ContractKind, EventInfo};
+use crate::types::MethodParameter;
+use anyhow::{Context, Result};
+use ethers::abi::Abi;
+use serde_json::Value;
+use std::path::Path;
+
+/// A deployed contract to fuzz in place
+#[derive(Debug, Clone)]
+pub struct LiveTarget {
+    /// Name the contract goes by in output and reports, the ABI file's name by default
+    pub name: String,
+    pub address: String,
+    pub abi: Abi,
+}
+
+impl LiveTarget {
+    /// Load the ABI from `abi_path` and name the target after the file unless `name` is given
+    pub fn load(address: &str, abi_path: &Path, name: Option<&str>) -> Result<Self> {
+        let abi = load_abi(abi_path)?;
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => {
+                let stem = abi_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("Target");
+                stem.trim_end_matches(".abi").to_string()
+            }
+        };
+        Ok(Self { name, address: address.to_string(), abi })
+    }
+
+    /// The contract as the fuzzer sees a parsed source file: its functions, fallback, receive and events
+    pub fn contract_info(&self) -> ContractInfo {
+        let methods = abi_encoding::methods_from_abi(&self.abi);
+        let events = self.abi.events()
+            .map(|event| EventInfo {
+                name: event.name.clone(),
+                parameters: event.inputs.iter()
+                    .enumerate()
+                    .map(|(index, input)| MethodParameter {
+                        name: if input.name.is_empty() { MethodParameter::placeholder_name(index) } else { input.name.clone() },
+                        param_type: abi_encoding::solidity_type(&input.kind, None),
+                        location: None,
+                    })
+                    .collect(),
+                anonymous: event.anonymous,
+            })
+            .collect();
+        ContractInfo {
+            name: self.name.clone(),
+            kind: ContractKind::Contract,
+            bases: Vec::new(),
+            fallback: methods.iter().find(|method| method.is_fallback).cloned(),
+            receive: methods.iter().find(|method| method.is_receive).cloned(),
+            methods,
+            events,
+            constructor: None,
+        }
+    }
+}
+
+/// An ABI from a JSON file: a bare ABI array, a Foundry or Hardhat artifact with an `abi` field,
+/// or an Etherscan `getabi` response, whose `result` holds the ABI as a string
+pub fn load_abi(path: &Path) -> Result<Abi> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("could not read ABI {}", path.display()))?;
+    let json: Value = serde_json::from_str(&text).with_context(|| format!("{} is not JSON", path.display()))?;
+    parse_abi(json, &path.display().to_string())
+}
+
+/// An ABI from any of the JSON documents `load_abi` reads; `origin` names it in errors
+pub fn parse_abi(json: Value, origin: &str) -> Result<Abi> {
+    let abi = match json {
+        Value::Array(_) => json,
+        Value::Object(ref fields) => match (fields.get("abi"), fields.get("result")) {
+            (Some(abi), _) => abi.clone(),
+            (None, Some(Value::String(result))) => serde_json::from_str(result)
+                .with_context(|| format!("the result of {} is not an ABI: {}", origin, result))?,
+            _ => anyhow::bail!("{} has neither an ABI array nor an abi field", origin),
+        },
+        _ => anyhow::bail!("{} has neither an ABI array nor an abi field", origin),
+    };
+    serde_json::from_value(abi).with_context(|| format!("invalid ABI in {}", origin))
+}
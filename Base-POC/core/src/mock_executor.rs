@@ -0,0 +1,324 @@
+use crate::anvil_executor::MethodExecutionResult;
+use crate::coverage::BranchEdge;
+use crate::events::EventLog;
+use crate::executor::Executor;
+use crate::reentrancy::CallFrame;
+use crate::types::{BlockContext, Cheat, TxFees};
+use crate::wraparound::WrappedOperation;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::types::U256;
+use std::collections::{HashMap, VecDeque};
+
+/// Scripted outcome of one `call_method` invocation
+#[derive(Debug, Clone)]
+pub enum MockResponse {
+    /// The call succeeded
+    Success,
+    /// The contract reverted with the given reason
+    Revert(String),
+    /// The call could not be carried out (simulated RPC/infrastructure failure)
+    RpcError(String),
+}
+
+/// A call recorded by `MockExecutor`
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub contract_name: String,
+    pub method_signature: String,
+    pub encoded_args: Vec<u8>,
+    pub sender: String,
+    pub fees: Option<TxFees>,
+    pub gas_limit: Option<u64>,
+    pub value: U256,
+}
+
+/// Executor that never touches the network. Calls are answered from a queue of scripted
+/// responses (falling back to `Success` once the queue is empty) and recorded for inspection.
+pub struct MockExecutor {
+    accounts: Vec<String>,
+    current_sender: String,
+    deployed_contracts: HashMap<String, String>,
+    responses: VecDeque<MockResponse>,
+    /// Outputs returned by `static_call`, one 32-byte zero word once empty
+    static_outputs: VecDeque<Vec<u8>>,
+    /// Branch edges reported for calls, in call order; no coverage once empty
+    coverage: VecDeque<Vec<BranchEdge>>,
+    /// Values returned by `contract_balance` and `account_balance`, zero once empty
+    balances: VecDeque<U256>,
+    /// Words returned by `storage_at`, zero once empty
+    storage_words: VecDeque<U256>,
+    calls: Vec<RecordedCall>,
+    next_snapshot: u64,
+    fees: Option<TxFees>,
+    gas_limit: Option<u64>,
+    value: U256,
+    block_context: BlockContext,
+}
+
+impl MockExecutor {
+    pub fn new() -> Self {
+        let accounts: Vec<String> = (1..=5u8)
+            .map(|i| format!("0x{}", hex::encode([i; 20])))
+            .collect();
+        Self {
+            current_sender: accounts[0].clone(),
+            accounts,
+            deployed_contracts: HashMap::new(),
+            responses: VecDeque::new(),
+            static_outputs: VecDeque::new(),
+            coverage: VecDeque::new(),
+            balances: VecDeque::new(),
+            storage_words: VecDeque::new(),
+            calls: Vec::new(),
+            next_snapshot: 0,
+            fees: None,
+            gas_limit: None,
+            value: U256::zero(),
+            block_context: BlockContext::default(),
+        }
+    }
+
+    /// Queue the outcome of the next un-scripted `call_method`
+    pub fn push_response(&mut self, response: MockResponse) {
+        self.responses.push_back(response);
+    }
+
+    /// Queue the output of a future `static_call`
+    pub fn push_static_output(&mut self, output: Vec<u8>) {
+        self.static_outputs.push_back(output);
+    }
+
+    /// Queue the branch edges reported for a future call
+    pub fn push_coverage(&mut self, edges: Vec<BranchEdge>) {
+        self.coverage.push_back(edges);
+    }
+
+    /// Queue the result of the next `contract_balance` or `account_balance`
+    pub fn push_balance(&mut self, balance: U256) {
+        self.balances.push_back(balance);
+    }
+
+    /// Queue the result of the next `storage_at`
+    pub fn push_storage_word(&mut self, word: U256) {
+        self.storage_words.push_back(word);
+    }
+
+    /// The block context set most recently, empty once reset
+    pub fn block_context(&self) -> &BlockContext {
+        &self.block_context
+    }
+
+    /// Every call received so far, in order
+    pub fn calls(&self) -> &[RecordedCall] {
+        &self.calls
+    }
+}
+
+impl Default for MockExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Executor for MockExecutor {
+    async fn deploy_contract(
+        &mut self,
+        contract_name: &str,
+        _bytecode: &[u8],
+        _constructor_args: Option<&[u8]>,
+    ) -> Result<String> {
+        let index = self.deployed_contracts.len() as u8 + 1;
+        let address = format!("0x{}", hex::encode([0xc0, index].repeat(10)));
+        self.deployed_contracts.insert(contract_name.to_string(), address.clone());
+        Ok(address)
+    }
+
+    async fn estimate_deployment_gas(&mut self, _bytecode: &[u8], _constructor_args: Option<&[u8]>) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    async fn attach_contract(&mut self, contract_name: &str, address: &str) -> Result<Vec<u8>> {
+        self.deployed_contracts.insert(contract_name.to_string(), address.to_string());
+        Ok(Vec::new())
+    }
+
+    async fn call_method(
+        &mut self,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<MethodExecutionResult> {
+        if !self.deployed_contracts.contains_key(contract_name) {
+            return Err(anyhow!("Contract {} not deployed", contract_name));
+        }
+
+        self.calls.push(RecordedCall {
+            contract_name: contract_name.to_string(),
+            method_signature: method_signature.to_string(),
+            encoded_args: encoded_args.to_vec(),
+            sender: self.current_sender.clone(),
+            fees: self.fees,
+            gas_limit: self.gas_limit,
+            value: self.value,
+        });
+
+        match self.responses.pop_front().unwrap_or(MockResponse::Success) {
+            MockResponse::Success => Ok(MethodExecutionResult {
+                success: true,
+                gas_used: 21000,
+                return_data: Vec::new(),
+                error: None,
+            }),
+            MockResponse::Revert(reason) => Ok(MethodExecutionResult {
+                success: false,
+                gas_used: 21000,
+                return_data: Vec::new(),
+                error: Some(reason),
+            }),
+            MockResponse::RpcError(message) => Err(anyhow!(message)),
+        }
+    }
+
+    async fn static_call(&mut self, contract_name: &str, _calldata: &[u8]) -> Result<Vec<u8>> {
+        if !self.deployed_contracts.contains_key(contract_name) {
+            return Err(anyhow!("Contract {} not deployed", contract_name));
+        }
+        Ok(self.static_outputs.pop_front().unwrap_or_else(|| vec![0u8; 32]))
+    }
+
+    async fn contract_balance(&mut self, contract_name: &str) -> Result<U256> {
+        if !self.deployed_contracts.contains_key(contract_name) {
+            return Err(anyhow!("Contract {} not deployed", contract_name));
+        }
+        Ok(self.balances.pop_front().unwrap_or_default())
+    }
+
+    async fn account_balance(&mut self, _address: &str) -> Result<U256> {
+        Ok(self.balances.pop_front().unwrap_or_default())
+    }
+
+    async fn code_at(&mut self, _address: &str) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    async fn storage_at(&mut self, contract_name: &str, _slot: U256) -> Result<U256> {
+        if !self.deployed_contracts.contains_key(contract_name) {
+            return Err(anyhow!("Contract {} not deployed", contract_name));
+        }
+        Ok(self.storage_words.pop_front().unwrap_or_default())
+    }
+
+    async fn last_call_coverage(&mut self) -> Result<Vec<BranchEdge>> {
+        Ok(self.coverage.pop_front().unwrap_or_default())
+    }
+
+    async fn last_call_frames(&mut self) -> Result<Vec<CallFrame>> {
+        Ok(Vec::new())
+    }
+
+    async fn last_call_wraparounds(&mut self) -> Result<Vec<WrappedOperation>> {
+        Ok(Vec::new())
+    }
+
+    async fn last_call_logs(&mut self) -> Result<Vec<EventLog>> {
+        Ok(Vec::new())
+    }
+
+    async fn snapshot(&mut self) -> Result<String> {
+        self.next_snapshot += 1;
+        Ok(format!("0x{:x}", self.next_snapshot))
+    }
+
+    async fn revert_to_snapshot(&mut self, _snapshot_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn base_fee_per_gas(&mut self) -> Result<Option<u128>> {
+        Ok(Some(1_000_000_000))
+    }
+
+    async fn fork_block_number(&mut self) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    async fn chain_id(&mut self) -> Result<u64> {
+        Ok(31337)
+    }
+
+    fn set_fees(&mut self, fees: Option<TxFees>) {
+        self.fees = fees;
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        self.gas_limit = gas_limit;
+    }
+
+    fn set_value(&mut self, value: U256) {
+        self.value = value;
+    }
+
+    async fn set_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        self.block_context = block_context.clone();
+        Ok(())
+    }
+
+    async fn reset_block_context(&mut self) -> Result<()> {
+        self.block_context = BlockContext::default();
+        Ok(())
+    }
+
+    fn register_errors(&mut self, _abi: &ethers::abi::Abi) {}
+
+    async fn apply_cheat(&mut self, _cheat: &Cheat) -> Result<()> {
+        Ok(())
+    }
+
+    async fn set_code(&mut self, _address: &str, _code: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_sender(&mut self, sender_index: usize) {
+        if sender_index < self.accounts.len() {
+            self.current_sender = self.accounts[sender_index].clone();
+        }
+    }
+
+    fn set_sender_address(&mut self, address: &str) -> bool {
+        match self.accounts.iter().find(|account| account.eq_ignore_ascii_case(address)) {
+            Some(account) => {
+                self.current_sender = account.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn impersonate(&mut self, address: &str) -> Result<()> {
+        if !self.accounts.iter().any(|account| account.eq_ignore_ascii_case(address)) {
+            self.accounts.push(address.to_string());
+        }
+        Ok(())
+    }
+
+    fn current_sender(&self) -> &str {
+        &self.current_sender
+    }
+
+    fn deployer(&self) -> &str {
+        &self.current_sender
+    }
+
+    fn accounts(&self) -> &[String] {
+        &self.accounts
+    }
+
+    fn contract_address(&self, contract_name: &str) -> Option<&str> {
+        self.deployed_contracts.get(contract_name).map(String::as_str)
+    }
+
+    fn rpc_url(&self) -> &str {
+        "mock://dry-run"
+    }
+}
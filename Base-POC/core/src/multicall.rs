@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Context, Result};
+use ethers::abi::{self, ParamType, Token};
+use ethers::types::Address;
+
+/// Where Multicall3 is deployed on Ethereum, Base and most other chains (and their forks)
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// `aggregate3((address target, bool allowFailure, bytes callData)[] calls)`
+pub const AGGREGATE3_SIGNATURE: &str = "aggregate3((address,bool,bytes)[])";
+
+/// Largest batch a single aggregator transaction may carry
+pub const MAX_BATCH_SIZE: usize = 256;
+
+/// Arguments of an `aggregate3` call running every calldata against `target`.
+/// Failures are allowed, so one reverting sub-call does not revert the batch.
+pub fn encode_aggregate3(target: &str, calldatas: &[Vec<u8>]) -> Result<Vec<u8>> {
+    let target: Address = target.parse().with_context(|| format!("Invalid target address {}", target))?;
+    let calls = calldatas.iter()
+        .map(|calldata| Token::Tuple(vec![
+            Token::Address(target),
+            Token::Bool(true),
+            Token::Bytes(calldata.clone()),
+        ]))
+        .collect();
+    Ok(abi::encode(&[Token::Array(calls)]))
+}
+
+/// `(success, returnData)` of every sub-call from the output of `aggregate3`
+pub fn decode_aggregate3(output: &[u8]) -> Result<Vec<(bool, Vec<u8>)>> {
+    let result_type = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let tokens = abi::decode(&[result_type], output).context("Malformed aggregate3 output")?;
+    let Some(Token::Array(results)) = tokens.into_iter().next() else {
+        return Err(anyhow!("Malformed aggregate3 output"));
+    };
+    results.into_iter()
+        .map(|result| match result {
+            Token::Tuple(fields) => match fields.as_slice() {
+                [Token::Bool(success), Token::Bytes(data)] => Ok((*success, data.clone())),
+                _ => Err(anyhow!("Malformed aggregate3 result")),
+            },
+            _ => Err(anyhow!("Malformed aggregate3 result")),
+        })
+        .collect()
+}
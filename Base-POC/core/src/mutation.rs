@@ -0,0 +1,251 @@
+//! Input strategies of an exploration iteration. Random generation draws every argument from
+//! scratch; corpus mutation takes an input that was interesting before (it reached new branches
+//! with `--coverage`, or a new outcome with `--mutate`) and makes structured edits to it: bit
+//! flips, nudges around type boundaries, array splices, arguments crossed over from another corpus
+//! input, and a different sender.
+
+use crate::coverage::{Corpus, CorpusEntry};
+use crate::generator::{self, GeneratorContext, IterationPlan, PlanOptions};
+use crate::types::{ArgProvenance, MethodParameter, SolidityType, SolidityValue};
+use ethers::types::{I256, U256};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Share of iterations that mutate a corpus input (once there is one) instead of drawing fresh values
+const MUTATION_PERCENT: u32 = 75;
+
+/// Arrays are not grown past this many elements by splicing
+const MAX_ARRAY_LENGTH: usize = 32;
+
+/// How the arguments and sender of an iteration are chosen. Strategies are asked in order; the
+/// first one that returns a plan decides the iteration.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// Plan the iteration from its seed, or `None` to leave it to the next strategy
+    fn plan(&self, parameters: &[MethodParameter], seed: u64, context: &GeneratorContext, options: &PlanOptions) -> Option<IterationPlan>;
+}
+
+/// Fresh values for every argument; always plans, so it goes last
+pub struct RandomGeneration;
+
+impl Strategy for RandomGeneration {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn plan(&self, parameters: &[MethodParameter], seed: u64, context: &GeneratorContext, options: &PlanOptions) -> Option<IterationPlan> {
+        Some(generator::plan_iteration(parameters, seed, context, options))
+    }
+}
+
+/// A mutated input of the method's corpus. Fees, block context and `msg.value` are drawn as
+/// usual; arguments and sender come from the corpus input.
+pub struct CorpusMutation<'a> {
+    pub corpus: &'a Corpus,
+}
+
+impl Strategy for CorpusMutation<'_> {
+    fn name(&self) -> &'static str {
+        "mutation"
+    }
+
+    fn plan(&self, parameters: &[MethodParameter], seed: u64, context: &GeneratorContext, options: &PlanOptions) -> Option<IterationPlan> {
+        let entry = mutate(self.corpus, parameters, generator::derive_seed(seed, "mutation"), context)?;
+        let mut plan = generator::plan_iteration(parameters, seed, context, options);
+        plan.args = entry.args;
+        plan.provenance = entry.provenance;
+        plan.sender_index = entry.sender_index;
+        Some(plan)
+    }
+}
+
+/// A mutated corpus input for this iteration, or `None` when it should draw fresh values.
+/// Depends only on the seed and the corpus, so a campaign stays reproducible from its master seed.
+fn mutate(corpus: &Corpus, parameters: &[MethodParameter], seed: u64, context: &GeneratorContext) -> Option<CorpusEntry> {
+    let entries = corpus.entries();
+    if entries.is_empty() || parameters.is_empty() {
+        return None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    if rng.gen_range(0..100) >= MUTATION_PERCENT {
+        return None;
+    }
+
+    let mut entry = entries[rng.gen_range(0..entries.len())].clone();
+    // Usually a single argument, sometimes several (havoc)
+    let mutations = if rng.gen_bool(0.25) { rng.gen_range(2..=parameters.len().max(2)) } else { 1 };
+    for _ in 0..mutations {
+        let index = rng.gen_range(0..parameters.len());
+        let param_type = &parameters[index].param_type;
+        entry.args[index] = if entries.len() > 1 && rng.gen_bool(0.1) {
+            // Crossover: the same argument of another corpus input, spliced in if it is an array
+            let donor = &entries[rng.gen_range(0..entries.len())].args[index];
+            splice(&mut rng, &entry.args[index], donor)
+        } else {
+            mutate_value(&mut rng, param_type, &entry.args[index], context)
+        };
+        entry.provenance[index] = ArgProvenance::Mutation;
+    }
+    // Sender swap: the same input from another account (access checks, per-sender balances)
+    let accounts = context.known_addresses.len();
+    if accounts > 1 && rng.gen_bool(0.2) {
+        entry.sender_index = (entry.sender_index + rng.gen_range(1..accounts)) % accounts;
+    }
+    Some(entry)
+}
+
+/// The head of one array followed by the tail of the other; other values are taken from the donor
+fn splice(rng: &mut StdRng, value: &SolidityValue, donor: &SolidityValue) -> SolidityValue {
+    match (value, donor) {
+        (SolidityValue::Array(values), SolidityValue::Array(donor_values)) => {
+            let head = rng.gen_range(0..=values.len());
+            let tail = rng.gen_range(0..=donor_values.len());
+            let spliced = values[..head].iter().chain(&donor_values[tail..]).take(MAX_ARRAY_LENGTH).cloned().collect();
+            SolidityValue::Array(spliced)
+        }
+        // Same length on both sides: each element from one or the other
+        (SolidityValue::FixedArray(values), SolidityValue::FixedArray(donor_values)) if values.len() == donor_values.len() => {
+            let mixed = values.iter().zip(donor_values)
+                .map(|(value, donor_value)| if rng.gen_bool(0.5) { value.clone() } else { donor_value.clone() })
+                .collect();
+            SolidityValue::FixedArray(mixed)
+        }
+        _ => donor.clone(),
+    }
+}
+
+/// Small edit of a value: nudge or bit-flip numbers (also around their type's boundaries), flip
+/// bools and bytes, edit strings, splice arrays and mutate array elements and struct fields.
+/// Falls back to a fresh value of the type (splicing in the generator's edge cases).
+fn mutate_value(rng: &mut StdRng, sol_type: &SolidityType, value: &SolidityValue, context: &GeneratorContext) -> SolidityValue {
+    if rng.gen_bool(0.2) {
+        return generator::generate_value(rng, sol_type, context);
+    }
+    let delta = rng.gen_range(1..=16u8);
+    let bit = rng.gen_range(0..8u32);
+    match value {
+        SolidityValue::Uint8(v) => SolidityValue::Uint8(nudge(rng, *v as u128, u8::MAX as u128, delta, bit) as u8),
+        SolidityValue::Uint16(v) => SolidityValue::Uint16(nudge(rng, *v as u128, u16::MAX as u128, delta, bit) as u16),
+        SolidityValue::Uint32(v) => SolidityValue::Uint32(nudge(rng, *v as u128, u32::MAX as u128, delta, bit) as u32),
+        SolidityValue::Uint64(v) => SolidityValue::Uint64(nudge(rng, *v as u128, u64::MAX as u128, delta, bit) as u64),
+        SolidityValue::Uint128(v) => SolidityValue::Uint128(nudge(rng, *v, u128::MAX, delta, bit)),
+        SolidityValue::Uint256(v) => {
+            let v = U256::from_dec_str(v).unwrap_or_default();
+            let mutated = match rng.gen_range(0..5) {
+                0 => v.overflowing_add(U256::from(delta)).0,
+                1 => v.overflowing_sub(U256::from(delta)).0,
+                2 => v ^ (U256::one() << rng.gen_range(0..256usize)),
+                3 => U256::MAX - U256::from(delta - 1),
+                _ => v / 2,
+            };
+            SolidityValue::Uint256(mutated.to_string())
+        }
+        SolidityValue::Int8(v) => SolidityValue::Int8(nudge_signed(rng, *v as i128, i8::MIN as i128, i8::MAX as i128, delta) as i8),
+        SolidityValue::Int16(v) => SolidityValue::Int16(nudge_signed(rng, *v as i128, i16::MIN as i128, i16::MAX as i128, delta) as i16),
+        SolidityValue::Int32(v) => SolidityValue::Int32(nudge_signed(rng, *v as i128, i32::MIN as i128, i32::MAX as i128, delta) as i32),
+        SolidityValue::Int64(v) => SolidityValue::Int64(nudge_signed(rng, *v as i128, i64::MIN as i128, i64::MAX as i128, delta) as i64),
+        SolidityValue::Int128(v) => SolidityValue::Int128(nudge_signed(rng, *v, i128::MIN, i128::MAX, delta)),
+        SolidityValue::Int256(v) => {
+            let v = I256::from_dec_str(v).unwrap_or_default();
+            let mutated = match rng.gen_range(0..4) {
+                0 => I256::MIN.overflowing_add(I256::from(delta - 1)).0,
+                1 => I256::MAX.overflowing_sub(I256::from(delta - 1)).0,
+                _ => v.overflowing_add(I256::from(signed_delta(rng, delta))).0,
+            };
+            SolidityValue::Int256(mutated.to_string())
+        }
+        SolidityValue::Bool(v) => SolidityValue::Bool(!v),
+        SolidityValue::Bytes1(v) => SolidityValue::Bytes1(flip_byte(rng, *v)),
+        SolidityValue::Bytes2(v) => SolidityValue::Bytes2(flip_byte(rng, *v)),
+        SolidityValue::Bytes4(v) => SolidityValue::Bytes4(flip_byte(rng, *v)),
+        SolidityValue::Bytes8(v) => SolidityValue::Bytes8(flip_byte(rng, *v)),
+        SolidityValue::Bytes16(v) => SolidityValue::Bytes16(flip_byte(rng, *v)),
+        SolidityValue::Bytes32(v) => SolidityValue::Bytes32(flip_byte(rng, *v)),
+        SolidityValue::Bytes(v) if !v.is_empty() => {
+            let mut v = v.clone();
+            let index = rng.gen_range(0..v.len());
+            v[index] ^= 1 << bit;
+            SolidityValue::Bytes(v)
+        }
+        SolidityValue::String(s) if !s.is_empty() && rng.gen_bool(0.5) => {
+            let mut s = s.clone();
+            s.pop();
+            SolidityValue::String(s)
+        }
+        SolidityValue::String(s) => SolidityValue::String(format!("{}{}", s, rng.gen_range('a'..='z'))),
+        SolidityValue::Array(values) => match sol_type {
+            SolidityType::Array(inner) => SolidityValue::Array(mutate_array(rng, inner, values, context)),
+            _ => generator::generate_value(rng, sol_type, context),
+        },
+        SolidityValue::FixedArray(values) if !values.is_empty() => match sol_type {
+            SolidityType::FixedArray(inner, _) => {
+                let mut values = values.clone();
+                let index = rng.gen_range(0..values.len());
+                values[index] = mutate_value(rng, inner, &values[index], context);
+                SolidityValue::FixedArray(values)
+            }
+            _ => generator::generate_value(rng, sol_type, context),
+        },
+        SolidityValue::Struct(fields) if !fields.is_empty() => match sol_type {
+            SolidityType::Struct(_, field_types) if field_types.len() == fields.len() => {
+                let mut fields = fields.clone();
+                let index = rng.gen_range(0..fields.len());
+                fields[index].1 = mutate_value(rng, &field_types[index].param_type, &fields[index].1, context);
+                SolidityValue::Struct(fields)
+            }
+            _ => generator::generate_value(rng, sol_type, context),
+        },
+        // Addresses: a fresh value of the type
+        _ => generator::generate_value(rng, sol_type, context),
+    }
+}
+
+/// Mutate, duplicate, drop or append one element
+fn mutate_array(rng: &mut StdRng, inner: &SolidityType, values: &[SolidityValue], context: &GeneratorContext) -> Vec<SolidityValue> {
+    let mut values = values.to_vec();
+    if values.is_empty() {
+        values.push(generator::generate_value(rng, inner, context));
+        return values;
+    }
+    let index = rng.gen_range(0..values.len());
+    match rng.gen_range(0..4) {
+        0 => values[index] = mutate_value(rng, inner, &values[index], context),
+        1 if values.len() < MAX_ARRAY_LENGTH => values.insert(index, values[index].clone()),
+        2 => {
+            values.remove(index);
+        }
+        _ if values.len() < MAX_ARRAY_LENGTH => values.push(generator::generate_value(rng, inner, context)),
+        _ => values[index] = mutate_value(rng, inner, &values[index], context),
+    }
+    values
+}
+
+/// Add or subtract a small delta, flip a bit, or land just inside the type's maximum
+fn nudge(rng: &mut StdRng, value: u128, max: u128, delta: u8, bit: u32) -> u128 {
+    match rng.gen_range(0..4) {
+        0 => value.wrapping_add(delta as u128),
+        1 => value.wrapping_sub(delta as u128),
+        2 => max - (delta as u128 - 1),
+        _ => value ^ (1 << bit),
+    }
+}
+
+/// Add or subtract a small delta, or land just inside the type's minimum or maximum
+fn nudge_signed(rng: &mut StdRng, value: i128, min: i128, max: i128, delta: u8) -> i128 {
+    match rng.gen_range(0..4) {
+        0 => min + (delta as i128 - 1),
+        1 => max - (delta as i128 - 1),
+        _ => value.wrapping_add(signed_delta(rng, delta) as i128),
+    }
+}
+
+fn signed_delta(rng: &mut StdRng, delta: u8) -> i64 {
+    if rng.gen_bool(0.5) { delta as i64 } else { -(delta as i64) }
+}
+
+fn flip_byte<const N: usize>(rng: &mut StdRng, mut bytes: [u8; N]) -> [u8; N] {
+    let index = rng.gen_range(0..N);
+    bytes[index] ^= 1 << rng.gen_range(0..8);
+    bytes
+}
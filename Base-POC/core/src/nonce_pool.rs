@@ -0,0 +1,136 @@
+use std::collections::{BTreeSet, HashMap};
+
+/// Nonces one sender may have pending at once
+pub const DEFAULT_RANGE_SIZE: u64 = 16;
+
+#[derive(Debug, Clone)]
+struct SenderNonces {
+    /// Next nonce to hand out
+    next: u64,
+    /// Handed out and submitted, receipt not seen yet
+    in_flight: BTreeSet<u64>,
+}
+
+/// Local nonce bookkeeping for every sender of an executor.
+///
+/// A nonce is reserved when its transaction is submitted, not when it is mined, so several
+/// transactions of one sender can be pending at the same time. Each sender owns a range of
+/// `range_size` nonces past its oldest pending one.
+#[derive(Debug, Clone)]
+pub struct NoncePool {
+    senders: HashMap<String, SenderNonces>,
+    range_size: u64,
+}
+
+impl NoncePool {
+    pub fn new(range_size: u64) -> Self {
+        Self {
+            senders: HashMap::new(),
+            range_size: range_size.max(1),
+        }
+    }
+
+    /// Track `sender`, whose next nonce on chain is `nonce`
+    pub fn add_sender(&mut self, sender: &str, nonce: u64) {
+        self.senders.insert(sender.to_string(), SenderNonces { next: nonce, in_flight: BTreeSet::new() });
+    }
+
+    /// Nonce the next transaction of `sender` will use
+    pub fn next_nonce(&self, sender: &str) -> u64 {
+        self.senders.get(sender).map(|nonces| nonces.next).unwrap_or(0)
+    }
+
+    /// Transactions of `sender` submitted but not mined yet
+    pub fn in_flight(&self, sender: &str) -> usize {
+        self.senders.get(sender).map(|nonces| nonces.in_flight.len()).unwrap_or(0)
+    }
+
+    /// Reserve the next nonce of `sender`, `None` once its range is used up by pending transactions
+    pub fn reserve(&mut self, sender: &str) -> Option<u64> {
+        let range_size = self.range_size;
+        let nonces = self.senders.get_mut(sender)?;
+        let oldest = nonces.in_flight.first().copied().unwrap_or(nonces.next);
+        if nonces.next - oldest >= range_size {
+            return None;
+        }
+        let nonce = nonces.next;
+        nonces.next += 1;
+        nonces.in_flight.insert(nonce);
+        Some(nonce)
+    }
+
+    /// The transaction with this nonce was mined (reverted or not)
+    pub fn confirm(&mut self, sender: &str, nonce: u64) {
+        if let Some(nonces) = self.senders.get_mut(sender) {
+            nonces.in_flight.remove(&nonce);
+        }
+    }
+
+    /// The transaction with this nonce never reached the pool, or its receipt never came. Returns false when later nonces are
+    /// already pending, which leaves a gap they are stuck behind until the sender is resynced.
+    pub fn release(&mut self, sender: &str, nonce: u64) -> bool {
+        let Some(nonces) = self.senders.get_mut(sender) else {
+            return true;
+        };
+        nonces.in_flight.remove(&nonce);
+        if nonces.next == nonce + 1 {
+            nonces.next = nonce;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adopt the node's view of `sender` (after a snapshot revert or a nonce error), forgetting
+    /// everything pending
+    pub fn resync(&mut self, sender: &str, nonce: u64) {
+        self.add_sender(sender, nonce);
+    }
+}
+
+impl Default for NoncePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_RANGE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "0x00000000000000000000000000000000000000a1";
+
+    #[test]
+    fn reserves_up_to_the_range_size() {
+        let mut pool = NoncePool::new(2);
+        pool.add_sender(SENDER, 5);
+        assert_eq!(pool.reserve(SENDER), Some(5));
+        assert_eq!(pool.reserve(SENDER), Some(6));
+        assert_eq!(pool.reserve(SENDER), None);
+
+        pool.confirm(SENDER, 5);
+        assert_eq!(pool.reserve(SENDER), Some(7));
+        assert_eq!(pool.in_flight(SENDER), 2);
+    }
+
+    #[test]
+    fn releasing_frees_the_slot() {
+        let mut pool = NoncePool::new(1);
+        pool.add_sender(SENDER, 0);
+        assert_eq!(pool.reserve(SENDER), Some(0));
+        assert!(pool.release(SENDER, 0));
+        assert_eq!(pool.in_flight(SENDER), 0);
+        assert_eq!(pool.reserve(SENDER), Some(0));
+    }
+
+    #[test]
+    fn releasing_behind_a_pending_nonce_reports_a_gap() {
+        let mut pool = NoncePool::new(4);
+        pool.add_sender(SENDER, 0);
+        pool.reserve(SENDER);
+        pool.reserve(SENDER);
+        assert!(!pool.release(SENDER, 0));
+        assert_eq!(pool.in_flight(SENDER), 1);
+        assert_eq!(pool.next_nonce(SENDER), 2);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{abi_encoding, harness};
+use ethers::abi::{ethabi, Abi};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A Solidity compiler version such as `0.8.19`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SolidityVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SolidityVersion {
+    /// Parse the first version number in a compiler version string (`0.8.19+commit.7dd6d404`)
+    /// or pragma constraint (`^0.8.0`, `>=0.6.0 <0.8.0`, where the lower bound is taken)
+    pub fn parse(text: &str) -> Option<Self> {
+        let start = text.find(|c: char| c.is_ascii_digit())?;
+        let numbers: Vec<u32> = text[start..]
+            .split(|c: char| !c.is_ascii_digit() && c != '.')
+            .next()?
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+
+        match numbers.as_slice() {
+            [major, minor, patch, ..] => Some(Self { major: *major, minor: *minor, patch: *patch }),
+            [major, minor] => Some(Self { major: *major, minor: *minor, patch: 0 }),
+            _ => None,
+        }
+    }
+
+    /// Whether this version satisfies a `pragma solidity` constraint such as `^0.8.0`,
+    /// `>=0.6.0 <0.8.0` or `0.7.6 || ^0.8.0`. `None` when the constraint cannot be parsed.
+    pub fn satisfies(&self, pragma: &str) -> Option<bool> {
+        let version = semver::Version::new(self.major as u64, self.minor as u64, self.patch as u64);
+        let mut satisfied = false;
+        for alternative in pragma.split("||") {
+            let mut comparators = Vec::new();
+            let mut operator = String::new();
+            for token in alternative.split_whitespace() {
+                // `>= 0.6.0` is valid Solidity; glue the operator back onto its version
+                if token.chars().all(|c| "<>=^~".contains(c)) {
+                    operator.push_str(token);
+                    continue;
+                }
+                let mut comparator = std::mem::take(&mut operator) + token;
+                if comparator.starts_with(|c: char| c.is_ascii_digit()) {
+                    // A bare version pins the compiler exactly in Solidity (it means `^` in semver)
+                    comparator.insert(0, '=');
+                }
+                comparators.push(comparator);
+            }
+            if comparators.is_empty() {
+                return None;
+            }
+            satisfied |= semver::VersionReq::parse(&comparators.join(", ")).ok()?.matches(&version);
+        }
+        Some(satisfied)
+    }
+
+    /// Solidity 0.8 made arithmetic checked by default: overflows revert with `Panic(0x11)`
+    pub fn has_checked_arithmetic(&self) -> bool {
+        (self.major, self.minor) >= (0, 8)
+    }
+}
+
+impl fmt::Display for SolidityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// How a target's compiler version changes the interpretation of execution outcomes
+#[derive(Debug, Clone)]
+pub struct OracleProfile {
+    /// Version the target was compiled with (or the pragma's lower bound when unknown)
+    pub version: Option<SolidityVersion>,
+    /// Overflow panics (`Panic(0x11)`) are the compiler's checked math working as intended, not findings
+    pub expect_checked_math_panics: bool,
+    /// Arithmetic wraps silently, so overflows never show up as reverts and need a trace-based detector
+    pub wraparound_detection: bool,
+    /// Lowercased revert reason fragments of checks doing their job, counted as expected reverts
+    pub expected_reverts: Vec<String>,
+    /// Panic codes always reported as findings, even when checked math or a pattern would expect them
+    pub bug_panics: Vec<u64>,
+}
+
+/// Revert reasons of access control, pausing, reentrancy guards and initializers working as
+/// intended: the OpenZeppelin messages and custom errors
+pub const DEFAULT_EXPECTED_REVERTS: [&str; 10] = [
+    "Ownable: caller is not the owner",
+    "OwnableUnauthorizedAccount",
+    "AccessControl: account",
+    "AccessControlUnauthorizedAccount",
+    "Pausable: paused",
+    "EnforcedPause",
+    "ReentrancyGuard: reentrant call",
+    "ReentrancyGuardReentrantCall",
+    "Initializable: contract is already initialized",
+    "InvalidInitialization",
+];
+
+/// `Panic(0x01)`: a failed `assert`, reported whatever else is configured
+pub const ASSERTION_PANIC: u64 = 0x01;
+
+/// How a revert counts in the campaign's totals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertClass {
+    /// Reported as a finding
+    Finding,
+    /// Overflow panic of checked arithmetic (Solidity >=0.8)
+    CheckedMath,
+    /// Reason matched an expected-revert pattern
+    Expected,
+    /// `vm.assume` of a Foundry fuzz harness turned the input away; counted as a skipped run
+    Rejected,
+}
+
+impl OracleProfile {
+    pub fn for_version(version: Option<SolidityVersion>) -> Self {
+        let checked = version.map(|v| v.has_checked_arithmetic());
+        Self {
+            version,
+            expect_checked_math_panics: checked == Some(true),
+            wraparound_detection: checked == Some(false),
+            expected_reverts: DEFAULT_EXPECTED_REVERTS.iter().map(|pattern| pattern.to_lowercase()).collect(),
+            bug_panics: vec![ASSERTION_PANIC],
+        }
+    }
+
+    /// Add the `--expected-revert` patterns and `--bug-panic` codes of the campaign
+    pub fn with_revert_rules(mut self, expected_reverts: &[String], bug_panics: &[u64]) -> Self {
+        self.expected_reverts.extend(expected_reverts.iter().map(|pattern| pattern.to_lowercase()));
+        self.bug_panics.extend(bug_panics);
+        self
+    }
+
+    /// Whether a revert is a finding, checked math doing its job, an expected revert or a rejected
+    /// input. Bug panic codes win over both, so `--bug-panic 0x11` reports overflows on Solidity >=0.8.
+    pub fn classify_revert(&self, error: &str) -> RevertClass {
+        if harness::is_assume_rejection(error) {
+            return RevertClass::Rejected;
+        }
+        if panic_code(error).is_some_and(|code| self.bug_panics.contains(&code)) {
+            return RevertClass::Finding;
+        }
+        if self.expect_checked_math_panics && is_checked_math_panic(error) {
+            return RevertClass::CheckedMath;
+        }
+        let error = error.to_lowercase();
+        if self.expected_reverts.iter().any(|pattern| error.contains(pattern.as_str())) {
+            RevertClass::Expected
+        } else {
+            RevertClass::Finding
+        }
+    }
+
+    /// Whether a revert is expected behavior for this target rather than a finding
+    pub fn is_expected_revert(&self, error: &str) -> bool {
+        self.classify_revert(error) != RevertClass::Finding
+    }
+
+    /// One-line description printed when fuzzing of a contract starts
+    pub fn describe(&self) -> String {
+        match self.version {
+            Some(version) if self.expect_checked_math_panics && self.bug_panics.contains(&0x11) => {
+                format!("Solidity {}: checked arithmetic (overflow panics reported as findings)", version)
+            }
+            Some(version) if self.expect_checked_math_panics => {
+                format!("Solidity {}: checked arithmetic (overflow panics are expected reverts)", version)
+            }
+            Some(version) => {
+                format!("Solidity {}: unchecked arithmetic (overflows wrap silently, wraparound detection enabled)", version)
+            }
+            None => "Solidity version unknown: every unexpected revert is reported".to_string(),
+        }
+    }
+}
+
+/// `Panic(0x11)`: arithmetic underflow or overflow in checked math
+fn is_checked_math_panic(error: &str) -> bool {
+    let msg = error.to_lowercase();
+    msg.contains("arithmetic underflow or overflow")
+        || (msg.contains("panic") && msg.contains("0x11"))
+        || msg.contains("0x4e487b710000000000000000000000000000000000000000000000000000000000000011")
+}
+
+/// Code of a Solidity panic, from a decoded reason (`panic: … (0x11)`) or raw `Panic(uint256)` data
+pub fn panic_code(error: &str) -> Option<u64> {
+    let msg = error.to_lowercase();
+    if let Some(start) = msg.find("4e487b71") {
+        let code = msg.get(start + 8..start + 8 + 64)?;
+        return u64::from_str_radix(&code[48..], 16).ok();
+    }
+    let after = &msg[msg.find("panic")?..];
+    let code = &after[after.find("(0x")? + 3..];
+    u64::from_str_radix(&code[..code.find(')')?], 16).ok()
+}
+
+/// A panic code as given to `--bug-panic`: hex (`0x11`) or decimal (`17`)
+pub fn parse_panic_code(text: &str) -> Result<u64, String> {
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    parsed.map_err(|_| format!("invalid panic code '{}' (expected e.g. 0x11 or 17)", text))
+}
+
+/// Custom errors declared in the ABIs of the contracts under test, by selector
+#[derive(Debug, Clone, Default)]
+pub struct CustomErrors {
+    errors: HashMap<[u8; 4], ethabi::AbiError>,
+}
+
+impl CustomErrors {
+    pub fn add(&mut self, abi: &Abi) {
+        for error in abi.errors() {
+            let selector = error.signature()[..4].try_into().expect("keccak256 output is 32 bytes");
+            self.errors.insert(selector, error.clone());
+        }
+    }
+
+    /// `Name(arg, ...)` for revert data of a known custom error
+    fn decode(&self, selector: &[u8], payload: &[u8]) -> Option<String> {
+        let error = self.errors.get(selector)?;
+        let tokens = error.decode(payload).ok()?;
+        let args: Vec<String> = tokens.iter().map(abi_encoding::format_token).collect();
+        Some(format!("{}({})", error.name, args.join(", ")))
+    }
+}
+
+/// Human-readable revert reason from revert data, in the style of Anvil's error messages.
+/// Custom errors are decoded with their arguments when `errors` declares them.
+pub fn decode_revert(output: &[u8], errors: &CustomErrors) -> String {
+    use ethers::abi::{decode, ParamType, Token};
+
+    if output.len() < 4 {
+        return "execution reverted".to_string();
+    }
+    let (selector, payload) = output.split_at(4);
+    match selector {
+        // Error(string)
+        [0x08, 0xc3, 0x79, 0xa0] => match decode(&[ParamType::String], payload).ok().and_then(|t| t.into_iter().next()) {
+            Some(Token::String(reason)) => reason,
+            _ => format!("execution reverted: 0x{}", hex::encode(output)),
+        },
+        // Panic(uint256)
+        [0x4e, 0x48, 0x7b, 0x71] => match decode(&[ParamType::Uint(256)], payload).ok().and_then(|t| t.into_iter().next()) {
+            Some(Token::Uint(code)) => format!("panic: {} (0x{:02x})", panic_description(code.low_u64()), code),
+            _ => format!("execution reverted: 0x{}", hex::encode(output)),
+        },
+        _ => match errors.decode(selector, payload) {
+            Some(error) => format!("custom error {}", error),
+            None => format!("custom error 0x{}", hex::encode(output)),
+        },
+    }
+}
+
+/// What failing calls are grouped by: the revert reason, without the arguments of a custom error
+/// (its name, or its selector when it could not be decoded) or of the event an event oracle finding names
+pub fn revert_group(error: &str) -> &str {
+    const CUSTOM_ERROR: &str = "custom error ";
+    if error.starts_with(crate::events::EVENT_ORACLE) {
+        return &error[..error.find('(').unwrap_or(error.len())];
+    }
+    match error.strip_prefix(CUSTOM_ERROR) {
+        Some(rest) if rest.starts_with("0x") => &error[..error.len().min(CUSTOM_ERROR.len() + 10)],
+        Some(rest) => &error[..CUSTOM_ERROR.len() + rest.find('(').unwrap_or(rest.len())],
+        None => error,
+    }
+}
+
+/// Meaning of a Solidity `Panic(uint256)` code
+fn panic_description(code: u64) -> &'static str {
+    match code {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic underflow or overflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic code",
+    }
+}
@@ -0,0 +1,72 @@
+use crate::types::FuzzSummary;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Directory layout of `--out-dir`:
+///
+/// ```text
+/// <out>/report.json
+/// <out>/<source file stem>/<Contract>/attacks/   attack recipes
+/// <out>/<source file stem>/<Contract>/txs/       transaction dumps
+/// <out>/<source file stem>/<Contract>/pocs/      Foundry PoC tests
+/// ```
+///
+/// Paths only depend on the source file and contract names, so repeated campaigns over the same
+/// inputs write to the same places.
+#[derive(Debug, Clone)]
+pub struct OutputLayout {
+    root: PathBuf,
+}
+
+impl OutputLayout {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn report_file(&self) -> PathBuf {
+        self.root.join("report.json")
+    }
+
+    /// `<out>/<source file stem>/<Contract>`; the file stem keeps same-named contracts of different files apart
+    pub fn contract_dir(&self, source_file: &Path, contract: &str) -> PathBuf {
+        let stem = source_file.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "input".to_string());
+        self.root.join(path_component(&stem)).join(path_component(contract))
+    }
+
+    pub fn attacks_dir(&self, source_file: &Path, contract: &str) -> PathBuf {
+        self.contract_dir(source_file, contract).join("attacks")
+    }
+
+    pub fn transactions_dir(&self, source_file: &Path, contract: &str) -> PathBuf {
+        self.contract_dir(source_file, contract).join("txs")
+    }
+
+    pub fn pocs_dir(&self, source_file: &Path, contract: &str) -> PathBuf {
+        self.contract_dir(source_file, contract).join("pocs")
+    }
+}
+
+/// Split a file's summary into one summary per contract with findings, each holding only that
+/// contract's findings, so per-contract artifacts can be written with the existing writers
+pub fn split_by_contract(summary: &FuzzSummary) -> BTreeMap<String, FuzzSummary> {
+    let mut by_contract: BTreeMap<String, FuzzSummary> = BTreeMap::new();
+    for finding in &summary.findings {
+        by_contract.entry(finding.contract.clone())
+            .or_insert_with(|| FuzzSummary { findings: Vec::new(), ..summary.clone() })
+            .findings
+            .push(finding.clone());
+    }
+    by_contract
+}
+
+fn path_component(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' { c } else { '_' })
+        .collect()
+}
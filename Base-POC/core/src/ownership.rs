@@ -0,0 +1,53 @@
+use crate::executor::Executor;
+use ethers::abi::{Abi, ParamType, Token};
+use ethers::types::Address;
+
+/// Parameterless getters that conventionally return a contract's privileged account, in the order they are tried
+const OWNER_GETTERS: [&str; 5] = ["owner", "getOwner", "admin", "governance", "governor"];
+
+/// Privileged account of a deployed contract, read from its owner getter
+#[derive(Debug, Clone)]
+pub struct ContractOwner {
+    /// Getter the address was read from, e.g. `owner`
+    pub getter: String,
+    pub address: String,
+}
+
+/// Read the owner of a deployed contract from the first owner-style getter in its ABI that returns
+/// a single address. A zero address (renounced ownership) or an unreadable getter is no owner.
+pub async fn read_owner(executor: &mut dyn Executor, contract_name: &str, abi: &Abi) -> Option<ContractOwner> {
+    for getter in OWNER_GETTERS {
+        let Some(function) = abi.functions().find(|function| {
+            function.name == getter && function.inputs.is_empty()
+                && function.outputs.len() == 1 && function.outputs[0].kind == ParamType::Address
+        }) else {
+            continue;
+        };
+        let Ok(output) = executor.static_call(contract_name, &function.short_signature()).await else {
+            continue;
+        };
+        match function.decode_output(&output).as_deref() {
+            Ok([Token::Address(address)]) if !address.is_zero() => {
+                return Some(ContractOwner {
+                    getter: getter.to_string(),
+                    address: format!("{:?}", address),
+                });
+            }
+            _ => continue,
+        }
+    }
+    None
+}
+
+/// Whether the owner is an account the fuzzer does not already send from (a mainnet EOA or
+/// multisig on a fork, or an address passed to the constructor)
+pub fn is_external(owner: &ContractOwner, accounts: &[String]) -> bool {
+    !accounts.iter().any(|account| account.eq_ignore_ascii_case(&owner.address))
+}
+
+/// Parse an account given with `--impersonate` or in `fuzzhead.toml`
+pub fn parse_account(value: &str) -> Result<String, String> {
+    value.trim().parse::<Address>()
+        .map(|address| format!("{:?}", address))
+        .map_err(|_| format!("'{}' is not an address (expected 0x followed by 40 hex digits)", value))
+}
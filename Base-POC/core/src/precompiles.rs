@@ -0,0 +1,110 @@
+use crate::types::{MethodParameter, SolidityValue};
+
+/// What lives at a reserved address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemKind {
+    /// Native code without EVM bytecode; calls succeed with whatever the precompile returns
+    Precompile,
+    /// Contract predeployed by the chain at a fixed address
+    SystemContract,
+}
+
+/// A precompile or system contract address of a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemAddress {
+    pub address: &'static str,
+    pub name: &'static str,
+    pub kind: SystemKind,
+}
+
+impl std::fmt::Display for SystemAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            SystemKind::Precompile => "precompile",
+            SystemKind::SystemContract => "system contract",
+        };
+        write!(f, "{} ({} {})", self.address, self.name, kind)
+    }
+}
+
+const fn precompile(address: &'static str, name: &'static str) -> SystemAddress {
+    SystemAddress { address, name, kind: SystemKind::Precompile }
+}
+
+const fn system_contract(address: &'static str, name: &'static str) -> SystemAddress {
+    SystemAddress { address, name, kind: SystemKind::SystemContract }
+}
+
+/// Precompiles of every EVM chain, up to Cancun's point evaluation
+const ETHEREUM: [SystemAddress; 10] = [
+    precompile("0x0000000000000000000000000000000000000001", "ecRecover"),
+    precompile("0x0000000000000000000000000000000000000002", "sha256"),
+    precompile("0x0000000000000000000000000000000000000003", "ripemd160"),
+    precompile("0x0000000000000000000000000000000000000004", "identity"),
+    precompile("0x0000000000000000000000000000000000000005", "modexp"),
+    precompile("0x0000000000000000000000000000000000000006", "ecAdd"),
+    precompile("0x0000000000000000000000000000000000000007", "ecMul"),
+    precompile("0x0000000000000000000000000000000000000008", "ecPairing"),
+    precompile("0x0000000000000000000000000000000000000009", "blake2f"),
+    precompile("0x000000000000000000000000000000000000000a", "pointEvaluation"),
+];
+
+/// RIP-7212 and the predeploys of OP Stack chains (Base, OP Mainnet and their testnets)
+const OP_STACK: [SystemAddress; 8] = [
+    precompile("0x0000000000000000000000000000000000000100", "P256VERIFY"),
+    system_contract("0x4200000000000000000000000000000000000006", "WETH9"),
+    system_contract("0x4200000000000000000000000000000000000007", "L2CrossDomainMessenger"),
+    system_contract("0x420000000000000000000000000000000000000f", "GasPriceOracle"),
+    system_contract("0x4200000000000000000000000000000000000010", "L2StandardBridge"),
+    system_contract("0x4200000000000000000000000000000000000011", "SequencerFeeVault"),
+    system_contract("0x4200000000000000000000000000000000000015", "L1Block"),
+    system_contract("0x4200000000000000000000000000000000000016", "L2ToL1MessagePasser"),
+];
+
+/// Native smart contracts of Horizen EON and its Gobi testnet
+const HORIZEN_EON: [SystemAddress; 2] = [
+    system_contract("0x0000000000000000000022222222222222222222", "ForgerStake"),
+    system_contract("0x0000000000000000000011111111111111111111", "WithdrawalRequest"),
+];
+
+/// Precompiles of every EVM chain, 0x01 to 0x0a
+pub fn ethereum() -> &'static [SystemAddress] {
+    &ETHEREUM
+}
+
+/// Precompiles and system contracts of a chain: Ethereum's precompiles everywhere, plus the
+/// chain's own where it is known. An unknown chain id (Anvil's 31337 included) gets Ethereum's only.
+pub fn for_chain(chain_id: Option<u64>) -> Vec<SystemAddress> {
+    let mut addresses = ETHEREUM.to_vec();
+    match chain_id {
+        Some(10 | 8453 | 84532 | 11155420) => addresses.extend(OP_STACK),
+        Some(7332 | 1663) => addresses.extend(HORIZEN_EON),
+        _ => {}
+    }
+    addresses
+}
+
+/// Precompile or system contract at `address`, if it is one of `known`
+pub fn lookup<'a>(known: &'a [SystemAddress], address: &str) -> Option<&'a SystemAddress> {
+    known.iter().find(|system| system.address.eq_ignore_ascii_case(address))
+}
+
+/// Parameters whose argument is (or, for arrays, contains) a precompile or system contract
+pub fn in_args<'a>(parameters: &'a [MethodParameter], args: &[SolidityValue], known: &'a [SystemAddress]) -> Vec<(&'a str, &'a SystemAddress)> {
+    let mut found = Vec::new();
+    for (param, arg) in parameters.iter().zip(args) {
+        let mut pending = vec![arg];
+        while let Some(value) = pending.pop() {
+            match value {
+                SolidityValue::Address(address) => {
+                    if let Some(system) = lookup(known, address) {
+                        found.push((param.name.as_str(), system));
+                    }
+                }
+                SolidityValue::Array(items) | SolidityValue::FixedArray(items) => pending.extend(items),
+                _ => {}
+            }
+        }
+    }
+    found
+}
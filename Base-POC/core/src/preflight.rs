@@ -0,0 +1,302 @@
+use crate::anvil_executor::AnvilForkExecutor;
+use crate::ast_parser::SolidityParser;
+use crate::config::{AccountOptions, Backend, CompilerBackend, CompilerOptions, FuzzConfig, SolcSelection};
+use crate::contract_compiler::ContractCompiler;
+use crate::oracle::SolidityVersion;
+use crate::solc_manager;
+use anyhow::Context;
+use ethers::types::U256;
+use serde_json::json;
+use std::fmt;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// The campaign can run, but something will be missing or degraded
+    Warning,
+    /// The campaign would fail midway
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// `RPC`, `Compiler` or `Constructor`
+    pub area: &'static str,
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// Everything the preflight phase found, printed as one report before the campaign starts
+#[derive(Debug, Default)]
+pub struct EnvironmentReport {
+    pub checks: Vec<Check>,
+}
+
+impl EnvironmentReport {
+    fn push(&mut self, area: &'static str, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(Check { area, name: name.into(), status, detail: detail.into() });
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.checks.iter().any(|check| check.status == CheckStatus::Failed)
+    }
+}
+
+impl fmt::Display for EnvironmentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "🩺 Environment report:")?;
+        for check in &self.checks {
+            let icon = match check.status {
+                CheckStatus::Ok => "✅",
+                CheckStatus::Warning => "⚠️ ",
+                CheckStatus::Failed => "❌",
+            };
+            writeln!(f, "   {} [{}] {}: {}", icon, check.area, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validate the RPC endpoints, the compiler and the constructors of every input file before any
+/// contract is compiled or deployed. RPC checks are skipped in dry runs, and the exploration
+/// endpoint is not checked on the in-process revm backend.
+pub async fn run(config: &FuzzConfig, client: &reqwest::Client, files: &[PathBuf]) -> EnvironmentReport {
+    let mut report = EnvironmentReport::default();
+
+    if !config.dry_run {
+        if config.backend == Backend::Anvil {
+            check_rpc(&mut report, client, "fork", &config.fork_url, &config.accounts).await;
+        }
+        if let Some(confirm_url) = &config.confirm_fork_url {
+            check_rpc(&mut report, client, "confirmation fork", confirm_url, &config.accounts).await;
+        }
+    }
+
+    let sources: Vec<(PathBuf, String)> = files.iter()
+        .filter_map(|file| match std::fs::read_to_string(file) {
+            Ok(source) => Some((file.clone(), source)),
+            Err(e) => {
+                report.push("Source", file.display().to_string(), CheckStatus::Failed, format!("unreadable: {}", e));
+                None
+            }
+        })
+        .collect();
+    // A live target (`--target-address`) has no sources and is never compiled
+    if !files.is_empty() {
+        check_compiler(&mut report, &sources, &config.compiler);
+    }
+    check_constructors(&mut report, &sources, config);
+
+    report
+}
+
+async fn check_rpc(report: &mut EnvironmentReport, client: &reqwest::Client, label: &str, url: &str, accounts: &AccountOptions) {
+    let rpc = |method: &'static str, params: serde_json::Value| AnvilForkExecutor::rpc_call(client, url, method, params);
+
+    match rpc("eth_chainId", json!([])).await {
+        Ok(chain_id) => {
+            let chain_id = chain_id.as_str()
+                .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| chain_id.to_string());
+            report.push("RPC", format!("{} chain id", label), CheckStatus::Ok, format!("{} at {}", chain_id, url));
+        }
+        Err(e) => {
+            // Nothing else can be checked on an unreachable endpoint
+            report.push("RPC", format!("{} reachable", label), CheckStatus::Failed, format!("{}: {}", url, e.root_cause()));
+            return;
+        }
+    }
+
+    let fork_block = match rpc("anvil_nodeInfo", json!([])).await {
+        Ok(info) => {
+            let block = info.get("forkConfig").and_then(|fork| fork.get("forkBlockNumber"));
+            let block = block.and_then(|block| {
+                block.as_u64()
+                    .or_else(|| block.as_str().and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok()))
+            });
+            match block {
+                Some(block) => report.push("RPC", format!("{} fork block", label), CheckStatus::Ok, block.to_string()),
+                None => report.push("RPC", format!("{} fork block", label), CheckStatus::Warning,
+                    "Anvil is not forking a chain; the campaign runs against an empty local chain"),
+            }
+            block
+        }
+        Err(e) if !accounts.keys.is_empty() => {
+            // Signed transactions are how nodes other than Anvil are driven
+            report.push("RPC", format!("{} Anvil cheats", label), CheckStatus::Warning,
+                format!("anvil_nodeInfo failed ({:#}); transactions are signed with the loaded keys, but iterations cannot be isolated with snapshots", e));
+            None
+        }
+        Err(e) => {
+            report.push("RPC", format!("{} Anvil cheats", label), CheckStatus::Failed,
+                format!("anvil_nodeInfo failed ({:#}); impersonation, snapshots and block-context cheats need Anvil", e));
+            None
+        }
+    };
+
+    if let Some(block) = fork_block {
+        // State the fork has not cached yet is fetched from upstream at the fork block, which needs
+        // an archive node once that block is older than the upstream's pruning window
+        match rpc("eth_getBalance", json!(["0x000000000000000000000000000000000000dEaD", format!("0x{:x}", block)])).await {
+            Ok(_) => report.push("RPC", format!("{} state at fork block", label), CheckStatus::Ok, format!("readable at block {}", block)),
+            Err(e) => report.push("RPC", format!("{} state at fork block", label), CheckStatus::Failed,
+                format!("cannot read state at block {} ({:#}); fork from an archive node or a more recent block", block, e)),
+        }
+    }
+
+    if !accounts.keys.is_empty() {
+        check_key_balances(report, client, label, url, accounts).await;
+        return;
+    }
+
+    let derived_accounts = accounts.mnemonic.is_some();
+    let accounts = match rpc("eth_accounts", json!([])).await {
+        Ok(accounts) => accounts.as_array()
+            .map(|accounts| accounts.iter().filter_map(|a| a.as_str().map(str::to_string)).collect::<Vec<_>>())
+            .unwrap_or_default(),
+        Err(e) => {
+            report.push("RPC", format!("{} accounts", label), CheckStatus::Failed, format!("eth_accounts failed: {:#}", e));
+            return;
+        }
+    };
+    let Some(sender) = accounts.first() else {
+        if derived_accounts {
+            report.push("RPC", format!("{} accounts", label), CheckStatus::Ok, "none unlocked, senders derived from the mnemonic");
+        } else {
+            report.push("RPC", format!("{} accounts", label), CheckStatus::Failed, "the node has no unlocked accounts to send from");
+        }
+        return;
+    };
+    report.push("RPC", format!("{} accounts", label), CheckStatus::Ok, format!("{} unlocked", accounts.len()));
+
+    match rpc("eth_estimateGas", json!([{ "from": sender, "to": sender, "value": "0x0" }])).await {
+        Ok(_) => report.push("RPC", format!("{} send capability", label), CheckStatus::Ok, format!("transactions from {} accepted", sender)),
+        Err(e) => report.push("RPC", format!("{} send capability", label), CheckStatus::Failed, format!("{:#}", e)),
+    }
+}
+
+/// Nothing funds the accounts of signing keys, so each one needs ETH for gas already
+async fn check_key_balances(report: &mut EnvironmentReport, client: &reqwest::Client, label: &str, url: &str, accounts: &AccountOptions) {
+    for key in &accounts.keys {
+        let address = key.address();
+        let balance = AnvilForkExecutor::rpc_call(client, url, "eth_getBalance", json!([address, "latest"])).await
+            .and_then(|balance| {
+                let balance = balance.as_str().context("eth_getBalance returned no data")?;
+                U256::from_str_radix(balance.trim_start_matches("0x"), 16).context("eth_getBalance returned invalid hex")
+            });
+        match balance {
+            Ok(balance) if balance.is_zero() => report.push("RPC", format!("{} key {}", label, address), CheckStatus::Failed,
+                format!("{} holds no ETH to pay for gas; fund it first", key.origin)),
+            Ok(balance) => report.push("RPC", format!("{} key {}", label, address), CheckStatus::Ok,
+                format!("{} wei", balance)),
+            Err(e) => report.push("RPC", format!("{} key {}", label, address), CheckStatus::Failed,
+                format!("eth_getBalance failed: {:#}", e)),
+        }
+    }
+}
+
+fn check_compiler(report: &mut EnvironmentReport, sources: &[(PathBuf, String)], options: &CompilerOptions) {
+    let compiler = ContractCompiler::locate();
+    let backend = options.backend;
+
+    if let Some(forge) = compiler.forge_path().filter(|_| matches!(backend, CompilerBackend::Auto | CompilerBackend::Forge)) {
+        // forge installs the solc version each pragma asks for on its own
+        report.push("Compiler", "forge", CheckStatus::Ok,
+            format!("{} (solc resolved per pragma)", tool_version(forge).unwrap_or_else(|| forge.to_string())));
+        return;
+    }
+
+    let (solc, name, missing) = match backend {
+        CompilerBackend::Auto => (compiler.solc_path(), "forge/solc",
+            "neither forge nor solc found in PATH (install Foundry: curl -L https://foundry.paradigm.xyz | bash && foundryup)"),
+        CompilerBackend::Forge => (None, "forge",
+            "forge not found in PATH (install Foundry: curl -L https://foundry.paradigm.xyz | bash && foundryup)"),
+        CompilerBackend::Solc => (compiler.solc_path(), "solc", "solc not found in PATH"),
+        CompilerBackend::StandardJson => (compiler.standard_json_path(), "solc/solcjs",
+            "neither solc nor solcjs found in PATH (npm install -g solc installs solcjs)"),
+    };
+    let svm_dir = solc_manager::svm_dir().map_or_else(|| "~/.svm".to_string(), |dir| dir.display().to_string());
+    let managed = backend != CompilerBackend::Forge && options.solc == SolcSelection::Auto;
+    if let (SolcSelection::Version(version), true) = (&options.solc, backend != CompilerBackend::Forge) {
+        report.push("Compiler", "solc", CheckStatus::Ok, format!("{} (--solc-version), installed to {} when missing", version, svm_dir));
+        return;
+    }
+    let Some(solc) = solc else {
+        if managed {
+            report.push("Compiler", "solc", CheckStatus::Ok,
+                format!("none in PATH; a release satisfying each pragma is installed to {}", svm_dir));
+        } else {
+            report.push("Compiler", name, CheckStatus::Failed, missing);
+        }
+        return;
+    };
+    // solc prints `Version: 0.8.x+commit…` on its second line, solcjs only the version
+    let version_text = tool_version(solc).unwrap_or_default();
+    let Some(version) = version_text.lines().find_map(|line| line.strip_prefix("Version:").or(Some(line)).and_then(SolidityVersion::parse)) else {
+        report.push("Compiler", "solc", CheckStatus::Warning, format!("{} found, but its version could not be read", solc));
+        return;
+    };
+
+    let incompatible: Vec<String> = sources.iter()
+        .filter_map(|(file, source)| {
+            let pragma = SolidityParser::extract_pragma(source)?;
+            (version.satisfies(&pragma) == Some(false)).then(|| format!("{} ({})", file.display(), pragma))
+        })
+        .collect();
+    if incompatible.is_empty() {
+        report.push("Compiler", "solc", CheckStatus::Ok, format!("{} satisfies every pragma", version));
+    } else if managed {
+        report.push("Compiler", "solc", CheckStatus::Ok,
+            format!("{} in PATH; releases matching {} are installed to {}", version, incompatible.join(", "), svm_dir));
+    } else {
+        report.push("Compiler", "solc", CheckStatus::Failed,
+            format!("{} does not satisfy the pragma of {}; install forge or a matching solc", version, incompatible.join(", ")));
+    }
+}
+
+/// Constructor arguments are given (flag or configuration) or prompted for at deployment; make sure that can actually happen
+fn check_constructors(report: &mut EnvironmentReport, sources: &[(PathBuf, String)], config: &FuzzConfig) {
+    let mut parser = SolidityParser::with_inherited_methods(config.all_contracts);
+    let interactive = std::io::stdin().is_terminal();
+
+    for (file, source) in sources {
+        let Ok(contracts) = parser.parse_contract(source, &file.display().to_string()) else {
+            continue;
+        };
+        let fuzzed = contracts.iter()
+            .filter(|contract| contract.kind.is_deployable() && !config.excludes_contract(&contract.name))
+            .take(if config.all_contracts { usize::MAX } else { 1 });
+        for contract in fuzzed {
+            let Some(constructor) = contract.constructor.as_ref().filter(|constructor| !constructor.parameters.is_empty()) else {
+                continue;
+            };
+            // Every ABI type can be entered at the prompt (bytes, arrays and tuples as literals)
+            let name = format!("{} constructor", contract.name);
+            if let Some(values) = config.constructor_args_for(&contract.name) {
+                let (status, detail) = if values.len() == constructor.parameters.len() {
+                    (CheckStatus::Ok, format!("{} argument(s) given", values.len()))
+                } else {
+                    (CheckStatus::Failed, format!("needs {} argument(s), {} given", constructor.parameters.len(), values.len()))
+                };
+                report.push("Constructor", name, status, detail);
+            } else if !interactive {
+                report.push("Constructor", name, CheckStatus::Failed,
+                    format!("needs {} argument(s), but stdin is not a terminal to prompt for them (pass --constructor-args)", constructor.parameters.len()));
+            } else {
+                report.push("Constructor", name, CheckStatus::Ok,
+                    format!("{} argument(s), prompted before deployment", constructor.parameters.len()));
+            }
+        }
+    }
+}
+
+/// Output of `<tool> --version`
+fn tool_version(path: &str) -> Option<String> {
+    let output = Command::new(path).arg("--version").output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,161 @@
+//! `fuzzhead.toml`: campaign settings kept next to the contracts, so a project is fuzzed the same
+//! way every time without a long command line. It is found like `foundry.toml`, in the directory
+//! of the input and its ancestors. Flags and environment variables given on the command line take
+//! precedence over the file.
+//!
+//! ```toml
+//! fork_url = "http://localhost:8545"
+//! fork_block_number = 19000000
+//! runs = 200
+//!
+//! [senders]
+//! accounts = 5
+//! owner_percent = 20
+//! impersonate = ["0x47ac0Fb4F2D84898e4D9E7b4DaB3C24507a6D503"]
+//!
+//! [exclude]
+//! contracts = ["Mock*"]
+//! functions = ["Vault.emergencyWithdraw", "renounceOwnership()"]
+//!
+//! [reverts]
+//! expected = ["Vault: cooldown"]
+//! bug_panics = [0x11, 0x12]
+//!
+//! [constructor_args]
+//! Vault = ["0x5FbDB2315678afecb367f032d93F642f64180aa3", "1000"]
+//!
+//! [detectors]
+//! reentrancy = true
+//! access_control = true
+//!
+//! [compiler]
+//! backend = "standard-json"
+//! optimizer_runs = 1000
+//! remappings = ["@openzeppelin/=lib/openzeppelin-contracts/"]
+//!
+//! [address_weights]
+//! contracts = 30
+//! random = 10
+//!
+//! [strategies.transfer]
+//! amount = "range:1..1e18"
+//!
+//! [strategies."Vault.setFee"]
+//! bps = "values:0,1,9999,10000"
+//! ```
+
+use crate::address_pool::AddressWeights;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "fuzzhead.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub fork_url: Option<String>,
+    /// Upstream block forks are pinned to
+    pub fork_block_number: Option<u64>,
+    /// Fuzz runs per method
+    pub runs: Option<usize>,
+    pub seed: Option<u64>,
+    pub senders: SenderConfig,
+    pub exclude: ExcludeConfig,
+    pub reverts: RevertConfig,
+    /// Constructor arguments per contract name, as literals (`0x…`, `1000`, `[1,2]`, `(0xab,5)`)
+    pub constructor_args: BTreeMap<String, Vec<String>>,
+    pub detectors: DetectorConfig,
+    pub compiler: CompilerConfig,
+    /// Weights of the address pool's sources, as with `--address-weights`; unlisted sources keep their default
+    pub address_weights: Option<AddressWeights>,
+    /// Value strategies (`range:1..1e18`) per method pattern and parameter name, as with `--strategy`
+    pub strategies: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Which accounts send the fuzzed calls
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SenderConfig {
+    pub accounts: Option<usize>,
+    pub mnemonic: Option<String>,
+    /// Percentage of runs sent from an impersonated owner
+    pub owner_percent: Option<u32>,
+    /// On-chain accounts impersonated and sent from, as with `--impersonate`
+    pub impersonate: Vec<String>,
+    /// Impersonate the accounts held in each contract's owner and admin variables
+    pub discover: Option<bool>,
+}
+
+/// Contracts and functions left out of the campaign. Entries are glob patterns; a function entry
+/// matches its name, its signature, or either prefixed with the contract (`Vault.withdraw`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExcludeConfig {
+    pub contracts: Vec<String>,
+    pub functions: Vec<String>,
+}
+
+/// Which reverts are expected and which panics are always findings, as with `--expected-revert`
+/// and `--bug-panic`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RevertConfig {
+    pub expected: Vec<String>,
+    pub bug_panics: Vec<u64>,
+}
+
+/// Oracles and fuzzing dimensions switched on for the campaign
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorConfig {
+    pub reentrancy: Option<bool>,
+    pub access_control: Option<bool>,
+    pub events: Option<bool>,
+    pub value_accounting: Option<bool>,
+    pub block_context: Option<bool>,
+    pub gas_limit: Option<bool>,
+    pub fees: Option<bool>,
+    pub coverage: Option<bool>,
+    pub mutate: Option<bool>,
+    /// Time, block and balance cheats between sequence calls
+    pub cheats: Option<bool>,
+    /// Time and block jumps before each sequence
+    pub time_jumps: Option<bool>,
+    /// Stateful sequences per contract
+    pub sequences: Option<usize>,
+    pub gas_threshold: Option<u64>,
+    pub gas_report: Option<bool>,
+}
+
+/// Compiler backend and the settings of the standard-JSON backend
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompilerConfig {
+    /// `auto`, `forge`, `solc` or `standard-json`
+    pub backend: Option<String>,
+    /// `auto`, `path` or a solc release such as `0.8.20`
+    pub solc_version: Option<String>,
+    pub optimizer: Option<bool>,
+    pub optimizer_runs: Option<u32>,
+    /// Import remappings, `[context:]prefix=target`; added to the ones given on the command line
+    pub remappings: Vec<String>,
+    pub evm_version: Option<String>,
+}
+
+impl ProjectConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid {}", path.display()))
+    }
+}
+
+/// The nearest `fuzzhead.toml` in `start` (a file's directory, or the directory itself) and its ancestors
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    let start = if start.is_file() { start.parent()? } else { start };
+    let start = start.canonicalize().ok()?;
+    start.ancestors()
+        .map(|directory| directory.join(FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
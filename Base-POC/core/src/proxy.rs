@@ -0,0 +1,139 @@
+//! Proxies. A contract that forwards its calls with `delegatecall` has few functions of its own:
+//! the methods worth fuzzing are its implementation's, called through the proxy so that they run
+//! on the proxy's storage. The implementation is read from the standard slots: EIP-1967
+//! (transparent and UUPS proxies, and beacon proxies through their beacon) and EIP-1822.
+
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::ast_parser::ContractInfo;
+use crate::executor::Executor;
+use crate::storage_slots;
+use crate::types::{ContractMethod, MethodVisibility};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::fmt;
+
+/// How a proxy finds its implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyKind {
+    /// EIP-1967 implementation slot with an admin in the admin slot
+    Transparent,
+    /// EIP-1967 implementation slot, upgraded through the implementation's `upgradeTo`
+    Uups,
+    /// EIP-1967 implementation slot, neither of the above as far as can be told
+    Eip1967,
+    /// EIP-1967 beacon slot; the beacon's `implementation()` is the implementation
+    Beacon,
+    /// EIP-1822 `PROXIABLE` slot
+    Eip1822,
+}
+
+impl fmt::Display for ProxyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyKind::Transparent => write!(f, "EIP-1967 transparent"),
+            ProxyKind::Uups => write!(f, "UUPS"),
+            ProxyKind::Eip1967 => write!(f, "EIP-1967"),
+            ProxyKind::Beacon => write!(f, "EIP-1967 beacon"),
+            ProxyKind::Eip1822 => write!(f, "EIP-1822"),
+        }
+    }
+}
+
+/// A fuzzed contract found to be a proxy, and the implementation its calls were fuzzed with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    /// The proxy, as the contract goes by in the campaign
+    pub contract: String,
+    pub address: String,
+    pub kind: ProxyKind,
+    pub implementation: String,
+    /// Contract of the campaign deployed at the implementation address, if any
+    pub implementation_contract: Option<String>,
+    pub beacon: Option<String>,
+    pub admin: Option<String>,
+    /// Where the implementation's methods came from: `source`, `explorer`, or `none` when only
+    /// the proxy's own ABI was fuzzed
+    pub abi_source: String,
+    /// Implementation methods fuzzed through the proxy
+    pub methods: usize,
+}
+
+/// Implementation, beacon and admin slots of a deployed contract
+#[derive(Debug, Clone)]
+pub struct ProxySlots {
+    pub kind: ProxyKind,
+    pub implementation: String,
+    pub beacon: Option<String>,
+    pub admin: Option<String>,
+}
+
+/// `keccak256("PROXIABLE")`, the implementation slot of EIP-1822
+fn eip1822_slot() -> U256 {
+    U256::from_big_endian(&Keccak256::digest(b"PROXIABLE"))
+}
+
+/// The address a slot holds, if it holds one: not zero, and nothing above the low 20 bytes
+fn address_in(word: U256) -> Option<String> {
+    (!word.is_zero() && word.bits() <= 160).then(|| format!("0x{:040x}", word))
+}
+
+async fn read_address(executor: &mut dyn Executor, contract_name: &str, slot: U256) -> Option<String> {
+    address_in(executor.storage_at(contract_name, slot).await.ok()?)
+}
+
+/// The proxy pattern of a deployed contract, `None` when none of the slots holds an address.
+/// A beacon is attached under `<contract>Beacon` to ask it for the implementation.
+pub async fn detect(executor: &mut dyn Executor, contract_name: &str) -> Option<ProxySlots> {
+    let implementation = read_address(executor, contract_name, storage_slots::eip1967_slot("eip1967.proxy.implementation")).await;
+    let admin = read_address(executor, contract_name, storage_slots::eip1967_slot("eip1967.proxy.admin")).await;
+    let beacon = read_address(executor, contract_name, storage_slots::eip1967_slot("eip1967.proxy.beacon")).await;
+    let legacy = read_address(executor, contract_name, eip1822_slot()).await;
+
+    if let Some(implementation) = implementation {
+        let kind = if admin.is_some() { ProxyKind::Transparent } else { ProxyKind::Eip1967 };
+        return Some(ProxySlots { kind, implementation, beacon: None, admin });
+    }
+    if let Some(beacon) = beacon {
+        let beacon_name = format!("{}Beacon", contract_name);
+        executor.attach_contract(&beacon_name, &beacon).await.ok()?;
+        let output = executor.static_call(&beacon_name, &calculate_selector("implementation()")).await.ok()?;
+        let implementation = address_in(U256::from_big_endian(output.get(..32)?))?;
+        return Some(ProxySlots { kind: ProxyKind::Beacon, implementation, beacon: Some(beacon), admin });
+    }
+    legacy.map(|implementation| ProxySlots { kind: ProxyKind::Eip1822, implementation, beacon: None, admin })
+}
+
+/// Methods of an implementation that can be called through a proxy: its external and public
+/// functions, without constructor, fallback and receive
+pub fn forwarded_methods(implementation: &ContractInfo) -> impl Iterator<Item = &ContractMethod> {
+    implementation.methods.iter().filter(|method| {
+        matches!(method.visibility, MethodVisibility::Public | MethodVisibility::External)
+            && !method.is_constructor && !method.is_fallback && !method.is_receive
+    })
+}
+
+/// The proxy with the implementation's methods and events added to its own; its fallback and
+/// receive stay those of the proxy
+pub fn with_implementation(mut proxy: ContractInfo, implementation: &ContractInfo) -> ContractInfo {
+    for method in forwarded_methods(implementation) {
+        let signature = abi_encoding::method_signature(method);
+        if !proxy.methods.iter().any(|known| abi_encoding::method_signature(known) == signature) {
+            proxy.methods.push(method.clone());
+        }
+    }
+    for event in &implementation.events {
+        if !proxy.events.iter().any(|known| known.name == event.name) {
+            proxy.events.push(event.clone());
+        }
+    }
+    proxy
+}
+
+/// Whether runtime code dispatches `signature`: its selector pushed by a `PUSH4`
+pub fn dispatches(code: &[u8], signature: &str) -> bool {
+    let selector = calculate_selector(signature);
+    code.windows(5).any(|window| window[0] == 0x63 && window[1..] == selector)
+}
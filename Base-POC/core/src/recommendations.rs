@@ -0,0 +1,191 @@
+//! Next-step suggestions printed at the end of each contract's campaign. They are read off the
+//! campaign's own statistics: methods whose calls all reverted (and why), constructor arguments
+//! left at zero, iterations skipped for unsupported parameter types, and calls lost to RPC errors.
+
+use crate::access_control;
+use crate::ast_parser::ContractInfo;
+use crate::abi_encoding;
+use crate::config::FuzzConfig;
+use crate::ownership::{self, ContractOwner};
+use crate::types::{ContractMethod, Finding, MethodParameter, MethodStats, SolidityType};
+use ethers::abi::{Abi, Token};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One suggested change to the campaign's setup or configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub contract: String,
+    /// Canonical signature of the method it is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    pub suggestion: String,
+}
+
+impl fmt::Display for Recommendation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.method {
+            Some(method) => write!(f, "{}: {}", method, self.suggestion),
+            None => write!(f, "{}", self.suggestion),
+        }
+    }
+}
+
+/// What one contract's campaign left behind
+pub struct ContractCampaign<'a> {
+    pub contract: &'a ContractInfo,
+    pub abi: &'a Abi,
+    pub constructor_args: Option<&'a [u8]>,
+    /// Stats of the contract's methods (a method fuzzed over several time slices has several)
+    pub method_stats: &'a [MethodStats],
+    /// Findings of the contract
+    pub findings: &'a [Finding],
+    /// Owner read from the owner getter at the end of the campaign
+    pub owner: Option<&'a ContractOwner>,
+    /// Whether the owner was impersonated for `--owner-percent` of the runs
+    pub owner_impersonated: bool,
+    pub accounts: &'a [String],
+}
+
+/// Revert reasons that point at a missing privilege rather than at bad arguments
+const ACCESS_REVERT_HINTS: [&str; 8] = ["owner", "unauthori", "not authori", "accesscontrol", "caller is not", "forbidden", "not allowed", "only "];
+
+fn is_access_revert(error: &str) -> bool {
+    let error = error.to_ascii_lowercase();
+    ACCESS_REVERT_HINTS.iter().any(|hint| error.contains(hint))
+}
+
+/// Parameter types the generator cannot produce values for
+fn is_unsupported(param_type: &SolidityType) -> bool {
+    match param_type {
+        SolidityType::Mapping(_, _) | SolidityType::Custom(_) => true,
+        SolidityType::Array(inner) | SolidityType::FixedArray(inner, _) => is_unsupported(inner),
+        SolidityType::Struct(_, fields) => fields.iter().any(|field| is_unsupported(&field.param_type)),
+        _ => false,
+    }
+}
+
+/// Calls, passes, failures, expected reverts, errors and skipped iterations of a method, summed over its stats
+fn totals(stats: &[MethodStats], signature: &str) -> (usize, usize, usize, usize, usize, usize) {
+    stats.iter()
+        .filter(|stats| stats.method == signature)
+        .fold((0, 0, 0, 0, 0, 0), |(calls, passed, failed, expected, errors, skipped), stats| {
+            (calls + stats.calls, passed + stats.passed, failed + stats.failed, expected + stats.expected_reverts,
+                errors + stats.errors, skipped + stats.skipped)
+        })
+}
+
+/// Constructor arguments deployed as zero, e.g. `owner = 0x000…` or `fee = 0`
+fn zero_constructor_args(abi: &Abi, constructor_args: Option<&[u8]>) -> Vec<String> {
+    let Some(constructor) = abi.constructor() else {
+        return Vec::new();
+    };
+    let kinds: Vec<_> = constructor.inputs.iter().map(|input| input.kind.clone()).collect();
+    let Ok(tokens) = ethers::abi::decode(&kinds, constructor_args.unwrap_or_default()) else {
+        return Vec::new();
+    };
+    constructor.inputs.iter().zip(tokens).enumerate()
+        .filter_map(|(index, (input, token))| {
+            let name = if input.name.is_empty() { MethodParameter::placeholder_name(index) } else { input.name.clone() };
+            match token {
+                Token::Address(address) if address.is_zero() => Some(format!("{} = address(0)", name)),
+                Token::Uint(value) if value.is_zero() => Some(format!("{} = 0", name)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn always_reverting(campaign: &ContractCampaign, method: &ContractMethod, signature: &str, config: &FuzzConfig) -> Option<String> {
+    let (calls, passed, failed, expected, _, _) = totals(campaign.method_stats, signature);
+    if calls == 0 || passed > 0 || failed + expected == 0 {
+        return None;
+    }
+    let mut reasons: Vec<&str> = campaign.findings.iter()
+        .filter(|finding| finding.signature == signature)
+        .map(|finding| finding.error.as_str())
+        .collect();
+    reasons.sort_unstable();
+    reasons.dedup();
+    let reason = match reasons.as_slice() {
+        // Expected reverts are mostly access control turning the sender away
+        [] => "with an expected revert reason".to_string(),
+        [reason] => format!("with `{}`", reason),
+        _ => format!("with {} different errors", reasons.len()),
+    };
+    let summary = format!("all {} calls reverted {}", calls, reason);
+
+    if access_control::is_privileged(method) || reasons.is_empty() || reasons.iter().any(|reason| is_access_revert(reason)) {
+        let suggestion = match campaign.owner {
+            Some(owner) if ownership::is_external(owner, campaign.accounts) && config.owner_percent == 0 =>
+                format!("{}; the owner {} is not a fuzzer account, enable owner impersonation with --owner-percent 10", summary, owner.address),
+            Some(owner) if campaign.owner_impersonated =>
+                format!("{}; raise --owner-percent above {} so more runs come from the owner {}", summary, config.owner_percent, owner.address),
+            Some(owner) if ownership::is_external(owner, campaign.accounts) =>
+                format!("{}; the owner {} could not be impersonated, use a node that supports anvil_impersonateAccount", summary, owner.address),
+            Some(_) if !config.access_control =>
+                format!("{}; it looks owner-only, pass --access-control to count reverts for other senders as expected and check what they can change", summary),
+            Some(_) => return None,
+            None => format!("{}; no owner could be read (no owner getter, or it returns address(0)), pass a fuzzer account ({}) as the owner or admin constructor argument", summary,
+                campaign.accounts.first().map_or("the first sender", String::as_str)),
+        };
+        return Some(suggestion);
+    }
+    if config.sequences == 0 && !config.keep_state {
+        return Some(format!("{}; if it needs earlier calls to set up state (a deposit, an approval), add --sequences 50", summary));
+    }
+    if reasons.len() == 1 {
+        return Some(format!("{}; generate valid arguments for it with a value hook (--hooks)", summary));
+    }
+    (!config.coverage_guided).then(|| format!("{}; add --coverage so inputs that get further into it are kept and mutated", summary))
+}
+
+/// Suggestions for one contract, most specific first
+pub fn for_contract(campaign: &ContractCampaign, config: &FuzzConfig) -> Vec<Recommendation> {
+    let contract = &campaign.contract.name;
+    let mut recommendations = Vec::new();
+    let mut reverting_methods = 0;
+
+    for method in &campaign.contract.methods {
+        let signature = abi_encoding::method_signature(method);
+        let (calls, _, _, _, errors, skipped) = totals(campaign.method_stats, &signature);
+        let mut add = |suggestion: String| recommendations.push(Recommendation {
+            contract: contract.clone(),
+            method: Some(signature.clone()),
+            suggestion,
+        });
+
+        if skipped > 0 {
+            let unsupported: Vec<String> = method.parameters.iter()
+                .filter(|param| is_unsupported(&param.param_type))
+                .map(|param| format!("{} {}", param.param_type.abi_type_string(), param.name))
+                .collect();
+            if !unsupported.is_empty() {
+                add(format!("{} iteration(s) skipped, no values can be generated for {}; fuzz it through a wrapper with supported parameter types",
+                    skipped, unsupported.join(", ")));
+            }
+        }
+        if calls > 0 && errors * 2 > calls {
+            add(format!("{} of {} calls errored (RPC/infrastructure); raise --rpc-timeout (now {}s) or check the node",
+                errors, calls, config.rpc_timeout_secs));
+            continue;
+        }
+        if let Some(suggestion) = always_reverting(campaign, method, &signature, config) {
+            reverting_methods += 1;
+            add(suggestion);
+        }
+    }
+
+    if reverting_methods > 0 {
+        let zero_args = zero_constructor_args(campaign.abi, campaign.constructor_args);
+        if !zero_args.is_empty() {
+            recommendations.insert(0, Recommendation {
+                contract: contract.clone(),
+                method: None,
+                suggestion: format!("deployed with {}; provide real constructor arguments, {} method(s) reverted on every call",
+                    zero_args.join(", "), reverting_methods),
+            });
+        }
+    }
+    recommendations
+}
@@ -0,0 +1,123 @@
+//! Reentrancy detection. A small attacker contract is deployed next to the target and drawn as an
+//! address argument. Before a call that passes it, the attacker is told the call's calldata; when
+//! the target later calls (or sends ETH to) the attacker mid-execution, the attacker sends the same
+//! call back into the target once. The call trace then shows whether the target ran again inside
+//! itself or turned the nested call away (a reentrancy guard reverts it).
+
+use crate::types::SolidityValue;
+use anyhow::{Context, Result};
+use ethers::abi::Token;
+use ethers::types::Address;
+use serde_json::Value;
+
+/// Name the attacker is deployed under in the executor
+pub const ATTACKER_NAME: &str = "FuzzheadReentrancyAttacker";
+
+/// Tells the attacker which target to re-enter and with which calldata, and re-arms it
+pub const CONFIGURE_SIGNATURE: &str = "configureReentry(address,bytes)";
+
+/// Init code of the attacker, hand-assembled so no compiler is needed. Storage: slot 0 is set once
+/// it has re-entered, slot 1 holds the target, slot 2 the calldata length and slots 3.. its words.
+///
+/// ```text
+/// selector == 0x7cd5284e (configureReentry) ? configure
+/// if sload(0) goto done
+/// sstore(0, 1); copy the calldata from slots 3.. to memory
+/// call(gas, sload(1), 0, 0, len, 0, 0)       ; the nested call may fail, its outcome is ignored
+/// done: return bytes32(msg.sig)              ; satisfies onERC721Received and similar hooks
+/// configure: sstore(0, 0); sstore(1, target); sstore(2, len); copy the calldata words to slots 3..
+/// ```
+const ATTACKER_INIT_CODE: &str = "609380600b6000396000f360003560e01c637cd5284e1461005f5760005461004d57600160005560025460005b8181101561003b578060051c600301548152602001610021565b506000600082600060006001545af150505b60003560e01c60e01b60005260206000f35b60006000556004356001556044358060025560005b818110156100915780606401358160051c60030155602001610074565b00";
+
+pub fn attacker_init_code() -> Vec<u8> {
+    hex::decode(ATTACKER_INIT_CODE).expect("attacker init code is valid hex")
+}
+
+/// Arguments of `configureReentry` (without selector): re-enter `target` with `calldata`
+pub fn configure_args(target: &str, calldata: &[u8]) -> Result<Vec<u8>> {
+    let target: Address = target.parse().with_context(|| format!("Invalid target address {}", target))?;
+    Ok(ethers::abi::encode(&[Token::Address(target), Token::Bytes(calldata.to_vec())]))
+}
+
+/// Whether any argument is (or contains) `address`
+pub fn contains_address(args: &[SolidityValue], address: &str) -> bool {
+    let mut pending: Vec<&SolidityValue> = args.iter().collect();
+    while let Some(value) = pending.pop() {
+        match value {
+            SolidityValue::Address(candidate) if candidate.eq_ignore_ascii_case(address) => return true,
+            SolidityValue::Array(items) | SolidityValue::FixedArray(items) => pending.extend(items),
+            SolidityValue::Struct(fields) => pending.extend(fields.iter().map(|(_, value)| value)),
+            _ => {}
+        }
+    }
+    false
+}
+
+/// One message call of a transaction, in execution order
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallFrame {
+    /// 0 for the transaction itself
+    pub depth: usize,
+    /// Account whose code ran, lowercase
+    pub to: String,
+    /// First four bytes of the input, if it had that many
+    pub selector: Option<[u8; 4]>,
+    pub reverted: bool,
+}
+
+fn selector(input: &[u8]) -> Option<[u8; 4]> {
+    input.get(..4).map(|bytes| [bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+impl CallFrame {
+    pub fn new(depth: usize, to: &str, input: &[u8], reverted: bool) -> Self {
+        Self { depth, to: to.to_ascii_lowercase(), selector: selector(input), reverted }
+    }
+}
+
+/// Frames of a `debug_traceTransaction` response from the `callTracer`
+pub fn frames_from_call_trace(trace: &Value) -> Vec<CallFrame> {
+    let mut frames = Vec::new();
+    let mut pending = vec![(0, trace)];
+    while let Some((depth, call)) = pending.pop() {
+        let input = call.get("input").and_then(Value::as_str)
+            .and_then(|input| hex::decode(input.trim_start_matches("0x")).ok())
+            .unwrap_or_default();
+        let to = call.get("to").and_then(Value::as_str).unwrap_or_default();
+        frames.push(CallFrame::new(depth, to, &input, call.get("error").is_some()));
+        if let Some(calls) = call.get("calls").and_then(Value::as_array) {
+            // Reversed, so the stack pops them in execution order
+            pending.extend(calls.iter().rev().map(|nested| (depth + 1, nested)));
+        }
+    }
+    frames
+}
+
+/// The target running again inside one of its own calls, by way of the attacker
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reentry {
+    /// Selector of the target's frame the attacker was called from
+    pub outer: Option<[u8; 4]>,
+    /// Selector the target was re-entered with
+    pub inner: Option<[u8; 4]>,
+}
+
+/// The first frame where the target runs, without reverting, below a frame of the attacker that
+/// itself runs below the target
+pub fn find_reentry(frames: &[CallFrame], target: &str, attacker: &str) -> Option<Reentry> {
+    let (target, attacker) = (target.to_ascii_lowercase(), attacker.to_ascii_lowercase());
+    // Frames enclosing the current one, outermost first
+    let mut enclosing: Vec<&CallFrame> = Vec::new();
+    for frame in frames {
+        enclosing.truncate(frame.depth);
+        if frame.to == target && !frame.reverted {
+            let attacker_frame = enclosing.iter().rposition(|outer| outer.to == attacker);
+            let outer = attacker_frame.and_then(|index| enclosing[..index].iter().rev().find(|outer| outer.to == target));
+            if let Some(outer) = outer {
+                return Some(Reentry { outer: outer.selector, inner: frame.selector });
+            }
+        }
+        enclosing.push(frame);
+    }
+    None
+}
@@ -0,0 +1,292 @@
+use crate::bricking::BrickingCall;
+use crate::call_tree::TraceFrame;
+use crate::case;
+use crate::fingerprint::StateChange;
+use crate::gas_profile::GasAlert;
+use crate::accounting::ValueFlows;
+use crate::explorer::{Explorer, ExplorerLink};
+use crate::invariants::InvariantViolation;
+use crate::proxy::ProxyInfo;
+use crate::recommendations::Recommendation;
+use crate::repro::{self, ReproSnippets};
+use crate::scribble;
+use crate::selectors::SelectorIssue;
+use crate::severity::{OracleClass, Severity};
+use crate::types::{ArgProvenance, Cheat, Finding, FuzzSummary, MethodSeed, MethodStats};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Machine-readable campaign results written with `--report-file`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CampaignReport {
+    /// Every per-method seed is derived from this; pass it as `--seed` to rerun the campaign
+    pub master_seed: u64,
+    /// True for `--dry-run`/`--simulate` campaigns: results come from the mock executor, not a chain
+    pub simulated: bool,
+    pub files: Vec<FileReport>,
+    /// Explorer the findings of files added from now on link to (`--explorer`)
+    #[serde(skip)]
+    pub explorer: Option<Explorer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileReport {
+    pub file: String,
+    pub method_seeds: Vec<MethodSeed>,
+    pub findings: Vec<FindingReport>,
+    #[serde(default)]
+    pub selector_issues: Vec<SelectorIssue>,
+    /// Proxies among the fuzzed contracts and the implementations they forward to
+    #[serde(default)]
+    pub proxies: Vec<ProxyInfo>,
+    #[serde(default)]
+    pub bricking_calls: Vec<BrickingCall>,
+    /// Methods with a call over `--gas-threshold` or out of gas; gas per method is in `method_stats`
+    #[serde(default)]
+    pub gas_alerts: Vec<GasAlert>,
+    #[serde(default)]
+    pub invariant_violations: Vec<InvariantViolation>,
+    #[serde(default)]
+    pub value_flows: Vec<ValueFlows>,
+    /// How fuzzing changed the values of parameterless view functions
+    #[serde(default)]
+    pub state_changes: Vec<StateChange>,
+    /// Next-step suggestions for a more effective campaign
+    #[serde(default)]
+    pub recommendations: Vec<Recommendation>,
+    #[serde(default)]
+    pub totals: RunTotals,
+    #[serde(default)]
+    pub method_stats: Vec<MethodStats>,
+    /// Wall-clock duration of the file's campaign
+    #[serde(default)]
+    pub elapsed_secs: f64,
+}
+
+/// Run counters of one file, as printed in its summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunTotals {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    pub flaky: usize,
+    pub unconfirmed: usize,
+    /// Candidate findings whose replay on the confirmation fork errored
+    #[serde(default)]
+    pub confirmation_errors: usize,
+    pub checked_math_panics: usize,
+    /// Reverts whose reason matched an expected-revert pattern
+    #[serde(default)]
+    pub expected_reverts: usize,
+    pub block_context_dependent: usize,
+    #[serde(default)]
+    pub gas_dependent: usize,
+    #[serde(default)]
+    pub system_address_successes: usize,
+    #[serde(default)]
+    pub reentrancies: usize,
+    #[serde(default)]
+    pub wraparounds: usize,
+    #[serde(default)]
+    pub access_denied: usize,
+    #[serde(default)]
+    pub unauthorized_changes: usize,
+    #[serde(default)]
+    pub ether_drains: usize,
+}
+
+impl RunTotals {
+    pub fn runs(&self) -> usize {
+        self.passed + self.failed + self.skipped + self.errors + self.flaky + self.unconfirmed + self.confirmation_errors + self.checked_math_panics + self.expected_reverts + self.access_denied
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindingReport {
+    pub contract: String,
+    pub method: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    #[serde(default)]
+    pub signature: String,
+    /// Arguments of the failing call in the JSON form `replay` reads
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+    /// Wei sent with the failing call, in decimal, when it sent any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub sender: String,
+    pub iteration: usize,
+    /// Seed of the failing iteration (`generator::iteration_seed(method seed, iteration)`)
+    pub seed: u64,
+    pub error: String,
+    /// Failing calls with the same revert reason this finding stands for
+    #[serde(default)]
+    pub occurrences: usize,
+    /// Label of the Scribble property the call violated, when the failure is a property check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    pub flaky: bool,
+    /// Oracle that reported the finding
+    #[serde(default)]
+    pub oracle: OracleClass,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Sent from the contract's owner, impersonated because the fuzzer does not control it
+    #[serde(default)]
+    pub impersonated_owner: bool,
+    /// Time, block and balance cheats applied right before the failing call (`--cheats`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cheats: Vec<Cheat>,
+    /// Frames of the failing call that reverted and the calls they ran inside, with the frame the revert started in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_trace: Vec<TraceFrame>,
+    /// Generation strategy behind each argument
+    #[serde(default)]
+    pub provenance: Vec<ArgProvenance>,
+    /// `cast` and `forge script` reproductions (absent for simulated campaigns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repro: Option<ReproSnippets>,
+    /// Explorer pages of the fork block and the accounts involved (with `--explorer`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explorer_links: Vec<ExplorerLink>,
+}
+
+impl CampaignReport {
+    pub fn new(master_seed: u64) -> Self {
+        Self {
+            master_seed,
+            simulated: false,
+            files: Vec::new(),
+            explorer: None,
+        }
+    }
+
+    pub fn add_file(&mut self, file: &str, summary: &FuzzSummary) {
+        self.files.push(FileReport {
+            file: file.to_string(),
+            method_seeds: summary.method_seeds.clone(),
+            findings: summary.findings.iter().map(|finding| {
+                let mut report = FindingReport::from(finding);
+                if !self.simulated {
+                    report.repro = repro::render(finding, file, &summary.deployments, summary.fork_block_number).ok();
+                    if let Some(explorer) = &self.explorer {
+                        report.explorer_links = explorer.links(finding, summary.fork_block_number);
+                    }
+                }
+                report
+            }).collect(),
+            selector_issues: summary.selector_issues.clone(),
+            proxies: summary.proxies.clone(),
+            bricking_calls: summary.bricking_calls.clone(),
+            gas_alerts: summary.gas_alerts.clone(),
+            invariant_violations: summary.invariant_violations.clone(),
+            value_flows: summary.value_flows.clone(),
+            state_changes: summary.state_changes.clone(),
+            recommendations: summary.recommendations.clone(),
+            totals: RunTotals {
+                passed: summary.total_passed,
+                failed: summary.total_failed,
+                skipped: summary.total_skipped,
+                errors: summary.total_errors,
+                flaky: summary.total_flaky,
+                unconfirmed: summary.total_unconfirmed,
+                confirmation_errors: summary.total_confirmation_errors,
+                checked_math_panics: summary.total_checked_math_panics,
+                expected_reverts: summary.total_expected_reverts,
+                block_context_dependent: summary.total_block_context_dependent,
+                gas_dependent: summary.total_gas_dependent,
+                system_address_successes: summary.total_system_address_successes,
+                reentrancies: summary.total_reentrancies,
+                wraparounds: summary.total_wraparounds,
+                access_denied: summary.total_access_denied,
+                unauthorized_changes: summary.total_unauthorized_changes,
+                ether_drains: summary.total_ether_drains,
+            },
+            method_stats: summary.method_stats.clone(),
+            elapsed_secs: summary.elapsed_secs,
+        });
+    }
+
+    /// Run counters summed over every file
+    pub fn totals(&self) -> RunTotals {
+        let mut totals = RunTotals::default();
+        for file in &self.files {
+            totals.passed += file.totals.passed;
+            totals.failed += file.totals.failed;
+            totals.skipped += file.totals.skipped;
+            totals.errors += file.totals.errors;
+            totals.flaky += file.totals.flaky;
+            totals.unconfirmed += file.totals.unconfirmed;
+            totals.confirmation_errors += file.totals.confirmation_errors;
+            totals.checked_math_panics += file.totals.checked_math_panics;
+            totals.expected_reverts += file.totals.expected_reverts;
+            totals.block_context_dependent += file.totals.block_context_dependent;
+            totals.gas_dependent += file.totals.gas_dependent;
+            totals.system_address_successes += file.totals.system_address_successes;
+            totals.reentrancies += file.totals.reentrancies;
+            totals.wraparounds += file.totals.wraparounds;
+            totals.access_denied += file.totals.access_denied;
+            totals.unauthorized_changes += file.totals.unauthorized_changes;
+            totals.ether_drains += file.totals.ether_drains;
+        }
+        totals
+    }
+
+    /// Oracle class of every finding, invariant violation and bricking call, one entry each
+    pub fn detections(&self) -> Vec<OracleClass> {
+        self.files.iter()
+            .flat_map(|file| {
+                file.findings.iter().map(|finding| finding.oracle)
+                    .chain(file.invariant_violations.iter().map(|_| OracleClass::Invariant))
+                    .chain(file.bricking_calls.iter().map(|_| OracleClass::Bricking))
+            })
+            .collect()
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read report {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid report {}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create report directory {}", parent.display()))?;
+        }
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write report to {}", path.display()))
+    }
+}
+
+impl From<&Finding> for FindingReport {
+    fn from(finding: &Finding) -> Self {
+        let oracle = OracleClass::of_finding(&finding.error, finding.is_flaky());
+        Self {
+            contract: finding.contract.clone(),
+            method: finding.method.clone(),
+            signature: finding.signature.clone(),
+            args: finding.args.iter().map(case::value_to_json).collect(),
+            value: (!finding.value.is_zero()).then(|| finding.value.to_string()),
+            sender: finding.sender.clone(),
+            iteration: finding.iteration,
+            seed: finding.seed,
+            error: finding.error.clone(),
+            occurrences: finding.occurrences,
+            property: scribble::violated_property(&finding.error).map(str::to_string),
+            flaky: finding.is_flaky(),
+            oracle,
+            severity: oracle.severity(),
+            impersonated_owner: finding.impersonated_owner,
+            cheats: finding.cheats.clone(),
+            call_trace: finding.call_trace.clone(),
+            provenance: finding.provenance.clone(),
+            repro: None,
+            explorer_links: Vec::new(),
+        }
+    }
+}
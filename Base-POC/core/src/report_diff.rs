@@ -0,0 +1,187 @@
+use crate::report::{CampaignReport, RunTotals};
+use crate::triage::{self, TriageStatus, TriageStore};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Delta between two campaign reports (an older baseline and a newer run)
+#[derive(Debug)]
+pub struct ReportDiff {
+    pub old_totals: RunTotals,
+    pub new_totals: RunTotals,
+    pub old_elapsed_secs: f64,
+    pub new_elapsed_secs: f64,
+    /// (methods with at least one passing call, methods fuzzed)
+    pub old_method_coverage: (usize, usize),
+    pub new_method_coverage: (usize, usize),
+    /// Findings as `Contract.method: error`
+    pub new_findings: Vec<String>,
+    /// New findings already marked as false positives or duplicates in triage
+    pub dismissed_findings: Vec<(String, TriageStatus)>,
+    pub resolved_findings: Vec<String>,
+    pub unchanged_findings: usize,
+    /// Triage verdicts of the new and resolved findings
+    pub triage: BTreeMap<String, TriageStatus>,
+    /// Methods whose average gas per call changed, largest relative change first
+    pub gas_changes: Vec<GasChange>,
+}
+
+#[derive(Debug)]
+pub struct GasChange {
+    /// `Contract.signature`
+    pub method: String,
+    pub old_average: u64,
+    pub new_average: u64,
+}
+
+impl GasChange {
+    fn relative_change(&self) -> f64 {
+        if self.old_average == 0 {
+            return 0.0;
+        }
+        (self.new_average as f64 - self.old_average as f64) / self.old_average as f64
+    }
+}
+
+/// Compare two reports. Findings and methods are matched by contract and method, not by file
+/// path, so reports written from different working directories still line up. With a triage
+/// store, new findings dismissed in triage are listed apart and the others show their verdict.
+pub fn diff(old: &CampaignReport, new: &CampaignReport, triage: Option<&TriageStore>) -> ReportDiff {
+    let old_findings = finding_keys(old);
+    let new_findings = finding_keys(new);
+
+    let old_gas = average_gas(old);
+    let new_gas = average_gas(new);
+    let mut gas_changes: Vec<GasChange> = new_gas.iter()
+        .filter_map(|(method, new_average)| {
+            let old_average = *old_gas.get(method)?;
+            (old_average != *new_average).then(|| GasChange {
+                method: method.clone(),
+                old_average,
+                new_average: *new_average,
+            })
+        })
+        .collect();
+    gas_changes.sort_by(|a, b| b.relative_change().abs().total_cmp(&a.relative_change().abs()));
+
+    let mut statuses = BTreeMap::new();
+    let mut added = Vec::new();
+    let mut dismissed_findings = Vec::new();
+    for key in new_findings.difference(&old_findings) {
+        match triage.and_then(|triage| triage.status(key)) {
+            Some(status) if status.is_dismissed() => dismissed_findings.push((key.clone(), status)),
+            Some(status) => {
+                statuses.insert(key.clone(), status);
+                added.push(key.clone());
+            }
+            None => added.push(key.clone()),
+        }
+    }
+    let resolved_findings: Vec<String> = old_findings.difference(&new_findings).cloned().collect();
+    for key in &resolved_findings {
+        if let Some(status) = triage.and_then(|triage| triage.status(key)) {
+            statuses.insert(key.clone(), status);
+        }
+    }
+
+    ReportDiff {
+        old_totals: old.totals(),
+        new_totals: new.totals(),
+        old_elapsed_secs: old.files.iter().map(|file| file.elapsed_secs).sum(),
+        new_elapsed_secs: new.files.iter().map(|file| file.elapsed_secs).sum(),
+        old_method_coverage: method_coverage(old),
+        new_method_coverage: method_coverage(new),
+        new_findings: added,
+        dismissed_findings,
+        resolved_findings,
+        unchanged_findings: new_findings.intersection(&old_findings).count(),
+        triage: statuses,
+        gas_changes,
+    }
+}
+
+fn finding_keys(report: &CampaignReport) -> BTreeSet<String> {
+    report.files.iter()
+        .flat_map(|file| &file.findings)
+        .filter(|finding| !finding.flaky)
+        .map(triage::finding_key)
+        .collect()
+}
+
+fn average_gas(report: &CampaignReport) -> BTreeMap<String, u64> {
+    let mut totals: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+    for stats in report.files.iter().flat_map(|file| &file.method_stats) {
+        let entry = totals.entry(format!("{}.{}", stats.contract, stats.method)).or_default();
+        entry.0 += stats.gas_used;
+        entry.1 += stats.calls as u64;
+    }
+    totals.into_iter()
+        .filter(|(_, (_, calls))| *calls > 0)
+        .map(|(method, (gas, calls))| (method, gas / calls))
+        .collect()
+}
+
+fn method_coverage(report: &CampaignReport) -> (usize, usize) {
+    let stats: Vec<_> = report.files.iter().flat_map(|file| &file.method_stats).collect();
+    (stats.iter().filter(|stats| stats.passed > 0).count(), stats.len())
+}
+
+impl ReportDiff {
+    fn triage_label(&self, finding: &str) -> String {
+        self.triage.get(finding).map(|status| format!(" [{}]", status)).unwrap_or_default()
+    }
+}
+
+fn throughput(runs: usize, elapsed_secs: f64) -> Option<f64> {
+    (elapsed_secs > 0.0).then(|| runs as f64 / elapsed_secs)
+}
+
+fn count_line(f: &mut fmt::Formatter<'_>, label: &str, old: usize, new: usize) -> fmt::Result {
+    writeln!(f, "   {:<22} {} → {} ({:+})", label, old, new, new as i64 - old as i64)
+}
+
+impl fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "📊 Campaign comparison (old → new):")?;
+        count_line(f, "📊 Runs", self.old_totals.runs(), self.new_totals.runs())?;
+        count_line(f, "✅ Passed", self.old_totals.passed, self.new_totals.passed)?;
+        count_line(f, "❌ Failed", self.old_totals.failed, self.new_totals.failed)?;
+        count_line(f, "🔁 Flaky", self.old_totals.flaky, self.new_totals.flaky)?;
+        count_line(f, "⚠️  Errored", self.old_totals.errors, self.new_totals.errors)?;
+
+        match (throughput(self.old_totals.runs(), self.old_elapsed_secs), throughput(self.new_totals.runs(), self.new_elapsed_secs)) {
+            (Some(old), Some(new)) => writeln!(f, "   {:<22} {:.1} → {:.1} runs/s ({:+.1}%)", "⚡ Throughput", old, new, (new - old) / old * 100.0)?,
+            _ => writeln!(f, "   {:<22} not recorded in both reports", "⚡ Throughput")?,
+        }
+
+        writeln!(f, "   {:<22} {}/{} → {}/{} methods with a passing call", "🧭 Method coverage",
+            self.old_method_coverage.0, self.old_method_coverage.1, self.new_method_coverage.0, self.new_method_coverage.1)?;
+
+        if self.gas_changes.is_empty() {
+            writeln!(f, "   ⛽ Gas: no changes in average gas per call")?;
+        } else {
+            writeln!(f, "   ⛽ Average gas per call:")?;
+            for change in self.gas_changes.iter().take(10) {
+                writeln!(f, "      {}: {} → {} ({:+.1}%)", change.method, change.old_average, change.new_average, change.relative_change() * 100.0)?;
+            }
+            if self.gas_changes.len() > 10 {
+                writeln!(f, "      ... and {} more", self.gas_changes.len() - 10)?;
+            }
+        }
+
+        writeln!(f, "   🔍 Findings: {} new, {} resolved, {} unchanged",
+            self.new_findings.len(), self.resolved_findings.len(), self.unchanged_findings)?;
+        for finding in &self.new_findings {
+            writeln!(f, "      + {}{}", finding, self.triage_label(finding))?;
+        }
+        for finding in &self.resolved_findings {
+            writeln!(f, "      - {}{}", finding, self.triage_label(finding))?;
+        }
+        if !self.dismissed_findings.is_empty() {
+            writeln!(f, "   🏷️  {} new finding(s) dismissed in triage:", self.dismissed_findings.len())?;
+            for (finding, status) in &self.dismissed_findings {
+                writeln!(f, "      ~ {} [{}]", finding, status)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,407 @@
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::types::{Cheat, Deployment, Finding, FuzzSummary, SequenceCall, SolidityValue};
+use anyhow::{Context, Result};
+use ethers::types::{Address, U256};
+use ethers::utils::to_checksum;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Ready-to-run reproductions of a finding for Foundry users, embedded in the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReproSnippets {
+    /// Shell commands replaying the call with `cast` on the campaign's Anvil fork
+    pub cast: String,
+    /// A `forge script` that deploys the target on a pinned fork and replays the call
+    pub forge_script: String,
+}
+
+/// Render both snippets for a finding. `deployments` are the contracts deployed in the same
+/// campaign; `fork_block_number` is the upstream block the campaign's fork started from.
+pub fn render(finding: &Finding, source_file: &str, deployments: &[Deployment], fork_block_number: Option<u64>) -> Result<ReproSnippets> {
+    let calldata = calldata_hex(finding)?;
+    let deployment = deployments.iter().find(|d| d.contract == finding.contract);
+
+    Ok(ReproSnippets {
+        cast: cast_snippet(finding, deployment, &calldata),
+        forge_script: forge_script(finding, &artifact_file(source_file), deployments, fork_block_number, &calldata),
+    })
+}
+
+fn cast_snippet(finding: &Finding, deployment: Option<&Deployment>, calldata: &str) -> String {
+    let target = deployment.map(|d| d.address.as_str())
+        .or(finding.contract_address.as_deref())
+        .unwrap_or("<target address>");
+
+    let mut lines = vec![
+        format!("# {}.{} (iteration {}, seed {}): {}", finding.contract, finding.signature, finding.iteration, finding.seed, single_line(&finding.error)),
+        "# Run against the Anvil fork the campaign used, where the target is still deployed".to_string(),
+        "export RPC_URL=http://localhost:8545".to_string(),
+    ];
+    for (index, call) in finding.sequence.iter().enumerate() {
+        let to = match other_contract(finding, call) {
+            Some(contract) => {
+                lines.push(format!("# Setup call {} of the stateful sequence: {}.{}", index + 1, contract, call.signature));
+                call.contract_address.as_deref().unwrap_or("<contract address>")
+            }
+            None => {
+                lines.push(format!("# Setup call {} of the stateful sequence: {}", index + 1, call.signature));
+                target
+            }
+        };
+        lines.extend(call.cheats.iter().flat_map(cast_cheat));
+        lines.push(format!("cast rpc anvil_impersonateAccount {} --rpc-url $RPC_URL", call.sender));
+        lines.push(format!("cast send {} 0x{} --from {} --unlocked --rpc-url $RPC_URL{}", to, sequence_calldata_hex(call), call.sender, cast_value(call.value)));
+    }
+    lines.extend(finding.cheats.iter().flat_map(cast_cheat));
+    lines.push(format!("cast rpc anvil_impersonateAccount {} --rpc-url $RPC_URL", finding.sender));
+    if let Some(block_context) = &finding.block_context {
+        if let Some(coinbase) = &block_context.coinbase {
+            lines.push(format!("cast rpc anvil_setCoinbase {} --rpc-url $RPC_URL", coinbase));
+        }
+        if let Some(base_fee) = block_context.base_fee_per_gas {
+            lines.push(format!("cast rpc anvil_setNextBlockBaseFeePerGas 0x{:x} --rpc-url $RPC_URL", base_fee));
+        }
+        if let Some(chain_id) = block_context.chain_id {
+            lines.push(format!("cast rpc anvil_setChainId 0x{:x} --rpc-url $RPC_URL", chain_id));
+        }
+    }
+
+    let mut send = format!("cast send {} '{}'", target, finding.signature);
+    match finding.args.iter().map(cast_arg).collect::<Option<Vec<_>>>() {
+        Some(args) => {
+            for arg in args {
+                send.push(' ');
+                send.push_str(&arg);
+            }
+        }
+        None => {
+            // Some values cannot be written as cast arguments; fall back to the raw calldata
+            send = format!("cast send {} 0x{}", target, calldata);
+        }
+    }
+    send.push_str(&format!(" --from {} --unlocked --rpc-url $RPC_URL{}", finding.sender, cast_value(finding.value)));
+    if let Some(fees) = finding.fees {
+        send.push_str(&format!(" --gas-price {} --priority-gas-price {}", fees.max_fee_per_gas, fees.max_priority_fee_per_gas));
+    }
+    if let Some(gas_limit) = finding.gas_limit {
+        send.push_str(&format!(" --gas-limit {}", gas_limit));
+    }
+    lines.push(send);
+    lines.push(format!("# Raw calldata: 0x{}", calldata));
+
+    lines.join("\n")
+}
+
+fn forge_script(finding: &Finding, source_file: &str, deployments: &[Deployment], fork_block_number: Option<u64>, calldata: &str) -> String {
+    let target = target_expression(source_file, &finding.contract, deployments);
+    let fork_flags = match fork_block_number {
+        Some(block) => format!("--fork-url $FORK_URL --fork-block-number {}", block),
+        None => "--fork-url $FORK_URL".to_string(),
+    };
+
+    let mut body = vec![
+        format!("        address target = {};", target),
+    ];
+    body.extend(call_lines(finding, calldata, source_file, deployments));
+    body.push("        console2.log(\"success:\", success);".to_string());
+    body.push("        console2.logBytes(returnData);".to_string());
+
+    format!(
+        "// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+import {{Script, console2}} from \"forge-std/Script.sol\";
+
+/// Reproduces {contract}.{signature} (iteration {iteration}, seed {seed}): {error}
+/// Run: forge script Repro.s.sol {fork_flags} -vvvv
+/// State from earlier calls of the campaign is not replayed, apart from the finding's own call sequence.
+contract Repro is Script {{
+    function run() external {{
+{body}
+    }}
+}}
+",
+        contract = finding.contract,
+        signature = finding.signature,
+        iteration = finding.iteration,
+        seed = finding.seed,
+        error = single_line(&finding.error),
+        fork_flags = fork_flags,
+        body = body.join("\n"),
+    )
+}
+
+/// A Foundry test that forks the chain, deploys the target and asserts that the finding's call
+/// still reverts: a proof of concept that can be shipped with an audit report
+pub fn foundry_test(finding: &Finding, source_file: &str, deployments: &[Deployment], fork_block_number: Option<u64>) -> Result<String> {
+    let calldata = calldata_hex(finding)?;
+    let fork = match fork_block_number {
+        Some(block) => format!("vm.createSelectFork(vm.envString(\"FORK_URL\"), {});", block),
+        None => "vm.createSelectFork(vm.envString(\"FORK_URL\"));".to_string(),
+    };
+
+    Ok(format!(
+        "// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+import {{Test}} from \"forge-std/Test.sol\";
+
+/// Proof of concept for {contract}.{signature} (iteration {iteration}, seed {seed})
+/// Observed: {error}
+/// Run: FORK_URL=<rpc url> forge test --match-contract {name} -vvvv
+contract {name} is Test {{
+    address target;
+
+    function setUp() public {{
+        {fork}
+        target = {target};
+    }}
+
+    function test_{method}_reverts() public {{
+{call}
+        assertFalse(success, \"call was expected to revert\");
+        emit log_named_bytes(\"revert data\", returnData);
+    }}
+}}
+",
+        name = poc_contract_name(finding),
+        contract = finding.contract,
+        signature = finding.signature,
+        iteration = finding.iteration,
+        seed = finding.seed,
+        error = single_line(&finding.error),
+        fork = fork,
+        target = target_expression(&artifact_file(source_file), &finding.contract, deployments),
+        method = finding.method,
+        call = call_lines(finding, &calldata, &artifact_file(source_file), deployments).join("\n"),
+    ))
+}
+
+/// Write one `<PoC contract>.t.sol` per confirmed finding of a summary into `dir`
+pub fn write_foundry_tests(dir: &Path, source_file: &str, summary: &FuzzSummary) -> Result<Vec<PathBuf>> {
+    let confirmed: Vec<&Finding> = summary.findings.iter()
+        .filter(|finding| !finding.is_flaky() && finding.confirmed_on_fork != Some(false))
+        .collect();
+    if confirmed.is_empty() {
+        return Ok(Vec::new());
+    }
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create PoC directory {}", dir.display()))?;
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for finding in confirmed {
+        let test = foundry_test(finding, source_file, &summary.deployments, summary.fork_block_number)?;
+        let mut path = dir.join(format!("{}.t.sol", poc_contract_name(finding)));
+        let mut suffix = 2;
+        while paths.contains(&path) {
+            path = dir.join(format!("{}_{}.t.sol", poc_contract_name(finding), suffix));
+            suffix += 1;
+        }
+        std::fs::write(&path, test)
+            .with_context(|| format!("Failed to write PoC {}", path.display()))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn poc_contract_name(finding: &Finding) -> String {
+    format!("PoC_{}_{}_{}", finding.contract, finding.method, finding.iteration)
+}
+
+fn calldata_hex(finding: &Finding) -> Result<String> {
+    let encoded_args = abi_encoding::encode_args(&finding.args)
+        .map_err(|e| anyhow::anyhow!("ABI encoding failed: {}", e))?;
+    let mut calldata = calculate_selector(&finding.signature).to_vec();
+    calldata.extend_from_slice(&encoded_args);
+    Ok(hex::encode(calldata))
+}
+
+/// Selector and arguments of a setup call of a stateful sequence
+fn sequence_calldata_hex(call: &SequenceCall) -> String {
+    let mut calldata = calculate_selector(&call.signature).to_vec();
+    calldata.extend_from_slice(&call.calldata);
+    hex::encode(calldata)
+}
+
+/// A fresh `deployCode` of the contract's artifact, or the address of a contract that was already
+/// on the chain (`--target-address`), which the fork brings along
+fn target_expression(artifact: &str, contract: &str, deployments: &[Deployment]) -> String {
+    let deployment = deployments.iter().find(|d| d.contract == contract);
+    match deployment.filter(|d| d.attached) {
+        Some(attached) => match attached.address.parse::<Address>() {
+            Ok(address) => to_checksum(&address, None),
+            Err(_) => format!("vm.parseAddress(\"{}\")", attached.address),
+        },
+        None => format!("deployCode(\"{}:{}\", hex\"{}\")", artifact, contract, constructor_args_hex(deployment)),
+    }
+}
+
+fn constructor_args_hex(deployment: Option<&Deployment>) -> String {
+    deployment
+        .and_then(|d| d.constructor_args.as_deref())
+        .map(hex::encode)
+        .unwrap_or_default()
+}
+
+/// The contract a setup call went to, when it is not the finding's own
+fn other_contract<'a>(finding: &Finding, call: &'a SequenceCall) -> Option<&'a str> {
+    call.contract.as_deref().filter(|contract| *contract != finding.contract)
+}
+
+/// Setup calls of the finding's stateful sequence (after deploying the other contracts a
+/// cross-contract sequence calls), block context, time and balance cheats, the prank and the
+/// call itself, as Solidity statements
+fn call_lines(finding: &Finding, calldata: &str, artifact: &str, deployments: &[Deployment]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut others: Vec<&str> = Vec::new();
+    for contract in finding.sequence.iter().filter_map(|call| other_contract(finding, call)) {
+        if !others.contains(&contract) {
+            others.push(contract);
+        }
+    }
+    if !others.is_empty() {
+        lines.push("        // Other contracts of the sequence; arguments holding their campaign addresses are not rewritten".to_string());
+    }
+    for contract in &others {
+        lines.push(format!("        address target_{} = {};", contract, target_expression(artifact, contract, deployments)));
+    }
+    for (index, call) in finding.sequence.iter().enumerate() {
+        let to = match other_contract(finding, call) {
+            Some(contract) => {
+                lines.push(format!("        // Setup call {}: {}.{}", index + 1, contract, call.signature));
+                format!("target_{}", contract)
+            }
+            None => {
+                lines.push(format!("        // Setup call {}: {}", index + 1, call.signature));
+                "target".to_string()
+            }
+        };
+        lines.extend(call.cheats.iter().map(cheat_line));
+        lines.extend(deal_line(&call.sender, call.value, &call.cheats));
+        lines.push(format!("        vm.prank(vm.parseAddress(\"{}\"));", call.sender));
+        lines.push(format!("        (bool setup{},) = {}.call{}(hex\"{}\");", index + 1, to, call_options(call.value), sequence_calldata_hex(call)));
+        lines.push(format!("        require(setup{}, \"setup call {} reverted\");", index + 1, index + 1));
+    }
+    if let Some(block_context) = &finding.block_context {
+        if let Some(coinbase) = &block_context.coinbase {
+            lines.push(format!("        vm.coinbase(vm.parseAddress(\"{}\"));", coinbase));
+        }
+        if let Some(base_fee) = block_context.base_fee_per_gas {
+            lines.push(format!("        vm.fee({});", base_fee));
+        }
+        if let Some(chain_id) = block_context.chain_id {
+            lines.push(format!("        vm.chainId({});", chain_id));
+        }
+    }
+    lines.extend(finding.cheats.iter().map(cheat_line));
+    lines.extend(deal_line(&finding.sender, finding.value, &finding.cheats));
+    lines.push(format!("        vm.prank(vm.parseAddress(\"{}\"));", finding.sender));
+    lines.push(format!("        (bool success, bytes memory returnData) = target.call{}(hex\"{}\");", call_options(finding.value), calldata));
+    lines
+}
+
+/// ` --value N` for a `cast send` that sends wei
+fn cast_value(value: U256) -> String {
+    if value.is_zero() { String::new() } else { format!(" --value {}", value) }
+}
+
+/// `{value: N}` call options for a call that sends wei
+fn call_options(value: U256) -> String {
+    if value.is_zero() { String::new() } else { format!("{{value: {}}}", value) }
+}
+
+/// Fund the sender with the wei it sends, since its balance on the fork may differ, unless a
+/// cheat of the call already dealt it the balance it had
+fn deal_line(sender: &str, value: U256, cheats: &[Cheat]) -> Option<String> {
+    let dealt = cheats.iter().any(|cheat| matches!(cheat, Cheat::Deal { account, .. } if account.eq_ignore_ascii_case(sender)));
+    (!value.is_zero() && !dealt).then(|| format!("        vm.deal(vm.parseAddress(\"{}\"), {});", sender, value))
+}
+
+/// A time, block or balance cheat of the sequence as a Foundry cheatcode call
+fn cheat_line(cheat: &Cheat) -> String {
+    match cheat {
+        Cheat::Warp { seconds } => format!("        vm.warp(block.timestamp + {});", seconds),
+        Cheat::Roll { blocks } => format!("        vm.roll(block.number + {});", blocks),
+        Cheat::Deal { account, wei } => format!("        vm.deal(vm.parseAddress(\"{}\"), {});", account, wei),
+    }
+}
+
+/// The same cheat as Anvil RPC calls
+fn cast_cheat(cheat: &Cheat) -> Vec<String> {
+    match cheat {
+        Cheat::Warp { seconds } => vec![
+            format!("cast rpc evm_increaseTime {} --rpc-url $RPC_URL", seconds),
+            "cast rpc evm_mine --rpc-url $RPC_URL".to_string(),
+        ],
+        Cheat::Roll { blocks } => vec![format!("cast rpc anvil_mine 0x{:x} --rpc-url $RPC_URL", blocks)],
+        Cheat::Deal { account, wei } => vec![format!("cast rpc anvil_setBalance {} 0x{:x} --rpc-url $RPC_URL", account, wei)],
+    }
+}
+
+/// A value in `cast`'s argument syntax, shell-quoted where needed. `None` for values that cannot
+/// be passed safely on the command line (structs, negative numbers).
+fn cast_arg(value: &SolidityValue) -> Option<String> {
+    match value {
+        SolidityValue::String(s) => Some(shell_quote(s)),
+        SolidityValue::Array(values) | SolidityValue::FixedArray(values) => Some(shell_quote(&array_literal(values)?)),
+        // A negative number would be parsed as a flag; use the raw calldata instead
+        other => scalar_literal(other).filter(|arg| !arg.starts_with('-')),
+    }
+}
+
+/// `[a,b,...]` with strings double-quoted, as cast parses array arguments
+fn array_literal(values: &[SolidityValue]) -> Option<String> {
+    let items = values.iter()
+        .map(|value| match value {
+            SolidityValue::String(s) => Some(format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))),
+            SolidityValue::Array(inner) | SolidityValue::FixedArray(inner) => array_literal(inner),
+            other => scalar_literal(other),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("[{}]", items.join(",")))
+}
+
+fn scalar_literal(value: &SolidityValue) -> Option<String> {
+    let literal = match value {
+        SolidityValue::Uint8(v) => v.to_string(),
+        SolidityValue::Uint16(v) => v.to_string(),
+        SolidityValue::Uint32(v) => v.to_string(),
+        SolidityValue::Uint64(v) => v.to_string(),
+        SolidityValue::Uint128(v) => v.to_string(),
+        SolidityValue::Uint256(v) => v.clone(),
+        SolidityValue::Int8(v) => v.to_string(),
+        SolidityValue::Int16(v) => v.to_string(),
+        SolidityValue::Int32(v) => v.to_string(),
+        SolidityValue::Int64(v) => v.to_string(),
+        SolidityValue::Int128(v) => v.to_string(),
+        SolidityValue::Int256(v) => v.clone(),
+        SolidityValue::Address(addr) => addr.clone(),
+        SolidityValue::Bool(b) => b.to_string(),
+        SolidityValue::Bytes(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes1(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes2(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes4(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes8(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes16(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::Bytes32(bs) => format!("0x{}", hex::encode(bs)),
+        SolidityValue::String(_) | SolidityValue::Array(_) | SolidityValue::FixedArray(_) | SolidityValue::Struct(_) => return None,
+    };
+    Some(literal)
+}
+
+/// forge looks artifacts up by file name (`Vault.sol:Vault`), not by the path the campaign was given
+fn artifact_file(source_file: &str) -> String {
+    std::path::Path::new(source_file)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| source_file.to_string())
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+fn single_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().to_string()
+}
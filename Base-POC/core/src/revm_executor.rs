@@ -0,0 +1,719 @@
+use crate::anvil_executor::{calculate_selector, derive_mnemonic_accounts, MethodExecutionResult};
+use crate::config::AccountOptions;
+use crate::coverage::BranchEdge;
+use crate::events::EventLog;
+use crate::executor::Executor;
+use crate::oracle::{decode_revert, CustomErrors};
+use crate::reentrancy::CallFrame;
+use crate::types::{BlockContext, Cheat, TxFees};
+use crate::wraparound::{ArithmeticOp, WrappedOperation};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use revm::db::{CacheDB, EmptyDB};
+use revm::interpreter::{CallInputs, CallOutcome, Interpreter};
+use revm::primitives::{
+    AccountInfo, Address, BlockEnv, Bytecode, Bytes, Env, ExecutionResult, Output, ResultAndState, SpecId, TxKind, U256,
+};
+use revm::{inspector_handle_register, Database, Evm, EvmContext, Inspector};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use tracing::debug;
+
+/// Anvil's default mnemonic, so the revm backend sends from the same addresses as a stock Anvil node
+const DEFAULT_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// Accounts Anvil creates by default
+const DEFAULT_ACCOUNTS: usize = 10;
+
+/// Balance of every sender account (10,000 ETH, as on Anvil)
+const ACCOUNT_BALANCE_WEI: u128 = 10_000 * 1_000_000_000_000_000_000;
+
+/// Gas limit of every transaction, as sent to Anvil
+const TX_GAS_LIMIT: u64 = 0x1000000;
+
+/// Chain id of the in-process chain (Anvil's default)
+const CHAIN_ID: u64 = 31337;
+
+/// Executor that runs every transaction in-process on revm, on an empty in-memory chain.
+/// There is no JSON-RPC, no mining and no receipt polling, so it is orders of magnitude faster
+/// than `AnvilForkExecutor`, but it cannot see any forked mainnet state.
+pub struct RevmExecutor {
+    db: CacheDB<EmptyDB>,
+    env: Env,
+    accounts: Vec<String>,
+    current_sender: String,
+    /// Account that deploys every contract, when a key has the deployer role
+    deployer: Option<String>,
+    deployed_contracts: HashMap<String, String>,
+    /// Database, block environment and chain id copies by snapshot id
+    snapshots: BTreeMap<u64, (CacheDB<EmptyDB>, BlockEnv, u64)>,
+    next_snapshot: u64,
+    fees: Option<TxFees>,
+    /// Gas limit of calls; `TX_GAS_LIMIT` when `None`
+    gas_limit: Option<u64>,
+    /// Wei sent with calls
+    value: U256,
+    /// Branch edges of the latest `call_method` transaction
+    last_coverage: Vec<BranchEdge>,
+    /// Message calls of the latest `call_method` transaction
+    last_frames: Vec<CallFrame>,
+    /// Wrapped arithmetic of the latest `call_method` transaction
+    last_wraparounds: Vec<WrappedOperation>,
+    /// Logs of the latest `call_method` transaction
+    last_logs: Vec<EventLog>,
+    /// Custom errors revert data is decoded with
+    custom_errors: CustomErrors,
+    /// Coinbase, base fee and chain id before the first `set_block_context`
+    original_block_context: Option<BlockContext>,
+}
+
+impl RevmExecutor {
+    /// Chain id seen by `CHAINID` and bound into transactions
+    fn set_chain_id(&mut self, chain_id: u64) {
+        self.env.cfg.chain_id = chain_id;
+        self.env.tx.chain_id = Some(chain_id);
+    }
+
+    /// Overwrite the block environment fields `block_context` sets
+    fn apply_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        if let Some(coinbase) = &block_context.coinbase {
+            self.env.block.coinbase = parse_address(coinbase)?;
+        }
+        if let Some(base_fee) = block_context.base_fee_per_gas {
+            self.env.block.basefee = U256::from(base_fee);
+        }
+        if let Some(chain_id) = block_context.chain_id {
+            self.set_chain_id(chain_id);
+        }
+        Ok(())
+    }
+
+    /// Create an empty chain with funded sender accounts (Anvil's default accounts unless a mnemonic
+    /// or keys are given; only the addresses of keys are needed, nothing is signed in-process)
+    pub fn new(account_options: &AccountOptions) -> Result<Self> {
+        let count = account_options.count.unwrap_or(DEFAULT_ACCOUNTS).max(1);
+        let accounts = if account_options.keys.is_empty() {
+            let mnemonic = account_options.mnemonic.as_deref().unwrap_or(DEFAULT_MNEMONIC);
+            derive_mnemonic_accounts(mnemonic, count)?
+        } else {
+            account_options.sender_keys().take(count).map(|key| key.address()).collect()
+        };
+        let deployer = account_options.deployer_key().map(|key| key.address());
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for account in accounts.iter().chain(&deployer) {
+            db.insert_account_info(parse_address(account)?, AccountInfo {
+                balance: U256::from(ACCOUNT_BALANCE_WEI),
+                ..Default::default()
+            });
+        }
+
+        let mut env = Env::default();
+        env.cfg.chain_id = CHAIN_ID;
+        env.tx.chain_id = Some(CHAIN_ID);
+        env.block.number = U256::from(1);
+        env.block.timestamp = U256::from(1);
+        env.block.gas_limit = U256::from(30_000_000u64);
+
+        debug!("revm executor ready with {} accounts", accounts.len());
+        Ok(Self {
+            db,
+            env,
+            current_sender: accounts[0].clone(),
+            accounts,
+            deployer,
+            deployed_contracts: HashMap::new(),
+            snapshots: BTreeMap::new(),
+            next_snapshot: 1,
+            fees: None,
+            gas_limit: None,
+            value: U256::ZERO,
+            last_coverage: Vec::new(),
+            last_frames: Vec::new(),
+            last_wraparounds: Vec::new(),
+            last_logs: Vec::new(),
+            custom_errors: CustomErrors::default(),
+            original_block_context: None,
+        })
+    }
+
+    /// Transaction environment for a call from `sender`
+    fn tx_env(&self, sender: &str, to: TxKind, data: Vec<u8>) -> Result<Box<Env>> {
+        let mut env = Box::new(self.env.clone());
+        env.tx.caller = parse_address(sender)?;
+        env.tx.transact_to = to;
+        env.tx.data = Bytes::from(data);
+        env.tx.value = U256::ZERO;
+        env.tx.gas_limit = TX_GAS_LIMIT;
+        // The nonce is not checked; revm still increments it, so created addresses match a real chain
+        env.tx.nonce = None;
+        match self.fees {
+            Some(fees) => {
+                env.tx.gas_price = U256::from(fees.max_fee_per_gas);
+                env.tx.gas_priority_fee = Some(U256::from(fees.max_priority_fee_per_gas));
+            }
+            None => {
+                env.tx.gas_price = env.block.basefee;
+                env.tx.gas_priority_fee = None;
+            }
+        }
+        Ok(env)
+    }
+
+    /// Execute a transaction and commit its state changes, recording the branch edges of the target
+    /// and the message calls
+    fn transact_commit(&mut self, env: Box<Env>) -> Result<(ExecutionResult, TraceRecorder)> {
+        let mut recorder = TraceRecorder::default();
+        let result = {
+            let mut evm = Evm::builder()
+                .with_db(&mut self.db)
+                .with_external_context(&mut recorder)
+                .with_env(env)
+                .with_spec_id(SpecId::CANCUN)
+                .append_handler_register(inspector_handle_register)
+                .build();
+            evm.transact_commit().map_err(|e| anyhow!("revm rejected the transaction: {:?}", e))?
+        };
+        self.advance_block();
+        Ok((result, recorder))
+    }
+
+    /// Every transaction gets its own block, like Anvil with instant mining
+    fn advance_block(&mut self) {
+        self.env.block.number += U256::from(1);
+        self.env.block.timestamp += U256::from(1);
+    }
+}
+
+#[async_trait]
+impl Executor for RevmExecutor {
+    async fn deploy_contract(
+        &mut self,
+        contract_name: &str,
+        bytecode: &[u8],
+        constructor_args: Option<&[u8]>,
+    ) -> Result<String> {
+        debug!("Deploying contract: {} in-process", contract_name);
+        let mut deployment_bytecode = bytecode.to_vec();
+        if let Some(args) = constructor_args {
+            deployment_bytecode.extend_from_slice(args);
+        }
+
+        let deployer = self.deployer.as_deref().unwrap_or(&self.current_sender);
+        let env = self.tx_env(deployer, TxKind::Create, deployment_bytecode)?;
+        let (result, _) = self.transact_commit(env)?;
+        let address = match result {
+            ExecutionResult::Success { output: Output::Create(_, Some(address)), .. } => address,
+            ExecutionResult::Success { .. } => return Err(anyhow!("Contract deployment returned no address")),
+            ExecutionResult::Revert { output, .. } => {
+                return Err(anyhow!(
+                    "Contract deployment failed: Transaction reverted\nRevert reason: {}",
+                    decode_revert(&output, &self.custom_errors)
+                ));
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                return Err(anyhow!("Contract deployment failed: EVM halted ({:?})", reason));
+            }
+        };
+
+        let contract_address = address.to_string();
+        debug!("Contract {} deployed at: {}", contract_name, contract_address);
+        self.deployed_contracts.insert(contract_name.to_string(), contract_address.clone());
+        Ok(contract_address)
+    }
+
+    async fn estimate_deployment_gas(&mut self, bytecode: &[u8], constructor_args: Option<&[u8]>) -> Result<Option<u64>> {
+        let mut deployment_bytecode = bytecode.to_vec();
+        if let Some(args) = constructor_args {
+            deployment_bytecode.extend_from_slice(args);
+        }
+        let deployer = self.deployer.as_deref().unwrap_or(&self.current_sender);
+        let env = self.tx_env(deployer, TxKind::Create, deployment_bytecode)?;
+        // Executed without committing, like eth_estimateGas
+        let ResultAndState { result, .. } = Evm::builder()
+            .with_db(&mut self.db)
+            .with_env(env)
+            .with_spec_id(SpecId::CANCUN)
+            .build()
+            .transact()
+            .map_err(|e| anyhow!("revm rejected the deployment: {:?}", e))?;
+        match result {
+            ExecutionResult::Success { gas_used, .. } => Ok(Some(gas_used)),
+            ExecutionResult::Revert { output, .. } => Err(anyhow!("Transaction reverted: {}", decode_revert(&output, &self.custom_errors))),
+            ExecutionResult::Halt { reason, .. } => Err(anyhow!("EVM halted: {:?}", reason)),
+        }
+    }
+
+    async fn attach_contract(&mut self, contract_name: &str, address: &str) -> Result<Vec<u8>> {
+        let code = self.code_at(address).await?;
+        if code.is_empty() {
+            // The in-process chain starts empty, only contracts deployed on it have code
+            return Err(anyhow!("No contract at {} on the revm chain, which has no fork state", address));
+        }
+        self.deployed_contracts.insert(contract_name.to_string(), address.to_string());
+        Ok(code)
+    }
+
+    async fn call_method(
+        &mut self,
+        contract_name: &str,
+        method_signature: &str,
+        encoded_args: &[u8],
+    ) -> Result<MethodExecutionResult> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow!("Contract {} not deployed", contract_name))?;
+        let to = TxKind::Call(parse_address(contract_address)?);
+
+        let mut call_data = calculate_selector(method_signature).to_vec();
+        call_data.extend_from_slice(encoded_args);
+
+        let mut env = self.tx_env(&self.current_sender, to, call_data)?;
+        env.tx.gas_limit = self.gas_limit.unwrap_or(TX_GAS_LIMIT);
+        env.tx.value = self.value;
+        self.last_coverage.clear();
+        self.last_frames.clear();
+        self.last_wraparounds.clear();
+        self.last_logs.clear();
+        let (result, recorder) = self.transact_commit(env)?;
+        self.last_coverage = recorder.edges;
+        self.last_frames = recorder.frames;
+        self.last_wraparounds = recorder.wraparounds;
+
+        Ok(match result {
+            ExecutionResult::Success { gas_used, output, logs, .. } => {
+                self.last_logs = logs.into_iter()
+                    .map(|log| EventLog {
+                        address: log.address.to_string(),
+                        topics: log.data.topics().iter().map(|topic| ethers::types::H256::from(topic.0)).collect(),
+                        data: log.data.data.to_vec().into(),
+                    })
+                    .collect();
+                MethodExecutionResult {
+                    success: true,
+                    gas_used,
+                    return_data: output.into_data().to_vec(),
+                    error: None,
+                }
+            }
+            ExecutionResult::Revert { gas_used, output } => MethodExecutionResult {
+                success: false,
+                gas_used,
+                return_data: output.to_vec(),
+                error: Some(decode_revert(&output, &self.custom_errors)),
+            },
+            ExecutionResult::Halt { reason, gas_used } => MethodExecutionResult {
+                success: false,
+                gas_used,
+                return_data: vec![],
+                error: Some(format!("EVM halted: {:?}", reason)),
+            },
+        })
+    }
+
+    async fn static_call(&mut self, contract_name: &str, calldata: &[u8]) -> Result<Vec<u8>> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow!("Contract {} not deployed", contract_name))?;
+        let to = TxKind::Call(parse_address(contract_address)?);
+        let mut env = self.tx_env(&self.accounts[0], to, calldata.to_vec())?;
+        // Nothing is committed, so the fee only has to satisfy the base fee
+        env.tx.gas_price = env.block.basefee;
+        env.tx.gas_priority_fee = None;
+
+        let ResultAndState { result, .. } = Evm::builder()
+            .with_db(&mut self.db)
+            .with_env(env)
+            .with_spec_id(SpecId::CANCUN)
+            .build()
+            .transact()
+            .map_err(|e| anyhow!("revm rejected the call: {:?}", e))?;
+        match result {
+            ExecutionResult::Success { output, .. } => Ok(output.into_data().to_vec()),
+            ExecutionResult::Revert { output, .. } => Err(anyhow!("Call reverted: {}", decode_revert(&output, &self.custom_errors))),
+            ExecutionResult::Halt { reason, .. } => Err(anyhow!("EVM halted: {:?}", reason)),
+        }
+    }
+
+    async fn contract_balance(&mut self, contract_name: &str) -> Result<ethers::types::U256> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow!("Contract {} not deployed", contract_name))?
+            .to_string();
+        self.account_balance(&contract_address).await
+    }
+
+    async fn account_balance(&mut self, address: &str) -> Result<ethers::types::U256> {
+        let balance = self.db.basic(parse_address(address)?)?
+            .map(|info| info.balance)
+            .unwrap_or_default();
+        Ok(ethers::types::U256::from_big_endian(&balance.to_be_bytes::<32>()))
+    }
+
+    async fn code_at(&mut self, address: &str) -> Result<Vec<u8>> {
+        Ok(self.db.basic(parse_address(address)?)?
+            .and_then(|info| info.code)
+            .map(|code| code.original_bytes().to_vec())
+            .unwrap_or_default())
+    }
+
+    async fn storage_at(&mut self, contract_name: &str, slot: ethers::types::U256) -> Result<ethers::types::U256> {
+        let contract_address = self.contract_address(contract_name)
+            .ok_or_else(|| anyhow!("Contract {} not deployed", contract_name))?;
+        let mut index = [0u8; 32];
+        slot.to_big_endian(&mut index);
+        let word = self.db.storage(parse_address(contract_address)?, U256::from_be_bytes(index))?;
+        Ok(ethers::types::U256::from_big_endian(&word.to_be_bytes::<32>()))
+    }
+
+    async fn last_call_coverage(&mut self) -> Result<Vec<BranchEdge>> {
+        Ok(self.last_coverage.clone())
+    }
+
+    async fn last_call_frames(&mut self) -> Result<Vec<CallFrame>> {
+        Ok(self.last_frames.clone())
+    }
+
+    async fn last_call_wraparounds(&mut self) -> Result<Vec<WrappedOperation>> {
+        Ok(self.last_wraparounds.clone())
+    }
+
+    async fn last_call_logs(&mut self) -> Result<Vec<EventLog>> {
+        Ok(self.last_logs.clone())
+    }
+
+    async fn snapshot(&mut self) -> Result<String> {
+        let id = self.next_snapshot;
+        self.next_snapshot += 1;
+        self.snapshots.insert(id, (self.db.clone(), self.env.block.clone(), self.env.cfg.chain_id));
+        Ok(format!("0x{:x}", id))
+    }
+
+    /// Like `evm_revert`, the snapshot and every later one are consumed
+    async fn revert_to_snapshot(&mut self, snapshot_id: &str) -> Result<()> {
+        let id = u64::from_str_radix(snapshot_id.trim_start_matches("0x"), 16)
+            .with_context(|| format!("Invalid snapshot id {}", snapshot_id))?;
+        let mut consumed = self.snapshots.split_off(&id);
+        let chain_id;
+        (self.db, self.env.block, chain_id) = consumed.remove(&id)
+            .ok_or_else(|| anyhow!("Unknown snapshot {}", snapshot_id))?;
+        self.set_chain_id(chain_id);
+        Ok(())
+    }
+
+    async fn base_fee_per_gas(&mut self) -> Result<Option<u128>> {
+        Ok(Some(self.env.block.basefee.to::<u128>()))
+    }
+
+    async fn fork_block_number(&mut self) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    async fn chain_id(&mut self) -> Result<u64> {
+        Ok(self.env.cfg.chain_id)
+    }
+
+    fn set_fees(&mut self, fees: Option<TxFees>) {
+        self.fees = fees;
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        self.gas_limit = gas_limit;
+    }
+
+    fn set_value(&mut self, value: ethers::types::U256) {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        self.value = U256::from_be_bytes(bytes);
+    }
+
+    async fn set_block_context(&mut self, block_context: &BlockContext) -> Result<()> {
+        if self.original_block_context.is_none() {
+            self.original_block_context = Some(BlockContext {
+                coinbase: Some(self.env.block.coinbase.to_string()),
+                base_fee_per_gas: Some(self.env.block.basefee.to::<u128>()),
+                chain_id: Some(self.env.cfg.chain_id),
+            });
+        }
+        self.apply_block_context(block_context)
+    }
+
+    async fn reset_block_context(&mut self) -> Result<()> {
+        match self.original_block_context.take() {
+            Some(original) => self.apply_block_context(&original),
+            None => Ok(()),
+        }
+    }
+
+    fn register_errors(&mut self, abi: &ethers::abi::Abi) {
+        self.custom_errors.add(abi);
+    }
+
+    async fn apply_cheat(&mut self, cheat: &Cheat) -> Result<()> {
+        match cheat {
+            Cheat::Warp { seconds } => self.env.block.timestamp += U256::from(*seconds),
+            Cheat::Roll { blocks } => self.env.block.number += U256::from(*blocks),
+            Cheat::Deal { account, wei } => {
+                let parsed = parse_address(account)?;
+                let info = self.db.basic(parsed)?.unwrap_or_default();
+                self.db.insert_account_info(parsed, AccountInfo { balance: U256::from_limbs(wei.0), ..info });
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_code(&mut self, address: &str, code: &[u8]) -> Result<()> {
+        let parsed = parse_address(address)?;
+        let info = self.db.basic(parsed)?.unwrap_or_default();
+        let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(code));
+        self.db.insert_account_info(parsed, AccountInfo { code_hash: bytecode.hash_slow(), code: Some(bytecode), ..info });
+        Ok(())
+    }
+
+    fn set_sender(&mut self, sender_index: usize) {
+        if sender_index < self.accounts.len() {
+            self.current_sender = self.accounts[sender_index].clone();
+        }
+    }
+
+    fn set_sender_address(&mut self, address: &str) -> bool {
+        match self.accounts.iter().find(|account| account.eq_ignore_ascii_case(address)) {
+            Some(account) => {
+                self.current_sender = account.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn impersonate(&mut self, address: &str) -> Result<()> {
+        if self.accounts.iter().any(|account| account.eq_ignore_ascii_case(address)) {
+            return Ok(());
+        }
+        // revm checks no signatures, any address can send once it can pay for gas
+        let parsed = parse_address(address)?;
+        let info = self.db.basic(parsed)?.unwrap_or_default();
+        if info.balance < U256::from(1_000_000_000_000_000_000u128) {
+            self.db.insert_account_info(parsed, AccountInfo { balance: U256::from(ACCOUNT_BALANCE_WEI), ..info });
+        }
+        self.accounts.push(address.to_string());
+        Ok(())
+    }
+
+    fn current_sender(&self) -> &str {
+        &self.current_sender
+    }
+
+    fn deployer(&self) -> &str {
+        self.deployer.as_deref().unwrap_or(&self.current_sender)
+    }
+
+    fn accounts(&self) -> &[String] {
+        &self.accounts
+    }
+
+    fn contract_address(&self, contract_name: &str) -> Option<&str> {
+        self.deployed_contracts.get(contract_name).map(String::as_str)
+    }
+
+    fn rpc_url(&self) -> &str {
+        "revm (in-process)"
+    }
+}
+
+/// Records the branch edges of the called contract while revm executes (same encoding as
+/// `coverage::branch_edges`, which derives them from `debug_traceTransaction`) and its wrapped
+/// arithmetic, and every message call like Anvil's `callTracer`
+#[derive(Default)]
+struct TraceRecorder {
+    pending_jumpi: Option<u64>,
+    edges: Vec<BranchEdge>,
+    wraparounds: Vec<WrappedOperation>,
+    frames: Vec<CallFrame>,
+    /// Indices into `frames` of the calls still running
+    open_frames: Vec<usize>,
+}
+
+impl<DB: Database> Inspector<DB> for TraceRecorder {
+    fn call(&mut self, context: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let depth = context.journaled_state.depth() as usize;
+        self.open_frames.push(self.frames.len());
+        self.frames.push(CallFrame::new(depth, &inputs.target_address.to_string(), &inputs.input, false));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut EvmContext<DB>, _inputs: &CallInputs, outcome: CallOutcome) -> CallOutcome {
+        if let Some(index) = self.open_frames.pop() {
+            self.frames[index].reverted = !outcome.instruction_result().is_ok();
+        }
+        outcome
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        // Only the outermost frame runs the target's own code
+        if context.journaled_state.depth() != 1 {
+            return;
+        }
+        let pc = interp.program_counter() as u64;
+        if let Some(jumpi_pc) = self.pending_jumpi.take() {
+            self.edges.push((jumpi_pc << 32) | (pc & 0xffff_ffff));
+        }
+        let opcode = interp.current_opcode();
+        if opcode == revm::interpreter::opcode::JUMPI {
+            self.pending_jumpi = Some(pc);
+        }
+        if let Some(op) = ArithmeticOp::from_opcode(opcode) {
+            if let (Ok(left), Ok(right)) = (interp.stack.peek(0), interp.stack.peek(1)) {
+                let left = ethers::types::U256::from_big_endian(&left.to_be_bytes::<32>());
+                let right = ethers::types::U256::from_big_endian(&right.to_be_bytes::<32>());
+                if op.wraps(left, right) {
+                    self.wraparounds.push(WrappedOperation { pc, op, left, right });
+                }
+            }
+        }
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address> {
+    Address::from_str(address).with_context(|| format!("Invalid address {}", address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wraparound::ArithmeticOp;
+
+    /// Creation code of a hand-assembled contract: any call stores its first argument `x` in slot 0,
+    /// adds it to `type(uint256).max` (a wraparound for x > 0), logs it under topic `0xaa` and
+    /// returns it; `x == 0` reverts with `Error("zero")`
+    const COUNTER: &str = concat!(
+        "6066600c60003960666000f3",
+        "6004358015604057",
+        "807fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0150",
+        "806000558060005260aa60206000a160206000f3",
+        "5b6308c379a060e01b60005260206004526004602452637a65726f60e01b60445260646000fd",
+    );
+
+    /// Program counter of the contract's only JUMPI, its fallthrough and its target
+    const JUMPI: u64 = 7;
+    const FALLTHROUGH: u64 = 8;
+    const REVERT_BRANCH: u64 = 0x40;
+
+    fn word(value: u64) -> Vec<u8> {
+        let mut word = [0u8; 32];
+        ethers::types::U256::from(value).to_big_endian(&mut word);
+        word.to_vec()
+    }
+
+    async fn deployed_counter() -> RevmExecutor {
+        let mut executor = RevmExecutor::new(&AccountOptions::default()).unwrap();
+        executor.deploy_contract("Counter", &hex::decode(COUNTER).unwrap(), None).await.unwrap();
+        executor
+    }
+
+    async fn stored(executor: &mut RevmExecutor) -> u64 {
+        executor.storage_at("Counter", 0.into()).await.unwrap().as_u64()
+    }
+
+    #[tokio::test]
+    async fn deploys_and_calls_a_contract() {
+        let mut executor = deployed_counter().await;
+        let address = executor.contract_address("Counter").unwrap().to_string();
+        assert_eq!(hex::encode(executor.code_at(&address).await.unwrap()), &COUNTER[24..]);
+
+        let result = executor.call_method("Counter", "set(uint256)", &word(5)).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.gas_used > 21_000);
+        assert_eq!(result.return_data, word(5));
+        assert_eq!(stored(&mut executor).await, 5);
+
+        let logs = executor.last_call_logs().await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].address.eq_ignore_ascii_case(&address));
+        assert_eq!(logs[0].topics, [ethers::types::H256::from_low_u64_be(0xaa)]);
+        assert_eq!(logs[0].data.to_vec(), word(5));
+    }
+
+    #[tokio::test]
+    async fn reverts_are_decoded() {
+        let mut executor = deployed_counter().await;
+        let result = executor.call_method("Counter", "set(uint256)", &word(0)).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("zero"));
+        assert!(executor.last_call_logs().await.unwrap().is_empty());
+
+        // Creation code that reverts without data
+        let error = executor.deploy_contract("Broken", &hex::decode("60006000fd").unwrap(), None).await.unwrap_err();
+        assert!(error.to_string().contains("Contract deployment failed"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn snapshots_restore_storage_and_are_consumed() {
+        let mut executor = deployed_counter().await;
+        executor.call_method("Counter", "set(uint256)", &word(5)).await.unwrap();
+        let block = executor.env.block.number;
+        let snapshot = executor.snapshot().await.unwrap();
+        executor.call_method("Counter", "set(uint256)", &word(9)).await.unwrap();
+        let later = executor.snapshot().await.unwrap();
+        assert_eq!(stored(&mut executor).await, 9);
+
+        executor.revert_to_snapshot(&snapshot).await.unwrap();
+        assert_eq!(stored(&mut executor).await, 5);
+        assert_eq!(executor.env.block.number, block);
+        assert!(executor.revert_to_snapshot(&later).await.is_err());
+        assert!(executor.revert_to_snapshot(&snapshot).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn coverage_and_traces_are_recorded_per_call() {
+        let mut executor = deployed_counter().await;
+        let address = executor.contract_address("Counter").unwrap().to_lowercase();
+
+        executor.call_method("Counter", "set(uint256)", &word(5)).await.unwrap();
+        assert_eq!(executor.last_call_coverage().await.unwrap(), [(JUMPI << 32) | FALLTHROUGH]);
+        let frames = executor.last_call_frames().await.unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!((frames[0].depth, frames[0].to.as_str(), frames[0].reverted), (0, address.as_str(), false));
+        assert_eq!(frames[0].selector, Some(calculate_selector("set(uint256)")));
+        let wraparounds = executor.last_call_wraparounds().await.unwrap();
+        assert_eq!(wraparounds.len(), 1);
+        assert_eq!(wraparounds[0].op, ArithmeticOp::Add);
+
+        executor.call_method("Counter", "set(uint256)", &word(0)).await.unwrap();
+        assert_eq!(executor.last_call_coverage().await.unwrap(), [(JUMPI << 32) | REVERT_BRANCH]);
+        assert!(executor.last_call_frames().await.unwrap()[0].reverted);
+        assert!(executor.last_call_wraparounds().await.unwrap().is_empty());
+    }
+
+    fn random_context() -> BlockContext {
+        BlockContext {
+            coinbase: Some("0x00000000000000000000000000000000000000c0".to_string()),
+            base_fee_per_gas: Some(7_000_000_000),
+            chain_id: Some(4_242_424),
+        }
+    }
+
+    #[tokio::test]
+    async fn snapshot_revert_restores_chain_id() {
+        let mut executor = RevmExecutor::new(&AccountOptions::default()).unwrap();
+        let snapshot = executor.snapshot().await.unwrap();
+        executor.set_block_context(&random_context()).await.unwrap();
+        assert_eq!(executor.chain_id().await.unwrap(), 4_242_424);
+
+        executor.revert_to_snapshot(&snapshot).await.unwrap();
+        assert_eq!(executor.chain_id().await.unwrap(), CHAIN_ID);
+        assert_eq!(executor.env.tx.chain_id, Some(CHAIN_ID));
+    }
+
+    #[tokio::test]
+    async fn reset_block_context_restores_original_environment() {
+        let mut executor = RevmExecutor::new(&AccountOptions::default()).unwrap();
+        let coinbase = executor.env.block.coinbase;
+        let base_fee = executor.env.block.basefee;
+
+        executor.set_block_context(&random_context()).await.unwrap();
+        executor.set_block_context(&BlockContext { chain_id: Some(1), ..Default::default() }).await.unwrap();
+        executor.reset_block_context().await.unwrap();
+
+        assert_eq!(executor.chain_id().await.unwrap(), CHAIN_ID);
+        assert_eq!(executor.env.block.coinbase, coinbase);
+        assert_eq!(executor.env.block.basefee, base_fee);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::ast_parser::SolidityParser;
+use crate::report::{CampaignReport, FileReport};
+use crate::triage;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/ExtropyIO/Fuzzhead";
+
+/// Rules results are reported under: (id, name, description, default level)
+const RULES: [(&str, &str, &str, &str); 4] = [
+    ("FH001", "FailingCall", "A fuzzed call failed and the failure reproduced on re-execution", "error"),
+    ("FH002", "FlakyFailure", "A fuzzed call failed but did not reproduce on every re-execution", "warning"),
+    ("FH003", "ScribblePropertyViolation", "A call violated a Scribble #if_succeeds postcondition", "error"),
+    ("FH004", "InvariantViolation", "An invariant property returned false or reverted", "error"),
+];
+
+/// The findings and invariant violations of a campaign as a SARIF 2.1.0 log, for GitHub code
+/// scanning and other dashboards. Findings with the same contract, method and error are one result.
+/// Each result points at the function (or, failing that, the contract) declaration in its source
+/// file. A simulated campaign has no findings, so its log has no results.
+pub fn render(report: &CampaignReport) -> Value {
+    let mut results = Vec::new();
+    if !report.simulated {
+        for file in &report.files {
+            results.extend(file_results(file));
+        }
+    }
+
+    let rules: Vec<Value> = RULES.iter().map(|(id, name, description, level)| json!({
+        "id": id,
+        "name": name,
+        "shortDescription": { "text": description },
+        "defaultConfiguration": { "level": level },
+    })).collect();
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Fuzzhead",
+                    "informationUri": INFORMATION_URI,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "%SRCROOT%": { "uri": format!("file://{}/", std::env::current_dir().unwrap_or_default().display()) },
+            },
+            "properties": {
+                "masterSeed": report.master_seed,
+                "simulated": report.simulated,
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn file_results(file: &FileReport) -> Vec<Value> {
+    let source = std::fs::read_to_string(&file.file).unwrap_or_default();
+    let mut results = Vec::new();
+
+    let mut seen = HashSet::new();
+    for finding in &file.findings {
+        let key = triage::finding_key(finding);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        let occurrences = file.findings.iter().filter(|other| triage::finding_key(other) == key).count();
+        let rule = if finding.property.is_some() {
+            "FH003"
+        } else if finding.flaky {
+            "FH002"
+        } else {
+            "FH001"
+        };
+        let call = if finding.signature.is_empty() { finding.method.clone() } else { finding.signature.clone() };
+        let args = finding.args.iter()
+            .map(|arg| arg.as_str().map(str::to_string).unwrap_or_else(|| arg.to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let text = match &finding.property {
+            Some(property) => format!("{}.{} violates \"{}\" when called with ({}) from {}", finding.contract, call, property, args, finding.sender),
+            None => format!("{}.{} fails when called with ({}) from {}: {}", finding.contract, call, args, finding.sender, finding.error),
+        };
+        let line = SolidityParser::locate(&source, &finding.contract, Some(&finding.method))
+            .or_else(|| SolidityParser::locate(&source, &finding.contract, None));
+        results.push(json!({
+            "ruleId": rule,
+            "level": if rule == "FH002" { "warning" } else { "error" },
+            "message": { "text": text },
+            "locations": [location(&file.file, line, &finding.contract, &finding.method)],
+            "partialFingerprints": { "fuzzhead/v1": key },
+            "properties": {
+                "signature": finding.signature,
+                "args": finding.args,
+                "sender": finding.sender,
+                "seed": finding.seed,
+                "iteration": finding.iteration,
+                "occurrences": occurrences,
+            },
+        }));
+    }
+
+    for violation in &file.invariant_violations {
+        let function = violation.invariant.split('(').next().unwrap_or(&violation.invariant);
+        let line = SolidityParser::locate(&source, &violation.contract, Some(function))
+            .or_else(|| SolidityParser::locate(&source, &violation.contract, None));
+        results.push(json!({
+            "ruleId": "FH004",
+            "level": "error",
+            "message": { "text": violation.to_string() },
+            "locations": [location(&file.file, line, &violation.contract, function)],
+            "partialFingerprints": { "fuzzhead/v1": format!("{}.{}", violation.contract, violation.invariant) },
+            "properties": {
+                "seed": violation.seed,
+            },
+        }));
+    }
+    results
+}
+
+fn location(file: &str, line: Option<usize>, contract: &str, function: &str) -> Value {
+    let mut physical = json!({ "artifactLocation": artifact_location(file) });
+    if let Some(line) = line {
+        physical["region"] = json!({ "startLine": line });
+    }
+    json!({
+        "physicalLocation": physical,
+        "logicalLocations": [{
+            "fullyQualifiedName": format!("{}.{}", contract, function),
+            "kind": "function",
+        }],
+    })
+}
+
+/// A source file relative to the working directory (the checkout code scanning resolves paths
+/// against), or as an absolute `file://` URI when it lies outside of it
+fn artifact_location(file: &str) -> Value {
+    let path = Path::new(file);
+    let relative = match std::env::current_dir() {
+        Ok(cwd) if path.is_absolute() => path.strip_prefix(cwd).ok().map(Path::to_path_buf),
+        _ => Some(path.to_path_buf()),
+    };
+    match relative {
+        Some(relative) => json!({
+            "uri": relative.to_string_lossy().trim_start_matches("./").replace('\\', "/"),
+            "uriBaseId": "%SRCROOT%",
+        }),
+        None => json!({ "uri": format!("file://{}", path.display()) }),
+    }
+}
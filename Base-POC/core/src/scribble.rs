@@ -0,0 +1,728 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Revert reason of a failed property check; the property's label follows it
+pub const VIOLATION_PREFIX: &str = "Scribble property violated: ";
+
+/// Name prefix of the parameterless functions `#invariant` annotations are compiled into
+const INVARIANT_PREFIX: &str = "invariant_scribble_";
+
+/// Keywords that may follow a parameter's type without being its name
+const LOCATION_KEYWORDS: [&str; 4] = ["memory", "calldata", "storage", "payable"];
+
+/// Keywords of a state variable declaration that are neither its type nor its name
+const VARIABLE_KEYWORDS: [&str; 6] = ["public", "private", "internal", "constant", "immutable", "override"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// Postcondition of a function, checked whenever the function returns
+    IfSucceeds,
+    /// Contract invariant, checked with the campaign's other invariants
+    Invariant,
+}
+
+impl fmt::Display for PropertyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IfSucceeds => write!(f, "#if_succeeds"),
+            Self::Invariant => write!(f, "#invariant"),
+        }
+    }
+}
+
+/// A Scribble annotation compiled into the source
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub kind: PropertyKind,
+    pub contract: String,
+    /// The annotated function, or the generated invariant function
+    pub function: String,
+    /// `{:msg "..."}` of the annotation, else its expression
+    pub label: String,
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}.{}: {}", self.kind, self.contract, self.function, self.label)
+    }
+}
+
+/// A source with its Scribble annotations compiled into checks
+#[derive(Debug)]
+pub struct Instrumentation {
+    pub source: String,
+    pub properties: Vec<Property>,
+    /// Annotations that were left out, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Label of the property behind a failure, when the error is a failed property check
+pub fn violated_property(error: &str) -> Option<&str> {
+    let (_, label) = error.split_once(VIOLATION_PREFIX)?;
+    Some(label.trim_end_matches(['"', '\'', ')', ' ']))
+}
+
+struct Annotation {
+    kind: PropertyKind,
+    label: String,
+    expression: String,
+}
+
+/// Compile the `#if_succeeds` and `#invariant` annotations of a source into Solidity, natively and
+/// for a subset of the Scribble language: plain Solidity expressions plus `old(...)`, `==>` and
+/// `$result`. A function with `#if_succeeds` keeps its signature and modifiers but calls its
+/// original body, moved to an internal function, and reverts with `VIOLATION_PREFIX` when a
+/// postcondition does not hold. Each contract invariant becomes an `invariant_scribble_N()`
+/// function. `None` when the source has no annotations.
+pub fn instrument(source: &str) -> Option<Instrumentation> {
+    let mut declarations = annotated_declarations(source);
+    if declarations.is_empty() {
+        return None;
+    }
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut properties = Vec::new();
+    let mut skipped = Vec::new();
+    let mut invariant_count = 0;
+    let mut wrapped_count = 0;
+
+    for (line, annotations) in declarations.drain(..) {
+        let header = lines[line].trim();
+        let start = line_starts[line] + (lines[line].len() - lines[line].trim_start().len());
+        let indent = &lines[line][..lines[line].len() - lines[line].trim_start().len()];
+
+        if let Some(name) = contract_name(header) {
+            for annotation in annotations.iter().filter(|a| a.kind == PropertyKind::IfSucceeds) {
+                skipped.push(format!("#if_succeeds {} on contract {}: contract-wide postconditions are not supported", annotation.label, name));
+            }
+            let invariants: Vec<&Annotation> = annotations.iter().filter(|a| a.kind == PropertyKind::Invariant).collect();
+            if invariants.is_empty() {
+                continue;
+            }
+            let Some(close) = find_code(source, start, b"{").and_then(|(open, _)| closing(source, open)) else {
+                skipped.push(format!("#invariant on contract {}: contract body not found", name));
+                continue;
+            };
+            let mut functions = String::new();
+            for annotation in invariants {
+                let condition = match translate(&annotation.expression) {
+                    Ok(condition) => condition,
+                    Err(reason) => {
+                        skipped.push(format!("#invariant {} on contract {}: {}", annotation.label, name, reason));
+                        continue;
+                    }
+                };
+                invariant_count += 1;
+                let function = format!("{}{}", INVARIANT_PREFIX, invariant_count);
+                functions.push_str(&format!(
+                    "\n{indent}    function {function}() public view returns (bool) {{\n{indent}        require({condition}, \"{}\");\n{indent}        return true;\n{indent}    }}\n{indent}",
+                    escape(&format!("{}{}", VIOLATION_PREFIX, annotation.label)),
+                ));
+                properties.push(Property {
+                    kind: PropertyKind::Invariant,
+                    contract: name.to_string(),
+                    function: format!("{}()", function),
+                    label: annotation.label.clone(),
+                });
+            }
+            edits.push((close, 0, functions));
+        } else if header.starts_with("function ") {
+            let contract = enclosing_contract(&lines, line).unwrap_or_default();
+            for annotation in annotations.iter().filter(|a| a.kind == PropertyKind::Invariant) {
+                skipped.push(format!("#invariant {} on a function of {}: invariants belong to contracts", annotation.label, contract));
+            }
+            let postconditions: Vec<&Annotation> = annotations.iter().filter(|a| a.kind == PropertyKind::IfSucceeds).collect();
+            if postconditions.is_empty() {
+                continue;
+            }
+            wrapped_count += 1;
+            let state = state_variables(source, &lines, &line_starts, line);
+            match wrap_function(source, start, indent, wrapped_count, &postconditions, &state) {
+                Ok((end, text, name)) => {
+                    properties.extend(postconditions.iter().map(|annotation| Property {
+                        kind: PropertyKind::IfSucceeds,
+                        contract: contract.clone(),
+                        function: name.clone(),
+                        label: annotation.label.clone(),
+                    }));
+                    edits.push((start, end - start, text));
+                }
+                Err(reason) => skipped.extend(postconditions.iter()
+                    .map(|annotation| format!("#if_succeeds {} in {}: {}", annotation.label, contract, reason))),
+            }
+        } else {
+            skipped.extend(annotations.iter()
+                .map(|annotation| format!("{} {}: not attached to a contract or function", annotation.kind, annotation.label)));
+        }
+    }
+
+    let mut instrumented = source.to_string();
+    edits.sort_by_key(|(offset, _, _)| *offset);
+    for (offset, length, text) in edits.into_iter().rev() {
+        instrumented.replace_range(offset..offset + length, &text);
+    }
+    Some(Instrumentation { source: instrumented, properties, skipped })
+}
+
+/// Annotations grouped by the line of the declaration that follows them
+fn annotated_declarations(source: &str) -> Vec<(usize, Vec<Annotation>)> {
+    let mut declarations = Vec::new();
+    let mut pending: Vec<Annotation> = Vec::new();
+    let mut open: Option<(PropertyKind, String)> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        if let Some(text) = comment_text(line) {
+            let (kind, mut text) = match open.take() {
+                Some((kind, so_far)) => (kind, format!("{} {}", so_far, text)),
+                None => {
+                    let text = text.trim_start_matches("@custom:scribble").trim();
+                    if let Some(rest) = text.strip_prefix("#if_succeeds") {
+                        (PropertyKind::IfSucceeds, rest.to_string())
+                    } else if let Some(rest) = text.strip_prefix("#invariant") {
+                        (PropertyKind::Invariant, rest.to_string())
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            match text.find(';') {
+                Some(end) => {
+                    text.truncate(end);
+                    pending.push(parse_annotation(kind, &text));
+                }
+                None => open = Some((kind, text)),
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some((kind, text)) = open.take() {
+            pending.push(parse_annotation(kind, &text));
+        }
+        if !pending.is_empty() {
+            declarations.push((index, std::mem::take(&mut pending)));
+        }
+    }
+    declarations
+}
+
+/// Text of a comment line without its markers
+fn comment_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    ["///", "//", "/**", "/*", "*"].iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+        .map(|text| text.trim_end_matches("*/").trim())
+}
+
+/// Split an annotation body into its `{:msg "..."}` label and its expression
+fn parse_annotation(kind: PropertyKind, text: &str) -> Annotation {
+    let text = text.trim();
+    let (message, expression) = match text.strip_prefix('{').and_then(|rest| rest.split_once('}')) {
+        Some((attributes, expression)) => {
+            let message = attributes.trim().strip_prefix(":msg").map(|message| message.trim().trim_matches(['"', '\'']).to_string());
+            (message, expression.trim())
+        }
+        None => (None, text),
+    };
+    let expression = expression.split_whitespace().collect::<Vec<_>>().join(" ");
+    Annotation {
+        kind,
+        label: message.filter(|message| !message.is_empty()).unwrap_or_else(|| expression.clone()),
+        expression,
+    }
+}
+
+fn contract_name(header: &str) -> Option<&str> {
+    let rest = header.strip_prefix("abstract contract ").or_else(|| header.strip_prefix("contract "))?;
+    rest.split(|c: char| c.is_whitespace() || c == '{').next().filter(|name| !name.is_empty())
+}
+
+fn enclosing_contract(lines: &[&str], line: usize) -> Option<String> {
+    lines[..line].iter().rev().find_map(|line| contract_name(line.trim())).map(str::to_string)
+}
+
+/// Solidity for a Scribble expression: `a ==> b` becomes `(!(a) || (b))`
+fn translate(expression: &str) -> Result<String, String> {
+    if ["forall", "unchecked_sum", "let"].iter().any(|keyword| find_word(expression, keyword).is_some()) {
+        return Err("forall, let and unchecked_sum are not supported".to_string());
+    }
+    let mut depth = 0i32;
+    let bytes = expression.as_bytes();
+    for index in 0..bytes.len() {
+        match bytes[index] {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth -= 1,
+            b'=' if depth == 0 && expression[index..].starts_with("==>") => {
+                let premise = expression[..index].trim();
+                let conclusion = translate(expression[index + 3..].trim())?;
+                return Ok(format!("(!({}) || {})", premise, conclusion));
+            }
+            _ => {}
+        }
+    }
+    Ok(format!("({})", expression))
+}
+
+/// Rewrite the function starting at `start`: the original keeps its header and calls its body, moved
+/// to an internal function, then checks the postconditions. Returns the end of the replaced text,
+/// the replacement and the function's name.
+fn wrap_function(
+    source: &str,
+    start: usize,
+    indent: &str,
+    index: usize,
+    postconditions: &[&Annotation],
+    state: &[(String, String)],
+) -> Result<(usize, String, String), String> {
+    let (open, byte) = find_code(source, start, b"{;").ok_or("function body not found")?;
+    if byte == b';' {
+        return Err("the function has no body".to_string());
+    }
+    let close = closing(source, open).ok_or("unbalanced braces in the function body")?;
+    let params_open = start + source[start..open].find('(').ok_or("malformed function header")?;
+    let params_close = closing(source, params_open).ok_or("malformed parameter list")?;
+    let name = source[start + "function".len()..params_open].trim().to_string();
+
+    let params: Vec<(String, Option<String>)> = split_list(&source[params_open + 1..params_close]).iter().map(|param| split_param(param)).collect();
+    if params.iter().any(|(_, name)| name.is_none()) {
+        return Err("every parameter needs a name".to_string());
+    }
+    let rest = &source[params_close + 1..open];
+    let (modifiers, returns) = match find_word(rest, "returns") {
+        Some(at) => {
+            let returns_open = params_close + 1 + at + source[params_close + 1 + at..].find('(').ok_or("malformed returns")?;
+            let returns_close = closing(source, returns_open).ok_or("malformed returns")?;
+            let modifiers = format!("{} {}", &rest[..at], &source[returns_close + 1..open]);
+            (modifiers, split_list(&source[returns_open + 1..returns_close]))
+        }
+        None => (rest.to_string(), Vec::new()),
+    };
+    let modifiers = modifiers.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mutability = ["pure", "view"].into_iter().find(|keyword| modifiers.split_whitespace().any(|word| word == *keyword));
+
+    let unnamed = returns.iter().filter(|ret| split_param(ret).1.is_none()).count();
+    let returns: Vec<(String, String)> = returns.iter().enumerate().map(|(position, ret)| {
+        let (param_type, ret_name) = split_param(ret);
+        let ret_name = ret_name.unwrap_or_else(|| if unnamed == 1 { "$result".to_string() } else { format!("$result{}", position) });
+        (param_type, ret_name)
+    }).collect();
+
+    let original = format!("__scribble_{}_{}", index, name);
+    let param_list = params.iter().map(|(param_type, name)| format!("{} {}", param_type, name.as_deref().unwrap_or_default())).collect::<Vec<_>>().join(", ");
+    let return_list = returns.iter().map(|(param_type, name)| format!("{} {}", param_type, name)).collect::<Vec<_>>().join(", ");
+    let arguments = params.iter().filter_map(|(_, name)| name.clone()).collect::<Vec<_>>().join(", ");
+
+    let mut typed: Vec<(String, String)> = params.iter().map(|(param_type, name)| (without_location(param_type), name.clone().unwrap_or_default())).collect();
+    typed.extend(state.iter().cloned());
+    let mut olds = Vec::new();
+    let mut checks = Vec::new();
+    for annotation in postconditions {
+        let expression = replace_old(&annotation.expression, &mut olds)?;
+        checks.push(format!("{indent}    if (!{}) revert(\"{}\");\n", translate(&expression)?, escape(&format!("{}{}", VIOLATION_PREFIX, annotation.label))));
+    }
+
+    let mut text = format!("function {}({}) {}", name, param_list, modifiers);
+    if !returns.is_empty() {
+        text.push_str(&format!(" returns ({})", return_list));
+    }
+    text.push_str(" {\n");
+    for (position, expression) in olds.iter().enumerate() {
+        text.push_str(&format!("{indent}    {} __scribble_old_{} = {};\n", with_location(&infer_type(expression, &typed)), position, expression));
+    }
+    let call = format!("{}({})", original, arguments);
+    match returns.len() {
+        0 => text.push_str(&format!("{indent}    {};\n", call)),
+        1 => text.push_str(&format!("{indent}    {} = {};\n", returns[0].1, call)),
+        _ => text.push_str(&format!("{indent}    ({}) = {};\n", returns.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>().join(", "), call)),
+    }
+    checks.iter().for_each(|check| text.push_str(check));
+    text.push_str(&format!("{indent}}}\n\n{indent}function {}({}) internal", original, param_list));
+    if let Some(mutability) = mutability {
+        text.push_str(&format!(" {}", mutability));
+    }
+    if !returns.is_empty() {
+        text.push_str(&format!(" returns ({})", return_list));
+    }
+    text.push(' ');
+    text.push_str(&source[open..=close]);
+    Ok((close + 1, text, name))
+}
+
+/// Replace every `old(expr)` with a variable holding `expr` from before the call
+fn replace_old(expression: &str, olds: &mut Vec<String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = expression;
+    while let Some(at) = find_word(rest, "old") {
+        let after = rest[at + 3..].trim_start();
+        if !after.starts_with('(') {
+            result.push_str(&rest[..at + 3]);
+            rest = &rest[at + 3..];
+            continue;
+        }
+        let open = rest.len() - after.len();
+        let close = closing(rest, open).ok_or("unbalanced parentheses in old(...)")?;
+        result.push_str(&rest[..at]);
+        result.push_str(&format!("__scribble_old_{}", olds.len()));
+        olds.push(rest[open + 1..close].trim().to_string());
+        rest = &rest[close + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Type of an `old(...)` expression: the declared type of the parameter or state variable it reads,
+/// through indexing and `.length`; `uint256` when it cannot be told
+fn infer_type(expression: &str, typed: &[(String, String)]) -> String {
+    let root_length = expression.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$')).unwrap_or(expression.len());
+    let Some((declared, _)) = typed.iter().find(|(_, name)| name == &expression[..root_length]) else {
+        return "uint256".to_string();
+    };
+    let mut current = declared.clone();
+    let mut rest = expression[root_length..].trim();
+    while !rest.is_empty() {
+        if rest.starts_with('[') {
+            let Some(close) = closing(rest, 0) else { break };
+            current = element_type(&current);
+            rest = rest[close + 1..].trim();
+        } else if rest == ".length" {
+            return "uint256".to_string();
+        } else {
+            // Arithmetic keeps the type of its operands; anything else is a guess
+            return if current.starts_with("uint") || current.starts_with("int") { current } else { "uint256".to_string() };
+        }
+    }
+    current
+}
+
+/// Value type of a mapping, element type of an array
+fn element_type(container: &str) -> String {
+    if let Some(inner) = container.strip_prefix("mapping").map(str::trim).and_then(|inner| inner.strip_prefix('(')).and_then(|inner| inner.strip_suffix(')')) {
+        let mut depth = 0i32;
+        for (index, byte) in inner.bytes().enumerate() {
+            match byte {
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                b'=' if depth == 0 && inner[index..].starts_with("=>") => return inner[index + 2..].trim().to_string(),
+                _ => {}
+            }
+        }
+    }
+    match container.rfind('[') {
+        Some(at) if container.ends_with(']') => container[..at].trim().to_string(),
+        _ => "uint256".to_string(),
+    }
+}
+
+/// Copies of reference types live in memory
+fn with_location(param_type: &str) -> String {
+    let is_reference = param_type.ends_with(']') || param_type == "string" || param_type == "bytes"
+        || param_type.starts_with(|c: char| c.is_ascii_uppercase());
+    if is_reference { format!("{} memory", param_type) } else { param_type.to_string() }
+}
+
+/// (type, name) of the state variables of the contract enclosing `line`
+fn state_variables(source: &str, lines: &[&str], line_starts: &[usize], line: usize) -> Vec<(String, String)> {
+    let Some(header) = (0..line).rev().find(|index| contract_name(lines[*index].trim()).is_some()) else {
+        return Vec::new();
+    };
+    let Some((open, _)) = find_code(source, line_starts[header], b"{") else {
+        return Vec::new();
+    };
+    let Some(close) = closing(source, open) else {
+        return Vec::new();
+    };
+
+    let mut variables = Vec::new();
+    let mut depth = 0i32;
+    let mut statement = String::new();
+    for (_, byte) in code_bytes(source, open + 1).into_iter().take_while(|(index, _)| *index < close) {
+        match byte {
+            b'{' => {
+                depth += 1;
+                statement.clear();
+            }
+            b'}' => {
+                depth -= 1;
+                statement.clear();
+            }
+            b';' if depth == 0 => {
+                if let Some(variable) = parse_state_variable(&statement) {
+                    variables.push(variable);
+                }
+                statement.clear();
+            }
+            _ if depth == 0 => statement.push(byte as char),
+            _ => {}
+        }
+    }
+    variables
+}
+
+fn parse_state_variable(statement: &str) -> Option<(String, String)> {
+    let declaration = statement.split('=').next()?.split_whitespace().collect::<Vec<_>>().join(" ");
+    let first = declaration.split(|c: char| c.is_whitespace() || c == '(').next()?;
+    if ["function", "event", "error", "modifier", "using", "struct", "enum", "constructor", "pragma", "import"].contains(&first) {
+        return None;
+    }
+    let (param_type, name) = declaration.rsplit_once(' ')?;
+    let param_type = param_type.split(' ').filter(|word| !VARIABLE_KEYWORDS.contains(word)).collect::<Vec<_>>().join(" ");
+    Some((param_type.replace("( ", "(").replace(" )", ")"), name.to_string()))
+}
+
+/// (type with data location, name) of a parameter
+fn split_param(param: &str) -> (String, Option<String>) {
+    let words: Vec<&str> = param.split_whitespace().collect();
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() && !LOCATION_KEYWORDS.contains(last) => (rest.join(" "), Some(last.to_string())),
+        _ => (words.join(" "), None),
+    }
+}
+
+/// A parameter type without its data location
+fn without_location(param_type: &str) -> String {
+    param_type.split_whitespace()
+        .filter(|word| *word == "payable" || !LOCATION_KEYWORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Comma-separated items of a parameter list, ignoring commas nested in parentheses
+fn split_list(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in list.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+    items
+}
+
+/// Offset of `word` in `text` where it is not part of a longer identifier
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$';
+    text.match_indices(word).map(|(index, _)| index).find(|index| {
+        !text[..*index].ends_with(is_identifier) && !text[index + word.len()..].starts_with(is_identifier)
+    })
+}
+
+/// Bytes of `source` from `from` on that are code, not comments or string literals
+fn code_bytes(source: &str, from: usize) -> Vec<(usize, u8)> {
+    let bytes = source.as_bytes();
+    let mut code = Vec::new();
+    let mut index = from;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index += 2;
+                while index + 1 < bytes.len() && !(bytes[index] == b'*' && bytes[index + 1] == b'/') {
+                    index += 1;
+                }
+                index += 2;
+            }
+            quote @ (b'"' | b'\'') => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != quote {
+                    index += if bytes[index] == b'\\' { 2 } else { 1 };
+                }
+                index += 1;
+            }
+            byte => {
+                code.push((index, byte));
+                index += 1;
+            }
+        }
+    }
+    code
+}
+
+/// First code byte at or after `from` that is one of `targets`
+fn find_code(source: &str, from: usize, targets: &[u8]) -> Option<(usize, u8)> {
+    code_bytes(source, from).into_iter().find(|(_, byte)| targets.contains(byte))
+}
+
+/// Offset of the bracket closing the one at `open`
+fn closing(source: &str, open: usize) -> Option<usize> {
+    let (opening, closing) = match source.as_bytes().get(open)? {
+        b'{' => (b'{', b'}'),
+        b'(' => (b'(', b')'),
+        b'[' => (b'[', b']'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (index, byte) in code_bytes(source, open) {
+        if byte == opening {
+            depth += 1;
+        } else if byte == closing {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Instrumented copy of a source file, written next to the original so its imports and Foundry
+/// project still resolve; removed again when dropped
+pub struct InstrumentedFile {
+    path: PathBuf,
+}
+
+impl InstrumentedFile {
+    pub fn write(original: &Path, source: &str) -> Result<Self> {
+        let stem = original.file_stem().and_then(|stem| stem.to_str()).context("Invalid source file name")?;
+        let path = original.with_file_name(format!("{}.scribble.sol", stem));
+        std::fs::write(&path, source)
+            .with_context(|| format!("Failed to write instrumented source {}", path.display()))?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for InstrumentedFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = r#"pragma solidity ^0.8.0;
+
+/// #invariant {:msg "supply covers balances"} totalSupply >= balances[owner];
+contract Token {
+    mapping(address => uint256) public balances;
+    uint256 public totalSupply;
+    address public owner;
+
+    /// #if_succeeds {:msg "receiver gains"} to != msg.sender ==> balances[to] == old(balances[to]) + amount;
+    /// #if_succeeds $result;
+    function transfer(address to, uint256 amount) public returns (bool) {
+        balances[msg.sender] -= amount;
+        balances[to] += amount;
+        return true;
+    }
+}
+"#;
+
+    /// The instrumented source must still be Solidity
+    fn assert_parses(source: &str) {
+        if let Err(diagnostics) = solang_parser::parse(source, 0) {
+            panic!("{:?}\n{}", diagnostics, source);
+        }
+    }
+
+    #[test]
+    fn sources_without_annotations_are_left_alone() {
+        assert!(instrument("contract A { function f() public {} }").is_none());
+    }
+
+    #[test]
+    fn annotations_become_properties_with_their_labels() {
+        let instrumentation = instrument(TOKEN).unwrap();
+        let properties: Vec<String> = instrumentation.properties.iter().map(Property::to_string).collect();
+        assert_eq!(properties, [
+            "#invariant Token.invariant_scribble_1(): supply covers balances",
+            "#if_succeeds Token.transfer: receiver gains",
+            "#if_succeeds Token.transfer: $result",
+        ]);
+        assert!(instrumentation.skipped.is_empty(), "{:?}", instrumentation.skipped);
+    }
+
+    #[test]
+    fn postconditions_wrap_the_function_body() {
+        let source = instrument(TOKEN).unwrap().source;
+        assert_parses(&source);
+        assert!(source.contains("function transfer(address to, uint256 amount) public returns (bool $result) {"), "{}", source);
+        assert!(source.contains("uint256 __scribble_old_0 = balances[to];"), "{}", source);
+        assert!(source.contains("$result = __scribble_1_transfer(to, amount);"), "{}", source);
+        assert!(source.contains("if (!(!(to != msg.sender) || (balances[to] == __scribble_old_0 + amount))) revert(\"Scribble property violated: receiver gains\");"), "{}", source);
+        assert!(source.contains("function __scribble_1_transfer(address to, uint256 amount) internal returns (bool $result) {"), "{}", source);
+    }
+
+    #[test]
+    fn invariants_become_view_functions_of_their_contract() {
+        let source = instrument(TOKEN).unwrap().source;
+        assert!(source.contains("function invariant_scribble_1() public view returns (bool) {"), "{}", source);
+        assert!(source.contains("require((totalSupply >= balances[owner]), \"Scribble property violated: supply covers balances\");"), "{}", source);
+    }
+
+    #[test]
+    fn annotations_may_span_several_comment_lines() {
+        let source = "contract A {\n    uint256 x;\n    /// #if_succeeds x ==\n    ///     old(x) + 1;\n    function inc() public { x += 1; }\n}\n";
+        let instrumentation = instrument(source).unwrap();
+        assert_eq!(instrumentation.properties[0].label, "x == old(x) + 1");
+        assert_parses(&instrumentation.source);
+    }
+
+    #[test]
+    fn implications_nest_to_the_right() {
+        assert_eq!(translate("a ==> b ==> c").unwrap(), "(!(a) || (!(b) || (c)))");
+        assert_eq!(translate("f(a == b) && c").unwrap(), "(f(a == b) && c)");
+        assert!(translate("forall (uint i in a) a[i] > 0").is_err());
+    }
+
+    #[test]
+    fn unsupported_annotations_are_skipped_with_a_reason() {
+        let source = r#"
+            /// #if_succeeds x > 0;
+            contract A {
+                uint256 x;
+                /// #invariant x > 0;
+                function f() public {}
+                /// #if_succeeds forall (uint i in a) a[i] > 0;
+                function g(uint256[] memory a) public {}
+                /// #if_succeeds x > 0;
+                function h(uint256) public {}
+            }
+        "#;
+        let instrumentation = instrument(source).unwrap();
+        assert!(instrumentation.properties.is_empty());
+        assert_eq!(instrumentation.skipped.len(), 4, "{:?}", instrumentation.skipped);
+        assert!(instrumentation.skipped[0].contains("contract-wide postconditions are not supported"));
+        assert!(instrumentation.skipped[1].contains("invariants belong to contracts"));
+        assert!(instrumentation.skipped[2].contains("forall"));
+        assert!(instrumentation.skipped[3].contains("every parameter needs a name"));
+        assert_eq!(instrumentation.source, source);
+    }
+
+    #[test]
+    fn violations_are_recognized_in_revert_reasons() {
+        assert_eq!(violated_property("execution reverted: Scribble property violated: receiver gains"), Some("receiver gains"));
+        assert_eq!(violated_property("Reverted(\"Scribble property violated: x > 0\")"), Some("x > 0"));
+        assert_eq!(violated_property("execution reverted: paused"), None);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::anvil_executor::calculate_selector;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// External functions of common proxy patterns (Transparent, UUPS, Beacon, EIP-1967 admin helpers).
+/// A target function that shares a selector with one of these clashes with the proxy in front of it.
+pub const PROXY_ADMIN_SIGNATURES: &[&str] = &[
+    "admin()",
+    "implementation()",
+    "changeAdmin(address)",
+    "upgradeTo(address)",
+    "upgradeToAndCall(address,bytes)",
+    "proxiableUUID()",
+    "beacon()",
+    "upgradeBeaconToAndCall(address,bytes)",
+    "getProxyAdmin(address)",
+    "getProxyImplementation(address)",
+    "changeProxyAdmin(address,address)",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectorIssueKind {
+    /// Two functions of the same contract share a selector
+    Collision,
+    /// A function shares a selector with a well-known proxy admin function but has a different signature
+    ProxyAdminClash,
+    /// The proxy's own dispatcher answers the selector, so calls never reach the implementation
+    DispatcherShadowing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectorIssue {
+    pub kind: SelectorIssueKind,
+    pub contract: String,
+    /// `0x`-prefixed 4-byte selector
+    pub selector: String,
+    pub signature: String,
+    /// The function it collides with or is shadowed by, e.g. `Proxy.upgradeTo(address)`
+    pub conflicts_with: String,
+}
+
+impl std::fmt::Display for SelectorIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            SelectorIssueKind::Collision => write!(f, "{}.{} and {} share selector {}",
+                self.contract, self.signature, self.conflicts_with, self.selector),
+            SelectorIssueKind::ProxyAdminClash => write!(f, "{}.{} clashes with proxy admin function {} (selector {})",
+                self.contract, self.signature, self.conflicts_with, self.selector),
+            SelectorIssueKind::DispatcherShadowing => write!(f, "{}.{} is shadowed by {} (selector {}), calls through the proxy never reach it",
+                self.contract, self.signature, self.conflicts_with, self.selector),
+        }
+    }
+}
+
+fn selector_hex(signature: &str) -> String {
+    format!("0x{}", hex::encode(calculate_selector(signature)))
+}
+
+/// Selector collisions inside one contract and clashes with well-known proxy admin functions.
+/// A function with exactly the signature of an admin function (a UUPS `upgradeTo`) is not a clash.
+pub fn check_contract(contract: &str, signatures: &[String]) -> Vec<SelectorIssue> {
+    let mut issues = Vec::new();
+
+    let mut by_selector: BTreeMap<[u8; 4], Vec<&str>> = BTreeMap::new();
+    for signature in signatures {
+        let entry = by_selector.entry(calculate_selector(signature)).or_default();
+        if !entry.contains(&signature.as_str()) {
+            entry.push(signature);
+        }
+    }
+    for (selector, colliding) in &by_selector {
+        for other in colliding.iter().skip(1) {
+            issues.push(SelectorIssue {
+                kind: SelectorIssueKind::Collision,
+                contract: contract.to_string(),
+                selector: format!("0x{}", hex::encode(selector)),
+                signature: other.to_string(),
+                conflicts_with: colliding[0].to_string(),
+            });
+        }
+    }
+
+    for signature in signatures {
+        let selector = calculate_selector(signature);
+        for admin in PROXY_ADMIN_SIGNATURES {
+            if *admin != signature && calculate_selector(admin) == selector {
+                issues.push(SelectorIssue {
+                    kind: SelectorIssueKind::ProxyAdminClash,
+                    contract: contract.to_string(),
+                    selector: selector_hex(signature),
+                    signature: signature.clone(),
+                    conflicts_with: admin.to_string(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// A contract is treated as a proxy when it forwards unknown calls (has a fallback) and
+/// exposes at least one well-known proxy admin function
+pub fn looks_like_proxy(signatures: &[String], has_fallback: bool) -> bool {
+    has_fallback && signatures.iter().any(|signature| PROXY_ADMIN_SIGNATURES.contains(&signature.as_str()))
+}
+
+/// Implementation functions whose selector is answered by the proxy's own dispatcher
+pub fn check_proxy_shadowing(proxy: &str, proxy_signatures: &[String], implementation: &str, implementation_signatures: &[String]) -> Vec<SelectorIssue> {
+    implementation_signatures.iter()
+        .filter_map(|signature| {
+            let selector = calculate_selector(signature);
+            proxy_signatures.iter()
+                .find(|proxy_signature| calculate_selector(proxy_signature) == selector)
+                .map(|proxy_signature| SelectorIssue {
+                    kind: SelectorIssueKind::DispatcherShadowing,
+                    contract: implementation.to_string(),
+                    selector: selector_hex(signature),
+                    signature: signature.clone(),
+                    conflicts_with: format!("{}.{}", proxy, proxy_signature),
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,180 @@
+//! Which oracle reported a finding, and how much it matters. A call that reverts is the weakest
+//! signal the fuzzer has; a re-entered method or an owner changed by a stranger is a bug on its
+//! own. Consumers of the report (the benchmark harness, CI gates) filter on these instead of
+//! counting every failed run.
+
+use crate::events;
+use crate::oracle;
+use crate::scribble;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            other => Err(format!("unknown severity '{}' (expected info, low, medium or high)", other)),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The oracle behind a finding, invariant violation or bricking call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OracleClass {
+    /// A call reverted and the revert reproduced
+    #[default]
+    FailingCall,
+    /// A call reverted but not on every re-execution
+    Flaky,
+    /// A Scribble `#if_succeeds` postcondition failed
+    Property,
+    /// An invariant returned false or reverted
+    Invariant,
+    /// A call left the contract unusable for calls that worked before it
+    Bricking,
+    /// The reentrancy attacker re-entered a method (`--reentrancy`)
+    Reentrancy,
+    /// Arithmetic wrapped around with an argument (Solidity < 0.8)
+    Wraparound,
+    /// A privileged method changed owner or admin state for a stranger (`--access-control`)
+    UnauthorizedChange,
+    /// A call left different state under a reduced gas limit
+    GasDependent,
+    /// A call's outcome changed with the block context
+    BlockContext,
+    /// A call succeeded with a precompile or system contract as an address argument
+    SystemAddress,
+    /// An account other than the deployer or owner took ETH out of the contract in a sequence
+    EtherDrain,
+    /// A call hit a Solidity panic (failed assertion, division by zero, index out of bounds…)
+    Panic,
+    /// A call emitted an event no correct call emits (`--events`)
+    Event,
+}
+
+impl OracleClass {
+    pub const ALL: [OracleClass; 14] = [
+        OracleClass::FailingCall,
+        OracleClass::Flaky,
+        OracleClass::Property,
+        OracleClass::Invariant,
+        OracleClass::Bricking,
+        OracleClass::Reentrancy,
+        OracleClass::Wraparound,
+        OracleClass::UnauthorizedChange,
+        OracleClass::GasDependent,
+        OracleClass::BlockContext,
+        OracleClass::SystemAddress,
+        OracleClass::EtherDrain,
+        OracleClass::Panic,
+        OracleClass::Event,
+    ];
+
+    /// Class of a finding, from the error its oracle reported
+    pub fn of_finding(error: &str, flaky: bool) -> Self {
+        if flaky {
+            OracleClass::Flaky
+        } else if error.starts_with("Ether drain:") {
+            OracleClass::EtherDrain
+        } else if error.starts_with(events::EVENT_ORACLE) {
+            OracleClass::Event
+        } else if error.starts_with("Reentrancy:") {
+            OracleClass::Reentrancy
+        } else if error.starts_with("Integer underflow") || error.starts_with("Integer overflow") {
+            OracleClass::Wraparound
+        } else if error.starts_with("Unauthorized state change") {
+            OracleClass::UnauthorizedChange
+        } else if error.starts_with("Gas-dependent state") {
+            OracleClass::GasDependent
+        } else if error.starts_with("Outcome depends on block context") {
+            OracleClass::BlockContext
+        } else if error.starts_with("Succeeded with ") {
+            OracleClass::SystemAddress
+        } else if scribble::violated_property(error).is_some() {
+            OracleClass::Property
+        } else if oracle::panic_code(error).is_some() {
+            OracleClass::Panic
+        } else {
+            OracleClass::FailingCall
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            OracleClass::Property
+            | OracleClass::Invariant
+            | OracleClass::Reentrancy
+            | OracleClass::UnauthorizedChange
+            | OracleClass::EtherDrain
+            | OracleClass::Event => Severity::High,
+            OracleClass::Bricking | OracleClass::Wraparound | OracleClass::GasDependent | OracleClass::Panic => Severity::Medium,
+            OracleClass::FailingCall | OracleClass::BlockContext | OracleClass::SystemAddress => Severity::Low,
+            OracleClass::Flaky => Severity::Info,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OracleClass::FailingCall => "failing-call",
+            OracleClass::Flaky => "flaky",
+            OracleClass::Property => "property",
+            OracleClass::Invariant => "invariant",
+            OracleClass::Bricking => "bricking",
+            OracleClass::Reentrancy => "reentrancy",
+            OracleClass::Wraparound => "wraparound",
+            OracleClass::UnauthorizedChange => "unauthorized-change",
+            OracleClass::GasDependent => "gas-dependent",
+            OracleClass::BlockContext => "block-context",
+            OracleClass::SystemAddress => "system-address",
+            OracleClass::EtherDrain => "ether-drain",
+            OracleClass::Panic => "panic",
+            OracleClass::Event => "event",
+        }
+    }
+}
+
+impl FromStr for OracleClass {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        OracleClass::ALL.into_iter()
+            .find(|class| class.name() == text)
+            .ok_or_else(|| format!("unknown oracle '{}' (expected one of {})",
+                text, OracleClass::ALL.map(|class| class.name()).join(", ")))
+    }
+}
+
+impl fmt::Display for OracleClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,137 @@
+use crate::types::SolidityValue;
+use ethers::types::{I256, U256};
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Simpler variants of a value, simplest first: zero and halves of numbers, `false`, the zero
+/// address, zeroed fixed bytes, and shorter strings, bytes and arrays (then their elements shrunk).
+/// Every candidate is strictly simpler than `value`, so shrinking by repeatedly taking one terminates.
+pub fn candidates(value: &SolidityValue) -> Vec<SolidityValue> {
+    match value {
+        SolidityValue::Uint8(v) => unsigned(U256::from(*v)).into_iter().map(|c| SolidityValue::Uint8(c.as_u32() as u8)).collect(),
+        SolidityValue::Uint16(v) => unsigned(U256::from(*v)).into_iter().map(|c| SolidityValue::Uint16(c.as_u32() as u16)).collect(),
+        SolidityValue::Uint32(v) => unsigned(U256::from(*v)).into_iter().map(|c| SolidityValue::Uint32(c.as_u32())).collect(),
+        SolidityValue::Uint64(v) => unsigned(U256::from(*v)).into_iter().map(|c| SolidityValue::Uint64(c.as_u64())).collect(),
+        SolidityValue::Uint128(v) => unsigned(U256::from(*v)).into_iter().map(|c| SolidityValue::Uint128(c.as_u128())).collect(),
+        SolidityValue::Uint256(v) => match U256::from_dec_str(v) {
+            Ok(v) => unsigned(v).into_iter().map(|c| SolidityValue::Uint256(c.to_string())).collect(),
+            Err(_) => Vec::new(),
+        },
+        SolidityValue::Int8(v) => signed(I256::from(*v)).into_iter().map(|c| SolidityValue::Int8(c.as_i32() as i8)).collect(),
+        SolidityValue::Int16(v) => signed(I256::from(*v)).into_iter().map(|c| SolidityValue::Int16(c.as_i32() as i16)).collect(),
+        SolidityValue::Int32(v) => signed(I256::from(*v)).into_iter().map(|c| SolidityValue::Int32(c.as_i32())).collect(),
+        SolidityValue::Int64(v) => signed(I256::from(*v)).into_iter().map(|c| SolidityValue::Int64(c.as_i64())).collect(),
+        SolidityValue::Int128(v) => signed(I256::from(*v)).into_iter().map(|c| SolidityValue::Int128(c.as_i128())).collect(),
+        SolidityValue::Int256(v) => match I256::from_dec_str(v) {
+            Ok(v) => signed(v).into_iter().map(|c| SolidityValue::Int256(c.to_string())).collect(),
+            Err(_) => Vec::new(),
+        },
+        SolidityValue::Address(addr) if !addr.eq_ignore_ascii_case(ZERO_ADDRESS) => vec![SolidityValue::Address(ZERO_ADDRESS.to_string())],
+        SolidityValue::Address(_) => Vec::new(),
+        SolidityValue::Bool(true) => vec![SolidityValue::Bool(false)],
+        SolidityValue::Bool(false) => Vec::new(),
+        SolidityValue::Bytes1(v) => zeroed(v).map(SolidityValue::Bytes1).into_iter().collect(),
+        SolidityValue::Bytes2(v) => zeroed(v).map(SolidityValue::Bytes2).into_iter().collect(),
+        SolidityValue::Bytes4(v) => zeroed(v).map(SolidityValue::Bytes4).into_iter().collect(),
+        SolidityValue::Bytes8(v) => zeroed(v).map(SolidityValue::Bytes8).into_iter().collect(),
+        SolidityValue::Bytes16(v) => zeroed(v).map(SolidityValue::Bytes16).into_iter().collect(),
+        SolidityValue::Bytes32(v) => zeroed(v).map(SolidityValue::Bytes32).into_iter().collect(),
+        SolidityValue::String(s) => {
+            let chars: Vec<char> = s.chars().collect();
+            shorter(&chars).into_iter().map(|c| SolidityValue::String(c.into_iter().collect())).collect()
+        }
+        SolidityValue::Bytes(v) => {
+            let mut candidates: Vec<_> = shorter(v).into_iter().map(SolidityValue::Bytes).collect();
+            if v.iter().any(|byte| *byte != 0) {
+                candidates.push(SolidityValue::Bytes(vec![0; v.len()]));
+            }
+            candidates
+        }
+        SolidityValue::Array(items) => {
+            let mut candidates: Vec<_> = shorter(items).into_iter().map(SolidityValue::Array).collect();
+            for (index, item) in items.iter().enumerate() {
+                candidates.extend(candidates_of(item).into_iter().map(|simpler| {
+                    let mut items = items.clone();
+                    items[index] = simpler;
+                    SolidityValue::Array(items)
+                }));
+            }
+            candidates
+        }
+        // The length is part of the type; only the elements get simpler
+        SolidityValue::FixedArray(items) => items.iter().enumerate()
+            .flat_map(|(index, item)| candidates_of(item).into_iter().map(move |simpler| (index, simpler)))
+            .map(|(index, simpler)| {
+                let mut items = items.clone();
+                items[index] = simpler;
+                SolidityValue::FixedArray(items)
+            })
+            .collect(),
+        SolidityValue::Struct(fields) => fields.iter().enumerate()
+            .flat_map(|(index, (_, field))| candidates_of(field).into_iter().map(move |simpler| (index, simpler)))
+            .map(|(index, simpler)| {
+                let mut fields = fields.clone();
+                fields[index].1 = simpler;
+                SolidityValue::Struct(fields)
+            })
+            .collect(),
+    }
+}
+
+/// Candidates of a nested value; only the simplest one, so arrays and structs stay cheap to shrink
+fn candidates_of(value: &SolidityValue) -> Vec<SolidityValue> {
+    candidates(value).into_iter().take(1).collect()
+}
+
+/// 0, 1, half and one less, keeping those below `value`
+fn unsigned(value: U256) -> Vec<U256> {
+    let mut candidates: Vec<U256> = Vec::new();
+    if value.is_zero() {
+        return candidates;
+    }
+    for candidate in [U256::zero(), U256::one(), value / 2, value - 1] {
+        if candidate < value && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// 0, half (towards zero), one closer to zero and the absolute value of a negative number
+fn signed(value: I256) -> Vec<I256> {
+    let mut candidates: Vec<I256> = Vec::new();
+    if value.is_zero() {
+        return candidates;
+    }
+    let closer = if value.is_negative() { value + I256::one() } else { value - I256::one() };
+    let mut options = vec![I256::zero(), value / I256::from(2), closer];
+    if value.is_negative() && value != I256::MIN {
+        options.push(-value);
+    }
+    for candidate in options {
+        if candidate != value && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+fn zeroed<const N: usize>(bytes: &[u8; N]) -> Option<[u8; N]> {
+    bytes.iter().any(|byte| *byte != 0).then_some([0; N])
+}
+
+/// Empty, first half and all but the last element
+fn shorter<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates: Vec<Vec<T>> = Vec::new();
+    if items.is_empty() {
+        return candidates;
+    }
+    candidates.push(Vec::new());
+    if items.len() / 2 > 0 {
+        candidates.push(items[..items.len() / 2].to_vec());
+    }
+    if items.len() - 1 > items.len() / 2 {
+        candidates.push(items[..items.len() - 1].to_vec());
+    }
+    candidates
+}
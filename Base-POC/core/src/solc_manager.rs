@@ -0,0 +1,116 @@
+//! Managed solc binaries, kept svm-style in `~/.svm/<version>/solc-<version>` so installs are
+//! shared with Foundry. The highest installed version satisfying a `pragma solidity` is used;
+//! when none does, the highest matching release is downloaded from binaries.soliditylang.org
+//! and checked against the Keccak-256 hash of the release list.
+
+use crate::oracle::SolidityVersion;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where the official solc builds are published, one directory per platform
+pub const RELEASES_URL: &str = "https://binaries.soliditylang.org";
+
+/// `~/.svm`, where svm and Foundry keep their solc binaries
+pub fn svm_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".svm"))
+}
+
+fn binary_path(svm_dir: &Path, version: SolidityVersion) -> PathBuf {
+    let file_name = format!("solc-{}{}", version, std::env::consts::EXE_SUFFIX);
+    svm_dir.join(version.to_string()).join(file_name)
+}
+
+/// Installed versions, highest first
+pub fn installed() -> Vec<(SolidityVersion, PathBuf)> {
+    let Some(svm_dir) = svm_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&svm_dir) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<(SolidityVersion, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version: semver::Version = name.parse().ok()?;
+            let version = SolidityVersion { major: version.major as u32, minor: version.minor as u32, patch: version.patch as u32 };
+            let path = binary_path(&svm_dir, version);
+            path.is_file().then_some((version, path))
+        })
+        .collect();
+    versions.sort_by_key(|(version, _)| std::cmp::Reverse(*version));
+    versions
+}
+
+/// Directory of the release list and builds for this machine
+fn platform() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("linux-amd64"),
+        // Builds for Intel Macs; newer ones are universal binaries, older ones run under Rosetta
+        ("macos", _) => Some("macosx-amd64"),
+        ("windows", "x86_64") => Some("windows-amd64"),
+        _ => None,
+    }
+}
+
+/// A solc satisfying `requirement` (a pragma constraint such as `^0.8.0`): the highest installed
+/// one, otherwise the highest release, downloaded first unless `offline`
+pub async fn ensure(requirement: &str, offline: bool) -> Result<(SolidityVersion, PathBuf)> {
+    if let Some(found) = installed().into_iter().find(|(version, _)| version.satisfies(requirement) == Some(true)) {
+        return Ok(found);
+    }
+    if offline {
+        return Err(anyhow::anyhow!("no installed solc satisfies {}", requirement));
+    }
+    let platform = platform()
+        .with_context(|| format!("no solc builds are published for {}-{}", std::env::consts::OS, std::env::consts::ARCH))?;
+    let svm_dir = svm_dir().context("no home directory to install solc into")?;
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(300)).build()?;
+
+    let list: Value = client.get(format!("{}/{}/list.json", RELEASES_URL, platform))
+        .send().await?
+        .error_for_status()?
+        .json().await
+        .context("Failed to read the solc release list")?;
+    let (version, build) = list.get("builds").and_then(Value::as_array).into_iter().flatten()
+        .filter(|build| build.get("prerelease").is_none())
+        .filter_map(|build| {
+            let version = SolidityVersion::parse(build.get("version")?.as_str()?)?;
+            // Older Windows releases are zip archives
+            let path = build.get("path")?.as_str()?;
+            (version.satisfies(requirement) == Some(true) && (platform != "windows-amd64" || path.ends_with(".exe")))
+                .then_some((version, build))
+        })
+        .max_by_key(|(version, _)| *version)
+        .with_context(|| format!("no solc release satisfies {}", requirement))?;
+    let path = build.get("path").and_then(Value::as_str).context("solc build without a path")?;
+    let expected_hash = build.get("keccak256").and_then(Value::as_str).context("solc build without a hash")?;
+
+    println!("📦 Downloading solc {} for {}", version, requirement);
+    let binary = client.get(format!("{}/{}/{}", RELEASES_URL, platform, path))
+        .send().await?
+        .error_for_status()?
+        .bytes().await
+        .with_context(|| format!("Failed to download solc {}", version))?;
+    let hash = format!("0x{}", hex::encode(Keccak256::digest(&binary)));
+    if !hash.eq_ignore_ascii_case(expected_hash) {
+        return Err(anyhow::anyhow!("solc {} download is corrupt: keccak256 {} instead of {}", version, hash, expected_hash));
+    }
+
+    let destination = binary_path(&svm_dir, version);
+    std::fs::create_dir_all(destination.parent().context("invalid solc path")?)?;
+    // Write next to the destination and rename, so a concurrent campaign never runs half a binary
+    let partial = destination.with_file_name(format!("solc-{}.partial-{}", version, std::process::id()));
+    std::fs::write(&partial, &binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&partial, std::fs::Permissions::from_mode(0o755))?;
+    }
+    std::fs::rename(&partial, &destination)?;
+    Ok((version, destination))
+}
@@ -0,0 +1,238 @@
+//! Compilation through solc's standard JSON interface. The `solc` command line used otherwise
+//! gets a single file and resolves nothing; here the input file and everything it imports are
+//! read by Fuzzhead, following relative imports and remappings the way solc names source units,
+//! and handed to `solc --standard-json` (or `solcjs --standard-json`, which has no file system
+//! access) together with the optimizer and EVM settings.
+
+use crate::config::CompilerOptions;
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// A remapping of import paths, `[context:]prefix=target`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remapping {
+    pub context: String,
+    pub prefix: String,
+    pub target: String,
+}
+
+impl std::str::FromStr for Remapping {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (key, target) = text.trim().split_once('=')
+            .ok_or_else(|| format!("invalid remapping '{}' (expected [context:]prefix=target)", text))?;
+        let (context, prefix) = key.split_once(':').unwrap_or(("", key));
+        if prefix.is_empty() {
+            return Err(format!("invalid remapping '{}': empty prefix", text));
+        }
+        Ok(Self { context: context.to_string(), prefix: prefix.to_string(), target: target.to_string() })
+    }
+}
+
+impl std::fmt::Display for Remapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.context.is_empty() {
+            write!(f, "{}:", self.context)?;
+        }
+        write!(f, "{}={}", self.prefix, self.target)
+    }
+}
+
+/// Sources of a compilation by source unit name, read from `base_dir`
+#[derive(Debug, Clone)]
+pub struct SourceTree {
+    pub base_dir: PathBuf,
+    /// Unit name of the file being compiled
+    pub root: String,
+    pub sources: Map<String, Value>,
+    pub remappings: Vec<Remapping>,
+}
+
+impl SourceTree {
+    /// The input file and every file it imports, directly or not. `base_dir` is what unit names
+    /// are relative to: the project root, or the file's own directory.
+    pub fn collect(source_path: &Path, base_dir: &Path, options: &CompilerOptions) -> Result<Self> {
+        let mut remappings = options.remappings.iter()
+            .map(|remapping| remapping.parse::<Remapping>().map_err(anyhow::Error::msg))
+            .collect::<Result<Vec<_>>>()?;
+        let remappings_txt = base_dir.join("remappings.txt");
+        if remappings_txt.is_file() {
+            let text = std::fs::read_to_string(&remappings_txt)?;
+            for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+                let remapping = line.parse::<Remapping>()
+                    .map_err(|e| anyhow::anyhow!("{}: {}", remappings_txt.display(), e))?;
+                // Remappings given in the configuration win over the project's
+                if !remappings.iter().any(|known| known.context == remapping.context && known.prefix == remapping.prefix) {
+                    remappings.push(remapping);
+                }
+            }
+        }
+
+        let root = source_path.strip_prefix(base_dir).map(unit_name)
+            .unwrap_or_else(|_| source_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default());
+        let mut tree = Self { base_dir: base_dir.to_path_buf(), root: root.clone(), sources: Map::new(), remappings };
+        let mut pending = VecDeque::from([(root, source_path.to_path_buf())]);
+        while let Some((unit, path)) = pending.pop_front() {
+            if tree.sources.contains_key(&unit) {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {} (source unit {})", path.display(), unit))?;
+            for import in imports(&content) {
+                let imported = tree.resolve(&unit, &import);
+                if !tree.sources.contains_key(&imported) {
+                    let file = tree.locate(&imported)
+                        .with_context(|| format!("Cannot resolve import \"{}\" of {} (looked for {})", import, unit, imported))?;
+                    pending.push_back((imported, file));
+                }
+            }
+            tree.sources.insert(unit, json!({ "content": content }));
+        }
+        Ok(tree)
+    }
+
+    /// Unit name an import of `importer` refers to: relative to the importer for `./` and `../`
+    /// paths, then remapped by the longest matching prefix, as solc does
+    fn resolve(&self, importer: &str, import: &str) -> String {
+        let path = if import.starts_with("./") || import.starts_with("../") {
+            let directory = importer.rsplit_once('/').map_or("", |(directory, _)| directory);
+            normalize(&format!("{}/{}", directory, import))
+        } else {
+            import.to_string()
+        };
+        self.remappings.iter()
+            .filter(|remapping| importer.starts_with(&remapping.context) && path.starts_with(&remapping.prefix))
+            .max_by_key(|remapping| (remapping.context.len(), remapping.prefix.len()))
+            .map_or(path.clone(), |remapping| format!("{}{}", remapping.target, &path[remapping.prefix.len()..]))
+    }
+
+    /// The file of a unit name: under the base directory, or under its `node_modules` for npm packages
+    pub fn locate(&self, unit: &str) -> Option<PathBuf> {
+        let unit_path = Path::new(unit);
+        if unit_path.is_absolute() {
+            return unit_path.is_file().then(|| unit_path.to_path_buf());
+        }
+        [self.base_dir.join(unit), self.base_dir.join("node_modules").join(unit)]
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// The standard JSON input compiling every source, with the outputs the fuzzer reads
+    pub fn input(&self, options: &CompilerOptions) -> Value {
+        let mut settings = json!({
+            "optimizer": { "enabled": options.optimizer, "runs": options.optimizer_runs },
+            "remappings": self.remappings.iter().map(Remapping::to_string).collect::<Vec<_>>(),
+            "outputSelection": { "*": { "*": ["abi", "evm.bytecode.object", "evm.bytecode.linkReferences", "evm.deployedBytecode.object", "metadata", "storageLayout"] } },
+        });
+        if let Some(evm_version) = &options.evm_version {
+            settings["evmVersion"] = json!(evm_version);
+        }
+        json!({ "language": "Solidity", "sources": self.sources, "settings": settings })
+    }
+}
+
+/// Run `compiler --standard-json` on `input` and return its output, failing on compiler errors
+pub fn compile(compiler: &str, input: &Value) -> Result<Value> {
+    let mut child = Command::new(compiler)
+        .arg("--standard-json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute {}", compiler))?;
+    child.stdin.take().context("No stdin for the compiler")?.write_all(input.to_string().as_bytes())?;
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // solcjs may print notices before the JSON document
+    let document = stdout.find('{').map(|start| &stdout[start..])
+        .with_context(|| format!("{} printed no standard JSON output: {}", compiler, String::from_utf8_lossy(&output.stderr)))?;
+    let json: Value = serde_json::from_str(document).context("Failed to parse standard JSON output")?;
+
+    let errors: Vec<&str> = json.get("errors").and_then(Value::as_array).into_iter().flatten()
+        .filter(|error| error.get("severity").and_then(Value::as_str) == Some("error"))
+        .map(|error| error.get("formattedMessage").or(error.get("message")).and_then(Value::as_str).unwrap_or("unknown error"))
+        .collect();
+    if !errors.is_empty() {
+        return Err(anyhow::anyhow!("Standard JSON compilation failed:\n{}", errors.join("\n")));
+    }
+    Ok(json)
+}
+
+/// Paths of the import directives of a source, skipping comments and other string literals
+pub fn imports(source: &str) -> Vec<String> {
+    let bytes = source.as_bytes();
+    let mut imports = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'i' if source[i..].starts_with("import")
+                && (i == 0 || !is_identifier(bytes[i - 1]))
+                && !bytes.get(i + 6).copied().is_some_and(is_identifier) =>
+            {
+                let end = source[i..].find(';').map_or(bytes.len(), |end| i + end);
+                let statement = &source[i..end];
+                if let Some(path) = first_string_literal(statement) {
+                    imports.push(path);
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    imports
+}
+
+fn first_string_literal(statement: &str) -> Option<String> {
+    let start = statement.find(['"', '\''])?;
+    let quote = statement[start..].chars().next()?;
+    let length = statement[start + 1..].find(quote)?;
+    Some(statement[start + 1..start + 1 + length].to_string())
+}
+
+fn is_identifier(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
+
+/// Drop `.` segments and fold `..` into the segment before it
+fn normalize(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// A relative path as a unit name, with `/` separators on every platform
+fn unit_name(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(segment.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
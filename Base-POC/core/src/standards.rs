@@ -0,0 +1,315 @@
+//! Token and access-control standards a deployed contract declares through ERC-165
+//! `supportsInterface` (or, for ERC-20, which predates it, exposes in its ABI), and the built-in
+//! properties that come with each of them.
+
+use crate::anvil_executor::calculate_selector;
+use crate::executor::Executor;
+use ethers::abi::{Abi, ParamType, Token};
+use ethers::types::{Address, U256};
+use std::fmt;
+
+/// Interface id of ERC-165 itself
+const ERC165_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+
+/// `DEFAULT_ADMIN_ROLE` of OpenZeppelin's AccessControl
+const DEFAULT_ADMIN_ROLE: [u8; 32] = [0; 32];
+
+/// Token ids probed by the ERC-1155 and ERC-2981 checks
+const PROBE_TOKEN_IDS: [u64; 2] = [0, 1];
+
+/// Sale prices `royaltyInfo` is asked about: a basis-point sized one and 1 ETH
+const PROBE_SALE_PRICES: [u64; 2] = [10_000, 1_000_000_000_000_000_000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standard {
+    Erc20,
+    Erc721,
+    Erc1155,
+    Erc2981,
+    AccessControl,
+}
+
+impl Standard {
+    pub const ALL: [Standard; 5] = [Standard::Erc20, Standard::Erc721, Standard::Erc1155, Standard::Erc2981, Standard::AccessControl];
+
+    /// ERC-165 interface id, `None` for ERC-20, which is recognized from the ABI instead
+    pub fn interface_id(&self) -> Option<[u8; 4]> {
+        match self {
+            Standard::Erc20 => None,
+            Standard::Erc721 => Some([0x80, 0xac, 0x58, 0xcd]),
+            Standard::Erc1155 => Some([0xd9, 0xb6, 0x7a, 0x26]),
+            Standard::Erc2981 => Some([0x2a, 0x55, 0x20, 0x5a]),
+            Standard::AccessControl => Some([0x79, 0x65, 0xdb, 0x0b]),
+        }
+    }
+
+    /// Properties checked for contracts that support the standard, named like invariants
+    pub fn checks(&self) -> &'static [&'static str] {
+        match self {
+            Standard::Erc20 => &["erc20-supply", "erc20-transfer-mint"],
+            Standard::Erc721 => &["erc721-zero-owner", "erc721-supply"],
+            Standard::Erc1155 => &["erc1155-batch-balance"],
+            Standard::Erc2981 => &["erc2981-royalty"],
+            Standard::AccessControl => &["access-control-admin"],
+        }
+    }
+}
+
+impl fmt::Display for Standard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Standard::Erc20 => "ERC-20",
+            Standard::Erc721 => "ERC-721",
+            Standard::Erc1155 => "ERC-1155",
+            Standard::Erc2981 => "ERC-2981",
+            Standard::AccessControl => "AccessControl",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+fn calldata(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut data = calculate_selector(signature).to_vec();
+    data.extend(ethers::abi::encode(args));
+    data
+}
+
+/// Decoded output of a view call, `None` when it reverts or returns something else
+async fn view(executor: &mut dyn Executor, contract_name: &str, signature: &str, args: &[Token], outputs: &[ParamType]) -> Option<Vec<Token>> {
+    let output = executor.static_call(contract_name, &calldata(signature, args)).await.ok()?;
+    ethers::abi::decode(outputs, &output).ok()
+}
+
+async fn view_uint(executor: &mut dyn Executor, contract_name: &str, signature: &str, args: &[Token]) -> Option<U256> {
+    match view(executor, contract_name, signature, args, &[ParamType::Uint(256)]).await?.as_slice() {
+        [Token::Uint(value)] => Some(*value),
+        _ => None,
+    }
+}
+
+async fn supports_interface(executor: &mut dyn Executor, contract_name: &str, interface_id: [u8; 4]) -> bool {
+    let args = [Token::FixedBytes(interface_id.to_vec())];
+    matches!(
+        view(executor, contract_name, "supportsInterface(bytes4)", &args, &[ParamType::Bool]).await.as_deref(),
+        Some([Token::Bool(true)])
+    )
+}
+
+/// Standards a deployed contract supports, by the ERC-165 procedure: the contract must claim
+/// `0x01ffc9a7` and deny `0xffffffff` before any other answer is trusted
+pub async fn detect(executor: &mut dyn Executor, contract_name: &str) -> Vec<Standard> {
+    if !supports_interface(executor, contract_name, ERC165_ID).await
+        || supports_interface(executor, contract_name, [0xff; 4]).await
+    {
+        return Vec::new();
+    }
+    let mut standards = Vec::new();
+    for standard in Standard::ALL {
+        let Some(interface_id) = standard.interface_id() else {
+            continue;
+        };
+        if supports_interface(executor, contract_name, interface_id).await {
+            standards.push(standard);
+        }
+    }
+    standards
+}
+
+/// ERC-20 functions the token checks call or that set a token apart from an ERC-721
+const ERC20_FUNCTIONS: [&str; 3] = ["totalSupply()", "balanceOf(address)", "transfer(address,uint256)"];
+
+/// Whether the ABI exposes the ERC-20 functions the token checks rely on
+pub fn is_erc20(abi: &Abi) -> bool {
+    ERC20_FUNCTIONS.iter().all(|signature| {
+        let selector = calculate_selector(signature);
+        abi.functions().any(|function| function.short_signature() == selector)
+    })
+}
+
+/// The built-in properties of the standards one contract supports, checked against the fuzzer's
+/// accounts. Each check is reported at most once, like an invariant.
+#[derive(Debug)]
+pub struct StandardChecks {
+    pub standards: Vec<Standard>,
+    /// Checks that have not been violated yet
+    active: Vec<&'static str>,
+    /// Accounts holding `DEFAULT_ADMIN_ROLE` after the last check
+    admins: Vec<bool>,
+    /// ERC-20 `totalSupply()` and the tokens held by the fuzzer's accounts and the contract after the last check
+    holdings: Option<(U256, U256)>,
+}
+
+impl StandardChecks {
+    pub async fn new(executor: &mut dyn Executor, contract_name: &str, standards: Vec<Standard>) -> Self {
+        let mut checks = Self {
+            active: standards.iter().flat_map(|standard| standard.checks().iter().copied()).collect(),
+            standards,
+            admins: Vec::new(),
+            holdings: None,
+        };
+        checks.resync(executor, contract_name).await;
+        checks
+    }
+
+    /// Names of the checks still active
+    pub fn active(&self) -> &[&'static str] {
+        &self.active
+    }
+
+    /// Adopt the current role assignments without judging them (after deployment or a restored snapshot)
+    pub async fn resync(&mut self, executor: &mut dyn Executor, contract_name: &str) {
+        if self.standards.contains(&Standard::AccessControl) {
+            self.admins = read_admins(executor, contract_name).await;
+        }
+        if self.standards.contains(&Standard::Erc20) {
+            self.holdings = read_holdings(executor, contract_name).await;
+        }
+    }
+
+    /// Run `check` against the current state and retire it when it fails, returning why
+    pub async fn check(&mut self, executor: &mut dyn Executor, contract_name: &str, check: &str) -> Option<String> {
+        let reason = match check {
+            "erc721-zero-owner" => check_zero_owner(executor, contract_name).await,
+            "erc721-supply" => check_supply(executor, contract_name).await,
+            "erc1155-batch-balance" => check_batch_balance(executor, contract_name).await,
+            "erc2981-royalty" => check_royalty(executor, contract_name).await,
+            "access-control-admin" => self.check_admins(executor, contract_name).await,
+            "erc20-supply" => check_token_supply(executor, contract_name).await,
+            "erc20-transfer-mint" => self.check_transfer_mint(executor, contract_name).await,
+            _ => None,
+        };
+        if reason.is_some() {
+            self.active.retain(|active| *active != check);
+        }
+        reason
+    }
+
+    /// An account may only become admin through a call from an admin: anything else is a
+    /// privilege escalation
+    async fn check_admins(&mut self, executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+        let admins = read_admins(executor, contract_name).await;
+        let sender = executor.current_sender().to_string();
+        let sender_was_admin = executor.accounts().iter().zip(&self.admins)
+            .any(|(account, admin)| *admin && account.eq_ignore_ascii_case(&sender));
+        let escalated: Vec<String> = executor.accounts().iter()
+            .zip(admins.iter().zip(self.admins.iter().chain(std::iter::repeat(&false))))
+            .filter(|(_, (now, before))| **now && !**before)
+            .map(|(account, _)| account.clone())
+            .collect();
+        self.admins = admins;
+        if escalated.is_empty() || sender_was_admin {
+            return None;
+        }
+        Some(format!("{} gained DEFAULT_ADMIN_ROLE through a call from non-admin {}", escalated.join(", "), sender))
+    }
+
+    /// Transfers move tokens, they do not create them: the tokens held by the fuzzer's accounts
+    /// and the contract may only grow by as much as `totalSupply()` does
+    async fn check_transfer_mint(&mut self, executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+        let (supply, held) = read_holdings(executor, contract_name).await?;
+        let previous = self.holdings.replace((supply, held));
+        let (previous_supply, previous_held) = previous?;
+        let gained = held.saturating_sub(previous_held);
+        let minted = supply.saturating_sub(previous_supply);
+        (gained > minted).then(|| format!("the fuzzer's accounts and the contract gained {} tokens while totalSupply() grew by {}: tokens were created without being minted",
+            gained, minted))
+    }
+}
+
+fn accounts_as_tokens(executor: &dyn Executor) -> Vec<Token> {
+    executor.accounts().iter()
+        .filter_map(|account| account.parse::<Address>().ok())
+        .map(Token::Address)
+        .collect()
+}
+
+async fn read_admins(executor: &mut dyn Executor, contract_name: &str) -> Vec<bool> {
+    let mut admins = Vec::new();
+    for account in accounts_as_tokens(executor) {
+        let args = [Token::FixedBytes(DEFAULT_ADMIN_ROLE.to_vec()), account];
+        let admin = view(executor, contract_name, "hasRole(bytes32,address)", &args, &[ParamType::Bool]).await;
+        admins.push(matches!(admin.as_deref(), Some([Token::Bool(true)])));
+    }
+    admins
+}
+
+/// `totalSupply()` and the ERC-20 balances of the fuzzer's accounts and the contract itself, which
+/// may hold tokens it pays out
+async fn read_holdings(executor: &mut dyn Executor, contract_name: &str) -> Option<(U256, U256)> {
+    let supply = view_uint(executor, contract_name, "totalSupply()", &[]).await?;
+    let mut holders = accounts_as_tokens(executor);
+    holders.extend(executor.contract_address(contract_name)
+        .and_then(|address| address.parse::<Address>().ok())
+        .map(Token::Address));
+    let mut held = U256::zero();
+    for holder in holders {
+        held = held.saturating_add(view_uint(executor, contract_name, "balanceOf(address)", &[holder]).await?);
+    }
+    Some((supply, held))
+}
+
+/// The sum of the balances the fuzzer can see cannot exceed the supply
+async fn check_token_supply(executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+    let (supply, held) = read_holdings(executor, contract_name).await?;
+    (held > supply).then(|| format!("the fuzzer's accounts and the contract hold {} tokens but totalSupply() is {}", held, supply))
+}
+
+/// EIP-721 treats tokens of the zero address as invalid; `balanceOf(address(0))` should revert or be 0
+async fn check_zero_owner(executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+    let balance = view_uint(executor, contract_name, "balanceOf(address)", &[Token::Address(Address::zero())]).await?;
+    (!balance.is_zero()).then(|| format!("balanceOf(address(0)) is {}: tokens were minted or transferred to the zero address", balance))
+}
+
+/// The fuzzer's accounts cannot own more tokens than exist (only for tokens with `totalSupply()`)
+async fn check_supply(executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+    let supply = view_uint(executor, contract_name, "totalSupply()", &[]).await?;
+    let mut held = U256::zero();
+    for account in accounts_as_tokens(executor) {
+        held = held.saturating_add(view_uint(executor, contract_name, "balanceOf(address)", &[account]).await?);
+    }
+    (held > supply).then(|| format!("the fuzzer's accounts hold {} tokens but totalSupply() is {}", held, supply))
+}
+
+/// `balanceOfBatch` must agree with `balanceOf` for every account and token id
+async fn check_batch_balance(executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+    let accounts = accounts_as_tokens(executor);
+    let (owners, ids): (Vec<Token>, Vec<Token>) = PROBE_TOKEN_IDS.iter()
+        .flat_map(|id| accounts.iter().map(move |account| (account.clone(), Token::Uint(U256::from(*id)))))
+        .unzip();
+    let batch = view(executor, contract_name, "balanceOfBatch(address[],uint256[])",
+        &[Token::Array(owners.clone()), Token::Array(ids.clone())],
+        &[ParamType::Array(Box::new(ParamType::Uint(256)))]).await?;
+    let [Token::Array(balances)] = batch.as_slice() else {
+        return None;
+    };
+    if balances.len() != owners.len() {
+        return Some(format!("balanceOfBatch returned {} balances for {} queries", balances.len(), owners.len()));
+    }
+    for ((owner, id), batched) in owners.into_iter().zip(ids).zip(balances) {
+        let single = view_uint(executor, contract_name, "balanceOf(address,uint256)", &[owner.clone(), id.clone()]).await?;
+        if *batched != Token::Uint(single) {
+            return Some(format!("balanceOfBatch reports {} of token {} for {} but balanceOf reports {}",
+                crate::abi_encoding::format_token(batched), crate::abi_encoding::format_token(&id),
+                crate::abi_encoding::format_token(&owner), single));
+        }
+    }
+    None
+}
+
+/// A royalty can never exceed the sale price it is taken from
+async fn check_royalty(executor: &mut dyn Executor, contract_name: &str) -> Option<String> {
+    for id in PROBE_TOKEN_IDS {
+        for price in PROBE_SALE_PRICES {
+            let args = [Token::Uint(U256::from(id)), Token::Uint(U256::from(price))];
+            let Some(info) = view(executor, contract_name, "royaltyInfo(uint256,uint256)", &args, &[ParamType::Address, ParamType::Uint(256)]).await else {
+                continue;
+            };
+            if let [_, Token::Uint(royalty)] = info.as_slice() {
+                if *royalty > U256::from(price) {
+                    return Some(format!("royaltyInfo({}, {}) asks for a royalty of {}, more than the sale price", id, price, royalty));
+                }
+            }
+        }
+    }
+    None
+}
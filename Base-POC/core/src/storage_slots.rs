@@ -0,0 +1,129 @@
+//! Solidity's storage layout rules, in one place for every oracle that reads state it has no
+//! getter for: `balances[holder]`, `allowance[owner][spender]`, `holders[i]`, proxy slots.
+
+use ethers::abi::Token;
+use ethers::types::U256;
+use sha3::{Digest, Keccak256};
+
+fn keccak(data: &[u8]) -> U256 {
+    U256::from_big_endian(&Keccak256::digest(data))
+}
+
+fn word(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+/// How a mapping key is hashed: value types are padded to a word like in the ABI, `string` and
+/// `bytes` keys are hashed as their raw bytes
+fn key_bytes(key: &Token) -> Vec<u8> {
+    match key {
+        Token::String(s) => s.as_bytes().to_vec(),
+        Token::Bytes(bytes) => bytes.clone(),
+        other => ethers::abi::encode(std::slice::from_ref(other)),
+    }
+}
+
+/// Slot of `mapping[key]` for a mapping declared at `slot`: `keccak256(key . slot)`
+pub fn mapping_slot(slot: U256, key: &Token) -> U256 {
+    let mut data = key_bytes(key);
+    data.extend_from_slice(&word(slot));
+    keccak(&data)
+}
+
+/// Slot of `mapping[keys[0]][keys[1]]…` for nested mappings declared at `slot`
+pub fn nested_mapping_slot(slot: U256, keys: &[Token]) -> U256 {
+    keys.iter().fold(slot, mapping_slot)
+}
+
+/// First slot of the elements of a dynamic array (or of the data of a long `bytes`/`string`)
+/// declared at `slot`, which itself holds the length: `keccak256(slot)`
+pub fn dynamic_array_base(slot: U256) -> U256 {
+    keccak(&word(slot))
+}
+
+/// Slot and byte offset of element `index` of an array whose elements start at `base`. Elements
+/// of `element_size` bytes (under 32) are packed several to a slot, larger ones take
+/// `ceil(element_size / 32)` whole slots each.
+pub fn array_element_slot(base: U256, index: U256, element_size: usize) -> (U256, usize) {
+    if element_size == 0 {
+        return (base, 0);
+    }
+    if element_size < 32 {
+        let per_slot = U256::from(32 / element_size);
+        let offset = (index % per_slot).as_usize() * element_size;
+        return (base.overflowing_add(index / per_slot).0, offset);
+    }
+    let slots_per_element = U256::from(element_size.div_ceil(32));
+    (base.overflowing_add(index.overflowing_mul(slots_per_element).0).0, 0)
+}
+
+/// Slot of element `index` of the dynamic array declared at `slot`, with its byte offset
+pub fn dynamic_array_element_slot(slot: U256, index: U256, element_size: usize) -> (U256, usize) {
+    array_element_slot(dynamic_array_base(slot), index, element_size)
+}
+
+/// EIP-1967 style slot of a label: `keccak256(label) - 1`, e.g. the implementation slot of
+/// `eip1967.proxy.implementation`
+pub fn eip1967_slot(label: &str) -> U256 {
+    keccak(label.as_bytes()) - U256::one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address;
+
+    fn slot(hex_str: &str) -> U256 {
+        U256::from_str_radix(hex_str, 16).unwrap()
+    }
+
+    #[test]
+    fn eip1967_slots_match_the_standard() {
+        assert_eq!(eip1967_slot("eip1967.proxy.implementation"), slot("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"));
+        assert_eq!(eip1967_slot("eip1967.proxy.admin"), slot("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"));
+        assert_eq!(eip1967_slot("eip1967.proxy.beacon"), slot("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50"));
+    }
+
+    #[test]
+    fn mapping_slot_hashes_the_padded_key_and_slot() {
+        // `balanceOf[address(0)]` of a `mapping(address => uint256)` at slot 0: keccak256 of two zero words
+        let balance_slot = mapping_slot(U256::zero(), &Token::Address(Address::zero()));
+        assert_eq!(balance_slot, slot("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"));
+        assert_eq!(mapping_slot(U256::zero(), &Token::Uint(U256::zero())), balance_slot);
+
+        // String keys are hashed unpadded, so `names[""]` at slot 0 hashes the slot word alone
+        assert_eq!(mapping_slot(U256::zero(), &Token::String(String::new())), dynamic_array_base(U256::zero()));
+    }
+
+    #[test]
+    fn nested_mapping_slot_applies_keys_outermost_first() {
+        let owner = Token::Address(Address::from_low_u64_be(1));
+        let spender = Token::Address(Address::from_low_u64_be(2));
+        let expected = mapping_slot(mapping_slot(U256::from(1), &owner), &spender);
+        assert_eq!(nested_mapping_slot(U256::from(1), &[owner, spender]), expected);
+        assert_eq!(nested_mapping_slot(U256::from(1), &[]), U256::from(1));
+    }
+
+    #[test]
+    fn dynamic_arrays_start_at_the_hash_of_their_slot() {
+        assert_eq!(dynamic_array_base(U256::zero()), slot("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"));
+        assert_eq!(dynamic_array_base(U256::from(1)), slot("b10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6"));
+        assert_eq!(dynamic_array_base(U256::from(2)), slot("405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace"));
+    }
+
+    #[test]
+    fn array_elements_are_packed_below_a_word() {
+        let base = U256::from(100);
+        // uint64[]: four elements per slot
+        assert_eq!(array_element_slot(base, U256::from(5), 8), (U256::from(101), 8));
+        // uint256[]: one slot each
+        assert_eq!(array_element_slot(base, U256::from(5), 32), (U256::from(105), 0));
+        // A 3-word struct per element
+        assert_eq!(array_element_slot(base, U256::from(2), 96), (U256::from(106), 0));
+        // uint8[]: 32 per slot
+        assert_eq!(array_element_slot(base, U256::from(33), 1), (U256::from(101), 1));
+        assert_eq!(dynamic_array_element_slot(U256::zero(), U256::from(3), 32).0, dynamic_array_base(U256::zero()) + 3);
+    }
+}
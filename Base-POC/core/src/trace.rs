@@ -0,0 +1,138 @@
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::types::{ContractMethod, TestResult};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Which part of a campaign sent a transaction
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TracePhase {
+    /// Regular fuzzing on the exploration fork
+    Explore,
+    /// Re-execution of a finding on the confirmation fork of a dual-phase campaign
+    Confirm,
+    /// A step of a replayed attack recipe
+    Replay,
+}
+
+/// One line of the `--trace-all` file
+#[derive(Debug, Serialize)]
+pub struct TraceRecord {
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u128,
+    pub phase: TracePhase,
+    pub contract: String,
+    pub sender: String,
+    /// `0x`-prefixed 4-byte selector
+    pub selector: String,
+    pub method: String,
+    /// Arguments decoded from the sent calldata
+    pub args: Vec<String>,
+    /// `passed`, `failed` or `error`
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub gas_used: u64,
+}
+
+impl TraceRecord {
+    /// `call` is the method signature and the ABI-encoded arguments that were sent
+    pub fn new(phase: TracePhase, contract: &str, sender: &str, method: &ContractMethod, call: &(String, Vec<u8>), result: &TestResult, gas_used: u64) -> Self {
+        let (signature, calldata) = call;
+        let (outcome, error) = match result {
+            TestResult::Passed => ("passed", None),
+            TestResult::Failed(error) => ("failed", Some(error.clone())),
+            TestResult::Error(error) => ("error", Some(error.clone())),
+        };
+        Self {
+            timestamp_ms: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis(),
+            phase,
+            contract: contract.to_string(),
+            sender: sender.to_string(),
+            selector: format!("0x{}", hex::encode(calculate_selector(signature))),
+            method: signature.clone(),
+            args: decode_args(method, calldata),
+            outcome,
+            error,
+            gas_used,
+        }
+    }
+}
+
+/// Appends a JSON line per sent transaction, keeping at most `max_per_second` lines per second
+/// so long campaigns do not drown the disk. Lines over the limit are counted, not buffered.
+pub struct TransactionTracer {
+    writer: BufWriter<File>,
+    /// 0 disables the limit
+    max_per_second: u32,
+    window_start: Instant,
+    in_window: u32,
+    written: u64,
+    dropped: u64,
+}
+
+impl TransactionTracer {
+    /// Open `path` for appending, so several fuzzer instances of one campaign share a file
+    pub fn open(path: &Path, max_per_second: u32) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)
+            .with_context(|| format!("Failed to open trace file {}", path.display()))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            max_per_second,
+            window_start: Instant::now(),
+            in_window: 0,
+            written: 0,
+            dropped: 0,
+        })
+    }
+
+    pub fn record(&mut self, record: &TraceRecord) -> Result<()> {
+        if self.window_start.elapsed() >= Duration::from_secs(1) {
+            self.window_start = Instant::now();
+            self.in_window = 0;
+        }
+        if self.max_per_second > 0 && self.in_window >= self.max_per_second {
+            self.dropped += 1;
+            return Ok(());
+        }
+        self.in_window += 1;
+
+        // One write per line: buffer flushes then never split a line, and parallel jobs appending
+        // to the same file cannot interleave inside one
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().context("Failed to flush trace file")
+    }
+
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+fn decode_args(method: &ContractMethod, calldata: &[u8]) -> Vec<String> {
+    let types: Option<Vec<_>> = method.parameters.iter()
+        .map(|param| abi_encoding::param_type(&param.param_type))
+        .collect();
+    let Some(types) = types else {
+        return vec![format!("0x{}", hex::encode(calldata))];
+    };
+    match ethers::abi::decode(&types, calldata) {
+        Ok(tokens) => tokens.iter().map(abi_encoding::format_token).collect(),
+        Err(_) => vec![format!("0x{}", hex::encode(calldata))],
+    }
+}
@@ -0,0 +1,199 @@
+use crate::report::{CampaignReport, FileReport, FindingReport};
+use anyhow::{Context, Result};
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Trace lines shown per finding
+const MAX_TRACE_LINES: usize = 5;
+
+/// Verdict of a human reviewer on a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageStatus {
+    Confirmed,
+    FalsePositive,
+    Duplicate,
+}
+
+impl TriageStatus {
+    /// False positives and duplicates need no further attention
+    pub fn is_dismissed(self) -> bool {
+        matches!(self, Self::FalsePositive | Self::Duplicate)
+    }
+}
+
+impl std::fmt::Display for TriageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Self::Confirmed => "confirmed",
+            Self::FalsePositive => "false positive",
+            Self::Duplicate => "duplicate",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriageEntry {
+    pub status: TriageStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Triage verdicts keyed by `finding_key`, kept in a JSON file across campaigns
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TriageStore {
+    pub findings: BTreeMap<String, TriageEntry>,
+}
+
+/// Key a finding is triaged under: `Contract.method: error`, the key report diffs match findings by
+pub fn finding_key(finding: &FindingReport) -> String {
+    format!("{}.{}: {}", finding.contract, finding.method, finding.error)
+}
+
+impl TriageStore {
+    /// Load a triage file; a missing file is an empty store
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read triage file {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid triage file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write triage file {}", path.display()))
+    }
+
+    pub fn status(&self, key: &str) -> Option<TriageStatus> {
+        self.findings.get(key).map(|entry| entry.status)
+    }
+}
+
+/// Findings given a verdict in one triage session
+#[derive(Debug, Default)]
+pub struct TriageSession {
+    pub reviewed: usize,
+    pub skipped: usize,
+}
+
+/// Walk through the findings of a report, show what is known about each and record the
+/// reviewer's verdict in `store`, saving it to `store_path` after every answer. Findings that
+/// already have a verdict are left out unless `revisit` is set. `trace` is a `--trace-all`
+/// file of the campaign, used to show the failing transactions.
+pub fn run(report: &CampaignReport, store: &mut TriageStore, store_path: &Path, trace: Option<&Path>, revisit: bool) -> Result<TriageSession> {
+    let trace_lines = match trace {
+        Some(path) => load_trace(path)?,
+        None => Vec::new(),
+    };
+    let pending: Vec<(&FileReport, &FindingReport)> = report.files.iter()
+        .flat_map(|file| file.findings.iter().map(move |finding| (file, finding)))
+        .filter(|(_, finding)| revisit || store.status(&finding_key(finding)).is_none())
+        .collect();
+
+    let mut session = TriageSession::default();
+    if pending.is_empty() {
+        println!("✅ No findings to triage");
+        return Ok(session);
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Triage needs an interactive terminal ({} finding(s) waiting for a verdict)", pending.len());
+    }
+
+    let choices = ["confirmed", "false positive", "duplicate", "skip", "quit"];
+    for (index, (file, finding)) in pending.iter().enumerate() {
+        let key = finding_key(finding);
+        println!();
+        println!("🔍 Finding {}/{}: {}.{} in {}", index + 1, pending.len(), finding.contract, finding.method, file.file);
+        show_finding(file, finding, &trace_lines);
+        if let Some(entry) = store.findings.get(&key) {
+            println!("   🏷️  Currently: {}{}", entry.status, entry.note.as_ref().map(|note| format!(" ({})", note)).unwrap_or_default());
+        }
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Verdict")
+            .items(&choices)
+            .default(0)
+            .interact()?;
+        let status = match choice {
+            0 => TriageStatus::Confirmed,
+            1 => TriageStatus::FalsePositive,
+            2 => TriageStatus::Duplicate,
+            3 => {
+                session.skipped += 1;
+                continue;
+            }
+            _ => break,
+        };
+        let note: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Note (optional)")
+            .allow_empty(true)
+            .interact_text()?;
+        store.findings.insert(key, TriageEntry {
+            status,
+            note: (!note.trim().is_empty()).then(|| note.trim().to_string()),
+        });
+        store.save(store_path)?;
+        session.reviewed += 1;
+    }
+    Ok(session)
+}
+
+fn show_finding(file: &FileReport, finding: &FindingReport, trace_lines: &[Value]) {
+    println!("   ❌ {}", finding.error);
+    if finding.flaky {
+        println!("   🔁 Flaky: did not reproduce on every re-execution");
+    }
+    let args = finding.args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().join(", ");
+    println!("   📞 {}({}) from {}, iteration {}, seed {}",
+        if finding.signature.is_empty() { &finding.method } else { &finding.signature }, args, finding.sender, finding.iteration, finding.seed);
+    if !finding.provenance.is_empty() {
+        println!("     ↳ {}", finding.provenance.iter().map(|provenance| provenance.to_string()).collect::<Vec<_>>().join(", "));
+    }
+
+    let changes: Vec<_> = file.state_changes.iter().filter(|change| change.contract == finding.contract).collect();
+    if !changes.is_empty() {
+        println!("   🧬 State changed over the campaign:");
+        for change in changes {
+            println!("     ↳ {}", change);
+        }
+    }
+
+    let trace: Vec<_> = trace_lines.iter()
+        .filter(|line| line["contract"] == finding.contract.as_str()
+            && line["sender"] == finding.sender.as_str()
+            && line["outcome"] == "failed"
+            && line["error"] == finding.error.as_str()
+            && (finding.signature.is_empty() || line["method"] == finding.signature.as_str()))
+        .collect();
+    if !trace.is_empty() {
+        println!("   🧵 Failing transactions in the trace:");
+        for line in trace.iter().rev().take(MAX_TRACE_LINES).rev() {
+            println!("     ↳ [{}] {}({}) gas {}", line["phase"].as_str().unwrap_or("?"), line["method"].as_str().unwrap_or("?"),
+                line["args"].as_array().map(|args| args.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(", ")).unwrap_or_default(),
+                line["gas_used"]);
+        }
+    }
+
+    if let Some(repro) = &finding.repro {
+        println!("   🛠️  Reproduce with cast:");
+        for line in repro.cast.lines() {
+            println!("     {}", line);
+        }
+    }
+}
+
+/// Lines of a `--trace-all` file; lines that do not parse are skipped
+fn load_trace(path: &Path) -> Result<Vec<Value>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trace {}", path.display()))?;
+    Ok(text.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+}
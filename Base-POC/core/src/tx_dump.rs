@@ -0,0 +1,97 @@
+use crate::abi_encoding;
+use crate::anvil_executor::calculate_selector;
+use crate::types::{BlockContext, Finding, FuzzSummary};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// The exact transaction behind a finding, written with `--dump-txs` so it can be re-sent with
+/// `cast`/`forge` (or any JSON-RPC client) without Fuzzhead
+#[derive(Debug, Serialize)]
+pub struct TransactionDump {
+    /// `Contract.signature`, iteration and seed of the finding
+    pub description: String,
+    pub error: String,
+    /// Unsigned transaction, usable as-is as the `eth_sendTransaction` parameter on a fork that
+    /// impersonates `from`
+    pub transaction: UnsignedTransaction,
+    /// Block environment overrides to apply first (`anvil_setCoinbase`, `anvil_setNextBlockBaseFeePerGas`, `anvil_setChainId`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_context: Option<BlockContext>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub from: String,
+    pub to: String,
+    /// Selector followed by the ABI-encoded arguments
+    pub data: String,
+    pub value: String,
+    /// Only set when the finding depends on a reduced gas limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub tx_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+}
+
+impl TransactionDump {
+    pub fn from_finding(finding: &Finding) -> Result<Self> {
+        let to = finding.contract_address.clone()
+            .with_context(|| format!("No deployment address recorded for {}", finding.contract))?;
+        let mut data = calculate_selector(&finding.signature).to_vec();
+        data.extend(abi_encoding::encode_args(&finding.args)
+            .map_err(|e| anyhow::anyhow!("ABI encoding failed: {}", e))?);
+
+        Ok(Self {
+            description: format!("{}.{} iteration {} (seed {})", finding.contract, finding.signature, finding.iteration, finding.seed),
+            error: finding.error.clone(),
+            transaction: UnsignedTransaction {
+                from: finding.sender.clone(),
+                to,
+                data: format!("0x{}", hex::encode(data)),
+                value: format!("0x{:x}", finding.value),
+                gas: finding.gas_limit.map(|gas_limit| format!("0x{:x}", gas_limit)),
+                tx_type: finding.fees.map(|_| "0x2".to_string()),
+                max_fee_per_gas: finding.fees.map(|fees| format!("0x{:x}", fees.max_fee_per_gas)),
+                max_priority_fee_per_gas: finding.fees.map(|fees| format!("0x{:x}", fees.max_priority_fee_per_gas)),
+                chain_id: finding.block_context.as_ref()
+                    .and_then(|block_context| block_context.chain_id)
+                    .map(|chain_id| format!("0x{:x}", chain_id)),
+            },
+            block_context: finding.block_context.clone(),
+        })
+    }
+}
+
+/// Write one `<contract>-<method>-<seed>.tx.json` file per finding of a summary into `dir`
+pub fn write_transactions(dir: &Path, summary: &FuzzSummary) -> Result<Vec<PathBuf>> {
+    if summary.findings.is_empty() {
+        return Ok(Vec::new());
+    }
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create transaction dump directory {}", dir.display()))?;
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for finding in &summary.findings {
+        let dump = TransactionDump::from_finding(finding)?;
+        // One iteration can produce two findings (a block-context divergence and a plain failure)
+        let stem = format!("{}-{}-{:016x}", finding.contract, finding.method, finding.seed);
+        let mut path = dir.join(format!("{}.tx.json", stem));
+        let mut suffix = 2;
+        while paths.contains(&path) {
+            path = dir.join(format!("{}-{}.tx.json", stem, suffix));
+            suffix += 1;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&dump)?)
+            .with_context(|| format!("Failed to write transaction dump {}", path.display()))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
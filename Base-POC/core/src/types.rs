@@ -0,0 +1,592 @@
+// type definitions
+use serde::{Deserialize, Serialize};
+use crate::selectors::SelectorIssue;
+use crate::bricking::BrickingCall;
+use crate::call_tree::TraceFrame;
+use crate::corpus::CorpusSequence;
+use crate::gas_profile::GasAlert;
+use crate::fingerprint::StateChange;
+use crate::invariants::InvariantViolation;
+use crate::accounting::{StorageVariable, ValueFlows};
+use crate::recommendations::Recommendation;
+use crate::linking::LibraryLink;
+use crate::proxy::ProxyInfo;
+use ethers::types::U256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolidityType {
+    Uint8, Uint16, Uint32, Uint64, Uint128, Uint256,
+    Int8, Int16, Int32, Int64, Int128, Int256,
+    Address, Bool, Bytes1, Bytes2, Bytes4, Bytes8, Bytes16, Bytes32,
+    String, Bytes, Array(Box<SolidityType>), Mapping(Box<SolidityType>, Box<SolidityType>),
+    /// `T[N]`, encoded in place (or behind an offset when `T` is dynamic)
+    FixedArray(Box<SolidityType>, usize),
+    /// A struct with its fields in declaration order, encoded as a tuple
+    Struct(String, Vec<MethodParameter>),
+    Custom(String),
+}
+
+impl SolidityType {
+    /// Canonical ABI type name as used in function signatures (e.g. `uint256`, `address[]`, `uint8[3][]`)
+    pub fn abi_type_string(&self) -> String {
+        match self {
+            SolidityType::Uint8 => "uint8".to_string(),
+            SolidityType::Uint16 => "uint16".to_string(),
+            SolidityType::Uint32 => "uint32".to_string(),
+            SolidityType::Uint64 => "uint64".to_string(),
+            SolidityType::Uint128 => "uint128".to_string(),
+            SolidityType::Uint256 => "uint256".to_string(),
+            SolidityType::Int8 => "int8".to_string(),
+            SolidityType::Int16 => "int16".to_string(),
+            SolidityType::Int32 => "int32".to_string(),
+            SolidityType::Int64 => "int64".to_string(),
+            SolidityType::Int128 => "int128".to_string(),
+            SolidityType::Int256 => "int256".to_string(),
+            SolidityType::Address => "address".to_string(),
+            SolidityType::Bool => "bool".to_string(),
+            SolidityType::Bytes1 => "bytes1".to_string(),
+            SolidityType::Bytes2 => "bytes2".to_string(),
+            SolidityType::Bytes4 => "bytes4".to_string(),
+            SolidityType::Bytes8 => "bytes8".to_string(),
+            SolidityType::Bytes16 => "bytes16".to_string(),
+            SolidityType::Bytes32 => "bytes32".to_string(),
+            SolidityType::String => "string".to_string(),
+            SolidityType::Bytes => "bytes".to_string(),
+            SolidityType::Array(inner) => format!("{}[]", inner.abi_type_string()),
+            SolidityType::FixedArray(inner, length) => format!("{}[{}]", inner.abi_type_string(), length),
+            SolidityType::Mapping(key, value) => {
+                format!("mapping({} => {})", key.abi_type_string(), value.abi_type_string())
+            }
+            SolidityType::Struct(_, fields) => {
+                let fields: Vec<String> = fields.iter().map(|field| field.param_type.abi_type_string()).collect();
+                format!("({})", fields.join(","))
+            }
+            SolidityType::Custom(name) => name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolidityValue {
+    Uint8(u8), Uint16(u16), Uint32(u32), Uint64(u64), Uint128(u128), Uint256(String),
+    Int8(i8), Int16(i16), Int32(i32), Int64(i64), Int128(i128), Int256(String),
+    Address(String), Bool(bool),
+    Bytes1([u8; 1]), Bytes2([u8; 2]), Bytes4([u8; 4]), Bytes8([u8; 8]),
+    Bytes16([u8; 16]), Bytes32([u8; 32]),
+    String(String), Bytes(Vec<u8>), Array(Vec<SolidityValue>),
+    /// Elements of a `T[N]`, exactly `N` of them
+    FixedArray(Vec<SolidityValue>),
+    /// Field names and values in declaration order, encoded as a tuple
+    Struct(Vec<(String, SolidityValue)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodParameter {
+    pub name: String,
+    pub param_type: SolidityType,
+    /// `memory`, `calldata` or `storage`, when declared
+    pub location: Option<DataLocation>,
+}
+
+/// Data location of a reference-type parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataLocation {
+    Memory,
+    Calldata,
+    Storage,
+}
+
+/// Whether a function reads, writes or receives ETH, as declared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateMutability {
+    Pure,
+    View,
+    NonPayable,
+    Payable,
+}
+
+impl MethodParameter {
+    /// Name given to parameters declared without one (e.g. `function f(uint256)`)
+    pub fn placeholder_name(index: usize) -> String {
+        format!("arg{}", index)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MethodVisibility {
+    Public,
+    External,
+    Internal,
+    Private,
+}
+
+
+#[derive(Debug, Clone)]
+pub struct ContractMethod {
+    pub name: String,
+    pub parameters: Vec<MethodParameter>,
+    pub visibility: MethodVisibility,
+    pub is_constructor: bool,
+    pub is_fallback: bool,
+    pub is_receive: bool,
+    pub mutability: StateMutability,
+    /// Names of the modifiers applied to the function, e.g. `onlyOwner`
+    pub modifiers: Vec<String>,
+    /// Return parameters; unnamed ones are named like unnamed inputs
+    pub returns: Vec<MethodParameter>,
+}
+
+impl ContractMethod {
+    /// Human-readable signature including parameter names, e.g. `transfer(address to, uint256 arg1)`
+    pub fn display_signature(&self) -> String {
+        let params = self.parameters.iter()
+            .map(|p| format!("{} {}", p.param_type.abi_type_string(), p.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({})", self.name, params)
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct FuzzSummary {
+    pub total_passed: usize,
+    pub total_failed: usize,
+    pub total_skipped: usize,
+    pub total_errors: usize,
+    pub total_flaky: usize,
+    /// Candidate findings from a dual-phase exploration that did not reproduce on the confirmation fork
+    pub total_unconfirmed: usize,
+    /// Candidate findings whose replay on the confirmation fork errored: neither confirmed nor refuted
+    pub total_confirmation_errors: usize,
+    /// Overflow panics on Solidity >=0.8 targets: checked math doing its job, not findings
+    pub total_checked_math_panics: usize,
+    /// Reverts matching an expected-revert pattern (access control, pausing, reentrancy guards…):
+    /// correct behavior, counted apart from failures and never reported as findings
+    pub total_expected_reverts: usize,
+    /// Inputs whose pass/fail outcome changed with the block context (miner-manipulable branches)
+    pub total_block_context_dependent: usize,
+    /// Inputs that succeeded under a reduced gas limit but left different state (partial execution under OOG)
+    pub total_gas_dependent: usize,
+    /// Calls that succeeded with a precompile or system contract as an address argument
+    pub total_system_address_successes: usize,
+    /// Methods the reentrancy attacker re-entered (`--reentrancy`)
+    pub total_reentrancies: usize,
+    /// Methods whose arguments made arithmetic wrap around (Solidity < 0.8)
+    pub total_wraparounds: usize,
+    /// Reverts of privileged methods for senders other than the deployer and owner (`--access-control`)
+    pub total_access_denied: usize,
+    /// Privileged methods that changed protected state for such a sender (`--access-control`)
+    pub total_unauthorized_changes: usize,
+    /// Sequences after which an account other than the deployer or owner had taken ETH out of the contract
+    pub total_ether_drains: usize,
+    pub findings: Vec<Finding>,
+    /// Declarations that were not compiled or deployed (interfaces, abstract contracts, libraries)
+    pub skipped_contracts: Vec<SkippedContract>,
+    /// The file's `pragma solidity` constraint, if any
+    pub pragma: Option<String>,
+    pub compile_info: Vec<CompileInfo>,
+    /// Seed every per-method seed was derived from
+    pub master_seed: u64,
+    pub method_seeds: Vec<MethodSeed>,
+    /// Selector collisions, proxy admin clashes and functions shadowed by a proxy's dispatcher
+    pub selector_issues: Vec<SelectorIssue>,
+    /// Methods that left the contract unusable and were skipped for the rest of the campaign
+    pub bricking_calls: Vec<BrickingCall>,
+    /// Methods with a call over the gas threshold or out of gas, at their costliest such call
+    pub gas_alerts: Vec<GasAlert>,
+    /// `invariant_*`/`echidna_*` properties that returned false or reverted during the campaign
+    pub invariant_violations: Vec<InvariantViolation>,
+    /// ETH moved into and out of each contract, when value accounting is on
+    pub value_flows: Vec<ValueFlows>,
+    /// Parameterless view functions whose value differs between deployment and the end of fuzzing
+    pub state_changes: Vec<StateChange>,
+    pub method_stats: Vec<MethodStats>,
+    /// Next-step suggestions printed at the end of each contract's campaign
+    pub recommendations: Vec<Recommendation>,
+    /// Where each fuzzed contract was deployed, and with which constructor arguments
+    pub deployments: Vec<Deployment>,
+    /// Fuzzed contracts found to be proxies, with their implementations
+    pub proxies: Vec<ProxyInfo>,
+    /// Corpus inputs of every method as one-call sequences, collected for `--export-corpus`
+    pub corpus: Vec<CorpusSequence>,
+    /// Upstream block of the Anvil fork the campaign ran on, if it was a fork
+    pub fork_block_number: Option<u64>,
+    /// Wall-clock time of the whole file's campaign, compilation and deployment included
+    pub elapsed_secs: f64,
+}
+
+impl FuzzSummary {
+    /// Add a later time slice of the same file's campaign
+    pub fn absorb(&mut self, slice: FuzzSummary) {
+        self.total_passed += slice.total_passed;
+        self.total_failed += slice.total_failed;
+        self.total_skipped += slice.total_skipped;
+        self.total_errors += slice.total_errors;
+        self.total_flaky += slice.total_flaky;
+        self.total_unconfirmed += slice.total_unconfirmed;
+        self.total_confirmation_errors += slice.total_confirmation_errors;
+        self.total_checked_math_panics += slice.total_checked_math_panics;
+        self.total_expected_reverts += slice.total_expected_reverts;
+        self.total_block_context_dependent += slice.total_block_context_dependent;
+        self.total_gas_dependent += slice.total_gas_dependent;
+        self.total_system_address_successes += slice.total_system_address_successes;
+        self.total_reentrancies += slice.total_reentrancies;
+        self.total_wraparounds += slice.total_wraparounds;
+        self.total_access_denied += slice.total_access_denied;
+        self.total_unauthorized_changes += slice.total_unauthorized_changes;
+        self.total_ether_drains += slice.total_ether_drains;
+        self.findings.extend(slice.findings);
+        self.skipped_contracts.extend(slice.skipped_contracts);
+        self.compile_info.extend(slice.compile_info);
+        self.method_seeds.extend(slice.method_seeds);
+        self.selector_issues.extend(slice.selector_issues);
+        self.bricking_calls.extend(slice.bricking_calls);
+        self.gas_alerts.extend(slice.gas_alerts);
+        self.invariant_violations.extend(slice.invariant_violations);
+        self.value_flows.extend(slice.value_flows);
+        self.state_changes.extend(slice.state_changes);
+        self.recommendations.extend(slice.recommendations);
+        self.deployments.extend(slice.deployments);
+        self.proxies.extend(slice.proxies);
+        self.corpus.extend(slice.corpus);
+        for stats in slice.method_stats {
+            match self.method_stats.iter_mut().find(|existing| existing.contract == stats.contract && existing.method == stats.method) {
+                Some(existing) => {
+                    if stats.calls > 0 {
+                        existing.gas_min = if existing.calls == 0 { stats.gas_min } else { existing.gas_min.min(stats.gas_min) };
+                        existing.gas_max = existing.gas_max.max(stats.gas_max);
+                    }
+                    existing.calls += stats.calls;
+                    existing.passed += stats.passed;
+                    existing.failed += stats.failed;
+                    existing.expected_reverts += stats.expected_reverts;
+                    existing.errors += stats.errors;
+                    existing.skipped += stats.skipped;
+                    existing.gas_used += stats.gas_used;
+                    // Coverage restarts every slice, so edge counts do not add up
+                    existing.branch_edges = existing.branch_edges.max(stats.branch_edges);
+                }
+                None => self.method_stats.push(stats),
+            }
+        }
+        self.elapsed_secs += slice.elapsed_secs;
+    }
+}
+
+/// A target deployed for fuzzing
+#[derive(Debug, Clone)]
+pub struct Deployment {
+    pub contract: String,
+    pub address: String,
+    /// ABI-encoded constructor arguments, if the constructor takes any
+    pub constructor_args: Option<Vec<u8>>,
+    /// Already on the chain (`--target-address`) rather than deployed by the campaign
+    pub attached: bool,
+}
+
+/// Per-method execution counters of a campaign (confirmation re-runs and probes excluded)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MethodStats {
+    pub contract: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub method: String,
+    /// Fuzzed calls that were sent (skipped iterations excluded)
+    pub calls: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Reverts whose reason matched an expected-revert pattern, not counted as failures
+    #[serde(default)]
+    pub expected_reverts: usize,
+    pub errors: usize,
+    /// Iterations skipped because some argument could not be generated
+    #[serde(default)]
+    pub skipped: usize,
+    /// Total gas used by those calls
+    pub gas_used: u64,
+    /// Gas of the cheapest and the costliest of those calls (a batched call counts its share of the batch)
+    #[serde(default)]
+    pub gas_min: u64,
+    #[serde(default)]
+    pub gas_max: u64,
+    /// Distinct branch edges reached by this method's calls (coverage-guided campaigns only)
+    #[serde(default)]
+    pub branch_edges: usize,
+}
+
+impl MethodStats {
+    /// Average gas per call
+    pub fn gas_mean(&self) -> u64 {
+        if self.calls == 0 { 0 } else { self.gas_used / self.calls as u64 }
+    }
+}
+
+/// Seed of one fuzzed method; iteration `n` uses `generator::iteration_seed(seed, n)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MethodSeed {
+    pub contract: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub method: String,
+    pub seed: u64,
+}
+
+/// Which compiler produced a contract's artifact, as shown in the per-file compatibility report
+#[derive(Debug, Clone)]
+pub struct CompileInfo {
+    pub contract: String,
+    /// "forge (project)", "forge (temp project)" or "solc"
+    pub backend: String,
+    pub compiler_version: Option<String>,
+    pub evm_version: Option<String>,
+    /// State variables from the artifact's storage layout; empty when the compiler did not emit one
+    pub storage_layout: Vec<StorageVariable>,
+    /// External libraries the bytecode has to be linked against before it is deployed
+    pub libraries: Vec<LibraryLink>,
+    /// Size of the runtime code in bytes, when the artifact has it
+    pub runtime_size: Option<usize>,
+}
+
+/// A declaration that was deliberately not compiled/deployed, with the reason shown in reports
+#[derive(Debug, Clone)]
+pub struct SkippedContract {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Generation strategy that produced an argument, shown next to findings to explain how an input came about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArgProvenance {
+    /// Uniformly random over the type (or a random string, byte array or array)
+    Uniform,
+    /// Small values around zero
+    SmallRange,
+    /// Mid-sized amounts and IDs
+    MediumRange,
+    /// Type boundaries and classic edge cases (0, 1, max, zero address)
+    Boundary,
+    PowerOfTwo,
+    PowerOfTen,
+    /// One of the executor's own accounts
+    KnownAccount,
+    /// A low address such as a precompile
+    LowAddress,
+    /// A precompile or system contract of the target chain
+    SystemAddress,
+    /// The reentrancy attacker contract (`--reentrancy`)
+    AttackerContract,
+    /// No generator for this type; the iteration is skipped
+    Unsupported,
+    /// Returned by the `fuzzhead_generate_value` hook of a `--hooks` module
+    Hook,
+    /// Mutated from a corpus input that reached new branches (coverage-guided mode)
+    Mutation,
+    /// Reduced from the generated value by shrinking a failing input
+    Shrunk,
+    /// Taken from a counterexample forge recorded (`--forge-failures`)
+    ForgeCounterexample,
+    /// Taken from an Echidna or Medusa corpus (`--import-corpus`)
+    ImportedCorpus,
+    /// A constant of the contract's source or bytecode, or a neighbour of one
+    Dictionary,
+    /// A contract deployed in the same campaign
+    DeployedContract,
+    /// A contract found behind an address getter of a campaign contract
+    DiscoveredContract,
+    /// Drawn from the parameter's strategy (`--strategy`, `[strategies]` in `fuzzhead.toml`)
+    Configured,
+}
+
+impl std::fmt::Display for ArgProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Self::Uniform => "uniform random",
+            Self::SmallRange => "small range",
+            Self::MediumRange => "medium range",
+            Self::Boundary => "boundary",
+            Self::PowerOfTwo => "power of two",
+            Self::PowerOfTen => "power of ten",
+            Self::KnownAccount => "known account",
+            Self::LowAddress => "low address",
+            Self::SystemAddress => "precompile or system contract",
+            Self::AttackerContract => "attacker contract",
+            Self::Unsupported => "unsupported",
+            Self::Hook => "user hook",
+            Self::Mutation => "corpus mutation",
+            Self::Shrunk => "shrunk",
+            Self::ForgeCounterexample => "forge counterexample",
+            Self::ImportedCorpus => "imported corpus",
+            Self::Dictionary => "contract constant",
+            Self::DeployedContract => "deployed contract",
+            Self::DiscoveredContract => "discovered contract",
+            Self::Configured => "configured strategy",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A failing input together with the outcome of its confirmation re-runs
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub contract: String,
+    /// Where the target was deployed when the finding was made
+    pub contract_address: Option<String>,
+    pub method: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    pub args: Vec<SolidityValue>,
+    /// Strategy behind each argument
+    pub provenance: Vec<ArgProvenance>,
+    pub sender: String,
+    pub iteration: usize,
+    /// RNG seed of the failing iteration; regenerates its arguments, sender and fees exactly
+    pub seed: u64,
+    pub error: String,
+    /// Number of confirmation re-runs that failed again
+    pub reproductions: usize,
+    /// Number of confirmation re-runs attempted
+    pub confirmation_runs: usize,
+    /// Failing calls of the method this finding stands for: later ones with the same revert
+    /// reason (custom errors by name) are counted here instead of being reported again
+    pub occurrences: usize,
+    /// Outcome of the dual-phase confirmation replay; `None` outside dual-phase campaigns
+    pub confirmed_on_fork: Option<bool>,
+    /// Explicit EIP-1559 fees the failing transaction was sent with; `None` when the node chose them
+    pub fees: Option<TxFees>,
+    /// Block environment the failing transaction ran under, when block-context fuzzing is on
+    pub block_context: Option<BlockContext>,
+    /// Gas limit the transaction was sent with, when it was not the default
+    pub gas_limit: Option<u64>,
+    /// Wei sent with the failing call (`msg.value`)
+    pub value: U256,
+    /// Calls that ran before this one in its stateful sequence, in order; empty for single-call findings
+    pub sequence: Vec<SequenceCall>,
+    /// Arguments as generated, when shrinking reduced them to `args`
+    pub shrunk_from: Option<Vec<SolidityValue>>,
+    /// Sent from the contract's owner, impersonated because the fuzzer does not control it
+    pub impersonated_owner: bool,
+    /// Time, block and balance cheats applied right before the failing call
+    pub cheats: Vec<Cheat>,
+    /// Pruned call tree of the failing call, re-executed once and traced (empty when it could not be)
+    pub call_trace: Vec<TraceFrame>,
+}
+
+impl Finding {
+    /// A finding of `error` for a call of `signature` on `contract`, reported once, with every
+    /// other field empty: no confirmation runs, fees, block context, sequence, cheats or call tree
+    pub fn new(contract: &str, signature: &str, args: Vec<SolidityValue>, error: String) -> Self {
+        Self {
+            contract: contract.to_string(),
+            contract_address: None,
+            method: signature.split('(').next().unwrap_or(signature).to_string(),
+            signature: signature.to_string(),
+            args,
+            provenance: Vec::new(),
+            sender: String::new(),
+            iteration: 0,
+            seed: 0,
+            error,
+            reproductions: 0,
+            confirmation_runs: 0,
+            occurrences: 1,
+            confirmed_on_fork: None,
+            fees: None,
+            block_context: None,
+            gas_limit: None,
+            value: U256::zero(),
+            sequence: Vec::new(),
+            shrunk_from: None,
+            impersonated_owner: false,
+            cheats: Vec::new(),
+            call_trace: Vec::new(),
+        }
+    }
+
+    /// A finding is flaky when at least one re-execution on the same state did not fail
+    pub fn is_flaky(&self) -> bool {
+        self.reproductions < self.confirmation_runs
+    }
+}
+
+/// A call of a stateful sequence that succeeded and set up state for the calls after it
+#[derive(Debug, Clone)]
+pub struct SequenceCall {
+    /// Canonical signature, e.g. `deposit(uint256)`
+    pub signature: String,
+    pub sender: String,
+    pub args: Vec<SolidityValue>,
+    /// ABI-encoded arguments (without selector)
+    pub calldata: Vec<u8>,
+    /// Wei sent with the call
+    pub value: U256,
+    /// Contract the call went to in a cross-contract sequence; `None` is the finding's own contract
+    pub contract: Option<String>,
+    /// Where that contract was deployed
+    pub contract_address: Option<String>,
+    /// Time, block and balance cheats applied right before the call
+    pub cheats: Vec<Cheat>,
+}
+
+/// A chain manipulation between two calls of a stateful sequence, applied through node cheats
+/// (`vm.warp`, `vm.roll` and `vm.deal` in a Foundry test)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "cheat", rename_all = "snake_case")]
+pub enum Cheat {
+    /// Move the chain's clock forward and mine a block
+    Warp { seconds: u64 },
+    /// Mine empty blocks
+    Roll { blocks: u64 },
+    /// Set an account's ETH balance
+    Deal { account: String, wei: U256 },
+}
+
+impl std::fmt::Display for Cheat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cheat::Warp { seconds } => write!(f, "warp +{}s", seconds),
+            Cheat::Roll { blocks } => write!(f, "roll +{} block(s)", blocks),
+            Cheat::Deal { account, wei } => write!(f, "deal {} {} wei", account, wei),
+        }
+    }
+}
+
+/// Block environment overrides applied through node cheats before a transaction
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BlockContext {
+    pub coinbase: Option<String>,
+    pub base_fee_per_gas: Option<u128>,
+    pub chain_id: Option<u64>,
+}
+
+impl std::fmt::Display for BlockContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(coinbase) = &self.coinbase {
+            parts.push(format!("coinbase={}", coinbase));
+        }
+        if let Some(base_fee) = self.base_fee_per_gas {
+            parts.push(format!("basefee={}", base_fee));
+        }
+        if let Some(chain_id) = self.chain_id {
+            parts.push(format!("chainid={}", chain_id));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// EIP-1559 fee parameters for a type-2 transaction, in wei
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxFees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+#[derive(Debug, Clone)]
+pub enum TestResult {
+    Passed,
+    /// The contract itself rejected the call (revert, failed status)
+    Failed(String),
+    /// Tooling or infrastructure problem (RPC timeout, nonce error, missing receipt, encoding bug).
+    /// These say nothing about the contract and must not be counted as findings.
+    Error(String),
+}
\ No newline at end of file
@@ -0,0 +1,418 @@
+//! Value strategies for single parameters. The built-in distribution of `generator` is tuned for
+//! no protocol in particular; a strategy constrains or biases the values of one parameter, e.g.
+//! token amounts between 1 wei and 1e18, deadlines around now, or fee basis points up to 10000.
+//! Strategies are given per method and parameter, as `transfer.amount=range:1..1e18` with
+//! `--strategy` or under `[strategies]` in `fuzzhead.toml`:
+//!
+//! ```text
+//! range:LO..HI        uniform integer between LO and HI, both included
+//! log:LO..HI          integer between LO and HI, every order of magnitude equally likely
+//! values:A,B,C        one of the listed literals (any parameter type)
+//! NN%:<strategy>      the strategy for NN% of the values, the built-in distribution for the rest
+//! ```
+//!
+//! Integers are decimal, `0x` hex or scientific (`1e18`, `2.5e6`), with optional `_` separators.
+//! Library users can implement `ValueStrategy` and add their own to `FuzzConfig::value_strategies`.
+
+use crate::hooks;
+use crate::types::{MethodParameter, SolidityType, SolidityValue};
+use ethers::types::{I256, U256};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::fmt;
+use std::sync::Arc;
+
+/// Generates the values of a parameter
+pub trait ValueStrategy: fmt::Debug + fmt::Display + Send + Sync {
+    /// Whether the strategy has values of this type
+    fn supports(&self, sol_type: &SolidityType) -> bool;
+
+    /// A value of the type, drawn from `rng` alone so that runs replay from their seed; `None`
+    /// leaves the parameter to the built-in generator
+    fn generate(&self, rng: &mut dyn RngCore, sol_type: &SolidityType) -> Option<SolidityValue>;
+
+    /// Whether the strategy could have produced `value`; shrinking keeps inside what this allows
+    fn allows(&self, _sol_type: &SolidityType, _value: &SolidityValue) -> bool {
+        true
+    }
+}
+
+/// A strategy bound to the parameters it generates
+#[derive(Debug, Clone)]
+pub struct ParameterStrategy {
+    /// Methods it applies to: a name, a signature, or either prefixed with `Contract.` (globs allowed)
+    pub method: String,
+    /// Parameter name, or its position counted from 0
+    pub parameter: String,
+    /// Share of the parameter's values drawn from the strategy, in percent
+    pub percent: u32,
+    pub strategy: Arc<dyn ValueStrategy>,
+}
+
+impl ParameterStrategy {
+    /// `method` and `parameter` generated by the strategy written as `spec`
+    pub fn parse(method: &str, parameter: &str, spec: &str) -> Result<Self, String> {
+        if method.is_empty() || parameter.is_empty() {
+            return Err(format!("invalid strategy target '{}.{}' (expected METHOD.PARAMETER)", method, parameter));
+        }
+        let (percent, spec) = match spec.split_once("%:") {
+            Some((percent, rest)) => {
+                let percent: u32 = percent.trim().parse().map_err(|_| format!("invalid share '{}%' in strategy '{}'", percent, spec))?;
+                if percent == 0 || percent > 100 {
+                    return Err(format!("share of strategy '{}' must be between 1% and 100%", spec));
+                }
+                (percent, rest)
+            }
+            None => (100, spec),
+        };
+        Ok(Self {
+            method: method.to_string(),
+            parameter: parameter.to_string(),
+            percent,
+            strategy: parse_strategy(spec)?,
+        })
+    }
+
+    /// A value from the strategy for `percent` of the seeds, `None` for the rest and for types
+    /// the strategy does not support
+    pub fn draw(&self, seed: u64, sol_type: &SolidityType) -> Option<SolidityValue> {
+        if !self.strategy.supports(sol_type) {
+            return None;
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        if rng.gen_range(0..100) >= self.percent {
+            return None;
+        }
+        self.strategy.generate(&mut rng, sol_type)
+    }
+
+    /// Whether this is the strategy of the parameter at `index`
+    pub fn targets(&self, index: usize, parameter: &MethodParameter) -> bool {
+        self.parameter == parameter.name || self.parameter == index.to_string()
+    }
+}
+
+impl std::str::FromStr for ParameterStrategy {
+    type Err = String;
+
+    /// `METHOD.PARAMETER=SPEC`; the method may itself hold dots (`Vault.deposit.assets=...`)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (target, spec) = text.split_once('=')
+            .ok_or_else(|| format!("invalid strategy '{}' (expected METHOD.PARAMETER=SPEC)", text))?;
+        let (method, parameter) = target.trim().rsplit_once('.')
+            .ok_or_else(|| format!("invalid strategy '{}' (expected METHOD.PARAMETER=SPEC)", text))?;
+        Self::parse(method, parameter, spec.trim())
+    }
+}
+
+impl fmt::Display for ParameterStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.percent < 100 {
+            write!(f, "{}%:", self.percent)?;
+        }
+        write!(f, "{}", self.strategy)
+    }
+}
+
+/// A built-in strategy from its spec
+pub fn parse_strategy(spec: &str) -> Result<Arc<dyn ValueStrategy>, String> {
+    let (kind, argument) = spec.trim().split_once(':')
+        .ok_or_else(|| format!("invalid strategy '{}' (expected range:, log: or values:)", spec))?;
+    match kind.trim() {
+        "range" => Ok(Arc::new(IntegerRange::parse(argument, false)?)),
+        "log" => Ok(Arc::new(IntegerRange::parse(argument, true)?)),
+        "values" => Ok(Arc::new(Values::parse(argument)?)),
+        other => Err(format!("unknown strategy '{}' (expected range, log or values)", other)),
+    }
+}
+
+/// An integer literal, which may be negative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Integer {
+    Unsigned(U256),
+    Negative(I256),
+}
+
+impl Integer {
+    fn as_signed(self) -> Option<I256> {
+        match self {
+            Integer::Unsigned(value) => I256::try_from(value).ok(),
+            Integer::Negative(value) => Some(value),
+        }
+    }
+}
+
+/// Parse an integer literal: decimal, `0x` hex or scientific, with optional `_` separators
+fn parse_integer(text: &str) -> Result<Integer, String> {
+    let cleaned: String = text.trim().chars().filter(|c| *c != '_').collect();
+    let (negative, digits) = match cleaned.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, cleaned.as_str()),
+    };
+    let invalid = || format!("invalid integer '{}'", text.trim());
+    let magnitude = if let Some(hex) = digits.strip_prefix("0x") {
+        U256::from_str_radix(hex, 16).map_err(|_| invalid())?
+    } else if let Some((mantissa, exponent)) = digits.split_once(['e', 'E']) {
+        let exponent: usize = exponent.parse().map_err(|_| invalid())?;
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if fraction.len() > exponent || whole.is_empty() && fraction.is_empty() {
+            return Err(format!("'{}' is not an integer", text.trim()));
+        }
+        let significand = U256::from_dec_str(&format!("{}{}", whole, fraction)).map_err(|_| invalid())?;
+        let scale = U256::from(10).checked_pow(U256::from(exponent - fraction.len())).ok_or_else(invalid)?;
+        significand.checked_mul(scale).ok_or_else(|| format!("'{}' does not fit 256 bits", text.trim()))?
+    } else {
+        U256::from_dec_str(digits).map_err(|_| invalid())?
+    };
+    if !negative || magnitude.is_zero() {
+        return Ok(Integer::Unsigned(magnitude));
+    }
+    let value = I256::try_from(magnitude).map(|value| -value)
+        .or_else(|_| if magnitude == I256::MIN.unsigned_abs() { Ok(I256::MIN) } else { Err(()) })
+        .map_err(|_| format!("'{}' does not fit int256", text.trim()))?;
+    Ok(Integer::Negative(value))
+}
+
+/// Signedness and bit width of the integer types
+pub(crate) fn integer_type(sol_type: &SolidityType) -> Option<(bool, usize)> {
+    Some(match sol_type {
+        SolidityType::Uint8 => (false, 8),
+        SolidityType::Uint16 => (false, 16),
+        SolidityType::Uint32 => (false, 32),
+        SolidityType::Uint64 => (false, 64),
+        SolidityType::Uint128 => (false, 128),
+        SolidityType::Uint256 => (false, 256),
+        SolidityType::Int8 => (true, 8),
+        SolidityType::Int16 => (true, 16),
+        SolidityType::Int32 => (true, 32),
+        SolidityType::Int64 => (true, 64),
+        SolidityType::Int128 => (true, 128),
+        SolidityType::Int256 => (true, 256),
+        _ => return None,
+    })
+}
+
+fn unsigned_max(bits: usize) -> U256 {
+    if bits == 256 { U256::MAX } else { (U256::one() << bits) - 1 }
+}
+
+fn signed_bounds(bits: usize) -> (I256, I256) {
+    let max = I256::from_raw(unsigned_max(bits - 1));
+    (-max - I256::one(), max)
+}
+
+/// A uniformly random integer between `low` and `high`, both included
+fn uniform(rng: &mut dyn RngCore, low: U256, high: U256) -> U256 {
+    let mut words = [0u64; 4];
+    words.iter_mut().for_each(|word| *word = rng.next_u64());
+    let random = U256(words);
+    let span = high - low;
+    if span == U256::MAX {
+        return random;
+    }
+    low + random % (span + 1)
+}
+
+/// An integer between `low` and `high` whose bit length is uniformly random
+fn log_uniform(rng: &mut dyn RngCore, low: U256, high: U256) -> U256 {
+    let (low_bits, high_bits) = (low.bits(), high.bits());
+    let bits = low_bits + (rng.next_u64() as usize) % (high_bits - low_bits + 1);
+    let floor = if bits == 0 { U256::zero() } else { U256::one() << (bits - 1) };
+    uniform(rng, low.max(floor), high.min(unsigned_max(bits)))
+}
+
+/// Decimal text of an integer value
+fn integer_text(value: &SolidityValue) -> Option<String> {
+    Some(match value {
+        SolidityValue::Uint8(v) => v.to_string(),
+        SolidityValue::Uint16(v) => v.to_string(),
+        SolidityValue::Uint32(v) => v.to_string(),
+        SolidityValue::Uint64(v) => v.to_string(),
+        SolidityValue::Uint128(v) => v.to_string(),
+        SolidityValue::Uint256(v) | SolidityValue::Int256(v) => v.clone(),
+        SolidityValue::Int8(v) => v.to_string(),
+        SolidityValue::Int16(v) => v.to_string(),
+        SolidityValue::Int32(v) => v.to_string(),
+        SolidityValue::Int64(v) => v.to_string(),
+        SolidityValue::Int128(v) => v.to_string(),
+        _ => return None,
+    })
+}
+
+/// Integers between two bounds, uniformly or log-uniformly distributed
+#[derive(Debug, Clone)]
+struct IntegerRange {
+    low: Integer,
+    high: Integer,
+    logarithmic: bool,
+    spec: String,
+}
+
+impl IntegerRange {
+    fn parse(argument: &str, logarithmic: bool) -> Result<Self, String> {
+        let (low, high) = argument.split_once("..=").or_else(|| argument.split_once(".."))
+            .ok_or_else(|| format!("invalid range '{}' (expected LO..HI)", argument))?;
+        let (low, high) = (parse_integer(low)?, parse_integer(high)?);
+        let ordered = match (low, high) {
+            (Integer::Unsigned(low), Integer::Unsigned(high)) => low <= high,
+            (Integer::Negative(_), Integer::Unsigned(_)) => true,
+            (Integer::Negative(low), Integer::Negative(high)) => low <= high,
+            (Integer::Unsigned(_), Integer::Negative(_)) => false,
+        };
+        if !ordered {
+            return Err(format!("empty range '{}'", argument.trim()));
+        }
+        if logarithmic && matches!(low, Integer::Negative(_)) {
+            return Err(format!("log range '{}' must not go below 0", argument.trim()));
+        }
+        let kind = if logarithmic { "log" } else { "range" };
+        Ok(Self { low, high, logarithmic, spec: format!("{}:{}", kind, argument.trim()) })
+    }
+
+    /// The bounds intersected with the range of an unsigned type of `bits` bits
+    fn unsigned_bounds(&self, bits: usize) -> Option<(U256, U256)> {
+        let low = match self.low {
+            Integer::Unsigned(low) => low,
+            Integer::Negative(_) => U256::zero(),
+        };
+        let Integer::Unsigned(high) = self.high else {
+            return None;
+        };
+        let high = high.min(unsigned_max(bits));
+        (low <= high).then_some((low, high))
+    }
+
+    /// The bounds intersected with the range of a signed type of `bits` bits
+    fn signed_bounds(&self, bits: usize) -> Option<(I256, I256)> {
+        let (type_low, type_high) = signed_bounds(bits);
+        let low = self.low.as_signed()?.max(type_low);
+        let high = self.high.as_signed().unwrap_or(I256::MAX).min(type_high);
+        (low <= high).then_some((low, high))
+    }
+}
+
+impl fmt::Display for IntegerRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.spec)
+    }
+}
+
+impl ValueStrategy for IntegerRange {
+    fn supports(&self, sol_type: &SolidityType) -> bool {
+        match integer_type(sol_type) {
+            Some((false, bits)) => self.unsigned_bounds(bits).is_some(),
+            Some((true, bits)) => self.signed_bounds(bits).is_some(),
+            None => false,
+        }
+    }
+
+    fn generate(&self, rng: &mut dyn RngCore, sol_type: &SolidityType) -> Option<SolidityValue> {
+        let (signed, bits) = integer_type(sol_type)?;
+        if !signed {
+            let (low, high) = self.unsigned_bounds(bits)?;
+            let value = if self.logarithmic { log_uniform(rng, low, high) } else { uniform(rng, low, high) };
+            return Some(match sol_type {
+                SolidityType::Uint8 => SolidityValue::Uint8(value.low_u32() as u8),
+                SolidityType::Uint16 => SolidityValue::Uint16(value.low_u32() as u16),
+                SolidityType::Uint32 => SolidityValue::Uint32(value.low_u32()),
+                SolidityType::Uint64 => SolidityValue::Uint64(value.low_u64()),
+                SolidityType::Uint128 => SolidityValue::Uint128(value.low_u128()),
+                _ => SolidityValue::Uint256(value.to_string()),
+            });
+        }
+        let (low, high) = self.signed_bounds(bits)?;
+        let value = if self.logarithmic {
+            // Log ranges start at 0 or above
+            I256::from_raw(log_uniform(rng, low.into_raw(), high.into_raw()))
+        } else {
+            let offset = uniform(rng, U256::zero(), high.overflowing_sub(low).0.into_raw());
+            I256::from_raw(low.into_raw().overflowing_add(offset).0)
+        };
+        Some(match sol_type {
+            SolidityType::Int8 => SolidityValue::Int8(value.low_i32() as i8),
+            SolidityType::Int16 => SolidityValue::Int16(value.low_i32() as i16),
+            SolidityType::Int32 => SolidityValue::Int32(value.low_i32()),
+            SolidityType::Int64 => SolidityValue::Int64(value.low_i64()),
+            SolidityType::Int128 => SolidityValue::Int128(value.low_i128()),
+            _ => SolidityValue::Int256(value.to_string()),
+        })
+    }
+
+    fn allows(&self, sol_type: &SolidityType, value: &SolidityValue) -> bool {
+        let Some((signed, bits)) = integer_type(sol_type) else {
+            return true;
+        };
+        let Some(text) = integer_text(value) else {
+            return true;
+        };
+        if signed {
+            let (Some((low, high)), Ok(value)) = (self.signed_bounds(bits), I256::from_dec_str(&text)) else {
+                return true;
+            };
+            (low..=high).contains(&value)
+        } else {
+            let (Some((low, high)), Ok(value)) = (self.unsigned_bounds(bits), U256::from_dec_str(&text)) else {
+                return true;
+            };
+            (low..=high).contains(&value)
+        }
+    }
+}
+
+/// One of a list of literals
+#[derive(Debug, Clone)]
+struct Values {
+    literals: Vec<String>,
+    spec: String,
+}
+
+impl Values {
+    fn parse(argument: &str) -> Result<Self, String> {
+        let literals: Vec<String> = argument.split(',')
+            .map(str::trim)
+            .filter(|literal| !literal.is_empty())
+            .map(str::to_string)
+            .collect();
+        if literals.is_empty() {
+            return Err("values: needs at least one literal".to_string());
+        }
+        Ok(Self { literals, spec: format!("values:{}", argument.trim()) })
+    }
+
+    fn parsed(&self, sol_type: &SolidityType) -> Vec<SolidityValue> {
+        self.literals.iter()
+            .filter_map(|literal| match integer_type(sol_type) {
+                // Integers are read like range bounds, so `1e18` and `0xff` fit any integer type
+                Some(_) => match parse_integer(literal).ok()? {
+                    Integer::Unsigned(value) => hooks::parse_value(sol_type, &value.to_string()),
+                    Integer::Negative(value) => hooks::parse_value(sol_type, &value.to_string()),
+                },
+                None => hooks::parse_value(sol_type, literal),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Values {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.spec)
+    }
+}
+
+impl ValueStrategy for Values {
+    fn supports(&self, sol_type: &SolidityType) -> bool {
+        self.parsed(sol_type).len() == self.literals.len()
+    }
+
+    fn generate(&self, rng: &mut dyn RngCore, sol_type: &SolidityType) -> Option<SolidityValue> {
+        let values = self.parsed(sol_type);
+        if values.is_empty() {
+            return None;
+        }
+        Some(values[(rng.next_u64() % values.len() as u64) as usize].clone())
+    }
+
+    fn allows(&self, sol_type: &SolidityType, value: &SolidityValue) -> bool {
+        self.parsed(sol_type).contains(value)
+    }
+}
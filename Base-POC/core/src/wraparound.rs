@@ -0,0 +1,150 @@
+//! Wraparound detection for targets compiled without checked arithmetic (Solidity < 0.8). An
+//! overflowing `ADD`/`MUL` or underflowing `SUB` of the target's own code is taken from the
+//! execution trace, and reported when one of its operands is a `uint256` argument of the call.
+//! Requiring the argument filters out the compiler's intentional wrapping (`x + not(0)` for
+//! `x - 1`, signed math), and only calls that succeed are checked, so SafeMath's
+//! wrap-then-`require` is not flagged.
+
+use crate::types::{MethodParameter, SolidityValue};
+use ethers::types::U256;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl ArithmeticOp {
+    pub fn from_opcode(opcode: u8) -> Option<Self> {
+        match opcode {
+            0x01 => Some(ArithmeticOp::Add),
+            0x02 => Some(ArithmeticOp::Mul),
+            0x03 => Some(ArithmeticOp::Sub),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ADD" => Some(ArithmeticOp::Add),
+            "MUL" => Some(ArithmeticOp::Mul),
+            "SUB" => Some(ArithmeticOp::Sub),
+            _ => None,
+        }
+    }
+
+    /// Whether `left op right` (`left` being the top of the stack) wraps around 2^256
+    pub fn wraps(&self, left: U256, right: U256) -> bool {
+        match self {
+            ArithmeticOp::Add => left.overflowing_add(right).1,
+            ArithmeticOp::Sub => left < right,
+            ArithmeticOp::Mul => left.overflowing_mul(right).1,
+        }
+    }
+}
+
+impl fmt::Display for ArithmeticOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            ArithmeticOp::Add => "+",
+            ArithmeticOp::Sub => "-",
+            ArithmeticOp::Mul => "*",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// An arithmetic instruction of the target whose result wrapped around
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedOperation {
+    pub pc: u64,
+    pub op: ArithmeticOp,
+    /// Top of the stack: the minuend of a `SUB`
+    pub left: U256,
+    pub right: U256,
+}
+
+impl WrappedOperation {
+    pub fn kind(&self) -> &'static str {
+        match self.op {
+            ArithmeticOp::Sub => "underflow",
+            ArithmeticOp::Add | ArithmeticOp::Mul => "overflow",
+        }
+    }
+}
+
+impl fmt::Display for WrappedOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} wraps around at pc 0x{:x}", self.left, self.op, self.right, self.pc)
+    }
+}
+
+fn stack_word(word: &serde_json::Value) -> Option<U256> {
+    U256::from_str_radix(word.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+/// Wrapped arithmetic from the `structLogs` of a `debug_traceTransaction` response with stacks
+/// enabled (bottom of the stack first). Only steps at depth 1 are the target's own code.
+pub fn wrapped_operations(struct_logs: &[serde_json::Value]) -> Vec<WrappedOperation> {
+    struct_logs.iter()
+        .filter(|step| step.get("depth").and_then(|depth| depth.as_u64()) == Some(1))
+        .filter_map(|step| {
+            let op = ArithmeticOp::from_name(step.get("op")?.as_str()?)?;
+            let pc = step.get("pc")?.as_u64()?;
+            let stack = step.get("stack")?.as_array()?;
+            let [.., right, left] = stack.as_slice() else {
+                return None;
+            };
+            let (left, right) = (stack_word(left)?, stack_word(right)?);
+            op.wraps(left, right).then_some(WrappedOperation { pc, op, left, right })
+        })
+        .collect()
+}
+
+/// `uint256` values among the arguments, labeled like `amounts[2]` or `order.price`
+fn uint_arguments(parameters: &[MethodParameter], args: &[SolidityValue]) -> Vec<(String, U256)> {
+    let mut pending: Vec<(String, &SolidityValue)> = parameters.iter()
+        .zip(args)
+        .map(|(param, arg)| (param.name.clone(), arg))
+        .collect();
+    let mut values = Vec::new();
+    while let Some((label, value)) = pending.pop() {
+        match value {
+            SolidityValue::Uint256(text) => {
+                if let Ok(value) = U256::from_dec_str(text) {
+                    values.push((label, value));
+                }
+            }
+            SolidityValue::Array(items) | SolidityValue::FixedArray(items) => pending.extend(items.iter().enumerate()
+                .map(|(index, item)| (format!("{}[{}]", label, index), item))),
+            SolidityValue::Struct(fields) => pending.extend(fields.iter()
+                .map(|(name, field)| (format!("{}.{}", label, name), field))),
+            _ => {}
+        }
+    }
+    values
+}
+
+/// Operands at or above 2^255 are negative constants (`not(0)`) or signed values, not amounts
+fn looks_negative(value: U256) -> bool {
+    value.bit(255)
+}
+
+/// The first wrapped operation an argument took part in, with the argument's label
+pub fn attribute<'a>(operations: &'a [WrappedOperation], parameters: &[MethodParameter], args: &[SolidityValue]) -> Option<(String, &'a WrappedOperation)> {
+    let arguments = uint_arguments(parameters, args);
+    operations.iter().find_map(|operation| {
+        let (label, other) = arguments.iter().find_map(|(label, value)| {
+            if *value == operation.left {
+                Some((label, operation.right))
+            } else if *value == operation.right {
+                Some((label, operation.left))
+            } else {
+                None
+            }
+        })?;
+        (operation.op == ArithmeticOp::Sub || !looks_negative(other)).then(|| (label.clone(), operation))
+    })
+}
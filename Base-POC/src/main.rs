@@ -1,42 +1,773 @@
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tracing::{error, warn};
-use crate::fuzz_solidity::SolidityFuzzer;
+use fuzzhead_core::fuzz_solidity::SolidityFuzzer;
+use fuzzhead_core::config::{AccountOptions, Backend, CompilerBackend, CompilerOptions, FeeOptions, FuzzConfig, MethodSource, SolcSelection};
+use fuzzhead_core::invariants::{self, CheckFrequency, InvariantSchedule};
+use fuzzhead_core::ast_parser::SolidityParser;
+use fuzzhead_core::types::{FuzzSummary, TestResult};
+use fuzzhead_core::anvil_executor::build_http_client;
+use fuzzhead_core::anvil_manager::{self, ForkOptions, ManagedAnvil};
+use fuzzhead_core::report::CampaignReport;
+use fuzzhead_core::explorer::Explorer;
+use fuzzhead_core::live_target::LiveTarget;
+use fuzzhead_core::ownership;
+use fuzzhead_core::oracle;
+use fuzzhead_core::case::FailingCase;
+use fuzzhead_core::triage::{self, TriageStore};
+use fuzzhead_core::report_diff;
+use fuzzhead_core::sarif;
+use fuzzhead_core::attacks::{self, ReplayOutcome};
+use fuzzhead_core::tx_dump;
+use fuzzhead_core::repro;
+use fuzzhead_core::output::{self, OutputLayout};
+use fuzzhead_core::trace::TransactionTracer;
+use fuzzhead_core::preflight;
+use fuzzhead_core::hooks::Hooks;
+use fuzzhead_core::forge_failures;
+use fuzzhead_core::corpus::{self, CorpusFormat, CorpusSequence};
+use fuzzhead_core::keys::{self, AccountKey, KeyRole, KeySource};
+use fuzzhead_core::constructor;
+use fuzzhead_core::project_config::{self, ProjectConfig};
+use fuzzhead_core::contract_compiler::ContractCompiler;
+use fuzzhead_core::foundry_project::{FoundryProject, ProjectArtifacts};
+use fuzzhead_core::value_strategy::ParameterStrategy;
+use fuzzhead_core::address_pool::AddressWeights;
+use std::time::{Duration, Instant};
 
-pub mod types;
-pub mod ast_parser;
-pub mod fuzz_solidity;
-pub mod anvil_executor;
-pub mod contract_compiler;
-pub mod constructor;
+/// What a campaign prints on stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            other => Err(format!("unknown output format '{}' (expected text, json or sarif)", other)),
+        }
+    }
+}
 
 #[derive(Parser)]
 #[command(name = "base-solidity-fuzzer")]
 #[command(about = "A Solidity fuzzer for Base smart contracts")]
 #[command(version)]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the Solidity contract file or directory
-    #[arg(short, long)]
-    input: String,
+    #[arg(short, long, required_unless_present = "target_address")]
+    input: Option<String>,
+
+    /// Fuzz the contract already deployed at this address on --fork-url instead of compiling and
+    /// deploying --input; its methods come from --abi
+    #[arg(long, value_name = "ADDRESS", value_parser = ownership::parse_account, requires = "abi", conflicts_with = "input")]
+    target_address: Option<String>,
+
+    /// ABI of --target-address: an ABI array, a Foundry or Hardhat artifact, or an Etherscan getabi response
+    #[arg(long, value_name = "PATH", requires = "target_address")]
+    abi: Option<PathBuf>,
+
+    /// Name --target-address goes by in output and reports (default: the ABI file's name)
+    #[arg(long, value_name = "NAME", requires = "target_address")]
+    target_name: Option<String>,
 
     /// Number of test cases to generate per method
-    #[arg(short, long, default_value = "100")]
+    #[arg(short, long, env = "FUZZ_RUNS", default_value = "100")]
     test_cases: usize,
 
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
     
-    /// RPC URL for Anvil fork (default: http://localhost:8545)
-    #[arg(long, default_value = "http://localhost:8545")]
+    /// RPC URL of the Anvil node (default: http://localhost:8545). Unless --no-spawn-anvil is set,
+    /// an Anvil node is started when nothing answers on a local URL, and a remote RPC that is not
+    /// Anvil is forked by a local node started for the campaign
+    #[arg(long, env = "FORK_URL", default_value = "http://localhost:8545")]
     fork_url: String,
+
+    /// Upstream block to fork. Started forks are pinned to the upstream's latest block when not
+    /// given; an existing forked node is reset to this block before the campaign
+    #[arg(long, value_name = "N")]
+    fork_block_number: Option<u64>,
+
+    /// Do not keep the state a started fork fetches in Anvil's on-disk cache
+    #[arg(long)]
+    no_fork_cache: bool,
+
+    /// Never start Anvil nodes: --fork-url, --confirm-fork-url and --job-fork-url must already be served
+    #[arg(long)]
+    no_spawn_anvil: bool,
+
+    /// Executor backend: anvil (JSON-RPC against --fork-url) or revm (in-process, empty chain, no fork state)
+    #[arg(long, default_value = "anvil")]
+    backend: Backend,
+
+    /// Compiler backend: auto (forge, else solc), forge, solc, or standard-json (solc or solcjs
+    /// fed standard JSON input, with imports and remappings resolved)
+    #[arg(long, default_value = "auto")]
+    compiler: CompilerBackend,
+
+    /// solc that compiles when forge does not: auto (the one in PATH if it satisfies the pragma,
+    /// otherwise a matching release installed to ~/.svm), path, or a release such as 0.8.20
+    #[arg(long, value_name = "VERSION", default_value = "auto")]
+    solc_version: SolcSelection,
+
+    /// Import remapping for the standard-json compiler, `[context:]prefix=target`, on top of the
+    /// project's remappings.txt (repeatable)
+    #[arg(long, value_name = "REMAPPING")]
+    remapping: Vec<String>,
+
+    /// Optimizer runs of the standard-json compiler
+    #[arg(long, default_value = "200")]
+    optimizer_runs: u32,
+
+    /// Compile without the optimizer (standard-json compiler)
+    #[arg(long)]
+    no_optimizer: bool,
+
+    /// EVM version the standard-json compiler targets, e.g. paris or cancun (the compiler's default when unset)
+    #[arg(long, value_name = "VERSION")]
+    evm_version: Option<String>,
+
+    /// Timeout in seconds for each JSON-RPC request
+    #[arg(long, default_value = "30")]
+    rpc_timeout: u64,
+
+    /// Timeout in seconds for a transaction to be mined
+    #[arg(long, default_value = "60")]
+    receipt_timeout: u64,
+
+    /// Trade realism for throughput: enable Anvil auto-impersonation, zero base fee,
+    /// instant mining and an unlimited block gas limit
+    #[arg(long)]
+    turbo: bool,
+
+    /// Re-execute each failing input this many times before reporting it (0 disables confirmation)
+    #[arg(long, default_value = "3")]
+    confirm_runs: usize,
+
+    /// Dual-phase campaign: explore on --fork-url with turbo cheats, then replay candidate
+    /// findings on this separate, normally configured Anvil fork and report only confirmed ones
+    #[arg(long)]
+    confirm_fork_url: Option<String>,
+
+    /// Parse, generate and encode inputs against a mock executor without any network access.
+    /// This is the only simulated mode: results are labeled as simulated and never count as findings.
+    #[arg(long, alias = "simulate")]
+    dry_run: bool,
+
+    /// Skip the ABI round-trip check (decode every encoded call and compare it with the generated inputs)
+    #[arg(long)]
+    no_verify_encoding: bool,
+
+    /// Number of sender accounts to use (default: every account the node reports, or 5 with --mnemonic)
+    #[arg(long)]
+    accounts: Option<usize>,
+
+    /// Derive sender accounts from this BIP-39 mnemonic; accounts the node does not manage are impersonated
+    #[arg(long)]
+    mnemonic: Option<String>,
+
+    /// Sign transactions with the hex private key held in this environment variable (repeatable).
+    /// Append :deployer to only deploy contracts with it, or :sender to only send fuzzed calls
+    #[arg(long, value_name = "VAR[:ROLE]", conflicts_with = "mnemonic")]
+    private_key_env: Vec<KeySource>,
+
+    /// Sign transactions with the key in this encrypted JSON keystore (repeatable), with the same
+    /// :deployer and :sender roles as --private-key-env
+    #[arg(long, value_name = "PATH[:ROLE]", conflicts_with = "mnemonic")]
+    keystore: Vec<KeySource>,
+
+    /// Environment variable holding the password of every --keystore (prompted for otherwise)
+    #[arg(long, value_name = "VAR", requires = "keystore")]
+    keystore_password_env: Option<String>,
+
+    /// Keys loaded from --private-key-env and --keystore
+    #[arg(skip)]
+    account_keys: Vec<AccountKey>,
+
+    /// Artifacts of the project built once by the `project` subcommand
+    #[arg(skip)]
+    project_artifacts: Option<Arc<ProjectArtifacts>>,
+
+    /// When a sender's transactions get stuck in the pool, replace them with higher-priced no-ops
+    #[arg(long)]
+    repair_nonce_gaps: bool,
+
+    /// Send type-2 transactions with this maxFeePerGas (wei)
+    #[arg(long, requires = "max_priority_fee_per_gas")]
+    max_fee_per_gas: Option<u128>,
+
+    /// Send type-2 transactions with this maxPriorityFeePerGas (wei)
+    #[arg(long, requires = "max_fee_per_gas")]
+    max_priority_fee_per_gas: Option<u128>,
+
+    /// Fuzz EIP-1559 fees: draw a new priority fee and max fee (relative to the base fee) for every run
+    #[arg(long, conflicts_with = "max_fee_per_gas")]
+    fuzz_fees: bool,
+
+    /// Master seed for input generation (random and printed when omitted)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Write a JSON report (seeds and findings) to this path
+    #[arg(long)]
+    report_file: Option<PathBuf>,
+
+    /// Result format on stdout: text (progress and summaries), json (the campaign report as one
+    /// JSON document) or sarif (findings as a SARIF 2.1.0 log); with json and sarif, progress and
+    /// summaries go to stderr
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    output: OutputFormat,
+
+    /// Write the findings as a SARIF 2.1.0 log to this path, for GitHub code scanning
+    #[arg(long, value_name = "PATH")]
+    sarif_file: Option<PathBuf>,
+
+    /// Directory mode: fuzz the files in round-robin time slices of this many seconds each instead of one after another
+    #[arg(long, value_name = "SECS")]
+    time_slice: Option<u64>,
+
+    /// Directory mode: fuzz up to this many files at once, each on its own chain (see --job-fork-url)
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), conflicts_with_all = ["time_slice", "confirm_fork_url"])]
+    jobs: u32,
+
+    /// Anvil node for one more parallel job, next to --fork-url (repeatable). Jobs on the anvil
+    /// backend never share a node, since their nonces and snapshots would collide
+    #[arg(long, value_name = "URL")]
+    job_fork_url: Vec<String>,
+
+    /// Stop a time-sliced directory campaign after this many seconds, reporting what unfinished files found so far
+    #[arg(long, value_name = "SECS", requires = "time_slice")]
+    time_budget: Option<u64>,
+
+    /// Fuzz block context (coinbase, base fee, chain id) and flag inputs whose outcome depends on it
+    #[arg(long)]
+    fuzz_block_context: bool,
+
+    /// Re-run some passing inputs with a gas limit near their observed usage and flag the ones that
+    /// still succeed but leave different state (nested calls running out of gas)
+    #[arg(long, conflicts_with = "multicall_batch")]
+    fuzz_gas_limit: bool,
+
+    /// Coverage-guided fuzzing: trace calls for branch coverage (debug_traceTransaction) and
+    /// mutate the inputs that reach new branches
+    #[arg(long)]
+    coverage: bool,
+
+    /// Keep inputs that end a method in a new way (first success, new revert reason) and mutate
+    /// them instead of always drawing fresh values; --coverage already mutates its corpus
+    #[arg(long)]
+    mutate: bool,
+
+    /// Send exploration calls in Multicall3 batches of this many calls (sub-calls see the aggregator
+    /// as msg.sender); failures are reported only if an individual transaction reproduces them
+    #[arg(long, value_name = "N", conflicts_with_all = ["coverage", "mutate", "fuzz_fees", "fuzz_block_context", "max_fee_per_gas"])]
+    multicall_batch: Option<usize>,
+
+    /// Multicall3 contract used by --multicall-batch
+    #[arg(long, default_value = fuzzhead_core::multicall::MULTICALL3_ADDRESS, requires = "multicall_batch")]
+    multicall_address: String,
+
+    /// After the per-method phase, run this many stateful sequences per contract: chains of calls
+    /// across all its methods with random senders and arguments, each on a fresh snapshot
+    #[arg(long, value_name = "N")]
+    sequences: Option<usize>,
+
+    /// Calls per stateful sequence
+    #[arg(long, value_name = "CALLS", default_value_t = 10, requires = "sequences")]
+    sequence_length: usize,
+
+    /// Before each call of a stateful sequence, sometimes warp the clock forward (seconds to
+    /// years), mine blocks or deal ETH to a sender, to reach time-locked and balance-gated logic
+    #[arg(long)]
+    cheats: bool,
+
+    /// Before each stateful sequence, warp the clock forward by a minute to four years and mine
+    /// the blocks of that time, so deadlines, auctions and vesting schedules are reached
+    #[arg(long)]
+    time_jumps: bool,
+
+    /// Flag every method with a call that used more than this much gas, as well as those with a
+    /// call that used all the gas it was sent with
+    #[arg(long, value_name = "GAS")]
+    gas_threshold: Option<u64>,
+
+    /// Print the cheapest, average and costliest call of every method after each contract
+    #[arg(long)]
+    gas_report: bool,
+
+    /// Re-execute shrunk variants of each deterministic failure up to this many times and report
+    /// the smallest arguments that still fail with the same error (0 disables shrinking)
+    #[arg(long, value_name = "RUNS", default_value_t = 100)]
+    shrink_runs: usize,
+
+    /// Percentage of runs sent from a contract's owner when it is an account the fuzzer does not
+    /// control (a mainnet EOA or multisig on a fork), impersonated through Anvil; 0 disables it
+    #[arg(long, value_name = "PERCENT", default_value_t = 10, value_parser = clap::value_parser!(u32).range(0..=100))]
+    owner_percent: u32,
+
+    /// Also send from this on-chain account (a token whale, owner or governance contract on a
+    /// fork), impersonated through Anvil and topped up with ETH for gas (repeatable)
+    #[arg(long, value_name = "ADDRESS", value_parser = ownership::parse_account)]
+    impersonate: Vec<String>,
+
+    /// Impersonate the accounts each deployed contract keeps in its owner and admin variables
+    /// (admin, governance, the EIP-1967 admin slot…) and send from them as well
+    #[arg(long)]
+    discover_senders: bool,
+
+    /// Let the state each fuzzed call leaves behind carry into the next iteration of the per-method
+    /// phase (intentionally stateful campaigns); by default every iteration starts from a snapshot
+    #[arg(long)]
+    keep_state: bool,
+
+    /// Longest an argument is printed in console output, in terminal columns; longer strings, byte
+    /// strings, arrays and structs are cut with "..." (0 prints arguments in full)
+    #[arg(long, value_name = "COLUMNS", default_value_t = fuzzhead_core::display::DEFAULT_MAX_ARG_LEN)]
+    max_arg_display_len: usize,
+
+    /// Where the methods to fuzz come from: source (the parsed Solidity) or abi (the compiled ABI,
+    /// which also covers functions inherited from imported files)
+    #[arg(long, value_name = "SOURCE", default_value = "source")]
+    methods_from: MethodSource,
+
+    /// Deploy an attacker contract that calls back into the target, pass its address as address
+    /// arguments, and report calls that run again inside themselves (requires debug_traceTransaction)
+    #[arg(long)]
+    reentrancy: bool,
+
+    /// Treat reverts of privileged-looking methods (only… modifiers, owner/admin/role setters) from
+    /// senders other than the deployer and owner as expected, and report those calls when they
+    /// succeed and change owner or admin state
+    #[arg(long)]
+    access_control: bool,
+
+    /// Decode the events every call emits and report ones no correct call emits: a Transfer
+    /// minting to address(0), or an OwnershipTransferred to an address chosen by a caller that
+    /// was not the owner (decoded events are logged with --verbose either way)
+    #[arg(long)]
+    events: bool,
+
+    /// Count reverts whose reason contains this text (case-insensitive) as expected, not as
+    /// failures (repeatable); common access-control, pause and reentrancy-guard reasons always are
+    #[arg(long, value_name = "PATTERN")]
+    expected_revert: Vec<String>,
+
+    /// Report Solidity panics with this code (hex or decimal) as findings, even overflows on
+    /// Solidity >=0.8 or reasons matching --expected-revert (repeatable); assertion failures always are
+    #[arg(long, value_name = "CODE", value_parser = oracle::parse_panic_code)]
+    bug_panic: Vec<u64>,
+
+    /// Save every confirmed finding as a replayable attack recipe (JSON) in this directory
+    #[arg(long)]
+    save_attacks: Option<PathBuf>,
+
+    /// Default invariant check frequency: per-call, per-sequence or every-N (calls)
+    #[arg(long, default_value = "per-call")]
+    invariant_frequency: CheckFrequency,
+
+    /// Check frequency of one invariant, as NAME=FREQUENCY (repeatable), e.g. invariant_solvency=every-10
+    #[arg(long = "invariant-frequency-for", value_name = "NAME=FREQUENCY", value_parser = invariants::parse_override)]
+    invariant_frequency_overrides: Vec<(String, CheckFrequency)>,
+
+    /// Track ETH flowing into and out of each target and compare its balance with accounting
+    /// variables found in the storage layout (forge builds only)
+    #[arg(long)]
+    value_accounting: bool,
+
+    /// Link findings to a block explorer: a preset (base, base-sepolia, ethereum, sepolia, optimism,
+    /// horizen-eon, horizen-gobi) or the base URL of a Blockscout or Etherscan-style explorer
+    #[arg(long, value_name = "NAME|URL")]
+    explorer: Option<Explorer>,
+
+    /// Write the exact transaction of every finding (from, to, data, fees, block context) as JSON into this directory
+    #[arg(long)]
+    dump_txs: Option<PathBuf>,
+
+    /// Write a Foundry proof-of-concept test (.t.sol) for every confirmed finding into this directory
+    #[arg(long)]
+    poc_dir: Option<PathBuf>,
+
+    /// Write the report, attack recipes, transaction dumps and PoCs under this directory, one subdirectory per contract
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+
+    /// Log every sent transaction (sender, selector, decoded args, outcome, gas) as newline-delimited JSON to this file
+    #[arg(long, value_name = "FILE")]
+    trace_all: Option<PathBuf>,
+
+    /// Maximum transactions per second written by --trace-all (0 for no limit); the rest are counted and dropped
+    #[arg(long, default_value_t = 1000)]
+    trace_rate: u32,
+
+    /// Start fuzzing without first checking the RPC, compiler and constructor arguments
+    #[arg(long, conflicts_with = "preflight_only")]
+    skip_preflight: bool,
+
+    /// Only run the preflight checks, print the environment report and exit
+    #[arg(long)]
+    preflight_only: bool,
+
+    /// Foundry project whose recorded fuzz and invariant failures (failure_persist_dir in foundry.toml)
+    /// are run as the first inputs of the methods they fit
+    #[arg(long, value_name = "PROJECT_DIR")]
+    forge_failures: Option<PathBuf>,
+
+    /// Echidna or Medusa corpus directory whose transactions are run as the first inputs of the
+    /// methods whose selector they call; can be repeated
+    #[arg(long, value_name = "DIR")]
+    import_corpus: Vec<PathBuf>,
+
+    /// Write every method's corpus (--coverage or --mutate) and the calls of each finding to this
+    /// directory, in the layout of --corpus-format
+    #[arg(long, value_name = "DIR")]
+    export_corpus: Option<PathBuf>,
+
+    /// Layout of --export-corpus: echidna or medusa
+    #[arg(long, default_value = "echidna", requires = "export_corpus")]
+    corpus_format: CorpusFormat,
+
+    /// Value strategy of a parameter, METHOD.PARAMETER=SPEC: range:LO..HI, log:LO..HI or
+    /// values:A,B,C, optionally prefixed with a share (25%:range:1..1e18); repeatable
+    #[arg(long, value_name = "METHOD.PARAM=SPEC")]
+    strategy: Vec<ParameterStrategy>,
+
+    /// Weights of the sources address arguments are drawn from, as SOURCE=WEIGHT pairs (deployer,
+    /// accounts, contracts, attacker, zero, precompiles, random); unlisted sources keep their default
+    #[arg(long, value_name = "SOURCE=WEIGHT,...")]
+    address_weights: Option<AddressWeights>,
+
+    /// WASM module of user hooks (value generation, outcome oracle, method order);
+    /// needs a build with `--features wasm-hooks`
+    #[arg(long, value_name = "FILE.wasm")]
+    hooks: Option<PathBuf>,
+
+    /// Campaign settings file (default: the nearest fuzzhead.toml in the input's directory or its
+    /// ancestors); flags and environment variables take precedence over it
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Leave contracts matching this name or glob out of the campaign (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    exclude_contract: Vec<String>,
+
+    /// Fuzz every contract of a file, with the methods inherited from its other declarations,
+    /// instead of only the first one
+    #[arg(long)]
+    all_contracts: bool,
+
+    /// Leave methods matching this name, signature or Contract.method glob out of the campaign (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    exclude_function: Vec<String>,
+
+    /// Constructor arguments as JSON instead of the interactive prompt: an array for every contract
+    /// ('["1000", "0xab..."]') or an object of arrays per contract name ('{"Vault": [1000]}')
+    #[arg(long, value_name = "JSON", value_parser = constructor::parse_constructor_args_json)]
+    constructor_args: Option<BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Work with recorded attack scenarios
+    Attacks {
+        #[command(subcommand)]
+        command: AttacksCommand,
+    },
+    /// Work with JSON campaign reports written by --report-file
+    Report {
+        #[command(subcommand)]
+        command: ReportCommand,
+    },
+    /// Walk through the findings of a report and record a verdict for each
+    Triage {
+        /// JSON campaign report written by --report-file
+        report: PathBuf,
+
+        /// File the verdicts are kept in (default: triage.json next to the report)
+        #[arg(long)]
+        triage_file: Option<PathBuf>,
+
+        /// Trace written by --trace-all during the campaign, to show the failing transactions
+        #[arg(long)]
+        trace: Option<PathBuf>,
+
+        /// Also walk through findings that already have a verdict
+        #[arg(long)]
+        revisit: bool,
+    },
+    /// Fuzz every deployable contract of a Foundry project, built once with `forge build`
+    Project {
+        /// Project directory, holding foundry.toml
+        path: PathBuf,
+    },
+    /// Re-execute one failing case on a fresh deployment for debugging
+    Replay {
+        /// Case file (contract, signature, seed, sender, args), or a JSON campaign report
+        case: PathBuf,
+
+        /// Finding of the report to replay, counted from 1 across all files
+        #[arg(long, value_name = "N")]
+        finding: Option<usize>,
+
+        /// Replay against this source file instead of the one the case was recorded from
+        #[arg(long)]
+        source: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommand {
+    /// Compare two campaign reports: runs, throughput, method coverage, gas and findings
+    Diff {
+        /// Baseline report
+        old: PathBuf,
+
+        /// Report to compare against the baseline
+        new: PathBuf,
+
+        /// Triage file (see `triage`): dismissed findings are listed apart, the others show their verdict
+        #[arg(long)]
+        triage: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AttacksCommand {
+    /// Re-validate recorded attack scenarios against the current contract sources
+    Replay {
+        /// Attack recipe file, or a directory of recipes
+        recipes: PathBuf,
+
+        /// Replay against this source file instead of the one each recipe was recorded from
+        #[arg(long)]
+        source: Option<PathBuf>,
+    },
+}
+
+impl Cli {
+    fn output_layout(&self) -> Option<OutputLayout> {
+        self.out_dir.as_ref().map(OutputLayout::new)
+    }
+
+    fn fuzz_config(&self) -> FuzzConfig {
+        FuzzConfig {
+            backend: self.backend,
+            compiler: CompilerOptions {
+                backend: self.compiler,
+                solc: self.solc_version.clone(),
+                optimizer: !self.no_optimizer,
+                optimizer_runs: self.optimizer_runs,
+                remappings: self.remapping.clone(),
+                evm_version: self.evm_version.clone(),
+            },
+            fork_url: self.fork_url.clone(),
+            fork_block_number: self.fork_block_number,
+            confirm_runs: self.confirm_runs,
+            rpc_timeout_secs: self.rpc_timeout,
+            receipt_timeout_secs: self.receipt_timeout,
+            turbo: self.turbo,
+            confirm_fork_url: self.confirm_fork_url.clone(),
+            dry_run: self.dry_run,
+            verify_encoding: !self.no_verify_encoding,
+            accounts: AccountOptions {
+                count: self.accounts,
+                mnemonic: self.mnemonic.clone(),
+                keys: self.account_keys.clone(),
+                impersonate: self.impersonate.clone(),
+            },
+            repair_nonce_gaps: self.repair_nonce_gaps,
+            fees: FeeOptions {
+                max_fee_per_gas: self.max_fee_per_gas,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+                fuzz: self.fuzz_fees,
+            },
+            seed: self.seed,
+            fuzz_block_context: self.fuzz_block_context,
+            fuzz_gas_limit: self.fuzz_gas_limit,
+            value_accounting: self.value_accounting,
+            invariant_schedule: InvariantSchedule {
+                default: self.invariant_frequency,
+                overrides: self.invariant_frequency_overrides.iter().cloned().collect(),
+            },
+            coverage_guided: self.coverage,
+            mutation: self.mutate,
+            export_corpus: self.export_corpus.is_some(),
+            multicall_batch: self.multicall_batch.unwrap_or(0),
+            multicall_address: self.multicall_address.clone(),
+            sequences: self.sequences.unwrap_or(0),
+            sequence_length: self.sequence_length.max(1),
+            cheats: self.cheats,
+            time_jumps: self.time_jumps,
+            gas_threshold: self.gas_threshold,
+            gas_report: self.gas_report,
+            shrink_runs: self.shrink_runs,
+            owner_percent: self.owner_percent,
+            discover_senders: self.discover_senders,
+            keep_state: self.keep_state,
+            max_arg_display_len: self.max_arg_display_len,
+            method_source: self.methods_from,
+            reentrancy: self.reentrancy,
+            access_control: self.access_control,
+            events: self.events,
+            expected_reverts: self.expected_revert.clone(),
+            bug_panics: self.bug_panic.clone(),
+            explorer: self.explorer.clone(),
+            value_strategies: self.strategy.clone(),
+            address_weights: self.address_weights.unwrap_or_default(),
+            runs_per_method: self.test_cases,
+            exclude_contracts: self.exclude_contract.clone(),
+            all_contracts: self.all_contracts,
+            exclude_functions: self.exclude_function.clone(),
+            constructor_args: self.constructor_args.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Fill in the settings of `fuzzhead.toml` (`--config`, or the nearest one of the input or
+    /// working directory) that were not given as flags or environment variables
+    fn apply_project_config(&mut self, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+        let path = match &self.config {
+            Some(path) => path.clone(),
+            None => {
+                let start = match &self.input {
+                    Some(input) => PathBuf::from(input),
+                    None => std::env::current_dir()?,
+                };
+                match project_config::discover(&start) {
+                    Some(path) => path,
+                    None => return Ok(()),
+                }
+            }
+        };
+        let project = ProjectConfig::load(&path)?;
+        println!("⚙️  Configuration loaded from {}", path.display());
+
+        let unset = |id: &str| !matches!(matches.value_source(id), Some(ValueSource::CommandLine | ValueSource::EnvVariable));
+        if let Some(fork_url) = project.fork_url.filter(|_| unset("fork_url")) {
+            self.fork_url = fork_url;
+        }
+        if unset("fork_block_number") {
+            self.fork_block_number = self.fork_block_number.or(project.fork_block_number);
+        }
+        if let Some(runs) = project.runs.filter(|_| unset("test_cases")) {
+            self.test_cases = runs;
+        }
+        if unset("seed") {
+            self.seed = self.seed.or(project.seed);
+        }
+        if unset("accounts") {
+            self.accounts = self.accounts.or(project.senders.accounts);
+        }
+        if unset("mnemonic") {
+            self.mnemonic = self.mnemonic.take().or(project.senders.mnemonic);
+        }
+        if let Some(percent) = project.senders.owner_percent.filter(|_| unset("owner_percent")) {
+            if percent > 100 {
+                return Err(format!("{}: senders.owner_percent must be between 0 and 100", path.display()).into());
+            }
+            self.owner_percent = percent;
+        }
+        if let Some(discover) = project.senders.discover.filter(|_| unset("discover_senders")) {
+            self.discover_senders = discover;
+        }
+        // Impersonated accounts add up, like exclusions
+        for account in &project.senders.impersonate {
+            let account = ownership::parse_account(account).map_err(|e| format!("{}: senders.impersonate: {}", path.display(), e))?;
+            if !self.impersonate.iter().any(|known| known.eq_ignore_ascii_case(&account)) {
+                self.impersonate.push(account);
+            }
+        }
+
+        let detectors = project.detectors;
+        let toggles = [
+            (detectors.reentrancy, "reentrancy", &mut self.reentrancy),
+            (detectors.access_control, "access_control", &mut self.access_control),
+            (detectors.events, "events", &mut self.events),
+            (detectors.value_accounting, "value_accounting", &mut self.value_accounting),
+            (detectors.block_context, "fuzz_block_context", &mut self.fuzz_block_context),
+            (detectors.gas_limit, "fuzz_gas_limit", &mut self.fuzz_gas_limit),
+            (detectors.fees, "fuzz_fees", &mut self.fuzz_fees),
+            (detectors.coverage, "coverage", &mut self.coverage),
+            (detectors.mutate, "mutate", &mut self.mutate),
+            (detectors.cheats, "cheats", &mut self.cheats),
+            (detectors.time_jumps, "time_jumps", &mut self.time_jumps),
+            (detectors.gas_report, "gas_report", &mut self.gas_report),
+        ];
+        for (enabled, id, flag) in toggles {
+            if let Some(enabled) = enabled.filter(|_| unset(id)) {
+                *flag = enabled;
+            }
+        }
+        if unset("sequences") {
+            self.sequences = self.sequences.or(detectors.sequences);
+        }
+        if unset("gas_threshold") {
+            self.gas_threshold = self.gas_threshold.or(detectors.gas_threshold);
+        }
+
+        let compiler = project.compiler;
+        if let Some(backend) = compiler.backend.filter(|_| unset("compiler")) {
+            self.compiler = backend.parse().map_err(|e| format!("{}: compiler.backend: {}", path.display(), e))?;
+        }
+        if let Some(solc_version) = compiler.solc_version.filter(|_| unset("solc_version")) {
+            self.solc_version = solc_version.parse().map_err(|e| format!("{}: compiler.solc_version: {}", path.display(), e))?;
+        }
+        if let Some(optimizer) = compiler.optimizer.filter(|_| unset("no_optimizer")) {
+            self.no_optimizer = !optimizer;
+        }
+        if let Some(runs) = compiler.optimizer_runs.filter(|_| unset("optimizer_runs")) {
+            self.optimizer_runs = runs;
+        }
+        if unset("evm_version") {
+            self.evm_version = self.evm_version.take().or(compiler.evm_version);
+        }
+        self.remapping.extend(compiler.remappings);
+
+        // Exclusions and revert rules add up; --constructor-args replaces the file's constructor arguments
+        self.exclude_contract.extend(project.exclude.contracts);
+        self.exclude_function.extend(project.exclude.functions);
+        self.expected_revert.extend(project.reverts.expected);
+        self.bug_panic.extend(project.reverts.bug_panics);
+        if self.constructor_args.is_none() {
+            self.constructor_args = Some(project.constructor_args);
+        }
+        if let Some(weights) = project.address_weights.filter(|_| unset("address_weights")) {
+            weights.validate().map_err(|e| format!("{}: address_weights: {}", path.display(), e))?;
+            self.address_weights = Some(weights);
+        }
+        // Strategies of the file come after the flags', so a flag wins for the same parameter
+        for (method, parameters) in project.strategies {
+            for (parameter, spec) in parameters {
+                let strategy = ParameterStrategy::parse(&method, &parameter, &spec)
+                    .map_err(|e| format!("{}: strategies.{}.{}: {}", path.display(), method, parameter, e))?;
+                self.strategy.push(strategy);
+            }
+        }
+        Ok(())
+    }
 }
 
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Initialize logging
     let log_level = if cli.verbose {
@@ -45,58 +776,340 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::Level::INFO
     };
 
+    // Machine-readable campaigns keep stdout for the report: logs and progress go to stderr
+    let campaign = matches!(cli.command, None | Some(Command::Project { .. }));
+    let machine_output = cli.output != OutputFormat::Text && campaign;
     tracing_subscriber::fmt()
         .with_max_level(log_level)
+        .with_writer(move || -> Box<dyn std::io::Write> {
+            if machine_output { Box::new(std::io::stderr()) } else { Box::new(std::io::stdout()) }
+        })
         .init();
+    let mut report_stdout = if machine_output { Some(divert_stdout()?) } else { None };
+
+    if let Some(Command::Report { command: ReportCommand::Diff { old, new, triage } }) = &cli.command {
+        let triage = triage.as_deref().map(TriageStore::load).transpose()?;
+        let diff = report_diff::diff(&CampaignReport::load(old)?, &CampaignReport::load(new)?, triage.as_ref());
+        print!("{}", diff);
+        return Ok(());
+    }
+
+    if let Some(Command::Triage { report, triage_file, trace, revisit }) = &cli.command {
+        let triage_file = triage_file.clone().unwrap_or_else(|| report.with_file_name("triage.json"));
+        let mut store = TriageStore::load(&triage_file)?;
+        let session = triage::run(&CampaignReport::load(report)?, &mut store, &triage_file, trace.as_deref(), *revisit)?;
+        println!("\n🏷️  {} finding(s) triaged, {} skipped; verdicts kept in {}", session.reviewed, session.skipped, triage_file.display());
+        return Ok(());
+    }
+
+    cli.apply_project_config(&matches)?;
+
+    if !cli.private_key_env.is_empty() || !cli.keystore.is_empty() {
+        cli.account_keys = keys::load(&cli.private_key_env, &cli.keystore, cli.keystore_password_env.as_deref())?;
+        for key in &cli.account_keys {
+            let role = match key.role {
+                KeyRole::Deployer => " (deployer)",
+                KeyRole::Sender => " (sender)",
+                KeyRole::Any => "",
+            };
+            println!("🔑 Signing as {}{}", key, role);
+        }
+    }
 
     // Process input (file or directory)
-    let input_path = Path::new(&cli.input);
-    if input_path.is_file() {
-        process_single_file(&cli, input_path).await?;
-    } else if input_path.is_dir() {
-        process_directory(&cli, input_path).await?;
+    // One pooled HTTP client is shared by every fuzzer instance
+    let client = build_http_client(Duration::from_secs(cli.rpc_timeout))?;
+    // Killed when dropped at the end of main, on errors too
+    let _anvil_nodes = if cli.dry_run || cli.no_spawn_anvil {
+        Vec::new()
     } else {
-        error!("Input path does not exist: {}", cli.input);
-        return Err("Invalid input path".into());
+        start_anvil_nodes(&mut cli, &client).await?
+    };
+
+    if let Some(trace_path) = &cli.trace_all {
+        // Every fuzzer instance appends to the trace, so start each campaign from an empty file
+        fs::File::create(trace_path)?;
+        println!("🧵 Tracing every transaction to {}", trace_path.display());
+    }
+
+    if let Some(Command::Attacks { command: AttacksCommand::Replay { recipes, source } }) = &cli.command {
+        return replay_attacks(&cli, recipes, source.as_deref(), &client).await;
+    }
+    if let Some(Command::Replay { case, finding, source }) = &cli.command {
+        return replay_case(&cli, case, *finding, source.as_deref(), &client).await;
+    }
+    // A project is built once up front; its files are then fuzzed from the shared artifacts
+    let project = match cli.command.as_ref() {
+        Some(Command::Project { path }) => {
+            let path = path.clone();
+            Some(build_project(&mut cli, &path)?)
+        },
+        _ => None,
+    };
+    // A live target has no source: nothing to compile, and preflight only checks the nodes
+    let input = match (&project, &cli.target_address) {
+        (Some(project), _) => Some(project.src.display().to_string()),
+        (None, Some(_)) => None,
+        (None, None) => Some(cli.input.clone().ok_or("--input is required")?),
+    };
+
+    if !cli.skip_preflight {
+        run_preflight(&cli, input.as_deref().map(Path::new), &client).await?;
+        if cli.preflight_only {
+            return Ok(());
+        }
+    }
+
+    // Every file of the campaign derives its method seeds from the same master seed
+    let master_seed = *cli.seed.get_or_insert_with(rand::random);
+    println!("🎲 Master seed: {} (pass --seed {} to reproduce)", master_seed, master_seed);
+    let mut report = CampaignReport::new(master_seed);
+    report.simulated = cli.dry_run;
+    report.explorer = cli.explorer.clone();
+
+    match input.as_deref().map(Path::new) {
+        Some(src_dir) if project.is_some() => process_project(&cli, src_dir, &client, &mut report).await?,
+        None => process_live_target(&cli, &client, &mut report).await?,
+        Some(input_path) if input_path.is_file() => process_single_file(&cli, input_path, &client, &mut report).await?,
+        Some(input_path) if input_path.is_dir() => process_directory(&cli, input_path, &client, &mut report).await?,
+        Some(input_path) => {
+            error!("Input path does not exist: {}", input_path.display());
+            return Err("Invalid input path".into());
+        }
+    }
+
+    let report_path = cli.report_file.clone().or_else(|| cli.output_layout().map(|layout| layout.report_file()));
+    if let Some(report_path) = &report_path {
+        report.write(report_path)?;
+        println!("📝 Report written to {}", report_path.display());
+    }
+    if let Some(sarif_path) = &cli.sarif_file {
+        fs::write(sarif_path, serde_json::to_string_pretty(&sarif::render(&report))?)?;
+        println!("🛡️  SARIF log written to {}", sarif_path.display());
+    }
+    if let Some(stdout) = &mut report_stdout {
+        match cli.output {
+            OutputFormat::Sarif => serde_json::to_writer_pretty(&mut *stdout, &sarif::render(&report))?,
+            _ => serde_json::to_writer_pretty(&mut *stdout, &report)?,
+        }
+        writeln!(stdout)?;
     }
 
     Ok(())
 }
 
-async fn process_single_file(cli: &Cli, file_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// Point stdout at stderr, so nothing printed during the campaign ends up in the JSON document,
+/// and return a handle to the original stdout for the report
+#[cfg(unix)]
+fn divert_stdout() -> std::io::Result<fs::File> {
+    use std::os::fd::FromRawFd;
+
+    std::io::stdout().flush()?;
+    // SAFETY: only duplicates the process's standard descriptors; the duplicate is owned by the returned file
+    unsafe {
+        let original = libc::dup(libc::STDOUT_FILENO);
+        if original < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            let error = std::io::Error::last_os_error();
+            libc::close(original);
+            return Err(error);
+        }
+        Ok(fs::File::from_raw_fd(original))
+    }
+}
+
+#[cfg(not(unix))]
+fn divert_stdout() -> std::io::Result<fs::File> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "--output json and sarif are only supported on Unix; use --report-file or --sarif-file instead"))
+}
+
+async fn process_single_file(cli: &Cli, file_path: &Path, client: &reqwest::Client, report: &mut CampaignReport) -> Result<(), Box<dyn std::error::Error>> {
 
     let source = fs::read_to_string(file_path)?;
-    let mut fuzzer = SolidityFuzzer::new(&cli.fork_url).await?;
+    let mut fuzzer = build_fuzzer(cli, client).await?;
 
     // Run fuzzing
-    let _summary = fuzzer.fuzz_contract(&source, file_path.to_str().unwrap()).await?;
+    let summary = fuzzer.fuzz_contract(&source, &file_path.to_string_lossy()).await?;
+    report.add_file(&file_path.display().to_string(), &summary);
+    save_attack_recipes(cli, file_path, &summary)?;
+    dump_transactions(cli, file_path, &summary)?;
+    write_pocs(cli, file_path, &summary)?;
+    export_corpus(cli, &summary)?;
+    print_explorer_links(cli, &summary);
     
     Ok(())
 }
 
-async fn process_directory(cli: &Cli, dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// Fuzz `--target-address` in place through `--abi`. Its findings are not saved as attack recipes,
+/// since replaying those compiles the contract from source.
+async fn process_live_target(cli: &Cli, client: &reqwest::Client, report: &mut CampaignReport) -> Result<(), Box<dyn std::error::Error>> {
+    let address = cli.target_address.as_deref().ok_or("--target-address is required")?;
+    let abi_path = cli.abi.as_deref().ok_or("--target-address needs --abi")?;
+    let target = LiveTarget::load(address, abi_path, cli.target_name.as_deref())?;
+    let mut fuzzer = build_fuzzer(cli, client).await?;
 
-    let mut total_passed = 0;
-    let mut total_failed = 0;
-    let mut total_skipped = 0;
+    let summary = fuzzer.fuzz_live(&target).await?;
+    report.add_file(&abi_path.display().to_string(), &summary);
+    dump_transactions(cli, abi_path, &summary)?;
+    write_pocs(cli, abi_path, &summary)?;
+    export_corpus(cli, &summary)?;
+    print_explorer_links(cli, &summary);
+
+    Ok(())
+}
+
+/// Build a Foundry project once and keep its artifacts for every file's campaign. A dry run
+/// without forge reads whatever build output the project already has.
+fn build_project(cli: &mut Cli, path: &Path) -> Result<FoundryProject, Box<dyn std::error::Error>> {
+    let project = FoundryProject::load(path)?;
+    match ContractCompiler::locate().forge_path() {
+        Some(forge) => {
+            println!("🏗️  Building Foundry project {} with forge build", project.root.display());
+            let started = Instant::now();
+            project.build(forge)?;
+            println!("   built in {:.1}s", started.elapsed().as_secs_f64());
+        }
+        None if cli.dry_run => println!("🧪 Dry run: forge not found, using the existing build in {}", project.out.display()),
+        None => return Err("The project subcommand needs forge in PATH (https://getfoundry.sh)".into()),
+    }
+    let artifacts = project.artifacts()?;
+    println!("🏗️  {} deployable contract(s) in {} file(s) under {}",
+        artifacts.len(), artifacts.sources().len(), project.src.display());
+    cli.project_artifacts = Some(Arc::new(artifacts));
+    Ok(project)
+}
 
+/// Fuzz the files of a project build that hold deployable contracts, or, in a dry run with
+/// nothing built, every file of its source directory
+async fn process_project(cli: &Cli, src_dir: &Path, client: &reqwest::Client, report: &mut CampaignReport) -> Result<(), Box<dyn std::error::Error>> {
+    let sources = cli.project_artifacts.as_deref().map(ProjectArtifacts::sources).unwrap_or_default();
+    let files = match sources.is_empty() {
+        true if cli.dry_run => find_solidity_files(src_dir)?,
+        true => return Err(format!("No deployable contracts found in the build of {}", src_dir.display()).into()),
+        false => sources,
+    };
+    process_files(cli, src_dir, files, client, report).await
+}
+
+async fn process_directory(cli: &Cli, dir_path: &Path, client: &reqwest::Client, report: &mut CampaignReport) -> Result<(), Box<dyn std::error::Error>> {
     // Find all Solidity files
     let solidity_files = find_solidity_files(dir_path)?;
+    process_files(cli, dir_path, solidity_files, client, report).await
+}
+
+/// Fuzz each file in turn and print the combined summary; paths are shown relative to `dir_path`
+async fn process_files(
+    cli: &Cli,
+    dir_path: &Path,
+    solidity_files: Vec<PathBuf>,
+    client: &reqwest::Client,
+    report: &mut CampaignReport,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut total_passed = 0;
+    let mut total_failed = 0;
+    let mut total_skipped = 0;
+    let mut total_errors = 0;
+    let mut total_flaky = 0;
+    let mut total_unconfirmed = 0;
+    let mut total_confirmation_errors = 0;
+    let mut total_checked_math_panics = 0;
+    let mut total_expected_reverts = 0;
+    let mut total_block_context_dependent = 0;
+    let mut total_gas_dependent = 0;
+    let mut total_system_address_successes = 0;
+    let mut total_reentrancies = 0;
+    let mut total_wraparounds = 0;
+    let mut total_access_denied = 0;
+    let mut total_unauthorized_changes = 0;
+    let mut total_ether_drains = 0;
+    let mut total_selector_issues = 0;
+    let mut total_bricking_calls = 0;
+    let mut total_invariant_violations = 0;
+    let mut total_gas_alerts = 0;
+    let mut skipped_contracts = Vec::new();
+    let mut compatibility_rows = Vec::new();
 
     let file_count = solidity_files.len();
+    // Time-sliced and parallel campaigns fuzz every file first; the outcomes are then reported in file order
+    let mut finished_outcomes = match (cli.time_slice, cli.jobs) {
+        (Some(secs), _) => Some(run_time_slices(cli, client, &solidity_files, Duration::from_secs(secs)).await?.into_iter()),
+        (None, jobs) if jobs > 1 => Some(run_jobs(cli, client, &solidity_files).await?.into_iter()),
+        _ => None,
+    };
     for file_path in solidity_files {
         
         let source = fs::read_to_string(&file_path)?;
-        let mut fuzzer = SolidityFuzzer::new(&cli.fork_url).await?;
 
-        match fuzzer.fuzz_contract(&source, file_path.to_str().unwrap()).await {
+        let display_path = file_path.strip_prefix(dir_path).unwrap_or(&file_path).display().to_string();
+        let pragma = SolidityParser::extract_pragma(&source).unwrap_or_else(|| "-".to_string());
+
+        let outcome = match finished_outcomes.as_mut() {
+            Some(outcomes) => outcomes.next().expect("one outcome per file"),
+            None => build_fuzzer(cli, client).await?.fuzz_contract(&source, &file_path.to_string_lossy()).await,
+        };
+        match outcome {
             Ok(summary) => {
+                if summary.compile_info.is_empty() {
+                    compatibility_rows.push(CompatibilityRow {
+                        file: display_path.clone(),
+                        pragma: pragma.clone(),
+                        compiler: "-".to_string(),
+                        evm_version: "-".to_string(),
+                        status: "no deployable contracts".to_string(),
+                    });
+                }
+                for info in &summary.compile_info {
+                    compatibility_rows.push(CompatibilityRow {
+                        file: format!("{} ({})", display_path, info.contract),
+                        pragma: pragma.clone(),
+                        compiler: format!("{} {}", info.backend, info.compiler_version.as_deref().unwrap_or("?")),
+                        evm_version: info.evm_version.clone().unwrap_or_else(|| "?".to_string()),
+                        status: "fuzzed".to_string(),
+                    });
+                }
                 total_passed += summary.total_passed;
                 total_failed += summary.total_failed;
                 total_skipped += summary.total_skipped;
+                total_errors += summary.total_errors;
+                total_flaky += summary.total_flaky;
+                total_unconfirmed += summary.total_unconfirmed;
+                total_confirmation_errors += summary.total_confirmation_errors;
+                total_checked_math_panics += summary.total_checked_math_panics;
+                total_expected_reverts += summary.total_expected_reverts;
+                total_block_context_dependent += summary.total_block_context_dependent;
+                total_gas_dependent += summary.total_gas_dependent;
+                total_system_address_successes += summary.total_system_address_successes;
+                total_reentrancies += summary.total_reentrancies;
+                total_wraparounds += summary.total_wraparounds;
+                total_access_denied += summary.total_access_denied;
+                total_unauthorized_changes += summary.total_unauthorized_changes;
+                total_ether_drains += summary.total_ether_drains;
+                total_selector_issues += summary.selector_issues.len();
+                total_bricking_calls += summary.bricking_calls.len();
+                total_invariant_violations += summary.invariant_violations.len();
+                total_gas_alerts += summary.gas_alerts.len();
+                report.add_file(&file_path.display().to_string(), &summary);
+                save_attack_recipes(cli, &file_path, &summary)?;
+                dump_transactions(cli, &file_path, &summary)?;
+                write_pocs(cli, &file_path, &summary)?;
+                export_corpus(cli, &summary)?;
+                print_explorer_links(cli, &summary);
+                skipped_contracts.extend(summary.skipped_contracts.into_iter()
+                    .map(|skipped| (file_path.display().to_string(), skipped)));
             }
             Err(e) => {
                 warn!("Failed to process {}: {}", file_path.display(), e);
+                let reason = e.to_string();
+                compatibility_rows.push(CompatibilityRow {
+                    file: display_path,
+                    pragma,
+                    compiler: "-".to_string(),
+                    evm_version: "-".to_string(),
+                    status: reason.lines().next().unwrap_or("failed").chars().take(60).collect(),
+                });
             }
         }
     }
@@ -108,11 +1121,522 @@ async fn process_directory(cli: &Cli, dir_path: &Path) -> Result<(), Box<dyn std
     if total_skipped > 0 {
         println!("   ⏭️  {} total runs skipped", total_skipped);
     }
-    println!("   📊 Total: {} runs across {} files", total_passed + total_failed + total_skipped, file_count);
+    if total_flaky > 0 {
+        println!("   🔁 {} total runs flaky (not reproducible)", total_flaky);
+    }
+    if total_block_context_dependent > 0 {
+        println!("   🎲 {} total inputs depend on block context (miner-manipulable)", total_block_context_dependent);
+    }
+    if total_gas_dependent > 0 {
+        println!("   ⛽ {} total inputs leave different state under a reduced gas limit", total_gas_dependent);
+    }
+    if total_system_address_successes > 0 {
+        println!("   🔌 {} total calls succeeded with a precompile or system contract argument", total_system_address_successes);
+    }
+    if total_reentrancies > 0 {
+        println!("   🪃 {} total methods can be re-entered", total_reentrancies);
+    }
+    if total_wraparounds > 0 {
+        println!("   🌀 {} total methods wrap arithmetic around", total_wraparounds);
+    }
+    if total_unauthorized_changes > 0 {
+        println!("   🔓 {} total privileged methods changed protected state for an unauthorized sender", total_unauthorized_changes);
+    }
+    if total_ether_drains > 0 {
+        println!("   💸 {} total sequences drained ETH to an unprivileged account", total_ether_drains);
+    }
+    if total_selector_issues > 0 {
+        println!("   🧷 {} selector collision(s) or shadowed function(s)", total_selector_issues);
+    }
+    if total_bricking_calls > 0 {
+        println!("   🧱 {} bricking method(s) skipped", total_bricking_calls);
+    }
+    if total_invariant_violations > 0 {
+        println!("   🚨 {} invariant violation(s)", total_invariant_violations);
+    }
+    if total_gas_alerts > 0 {
+        println!("   🔥 {} method(s) with a call over the gas threshold or out of gas", total_gas_alerts);
+    }
+    if total_checked_math_panics > 0 {
+        println!("   🧮 {} total runs hit checked-math panics (expected on Solidity >=0.8)", total_checked_math_panics);
+    }
+    if total_expected_reverts > 0 {
+        println!("   🚧 {} total runs reverted with an expected reason (access control, pausing, reentrancy guards…)", total_expected_reverts);
+    }
+    if total_access_denied > 0 {
+        println!("   🛂 {} total runs of privileged methods turned away an unauthorized sender (expected)", total_access_denied);
+    }
+    if total_unconfirmed > 0 {
+        println!("   🧪 {} total runs unconfirmed (failed only under turbo exploration or inside a multicall batch)", total_unconfirmed);
+    }
+    if total_confirmation_errors > 0 {
+        println!("   ⚠️  {} total candidate runs could not be replayed on the confirmation fork (RPC/infrastructure)", total_confirmation_errors);
+    }
+    if total_errors > 0 {
+        println!("   ⚠️  {} total runs errored (RPC/infrastructure)", total_errors);
+    }
+    println!("   📊 Total: {} runs across {} files", total_passed + total_failed + total_skipped + total_errors + total_flaky + total_unconfirmed + total_confirmation_errors + total_checked_math_panics + total_expected_reverts + total_access_denied, file_count);
+    if let Some(seed) = cli.seed {
+        println!("   🎲 Seed {} (pass --seed {} to reproduce)", seed, seed);
+    }
+
+    print_compatibility_table(&compatibility_rows);
+
+    if !skipped_contracts.is_empty() {
+        println!("\n⏭️  Non-deployable declarations (not compiled or fuzzed):");
+        for (file, skipped) in &skipped_contracts {
+            println!("   - {} in {}: {}", skipped.name, file, skipped.reason);
+        }
+    }
+
+    Ok(())
+}
+
+/// Start an Anvil node for every node URL of the campaign that is not served by one (see
+/// `anvil_manager::ensure_node`) and point the URL at it. When `--fork-url` was started, the
+/// missing nodes of `--jobs` are started as well, forking the same chain. The block the
+/// `--fork-url` fork was pinned to becomes `--fork-block-number`, so every node forks the same state.
+async fn start_anvil_nodes(cli: &mut Cli, client: &reqwest::Client) -> Result<Vec<ManagedAnvil>, Box<dyn std::error::Error>> {
+    let mut nodes = Vec::new();
+    if cli.backend == Backend::Anvil {
+        let fork = fork_options(cli);
+        ensure_node(client, &mut cli.fork_url, fork, "--fork-url", &mut nodes).await?;
+        if let Some(block) = nodes.first().and_then(ManagedAnvil::fork_block_number) {
+            cli.fork_block_number = Some(block);
+        }
+        let fork = fork_options(cli);
+        for url in cli.job_fork_url.iter_mut() {
+            ensure_node(client, url, fork, "--job-fork-url", &mut nodes).await?;
+        }
+    }
+    let fork = fork_options(cli);
+    if let Some(url) = cli.confirm_fork_url.as_mut() {
+        ensure_node(client, url, fork, "--confirm-fork-url", &mut nodes).await?;
+    }
+
+    let fork_node = nodes.first().filter(|node| node.url() == cli.fork_url);
+    let missing_jobs = (cli.jobs as usize).saturating_sub(1 + cli.job_fork_url.len());
+    if let (Some(fork_node), true) = (fork_node, missing_jobs > 0) {
+        let upstream = fork_node.upstream().map(str::to_string);
+        for _ in 0..missing_jobs {
+            let node = ManagedAnvil::spawn(client, upstream.as_deref(), fork_options(cli)).await?;
+            print_started(&node, "--jobs");
+            cli.job_fork_url.push(node.url().to_string());
+            nodes.push(node);
+        }
+    }
+    Ok(nodes)
+}
+
+fn fork_options(cli: &Cli) -> ForkOptions {
+    ForkOptions { block_number: cli.fork_block_number, no_storage_caching: cli.no_fork_cache }
+}
+
+async fn ensure_node(client: &reqwest::Client, url: &mut String, fork: ForkOptions, label: &str, nodes: &mut Vec<ManagedAnvil>) -> Result<(), anyhow::Error> {
+    if let Some(node) = anvil_manager::ensure_node(client, url, fork).await? {
+        print_started(&node, label);
+        *url = node.url().to_string();
+        nodes.push(node);
+    }
+    Ok(())
+}
+
+fn print_started(node: &ManagedAnvil, label: &str) {
+    let chain = match (node.upstream(), node.fork_block_number()) {
+        (Some(upstream), Some(block)) => format!("a fork of {} at block {} (pass --fork-block-number {} to reproduce)", upstream, block, block),
+        (Some(upstream), None) => format!("a fork of {}", upstream),
+        (None, _) => "an empty local chain".to_string(),
+    };
+    println!("🔨 Started Anvil for {} on {} with {} (log: {})", label, node.url(), chain, node.log_path().display());
+}
+
+/// Fuzz the files of a directory on up to `--jobs` tokio tasks. Every job runs on a chain of its
+/// own: the in-process revm chain or dry-run mock of its fuzzer, or on the anvil backend one of the
+/// nodes of `--fork-url` and `--job-fork-url`. Outcomes are returned in file order.
+async fn run_jobs(
+    cli: &Cli,
+    client: &reqwest::Client,
+    files: &[PathBuf],
+) -> Result<Vec<Result<FuzzSummary, anyhow::Error>>, Box<dyn std::error::Error>> {
+    let jobs = cli.jobs as usize;
+    let mut free_nodes: Vec<String> = if cli.backend == Backend::Anvil && !cli.dry_run {
+        std::iter::once(cli.fork_url.clone()).chain(cli.job_fork_url.iter().cloned()).take(jobs).collect()
+    } else {
+        vec![cli.fork_url.clone(); jobs]
+    };
+    if free_nodes.len() < jobs {
+        println!("⚠️  --jobs {} needs one Anvil node per job, running {} (add nodes with --job-fork-url)", jobs, free_nodes.len());
+    }
+    println!("⚙️  Fuzzing {} file(s) on {} parallel job(s); their progress lines interleave", files.len(), free_nodes.len().min(files.len()));
+
+    let mut outcomes: Vec<Option<Result<FuzzSummary, anyhow::Error>>> = files.iter().map(|_| None).collect();
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut next = 0;
+    while next < files.len() || !tasks.is_empty() {
+        while next < files.len() {
+            let Some(node) = free_nodes.pop() else {
+                break;
+            };
+            let source = fs::read_to_string(&files[next])?;
+            let filename = files[next].to_string_lossy().into_owned();
+            let mut config = cli.fuzz_config();
+            config.fork_url = node.clone();
+            let mut fuzzer = build_fuzzer_with(cli, config, client).await?;
+            let index = next;
+            tasks.spawn(async move {
+                let outcome = fuzzer.fuzz_contract(&source, &filename).await;
+                (index, node, outcome)
+            });
+            next += 1;
+        }
+        if let Some(joined) = tasks.join_next().await {
+            let (index, node, outcome) = joined?;
+            outcomes[index] = Some(outcome);
+            free_nodes.push(node);
+        }
+    }
+    Ok(outcomes.into_iter().map(|outcome| outcome.expect("every file was fuzzed")).collect())
+}
+
+/// Fuzz every file of a directory in rounds of one `slice` each, so early results cover the whole
+/// directory and a slow file cannot hold up the rest. Each file keeps its own fuzzer across rounds.
+/// With `--time-budget`, stops when the budget is used up; unfinished files keep what they found.
+async fn run_time_slices(
+    cli: &Cli,
+    client: &reqwest::Client,
+    files: &[PathBuf],
+    slice: Duration,
+) -> Result<Vec<Result<FuzzSummary, anyhow::Error>>, Box<dyn std::error::Error>> {
+    let budget = cli.time_budget.map(Duration::from_secs);
+    let start = Instant::now();
+    let budget_used = || budget.is_some_and(|budget| start.elapsed() >= budget);
+
+    let mut campaigns = Vec::new();
+    for file_path in files {
+        campaigns.push((fs::read_to_string(file_path)?, build_fuzzer(cli, client).await?));
+    }
+    let mut outcomes: Vec<Option<Result<FuzzSummary, anyhow::Error>>> = files.iter().map(|_| None).collect();
+    let mut finished = vec![false; files.len()];
+
+    let mut round = 0;
+    while finished.contains(&false) && !budget_used() {
+        round += 1;
+        println!("\n⏱️  Round {}: {}s slices over {} unfinished file(s)", round, slice.as_secs(), finished.iter().filter(|done| !**done).count());
+        for (index, (source, fuzzer)) in campaigns.iter_mut().enumerate() {
+            if finished[index] {
+                continue;
+            }
+            if budget_used() {
+                break;
+            }
+            println!("\n📄 {}", files[index].display());
+            match fuzzer.fuzz_slice(source, &files[index].to_string_lossy(), slice).await {
+                Ok(summary) => {
+                    finished[index] = fuzzer.campaign_complete();
+                    match &mut outcomes[index] {
+                        Some(Ok(merged)) => merged.absorb(summary),
+                        outcome => *outcome = Some(Ok(summary)),
+                    }
+                }
+                Err(e) => {
+                    outcomes[index] = Some(Err(e));
+                    finished[index] = true;
+                }
+            }
+        }
+    }
+
+    let unfinished: Vec<_> = files.iter().zip(&finished).filter(|(_, done)| !**done).map(|(file, _)| file).collect();
+    if !unfinished.is_empty() {
+        println!("\n⏱️  Time budget used up after {} round(s), {} file(s) unfinished (partial results are reported):", round, unfinished.len());
+        for file in unfinished {
+            println!("   - {}", file.display());
+        }
+    }
+    Ok(outcomes.into_iter()
+        .map(|outcome| outcome.unwrap_or_else(|| Err(anyhow::anyhow!("not started within the time budget"))))
+        .collect())
+}
+
+/// Check the environment once, up front, instead of failing midway through the campaign
+async fn run_preflight(cli: &Cli, input_path: Option<&Path>, client: &reqwest::Client) -> Result<(), Box<dyn std::error::Error>> {
+    let files = match input_path {
+        Some(input_path) if input_path.is_dir() => find_solidity_files(input_path)?,
+        Some(input_path) => vec![input_path.to_path_buf()],
+        None => Vec::new(),
+    };
+    let environment = preflight::run(&cli.fuzz_config(), client, &files).await;
+    print!("{}", environment);
+
+    if environment.has_failures() {
+        if cli.dry_run {
+            println!("🧪 Dry run: continuing despite failed checks");
+        } else {
+            return Err("Preflight checks failed (see the environment report above, or pass --skip-preflight)".into());
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// A fuzzer for one file (or replay), attached to the `--trace-all` file when set
+async fn build_fuzzer(cli: &Cli, client: &reqwest::Client) -> Result<SolidityFuzzer, Box<dyn std::error::Error>> {
+    build_fuzzer_with(cli, cli.fuzz_config(), client).await
+}
+
+/// `build_fuzzer` with a config that differs from the command line (the node of a parallel job)
+async fn build_fuzzer_with(cli: &Cli, config: FuzzConfig, client: &reqwest::Client) -> Result<SolidityFuzzer, Box<dyn std::error::Error>> {
+    let mut fuzzer = SolidityFuzzer::with_client(config, client.clone()).await?;
+    if let Some(trace_path) = &cli.trace_all {
+        fuzzer.set_tracer(TransactionTracer::open(trace_path, cli.trace_rate)?);
+    }
+    if let Some(hooks_path) = &cli.hooks {
+        let hooks = Hooks::load(hooks_path)?;
+        let exported = hooks.exported();
+        println!("🪝 Hooks from {}: {}", hooks_path.display(),
+            if exported.is_empty() { "none exported".to_string() } else { exported.join(", ") });
+        fuzzer.set_hooks(hooks);
+    }
+    if let Some(project_root) = &cli.forge_failures {
+        let counterexamples = forge_failures::load(project_root)?;
+        println!("🧩 {} forge counterexample(s) found in {}", counterexamples.len(), project_root.display());
+        fuzzer.set_forge_counterexamples(counterexamples);
+    }
+    if !cli.import_corpus.is_empty() {
+        let mut transactions = Vec::new();
+        for dir in &cli.import_corpus {
+            let loaded = corpus::load(dir)?;
+            println!("📥 {} corpus transaction(s) found in {}", loaded.len(), dir.display());
+            transactions.extend(loaded);
+        }
+        fuzzer.set_imported_corpus(transactions);
+    }
+    if let Some(artifacts) = &cli.project_artifacts {
+        fuzzer.set_project_artifacts(artifacts.clone());
+    }
+    Ok(fuzzer)
+}
+
+/// Where one kind of per-file artifact goes: the explicit directory of its own flag for the whole
+/// file, otherwise one `--out-dir` subdirectory per contract (with only that contract's findings)
+fn artifact_dirs(
+    cli: &Cli,
+    explicit: &Option<PathBuf>,
+    file_path: &Path,
+    summary: &FuzzSummary,
+    subdir: fn(&OutputLayout, &Path, &str) -> PathBuf,
+) -> Vec<(PathBuf, FuzzSummary)> {
+    if let Some(dir) = explicit {
+        return vec![(dir.clone(), summary.clone())];
+    }
+    let Some(layout) = cli.output_layout() else {
+        return Vec::new();
+    };
+    output::split_by_contract(summary)
+        .into_iter()
+        .map(|(contract, contract_summary)| (subdir(&layout, file_path, &contract), contract_summary))
+        .collect()
+}
+
+/// Write the confirmed findings of a file as attack recipes when `--save-attacks` or `--out-dir` is set
+fn save_attack_recipes(cli: &Cli, file_path: &Path, summary: &FuzzSummary) -> Result<(), Box<dyn std::error::Error>> {
+    if cli.dry_run {
+        // Simulated outcomes are not findings and must not become regression scenarios
+        return Ok(());
+    }
+
+    for (dir, summary) in artifact_dirs(cli, &cli.save_attacks, file_path, summary, OutputLayout::attacks_dir) {
+        for recipe in attacks::recipes_from_summary(&file_path.display().to_string(), &summary)? {
+            let path = recipe.save(&dir)?;
+            println!("🗡️  Attack recipe saved: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Write the transactions behind a file's findings when `--dump-txs` or `--out-dir` is set
+fn dump_transactions(cli: &Cli, file_path: &Path, summary: &FuzzSummary) -> Result<(), Box<dyn std::error::Error>> {
+    if cli.dry_run {
+        // Mock addresses and senders do not exist on any chain
+        return Ok(());
+    }
+
+    for (dir, summary) in artifact_dirs(cli, &cli.dump_txs, file_path, summary, OutputLayout::transactions_dir) {
+        for path in tx_dump::write_transactions(&dir, &summary)? {
+            println!("📤 Transaction dump written: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Write Foundry PoC tests for a file's confirmed findings when `--poc-dir` or `--out-dir` is set
+fn write_pocs(cli: &Cli, file_path: &Path, summary: &FuzzSummary) -> Result<(), Box<dyn std::error::Error>> {
+    if cli.dry_run {
+        // Simulated outcomes are not findings
+        return Ok(());
+    }
+
+    for (dir, summary) in artifact_dirs(cli, &cli.poc_dir, file_path, summary, OutputLayout::pocs_dir) {
+        for path in repro::write_foundry_tests(&dir, &file_path.display().to_string(), &summary)? {
+            println!("🧾 PoC test written: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Write a file's corpus and the calls of its findings as Echidna or Medusa seeds when `--export-corpus` is set
+fn export_corpus(cli: &Cli, summary: &FuzzSummary) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(dir) = &cli.export_corpus else {
+        return Ok(());
+    };
+    // Simulated outcomes are not findings
+    let findings = summary.findings.iter().filter(|finding| !cli.dry_run && !finding.is_flaky());
+    let sequences: Vec<CorpusSequence> = summary.corpus.iter().cloned()
+        .chain(findings.filter_map(CorpusSequence::from_finding))
+        .collect();
+    let paths = corpus::export(dir, cli.corpus_format, &sequences)?;
+    if !paths.is_empty() {
+        println!("🌱 {} {} corpus sequence(s) written to {}", paths.len(), cli.corpus_format, dir.display());
+    }
+    Ok(())
+}
+
+/// List the explorer pages of a file's confirmed findings when `--explorer` is set
+fn print_explorer_links(cli: &Cli, summary: &FuzzSummary) {
+    let Some(explorer) = &cli.explorer else {
+        return;
+    };
+    if cli.dry_run {
+        // Mock senders and fork state do not exist on any chain
+        return;
+    }
+
+    for finding in summary.findings.iter().filter(|finding| !finding.is_flaky() && finding.confirmed_on_fork != Some(false)) {
+        let links = explorer.links(finding, summary.fork_block_number);
+        if links.is_empty() {
+            continue;
+        }
+        println!("🌐 {}.{} (iteration {}) on the explorer:", finding.contract, finding.method, finding.iteration);
+        for link in links {
+            println!("     ↳ {}: {}", link.label, link.url);
+        }
+    }
+}
+
+/// `attacks replay`: re-validate recorded scenarios against the current contract versions.
+/// Fails when any scenario still reproduces, so it can gate a fix in CI.
+async fn replay_attacks(cli: &Cli, recipes_path: &Path, source_override: Option<&Path>, client: &reqwest::Client) -> Result<(), Box<dyn std::error::Error>> {
+    let recipes = attacks::load_recipes(recipes_path)?;
+    println!("🗡️  Replaying {} attack recipe(s) on {}", recipes.len(), cli.fork_url);
+
+    let mut fuzzer = build_fuzzer(cli, client).await?;
+    let mut reproduced = 0;
+    let mut fixed = 0;
+    let mut obsolete = 0;
+    let mut errors = 0;
+
+    for (path, recipe) in &recipes {
+        let source_path = source_override.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(&recipe.source_file));
+        println!("\n- {} ({})", recipe.name, path.display());
+
+        let outcome = match fs::read_to_string(&source_path) {
+            Ok(source) => fuzzer.replay_attack(&source, &source_path.display().to_string(), recipe).await
+                .unwrap_or_else(|e| ReplayOutcome::Error(format!("{:#}", e))),
+            Err(e) => ReplayOutcome::Error(format!("cannot read {}: {}", source_path.display(), e)),
+        };
+        match outcome {
+            ReplayOutcome::Reproduced(detail) => {
+                println!("  ❌ STILL REPRODUCES: {}", detail);
+                reproduced += 1;
+            }
+            ReplayOutcome::Fixed(detail) => {
+                println!("  ✅ FIXED: {}", detail);
+                fixed += 1;
+            }
+            ReplayOutcome::Obsolete(detail) => {
+                println!("  ⏭️  OBSOLETE: {}", detail);
+                obsolete += 1;
+            }
+            ReplayOutcome::Error(detail) => {
+                println!("  ⚠️  ERROR (no verdict): {}", detail);
+                errors += 1;
+            }
+        }
+    }
 
+    println!("\n🏁 Attack replay summary:");
+    println!("   ✅ {} fixed", fixed);
+    println!("   ❌ {} still reproduce", reproduced);
+    if obsolete > 0 {
+        println!("   ⏭️  {} obsolete (target changed, scenario no longer applies)", obsolete);
+    }
+    if errors > 0 {
+        println!("   ⚠️  {} errored (RPC/infrastructure)", errors);
+    }
+
+    if reproduced > 0 {
+        return Err(format!("{} attack recipe(s) still reproduce", reproduced).into());
+    }
     Ok(())
 }
 
+/// `replay`: re-execute a single failing case exactly and print its outcome
+async fn replay_case(cli: &Cli, case_path: &Path, finding: Option<usize>, source_override: Option<&Path>, client: &reqwest::Client) -> Result<(), Box<dyn std::error::Error>> {
+    let case = FailingCase::load(case_path, finding)?;
+    let source_path = source_override.map(Path::to_path_buf)
+        .or_else(|| case.source_file.as_ref().map(PathBuf::from))
+        .ok_or("The case names no source file, pass --source")?;
+    let source = fs::read_to_string(&source_path)?;
+
+    let mut fuzzer = build_fuzzer(cli, client).await?;
+    match fuzzer.replay_case(&source, &source_path.display().to_string(), &case).await? {
+        TestResult::Passed => println!("  ✅ PASSED: the case no longer fails"),
+        TestResult::Failed(error) => println!("  ❌ FAILED: {}", error),
+        TestResult::Error(error) => println!("  ⚠️  ERROR (not a contract failure): {}", error),
+    }
+    if cli.dry_run {
+        println!("  🧪 SIMULATED: the outcome comes from the mock executor, nothing ran on a chain");
+    }
+    Ok(())
+}
+
+/// One line of the per-file pragma/compiler compatibility table printed in directory mode
+struct CompatibilityRow {
+    file: String,
+    pragma: String,
+    compiler: String,
+    evm_version: String,
+    status: String,
+}
+
+fn print_compatibility_table(rows: &[CompatibilityRow]) {
+    if rows.is_empty() {
+        return;
+    }
+
+    let headers = ["File", "Pragma", "Compiler", "EVM", "Status"];
+    let columns: Vec<Vec<&str>> = rows.iter()
+        .map(|r| vec![r.file.as_str(), r.pragma.as_str(), r.compiler.as_str(), r.evm_version.as_str(), r.status.as_str()])
+        .collect();
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| columns.iter().map(|c| c[i].chars().count()).max().unwrap_or(0).max(headers[i].len()))
+        .collect();
+
+    let format_row = |cells: &[&str]| {
+        cells.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    println!("\n🧾 Compiler compatibility:");
+    println!("   {}", format_row(&headers));
+    println!("   {}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("-+-"));
+    for cells in &columns {
+        println!("   {}", format_row(cells));
+    }
+}
+
 fn find_solidity_files(dir_path: &Path) -> Result<Vec<std::path::PathBuf>, Box<dyn std::error::Error>> {
     let mut solidity_files = Vec::new();
     
@@ -132,6 +1656,8 @@ fn find_solidity_files(dir_path: &Path) -> Result<Vec<std::path::PathBuf>, Box<d
             solidity_files.extend(sub_files);
         }
     }
-    
+
+    // read_dir order is platform-dependent; a fixed order keeps campaigns reproducible
+    solidity_files.sort();
     Ok(solidity_files)
 }